@@ -0,0 +1,48 @@
+//! Times JavaScript pattern detection on a generated 1MB+ file, with the detector passes on a
+//! single thread and then on rayon's default pool. Run with `cargo bench --bench pattern_detection`,
+//! setting `BENCH_KB` to change the file size
+use std::time::{Duration, Instant};
+
+use unified_test_framework::{JavaScriptAdapter, TestGenerator};
+
+const RUNS: u32 = 3;
+
+/// Declarations, arrow functions, class methods and API calls, repeated past `min_bytes`
+fn javascript_source(min_bytes: usize) -> String {
+    let mut source = String::new();
+    let mut n = 0;
+    while source.len() < min_bytes {
+        source.push_str(&format!(
+            "function add{n}(a, b) {{\n  return a + b;\n}}\n\n\
+             const fetchUser{n} = async (id) => {{\n  const response = await fetch('/api/users/{n}');\n  return response.json();\n}};\n\n\
+             class Cart{n} {{\n  total(items) {{\n    return items.reduce((sum, item) => sum + item.price, 0);\n  }}\n}}\n\n"
+        ));
+        n += 1;
+    }
+    source
+}
+
+fn time(runtime: &tokio::runtime::Runtime, adapter: &JavaScriptAdapter, source: &str) -> (Duration, usize) {
+    let start = Instant::now();
+    let mut found = 0;
+    for _ in 0..RUNS {
+        found = runtime.block_on(adapter.analyze_code(source, "bench.js")).unwrap().len();
+    }
+    (start.elapsed() / RUNS, found)
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let adapter = JavaScriptAdapter::new();
+    let kb = std::env::var("BENCH_KB").map_or(1024, |kb| kb.parse().unwrap());
+    let source = javascript_source(kb * 1024);
+
+    let single = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    let (sequential, sequential_found) = single.install(|| time(&runtime, &adapter, &source));
+    let (parallel, parallel_found) = time(&runtime, &adapter, &source);
+    assert_eq!(sequential_found, parallel_found);
+
+    println!("{} KB of JavaScript, {} patterns", source.len() / 1024, parallel_found);
+    println!("1 thread:   {:?}", sequential);
+    println!("{} threads: {:?} ({:.2}x)", rayon::current_num_threads(), parallel, sequential.as_secs_f64() / parallel.as_secs_f64());
+}
@@ -0,0 +1,32 @@
+//! Times rendering one generated Python test file per source file, with an engine built for
+//! each file and then with the shared one. Run with `cargo bench --bench template_rendering`
+use std::time::{Duration, Instant};
+
+use unified_test_framework::{PythonAdapter, TemplateEngine, TestOrchestrator, TestSuiteContext};
+
+const FILES: u32 = 10_000;
+
+const SOURCE: &str = "def add(a, b):\n    return a + b\n\ndef is_valid_email(email):\n    return '@' in email\n";
+
+fn per_file(render: impl Fn() -> String) -> Duration {
+    let start = Instant::now();
+    for _ in 0..FILES {
+        assert!(!render().is_empty());
+    }
+    start.elapsed() / FILES
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut orchestrator = TestOrchestrator::new();
+    orchestrator.register_adapter("python".to_string(), Box::new(PythonAdapter::new()));
+    let (suite, _) = runtime.block_on(orchestrator.generate_tests_with_patterns("calc.py", SOURCE)).unwrap();
+    let context = TestSuiteContext::from_suite(&suite);
+
+    let built = per_file(|| TemplateEngine::new().unwrap().render_test_suite("python", "pytest", &context).unwrap());
+    let shared = per_file(|| TemplateEngine::shared().render_test_suite("python", "pytest", &context).unwrap());
+
+    println!("{} test files of {} tests", FILES, suite.test_cases.len());
+    println!("engine per file: {:?} per file", built);
+    println!("shared engine:   {:?} per file", shared);
+}
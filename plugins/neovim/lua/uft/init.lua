@@ -0,0 +1,97 @@
+-- Neovim client for `uft server`: untested functions as virtual text, :UftGenerate and
+-- :UftQuickfix. See the `server` section of docs/usage/USAGE.md for the protocol
+local M = {}
+
+M.config = {
+  -- Command starting the language server
+  cmd = { 'uft', 'server' },
+  filetypes = { 'javascript', 'javascriptreact', 'typescript', 'typescriptreact', 'python', 'rust', 'go', 'java' },
+  -- Files whose directory marks the workspace root
+  root_markers = { 'uft.toml', '.uftrc', '.git' },
+  -- Sent as the `unified_testing` settings section
+  settings = {},
+  -- Show the untested-function diagnostics at the end of their line
+  virtual_text = true,
+}
+
+local function client(bufnr)
+  return vim.lsp.get_clients({ bufnr = bufnr or 0, name = 'uft' })[1]
+end
+
+local function execute(command, arguments, on_result)
+  local uft = client()
+  if not uft then
+    vim.notify('uft: the server is not attached to this buffer', vim.log.levels.WARN)
+    return
+  end
+  uft.request('workspace/executeCommand', { command = command, arguments = arguments }, function(err, result)
+    if err then
+      vim.notify('uft: ' .. err.message, vim.log.levels.ERROR)
+    elseif on_result then
+      on_result(result)
+    end
+  end, 0)
+end
+
+--- Write the tests for the current file, creating its test file or merging into it
+function M.generate()
+  execute('uft.generateTests', { vim.uri_from_bufnr(0) })
+end
+
+--- Generate tests for the function or class under the cursor, applying the server's edit
+function M.generate_symbol()
+  vim.lsp.buf.code_action({
+    apply = true,
+    filter = function(action)
+      return action.kind == 'refactor' and vim.startswith(action.title, 'Generate tests for `')
+    end,
+  })
+end
+
+--- Fill the quickfix list with the untested functions under `paths`, the workspace when empty
+function M.quickfix(paths)
+  local uris = vim.tbl_map(function(path)
+    return vim.uri_from_fname(vim.fn.fnamemodify(path, ':p'))
+  end, paths or {})
+  execute('uft.untestedFunctions', uris, function(locations)
+    local items = vim.tbl_map(function(location)
+      return {
+        filename = vim.uri_to_fname(location.uri),
+        lnum = location.range.start.line + 1,
+        col = location.range.start.character + 1,
+        text = string.format('`%s` has no tests', location.name),
+        type = 'I',
+      }
+    end, locations or {})
+    vim.fn.setqflist({}, ' ', { title = 'uft: untested functions', items = items })
+    if #items > 0 then
+      vim.cmd('copen')
+    else
+      vim.notify('uft: every function has tests')
+    end
+  end)
+end
+
+function M.setup(opts)
+  M.config = vim.tbl_deep_extend('force', M.config, opts or {})
+  local group = vim.api.nvim_create_augroup('uft', { clear = true })
+  vim.api.nvim_create_autocmd('FileType', {
+    group = group,
+    pattern = M.config.filetypes,
+    callback = function(args)
+      local marker = vim.fs.find(M.config.root_markers, { upward = true, path = vim.fs.dirname(args.file) })[1]
+      vim.lsp.start({
+        name = 'uft',
+        cmd = M.config.cmd,
+        root_dir = marker and vim.fs.dirname(marker) or vim.fn.getcwd(),
+        init_options = { unified_testing = M.config.settings },
+        settings = { unified_testing = M.config.settings },
+        on_attach = function(uft)
+          vim.diagnostic.config({ virtual_text = M.config.virtual_text }, vim.lsp.diagnostic.get_namespace(uft.id))
+        end,
+      }, { bufnr = args.buf })
+    end,
+  })
+end
+
+return M
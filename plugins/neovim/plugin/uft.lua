@@ -0,0 +1,17 @@
+-- Commands are defined here so they exist before setup(); the server starts from setup()
+if vim.g.loaded_uft then
+  return
+end
+vim.g.loaded_uft = true
+
+vim.api.nvim_create_user_command('UftGenerate', function()
+  require('uft').generate()
+end, { desc = 'Generate tests for the current file' })
+
+vim.api.nvim_create_user_command('UftGenerateSymbol', function()
+  require('uft').generate_symbol()
+end, { desc = 'Generate tests for the function or class under the cursor' })
+
+vim.api.nvim_create_user_command('UftQuickfix', function(args)
+  require('uft').quickfix(args.fargs)
+end, { nargs = '*', complete = 'file', desc = 'List untested functions in the quickfix list' })
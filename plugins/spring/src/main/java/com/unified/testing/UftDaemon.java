@@ -0,0 +1,183 @@
+package com.unified.testing;
+
+import com.fasterxml.jackson.databind.JsonNode;
+import com.fasterxml.jackson.databind.ObjectMapper;
+import com.fasterxml.jackson.databind.node.ObjectNode;
+import com.intellij.openapi.Disposable;
+import com.intellij.openapi.diagnostic.Logger;
+import com.intellij.openapi.progress.ProgressIndicator;
+import com.intellij.openapi.project.Project;
+import com.intellij.openapi.util.Disposer;
+
+import org.jetbrains.annotations.NotNull;
+import org.jetbrains.annotations.Nullable;
+
+import java.io.BufferedReader;
+import java.io.File;
+import java.io.IOException;
+import java.io.InputStreamReader;
+import java.io.OutputStreamWriter;
+import java.io.Writer;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.util.List;
+import java.util.Map;
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.ConcurrentHashMap;
+import java.util.concurrent.ExecutionException;
+import java.util.concurrent.TimeUnit;
+import java.util.concurrent.TimeoutException;
+import java.util.concurrent.atomic.AtomicLong;
+
+/**
+ * Client for `uft daemon --jsonrpc`, one process per project: JSON-RPC 2.0 requests on its
+ * stdin, responses and `$/progress` notifications on its stdout, one message per line
+ */
+public class UftDaemon implements Disposable {
+    private static final Logger LOG = Logger.getInstance(UftDaemon.class);
+    private static final ObjectMapper JSON = new ObjectMapper();
+    private static final Map<Project, UftDaemon> DAEMONS = new ConcurrentHashMap<>();
+
+    private final Process process;
+    private final Writer stdin;
+    private final AtomicLong nextId = new AtomicLong(1);
+    private final Map<Long, CompletableFuture<JsonNode>> pending = new ConcurrentHashMap<>();
+    private final Map<Long, ProgressIndicator> indicators = new ConcurrentHashMap<>();
+
+    private UftDaemon(@NotNull Path root) throws IOException {
+        ProcessBuilder builder = new ProcessBuilder(findBinary(), "daemon", "--jsonrpc");
+        builder.directory(root.toFile());
+        builder.redirectError(ProcessBuilder.Redirect.DISCARD);
+        process = builder.start();
+        stdin = new OutputStreamWriter(process.getOutputStream(), StandardCharsets.UTF_8);
+
+        Thread reader = new Thread(this::readMessages, "uft-daemon-reader");
+        reader.setDaemon(true);
+        reader.start();
+    }
+
+    /** The project's daemon, started in its base directory on first use */
+    public static synchronized UftDaemon forProject(@NotNull Project project) throws IOException {
+        UftDaemon daemon = DAEMONS.get(project);
+        if (daemon != null && daemon.process.isAlive()) {
+            return daemon;
+        }
+        String basePath = project.getBasePath();
+        daemon = new UftDaemon(basePath != null ? Paths.get(basePath) : Paths.get("."));
+        DAEMONS.put(project, daemon);
+        Disposer.register(project, daemon);
+        return daemon;
+    }
+
+    /** The patterns in a file and the functions its test file misses */
+    public JsonNode analyze(@NotNull String path, @Nullable String source, @NotNull ProgressIndicator indicator) throws IOException {
+        ObjectNode params = JSON.createObjectNode().put("path", path);
+        if (source != null) {
+            params.put("source", source);
+        }
+        return request("analyze", params, indicator);
+    }
+
+    /** Generate a file's tests, merging them into its test file; all functions when `symbols` is empty */
+    public JsonNode generate(@NotNull String path, @NotNull List<String> symbols, @NotNull ProgressIndicator indicator) throws IOException {
+        ObjectNode params = JSON.createObjectNode().put("path", path);
+        symbols.forEach(params.putArray("symbols")::add);
+        return request("generate", params, indicator);
+    }
+
+    private JsonNode request(String method, JsonNode params, ProgressIndicator indicator) throws IOException {
+        long id = nextId.getAndIncrement();
+        CompletableFuture<JsonNode> response = new CompletableFuture<>();
+        pending.put(id, response);
+        indicators.put(id, indicator);
+
+        ObjectNode message = JSON.createObjectNode().put("jsonrpc", "2.0").put("id", id).put("method", method);
+        message.set("params", params);
+        try {
+            synchronized (stdin) {
+                stdin.write(JSON.writeValueAsString(message) + "\n");
+                stdin.flush();
+            }
+            while (true) {
+                indicator.checkCanceled();
+                try {
+                    JsonNode reply = response.get(100, TimeUnit.MILLISECONDS);
+                    if (reply.has("error")) {
+                        throw new IOException(reply.get("error").path("message").asText());
+                    }
+                    return reply.get("result");
+                } catch (TimeoutException e) {
+                    // poll again so cancelling the task stops waiting
+                }
+            }
+        } catch (InterruptedException | ExecutionException e) {
+            throw new IOException("uft daemon stopped: " + e.getMessage(), e);
+        } finally {
+            pending.remove(id);
+            indicators.remove(id);
+        }
+    }
+
+    private void readMessages() {
+        try (BufferedReader stdout = new BufferedReader(new InputStreamReader(process.getInputStream(), StandardCharsets.UTF_8))) {
+            String line;
+            while ((line = stdout.readLine()) != null) {
+                JsonNode message = JSON.readTree(line);
+                if ("$/progress".equals(message.path("method").asText())) {
+                    JsonNode params = message.get("params");
+                    ProgressIndicator indicator = indicators.get(params.path("id").asLong());
+                    if (indicator != null) {
+                        indicator.setIndeterminate(false);
+                        indicator.setFraction(params.path("fraction").asDouble());
+                        indicator.setText(params.path("message").asText());
+                    }
+                } else if (message.has("id")) {
+                    CompletableFuture<JsonNode> response = pending.get(message.get("id").asLong());
+                    if (response != null) {
+                        response.complete(message);
+                    }
+                }
+            }
+        } catch (IOException e) {
+            LOG.warn("Reading from the uft daemon failed", e);
+        }
+        pending.values().forEach(response -> response.completeExceptionally(new IOException("uft daemon exited")));
+    }
+
+    @Override
+    public void dispose() {
+        DAEMONS.values().remove(this);
+        try {
+            synchronized (stdin) {
+                stdin.write("{\"jsonrpc\": \"2.0\", \"id\": 0, \"method\": \"shutdown\"}\n");
+                stdin.flush();
+            }
+        } catch (IOException e) {
+            // already gone
+        }
+        process.destroy();
+    }
+
+    /** The uft binary: on the PATH, else where cargo installs it */
+    private static String findBinary() {
+        String[] names = {"uft", "utf"};
+        String path = System.getenv("PATH");
+        for (String name : names) {
+            if (path != null) {
+                for (String directory : path.split(File.pathSeparator)) {
+                    Path candidate = Paths.get(directory, name);
+                    if (Files.isExecutable(candidate)) {
+                        return candidate.toString();
+                    }
+                }
+            }
+            Path cargo = Paths.get(System.getProperty("user.home"), ".cargo", "bin", name);
+            if (Files.isExecutable(cargo)) {
+                return cargo.toString();
+            }
+        }
+        return names[0];
+    }
+}
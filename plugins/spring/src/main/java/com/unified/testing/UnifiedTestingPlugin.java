@@ -1,47 +1,48 @@
 package com.unified.testing;
 
+import com.fasterxml.jackson.databind.JsonNode;
 import com.intellij.openapi.actionSystem.AnAction;
 import com.intellij.openapi.actionSystem.AnActionEvent;
 import com.intellij.openapi.actionSystem.CommonDataKeys;
-import com.intellij.openapi.editor.Editor;
+import com.intellij.openapi.editor.Document;
 import com.intellij.openapi.project.Project;
+import com.intellij.openapi.vfs.LocalFileSystem;
 import com.intellij.openapi.vfs.VirtualFile;
 import com.intellij.openapi.ui.Messages;
+import com.intellij.openapi.progress.ProcessCanceledException;
 import com.intellij.openapi.progress.ProgressIndicator;
 import com.intellij.openapi.progress.ProgressManager;
 import com.intellij.openapi.progress.Task;
 import com.intellij.openapi.application.ApplicationManager;
-import com.intellij.openapi.command.WriteCommandAction;
+import com.intellij.openapi.application.ReadAction;
 import com.intellij.openapi.fileEditor.FileDocumentManager;
+import com.intellij.openapi.fileEditor.OpenFileDescriptor;
 import com.intellij.openapi.diagnostic.Logger;
 
 import org.jetbrains.annotations.NotNull;
 
-import java.io.BufferedReader;
-import java.io.IOException;
-import java.io.InputStreamReader;
-import java.nio.file.Files;
-import java.nio.file.Path;
-import java.nio.file.Paths;
 import java.util.ArrayList;
+import java.util.Collections;
 import java.util.List;
 
 /**
- * Main plugin class for Unified Testing Framework integration with IntelliJ IDEA / Spring Tool Suite
+ * Main plugin class for Unified Testing Framework integration with IntelliJ IDEA / Spring Tool Suite.
+ * The actions ask the project's `uft daemon --jsonrpc` to analyze and generate, and show its
+ * progress notifications in the background task's indicator
  */
 public class UnifiedTestingPlugin {
     private static final Logger LOG = Logger.getInstance(UnifiedTestingPlugin.class);
     private static final String PLUGIN_NAME = "Unified Testing Framework";
-    
+
     /**
      * Action to generate tests for the currently open file
      */
     public static class GenerateTestsAction extends AnAction {
-        
+
         public GenerateTestsAction() {
             super("Generate Tests", "Generate unit tests for the current file", null);
         }
-        
+
         @Override
         public void actionPerformed(@NotNull AnActionEvent e) {
             Project project = e.getProject();
@@ -49,46 +50,48 @@ public class UnifiedTestingPlugin {
                 Messages.showErrorDialog("No project is open", PLUGIN_NAME);
                 return;
             }
-            
+
             VirtualFile file = e.getData(CommonDataKeys.VIRTUAL_FILE);
             if (file == null) {
                 Messages.showErrorDialog("No file is selected", PLUGIN_NAME);
                 return;
             }
-            
+            // The daemon reads the file from disk
+            FileDocumentManager.getInstance().saveAllDocuments();
+
             // Run the test generation in a background task
             ProgressManager.getInstance().run(new Task.Backgroundable(project, "Generating Tests...", true) {
                 @Override
                 public void run(@NotNull ProgressIndicator indicator) {
                     try {
-                        indicator.setIndeterminate(false);
-                        indicator.setFraction(0.0);
-                        indicator.setText("Analyzing code patterns...");
-                        
-                        String filePath = file.getPath();
-                        String result = executeUnifiedTesting("generate", filePath);
-                        
-                        indicator.setFraction(1.0);
-                        indicator.setText("Tests generated successfully");
-                        
+                        JsonNode result = UftDaemon.forProject(project).generate(file.getPath(), Collections.emptyList(), indicator);
+                        String testFile = result.path("test_file").asText();
+                        int line = result.path("new_tests").path(0).path("line").asInt(1);
+
                         ApplicationManager.getApplication().invokeLater(() -> {
-                            Messages.showInfoMessage(project, 
-                                "Tests generated successfully:\n" + result, 
+                            VirtualFile tests = LocalFileSystem.getInstance().refreshAndFindFileByPath(testFile);
+                            if (tests != null) {
+                                new OpenFileDescriptor(project, tests, line - 1, 0).navigate(true);
+                            }
+                            Messages.showInfoMessage(project,
+                                String.format("Added %d tests to %s", result.path("added").asInt(), testFile),
                                 PLUGIN_NAME);
                         });
-                        
+
+                    } catch (ProcessCanceledException ex) {
+                        throw ex;
                     } catch (Exception ex) {
-                        LOG.error("Failed to generate tests", ex);
+                        LOG.warn("Failed to generate tests", ex);
                         ApplicationManager.getApplication().invokeLater(() -> {
-                            Messages.showErrorDialog(project, 
-                                "Failed to generate tests: " + ex.getMessage(), 
+                            Messages.showErrorDialog(project,
+                                "Failed to generate tests: " + ex.getMessage(),
                                 PLUGIN_NAME);
                         });
                     }
                 }
             });
         }
-        
+
         @Override
         public void update(@NotNull AnActionEvent e) {
             VirtualFile file = e.getData(CommonDataKeys.VIRTUAL_FILE);
@@ -96,16 +99,16 @@ public class UnifiedTestingPlugin {
             e.getPresentation().setEnabled(enabled);
         }
     }
-    
+
     /**
      * Action to analyze patterns in the currently open file
      */
     public static class AnalyzeFileAction extends AnAction {
-        
+
         public AnalyzeFileAction() {
             super("Analyze Patterns", "Analyze testable patterns in the current file", null);
         }
-        
+
         @Override
         public void actionPerformed(@NotNull AnActionEvent e) {
             Project project = e.getProject();
@@ -113,46 +116,46 @@ public class UnifiedTestingPlugin {
                 Messages.showErrorDialog("No project is open", PLUGIN_NAME);
                 return;
             }
-            
+
             VirtualFile file = e.getData(CommonDataKeys.VIRTUAL_FILE);
             if (file == null) {
                 Messages.showErrorDialog("No file is selected", PLUGIN_NAME);
                 return;
             }
-            
+
             // Run the analysis in a background task
             ProgressManager.getInstance().run(new Task.Backgroundable(project, "Analyzing File...", true) {
                 @Override
                 public void run(@NotNull ProgressIndicator indicator) {
                     try {
-                        indicator.setIndeterminate(false);
-                        indicator.setFraction(0.0);
-                        indicator.setText("Analyzing code patterns...");
-                        
-                        String filePath = file.getPath();
-                        String result = executeUnifiedTesting("analyze", filePath);
-                        
-                        indicator.setFraction(1.0);
-                        indicator.setText("Analysis completed");
-                        
+                        // Unsaved changes are analyzed as they are in the editor
+                        String source = ReadAction.compute(() -> {
+                            Document document = FileDocumentManager.getInstance().getDocument(file);
+                            return document != null ? document.getText() : null;
+                        });
+                        JsonNode report = UftDaemon.forProject(project).analyze(file.getPath(), source, indicator);
+                        String result = describe(report);
+
                         ApplicationManager.getApplication().invokeLater(() -> {
-                            Messages.showInfoMessage(project, 
-                                "Analysis results:\n" + result, 
+                            Messages.showInfoMessage(project,
+                                "Analysis results:\n" + result,
                                 PLUGIN_NAME);
                         });
-                        
+
+                    } catch (ProcessCanceledException ex) {
+                        throw ex;
                     } catch (Exception ex) {
-                        LOG.error("Failed to analyze file", ex);
+                        LOG.warn("Failed to analyze file", ex);
                         ApplicationManager.getApplication().invokeLater(() -> {
-                            Messages.showErrorDialog(project, 
-                                "Failed to analyze file: " + ex.getMessage(), 
+                            Messages.showErrorDialog(project,
+                                "Failed to analyze file: " + ex.getMessage(),
                                 PLUGIN_NAME);
                         });
                     }
                 }
             });
         }
-        
+
         @Override
         public void update(@NotNull AnActionEvent e) {
             VirtualFile file = e.getData(CommonDataKeys.VIRTUAL_FILE);
@@ -160,74 +163,40 @@ public class UnifiedTestingPlugin {
             e.getPresentation().setEnabled(enabled);
         }
     }
-    
-    /**
-     * Check if the file type is supported by the Unified Testing Framework
-     */
-    private static boolean isSupported(VirtualFile file) {
-        String extension = file.getExtension();
-        return extension != null && (
-            extension.equals("js") || 
-            extension.equals("jsx") || 
-            extension.equals("ts") || 
-            extension.equals("tsx") || 
-            extension.equals("py") || 
-            extension.equals("rs")
-        );
-    }
-    
+
     /**
-     * Execute the unified-testing binary with the given command and file path
+     * Summarize the daemon's `analyze` report: each pattern with its line, then the untested functions
      */
-    private static String executeUnifiedTesting(String command, String filePath) throws IOException, InterruptedException {
-        List<String> commandLine = new ArrayList<>();
-        
-        // Try to find the unified-testing binary
-        String binaryPath = findUnifiedTestingBinary();
-        commandLine.add(binaryPath);
-        commandLine.add(command);
-        commandLine.add(filePath);
-        
-        ProcessBuilder processBuilder = new ProcessBuilder(commandLine);
-        processBuilder.redirectErrorStream(true);
-        
-        Process process = processBuilder.start();
-        
-        StringBuilder output = new StringBuilder();
-        try (BufferedReader reader = new BufferedReader(new InputStreamReader(process.getInputStream()))) {
-            String line;
-            while ((line = reader.readLine()) != null) {
-                output.append(line).append("\n");
-            }
+    private static String describe(JsonNode report) {
+        StringBuilder text = new StringBuilder();
+        text.append(report.path("patterns").size()).append(" patterns in ").append(report.path("language").asText()).append(" code\n");
+        for (JsonNode pattern : report.path("patterns")) {
+            text.append("  line ").append(pattern.path("location").path("line").asInt())
+                .append(": ").append(pattern.path("id").asText()).append("\n");
         }
-        
-        int exitCode = process.waitFor();
-        if (exitCode != 0) {
-            throw new RuntimeException("Command failed with exit code: " + exitCode);
+        List<String> untested = new ArrayList<>();
+        report.path("untested").forEach(name -> untested.add(name.asText()));
+        if (!untested.isEmpty()) {
+            text.append("No tests in ").append(report.path("test_file").asText())
+                .append(" for: ").append(String.join(", ", untested));
         }
-        
-        return output.toString().trim();
+        return text.toString().trim();
     }
-    
+
     /**
-     * Find the unified-testing binary in common locations
+     * Check if the file type is supported by the Unified Testing Framework
      */
-    private static String findUnifiedTestingBinary() {
-        String[] possiblePaths = {
-            "unified-testing",
-            "./target/release/unified-testing",
-            "./target/debug/unified-testing",
-            System.getProperty("user.home") + "/.cargo/bin/unified-testing"
-        };
-        
-        for (String path : possiblePaths) {
-            Path binaryPath = Paths.get(path);
-            if (Files.exists(binaryPath) && Files.isExecutable(binaryPath)) {
-                return path;
-            }
-        }
-        
-        // Default to system PATH
-        return "unified-testing";
+    private static boolean isSupported(VirtualFile file) {
+        String extension = file.getExtension();
+        return extension != null && (
+            extension.equals("js") ||
+            extension.equals("jsx") ||
+            extension.equals("ts") ||
+            extension.equals("tsx") ||
+            extension.equals("py") ||
+            extension.equals("rs") ||
+            extension.equals("go") ||
+            extension.equals("java")
+        );
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,14 @@
 import * as vscode from 'vscode';
 import * as path from 'path';
 import { spawn, ChildProcess } from 'child_process';
+import { GeneratedTestsController } from './testExplorer';
 
 export function activate(context: vscode.ExtensionContext) {
     console.log('Unified Testing Extension is now active!');
 
+    const testController = new GeneratedTestsController(executeUnifiedTesting);
+    testController.refresh();
+
     // Register commands
     const generateTestsCommand = vscode.commands.registerCommand('unified-testing.generateTests', async () => {
         const editor = vscode.window.activeTextEditor;
@@ -20,10 +24,14 @@ export function activate(context: vscode.ExtensionContext) {
             outputChannel.show();
             outputChannel.appendLine(`Generating tests for: ${filePath}`);
             
-            const result = await executeUnifiedTesting(['generate', filePath]);
+            const folder = vscode.workspace.getWorkspaceFolder(editor.document.uri);
+            const settingsArgs = generateSettingsArgs(editor.document.languageId, editor.document.uri);
+            const result = await executeUnifiedTesting(['generate', filePath, ...settingsArgs], folder?.uri.fsPath);
             outputChannel.appendLine('Tests generated successfully:');
             outputChannel.appendLine(result);
             
+            codeLensProvider.refresh();
+            await testController.refresh();
             vscode.window.showInformationMessage('Tests generated successfully!');
         } catch (error) {
             outputChannel.appendLine(`Error: ${error}`);
@@ -58,6 +66,22 @@ export function activate(context: vscode.ExtensionContext) {
 
     // Register code lens provider for test generation
     const codeLensProvider = new UnifiedTestingCodeLensProvider();
+    const generateSymbolTestsCommand = vscode.commands.registerCommand('unified-testing.generateTestsForSymbol', async (uri: vscode.Uri, symbol: string) => {
+        const folder = vscode.workspace.getWorkspaceFolder(uri);
+        try {
+            const document = await vscode.workspace.openTextDocument(uri);
+            const settingsArgs = generateSettingsArgs(document.languageId, uri);
+            const result = JSON.parse(await executeUnifiedTesting(['generate', uri.fsPath, '--symbol', symbol, '--format', 'json', ...settingsArgs], folder?.uri.fsPath));
+            const [firstNewTest] = result.new_tests ?? [];
+            const line = firstNewTest ? firstNewTest.line - 1 : 0;
+            const testDocument = await vscode.workspace.openTextDocument(vscode.Uri.file(result.test_file));
+            await vscode.window.showTextDocument(testDocument, { selection: new vscode.Range(line, 0, line, 0) });
+            codeLensProvider.refresh();
+            await testController.refresh();
+        } catch (error) {
+            vscode.window.showErrorMessage(`Failed to generate tests for ${symbol}: ${error}`);
+        }
+    });
     const codeLensDisposable = vscode.languages.registerCodeLensProvider(
         [
             { language: 'javascript' },
@@ -81,58 +105,60 @@ export function activate(context: vscode.ExtensionContext) {
     );
 
     context.subscriptions.push(
+        testController,
         generateTestsCommand,
+        generateSymbolTestsCommand,
+        vscode.workspace.onDidSaveTextDocument(() => codeLensProvider.refresh()),
         analyzeFileCommand,
         codeLensDisposable,
         hoverDisposable
     );
 }
 
+/** `uft analyze --format json`'s report, as far as the CodeLens needs it */
+interface AnalysisReport {
+    patterns: { pattern_type: { Function?: { name: string } }; location: { line: number } }[];
+    untested: string[];
+}
+
+/** A "Generate tests" lens above each function `uft analyze` finds no tests for */
 class UnifiedTestingCodeLensProvider implements vscode.CodeLensProvider {
+    private readonly changed = new vscode.EventEmitter<void>();
+    readonly onDidChangeCodeLenses = this.changed.event;
+
+    /** Analyze the documents again, e.g. after tests were generated */
+    refresh(): void {
+        this.changed.fire();
+    }
+
     async provideCodeLenses(document: vscode.TextDocument): Promise<vscode.CodeLens[]> {
-        const codeLenses: vscode.CodeLens[] = [];
-        const text = document.getText();
-        
-        // Simple pattern detection for function declarations
-        const functionRegex = /function\s+(\w+)\s*\(([^)]*)\)/g;
-        const pyFunctionRegex = /def\s+(\w+)\s*\(([^)]*)\):/g;
-        const rustFunctionRegex = /(pub\s+)?fn\s+(\w+)\s*\(([^)]*)\)/g;
-        
-        let match;
-        
-        // JavaScript/TypeScript functions
-        while ((match = functionRegex.exec(text)) !== null) {
-            const line = document.positionAt(match.index).line;
-            const range = new vscode.Range(line, 0, line, match[0].length);
-            
-            codeLenses.push(new vscode.CodeLens(range, {
-                title: "🧪 Generate Tests",
-                command: "unified-testing.generateTests"
-            }));
+        if (document.uri.scheme !== 'file') {
+            return [];
         }
-        
-        // Python functions
-        while ((match = pyFunctionRegex.exec(text)) !== null) {
-            const line = document.positionAt(match.index).line;
-            const range = new vscode.Range(line, 0, line, match[0].length);
-            
-            codeLenses.push(new vscode.CodeLens(range, {
-                title: "🧪 Generate Tests",
-                command: "unified-testing.generateTests"
-            }));
+        const folder = vscode.workspace.getWorkspaceFolder(document.uri);
+        let report: AnalysisReport;
+        try {
+            report = JSON.parse(await executeUnifiedTesting(['analyze', document.uri.fsPath, '--format', 'json'], folder?.uri.fsPath));
+        } catch (error) {
+            return [];
         }
-        
-        // Rust functions
-        while ((match = rustFunctionRegex.exec(text)) !== null) {
-            const line = document.positionAt(match.index).line;
-            const range = new vscode.Range(line, 0, line, match[0].length);
-            
-            codeLenses.push(new vscode.CodeLens(range, {
+
+        const untested = new Set(report.untested);
+        const codeLenses: vscode.CodeLens[] = [];
+        for (const pattern of report.patterns) {
+            const name = pattern.pattern_type.Function?.name;
+            if (!name || !untested.has(name)) {
+                continue;
+            }
+            untested.delete(name);
+            const line = Math.min(Math.max(pattern.location.line - 1, 0), document.lineCount - 1);
+            codeLenses.push(new vscode.CodeLens(document.lineAt(line).range, {
                 title: "🧪 Generate Tests",
-                command: "unified-testing.generateTests"
+                tooltip: `Generate tests for ${name}`,
+                command: "unified-testing.generateTestsForSymbol",
+                arguments: [document.uri, name]
             }));
         }
-        
         return codeLenses;
     }
 }
@@ -172,7 +198,29 @@ class UnifiedTestingHoverProvider implements vscode.HoverProvider {
     }
 }
 
-async function executeUnifiedTesting(args: string[]): Promise<string> {
+/**
+ * `generate` flags for the `unifiedTesting.*` settings: the framework set for the file's
+ * language, the confidence threshold, and what to do with a test file that already exists
+ */
+function generateSettingsArgs(languageId: string, scope: vscode.Uri): string[] {
+    const config = vscode.workspace.getConfiguration('unifiedTesting', scope);
+    const args: string[] = [];
+    const framework = config.get<Record<string, string>>('frameworks', {})[languageId];
+    if (framework) {
+        args.push('--framework', framework);
+    }
+    const minConfidence = config.get<number | null>('minConfidence', null);
+    if (typeof minConfidence === 'number') {
+        args.push('--min-confidence', String(minConfidence));
+    }
+    const overwrite = config.get<string>('overwrite', '');
+    if (overwrite) {
+        args.push(`--${overwrite}`);
+    }
+    return args;
+}
+
+async function executeUnifiedTesting(args: string[], cwd?: string): Promise<string> {
     return new Promise((resolve, reject) => {
         // Try to find unified-testing binary in common locations
         const possiblePaths = [
@@ -199,6 +247,7 @@ async function executeUnifiedTesting(args: string[]): Promise<string> {
         }
         
         const process = spawn(binaryPath, args, {
+            cwd,
             stdio: ['pipe', 'pipe', 'pipe']
         });
         
@@ -0,0 +1,172 @@
+import * as vscode from 'vscode';
+import * as path from 'path';
+import { spawn } from 'child_process';
+
+/** A test file as `uft run --list --format json` reports it */
+interface ListedTestFile {
+    file: string;
+    runner: string;
+    command: string[];
+    tests: { name: string; line: number; command: string[] }[];
+}
+
+type RunTests = (args: string[], cwd?: string) => Promise<string>;
+
+/**
+ * Shows the generated tests of each workspace folder in the Test Explorer, with run and debug
+ * buttons running each test with its language's runner.
+ */
+export class GeneratedTestsController implements vscode.Disposable {
+    private readonly controller: vscode.TestController;
+    /** The command running each test item, and the folder it runs in */
+    private readonly commands = new WeakMap<vscode.TestItem, { command: string[]; runner: string; cwd: string }>();
+
+    constructor(private readonly runUft: RunTests) {
+        this.controller = vscode.tests.createTestController('uftGeneratedTests', 'Generated Tests');
+        this.controller.refreshHandler = () => this.refresh();
+        this.controller.createRunProfile('Run', vscode.TestRunProfileKind.Run, (request, token) => this.run(request, token), true);
+        this.controller.createRunProfile('Debug', vscode.TestRunProfileKind.Debug, (request, token) => this.debug(request, token), true);
+    }
+
+    /** List every workspace folder's generated tests again */
+    async refresh(): Promise<void> {
+        const items: vscode.TestItem[] = [];
+        for (const folder of vscode.workspace.workspaceFolders ?? []) {
+            const root = folder.uri.fsPath;
+            let files: ListedTestFile[];
+            try {
+                files = JSON.parse(await this.runUft(['run', root, '--list', '--format', 'json'], root)).files;
+            } catch (error) {
+                console.error(`Listing the generated tests in ${root} failed: ${error}`);
+                continue;
+            }
+            for (const file of files) {
+                const uri = vscode.Uri.file(path.join(root, file.file));
+                const fileItem = this.controller.createTestItem(uri.toString(), file.file, uri);
+                this.commands.set(fileItem, { command: file.command, runner: file.runner, cwd: root });
+                for (const test of file.tests) {
+                    const testItem = this.controller.createTestItem(`${uri.toString()}::${test.name}`, test.name, uri);
+                    testItem.range = new vscode.Range(test.line - 1, 0, test.line - 1, 0);
+                    this.commands.set(testItem, { command: test.command, runner: file.runner, cwd: root });
+                    fileItem.children.add(testItem);
+                }
+                items.push(fileItem);
+            }
+        }
+        this.controller.items.replace(items);
+    }
+
+    /** The tests a request covers: each test of a requested file, skipping excluded ones */
+    private requestedTests(request: vscode.TestRunRequest): vscode.TestItem[] {
+        const excluded = new Set(request.exclude ?? []);
+        const roots: vscode.TestItem[] = [];
+        if (request.include) {
+            roots.push(...request.include);
+        } else {
+            this.controller.items.forEach(item => roots.push(item));
+        }
+        const tests: vscode.TestItem[] = [];
+        for (const item of roots.filter(item => !excluded.has(item))) {
+            if (item.children.size === 0) {
+                tests.push(item);
+            } else {
+                item.children.forEach(child => {
+                    if (!excluded.has(child)) {
+                        tests.push(child);
+                    }
+                });
+            }
+        }
+        return tests;
+    }
+
+    private async run(request: vscode.TestRunRequest, token: vscode.CancellationToken): Promise<void> {
+        const run = this.controller.createTestRun(request);
+        const tests = this.requestedTests(request);
+        tests.forEach(test => run.enqueued(test));
+        for (const test of tests) {
+            const command = this.commands.get(test);
+            if (token.isCancellationRequested || !command) {
+                run.skipped(test);
+                continue;
+            }
+            run.started(test);
+            const started = Date.now();
+            const { success, output } = await runCommand(command.command, command.cwd, token);
+            run.appendOutput(output.replace(/\r?\n/g, '\r\n'), undefined, test);
+            if (success) {
+                run.passed(test, Date.now() - started);
+            } else {
+                const message = new vscode.TestMessage(output.trim() || `${command.command.join(' ')} failed`);
+                if (test.uri && test.range) {
+                    message.location = new vscode.Location(test.uri, test.range);
+                }
+                run.failed(test, message, Date.now() - started);
+            }
+        }
+        run.end();
+    }
+
+    /** Start the first requested test under the debugger its language's extension provides */
+    private async debug(request: vscode.TestRunRequest, token: vscode.CancellationToken): Promise<void> {
+        const [test] = this.requestedTests(request);
+        const command = test && this.commands.get(test);
+        if (!command || token.isCancellationRequested) {
+            return;
+        }
+        const configuration = debugConfiguration(test.label, command.command, command.runner, command.cwd);
+        if (!configuration) {
+            vscode.window.showErrorMessage(`Debugging ${command.runner} tests isn't supported yet; run them instead`);
+            return;
+        }
+        const folder = vscode.workspace.getWorkspaceFolder(vscode.Uri.file(command.cwd));
+        await vscode.debug.startDebugging(folder, configuration);
+    }
+
+    dispose(): void {
+        this.controller.dispose();
+    }
+}
+
+/** A launch configuration for `command`, which runs the test `name` with `runner` */
+function debugConfiguration(name: string, command: string[], runner: string, cwd: string): vscode.DebugConfiguration | undefined {
+    const base = { name: `Debug ${name}`, request: 'launch', cwd };
+    switch (runner) {
+        case 'pytest':
+            // python3 -m pytest -q <file> -k <name>
+            return { ...base, type: 'debugpy', module: 'pytest', args: command.slice(3), justMyCode: false };
+        case 'npm test':
+            return { ...base, type: 'node-terminal', command: command.map(quote).join(' ') };
+        case 'cargo test':
+            // cargo test --test <target> -- <name>
+            return { ...base, type: 'lldb', cargo: { args: ['test', '--no-run', ...command.slice(2, 4)] }, args: command.slice(5) };
+        case 'go test':
+            // go test -v -run ^<name>$ ./<package>
+            return { ...base, type: 'go', mode: 'test', program: path.join(cwd, command[command.length - 1]), args: ['-test.run', command[4]] };
+        default:
+            return undefined;
+    }
+}
+
+function quote(argument: string): string {
+    return /^[\w./:=@^$-]+$/.test(argument) ? argument : `'${argument.replace(/'/g, `'\\''`)}'`;
+}
+
+/** Run `command` in `cwd`, collecting its stdout and stderr */
+function runCommand(command: string[], cwd: string, token: vscode.CancellationToken): Promise<{ success: boolean; output: string }> {
+    return new Promise(resolve => {
+        const child = spawn(command[0], command.slice(1), { cwd, shell: process.platform === 'win32' });
+        let output = '';
+        child.stdout.on('data', data => output += data.toString());
+        child.stderr.on('data', data => output += data.toString());
+        const cancel = token.onCancellationRequested(() => child.kill());
+        child.on('close', code => {
+            cancel.dispose();
+            resolve({ success: code === 0, output });
+        });
+        child.on('error', error => {
+            cancel.dispose();
+            resolve({ success: false, output: `Could not run ${command[0]}: ${error.message}` });
+        });
+    });
+}
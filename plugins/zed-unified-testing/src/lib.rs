@@ -1,6 +1,62 @@
-use zed_extension_api::{self as zed, Result};
+//! Zed extension for uft. Zed starts `uft server` as a language server, which offers
+//! "Generate tests for `symbol`" and "Generate tests for this file" as code actions and reports
+//! untested functions. The `/generate-tests <file>` slash command puts a file's tests in the
+//! assistant, and tasks.json has tasks generating tests for the open file and running them.
+
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::process::Command;
+use zed_extension_api::{
+    self as zed, settings::LspSettings, LanguageServerId, Result, SlashCommand, SlashCommandOutput,
+    SlashCommandOutputSection, Worktree,
+};
+
+/// The language server id in extension.toml
+const LANGUAGE_SERVER_ID: &str = "unified-testing";
+
+/// Names uft is installed under: `uft`, or `utf` as cargo builds it
+const BINARY_NAMES: [&str; 2] = ["uft", "utf"];
+
+/// GitHub repository whose releases the language server is downloaded from
+const RELEASE_REPOSITORY: &str = "samirparhi-dev/unified-test-framework";
+
+/// The release to download, from `lsp.unified-testing.settings.release`: a tag, and the
+/// SHA-256 of its uft binary when it shouldn't come from the release's `.sha256` asset
+#[derive(Debug, Default, PartialEq)]
+struct ReleasePin {
+    version: Option<String>,
+    sha256: Option<String>,
+}
+
+impl ReleasePin {
+    fn from_settings(settings: &zed::serde_json::Value) -> Self {
+        let release = &settings["release"];
+        let field = |name: &str| release[name].as_str().map(str::trim).filter(|value| !value.is_empty()).map(String::from);
+        Self {
+            version: field("version"),
+            sha256: field("sha256"),
+        }
+    }
+}
+
+/// The uft binary set as `lsp.unified-testing.binary.path` in the settings
+fn configured_binary(worktree: &Worktree) -> Option<String> {
+    LspSettings::for_worktree(LANGUAGE_SERVER_ID, worktree).ok()?.binary?.path
+}
+
+/// Directory a release is unpacked to, in the extension's working directory
+fn version_dir(version: &str) -> String {
+    format!("uft-{}", version)
+}
+
+/// The hex digest in a `.sha256` asset, as `sha256sum` writes it: the digest, then the file name
+fn parse_checksum(checksum: &str) -> Option<String> {
+    let digest = checksum.split_whitespace().next()?;
+    (digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())).then(|| digest.to_ascii_lowercase())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
 
 struct UnifiedTestingExtension {
     cached_binary_path: Option<String>,
@@ -15,186 +71,212 @@ impl zed::Extension for UnifiedTestingExtension {
 
     fn language_server_command(
         &mut self,
-        language_server_id: &zed::LanguageServerId,
-        worktree: &zed::Worktree,
+        language_server_id: &LanguageServerId,
+        worktree: &Worktree,
     ) -> Result<zed::Command> {
-        match language_server_id.as_ref() {
-            "unified-testing" => {
-                let binary_path = self.binary_path()?;
-                Ok(zed::Command {
-                    command: binary_path,
-                    args: vec!["server".into()],
-                    env: Default::default(),
-                })
-            }
-            _ => Err("Unknown language server".into()),
+        if language_server_id.as_ref() != LANGUAGE_SERVER_ID {
+            return Err(format!("Unknown language server {}", language_server_id.as_ref()));
         }
+        Ok(zed::Command {
+            command: self.binary_path(worktree, Some(language_server_id))?,
+            args: vec!["server".into()],
+            env: worktree.shell_env(),
+        })
     }
 
+    /// `lsp.unified-testing.settings` from the user's or project's settings, which the server
+    /// reads as its `unified_testing` section; its own defaults apply to what they leave out
     fn language_server_workspace_configuration(
         &mut self,
-        language_server_id: &zed::LanguageServerId,
-        worktree: &zed::Worktree,
+        language_server_id: &LanguageServerId,
+        worktree: &Worktree,
     ) -> Result<Option<zed::serde_json::Value>> {
-        if language_server_id.as_ref() != "unified-testing" {
+        if language_server_id.as_ref() != LANGUAGE_SERVER_ID {
             return Ok(None);
         }
 
-        Ok(Some(zed::serde_json::json!({
-            "unified_testing": {
-                "auto_generate": true,
-                "output_directory": "tests/",
-                "supported_languages": ["javascript", "typescript", "python", "rust"]
+        let settings = LspSettings::for_worktree(LANGUAGE_SERVER_ID, worktree)
+            .ok()
+            .and_then(|settings| settings.settings)
+            .unwrap_or_else(|| zed::serde_json::json!({}));
+        Ok(Some(zed::serde_json::json!({ "unified_testing": settings })))
+    }
+
+    fn run_slash_command(
+        &self,
+        command: SlashCommand,
+        args: Vec<String>,
+        worktree: Option<&Worktree>,
+    ) -> Result<SlashCommandOutput> {
+        match command.name.as_str() {
+            "generate-tests" => {
+                let worktree = worktree.ok_or("/generate-tests needs an open project")?;
+                let file = args.join(" ");
+                if file.trim().is_empty() {
+                    return Err("/generate-tests takes the file to generate tests for".into());
+                }
+                let tests = self.generate_tests(worktree, file.trim())?;
+                let text = format!("Tests for `{}`:\n\n```\n{}\n```\n", file.trim(), tests.trim_end());
+                Ok(SlashCommandOutput {
+                    sections: vec![SlashCommandOutputSection {
+                        range: (0..text.len()).into(),
+                        label: format!("Tests for {}", file.trim()),
+                    }],
+                    text,
+                })
             }
-        })))
+            name => Err(format!("Unknown slash command /{}", name)),
+        }
     }
 }
 
 impl UnifiedTestingExtension {
-    fn binary_path(&mut self) -> Result<String> {
+    /// uft at `lsp.unified-testing.binary.path` in the settings, else on the project's PATH,
+    /// else downloaded from the pinned or latest release and checked against its SHA-256. Only
+    /// the language server reports download progress
+    fn binary_path(&mut self, worktree: &Worktree, language_server_id: Option<&LanguageServerId>) -> Result<String> {
+        if let Some(path) = configured_binary(worktree) {
+            return Ok(path);
+        }
+        if let Some(path) = BINARY_NAMES.iter().find_map(|name| worktree.which(name)) {
+            return Ok(path);
+        }
+        let pin = LspSettings::for_worktree(LANGUAGE_SERVER_ID, worktree)
+            .ok()
+            .and_then(|settings| settings.settings)
+            .map(|settings| ReleasePin::from_settings(&settings))
+            .unwrap_or_default();
         if let Some(path) = &self.cached_binary_path {
-            if fs::metadata(path).map_or(false, |stat| stat.is_file()) {
+            let pinned_elsewhere = pin.version.as_ref().is_some_and(|version| !path.starts_with(&version_dir(version)));
+            if !pinned_elsewhere && fs::metadata(path).is_ok_and(|stat| stat.is_file()) {
                 return Ok(path.clone());
             }
         }
+        let set_status = |status: zed::LanguageServerInstallationStatus| {
+            if let Some(id) = language_server_id {
+                zed::set_language_server_installation_status(id, &status);
+            }
+        };
+
+        set_status(zed::LanguageServerInstallationStatus::CheckingForUpdate);
+        let release = match &pin.version {
+            Some(version) => zed::github_release_by_tag_name(RELEASE_REPOSITORY, version)
+                .map_err(|e| format!("uft release {} not found: {}", version, e))?,
+            None => zed::latest_github_release(
+                RELEASE_REPOSITORY,
+                zed::GithubReleaseOptions {
+                    require_assets: true,
+                    pre_release: false,
+                },
+            )?,
+        };
 
-        zed::set_language_server_installation_status(
-            &zed::LanguageServerId("unified-testing".into()),
-            &zed::LanguageServerInstallationStatus::CheckingForUpdate,
-        );
-
-        let release = zed::latest_github_release(
-            "unified-testing/unified-test-framework",
-            zed::GithubReleaseOptions {
-                require_assets: true,
-                pre_release: false,
-            },
-        )?;
-
-        let (platform, arch) = zed::current_platform();
-        let asset_name = format!(
-            "unified-testing-{}-{}{}",
-            platform,
-            arch,
-            if platform == "windows" { ".exe" } else { "" }
-        );
-
-        let asset = release
-            .assets
-            .iter()
-            .find(|asset| asset.name == asset_name)
-            .ok_or_else(|| format!("no asset found matching {:?}", asset_name))?;
-
-        let version_dir = format!("unified-testing-{}", release.version);
-        let binary_path = format!("{version_dir}/{asset_name}");
-
-        if !fs::metadata(&binary_path).map_or(false, |stat| stat.is_file()) {
-            zed::set_language_server_installation_status(
-                &zed::LanguageServerId("unified-testing".into()),
-                &zed::LanguageServerInstallationStatus::Downloading,
-            );
-
-            zed::download_file(
-                &asset.download_url,
-                &binary_path,
-                zed::DownloadedFileType::Gzip,
-            )
-            .map_err(|e| format!("failed to download file: {e}"))?;
-
-            let entries =
-                fs::read_dir(".").map_err(|e| format!("failed to list working directory {e}"))?;
-            for entry in entries {
-                let entry = entry.map_err(|e| format!("failed to load directory entry {e}"))?;
-                if entry.file_name().to_str() != Some(&version_dir) {
-                    fs::remove_dir_all(&entry.path()).ok();
+        let (os, arch) = zed::current_platform();
+        let platform = match os {
+            zed::Os::Mac => "macos",
+            zed::Os::Linux => "linux",
+            zed::Os::Windows => "windows",
+        };
+        let arch = match arch {
+            zed::Architecture::Aarch64 => "aarch64",
+            zed::Architecture::X86 => "x86",
+            zed::Architecture::X8664 => "x86_64",
+        };
+        let asset_name = format!("uft-{}-{}.tar.gz", platform, arch);
+        let find_asset = |name: &str| {
+            release
+                .assets
+                .iter()
+                .find(|asset| asset.name == name)
+                .ok_or_else(|| format!("no asset found matching {:?}; set lsp.unified-testing.binary.path to a uft binary instead", name))
+        };
+        let asset = find_asset(&asset_name)?;
+
+        let version_dir = version_dir(&release.version);
+        let executable = if os == zed::Os::Windows { ".exe" } else { "" };
+        let installed = || {
+            BINARY_NAMES
+                .iter()
+                .map(|name| format!("{version_dir}/{name}{executable}"))
+                .find(|path| fs::metadata(path).is_ok_and(|stat| stat.is_file()))
+        };
+
+        let binary_path = match installed() {
+            Some(path) => path,
+            None => {
+                set_status(zed::LanguageServerInstallationStatus::Downloading);
+                let expected = match &pin.sha256 {
+                    Some(sha256) => sha256.clone(),
+                    None => {
+                        let checksum_file = format!("{version_dir}.sha256");
+                        zed::download_file(
+                            &find_asset(&format!("{asset_name}.sha256"))?.download_url,
+                            &checksum_file,
+                            zed::DownloadedFileType::Uncompressed,
+                        )
+                        .map_err(|e| format!("failed to download the checksum: {e}"))?;
+                        let checksum = fs::read_to_string(&checksum_file).map_err(|e| format!("failed to read the checksum: {e}"))?;
+                        fs::remove_file(&checksum_file).ok();
+                        parse_checksum(&checksum).ok_or_else(|| format!("{asset_name}.sha256 holds no SHA-256 checksum"))?
+                    }
+                };
+
+                zed::download_file(
+                    &asset.download_url,
+                    &version_dir,
+                    zed::DownloadedFileType::GzipTar,
+                )
+                .map_err(|e| format!("failed to download file: {e}"))?;
+                let path = installed().ok_or_else(|| format!("{asset_name} holds no uft binary"))?;
+                let actual = sha256_hex(&fs::read(&path).map_err(|e| format!("failed to read {path}: {e}"))?);
+                if !actual.eq_ignore_ascii_case(&expected) {
+                    fs::remove_dir_all(&version_dir).ok();
+                    set_status(zed::LanguageServerInstallationStatus::None);
+                    return Err(format!(
+                        "uft {} failed verification: SHA-256 {} instead of {}",
+                        release.version, actual, expected
+                    ));
                 }
+                zed::make_file_executable(&path)?;
+
+                let entries =
+                    fs::read_dir(".").map_err(|e| format!("failed to list working directory {e}"))?;
+                for entry in entries {
+                    let entry = entry.map_err(|e| format!("failed to load directory entry {e}"))?;
+                    if entry.file_name().to_str() != Some(&version_dir) {
+                        fs::remove_dir_all(entry.path()).ok();
+                    }
+                }
+                path
             }
-        }
+        };
 
-        zed::set_language_server_installation_status(
-            &zed::LanguageServerId("unified-testing".into()),
-            &zed::LanguageServerInstallationStatus::None,
-        );
+        set_status(zed::LanguageServerInstallationStatus::None);
         self.cached_binary_path = Some(binary_path.clone());
         Ok(binary_path)
     }
-}
-
-zed::register_extension!(UnifiedTestingExtension);
-
-// Additional helper functions for the extension
-impl UnifiedTestingExtension {
-    fn execute_command(&self, command: &str, args: &[&str], working_dir: Option<&str>) -> Result<String> {
-        let binary_path = self.cached_binary_path.as_ref()
-            .ok_or("Binary path not cached")?;
-        
-        let mut cmd = Command::new(binary_path);
-        cmd.args(args);
-        
-        if let Some(dir) = working_dir {
-            cmd.current_dir(dir);
-        }
-        
-        let output = cmd.output()
-            .map_err(|e| format!("Failed to execute command: {}", e))?;
-        
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
-        } else {
-            Err(format!("Command failed: {}", String::from_utf8_lossy(&output.stderr)))
-        }
-    }
 
-    pub fn generate_tests_for_file(&self, file_path: &str, output_dir: Option<&str>) -> Result<String> {
-        let mut args = vec!["generate", file_path];
-        if let Some(dir) = output_dir {
-            args.extend(&["--output", dir]);
+    /// The test file `uft generate` writes for `file`, relative to the worktree root, without
+    /// writing it
+    fn generate_tests(&self, worktree: &Worktree, file: &str) -> Result<String> {
+        // Slash commands can't cache a download, so this only finds uft on PATH or fetched by
+        // the language server
+        let binary = configured_binary(worktree)
+            .or_else(|| BINARY_NAMES.iter().find_map(|name| worktree.which(name)))
+            .or_else(|| self.cached_binary_path.clone())
+            .ok_or("uft isn't installed: put it on your PATH, set lsp.unified-testing.binary.path, or open a file so the language server downloads it")?;
+        let root = worktree.root_path();
+        let path = if file.starts_with('/') { file.to_string() } else { format!("{}/{}", root, file) };
+        let output = zed::process::Command::new(binary)
+            .args(["generate", path.as_str(), "--stdout", "--config-dir", &format!("{}/language_configs", root)])
+            .envs(worktree.shell_env())
+            .output()?;
+        if output.status != Some(0) {
+            return Err(format!("uft generate failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
         }
-        self.execute_command("generate", &args, None)
-    }
-
-    pub fn analyze_file(&self, file_path: &str) -> Result<String> {
-        self.execute_command("analyze", &["analyze", file_path], None)
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 }
 
-// Language server commands for Zed integration
-pub fn register_commands() {
-    // Generate tests command
-    zed::register_command("unified-testing:generate-tests", |editor: &mut zed::Editor| {
-        if let Some(buffer) = editor.buffer() {
-            if let Some(file_path) = buffer.file_path() {
-                let extension = UnifiedTestingExtension::new();
-                match extension.generate_tests_for_file(&file_path, Some("tests/")) {
-                    Ok(output) => {
-                        zed::show_message(&format!("Tests generated successfully:\n{}", output));
-                    }
-                    Err(e) => {
-                        zed::show_error(&format!("Failed to generate tests: {}", e));
-                    }
-                }
-            } else {
-                zed::show_error("No file is currently open");
-            }
-        }
-    });
-
-    // Analyze file command
-    zed::register_command("unified-testing:analyze-file", |editor: &mut zed::Editor| {
-        if let Some(buffer) = editor.buffer() {
-            if let Some(file_path) = buffer.file_path() {
-                let extension = UnifiedTestingExtension::new();
-                match extension.analyze_file(&file_path) {
-                    Ok(output) => {
-                        zed::show_message(&format!("Analysis results:\n{}", output));
-                    }
-                    Err(e) => {
-                        zed::show_error(&format!("Failed to analyze file: {}", e));
-                    }
-                }
-            } else {
-                zed::show_error("No file is currently open");
-            }
-        }
-    });
-}
\ No newline at end of file
+zed::register_extension!(UnifiedTestingExtension);
+
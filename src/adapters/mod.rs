@@ -8,4 +8,38 @@ pub use javascript::*;
 pub use python::*;
 pub use rust::*;
 pub use go::*;
-pub use java::*;
\ No newline at end of file
+pub use java::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Compile an adapter's detector regexes when the adapter is built, so an invalid pattern fails
+/// at startup instead of partway through a scan
+pub(crate) fn compile_regexes(regexes: &[&Lazy<Regex>]) {
+    for regex in regexes {
+        Lazy::force(regex);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_regexes_forces_each_regex() {
+        static WORD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\w+").unwrap());
+        static DIGIT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+        assert!(Lazy::get(&WORD_REGEX).is_none() && Lazy::get(&DIGIT_REGEX).is_none());
+
+        compile_regexes(&[&WORD_REGEX, &DIGIT_REGEX]);
+        assert!(Lazy::get(&WORD_REGEX).is_some_and(|regex| regex.is_match("add")));
+        assert!(Lazy::get(&DIGIT_REGEX).is_some_and(|regex| regex.is_match("42")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_regex_fails_at_construction() {
+        // Built at runtime, so the pattern isn't rejected before the test can run
+        static UNCLOSED_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(&String::from("(unclosed")).unwrap());
+        compile_regexes(&[&UNCLOSED_REGEX]);
+    }
+}
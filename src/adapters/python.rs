@@ -1,18 +1,52 @@
 use crate::core::*;
-use crate::templates::{TemplateEngine, TestTemplateData, TestPattern};
+use crate::templates::{filters, ApiTestContext, TemplateEngine, TestTemplateData, TestPattern};
 use anyhow::Result;
 use async_trait::async_trait;
+use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
 
-pub struct PythonAdapter;
+static TOP_LEVEL_STATEMENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^\S").unwrap());
+static RAISE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)(?:^[ \t]*if\s+([^\n]+?):\s*\n?[ \t]*)?raise[ \t]+([A-Za-z_][\w.]*)").unwrap());
+static PATCH_DECORATOR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"@patch\("[\w.]*?(\w+)"(?:,[^)]*)?\)"#).unwrap());
+static PRINT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^print\((.*)\)$").unwrap());
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"def\s+(\w+)\s*\(([^)]*)\):").unwrap());
+static HTTP_CALL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\b(?:requests|httpx)\.(get|post|put|delete)\(\s*f?["']([^"']+)["']"#).unwrap());
+static SESSION_CALL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\w*session\.(query|add|merge|delete)\(\s*([A-Z]\w*)").unwrap());
+static DJANGO_QUERY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b([A-Z]\w*)\.objects\.(create|get_or_create|get|filter|exclude|all|update|delete)\(").unwrap());
+static CELERY_TASK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^@(?:\w+\.)?(?:task|shared_task)\b[^\n]*\n(?:@[^\n]*\n)*(?:async\s+)?def\s+(\w+)\s*\(([^)]*)\)").unwrap());
+static GRPC_STUB_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"([\w.]+)\s*=\s*(\w+)_pb2_grpc\.(\w+)Stub\s*\(").unwrap());
+static PATH_PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^}]*\}").unwrap());
+static NON_WORD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\W+").unwrap());
+static DEF_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bdef\s+(\w+)\s*\(").unwrap());
+static IMPORT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^(?:from\s+([\w.]+)\s+import\s+\(?([^)\n]+)|import\s+([^\n]+))").unwrap());
+static CLASS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^class\s+(\w+)[^:\n]*:[^\n]*\n").unwrap());
+static INIT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"def\s+__init__\s*\(([^)]*)\)").unwrap());
+static INDENTED_CLASS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[ \t]*class\s+(\w+)").unwrap());
+static PB2_MODULE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\w+)_pb2\.").unwrap());
+static TOP_LEVEL_DEFINITION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^(?:(?:async\s+)?def|class)\s+(\w+)").unwrap());
+
+pub struct PythonAdapter {
+    config: AdapterConfig,
+}
 
 impl PythonAdapter {
     pub fn new() -> Self {
-        Self
+        Self::with_config(AdapterConfig::for_language("python"))
+    }
+
+    pub fn with_config(config: AdapterConfig) -> Self {
+        super::compile_regexes(&[&TOP_LEVEL_STATEMENT_REGEX, &RAISE_REGEX, &PATCH_DECORATOR_REGEX, &PRINT_REGEX, &FUNCTION_REGEX, &HTTP_CALL_REGEX, &SESSION_CALL_REGEX, &DJANGO_QUERY_REGEX, &CELERY_TASK_REGEX, &GRPC_STUB_REGEX, &PATH_PLACEHOLDER_REGEX, &NON_WORD_REGEX, &DEF_REGEX, &IMPORT_REGEX, &CLASS_REGEX, &INIT_REGEX, &INDENTED_CLASS_REGEX, &PB2_MODULE_REGEX]);
+        Self { config }
     }
     
     pub fn generate_test_with_template(&self, pattern: &TestPattern, template_engine: &TemplateEngine) -> Result<String> {
-        let template_data = match pattern {
+        self.generate_test_with_framework(pattern, "pytest", template_engine)
+    }
+
+    /// Render a pattern's tests from the `pytest/*` or `unittest/*` templates
+    pub fn generate_test_with_framework(&self, pattern: &TestPattern, framework: &str, template_engine: &TemplateEngine) -> Result<String> {
+        let mut template_data = match pattern {
             TestPattern::Function { name, params, return_type } => {
                 TestTemplateData {
                     function_name: name.clone(),
@@ -83,14 +117,91 @@ impl PythonAdapter {
             },
         };
         
+        if framework == "unittest" {
+            template_data.imports.retain(|import| import != "import pytest");
+            template_data.imports.insert(0, "import unittest".to_string());
+        }
+
         let template_name = match pattern {
-            TestPattern::Function { .. } => "pytest/function_test",
-            TestPattern::AsyncFunction { .. } => "pytest/async_test",
-            TestPattern::Class { .. } => "pytest/class_test",
-            TestPattern::ApiEndpoint { .. } => "pytest/api_test",
+            TestPattern::Function { .. } => format!("{}/function_test", framework),
+            TestPattern::AsyncFunction { .. } => format!("{}/async_test", framework),
+            TestPattern::Class { .. } => format!("{}/class_test", framework),
+            TestPattern::ApiEndpoint { .. } => format!("{}/api_test", framework),
         };
         
-        template_engine.render_test(template_name, &template_data)
+        match pattern {
+            TestPattern::Class { methods, .. } => template_engine.render_class_test(&template_name, &template_data, methods.clone()),
+            TestPattern::ApiEndpoint { path, method, params } => {
+                let endpoint = ApiTestContext { method: method.clone(), path: path.clone(), params: params.clone() };
+                template_engine.render_api_test(&template_name, &template_data, &endpoint)
+            }
+            _ => template_engine.render_test(&template_name, &template_data),
+        }
+    }
+
+    /// A unittest module for the functions in `source`: a `TestCase` for each function and one
+    /// for each class covering its public methods, with the imports hoisted to the top. Empty
+    /// when there are no functions
+    pub fn generate_unittest_module(&self, patterns: &[TestablePattern], source: &str, template_engine: &TemplateEngine) -> Result<String> {
+        let mut subjects: Vec<TestPattern> = Vec::new();
+        for pattern in patterns {
+            let PatternType::Function(func) = &pattern.pattern_type else { continue };
+            match enclosing_python_class(&func.name, source) {
+                Some(class) => {
+                    let position = subjects.iter().position(|subject| matches!(subject, TestPattern::Class { name, .. } if *name == class));
+                    let index = position.unwrap_or_else(|| {
+                        subjects.push(TestPattern::Class { name: class, methods: Vec::new() });
+                        subjects.len() - 1
+                    });
+                    if let TestPattern::Class { methods, .. } = &mut subjects[index] {
+                        if !func.name.starts_with('_') && !methods.contains(&func.name) {
+                            methods.push(func.name.clone());
+                        }
+                    }
+                }
+                None => {
+                    let (name, params, return_type) = (func.name.clone(), func.parameters.clone(), func.return_type.clone());
+                    subjects.push(if is_async_python(&func.name, source) {
+                        TestPattern::AsyncFunction { name, params, return_type }
+                    } else {
+                        TestPattern::Function { name, params, return_type }
+                    });
+                }
+            }
+        }
+        if subjects.is_empty() {
+            return Ok(String::new());
+        }
+
+        // `import x` modules, and the names imported from each `from x import` module
+        let mut modules: BTreeSet<String> = BTreeSet::new();
+        let mut from_imports: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut bodies = Vec::new();
+        for subject in &subjects {
+            let rendered = self.generate_test_with_framework(subject, "unittest", template_engine)?;
+            let mut lines = rendered.lines().peekable();
+            while let Some(line) = lines.next_if(|line| line.is_empty() || line.starts_with("import ") || line.starts_with("from ")) {
+                if let Some(module) = line.strip_prefix("import ") {
+                    modules.insert(module.trim().to_string());
+                } else if let Some((module, names)) = line.strip_prefix("from ").and_then(|rest| rest.split_once(" import ")) {
+                    from_imports.entry(module.trim().to_string()).or_default()
+                        .extend(names.split(',').map(|name| name.trim().to_string()));
+                }
+            }
+            bodies.push(lines.collect::<Vec<_>>().join("\n"));
+        }
+        let code = bodies.join("\n");
+        if let Some((module, names)) = patterns.first().and_then(|pattern| module_imports(&pattern.location.file, source, &code)) {
+            from_imports.entry(module).or_default().extend(names);
+        }
+        let imports: Vec<String> = modules.iter().map(|module| format!("import {}", module))
+            .chain(from_imports.iter().map(|(module, names)| format!("from {} import {}", module, names.iter().cloned().collect::<Vec<_>>().join(", "))))
+            .collect();
+        Ok(format!(
+            "{}\n\n\n{}\n\n\nif __name__ == \"__main__\":\n    unittest.main()\n",
+            imports.join("\n"),
+            bodies.join("\n\n\n")
+        ))
     }
     
     fn generate_inputs_for_params(&self, params: &[String]) -> Vec<serde_json::Value> {
@@ -187,6 +298,18 @@ impl PythonAdapter {
 
     fn generate_function_tests(&self, func: &FunctionPattern, source: &str) -> Vec<TestCase> {
         let mut tests = Vec::new();
+
+        if self.config.style == TestStyle::Parameterized && func.name != "__init__" {
+            if let Some(table_test) = self.generate_parameterized_test_python(func, source) {
+                tests.push(table_test);
+                if self.config.generate_error_tests {
+                    let error_tests = self.explicit_error_tests(func, source)
+                        .unwrap_or_else(|| vec![self.generic_error_test(func)]);
+                    tests.extend(self.config.limit_edge_cases(error_tests));
+                }
+                return tests;
+            }
+        }
         
         match func.name.as_str() {
             "calculate_area" => {
@@ -343,34 +466,49 @@ impl PythonAdapter {
     fn generate_generic_function_tests(&self, func: &FunctionPattern, source: &str) -> Vec<TestCase> {
         let mut tests = Vec::new();
         
-        // Generate comprehensive functionality test
-        tests.push(TestCase {
-            id: uuid::Uuid::new_v4().to_string(),
-            name: format!("test_{}_functionality", func.name),
-            description: format!("Test {} function with valid inputs", func.name),
-            input: self.generate_sample_inputs_python(func),
-            expected_output: self.generate_expected_output_python(func, source),
-            test_body: self.generate_basic_test_body_python(func),
-            assertions: vec![],
-            test_category: TestCategory::HappyPath,
-        });
+        // Generate comprehensive functionality test, or a snapshot for dict/list results
+        if self.config.style == TestStyle::Snapshot && self.returns_structured_value_python(func, source) {
+            tests.push(TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}_snapshot", func.name)),
+                description: format!("Snapshot of {} output", func.name),
+                input: self.generate_sample_inputs_python(func),
+                expected_output: serde_json::json!(null),
+                test_body: format!(
+                    "        result = {}({})\n        assert result == snapshot\n",
+                    func.name,
+                    self.generate_sample_parameters_python(func)
+                ),
+                assertions: vec![],
+                test_category: TestCategory::HappyPath,
+            });
+        } else {
+            tests.push(TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}_functionality", func.name)),
+                description: format!("Test {} function with valid inputs", func.name),
+                input: self.generate_sample_inputs_python(func),
+                expected_output: self.generate_expected_output_python(func, source),
+                test_body: self.generate_basic_test_body_python(func),
+                assertions: vec![],
+                test_category: TestCategory::HappyPath,
+            });
+        }
 
-        // Generate error handling tests
-        tests.push(TestCase {
-            id: uuid::Uuid::new_v4().to_string(),
-            name: format!("test_{}_error_handling", func.name),
-            description: format!("Test {} function error handling", func.name),
-            input: serde_json::json!({}),
-            expected_output: serde_json::json!(null),
-            test_body: self.generate_error_test_body_python(func),
-            assertions: vec![],
-            test_category: TestCategory::ErrorHandling,
-        });
+        let mut edge_cases = Vec::new();
+
+        // Generate error handling tests: the exceptions the body raises, or a generic guess when it can't be read
+        if self.config.generate_error_tests {
+            match self.explicit_error_tests(func, source) {
+                Some(tests) => edge_cases.extend(tests),
+                None => edge_cases.push(self.generic_error_test(func)),
+            }
+        }
 
         // Generate boundary tests
-        tests.push(TestCase {
+        edge_cases.push(TestCase {
             id: uuid::Uuid::new_v4().to_string(),
-            name: format!("test_{}_boundary_conditions", func.name),
+            name: self.config.test_name("test_", &format!("{}_boundary_conditions", func.name)),
             description: format!("Test {} function boundary conditions", func.name),
             input: serde_json::json!({}),
             expected_output: serde_json::json!(null),
@@ -381,9 +519,9 @@ impl PythonAdapter {
 
         // Generate type validation tests if function has parameters
         if !func.parameters.is_empty() {
-            tests.push(TestCase {
+            edge_cases.push(TestCase {
                 id: uuid::Uuid::new_v4().to_string(),
-                name: format!("test_{}_type_validation", func.name),
+                name: self.config.test_name("test_", &format!("{}_type_validation", func.name)),
                 description: format!("Test {} function type validation", func.name),
                 input: serde_json::json!({}),
                 expected_output: serde_json::json!(null),
@@ -393,9 +531,80 @@ impl PythonAdapter {
             });
         }
 
+        tests.extend(self.config.limit_edge_cases(edge_cases));
         tests
     }
 
+    /// A single `pytest.mark.parametrize` test over typical, zero and negative inputs
+    fn generate_parameterized_test_python(&self, func: &FunctionPattern, source: &str) -> Option<TestCase> {
+        let names: Vec<String> = func.parameters.iter()
+            .map(|p| p.split([':', '=']).next().unwrap_or("").trim().to_string())
+            .filter(|p| !p.is_empty() && p != "self" && p != "cls" && !p.starts_with('*'))
+            .collect();
+        if names.is_empty() {
+            return None;
+        }
+
+        let sample = names.iter().enumerate()
+            .map(|(i, name)| self.get_sample_value_for_python_param(name, i))
+            .collect();
+        let table = CaseTable::from_sample(
+            &func.name,
+            names.clone(),
+            Some("Any".to_string()),
+            sample,
+            self.generate_expected_output_python(func, source),
+        );
+
+        Some(TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("{}_cases", func.name)),
+            description: format!("Test {} across a table of inputs", func.name),
+            input: table.to_value(),
+            expected_output: serde_json::json!(null),
+            test_body: format!(
+                "        result = {}({})\n        if expected is not None:\n            assert result == expected\n",
+                func.name,
+                names.join(", ")
+            ),
+            assertions: vec![],
+            test_category: TestCategory::HappyPath,
+        })
+    }
+
+    /// `@pytest.mark.parametrize` decorator listing a table's rows
+    fn parametrize_decorator(&self, table: &CaseTable) -> String {
+        let mut argnames = table.parameters.clone();
+        argnames.push("expected".to_string());
+
+        let mut decorator = format!("    @pytest.mark.parametrize(\"{}\", [\n", argnames.join(", "));
+        for row in &table.rows {
+            let values: Vec<String> = row.inputs.iter().chain(std::iter::once(&row.expected)).map(python_literal).collect();
+            decorator.push_str(&format!("        pytest.param({}, id=\"{}\"),\n", values.join(", "), row.label));
+        }
+        decorator.push_str("    ])\n");
+        decorator
+    }
+
+    /// Whether the function is annotated or observed to return a dict/list
+    fn returns_structured_value_python(&self, func: &FunctionPattern, source: &str) -> bool {
+        let def_regex = Regex::new(&format!(r"(?m)^[ \t]*(?:async\s+)?def\s+{}\s*\([^)]*\)\s*(?:->\s*([^:]+))?:", regex::escape(&func.name))).unwrap();
+        let Some(captures) = def_regex.captures(source) else {
+            return false;
+        };
+
+        if let Some(annotation) = captures.get(1) {
+            let annotation = annotation.as_str().trim();
+            return ["dict", "list", "Dict", "List", "tuple", "Tuple"].iter().any(|t| annotation.starts_with(t));
+        }
+
+        // Scan the body up to the next top-level statement
+        let body = &source[captures.get(0).unwrap().end()..];
+        let body_end = TOP_LEVEL_STATEMENT_REGEX.find(body).map_or(body.len(), |m| m.start());
+        let body = &body[..body_end];
+        ["return {", "return [", "return dict(", "return list("].iter().any(|r| body.contains(r))
+    }
+
     fn generate_sample_inputs_python(&self, func: &FunctionPattern) -> serde_json::Value {
         let mut inputs = serde_json::Map::new();
         for (i, param) in func.parameters.iter().enumerate() {
@@ -405,7 +614,7 @@ impl PythonAdapter {
         serde_json::Value::Object(inputs)
     }
 
-    fn get_sample_value_for_python_param(&self, param: &str, index: usize) -> serde_json::Value {
+    pub(crate) fn get_sample_value_for_python_param(&self, param: &str, index: usize) -> serde_json::Value {
         let param_lower = param.to_lowercase();
         match param_lower.as_str() {
             p if p.contains("email") || p.contains("mail") => serde_json::json!("test@example.com"),
@@ -502,6 +711,68 @@ impl PythonAdapter {
         test_body
     }
 
+    fn generic_error_test(&self, func: &FunctionPattern) -> TestCase {
+        TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("{}_error_handling", func.name)),
+            description: format!("Test {} function error handling", func.name),
+            input: serde_json::json!({}),
+            expected_output: serde_json::json!(null),
+            test_body: self.generate_error_test_body_python(func),
+            assertions: vec![],
+            test_category: TestCategory::ErrorHandling,
+        }
+    }
+
+    /// One `pytest.raises` test per exception the function raises, calling it with arguments
+    /// that satisfy the guarding condition; `None` when the function body can't be found
+    fn explicit_error_tests(&self, func: &FunctionPattern, source: &str) -> Option<Vec<TestCase>> {
+        let body = function_body_python(&func.name, source)?;
+        let sites = dedup_error_sites(RAISE_REGEX.captures_iter(body)
+            .map(|cap| ErrorSite {
+                error: cap[2].to_string(),
+                guard: cap.get(1).map(|g| g.as_str().trim().to_string()),
+            })
+            .collect());
+
+        let names: Vec<&str> = func.parameters.iter()
+            .filter(|p| !p.is_empty() && !matches!(p.as_str(), "self" | "cls"))
+            .map(|param| param.split([':', '=']).next().unwrap_or(param).trim())
+            .collect();
+
+        Some(sites.iter().filter_map(|site| {
+            let args: Vec<String> = names.iter().zip(site.triggers(&names)?)
+                .enumerate()
+                .map(|(i, (name, trigger))| {
+                    match trigger {
+                        Some(TriggerValue::Missing) => "None".to_string(),
+                        Some(TriggerValue::Empty) => "\"\"".to_string(),
+                        Some(TriggerValue::Negative) => "-1".to_string(),
+                        Some(TriggerValue::Zero) => "0".to_string(),
+                        Some(TriggerValue::WrongType) if site.guard.as_deref().is_some_and(|g| g.contains("str")) => "123".to_string(),
+                        Some(TriggerValue::WrongType) => "\"invalid\"".to_string(),
+                        None => python_literal(&self.get_sample_value_for_python_param(name, i)),
+                    }
+                })
+                .collect();
+            let exception = site.error.rsplit('.').next().unwrap_or(&site.error);
+
+            Some(TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}_raises_{}", func.name, filters::snake_case(exception).unwrap_or_default())),
+                description: match &site.guard {
+                    Some(guard) => format!("Test {} raises {} when {}", func.name, site.error, guard),
+                    None => format!("Test {} raises {}", func.name, site.error),
+                },
+                input: serde_json::json!({}),
+                expected_output: serde_json::json!({ "raises": site.error }),
+                test_body: format!("        with pytest.raises({}):\n            {}({})\n", site.error, func.name, args.join(", ")),
+                assertions: vec![],
+                test_category: TestCategory::ErrorHandling,
+            })
+        }).collect())
+    }
+
     fn generate_error_test_body_python(&self, func: &FunctionPattern) -> String {
         let func_name = &func.name;
         let mut test_body = String::new();
@@ -653,33 +924,643 @@ impl PythonAdapter {
         params.join(", ")
     }
 
+    /// `@patch` decorator lines for the imported names a function's body uses, targeting
+    /// them where the module under test looks them up
+    fn patch_decorators(&self, func: &FunctionPattern, source: &str, file_path: &str) -> String {
+        let path = std::path::Path::new(file_path);
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            return String::new();
+        };
+        if stem == "unknown" {
+            return String::new();
+        }
+        // Patch the names where the module under test looks them up, by its import path
+        let module = python_module(path).map_or_else(|| stem.to_string(), |module| module.name);
+        let Some(body) = function_body_python(&func.name, source) else {
+            return String::new();
+        };
+
+        imported_names_python(source).iter()
+            .filter(|name| Regex::new(&format!(r"(?:^|[^.\w]){}\b", regex::escape(name))).unwrap().is_match(body))
+            .map(|name| {
+                // Awaited dependencies need a mock whose calls return awaitables
+                let awaited = Regex::new(&format!(r"\bawait\s+{}\b", regex::escape(name))).unwrap().is_match(body);
+                if awaited {
+                    format!("    @patch(\"{}.{}\", new_callable=AsyncMock)\n", module, name)
+                } else {
+                    format!("    @patch(\"{}.{}\")\n", module, name)
+                }
+            })
+            .collect()
+    }
+
+    /// Split a test body into its leading decorator lines and the statements after them
+    fn split_decorators<'a>(&self, test_body: &'a str) -> (&'a str, &'a str) {
+        let end = test_body.split_inclusive('\n')
+            .take_while(|line| line.starts_with("    @"))
+            .map(str::len)
+            .sum();
+        test_body.split_at(end)
+    }
+
+    /// Mock arguments injected by `@patch` decorators; the bottom decorator supplies the first
+    fn patch_params(&self, decorators: &str) -> String {
+        PATCH_DECORATOR_REGEX.captures_iter(decorators)
+            .map(|cap| format!(", mock_{}", cap[1].to_lowercase()))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect()
+    }
+
+    /// Extra test-method parameters for the pytest fixtures a test body uses
+    fn fixture_params(&self, test_body: &str, fixtures: &[Fixture]) -> String {
+        ["snapshot", "monkeypatch"].into_iter()
+            .chain(fixtures.iter().map(|f| f.name.as_str()))
+            .filter(|name| Regex::new(&format!(r"\b{}\b", regex::escape(name))).unwrap().is_match(test_body))
+            .map(|name| format!(", {}", name))
+            .collect()
+    }
+
+    /// A `pytest.fixture` building the class from sample constructor arguments, plus a test using it
+    fn generate_class_fixture(&self, class: &PythonClass) -> (Fixture, TestCase) {
+        let fixture_name = filters::snake_case(&class.name).unwrap_or_default();
+        let args: Vec<String> = class.init_params.iter().enumerate()
+            .map(|(i, param)| format!("{}={}", param, python_literal(&self.get_sample_value_for_python_param(param, i))))
+            .collect();
+
+        let fixture = Fixture {
+            name: fixture_name.clone(),
+            code: format!(
+                "    @pytest.fixture\n    def {}(self):\n        return {}({})\n",
+                fixture_name,
+                class.name,
+                args.join(", ")
+            ),
+        };
+        let test = TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("{}_instance", fixture_name)),
+            description: format!("Test {} can be constructed", class.name),
+            input: serde_json::json!({}),
+            expected_output: serde_json::json!(null),
+            test_body: format!("        assert isinstance({}, {})\n", fixture_name, class.name),
+            assertions: vec![],
+            test_category: TestCategory::HappyPath,
+        };
+        (fixture, test)
+    }
+
+    /// Security tests for the file's authentication code: expired and tampered tokens are
+    /// rejected, issued tokens expire, password hashes are salted and verify, OAuth
+    /// authorization URLs carry `state`, and protected routes refuse anonymous requests
+    fn generate_security_tests(&self, source: &str) -> Vec<TestCase> {
+        let calls = python_auth_calls(source);
+        let mut tests = Vec::new();
+        let security_test = |name: String, description: String, test_body: String| TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &name),
+            description,
+            input: serde_json::json!({}),
+            expected_output: serde_json::json!(null),
+            test_body,
+            assertions: vec![],
+            test_category: TestCategory::Security,
+        };
+        let password = "\"correct horse battery staple\"";
+        let verifier = calls.iter().find(|c| c.flow == AuthFlow::VerifyPassword);
+
+        for call in &calls {
+            let args_with = |index: usize, value: &str| -> String {
+                call.params.iter().enumerate()
+                    .filter(|(_, param)| !param.starts_with('*'))
+                    .map(|(i, param)| match (i == index, call.secret_param()) {
+                        (true, _) => value.to_string(),
+                        (false, Some(secret)) if param_name(param) == secret => "\"test-secret\"".to_string(),
+                        _ => python_literal(&self.get_sample_value_for_python_param(param_name(param), i)),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let f = &call.function;
+            let test = match &call.flow {
+                AuthFlow::VerifyToken { secret, algorithm } if algorithm.starts_with("HS") => {
+                    let Some(index) = call.param_index(&["token", "jwt", "bearer"]) else { continue };
+                    let reject = |token: &str| -> String {
+                        let invocation = format!("{}({})", f, args_with(index, token));
+                        if call.swallows_errors {
+                            format!("        assert not {}\n", invocation)
+                        } else {
+                            format!("        with pytest.raises(Exception):\n            {}\n", invocation)
+                        }
+                    };
+                    let key = python_signing_key(call, secret);
+                    tests.push(security_test(
+                        format!("{}_rejects_expired_token", f),
+                        format!("{} rejects a token that expired an hour ago", f),
+                        with_python_env(secret, &format!(
+                            "        expired = jwt.encode({{\"sub\": \"user-1\", \"exp\": int(time.time()) - 3600}}, {}, algorithm=\"{}\")\n{}",
+                            key, algorithm, reject("expired")
+                        )),
+                    ));
+                    security_test(
+                        format!("{}_rejects_tampered_token", f),
+                        format!("{} rejects a token whose signature was altered", f),
+                        with_python_env(secret, &format!(
+                            "        token = jwt.encode({{\"sub\": \"user-1\"}}, {}, algorithm=\"{}\")\n        header, payload, signature = token.split(\".\")\n        tampered = f\"{{header}}.{{payload}}.{{'B' if signature[0] == 'A' else 'A'}}{{signature[1:]}}\"\n{}",
+                            key, algorithm, reject("tampered")
+                        )),
+                    )
+                }
+                AuthFlow::IssueToken { secret, .. } => security_test(
+                    format!("{}_issues_expiring_token", f),
+                    format!("Tokens issued by {} carry a future expiry", f),
+                    with_python_env(secret, &format!(
+                        "        token = {}({})\n        claims = jwt.decode(token, options={{\"verify_signature\": False}})\n        assert claims[\"exp\"] > time.time()\n",
+                        f, args_with(usize::MAX, "")
+                    )),
+                ),
+                AuthFlow::HashPassword => {
+                    let Some(index) = call.param_index(&["password", "pass", "pwd", "plain"]) else { continue };
+                    let hash_call = format!("{}({})", f, args_with(index, password));
+                    if let Some((verify, (password_index, hash_index))) = verifier.and_then(|v| Some((v, v.verify_param_indexes()?))) {
+                        let verify_call = |candidate: &str| -> String {
+                            let args: Vec<String> = verify.params.iter().enumerate()
+                                .map(|(i, param)| match i {
+                                    i if i == hash_index => "hashed".to_string(),
+                                    i if i == password_index => candidate.to_string(),
+                                    _ => python_literal(&self.get_sample_value_for_python_param(param_name(param), i)),
+                                })
+                                .collect();
+                            format!("{}({})", verify.function, args.join(", "))
+                        };
+                        let mut test = security_test(
+                            format!("{}_verifies_password_hashed_by_{}", verify.function, f),
+                            format!("{} accepts the password hashed by {} and rejects another", verify.function, f),
+                            format!(
+                                "        hashed = {}\n        assert {}\n        assert not {}\n",
+                                hash_call, verify_call(password), verify_call("\"wrong password\"")
+                            ),
+                        );
+                        for function in [f, &verify.function] {
+                            if is_async_python(function, source) {
+                                test = self.make_async_test(test, function);
+                            }
+                        }
+                        tests.push(test);
+                    }
+                    security_test(
+                        format!("{}_salts_password_hash", f),
+                        format!("{} never returns the plain password and salts every hash", f),
+                        format!("        hashed = {call}\n        assert hashed != {}\n        assert {call} != hashed\n", password, call = hash_call),
+                    )
+                }
+                AuthFlow::OAuthAuthorize => security_test(
+                    format!("{}_includes_state", f),
+                    format!("The authorization URL from {} carries a state parameter against CSRF", f),
+                    format!(
+                        "        url = {}({})\n        url = url[0] if isinstance(url, tuple) else url\n        assert \"state=\" in url\n",
+                        f, args_with(usize::MAX, "")
+                    ),
+                ),
+                _ => continue,
+            };
+            tests.push(if is_async_python(f, source) { self.make_async_test(test, f) } else { test });
+        }
+        let fastapi = source.contains("fastapi");
+        for route in python_protected_routes(source) {
+            let (client, check) = if fastapi {
+                ("TestClient(app)", "assert response.status_code in (401, 403)")
+            } else {
+                ("app.test_client()", "assert response.status_code == 401")
+            };
+            tests.push(security_test(
+                format!("{}_requires_authentication", route.slug()),
+                format!("{} {} refuses requests without credentials", route.method, route.path),
+                format!("        response = {}.{}(\"{}\")\n        {}\n", client, route.method.to_lowercase(), route.path, check),
+            ));
+        }
+        tests
+    }
+
+    /// Tests of functions reading environment variables, run with each variable set and
+    /// deleted through pytest's `monkeypatch`, which restores the environment afterwards
+    fn generate_env_tests(&self, source: &str) -> Vec<TestCase> {
+        let mut tests = Vec::new();
+        for env in python_env_uses(source) {
+            let args: Vec<String> = env.params.iter().enumerate()
+                .filter(|(_, param)| !param.starts_with('*'))
+                .map(|(i, param)| python_literal(&self.get_sample_value_for_python_param(param_name(param), i)))
+                .collect();
+            let call = format!("{}({})", env.function, args.join(", "));
+            let returns = |value: &str| if env.returned { format!("        assert {} == {}\n", call, value) } else { format!("        {}\n", call) };
+            let env_test = |name: String, description: String, test_body: String, test_category: TestCategory| {
+                let test = TestCase {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: self.config.test_name("test_", &name),
+                    description,
+                    input: serde_json::json!({}),
+                    expected_output: serde_json::json!(null),
+                    test_body,
+                    assertions: vec![],
+                    test_category,
+                };
+                if is_async_python(&env.function, source) { self.make_async_test(test, &env.function) } else { test }
+            };
+            let variable = env.variable.to_lowercase();
+            let value = format!("\"{}\"", env.set_value());
+
+            tests.push(env_test(
+                format!("{}_with_{}_set", env.function, variable),
+                format!("Test {} with {} set", env.function, env.variable),
+                format!("        monkeypatch.setenv(\"{}\", {})\n{}", env.variable, value, returns(&value)),
+                TestCategory::HappyPath,
+            ));
+            let unset = format!("        monkeypatch.delenv(\"{}\", raising=False)\n", env.variable);
+            match &env.missing {
+                MissingEnv::Default(default) => tests.push(env_test(
+                    format!("{}_with_{}_unset", env.function, variable),
+                    format!("Test {} falls back to {} without {}", env.function, default, env.variable),
+                    format!("{}{}", unset, returns(default)),
+                    TestCategory::EdgeCase,
+                )),
+                MissingEnv::Error => {
+                    let subscript = Regex::new(&format!(r#"os\.environ\[\s*["']{}["']"#, env.variable)).unwrap().is_match(source);
+                    tests.push(env_test(
+                        format!("{}_with_{}_unset", env.function, variable),
+                        format!("Test {} raises without {}", env.function, env.variable),
+                        format!("{}        with pytest.raises({}):\n            {}\n", unset, if subscript { "KeyError" } else { "Exception" }, call),
+                        TestCategory::ErrorHandling,
+                    ));
+                }
+                _ => {}
+            }
+        }
+        tests
+    }
+
+    /// Edge-case tests of functions applying a regex to their parameter: anchoring, empty and
+    /// non-ASCII inputs for functions returning the match result, with expectations from running
+    /// the pattern, and a time budget on backtracking-prone inputs for every such function
+    fn generate_regex_tests(&self, source: &str) -> Vec<TestCase> {
+        let mut tests = Vec::new();
+        for regex in python_regex_uses(source) {
+            if is_async_python(&regex.function, source) {
+                continue;
+            }
+            let regex_test = |name: &str, description: String, test_body: String, test_category: TestCategory| TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}_regex_{}", regex.function, name)),
+                description,
+                input: serde_json::json!({"pattern": regex.pattern, "flags": regex.flags}),
+                expected_output: serde_json::json!(null),
+                test_body,
+                assertions: vec![],
+                test_category,
+            };
+
+            if regex.predicate {
+                for edge in [RegexEdge::Anchoring, RegexEdge::EmptyString, RegexEdge::UnicodeInput] {
+                    let mut test_body: String = regex.cases(edge).iter()
+                        .filter_map(|case| {
+                            let call = format!("{}({})", regex.function, python_literal(&serde_json::json!(case.input)));
+                            Some(if case.expected? { format!("        assert {}\n", call) } else { format!("        assert not {}\n", call) })
+                        })
+                        .collect();
+                    if test_body.is_empty() {
+                        continue;
+                    }
+                    if edge == RegexEdge::Anchoring && regex.mode != RegexMode::Full && regex.pattern.ends_with('$') && !regex.pattern.ends_with("\\$") {
+                        test_body.insert_str(0, "        # `$` also matches before a trailing newline; anchor with \\Z or use re.fullmatch\n");
+                    }
+                    tests.push(regex_test(
+                        edge.slug(),
+                        format!("Test {} against {:?} with {}", regex.function, regex.pattern, edge.describe()),
+                        test_body,
+                        TestCategory::EdgeCase,
+                    ));
+                }
+            }
+
+            let unit = python_literal(&serde_json::json!(regex.backtracking_unit().to_string()));
+            let inputs: Vec<String> = BACKTRACKING_REPEATS.iter().map(|n| format!("{} * {} + \"!\"", unit, n)).collect();
+            let call = if regex.predicate {
+                format!("            {}(text)\n", regex.function)
+            } else {
+                format!("            with contextlib.suppress(Exception):\n                {}(text)\n", regex.function)
+            };
+            tests.push(regex_test(
+                "backtracking",
+                format!("Test {} finishes {:?} on long non-matching input within {} ms", regex.function, regex.pattern, BACKTRACKING_BUDGET_MS),
+                format!(
+                    "        for text in [{}]:\n            start = time.perf_counter()\n{}            assert time.perf_counter() - start < {}\n",
+                    inputs.join(", "),
+                    call,
+                    BACKTRACKING_BUDGET_MS as f64 / 1000.0
+                ),
+                TestCategory::Performance,
+            ));
+        }
+        tests
+    }
+
+    /// One test per member for functions branching on an `Enum`: members with a `case` or
+    /// comparison of their own are passed in and must not raise, while members left to
+    /// `case _`, `else` or `raise NotImplementedError`, or not handled at all, get a failing
+    /// test saying so
+    fn generate_variant_tests(&self, source: &str) -> Vec<TestCase> {
+        let mut tests = Vec::new();
+        for dispatch in python_variant_dispatches(source) {
+            if is_async_python(&dispatch.function, source) {
+                continue;
+            }
+            let enum_name = &dispatch.enum_type.name;
+            for variant in &dispatch.enum_type.variants {
+                let member = format!("{}.{}", enum_name, variant.name);
+                let call = match dispatch.receiver {
+                    Receiver::Method => format!("        {}.{}()\n", member, dispatch.function),
+                    _ => format!("        {}({})\n", dispatch.function, member),
+                };
+                let (test_body, description, test_category) = match dispatch.gap(&variant.name) {
+                    None => (call, format!("Test {} handles {}", dispatch.function, member), TestCategory::HappyPath),
+                    Some(gap) => (
+                        format!("{}        pytest.fail(\"{} is not handled by {}: {}\")\n", call, member, dispatch.function, gap),
+                        format!("Flags that {} doesn't handle {}: {}", dispatch.function, member, gap),
+                        TestCategory::EdgeCase,
+                    ),
+                };
+                tests.push(TestCase {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: self.config.test_name("test_", &format!("{}_handles_{}", dispatch.function, variant.name.to_lowercase())),
+                    description,
+                    input: serde_json::json!({"enum": enum_name, "variant": variant.name}),
+                    expected_output: serde_json::json!(null),
+                    test_body,
+                    assertions: vec![],
+                    test_category,
+                });
+            }
+        }
+        tests
+    }
+
+    /// Tests of builder classes: building with only the required fields set keeps the defaults,
+    /// every value set reaches the built object, and leaving out a required field raises
+    fn generate_builder_tests(&self, source: &str) -> Vec<TestCase> {
+        let mut tests = Vec::new();
+        for builder in python_builders(source) {
+            let values: Vec<String> = builder.setters.iter().enumerate().map(|(i, setter)| {
+                let value = setter.param_type.as_deref().and_then(|ty| pydantic_sample(ty, i))
+                    .unwrap_or_else(|| self.get_sample_value_for_python_param(&setter.param, i));
+                python_literal(&value)
+            }).collect();
+            let chain = |fields: &dyn Fn(&BuilderSetter) -> bool| -> String {
+                let mut chain = builder.create.clone();
+                for (setter, value) in builder.setters.iter().zip(&values) {
+                    if fields(setter) {
+                        chain.push_str(&format!(".{}({})", setter.method, value));
+                    }
+                }
+                chain
+            };
+            let expectation = |field: &str, expected: &str| -> Option<String> {
+                Some(format!("        assert built{} == {}\n", builder.target_field(field)?.accessor, expected))
+            };
+            let built = |chain: String, assertions: String| if assertions.is_empty() {
+                format!("        assert {}.build() is not None\n", chain)
+            } else {
+                format!("        built = {}.build()\n{}", chain, assertions)
+            };
+            let builder_test = |name: String, description: String, test_body: String, test_category: TestCategory| TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}_{}", filters::snake_case(&builder.name).unwrap_or_default(), name)),
+                description,
+                input: serde_json::json!({"builder": builder.name}),
+                expected_output: serde_json::json!(null),
+                test_body,
+                assertions: vec![],
+                test_category,
+            };
+
+            let assertions: String = builder.defaults.iter()
+                .filter(|(field, _)| builder.missing(field).is_none())
+                .filter_map(|(field, value)| expectation(field, value))
+                .collect();
+            tests.push(builder_test(
+                "defaults".to_string(),
+                format!("Test {} builds with only its required fields set, using its defaults", builder.name),
+                built(chain(&|setter| builder.missing(&setter.field).is_some()), assertions),
+                TestCategory::HappyPath,
+            ));
+            let assertions: String = builder.setters.iter().zip(&values)
+                .filter_map(|(setter, value)| expectation(&setter.field, value))
+                .collect();
+            tests.push(builder_test(
+                "full_configuration".to_string(),
+                format!("Test every value set on {} reaches the built object", builder.name),
+                built(chain(&|_| true), assertions),
+                TestCategory::HappyPath,
+            ));
+            for (field, missing) in &builder.required {
+                let MissingField::Error(Some(error)) = missing else { continue };
+                tests.push(builder_test(
+                    format!("requires_{}", field),
+                    format!("Test {} raises {} without {}", builder.name, error, field),
+                    format!("        with pytest.raises({}):\n            {}.build()\n", error, chain(&|setter| setter.field != *field)),
+                    TestCategory::ErrorHandling,
+                ));
+            }
+        }
+        tests
+    }
+
+    /// Tests calling a lock-guarded function or method from 8 threads at once, or 8 tasks for
+    /// `asyncio` locks, 100 times each, checking the attribute it counts with
+    fn generate_concurrency_tests(&self, source: &str) -> Vec<TestCase> {
+        python_concurrent_units(source).into_iter().map(|unit| {
+            let args: Vec<String> = unit.params.iter().enumerate().map(|(i, (name, ty))| {
+                let value = ty.as_deref().and_then(|ty| pydantic_sample(ty, i))
+                    .unwrap_or_else(|| self.get_sample_value_for_python_param(name, i));
+                python_literal(&value)
+            }).collect();
+            let receiver = if unit.owner.is_some() { "shared." } else { "" };
+            let call = format!("{}{}({})", receiver, unit.function, args.join(", "));
+
+            let mut body = String::new();
+            if unit.asynchronous {
+                body.push_str("    @pytest.mark.asyncio\n");
+            }
+            if let Some(create) = &unit.create {
+                body.push_str(&format!("        shared = {}\n\n", create));
+            }
+            if unit.asynchronous {
+                body.push_str(&format!("        async def work():\n            for _ in range(100):\n                await {}\n\n", call));
+                body.push_str("        await asyncio.gather(*(work() for _ in range(8)))\n");
+            } else {
+                body.push_str(&format!("        def work():\n            for _ in range(100):\n                {}\n\n", call));
+                body.push_str("        threads = [threading.Thread(target=work) for _ in range(8)]\n");
+                body.push_str("        for thread in threads:\n            thread.start()\n");
+                body.push_str("        for thread in threads:\n            thread.join()\n");
+            }
+            if let Some(counter) = &unit.counter {
+                body.push_str(&format!("        assert {} == 800\n", counter));
+            }
+            let prefix = unit.owner.as_deref().map(|owner| format!("{}_", filters::snake_case(owner).unwrap_or_default())).unwrap_or_default();
+            TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}{}_concurrently", prefix, unit.function)),
+                description: format!("Test {} from 8 {} at once", unit.function, if unit.asynchronous { "tasks" } else { "threads" }),
+                input: serde_json::json!({"function": unit.function, "owner": unit.owner}),
+                expected_output: serde_json::json!(null),
+                test_body: body,
+                assertions: vec![],
+                test_category: TestCategory::EdgeCase,
+            }
+        }).collect()
+    }
+
+    /// Tests of pydantic models: a model validated from sample fields survives
+    /// `model_dump_json` and `model_validate_json`, and truncated JSON, or JSON missing the
+    /// required fields, raises `ValidationError`
+    fn generate_serialization_tests(&self, source: &str) -> Vec<TestCase> {
+        let mut tests = Vec::new();
+        for model in pydantic_models(source) {
+            let model_test = |name: &str, description: String, test_body: String, test_category: TestCategory| TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}_{}", filters::snake_case(&model.name).unwrap_or_default(), name)),
+                description,
+                input: serde_json::json!({"type": model.name}),
+                expected_output: serde_json::json!(null),
+                test_body,
+                assertions: vec![],
+                test_category,
+            };
+
+            let mut data = Vec::new();
+            let mut complete = true;
+            for (i, field) in model.fields.iter().enumerate() {
+                match pydantic_sample(&field.ty, i) {
+                    Some(value) => data.push(format!("\"{}\": {}", field.key, python_literal(&value))),
+                    None => complete &= field.has_default,
+                }
+            }
+            if complete {
+                let by_alias = if model.fields.iter().any(|f| f.key != f.name) { "by_alias=True" } else { "" };
+                tests.push(model_test(
+                    "pydantic_roundtrip",
+                    format!("Test {} survives serializing to JSON and back", model.name),
+                    format!(
+                        "        value = {name}.model_validate({{{data}}})\n        assert {name}.model_validate_json(value.model_dump_json({by_alias})) == value\n",
+                        name = model.name, data = data.join(", "), by_alias = by_alias
+                    ),
+                    TestCategory::HappyPath,
+                ));
+            }
+            let mut test_body = format!("        with pytest.raises(ValidationError):\n            {}.model_validate_json({:?})\n", model.name, malformed_json(&model));
+            if !model.required_fields().is_empty() {
+                test_body.push_str(&format!("        with pytest.raises(ValidationError):\n            {}.model_validate_json(\"{{}}\")\n", model.name));
+            }
+            tests.push(model_test(
+                "rejects_malformed_json",
+                format!("Test {} rejects truncated JSON and JSON missing its required fields", model.name),
+                test_body,
+                TestCategory::ErrorHandling,
+            ));
+        }
+        tests
+    }
+
+    /// One test per function replaying the `>>>` session in its docstring
+    fn generate_doc_example_tests(&self, source: &str) -> Vec<TestCase> {
+        python_doc_examples(source).into_iter().map(|example| {
+            let mut test_body = String::new();
+            for step in &example.steps {
+                let indent_code = |indent: &str| step.code.lines().map(|line| format!("{}{}\n", indent, line)).collect::<String>();
+                let single_line = !step.code.contains('\n');
+
+                match &step.outcome {
+                    Some(DocOutcome::Value(output)) if single_line => match PRINT_REGEX.captures(&step.code) {
+                        Some(printed) => test_body.push_str(&format!("        assert str({}) == {:?}\n", &printed[1], output)),
+                        None if !output.contains('\n') && !output.starts_with('<') => {
+                            test_body.push_str(&format!("        assert {} == {}\n", step.code, output))
+                        }
+                        None => test_body.push_str(&indent_code("        ")),
+                    },
+                    Some(DocOutcome::Raises(error)) => {
+                        test_body.push_str(&format!("        with pytest.raises({}):\n", error));
+                        test_body.push_str(&indent_code("            "));
+                    }
+                    _ => test_body.push_str(&indent_code("        ")),
+                }
+            }
+
+            TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("{}_doc_examples", example.function)),
+                description: format!("Test {} against its docstring examples", example.function),
+                input: serde_json::json!({}),
+                expected_output: serde_json::json!(null),
+                test_body,
+                assertions: vec![],
+                test_category: TestCategory::HappyPath,
+            }
+        }).collect()
+    }
+
+    /// Tests for a method, calling it on the class fixture instead of as a free function
+    fn generate_method_tests(&self, func: &FunctionPattern, class: &PythonClass, source: &str) -> Vec<TestCase> {
+        let unbound = FunctionPattern {
+            parameters: func.parameters.iter().filter(|p| !matches!(p.as_str(), "self" | "cls")).cloned().collect(),
+            ..func.clone()
+        };
+        let fixture_name = filters::snake_case(&class.name).unwrap_or_default();
+        let call_regex = Regex::new(&format!(r"(^|[^\w.]){}\(", regex::escape(&func.name))).unwrap();
+
+        self.generate_function_tests(&unbound, source).into_iter()
+            .map(|mut test| {
+                test.test_body = call_regex
+                    .replace_all(&test.test_body, format!("${{1}}{}.{}(", fixture_name, func.name).as_str())
+                    .into_owned();
+                test
+            })
+            .collect()
+    }
+
+    /// Mark a test of a coroutine function for pytest-asyncio and await its calls
+    fn make_async_test(&self, mut test: TestCase, func_name: &str) -> TestCase {
+        let call_regex = Regex::new(&format!(r"(^|[^\w.])((?:\w+\.)?{}\()", regex::escape(func_name))).unwrap();
+        test.test_body = call_regex
+            .replace_all(&test.test_body, "${1}await ${2}")
+            .replace("await await ", "await ");
+        test.test_body.insert_str(0, "    @pytest.mark.asyncio\n");
+        test
+    }
+
     fn detect_patterns(&self, source: &str) -> Vec<TestablePattern> {
         let mut patterns = Vec::new();
         
         // Detect function definitions
-        if let Ok(function_regex) = Regex::new(r"def\s+(\w+)\s*\(([^)]*)\):") {
-            for captures in function_regex.captures_iter(source) {
-                if let (Some(name), Some(params)) = (captures.get(1), captures.get(2)) {
-                    patterns.push(TestablePattern {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        pattern_type: PatternType::Function(FunctionPattern {
-                            name: name.as_str().to_string(),
-                            parameters: params.as_str().split(',').map(|s| s.trim().to_string()).collect(),
-                            return_type: None,
-                        }),
-                        location: SourceLocation {
-                            file: "unknown".to_string(),
-                            line: 1,
-                            column: name.start(),
-                        },
-                        context: Context {
-                            function_name: Some(name.as_str().to_string()),
-                            class_name: None,
-                            module_name: None,
-                        },
-                        confidence: 0.9,
-                    });
-                }
+        for captures in FUNCTION_REGEX.captures_iter(source) {
+            if let (Some(name), Some(params)) = (captures.get(1), captures.get(2)) {
+                patterns.push(TestablePattern {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    pattern_type: PatternType::Function(FunctionPattern {
+                        name: name.as_str().to_string(),
+                        parameters: params.as_str().split(',').map(|s| s.trim().to_string()).collect(),
+                        return_type: None,
+                    }),
+                    location: SourceLocation {
+                        file: "unknown".to_string(),
+                        line: 1,
+                        column: name.start(),
+                    },
+                    context: Context {
+                        function_name: Some(name.as_str().to_string()),
+                        class_name: None,
+                        module_name: None,
+                    },
+                    confidence: 0.9,
+                });
             }
         }
 
@@ -708,25 +1589,670 @@ impl PythonAdapter {
 
         patterns
     }
-}
 
-#[async_trait]
-impl TestGenerator for PythonAdapter {
-    async fn analyze_code(&self, source: &str, _file_path: &str) -> Result<Vec<TestablePattern>> {
-        Ok(self.detect_patterns(source))
-    }
+    /// HTTP calls through `requests`/`httpx`, SQLAlchemy and Django ORM operations, and Celery tasks
+    fn detect_integration_patterns(&self, source: &str, file_path: &str) -> Vec<TestablePattern> {
+        let module = std::path::Path::new(file_path).file_stem().and_then(|s| s.to_str()).map(str::to_string);
+        let pattern = |pattern_type: PatternType, column: usize, confidence: f32| TestablePattern {
+            id: uuid::Uuid::new_v4().to_string(),
+            pattern_type,
+            location: SourceLocation {
+                file: file_path.to_string(),
+                line: source[..column].lines().count().max(1),
+                column,
+            },
+            context: Context {
+                function_name: enclosing_function_python(source, column),
+                class_name: None,
+                module_name: module.clone(),
+            },
+            confidence,
+        };
+        if is_proto_file(source) {
+            return proto_services(source, file_path).into_iter()
+                .map(|(offset, rpc)| pattern(PatternType::GrpcCall(rpc), offset, 0.9))
+                .collect();
+        }
+        let has_transaction = source.contains(".commit()") || source.contains("transaction.atomic") || source.contains(".begin(");
+        let mut patterns = Vec::new();
 
-    async fn generate_tests(&self, patterns: Vec<TestablePattern>) -> Result<TestSuite> {
-        self.generate_comprehensive_tests(patterns, "").await
-    }
+        for cap in HTTP_CALL_REGEX.captures_iter(source) {
+            let method = match &cap[1] {
+                "post" => HttpMethod::Post,
+                "put" => HttpMethod::Put,
+                "delete" => HttpMethod::Delete,
+                _ => HttpMethod::Get,
+            };
+            patterns.push(pattern(PatternType::ApiIntegration(ApiIntegrationPattern {
+                endpoint: cap[2].to_string(),
+                method,
+                request_body: None,
+                response_type: None,
+                authentication_required: source.contains("Authorization") || source.contains("Bearer") || source.contains("auth="),
+            }), cap.get(0).unwrap().start(), 0.85));
+        }
 
-    async fn generate_comprehensive_tests(&self, patterns: Vec<TestablePattern>, source: &str) -> Result<TestSuite> {
-        let mut test_cases = Vec::new();
+        for (cap, call, model) in SESSION_CALL_REGEX.captures_iter(source).map(|c| (c.get(0).unwrap(), format!("session.{}", &c[1]), c[2].to_string()))
+            .chain(DJANGO_QUERY_REGEX.captures_iter(source).map(|c| (c.get(0).unwrap(), format!("objects.{}", &c[2]), c[1].to_string())))
+        {
+            let operation_type = match call.rsplit('.').next().unwrap_or_default() {
+                "add" | "merge" | "create" | "get_or_create" => DatabaseOperation::Create,
+                "get" | "all" => DatabaseOperation::Read,
+                "update" => DatabaseOperation::Update,
+                "delete" => DatabaseOperation::Delete,
+                _ => DatabaseOperation::Query,
+            };
+            patterns.push(pattern(PatternType::DatabaseOperation(DatabasePattern {
+                operation_type,
+                table_name: model,
+                method_name: call,
+                has_transaction,
+            }), cap.start(), 0.8));
+        }
+
+        for stub in GRPC_STUB_REGEX.captures_iter(source) {
+            let call_regex = Regex::new(&format!(r"\b{}\.([A-Z]\w*)\(\s*(?:request\s*=\s*)?(?:\w+_pb2\.(\w+)\()?", regex::escape(&stub[1]))).unwrap();
+            for call in call_regex.captures_iter(source) {
+                patterns.push(pattern(PatternType::GrpcCall(GrpcPattern {
+                    package: None,
+                    service: stub[3].to_string(),
+                    method: call[1].to_string(),
+                    request_type: call.get(2).map(|t| t.as_str().to_string()),
+                    response_type: None,
+                    client_streaming: false,
+                    server_streaming: false,
+                    proto_file: Some(format!("{}.proto", &stub[2])),
+                }), call.get(0).unwrap().start(), 0.8));
+            }
+        }
+
+        for (offset, queue) in message_queue_calls(source) {
+            patterns.push(pattern(PatternType::MessageQueue(queue), offset, 0.8));
+        }
+
+        for cap in CELERY_TASK_REGEX.captures_iter(source) {
+            let params = cap[2].split(',')
+                .map(|p| p.split([':', '=']).next().unwrap_or_default().trim().to_string())
+                .filter(|p| !p.is_empty() && p != "self" && !p.starts_with('*'))
+                .collect();
+            patterns.push(pattern(PatternType::ServiceIntegration(ServicePattern {
+                service_name: "celery".to_string(),
+                method_name: cap[1].to_string(),
+                dependencies: params,
+                is_async: true,
+            }), cap.get(1).unwrap().start(), 0.85));
+        }
+
+        if let Some(cli) = argparse_cli(source) {
+            let offset = source.find("ArgumentParser").unwrap_or(0);
+            patterns.push(pattern(PatternType::CliCommand(CliPattern { script: file_path.to_string(), ..cli }), offset, 0.9));
+        }
+
+        patterns
+    }
+
+    /// Contract tests running the script through `subprocess`: `--help` succeeds, a bare run
+    /// reports the missing required arguments, sample values for them parse, and every
+    /// subcommand has help
+    fn generate_cli_tests(&self, cli: &CliPattern) -> Vec<TestCase> {
+        let cli_test = |name: String, description: String, args: &[String], test_body: String, test_category: TestCategory| {
+            let argv: String = args.iter().map(|arg| format!(", {:?}", arg)).collect();
+            TestCase {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: self.config.test_name("test_", &format!("cli_{}_{}", cli.slug(), name)),
+                description,
+                input: serde_json::json!({"args": args}),
+                expected_output: serde_json::json!(null),
+                test_body: format!(
+                    "        result = subprocess.run([sys.executable, {:?}{}], capture_output=True, text=True, timeout=30)\n{}",
+                    cli.script, argv, test_body
+                ),
+                assertions: vec![],
+                test_category,
+            }
+        };
+        let help = "        assert result.returncode == 0\n        assert \"usage\" in result.stdout.lower()\n";
+
+        let mut tests = vec![cli_test(
+            "help".to_string(),
+            "CLI contract test: --help succeeds and prints usage".to_string(),
+            &["--help".to_string()],
+            help.to_string(),
+            TestCategory::HappyPath,
+        )];
+        let required = cli.required_arguments();
+        if !required.is_empty() {
+            let mentions: String = required.iter()
+                .map(|arg| format!("        assert {:?} in result.stderr.lower()\n", arg.mention()))
+                .collect();
+            tests.push(cli_test(
+                "missing_required_arguments".to_string(),
+                "CLI contract test: running without the required arguments is a usage error naming them".to_string(),
+                &[],
+                format!("        assert result.returncode == 2\n{}", mentions),
+                TestCategory::ErrorHandling,
+            ));
+        }
+        if cli.subcommands.is_empty() {
+            tests.push(cli_test(
+                "accepts_valid_arguments".to_string(),
+                "CLI contract test: the required arguments with sample values parse".to_string(),
+                &cli.valid_args(),
+                "        assert result.returncode != 2, result.stderr\n".to_string(),
+                TestCategory::HappyPath,
+            ));
+        }
+        for subcommand in &cli.subcommands {
+            tests.push(cli_test(
+                format!("{}_help", subcommand.replace('-', "_")),
+                format!("CLI contract test: {} --help succeeds and prints usage", subcommand),
+                &[subcommand.clone(), "--help".to_string()],
+                help.to_string(),
+                TestCategory::HappyPath,
+            ));
+        }
+        tests
+    }
+
+    fn generate_api_integration_test(&self, api: &ApiIntegrationPattern, caller: Option<&str>) -> TestCase {
+        let path = PATH_PLACEHOLDER_REGEX.replace_all(&api.endpoint, "1");
+        let url = if path.starts_with("http://") || path.starts_with("https://") {
+            format!("{:?}", path)
+        } else {
+            format!("f\"{{base_url}}{}\"", path)
+        };
+        let method = api.method.to_string().to_lowercase();
+        let payload = if matches!(api.method, HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch) { ", json={}" } else { "" };
+        let slug = NON_WORD_REGEX.replace_all(&path.replace("https://", "").replace("http://", ""), "_").trim_matches('_').to_lowercase();
+
+        TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("api_{}_{}", method, slug)),
+            description: format!(
+                "Integration test for {} {}{}",
+                api.method.to_string(),
+                api.endpoint,
+                caller.map(|c| format!(" called by {}", c)).unwrap_or_default()
+            ),
+            input: serde_json::json!({"endpoint": api.endpoint, "method": api.method, "auth_required": api.authentication_required}),
+            expected_output: serde_json::json!({"status": "< 500"}),
+            test_body: format!(
+                "        response = requests.{}({}{}, timeout=10)\n        assert response.status_code < 500\n",
+                method, url, payload
+            ),
+            assertions: vec![],
+            test_category: TestCategory::Integration,
+        }
+    }
+
+    fn generate_database_integration_test(&self, db: &DatabasePattern, caller: Option<&str>) -> TestCase {
+        let operation = db.operation_type.to_string().to_lowercase();
+        let test_body = if db.method_name.starts_with("objects.") {
+            format!("    @pytest.mark.django_db\n        assert {}.objects.count() >= 0\n", db.table_name)
+        } else {
+            format!("        rows = db_session.query({}).all()\n        assert isinstance(rows, list)\n", db.table_name)
+        };
+
+        TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("database_{}_{}", operation, filters::snake_case(&db.table_name).unwrap_or_default())),
+            description: format!(
+                "Integration test for the {} {} operation{}",
+                db.table_name,
+                operation,
+                caller.map(|c| format!(" in {}", c)).unwrap_or_default()
+            ),
+            input: serde_json::json!({"operation": operation, "table": db.table_name, "has_transaction": db.has_transaction}),
+            expected_output: serde_json::json!({"success": true}),
+            test_body,
+            assertions: vec![],
+            test_category: TestCategory::Integration,
+        }
+    }
+
+    fn generate_task_integration_test(&self, task: &ServicePattern) -> TestCase {
+        let args: Vec<String> = task.dependencies.iter().enumerate()
+            .map(|(i, param)| python_literal(&self.get_sample_value_for_python_param(param, i)))
+            .collect();
+
+        TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("task_{}", task.method_name)),
+            description: format!("Integration test running the {} Celery task eagerly", task.method_name),
+            input: serde_json::json!({"task": task.method_name, "args": task.dependencies}),
+            expected_output: serde_json::json!({"successful": true}),
+            test_body: format!(
+                "        result = {}.apply(args=[{}])\n        assert result.successful(), result.traceback\n",
+                task.method_name,
+                args.join(", ")
+            ),
+            assertions: vec![],
+            test_category: TestCategory::Integration,
+        }
+    }
+
+    /// The recorded request sent with `requests`, its `{{variables}}` read from the `variables` fixture
+    fn generate_recorded_request_test(&self, request: &RecordedRequest) -> TestCase {
+        let mut args = vec![python_template(&request.url)];
+        if !request.headers.is_empty() {
+            let headers: Vec<String> = request.headers.iter()
+                .map(|(name, value)| format!("{:?}: {}", name, python_template(value)))
+                .collect();
+            args.push(format!("headers={{{}}}", headers.join(", ")));
+        }
+        match &request.body {
+            Some(RecordedBody::Json(json)) => args.push(format!("json={}", python_template_value(json))),
+            Some(RecordedBody::Text(text)) => args.push(format!("data={}", python_template(text))),
+            None => {}
+        }
+        args.push("timeout=10".to_string());
+        let mut assertion = match request.expected_status {
+            Some(status) => format!("assert response.status_code == {}", status),
+            None => "assert response.status_code < 400".to_string(),
+        };
+        if let Some(schema) = &request.response_schema {
+            assertion.push_str(&format!("\n        jsonschema.validate(response.json(), {})", python_literal(schema)));
+        }
+
+        TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("replay_{}", request.slug())),
+            description: format!("Replays the recorded request {}: {} {}", request.name, request.method.to_string(), request.url),
+            input: serde_json::json!({"url": request.url, "method": request.method, "headers": request.headers}),
+            expected_output: serde_json::json!({"status": request.expected_status}),
+            test_body: format!(
+                "        response = requests.{}({})\n        {}\n",
+                request.method.to_string().to_lowercase(),
+                args.join(", "),
+                assertion
+            ),
+            assertions: vec![],
+            test_category: TestCategory::Integration,
+        }
+    }
+
+    /// Publish a message to the destination and read it back: SQS runs in-process under moto,
+    /// Kafka and RabbitMQ need a test broker named by an environment variable
+    fn generate_message_queue_integration_test(&self, queue: &MessageQueuePattern, caller: Option<&str>) -> TestCase {
+        let destination = queue.test_destination();
+        let test_body = match queue.broker {
+            MessageBroker::Kafka => format!(
+                "        producer = KafkaProducer(bootstrap_servers=kafka_bootstrap_servers)\n        producer.send(\"{d}\", b\"utf-test-message\").get(timeout=10)\n        producer.close()\n        consumer = KafkaConsumer(\"{d}\", bootstrap_servers=kafka_bootstrap_servers, auto_offset_reset=\"earliest\", consumer_timeout_ms=10000)\n        assert b\"utf-test-message\" in [message.value for message in consumer]\n        consumer.close()\n",
+                d = destination
+            ),
+            MessageBroker::RabbitMq => format!(
+                "        connection = pika.BlockingConnection(pika.URLParameters(rabbitmq_url))\n        channel = connection.channel()\n        channel.queue_declare(queue=\"{d}\", auto_delete=True)\n        channel.basic_publish(exchange=\"\", routing_key=\"{d}\", body=b\"utf-test-message\")\n        _, _, body = channel.basic_get(queue=\"{d}\", auto_ack=True)\n        connection.close()\n        assert body == b\"utf-test-message\"\n",
+                d = destination
+            ),
+            MessageBroker::Sqs => format!(
+                "    @mock_aws\n        sqs = boto3.client(\"sqs\", region_name=\"us-east-1\")\n        queue_url = sqs.create_queue(QueueName=\"{d}\")[\"QueueUrl\"]\n        sqs.send_message(QueueUrl=queue_url, MessageBody=\"utf-test-message\")\n        messages = sqs.receive_message(QueueUrl=queue_url, WaitTimeSeconds=1).get(\"Messages\", [])\n        assert [message[\"Body\"] for message in messages] == [\"utf-test-message\"]\n",
+                d = destination
+            ),
+        };
+
+        TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("{}_{}_roundtrip", queue.broker.name().to_lowercase(), queue.slug())),
+            description: format!(
+                "Integration test sending to and receiving from {} {}{}",
+                queue.broker.name(),
+                destination,
+                caller.map(|c| format!(" as {} does", c)).unwrap_or_default()
+            ),
+            input: serde_json::json!({"broker": queue.broker, "role": queue.role, "destination": queue.destination}),
+            expected_output: serde_json::json!({"received": "utf-test-message"}),
+            test_body,
+            assertions: vec![],
+            test_category: TestCategory::Integration,
+        }
+    }
+
+    /// A call through the stub connected to the service's in-process mock server
+    fn generate_grpc_integration_test(&self, rpc: &GrpcPattern) -> TestCase {
+        let stub = format!("{}_stub", filters::snake_case(&rpc.service).unwrap_or_default());
+        let request = grpc_message_python(rpc, false);
+        let request = if rpc.client_streaming { format!("iter([{}])", request) } else { request };
+        let call = format!("{}.{}({}, timeout=5)", stub, rpc.method, request);
+        let test_body = if rpc.server_streaming {
+            format!("        responses = list({})\n        assert responses == [{}]\n", call, grpc_message_python(rpc, true))
+        } else {
+            format!("        response = {}\n        assert response == {}\n", call, grpc_message_python(rpc, true))
+        };
+
+        TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.config.test_name("test_", &format!("grpc_{}_{}", filters::snake_case(&rpc.service).unwrap_or_default(), filters::snake_case(&rpc.method).unwrap_or_default())),
+            description: format!("Integration test calling {}.{} against a mock server", rpc.service, rpc.method),
+            input: serde_json::json!({"service": rpc.service, "method": rpc.method, "request": rpc.request_type}),
+            expected_output: serde_json::json!({"response": rpc.response_type}),
+            test_body,
+            assertions: vec![],
+            test_category: TestCategory::Integration,
+        }
+    }
+
+    /// A fixture serving a servicer that answers every RPC with an empty message on an
+    /// ephemeral port, yielding a stub connected to it
+    fn generate_grpc_fixture(&self, service: &str, rpcs: &[&GrpcPattern]) -> Fixture {
+        let grpc_module = format!("{}_grpc", pb2_module(rpcs[0]));
+        let methods: Vec<String> = rpcs.iter().map(|rpc| {
+            let response = grpc_message_python(rpc, true);
+            match (rpc.client_streaming, rpc.server_streaming) {
+                (false, false) => format!("            def {}(self, request, context):\n                return {}\n", rpc.method, response),
+                (false, true) => format!("            def {}(self, request, context):\n                return iter([{}])\n", rpc.method, response),
+                (true, false) => format!("            def {}(self, request_iterator, context):\n                for _ in request_iterator:\n                    pass\n                return {}\n", rpc.method, response),
+                (true, true) => format!("            def {}(self, request_iterator, context):\n                for _ in request_iterator:\n                    yield {}\n", rpc.method, response),
+            }
+        }).collect();
+        let name = format!("{}_stub", filters::snake_case(service).unwrap_or_default());
+
+        Fixture {
+            code: format!(
+                "    @pytest.fixture\n    def {name}(self):\n        class Mock{service}Servicer({module}.{service}Servicer):\n{methods}\n        server = grpc.server(futures.ThreadPoolExecutor(max_workers=2))\n        {module}.add_{service}Servicer_to_server(Mock{service}Servicer(), server)\n        port = server.add_insecure_port(\"127.0.0.1:0\")\n        server.start()\n        channel = grpc.insecure_channel(f\"127.0.0.1:{{port}}\")\n        yield {module}.{service}Stub(channel)\n        channel.close()\n        server.stop(None)\n",
+                name = name,
+                service = service,
+                module = grpc_module,
+                methods = methods.join("\n"),
+            ),
+            name,
+        }
+    }
+}
+
+/// `*_pb2` module generated for the RPC's `.proto`
+fn pb2_module(rpc: &GrpcPattern) -> String {
+    let stem = rpc.proto_file.as_deref()
+        .and_then(|file| std::path::Path::new(file).file_stem())
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("service");
+    format!("{}_pb2", stem.replace(['-', '.'], "_"))
+}
+
+/// Empty instance of an RPC's request or response message. Well-known types come from
+/// `google.protobuf`; types not seen in a `.proto` are looked up through the service descriptor.
+fn grpc_message_python(rpc: &GrpcPattern, response: bool) -> String {
+    let message_type = if response { &rpc.response_type } else { &rpc.request_type };
+    match message_type {
+        Some(well_known) if well_known.starts_with("google.protobuf.") => {
+            let name = well_known.rsplit('.').next().unwrap_or_default();
+            format!("{}_pb2.{}()", filters::snake_case(name).unwrap_or_default(), name)
+        }
+        Some(message_type) => format!("{}.{}()", pb2_module(rpc), message_type.rsplit('.').next().unwrap_or_default()),
+        None => format!(
+            "message_factory.GetMessageClass({}.DESCRIPTOR.services_by_name[\"{}\"].methods_by_name[\"{}\"].{})()",
+            pb2_module(rpc),
+            rpc.service,
+            rpc.method,
+            if response { "output_type" } else { "input_type" }
+        ),
+    }
+}
+
+/// Name of the innermost `def` that starts before `offset`
+fn enclosing_function_python(source: &str, offset: usize) -> Option<String> {
+    DEF_REGEX.captures_iter(&source[..offset]).last().map(|cap| cap[1].to_string())
+}
+
+/// Modules whose functions are pure enough that patching them would only get in the way
+const UNPATCHED_MODULES: &[&str] = &[
+    "__future__", "abc", "collections", "dataclasses", "enum", "functools", "itertools", "math", "re", "typing",
+];
+
+/// Names bound by the top-level `import`/`from ... import` statements of a module
+fn imported_names_python(source: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+
+    for cap in IMPORT_REGEX.captures_iter(source) {
+        let (module, items, from_import) = match (cap.get(1), cap.get(2), cap.get(3)) {
+            (Some(module), Some(items), _) => (module.as_str(), items.as_str(), true),
+            (_, _, Some(items)) => (items.as_str(), items.as_str(), false),
+            _ => continue,
+        };
+        if UNPATCHED_MODULES.contains(&module.split('.').next().unwrap_or(module)) {
+            continue;
+        }
+
+        for item in items.split(',').map(str::trim).filter(|item| !item.is_empty() && *item != "*") {
+            let name = match item.split_once(" as ") {
+                Some((_, alias)) => alias.trim(),
+                None if from_import => item,
+                // `import os.path` binds `os`
+                None => item.split('.').next().unwrap_or(item),
+            };
+            if !UNPATCHED_MODULES.contains(&name) && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+
+    names
+}
+
+/// A top-level class with an `__init__`
+struct PythonClass {
+    name: String,
+    /// Constructor parameter names, without `self`, annotations or defaults
+    init_params: Vec<String>,
+    /// Byte range of the class definition in the source
+    span: std::ops::Range<usize>,
+}
+
+fn python_classes(source: &str) -> Vec<PythonClass> {
+    CLASS_REGEX.captures_iter(source)
+        .filter_map(|cap| {
+            let start = cap.get(0).unwrap().start();
+            let body_start = cap.get(0).unwrap().end();
+            let body_len = TOP_LEVEL_STATEMENT_REGEX.find(&source[body_start..]).map_or(source.len() - body_start, |m| m.start());
+            let body = &source[body_start..body_start + body_len];
+
+            let init_params = INIT_REGEX.captures(body)?[1]
+                .split(',')
+                .map(|p| p.split([':', '=']).next().unwrap_or("").trim().to_string())
+                .filter(|p| !p.is_empty() && p != "self" && !p.starts_with('*'))
+                .collect();
+
+            Some(PythonClass {
+                name: cap[1].to_string(),
+                init_params,
+                span: start..body_start + body_len,
+            })
+        })
+        .collect()
+}
+
+/// The module path of `file`, from the package layout around it, and the module-level
+/// functions and classes of `source` that `code` uses, for a `from pkg.module import ...`
+fn module_imports(file: &str, source: &str, code: &str) -> Option<(String, Vec<String>)> {
+    let module = python_module(std::path::Path::new(file))?;
+    let mut names: Vec<String> = Vec::new();
+    for cap in TOP_LEVEL_DEFINITION_REGEX.captures_iter(source) {
+        let name = &cap[1];
+        if !names.iter().any(|n| n == name) && Regex::new(&format!(r"\b{}\b", regex::escape(name))).unwrap().is_match(code) {
+            names.push(name.to_string());
+        }
+    }
+    (!names.is_empty()).then_some((module.name, names))
+}
+
+/// Sample value for a pydantic field annotation: scalars, empty collections, the first
+/// `Literal` choice and the inner type of `Optional[...]` or `... | None`
+fn pydantic_sample(annotation: &str, index: usize) -> Option<serde_json::Value> {
+    let annotation = annotation.trim();
+    let inner = |prefix: &str| annotation.strip_prefix(prefix).and_then(|rest| rest.strip_suffix(']'));
+    if let Some(optional) = inner("Optional[").or_else(|| annotation.strip_suffix("| None")) {
+        return pydantic_sample(optional, index);
+    }
+    if let Some(choices) = inner("Literal[") {
+        let first = choices.split(',').next()?.trim();
+        return Some(match first.trim_matches(['"', '\'']) {
+            unquoted if unquoted.len() < first.len() => serde_json::json!(unquoted),
+            _ => serde_json::from_str(first).ok()?,
+        });
+    }
+    let base = annotation.split('[').next().unwrap_or(annotation);
+    match base {
+        "str" => Some(serde_json::json!(format!("test_string_{}", index))),
+        "int" => Some(serde_json::json!(5)),
+        "float" => Some(serde_json::json!(2.5)),
+        "bool" => Some(serde_json::json!(true)),
+        "EmailStr" => Some(serde_json::json!("test@example.com")),
+        "list" | "List" | "set" | "Set" | "tuple" | "Tuple" => Some(serde_json::json!([])),
+        "dict" | "Dict" => Some(serde_json::json!({})),
+        _ => None,
+    }
+}
+
+/// Source lines of a function's body, excluding the `def` line
+fn function_body_python<'a>(func_name: &str, source: &'a str) -> Option<&'a str> {
+    let def_regex = Regex::new(&format!(r"(?m)^([ \t]*)(?:async\s+)?def\s+{}\s*\(.*\n", regex::escape(func_name))).unwrap();
+    let cap = def_regex.captures(source)?;
+    let indent = cap[1].len();
+    let rest = &source[cap.get(0).unwrap().end()..];
+
+    let mut end = 0;
+    for line in rest.split_inclusive('\n') {
+        if !line.trim().is_empty() && line.len() - line.trim_start().len() <= indent {
+            break;
+        }
+        end += line.len();
+    }
+    Some(&rest[..end])
+}
+
+/// Whether `func_name` is defined with `async def`
+fn is_async_python(func_name: &str, source: &str) -> bool {
+    Regex::new(&format!(r"(?m)^[ \t]*async\s+def\s+{}\s*\(", regex::escape(func_name))).unwrap().is_match(source)
+}
+
+/// The class a function is defined in: the nearest `class` line above its `def` that is
+/// indented less
+fn enclosing_python_class(func_name: &str, source: &str) -> Option<String> {
+    let def_regex = Regex::new(&format!(r"^([ \t]*)(?:async\s+)?def\s+{}\s*\(", regex::escape(func_name))).unwrap();
+    let lines: Vec<&str> = source.lines().collect();
+    let (index, indent) = lines.iter().enumerate().find_map(|(i, line)| def_regex.captures(line).map(|captures| (i, captures[1].len())))?;
+    lines[..index].iter().rev()
+        .find(|line| !line.trim().is_empty() && line.len() - line.trim_start().len() < indent)
+        .and_then(|line| INDENTED_CLASS_REGEX.captures(line))
+        .map(|captures| captures[1].to_string())
+}
+
+/// Key expression signing test tokens the way `call` checks them: a key passed in or read
+/// from the environment (patched by [`with_python_env`]) is `"test-secret"`
+fn python_signing_key(call: &AuthCall, secret: &str) -> String {
+    if call.secret_param().is_some() || secret_env_var(secret).is_some() {
+        "\"test-secret\"".to_string()
+    } else {
+        secret.to_string()
+    }
+}
+
+/// Run a test body with the secret's environment variable set to `test-secret`
+fn with_python_env(secret: &str, body: &str) -> String {
+    match secret_env_var(secret) {
+        Some(var) => format!(
+            "        with patch.dict(os.environ, {{\"{}\": \"test-secret\"}}):\n{}",
+            var,
+            body.lines().map(|line| format!("    {}\n", line)).collect::<String>()
+        ),
+        None => body.to_string(),
+    }
+}
+
+/// Python string for a recorded string, an f-string reading `variables` when it has `{{variable}}` references
+fn python_template(template: &str) -> String {
+    let parts = template_parts(template);
+    match parts.as_slice() {
+        [TemplatePart::Variable(name)] => return format!("variables['{}']", name),
+        _ if !parts.iter().any(|part| matches!(part, TemplatePart::Variable(_))) => {
+            return python_literal(&serde_json::Value::String(template.to_string()));
+        }
+        _ => {}
+    }
+    let mut literal = String::from("f\"");
+    for part in parts {
+        match part {
+            TemplatePart::Text(text) => {
+                let quoted = serde_json::to_string(text).unwrap_or_default();
+                literal.push_str(&quoted[1..quoted.len() - 1].replace('{', "{{").replace('}', "}}"));
+            }
+            TemplatePart::Variable(name) => literal.push_str(&format!("{{variables['{}']}}", name)),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// [`python_literal`] with strings rendered by [`python_template`]
+fn python_template_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => python_template(s),
+        serde_json::Value::Array(items) => format!("[{}]", items.iter().map(python_template_value).collect::<Vec<_>>().join(", ")),
+        serde_json::Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter().map(|(k, v)| format!("{:?}: {}", k, python_template_value(v))).collect::<Vec<_>>().join(", ")
+        ),
+        other => python_literal(other),
+    }
+}
+
+/// Render a JSON value as a Python literal
+pub(crate) fn python_literal(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "None".to_string(),
+        serde_json::Value::Bool(true) => "True".to_string(),
+        serde_json::Value::Bool(false) => "False".to_string(),
+        serde_json::Value::Array(items) => format!("[{}]", items.iter().map(python_literal).collect::<Vec<_>>().join(", ")),
+        serde_json::Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter().map(|(k, v)| format!("{:?}: {}", k, python_literal(v))).collect::<Vec<_>>().join(", ")
+        ),
+        other => other.to_string(),
+    }
+}
+
+#[async_trait]
+impl TestGenerator for PythonAdapter {
+    async fn analyze_code(&self, source: &str, file_path: &str) -> Result<Vec<TestablePattern>> {
+        let mut patterns = self.detect_patterns(source);
+        for pattern in &mut patterns {
+            pattern.location.file = file_path.to_string();
+        }
+        Ok(patterns)
+    }
+
+    async fn generate_tests(&self, patterns: Vec<TestablePattern>) -> Result<TestSuite> {
+        self.generate_comprehensive_tests(patterns, "").await
+    }
+
+    async fn generate_comprehensive_tests(&self, patterns: Vec<TestablePattern>, source: &str) -> Result<TestSuite> {
+        let mut test_cases = Vec::new();
+        let mut fixtures = Vec::new();
+
+        let file = patterns.iter().map(|pattern| pattern.location.file.clone()).find(|file| !file.is_empty());
+        let classes = python_classes(source);
+        for class in &classes {
+            let (fixture, test) = self.generate_class_fixture(class);
+            fixtures.push(fixture);
+            test_cases.push(test);
+        }
 
         for pattern in patterns {
             match &pattern.pattern_type {
                 PatternType::Function(func) => {
-                    test_cases.extend(self.generate_function_tests(func, source));
+                    let decorators = self.patch_decorators(func, source, &pattern.location.file);
+                    let owner = classes.iter().find(|class| class.span.contains(&pattern.location.column));
+                    let tests = match owner {
+                        Some(class) if !func.name.starts_with("__") => self.generate_method_tests(func, class, source),
+                        _ => self.generate_function_tests(func, source),
+                    };
+                    let is_async = is_async_python(&func.name, source);
+                    test_cases.extend(tests.into_iter().map(|mut test| {
+                        test.test_body.insert_str(0, &decorators);
+                        if is_async {
+                            test = self.make_async_test(test, &func.name);
+                        }
+                        test
+                    }));
                 }
                 PatternType::FormValidation(field) => {
                     if field.field_type == FieldType::Email {
@@ -736,21 +2262,69 @@ impl TestGenerator for PythonAdapter {
                 _ => {}
             }
         }
+        test_cases.extend(self.generate_doc_example_tests(source));
+        test_cases.extend(self.generate_security_tests(source));
+        test_cases.extend(self.generate_env_tests(source));
+        test_cases.extend(self.generate_regex_tests(source));
+        test_cases.extend(self.generate_serialization_tests(source));
+        test_cases.extend(self.generate_variant_tests(source));
+        test_cases.extend(self.generate_builder_tests(source));
+        test_cases.extend(self.generate_concurrency_tests(source));
+
+        let mut imports = vec![
+            "import pytest".to_string(),
+            "import unittest.mock".to_string(),
+            "from unittest.mock import patch, MagicMock".to_string(),
+        ];
+        let mut setup_requirements = Vec::new();
+        let uses = |snippet: &str| test_cases.iter().any(|test| test.test_body.contains(snippet));
+        if uses("os.environ") {
+            imports.push("import os".to_string());
+        }
+        if uses("time.time()") || uses("time.perf_counter()") {
+            imports.push("import time".to_string());
+        }
+        if uses("threading.Thread(") {
+            imports.push("import threading".to_string());
+        }
+        if uses("asyncio.gather(") {
+            imports.push("import asyncio".to_string());
+        }
+        if uses("contextlib.") {
+            imports.push("import contextlib".to_string());
+        }
+        if uses("pytest.raises(ValidationError)") {
+            imports.push("from pydantic import ValidationError".to_string());
+        }
+        if uses("jwt.") {
+            imports.push("import jwt".to_string());
+        }
+        if uses("TestClient(app)") {
+            imports.push("from fastapi.testclient import TestClient".to_string());
+            setup_requirements.push("Install `httpx` for FastAPI's `TestClient` and make the `app` available to the tests".to_string());
+        }
+        if test_cases.iter().any(|test| test.test_body.contains("@pytest.mark.asyncio")) {
+            imports.push("from unittest.mock import AsyncMock".to_string());
+            setup_requirements.push("Install `pytest-asyncio` to run the `@pytest.mark.asyncio` tests".to_string());
+        }
+        let code: String = test_cases.iter().map(|test| test.test_body.as_str())
+            .chain(fixtures.iter().map(|fixture| fixture.code.as_str()))
+            .collect();
+        if let Some((module, names)) = file.and_then(|file| module_imports(&file, source, &code)) {
+            imports.push(format!("from {} import {}", module, names.join(", ")));
+        }
 
         let mut test_suite = TestSuite {
             name: "Generated Python Tests".to_string(),
             language: "python".to_string(),
             framework: "pytest".to_string(),
             test_cases,
-            imports: vec![
-                "import pytest".to_string(),
-                "import unittest.mock".to_string(),
-                "from unittest.mock import patch, MagicMock".to_string(),
-            ],
+            imports,
             test_type: crate::core::TestType::Unit,
-            setup_requirements: vec![],
+            setup_requirements,
             cleanup_requirements: vec![],
             coverage_target: self.get_coverage_target(),
+            fixtures,
             test_code: None,
         };
 
@@ -772,11 +2346,32 @@ impl TestGenerator for PythonAdapter {
         code.push_str("\n\n");
         
         code.push_str("class TestGenerated:\n");
+
+        for fixture in &test_suite.fixtures {
+            code.push_str(&fixture.code);
+            code.push('\n');
+        }
         
         for test_case in &test_suite.test_cases {
-            code.push_str(&format!("    def {}(self):\n", test_case.name));
+            let case_params = match CaseTable::from_test_case(test_case) {
+                Some(table) => {
+                    code.push_str(&self.parametrize_decorator(&table));
+                    format!(", {}, expected", table.parameters.join(", "))
+                }
+                None => String::new(),
+            };
+            let (decorators, body) = self.split_decorators(&test_case.test_body);
+            code.push_str(decorators);
+            code.push_str(&format!(
+                "    {}def {}(self{}{}{}):\n",
+                if decorators.contains("@pytest.mark.asyncio") { "async " } else { "" },
+                test_case.name,
+                self.patch_params(decorators),
+                case_params,
+                self.fixture_params(body, &test_suite.fixtures)
+            ));
             code.push_str(&format!("        \"\"\"{}\"\"\"\n", test_case.description));
-            code.push_str(&test_case.test_body);
+            code.push_str(body);
             code.push_str("\n");
         }
         
@@ -790,6 +2385,332 @@ impl TestGenerator for PythonAdapter {
     fn get_supported_frameworks(&self) -> Vec<&str> {
         vec!["pytest", "unittest"]
     }
+
+    fn configure(&mut self, config: AdapterConfig) {
+        self.config = config;
+    }
+
+    fn as_integration(&self) -> Option<&(dyn IntegrationTestGenerator + Send + Sync)> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl IntegrationTestGenerator for PythonAdapter {
+    async fn analyze_integration_patterns(&self, source: &str, file_path: &str) -> Result<Vec<TestablePattern>> {
+        if let Some(requests) = recorded_requests(source) {
+            return Ok(recorded_request_patterns(requests, file_path));
+        }
+        Ok(self.detect_integration_patterns(source, file_path))
+    }
+
+    async fn generate_integration_tests(&self, patterns: Vec<TestablePattern>) -> Result<TestSuite> {
+        let mut test_cases: Vec<TestCase> = Vec::new();
+        let mut imported: Vec<String> = Vec::new();
+
+        for pattern in &patterns {
+            if let PatternType::CliCommand(cli) = &pattern.pattern_type {
+                test_cases.extend(self.generate_cli_tests(cli));
+                continue;
+            }
+            let caller = pattern.context.function_name.as_deref();
+            let (test, name) = match &pattern.pattern_type {
+                PatternType::ApiIntegration(api) => (self.generate_api_integration_test(api, caller), None),
+                PatternType::DatabaseOperation(db) => (self.generate_database_integration_test(db, caller), Some(db.table_name.clone())),
+                PatternType::ServiceIntegration(task) => (self.generate_task_integration_test(task), Some(task.method_name.clone())),
+                PatternType::GrpcCall(rpc) => (self.generate_grpc_integration_test(rpc), None),
+                PatternType::MessageQueue(queue) => (self.generate_message_queue_integration_test(queue, caller), None),
+                PatternType::RecordedRequest(request) => (self.generate_recorded_request_test(request), None),
+                _ => continue,
+            };
+            if test_cases.iter().any(|t| t.name == test.name) {
+                continue;
+            }
+            if let Some(name) = name.filter(|n| !imported.contains(n)) {
+                imported.push(name);
+            }
+            test_cases.push(test);
+        }
+
+        let uses = |needle: &str| test_cases.iter().any(|t| t.test_body.contains(needle));
+        let mut imports = vec!["import os".to_string(), "import pytest".to_string()];
+        let mut fixtures = Vec::new();
+        if uses("requests.") {
+            imports.push("import requests".to_string());
+        }
+        if uses("jsonschema.") {
+            imports.push("import jsonschema".to_string());
+        }
+        if uses("subprocess.run(") {
+            imports.push("import subprocess".to_string());
+            imports.push("import sys".to_string());
+        }
+        if uses("{base_url}") {
+            fixtures.push(Fixture {
+                name: "base_url".to_string(),
+                code: "    @pytest.fixture\n    def base_url(self):\n        url = os.environ.get(\"TEST_BASE_URL\")\n        if not url:\n            pytest.skip(\"TEST_BASE_URL is not set\")\n        return url.rstrip(\"/\")\n".to_string(),
+            });
+        }
+        let variables = recorded_variables(&patterns);
+        if !variables.is_empty() {
+            let entries: String = variables.iter()
+                .map(|(name, value)| format!("            {:?}: os.environ.get({:?}, {}),\n", name, variable_env_var(name), python_literal(&serde_json::Value::String(value.clone()))))
+                .collect();
+            fixtures.push(Fixture {
+                name: "variables".to_string(),
+                code: format!("    @pytest.fixture\n    def variables(self):\n        return {{\n{}        }}\n", entries),
+            });
+        }
+        if uses("db_session") {
+            imports.push("from sqlalchemy import create_engine".to_string());
+            imports.push("from sqlalchemy.orm import sessionmaker".to_string());
+            imported.push("Base".to_string());
+            fixtures.push(Fixture {
+                name: "db_session".to_string(),
+                code: "    @pytest.fixture\n    def db_session(self):\n        engine = create_engine(os.environ.get(\"TEST_DATABASE_URL\", \"sqlite://\"))\n        Base.metadata.create_all(engine)\n        session = sessionmaker(bind=engine)()\n        yield session\n        session.rollback()\n        session.close()\n        Base.metadata.drop_all(engine)\n".to_string(),
+            });
+        }
+        for (fixture, broker, import) in [
+            ("kafka_bootstrap_servers", MessageBroker::Kafka, "from kafka import KafkaConsumer, KafkaProducer"),
+            ("rabbitmq_url", MessageBroker::RabbitMq, "import pika"),
+        ] {
+            if uses(fixture) {
+                imports.push(import.to_string());
+                fixtures.push(Fixture {
+                    name: fixture.to_string(),
+                    code: format!(
+                        "    @pytest.fixture\n    def {name}(self):\n        value = os.environ.get(\"{var}\")\n        if not value:\n            pytest.skip(\"{var} is not set\")\n        return value\n",
+                        name = fixture,
+                        var = broker.env_var()
+                    ),
+                });
+            }
+        }
+        if uses("@mock_aws") {
+            imports.push("import boto3".to_string());
+            imports.push("from moto import mock_aws".to_string());
+        }
+
+        let mut rpcs: Vec<&GrpcPattern> = Vec::new();
+        for pattern in &patterns {
+            if let PatternType::GrpcCall(rpc) = &pattern.pattern_type {
+                if !rpcs.iter().any(|r| r.service == rpc.service && r.method == rpc.method) {
+                    rpcs.push(rpc);
+                }
+            }
+        }
+        if !rpcs.is_empty() {
+            imports.push("from concurrent import futures".to_string());
+            imports.push("import grpc".to_string());
+            let mut services: Vec<&str> = Vec::new();
+            for rpc in &rpcs {
+                for module in [pb2_module(rpc), format!("{}_grpc", pb2_module(rpc))] {
+                    if !imports.contains(&format!("import {}", module)) {
+                        imports.push(format!("import {}", module));
+                    }
+                }
+                if !services.contains(&rpc.service.as_str()) {
+                    services.push(&rpc.service);
+                }
+            }
+            let bodies: String = test_cases.iter().map(|t| t.test_body.as_str()).collect();
+            for cap in PB2_MODULE_REGEX.captures_iter(&bodies) {
+                let import = format!("from google.protobuf import {}_pb2", &cap[1]);
+                if !imports.contains(&format!("import {}_pb2", &cap[1])) && !imports.contains(&import) {
+                    imports.push(import);
+                }
+            }
+            if uses("message_factory.") {
+                imports.push("from google.protobuf import message_factory".to_string());
+            }
+            for service in services {
+                let service_rpcs: Vec<&GrpcPattern> = rpcs.iter().copied().filter(|r| r.service == service).collect();
+                fixtures.push(self.generate_grpc_fixture(service, &service_rpcs));
+            }
+        }
+        let module = patterns.iter().find_map(|p| p.context.module_name.clone());
+        if let (Some(module), false) = (module, imported.is_empty()) {
+            imports.push(format!("from {} import {}", module, imported.join(", ")));
+        }
+
+        let mut setup_requirements = self.get_setup_requirements(&patterns);
+        let mut cleanup_requirements = self.get_cleanup_requirements(&patterns);
+        let services = self.test_services(&patterns);
+        if let (Some(setup), false) = (self.config.containers, services.is_empty()) {
+            if setup == ContainerSetup::Testcontainers {
+                let (import, fixture, extras) = testcontainers_fixture(&services);
+                imports.extend(import);
+                fixtures.insert(0, fixture);
+                let driver = if services.iter().any(|(service, _)| *service == TestService::Postgres) { " and `psycopg2-binary`" } else { "" };
+                setup_requirements.push(format!(
+                    "Install `testcontainers[{}]`{} and have Docker running; the containers start once per test class",
+                    extras, driver
+                ));
+            }
+            setup_requirements = containerized_requirements(setup_requirements, &services, setup);
+            cleanup_requirements.extend(containerized_cleanup(setup));
+        }
+
+        let mut test_suite = TestSuite {
+            name: "Generated Python Integration Tests".to_string(),
+            language: "python".to_string(),
+            framework: "pytest".to_string(),
+            test_cases,
+            imports,
+            test_type: TestType::Integration,
+            setup_requirements,
+            cleanup_requirements,
+            coverage_target: self.get_coverage_target(),
+            fixtures,
+            test_code: None,
+        };
+        test_suite.test_code = Some(self.generate_test_code(&test_suite)?);
+        Ok(test_suite)
+    }
+
+    fn get_integration_frameworks(&self) -> Vec<&str> {
+        vec!["pytest", "pytest-django", "requests"]
+    }
+
+    fn get_setup_requirements(&self, patterns: &[TestablePattern]) -> Vec<String> {
+        let mut requirements = Vec::new();
+
+        for pattern in patterns {
+            match &pattern.pattern_type {
+                PatternType::ApiIntegration(_) => {
+                    requirements.push("Start the service under test and set TEST_BASE_URL".to_string());
+                }
+                PatternType::CliCommand(cli) => {
+                    requirements.push(format!("Run the tests from the project root so `{}` resolves", cli.script));
+                }
+                PatternType::RecordedRequest(request) => {
+                    requirements.push("Install `requests`".to_string());
+                    if request.response_schema.is_some() {
+                        requirements.push("Install `jsonschema`".to_string());
+                    }
+                }
+                PatternType::DatabaseOperation(db) if db.method_name.starts_with("objects.") => {
+                    requirements.push("Install pytest-django and set DJANGO_SETTINGS_MODULE".to_string());
+                }
+                PatternType::DatabaseOperation(_) => {
+                    requirements.push("Set TEST_DATABASE_URL (defaults to in-memory SQLite)".to_string());
+                }
+                PatternType::ServiceIntegration(_) => {
+                    requirements.push("Configure Celery with task_always_eager or a test broker".to_string());
+                }
+                PatternType::MessageQueue(queue) => requirements.extend(match queue.broker {
+                    MessageBroker::Kafka => vec![
+                        "Install `kafka-python`",
+                        "Set KAFKA_BOOTSTRAP_SERVERS to a test broker, e.g. `docker run -p 9092:9092 apache/kafka` (tests skip when unset)",
+                    ],
+                    MessageBroker::RabbitMq => vec![
+                        "Install `pika`",
+                        "Set RABBITMQ_URL to a test broker, e.g. `docker run -p 5672:5672 rabbitmq` (tests skip when unset)",
+                    ],
+                    MessageBroker::Sqs => vec!["Install `boto3` and `moto`; SQS runs in-process under `@mock_aws`"],
+                }.into_iter().map(String::from)),
+                PatternType::GrpcCall(rpc) => {
+                    requirements.push("Install `grpcio` and `protobuf`".to_string());
+                    if let Some(proto_file) = &rpc.proto_file {
+                        requirements.push(format!("Generate the Python modules for {} with `python -m grpc_tools.protoc`", proto_file));
+                    }
+                }
+                _ => {}
+            }
+        }
+        requirements.extend(recorded_variables_requirement(patterns));
+
+        requirements.sort();
+        requirements.dedup();
+        requirements
+    }
+
+    fn get_cleanup_requirements(&self, patterns: &[TestablePattern]) -> Vec<String> {
+        let mut requirements = Vec::new();
+
+        for pattern in patterns {
+            match &pattern.pattern_type {
+                PatternType::ApiIntegration(_) => {
+                    requirements.push("Remove records created through the API".to_string());
+                }
+                PatternType::RecordedRequest(request) if !matches!(request.method, HttpMethod::Get) => {
+                    requirements.push("Remove records created by the replayed requests".to_string());
+                }
+                PatternType::DatabaseOperation(_) => {
+                    requirements.push("Drop test tables".to_string());
+                }
+                PatternType::ServiceIntegration(_) => {
+                    requirements.push("Purge the test broker queues".to_string());
+                }
+                PatternType::MessageQueue(queue) if queue.broker == MessageBroker::Kafka => {
+                    requirements.push("Delete the topics the tests wrote to".to_string());
+                }
+                _ => {}
+            }
+        }
+
+        requirements.sort();
+        requirements.dedup();
+        requirements
+    }
+
+    fn test_services(&self, patterns: &[TestablePattern]) -> Vec<(TestService, &'static str)> {
+        let mut services = Vec::new();
+        for pattern in patterns {
+            let service = match &pattern.pattern_type {
+                // pytest-django creates its own test database and moto stands in for SQS
+                PatternType::DatabaseOperation(db) if !db.method_name.starts_with("objects.") => (TestService::Postgres, "TEST_DATABASE_URL"),
+                PatternType::MessageQueue(queue) if queue.broker != MessageBroker::Sqs => (queue.broker.into(), queue.broker.env_var()),
+                _ => continue,
+            };
+            if !services.contains(&service) {
+                services.push(service);
+            }
+        }
+        services
+    }
+}
+
+/// Imports, a class-scoped autouse fixture starting every service and exporting its address
+/// before the first test, and the `testcontainers` extras it needs
+fn testcontainers_fixture(services: &[(TestService, &str)]) -> (Vec<String>, Fixture, String) {
+    let mut imports = Vec::new();
+    let mut containers = Vec::new();
+    let mut exports = String::new();
+    let mut extras = Vec::new();
+    for (service, var) in services {
+        let name = service.name();
+        let (import, container, address) = match service {
+            TestService::Postgres => ("from testcontainers.postgres import PostgresContainer", format!("PostgresContainer(\"{}\")", service.image()), "postgres.get_connection_url()".to_string()),
+            TestService::Kafka => ("from testcontainers.kafka import KafkaContainer", "KafkaContainer()".to_string(), "kafka.get_bootstrap_server()".to_string()),
+            TestService::RabbitMq => (
+                "from testcontainers.rabbitmq import RabbitMqContainer",
+                format!("RabbitMqContainer(\"{}\")", service.image()),
+                "f\"amqp://guest:guest@{rabbitmq.get_container_host_ip()}:{rabbitmq.get_exposed_port(5672)}/\"".to_string(),
+            ),
+            TestService::Sqs => (
+                "from testcontainers.core.container import DockerContainer",
+                format!("DockerContainer(\"{}\").with_exposed_ports({})", service.image(), service.port()),
+                format!("f\"http://{{sqs.get_container_host_ip()}}:{{sqs.get_exposed_port({})}}\"", service.port()),
+            ),
+        };
+        imports.push(import.to_string());
+        containers.push(format!("{} as {}", container, name));
+        exports.push_str(&format!("            os.environ[\"{}\"] = {}\n", var, address));
+        if *service != TestService::Sqs {
+            extras.push(name);
+        }
+    }
+
+    let fixture = Fixture {
+        name: "containers".to_string(),
+        code: format!(
+            "    @pytest.fixture(scope=\"class\", autouse=True)\n    def containers(self):\n        with {}:\n{}            yield\n",
+            containers.join(", "),
+            exports
+        ),
+    };
+    (imports, fixture, extras.join(","))
 }
 
 #[cfg(test)]
@@ -815,6 +2736,25 @@ mod tests {
         assert_eq!(frameworks, vec!["pytest", "unittest"]);
     }
 
+    #[test]
+    fn test_generate_unittest_module() {
+        let adapter = PythonAdapter::new();
+        let source = "class Cart:\n    def add(self, item):\n        pass\n\n    def _reset(self):\n        pass\n\ndef total(prices):\n    return sum(prices)\n\nasync def fetch_user(user_id):\n    return user_id\n";
+        let patterns = adapter.detect_patterns(source);
+        let module = adapter.generate_unittest_module(&patterns, source, &TemplateEngine::new().unwrap()).unwrap();
+
+        assert!(module.starts_with("import asyncio\nimport unittest\nfrom unittest.mock import AsyncMock, MagicMock, patch\n\n\nclass"));
+        assert!(module.contains("class TestCart(unittest.TestCase):\n    def setUp(self):\n        self.instance = Cart()"));
+        assert!(module.contains("def test_add_is_callable(self):"));
+        assert!(!module.contains("test_reset"));
+        assert!(module.contains("class TestTotal(unittest.TestCase):"));
+        assert!(module.contains("self.assertEqual(total(*self.args), total(*self.args))"));
+        assert!(module.contains("class TestFetchUserAsync(unittest.IsolatedAsyncioTestCase):"));
+        assert!(!module.contains("pytest"));
+        assert!(module.ends_with("if __name__ == \"__main__\":\n    unittest.main()\n"));
+        assert!(adapter.generate_unittest_module(&[], "", &TemplateEngine::new().unwrap()).unwrap().is_empty());
+    }
+
     #[test]
     fn test_detect_patterns_function_def() {
         let adapter = PythonAdapter::new();
@@ -955,8 +2895,8 @@ mod tests {
         let test_suite = result.unwrap();
         assert_eq!(test_suite.language, "python");
         assert_eq!(test_suite.framework, "pytest");
-        assert_eq!(test_suite.test_cases.len(), 1);
-        assert!(test_suite.test_cases[0].name.contains("test_calculate_area"));
+        assert_eq!(test_suite.test_cases.len(), 4); // positive, edge case, negative and type error tests
+        assert!(test_suite.test_cases.iter().all(|t| t.name.starts_with("test_calculate_area")));
     }
 
     #[tokio::test]
@@ -986,8 +2926,8 @@ mod tests {
         assert!(result.is_ok());
         
         let test_suite = result.unwrap();
-        assert_eq!(test_suite.test_cases.len(), 1);
-        assert!(test_suite.test_cases[0].name.contains("test_email_validation"));
+        assert_eq!(test_suite.test_cases.len(), 3); // valid, invalid and edge case email tests
+        assert!(test_suite.test_cases.iter().all(|t| t.name.contains("email")));
     }
 
     #[tokio::test]
@@ -1058,7 +2998,7 @@ mod tests {
         
         let test_suite = result.unwrap();
         assert_eq!(test_suite.test_cases.len(), 0);
-        assert_eq!(test_suite.imports.len(), 1);
+        assert_eq!(test_suite.imports.len(), 3);
         assert_eq!(test_suite.imports[0], "import pytest");
     }
 
@@ -1110,6 +3050,433 @@ mod tests {
         assert!(result.is_ok());
         
         let test_suite = result.unwrap();
-        assert_eq!(test_suite.test_cases.len(), 2); // 1 function + 1 email validation
+        assert_eq!(test_suite.test_cases.len(), 7); // 4 function + 3 email validation tests
+    }
+
+    #[test]
+    fn test_snapshot_style_for_dict_return() {
+        let adapter = PythonAdapter::with_config(AdapterConfig {
+            style: TestStyle::Snapshot,
+            ..AdapterConfig::for_language("python")
+        });
+        let source = "def build_user(name):\n    return {'name': name}\n\ndef area(w, h):\n    return w * h\n";
+        let build = FunctionPattern { name: "build_user".to_string(), parameters: vec!["name".to_string()], return_type: None };
+        let area = FunctionPattern { name: "area".to_string(), parameters: vec!["w".to_string(), "h".to_string()], return_type: None };
+
+        let build_tests = adapter.generate_generic_function_tests(&build, source);
+        assert_eq!(build_tests[0].name, "test_build_user_snapshot");
+        assert!(build_tests[0].test_body.contains("assert result == snapshot"));
+        assert!(!adapter.generate_generic_function_tests(&area, source)[0].test_body.contains("snapshot"));
+
+        assert_eq!(adapter.fixture_params(&build_tests[0].test_body, &[]), ", snapshot");
+        assert_eq!(adapter.fixture_params("        assert True\n", &[]), "");
+    }
+
+    #[test]
+    fn test_adapter_config_disables_error_tests() {
+        let adapter = PythonAdapter::with_config(AdapterConfig {
+            generate_error_tests: false,
+            naming_prefix: Some("check_".to_string()),
+            ..AdapterConfig::for_language("python")
+        });
+        let func = FunctionPattern {
+            name: "process".to_string(),
+            parameters: vec!["value".to_string()],
+            return_type: None,
+        };
+
+        let tests = adapter.generate_generic_function_tests(&func, "");
+        assert_eq!(tests.len(), 3);
+        assert_eq!(tests[0].name, "check_process_functionality");
+        assert!(!tests.iter().any(|t| matches!(t.test_category, TestCategory::ErrorHandling)));
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_parameterized_style_renders_parametrize() {
+        let adapter = PythonAdapter::with_config(AdapterConfig {
+            style: TestStyle::Parameterized,
+            generate_error_tests: false,
+            ..AdapterConfig::for_language("python")
+        });
+        let func = FunctionPattern {
+            name: "add".to_string(),
+            parameters: vec!["a: int".to_string(), "b: int".to_string()],
+            return_type: None,
+        };
+
+        let tests = adapter.generate_function_tests(&func, "");
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].name, "test_add_cases");
+
+        let suite = TestSuite {
+            name: "Generated Python Tests".to_string(),
+            language: "python".to_string(),
+            framework: "pytest".to_string(),
+            test_cases: tests,
+            imports: vec!["import pytest".to_string()],
+            test_type: TestType::Unit,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 90.0,
+            fixtures: vec![],
+            test_code: None,
+        };
+        let code = adapter.generate_test_code(&suite).unwrap();
+        assert!(code.contains("    @pytest.mark.parametrize(\"a, b, expected\", [\n"));
+        assert!(code.contains("        pytest.param(5, 5, 10, id=\"typical\"),\n"));
+        assert!(code.contains("        pytest.param(-5, -5, -10, id=\"negative values\"),\n"));
+        assert!(code.contains("    def test_add_cases(self, a, b, expected):\n"));
+        assert!(code.contains("        result = add(a, b)\n"));
+    }
+    #[test]
+    fn test_imported_names_python() {
+        let source = "import os.path\nimport requests as http\nfrom typing import List\nfrom app.db import (get_user, save_user)\n";
+        assert_eq!(imported_names_python(source), vec!["os", "http", "get_user", "save_user"]);
+    }
+
+    #[tokio::test]
+    async fn test_patch_decorators_for_used_imports() {
+        let adapter = PythonAdapter::new();
+        let source = "import requests\nfrom app.db import get_user, save_user\n\ndef load_profile(user_id):\n    user = get_user(user_id)\n    return requests.get(user.url)\n\ndef double(x):\n    return x * 2\n";
+        let patterns = adapter.analyze_code(source, "src/profiles.py").await.unwrap();
+
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let code = suite.test_code.unwrap();
+        assert!(code.contains("    @patch(\"profiles.requests\")\n    @patch(\"profiles.get_user\")\n    def test_load_profile_functionality(self, mock_get_user, mock_requests):\n"));
+        assert!(!code.contains("profiles.save_user"));
+        assert!(code.contains("    def test_double_functionality(self):\n"));
+    }
+    #[tokio::test]
+    async fn test_class_fixture_used_by_method_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "class BankAccount:\n    def __init__(self, name: str, amount=0):\n        self.name = name\n        self.amount = amount\n\n    def deposit(self, amount):\n        self.amount += amount\n\ndef helper(x):\n    return x\n";
+        let patterns = adapter.analyze_code(source, "bank.py").await.unwrap();
+
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        assert_eq!(suite.fixtures.len(), 1);
+        assert_eq!(suite.fixtures[0].name, "bank_account");
+        assert!(suite.fixtures[0].code.contains("        return BankAccount(name=\"TestName1\", amount=19.99)\n"));
+
+        let code = suite.test_code.unwrap();
+        assert!(code.contains("    def test_bank_account_instance(self, bank_account):\n"));
+        assert!(code.contains("    def test_deposit_functionality(self, bank_account):\n"));
+        assert!(code.contains("        result = bank_account.deposit(19.99)\n"));
+        assert!(code.contains("        result = helper(5)\n"));
+    }
+    #[test]
+    fn test_error_tests_from_raised_exceptions() {
+        let adapter = PythonAdapter::new();
+        let source = "def divide(a, b):\n    if b == 0:\n        raise ZeroDivisionError(\"b is zero\")\n    if not isinstance(a, int): raise TypeError(\"a\")\n    return a / b\n\ndef double(x):\n    return x * 2\n\ndef load(path):\n    try:\n        return open(path).read()\n    except OSError:\n        raise\n    return None\n";
+        let divide = FunctionPattern { name: "divide".to_string(), parameters: vec!["a".to_string(), "b".to_string()], return_type: None };
+        let double = FunctionPattern { name: "double".to_string(), parameters: vec!["x".to_string()], return_type: None };
+
+        let errors: Vec<TestCase> = adapter.generate_function_tests(&divide, source).into_iter()
+            .filter(|t| matches!(t.test_category, TestCategory::ErrorHandling))
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].name, "test_divide_raises_zero_division_error");
+        assert_eq!(errors[0].test_body, "        with pytest.raises(ZeroDivisionError):\n            divide(5, 0)\n");
+        assert_eq!(errors[1].test_body, "        with pytest.raises(TypeError):\n            divide(\"invalid\", 5)\n");
+
+        assert!(!adapter.generate_function_tests(&double, source).iter().any(|t| matches!(t.test_category, TestCategory::ErrorHandling)));
+
+        // A bare re-raise names no exception, and the next line's keyword isn't one
+        let load = FunctionPattern { name: "load".to_string(), parameters: vec!["path".to_string()], return_type: None };
+        assert!(!adapter.generate_function_tests(&load, source).iter().any(|t| t.test_body.contains("pytest.raises(return)")));
+    }
+    #[test]
+    fn test_doc_example_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "def greet(name):\n    \"\"\"\n    >>> greet('Ada')\n    'Hello Ada'\n    >>> print(greet('Bo'))\n    Hello Bo\n    >>> greet(None)\n    Traceback (most recent call last):\n    TypeError: bad name\n    \"\"\"\n    return 'Hello ' + name\n";
+
+        let tests = adapter.generate_doc_example_tests(source);
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].name, "test_greet_doc_examples");
+        assert_eq!(
+            tests[0].test_body,
+            "        assert greet('Ada') == 'Hello Ada'\n        assert str(greet('Bo')) == \"Hello Bo\"\n        with pytest.raises(TypeError):\n            greet(None)\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_functions_get_pytest_asyncio_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "from app.http import get_json\n\nasync def fetch_user(user_id):\n    return await get_json(user_id)\n\ndef double(x):\n    return x * 2\n";
+        let patterns = adapter.analyze_code(source, "src/users.py").await.unwrap();
+
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let code = suite.test_code.unwrap();
+        assert!(code.contains("    @pytest.mark.asyncio\n    @patch(\"users.get_json\", new_callable=AsyncMock)\n    async def test_fetch_user_functionality(self, mock_get_json):\n"));
+        assert!(code.contains("        result = await fetch_user("));
+        assert!(code.contains("    def test_double_functionality(self):\n        \"\"\"Test double function with valid inputs\"\"\"\n        result = double("));
+        assert!(suite.imports.contains(&"from unittest.mock import AsyncMock".to_string()));
+        assert!(suite.setup_requirements.iter().any(|r| r.contains("pytest-asyncio")));
+    }
+    #[tokio::test]
+    async fn test_integration_tests_for_http_orm_and_tasks() {
+        let adapter = PythonAdapter::new();
+        let source = "import requests\nfrom celery import shared_task\n\ndef fetch_order(order_id):\n    return requests.get(f\"/api/orders/{order_id}\").json()\n\ndef list_orders(session):\n    return session.query(Order).all()\n\ndef active_users():\n    return User.objects.filter(active=True)\n\n@shared_task\ndef send_receipt(order_id):\n    pass\n";
+        let patterns = adapter.analyze_integration_patterns(source, "app/orders.py").await.unwrap();
+        assert_eq!(patterns.len(), 4);
+        assert_eq!(patterns[0].context.function_name.as_deref(), Some("fetch_order"));
+
+        let suite = adapter.generate_integration_tests(patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+        assert!(code.contains("    def test_api_get_api_orders_1(self, base_url):\n"));
+        assert!(code.contains("        response = requests.get(f\"{base_url}/api/orders/1\", timeout=10)\n"));
+        assert!(code.contains("    def test_database_query_order(self, db_session):\n"));
+        assert!(code.contains("    @pytest.mark.django_db\n    def test_database_query_user(self):\n"));
+        assert!(code.contains("        result = send_receipt.apply(args=[1])\n"));
+        assert!(suite.imports.contains(&"from orders import Order, User, send_receipt, Base".to_string()));
+        assert!(suite.setup_requirements.iter().any(|r| r.contains("TEST_BASE_URL")));
+        assert!(suite.cleanup_requirements.iter().any(|r| r.contains("broker")));
+    }
+    #[tokio::test]
+    async fn test_argparse_cli_contract_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "import argparse\n\ndef main():\n    parser = argparse.ArgumentParser(prog=\"convert\")\n    parser.add_argument(\"input\")\n    parser.add_argument(\"--format\", required=True)\n    parser.add_argument(\"-v\", \"--verbose\", action=\"store_true\")\n    args = parser.parse_args()\n\nif __name__ == \"__main__\":\n    main()\n";
+        let patterns = adapter.analyze_integration_patterns(source, "tools/convert.py").await.unwrap();
+        let suite = adapter.generate_integration_tests(patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+
+        assert!(code.contains("        result = subprocess.run([sys.executable, \"tools/convert.py\", \"--help\"], capture_output=True, text=True, timeout=30)\n        assert result.returncode == 0\n"));
+        assert!(code.contains("        assert result.returncode == 2\n        assert \"input\" in result.stderr.lower()\n        assert \"--format\" in result.stderr.lower()\n"));
+        assert!(code.contains("\"--format\", \"value\", \"utf-cli-test.txt\"], capture_output=True, text=True, timeout=30)\n        assert result.returncode != 2, result.stderr\n"));
+        assert!(suite.imports.contains(&"import subprocess".to_string()));
+    }
+    #[tokio::test]
+    async fn test_grpc_integration_tests_from_proto() {
+        let adapter = PythonAdapter::new();
+        let proto = "syntax = \"proto3\";\npackage routeguide;\n\nservice RouteGuide {\n  rpc GetFeature(Point) returns (Feature) {}\n  rpc ListFeatures(Rectangle) returns (stream Feature) {}\n}\n";
+        let patterns = adapter.analyze_integration_patterns(proto, "protos/route_guide.proto").await.unwrap();
+        assert_eq!(patterns.len(), 2);
+
+        let suite = adapter.generate_integration_tests(patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+        assert!(code.contains("import route_guide_pb2_grpc\n"));
+        assert!(code.contains("        class MockRouteGuideServicer(route_guide_pb2_grpc.RouteGuideServicer):\n            def GetFeature(self, request, context):\n                return route_guide_pb2.Feature()\n"));
+        assert!(code.contains("    def test_grpc_route_guide_get_feature(self, route_guide_stub):\n"));
+        assert!(code.contains("        responses = list(route_guide_stub.ListFeatures(route_guide_pb2.Rectangle(), timeout=5))\n        assert responses == [route_guide_pb2.Feature()]\n"));
+
+        let client = "stub = helloworld_pb2_grpc.GreeterStub(channel)\nreply = stub.SayHello(helloworld_pb2.HelloRequest(name=name))\n";
+        let patterns = adapter.analyze_integration_patterns(client, "greet.py").await.unwrap();
+        let PatternType::GrpcCall(rpc) = &patterns[0].pattern_type else { panic!("expected a gRPC call") };
+        assert_eq!((rpc.method.as_str(), rpc.request_type.as_deref()), ("SayHello", Some("HelloRequest")));
+        assert_eq!(rpc.proto_file.as_deref(), Some("helloworld.proto"));
+    }
+
+    #[tokio::test]
+    async fn test_message_queue_round_trip_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "from kafka import KafkaProducer\nimport boto3\n\ndef emit(producer):\n    producer.send(\"events\", b\"x\")\n\ndef push(sqs):\n    sqs.send_message(QueueUrl=url, MessageBody=\"x\")\n";
+        let patterns = adapter.analyze_integration_patterns(source, "events.py").await.unwrap();
+        let suite = adapter.generate_integration_tests(patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+
+        assert!(code.contains("    def test_kafka_events_roundtrip(self, kafka_bootstrap_servers):\n"));
+        assert!(code.contains("            pytest.skip(\"KAFKA_BOOTSTRAP_SERVERS is not set\")\n"));
+        assert!(code.contains("    @mock_aws\n    def test_sqs_utf_test_queue_roundtrip(self):\n"));
+        assert!(suite.setup_requirements.iter().any(|r| r.starts_with("Install `kafka-python`")));
+        assert!(suite.cleanup_requirements.contains(&"Delete the topics the tests wrote to".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_postman_collection_replay_tests() {
+        let adapter = PythonAdapter::new();
+        let collection = r#"{"info": {"name": "Users", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"}, "variable": [{"key": "baseUrl", "value": "https://api.example.com"}], "item": [{"name": "Create user", "request": {"method": "POST", "header": [{"key": "Content-Type", "value": "application/json"}], "body": {"mode": "raw", "raw": "{\"name\": \"Ada\"}"}, "url": "{{baseUrl}}/users"}, "response": [{"code": 201}]}]}"#;
+        let patterns = adapter.analyze_integration_patterns(collection, "users.postman_collection.json").await.unwrap();
+        let suite = adapter.generate_integration_tests(patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+
+        assert!(code.contains("            \"baseUrl\": os.environ.get(\"BASE_URL\", \"https://api.example.com\"),\n"));
+        assert!(code.contains("    def test_replay_create_user(self, variables):\n"));
+        assert!(code.contains("        response = requests.post(f\"{variables['baseUrl']}/users\", headers={\"Content-Type\": \"application/json\"}, json={\"name\": \"Ada\"}, timeout=10)\n        assert response.status_code == 201\n"));
+        assert!(!code.contains("def base_url"));
+        assert!(suite.setup_requirements.iter().any(|r| r.starts_with("Point the replayed requests at a test deployment with BASE_URL")));
+    }
+
+    #[tokio::test]
+    async fn test_har_capture_replay_tests() {
+        let adapter = PythonAdapter::new();
+        let har = r#"{"log": {"version": "1.2", "entries": [{"_resourceType": "fetch", "request": {"method": "GET", "url": "https://shop.example.com/api/orders/7", "headers": [{"name": "Cookie", "value": "session=abc"}]}, "response": {"status": 200, "content": {"mimeType": "application/json", "text": "{\"id\": 7}"}}}]}}"#;
+        let patterns = adapter.analyze_integration_patterns(har, "shop.har").await.unwrap();
+        let suite = adapter.generate_integration_tests(patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+
+        assert!(code.contains("import jsonschema\n"));
+        assert!(code.contains("            \"cookie\": os.environ.get(\"COOKIE\", \"session=abc\"),\n"));
+        assert!(code.contains("        response = requests.get(f\"{variables['baseUrl']}/api/orders/7\", headers={\"Cookie\": variables['cookie']}, timeout=10)\n        assert response.status_code == 200\n        jsonschema.validate(response.json(), {\"properties\": {\"id\": {\"type\": \"integer\"}}, \"required\": [\"id\"], \"type\": \"object\"})\n"));
+        assert!(suite.setup_requirements.contains(&"Install `jsonschema`".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_testcontainers_fixture_exports_service_addresses() {
+        let adapter = PythonAdapter::with_config(AdapterConfig { containers: Some(ContainerSetup::Testcontainers), ..AdapterConfig::default() });
+        let source = "import pika\n\ndef push(channel):\n    channel.basic_publish(exchange=\"\", routing_key=\"jobs\", body=\"x\")\n";
+        let patterns = adapter.analyze_integration_patterns(source, "jobs.py").await.unwrap();
+        let suite = adapter.generate_integration_tests(patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+
+        assert!(code.contains("from testcontainers.rabbitmq import RabbitMqContainer\n"));
+        assert!(code.contains("    @pytest.fixture(scope=\"class\", autouse=True)\n    def containers(self):\n        with RabbitMqContainer(\"rabbitmq:3-alpine\") as rabbitmq:\n            os.environ[\"RABBITMQ_URL\"] = "));
+        assert!(suite.setup_requirements.contains(&"Install `testcontainers[rabbitmq]` and have Docker running; the containers start once per test class".to_string()));
+        assert!(!suite.setup_requirements.iter().any(|r| r.starts_with("Set RABBITMQ_URL")));
+    }
+    #[tokio::test]
+    async fn test_env_var_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "import os\n\ndef log_level():\n    return os.getenv(\"LOG_LEVEL\", \"info\")\n\ndef api_key():\n    return os.environ[\"API_KEY\"]\n";
+        let patterns = adapter.analyze_code(source, "settings.py").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let env: Vec<&TestCase> = suite.test_cases.iter().filter(|t| t.test_body.contains("env")).collect();
+        let names: Vec<&str> = env.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec![
+            "test_log_level_with_log_level_set",
+            "test_log_level_with_log_level_unset",
+            "test_api_key_with_api_key_set",
+            "test_api_key_with_api_key_unset",
+        ]);
+        assert_eq!(env[0].test_body, "        monkeypatch.setenv(\"LOG_LEVEL\", \"debug\")\n        assert log_level() == \"debug\"\n");
+        assert_eq!(env[3].test_body, "        monkeypatch.delenv(\"API_KEY\", raising=False)\n        with pytest.raises(KeyError):\n            api_key()\n");
+        assert!(suite.test_code.unwrap().contains("    def test_log_level_with_log_level_unset(self, monkeypatch):\n"));
+    }
+    #[tokio::test]
+    async fn test_pydantic_roundtrip_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "from typing import Optional\nfrom pydantic import BaseModel, Field\n\nclass Item(BaseModel):\n    name: str\n    price: float = Field(..., alias=\"unitPrice\")\n    note: Optional[str] = None\n\nclass Upload(BaseModel):\n    data: bytes\n";
+        let patterns = adapter.analyze_code(source, "models.py").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let models: Vec<&TestCase> = suite.test_cases.iter().filter(|t| t.input.get("type").is_some()).collect();
+        let names: Vec<&str> = models.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec![
+            "test_item_pydantic_roundtrip",
+            "test_item_rejects_malformed_json",
+            "test_upload_rejects_malformed_json",
+        ]);
+        assert_eq!(models[0].test_body, "        value = Item.model_validate({\"name\": \"test_string_0\", \"unitPrice\": 2.5, \"note\": \"test_string_2\"})\n        assert Item.model_validate_json(value.model_dump_json(by_alias=True)) == value\n");
+        assert_eq!(models[1].test_body, "        with pytest.raises(ValidationError):\n            Item.model_validate_json(\"{\\\"name\\\": \")\n        with pytest.raises(ValidationError):\n            Item.model_validate_json(\"{}\")\n");
+        assert!(suite.imports.contains(&"from pydantic import ValidationError".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_builder_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "from dataclasses import dataclass\n\n@dataclass\nclass Email:\n    to: str\n    subject: str\n\nclass EmailBuilder:\n    def __init__(self):\n        self.subject = \"\"\n        self.to = None\n\n    def with_to(self, to: str):\n        self.to = to\n        return self\n\n    def with_subject(self, subject: str):\n        self.subject = subject\n        return self\n\n    def build(self):\n        if self.to is None:\n            raise ValueError(\"to is required\")\n        return Email(self.to, self.subject)\n";
+        let patterns = adapter.analyze_code(source, "email.py").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let builder: Vec<&TestCase> = suite.test_cases.iter().filter(|t| t.input.get("builder").is_some()).collect();
+        let names: Vec<&str> = builder.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["test_email_builder_defaults", "test_email_builder_full_configuration", "test_email_builder_requires_to"]);
+        assert_eq!(builder[0].test_body, "        built = EmailBuilder().with_to(\"test_string_0\").build()\n        assert built.subject == \"\"\n");
+        assert_eq!(builder[1].test_body, "        built = EmailBuilder().with_to(\"test_string_0\").with_subject(\"test_string_1\").build()\n        assert built.to == \"test_string_0\"\n        assert built.subject == \"test_string_1\"\n");
+        assert_eq!(builder[2].test_body, "        with pytest.raises(ValueError):\n            EmailBuilder().with_subject(\"test_string_1\").build()\n");
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "import asyncio\nimport threading\n\nclass Counter:\n    def __init__(self):\n        self._lock = threading.Lock()\n        self.count = 0\n\n    def increment(self):\n        with self._lock:\n            self.count += 1\n\nclass Cache:\n    def __init__(self):\n        self._lock = asyncio.Lock()\n\n    async def put(self, key: str):\n        async with self._lock:\n            pass\n";
+        let patterns = adapter.analyze_code(source, "shared.py").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let concurrent: Vec<&TestCase> = suite.test_cases.iter().filter(|t| t.input.get("owner").is_some()).collect();
+        let names: Vec<&str> = concurrent.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["test_counter_increment_concurrently", "test_cache_put_concurrently"]);
+        assert_eq!(concurrent[0].test_body, "        shared = Counter()\n\n        def work():\n            for _ in range(100):\n                shared.increment()\n\n        threads = [threading.Thread(target=work) for _ in range(8)]\n        for thread in threads:\n            thread.start()\n        for thread in threads:\n            thread.join()\n        assert shared.count == 800\n");
+        assert_eq!(concurrent[1].test_body, "    @pytest.mark.asyncio\n        shared = Cache()\n\n        async def work():\n            for _ in range(100):\n                await shared.put(\"test_string_0\")\n\n        await asyncio.gather(*(work() for _ in range(8)))\n");
+        assert!(suite.imports.contains(&"import threading".to_string()) && suite.imports.contains(&"import asyncio".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_enum_member_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "from enum import Enum\n\nclass Status(Enum):\n    OPEN = \"open\"\n    CLOSED = \"closed\"\n    ARCHIVED = \"archived\"\n\ndef label(status: Status) -> str:\n    match status:\n        case Status.OPEN:\n            return \"Open\"\n        case Status.CLOSED:\n            return \"Closed\"\n";
+        let patterns = adapter.analyze_code(source, "status.py").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let members: Vec<&TestCase> = suite.test_cases.iter().filter(|t| t.input.get("variant").is_some()).collect();
+        let names: Vec<&str> = members.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["test_label_handles_open", "test_label_handles_closed", "test_label_handles_archived"]);
+        assert_eq!(members[0].test_body, "        label(Status.OPEN)\n");
+        assert_eq!(members[2].test_body, "        label(Status.ARCHIVED)\n        pytest.fail(\"Status.ARCHIVED is not handled by label: no branch handles it\")\n");
+    }
+
+    #[tokio::test]
+    async fn test_regex_edge_case_tests() {
+        let adapter = PythonAdapter::new();
+        let source = "import re\n\ndef is_zip(code):\n    return re.match(r\"^\\d{5}$\", code) is not None\n\ndef words(text):\n    return re.findall(r\"(\\w+\\s?)*\", text)\n";
+        let patterns = adapter.analyze_code(source, "format.py").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let regex: Vec<&TestCase> = suite.test_cases.iter().filter(|t| t.input.get("pattern").is_some()).collect();
+        let names: Vec<&str> = regex.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec![
+            "test_is_zip_regex_anchoring",
+            "test_is_zip_regex_empty_string",
+            "test_is_zip_regex_unicode_input",
+            "test_is_zip_regex_backtracking",
+            "test_words_regex_backtracking",
+        ]);
+        assert_eq!(regex[0].test_body, "        # `$` also matches before a trailing newline; anchor with \\Z or use re.fullmatch\n        assert is_zip(\"00000\")\n        assert not is_zip(\" 00000 \")\n        assert not is_zip(\"00000\\n\")\n");
+        assert!(regex[4].test_body.starts_with("        for text in [\"a\" * 25 + \"!\", \"a\" * 10000 + \"!\"]:\n            start = time.perf_counter()\n            with contextlib.suppress(Exception):\n                words(text)\n            assert time.perf_counter() - start < 0.25\n"));
+        assert!(suite.imports.contains(&"import contextlib".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_security_tests_for_auth_code() {
+        let adapter = PythonAdapter::new();
+        let source = "import os\nimport jwt\nimport bcrypt\nfrom fastapi import Depends\n\ndef decode_token(token):\n    return jwt.decode(token, os.environ[\"JWT_SECRET\"], algorithms=[\"HS256\"])\n\ndef hash_password(password):\n    return bcrypt.hashpw(password.encode(), bcrypt.gensalt())\n\ndef check_password(password, hashed):\n    return bcrypt.checkpw(password.encode(), hashed)\n\n@app.get(\"/me\")\ndef me(user=Depends(get_current_user)):\n    return user\n";
+        let patterns = adapter.analyze_code(source, "auth.py").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        let security: Vec<&TestCase> = suite.test_cases.iter().filter(|t| matches!(t.test_category, TestCategory::Security)).collect();
+
+        let names: Vec<&str> = security.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec![
+            "test_decode_token_rejects_expired_token",
+            "test_decode_token_rejects_tampered_token",
+            "test_check_password_verifies_password_hashed_by_hash_password",
+            "test_hash_password_salts_password_hash",
+            "test_get_me_requires_authentication",
+        ]);
+        assert!(security[0].test_body.starts_with("        with patch.dict(os.environ, {\"JWT_SECRET\": \"test-secret\"}):\n            expired = jwt.encode({\"sub\": \"user-1\", \"exp\": int(time.time()) - 3600}, \"test-secret\", algorithm=\"HS256\")\n            with pytest.raises(Exception):\n                decode_token(expired)\n"));
+        assert!(security[2].test_body.contains("        assert not check_password(\"wrong password\", hashed)\n"));
+        assert!(security[4].test_body.contains("TestClient(app).get(\"/me\")\n        assert response.status_code in (401, 403)\n"));
+        assert!(suite.imports.contains(&"from fastapi.testclient import TestClient".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_tests_import_the_module_from_its_package() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/shop")).unwrap();
+        std::fs::write(dir.path().join("pyproject.toml"), "[project]\nname = \"shop\"\n").unwrap();
+        let source = "class Cart:\n    def __init__(self, owner):\n        self.owner = owner\n\n    def total(self):\n        return 0\n\ndef add(a, b):\n    return a + b\n\ndef _round(value):\n    return value\n";
+        let file = dir.path().join("src/shop/cart.py");
+        std::fs::write(&file, source).unwrap();
+
+        let adapter = PythonAdapter::new();
+        let patterns = adapter.analyze_code(source, file.to_str().unwrap()).await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns.clone(), source).await.unwrap();
+        let import = suite.imports.iter().find(|import| import.starts_with("from shop.cart import")).unwrap();
+        assert!(import.contains("Cart") && import.contains("add"));
+        assert!(suite.test_code.unwrap().contains(&format!("{}\n", import)));
+
+        let module = adapter.generate_unittest_module(&patterns, source, &TemplateEngine::new().unwrap()).unwrap();
+        assert!(module.contains("\nfrom shop.cart import "));
+
+        let patterns = adapter.analyze_code(source, "").await.unwrap();
+        let suite = adapter.generate_comprehensive_tests(patterns, source).await.unwrap();
+        assert!(!suite.imports.iter().any(|import| import.contains("cart")));
+    }
+
+    #[tokio::test]
+    async fn test_patch_targets_use_the_package_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/shop")).unwrap();
+        std::fs::write(dir.path().join("pyproject.toml"), "[project]\nname = \"shop\"\n").unwrap();
+        let source = "from shop.http import get_json\n\ndef load_user(user_id):\n    return get_json(user_id)\n";
+        let file = dir.path().join("src/shop/users.py");
+        std::fs::write(&file, source).unwrap();
+
+        let adapter = PythonAdapter::new();
+        let patterns = adapter.analyze_code(source, file.to_str().unwrap()).await.unwrap();
+        let code = adapter.generate_comprehensive_tests(patterns, source).await.unwrap().test_code.unwrap();
+        assert!(code.contains("    @patch(\"shop.users.get_json\")\n"), "{}", code);
+        assert!(!code.contains("@patch(\"users.get_json\")"));
+    }
+}
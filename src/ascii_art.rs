@@ -225,7 +225,7 @@ mod tests {
     #[test]
     fn test_banner_generation() {
         let banner = AsciiArt::get_stylized_banner();
-        assert!(banner.contains("UTF"));
+        assert!(banner.contains("Unified Test Framework"));
         assert!(banner.contains("$@m!₹"));
     }
     
@@ -1,18 +1,32 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
-use unified_test_framework::{TestOrchestrator, LanguageLoader, IntegrationTestGenerator, AsciiArt};
+use clap::{Args, Parser, Subcommand};
+use unified_test_framework::{TestOrchestrator, TestGenerator, LanguageLoader, AsciiArt, ProjectConfig, FuzzGenerator, harness_language, AdapterConfig, TestStyle, AsyncRuntime, TestPlacement, Executor, BenchGenerator, PatternType, CoverageReport, ContainerSetup, TestService, compose_file, COMPOSE_FILE, PactGenerator, ApiTestGenerator, OpenApiSpec, recorded_requests, TestType, E2eGenerator, E2eFramework, generate_test_file_content, generate_test_file_content_with_framework, write_generated_file, write_tests, OverwritePolicy, TestFileUpdate, serve, PROJECT_CONFIG_FILE, IGNORE_FILE, TestRunner, FileReport, GenerationReport, UftError, RunCheckpoint, GitChanges, ChangedFile, untested_annotations, write_step_summary, GitHost, PullRequest, changed_paths, has_local_changes, generated_tests_message, commit_on_branch, TemplateEngine, validate_config_dir, parse_pack_spec, user_language_dir, LanguageRegistry, DEFAULT_REGISTRY, push_branch, GitCredentials, GenerationManifest, pattern_ids, backup_path, MANIFEST_FILE, test_locations, test_names, TestablePattern, Daemon, run_daemon, OversizedFiles, SourceRead, read_source, parse_file_size, DEFAULT_MAX_FILE_SIZE, sniff_source, read_existing_tests, python_module};
 use std::fs;
-use std::path::Path;
-use std::collections::HashMap;
-use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap};
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
 use git2::Repository;
 use walkdir::WalkDir;
+use ignore::WalkBuilder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use tracing::{debug, error, info, warn, Level};
+use tracing::level_filters::LevelFilter;
+use indicatif::{ProgressBar, ProgressStyle};
 
 #[derive(Parser)]
 #[command(name = "utf")]
 #[command(about = "A unified test generation framework for multiple languages")]
 #[command(version)]
 struct Cli {
+    /// Show more detail: -v for the steps taken per file and adapter, -vv for everything
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only print results and errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -21,14 +35,69 @@ struct Cli {
 enum Commands {
     /// Generate tests for a file
     Generate {
-        /// Path to the file to analyze
+        /// Path to the file to analyze, or - to read it from stdin (needs --lang)
         path: String,
+        /// Language of the source, or a file extension: required with stdin, and overrides the
+        /// file's extension otherwise
+        #[arg(long)]
+        lang: Option<String>,
+        /// Print the test file on stdout instead of writing it; implied by reading stdin
+        #[arg(long)]
+        stdout: bool,
         /// Output directory for generated tests
         #[arg(short, long, default_value = "tests/")]
         output: String,
         /// Custom language configs directory
         #[arg(short, long, default_value = "./language_configs")]
         config_dir: String,
+        /// Exit with an error when the detected patterns can't meet the coverage target
+        #[arg(long)]
+        enforce_coverage: bool,
+        /// Include integration tests in the same output file when the language supports them
+        #[arg(long)]
+        with_integration: bool,
+        /// Test style: standard, snapshot (snapshot assertions for object/struct results) or parameterized (one table-driven test per function)
+        #[arg(long, default_value = "standard")]
+        style: TestStyle,
+        /// Runtime for Rust async tests: tokio (#[tokio::test]) or async-std (#[async_std::test])
+        #[arg(long, default_value = "tokio")]
+        async_runtime: AsyncRuntime,
+        /// Test only exported functions, from an external package (Go's `foo_test`, importing
+        /// the package by its go.mod module path)
+        #[arg(long)]
+        black_box: bool,
+        /// Where Rust tests go: inline (a #[cfg(test)] mod tests in the source file) or
+        /// integration (tests/<file>.rs, importing the module from the crate)
+        #[arg(long, default_value = "inline")]
+        placement: TestPlacement,
+        /// Run each function on its sample inputs (node, python3 or rustc, in a scratch directory)
+        /// and assert the observed outputs
+        #[arg(long)]
+        execute: bool,
+        /// Existing coverage (LCOV tracefile or Cobertura XML); only uncovered functions get tests
+        #[arg(long)]
+        coverage: Option<String>,
+        /// Only generate tests for this function, or every method of this class; repeatable. The
+        /// tests are merged into an existing test file unless --force or --skip
+        #[arg(long = "symbol", conflicts_with = "with_integration")]
+        symbols: Vec<String>,
+        /// Test framework to write the tests for, over the project config's [frameworks]
+        #[arg(long)]
+        framework: Option<String>,
+        /// Patterns detected with lower confidence than this get no tests, over the project
+        /// config's confidence_threshold
+        #[arg(long)]
+        min_confidence: Option<f32>,
+        /// What to generate: unit, or e2e (Playwright/Cypress specs visiting the routes found
+        /// under the path)
+        #[arg(long = "type", default_value = "unit")]
+        test_type: TestType,
+        /// Output format: text, or json (the structured results on stdout, progress on stderr)
+        #[arg(long, default_value = "text")]
+        format: OutputFormat,
+        /// An existing test file is replaced unless --skip or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
     },
     /// Analyze code patterns in a file
     Analyze {
@@ -37,6 +106,9 @@ enum Commands {
         /// Custom language configs directory
         #[arg(short, long, default_value = "./language_configs")]
         config_dir: String,
+        /// Output format: text, or json (the structured results on stdout, progress on stderr)
+        #[arg(long, default_value = "text")]
+        format: OutputFormat,
     },
     /// Build IDE plugins
     Plugin {
@@ -52,6 +124,28 @@ enum Commands {
         /// Custom language configs directory
         #[arg(short, long, default_value = "./language_configs")]
         config_dir: String,
+        /// Output format: a table, or JSON with each language's capabilities
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Check language config JSON files against the published schema
+    ValidateConfig {
+        /// A config file, or a directory of them
+        #[arg(default_value = "./language_configs")]
+        path: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Install community language packs from a registry
+    Lang {
+        #[command(subcommand)]
+        action: LangAction,
+    },
+    /// Inspect the built-in test templates, to debug one before a full generation
+    Templates {
+        #[command(subcommand)]
+        action: TemplatesAction,
     },
     /// Generate tests for all supported files in a directory
     Dir {
@@ -60,6 +154,178 @@ enum Commands {
         /// Custom language configs directory
         #[arg(short, long, default_value = "./language_configs")]
         config_dir: String,
+        /// Test style: standard, snapshot (snapshot assertions for object/struct results) or parameterized (one table-driven test per function)
+        #[arg(long, default_value = "standard")]
+        style: TestStyle,
+        /// Runtime for Rust async tests: tokio (#[tokio::test]) or async-std (#[async_std::test])
+        #[arg(long, default_value = "tokio")]
+        async_runtime: AsyncRuntime,
+        /// Test only exported functions, from an external package (Go's `foo_test`, importing
+        /// the package by its go.mod module path)
+        #[arg(long)]
+        black_box: bool,
+        /// Where Rust tests go: inline (a #[cfg(test)] mod tests in the source file) or
+        /// integration (tests/<file>.rs, importing the module from the crate)
+        #[arg(long, default_value = "inline")]
+        placement: TestPlacement,
+        /// Existing coverage (LCOV tracefile or Cobertura XML); only uncovered functions get tests
+        #[arg(long)]
+        coverage: Option<String>,
+        /// What to generate: unit, or e2e (Playwright/Cypress specs visiting the routes found
+        /// under the path)
+        #[arg(long = "type", default_value = "unit")]
+        test_type: TestType,
+        /// Only generate for files matching this glob (relative to the directory; repeatable)
+        #[arg(long)]
+        include: Vec<String>,
+        /// Skip files matching this glob (relative to the directory; repeatable)
+        #[arg(long)]
+        exclude: Vec<String>,
+        /// Output format: text, or json (the structured results on stdout, progress on stderr)
+        #[arg(long, default_value = "text")]
+        format: OutputFormat,
+        /// Carry on an interrupted run from its checkpoint (.uft/state.json) instead of starting over
+        #[arg(long)]
+        resume: bool,
+        /// Files with a test file are skipped unless --force or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+        #[command(flatten)]
+        file_size: FileSizeArgs,
+    },
+    /// Watch a directory and regenerate tests for source files as they're saved, merging new
+    /// tests into existing test files
+    Watch {
+        /// Path to the directory to watch
+        path: String,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// Milliseconds to wait after the last change to a file before regenerating its tests
+        #[arg(long, default_value = "500")]
+        debounce: u64,
+        /// Only generate for files matching this glob (relative to the directory; repeatable)
+        #[arg(long)]
+        include: Vec<String>,
+        /// Skip files matching this glob (relative to the directory; repeatable)
+        #[arg(long)]
+        exclude: Vec<String>,
+        /// New tests are merged into existing test files unless --force or --skip
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+    },
+    /// Write a starter uft.toml for a project, with a framework for each language it uses
+    Init {
+        /// Project root to write uft.toml to
+        #[arg(default_value = ".")]
+        path: String,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// Overwrite an existing uft.toml
+        #[arg(long)]
+        force: bool,
+    },
+    /// Run the generated tests with the project's test runners (cargo test, pytest, npm test,
+    /// go test, gradle test) and report how many passed and failed
+    Run {
+        /// Project directory whose generated tests to run
+        #[arg(default_value = ".")]
+        path: String,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// List the test files, their tests and the commands running each instead of running them
+        #[arg(long)]
+        list: bool,
+        /// Output format: text, or json (the structured results on stdout, progress on stderr)
+        #[arg(long, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Report per file the patterns found, the tests generated and their confidence, and the
+    /// public functions the existing tests miss, as an HTML page and a Markdown fragment
+    Report {
+        /// Directory to report on
+        #[arg(default_value = ".")]
+        path: String,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// Where to write the HTML report
+        #[arg(long, default_value = "uft-report.html")]
+        html: String,
+        /// Where to write the Markdown fragment, e.g. for a PR comment
+        #[arg(long, default_value = "uft-report.md")]
+        markdown: String,
+        /// Also report to a CI system: github annotates untested functions and writes the job summary
+        #[arg(long)]
+        ci: Option<CiProvider>,
+    },
+    /// Report on a project from a CI job: annotations for untested functions, a job summary,
+    /// and the HTML and Markdown reports written as artifacts to upload
+    Ci {
+        /// Directory to report on
+        #[arg(default_value = ".")]
+        path: String,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// CI system to report to
+        #[arg(long, default_value = "github")]
+        provider: CiProvider,
+        /// Directory the reports are written to
+        #[arg(long, default_value = "uft-artifacts")]
+        artifacts: String,
+    },
+    /// Generate tests for just the functions changed since a git ref, merging them into the
+    /// existing test files; the PR-time workflow
+    Diff {
+        /// Ref to compare with; changes are taken from its merge base with HEAD, plus
+        /// uncommitted and untracked files
+        #[arg(long, default_value = "origin/main")]
+        base: String,
+        /// Repository, or a directory inside it
+        #[arg(default_value = ".")]
+        path: String,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// Only generate for files matching this glob (relative to the repository; repeatable)
+        #[arg(long)]
+        include: Vec<String>,
+        /// Skip files matching this glob (relative to the repository; repeatable)
+        #[arg(long)]
+        exclude: Vec<String>,
+        /// New tests are merged into existing test files unless --force or --skip
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+    },
+    /// Undo the last generating run in a directory: delete the files it created and restore the
+    /// ones it modified, as recorded in its uft-manifest.json
+    Rollback {
+        /// Directory the run wrote its manifest to
+        #[arg(default_value = ".")]
+        path: String,
+        /// Also roll back files changed since the run wrote them, losing those changes
+        #[arg(long)]
+        force: bool,
+    },
+    /// Run as a language server on stdin/stdout, reporting untested functions and generating
+    /// tests on request (the editor plugins start this)
+    Server {
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+    },
+    /// Run as a daemon answering `analyze` and `generate` requests on stdin/stdout, with
+    /// progress notifications (the IntelliJ plugin starts this)
+    Daemon {
+        /// Speak JSON-RPC 2.0, one message per line (the only protocol so far)
+        #[arg(long, required = true)]
+        jsonrpc: bool,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
     },
     /// Generate integration tests for a file
     IntegrationTest {
@@ -71,20 +337,108 @@ enum Commands {
         /// Custom language configs directory
         #[arg(short, long, default_value = "./language_configs")]
         config_dir: String,
+        /// Language to generate tests in, required for `.proto` files, Postman collections and HAR captures
+        #[arg(long)]
+        language: Option<String>,
+        /// Start detected databases and brokers with testcontainers (in the suite) or compose
+        /// (a docker-compose.test.yml next to it)
+        #[arg(long)]
+        containers: Option<ContainerSetup>,
+        /// Existing files are replaced unless --skip or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+    },
+    /// Generate fuzz targets for functions taking string/byte input
+    Fuzz {
+        /// Path to the Rust, Go, Python or C/C++ file to analyze
+        path: String,
+        /// Existing harnesses are skipped unless --force or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+    },
+    /// Generate benchmarks (criterion, pytest-benchmark, vitest bench or Deno.bench) for detected functions
+    Bench {
+        /// Path to the Rust, Python or JavaScript file to analyze
+        path: String,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// Existing benchmarks are skipped unless --force or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+    },
+    /// Generate Pact consumer contract tests for the API calls in a file
+    Contract {
+        /// Path to the JavaScript, Python or Rust file making the API calls
+        path: String,
+        /// Consumer name recorded in the pact (default: the file's name)
+        #[arg(long)]
+        consumer: Option<String>,
+        /// Provider name recorded in the pact (default: the host the calls go to)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Custom language configs directory
+        #[arg(short, long, default_value = "./language_configs")]
+        config_dir: String,
+        /// Existing contract tests are skipped unless --force or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+    },
+    /// Generate request/response tests for every operation of an OpenAPI (or Swagger) document
+    ApiTests {
+        /// Path to the OpenAPI document (YAML or JSON)
+        #[arg(long)]
+        spec: String,
+        /// Language to generate tests in (python, javascript or rust)
+        #[arg(long)]
+        language: String,
+        /// Output directory for generated tests
+        #[arg(short, long, default_value = "api-tests/")]
+        output: String,
+        /// Existing test files are skipped unless --force or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
     },
     /// Generate tests for all supported files in a Git repository
     GitRepo {
-        /// Git repository URL
+        /// Git repository URL, or the path of a local checkout to work in place
         url: String,
         /// Custom language configs directory
         #[arg(short, long, default_value = "./language_configs")]
         config_dir: String,
-        /// Branch to checkout (default: main)
-        #[arg(short, long, default_value = "main")]
-        branch: String,
+        /// Branch to checkout (default: the repository's default branch)
+        #[arg(short, long)]
+        branch: Option<String>,
         /// Write tests to repository's standard test directories instead of separate folder
         #[arg(long, default_value = "true")]
         in_repo: bool,
+        /// Only generate for files matching this glob (relative to the repository; repeatable)
+        #[arg(long)]
+        include: Vec<String>,
+        /// Skip files matching this glob (relative to the repository; repeatable)
+        #[arg(long)]
+        exclude: Vec<String>,
+        /// Carry on an interrupted run in the existing clone from its checkpoint
+        /// (.uft/state.json) instead of cloning again
+        #[arg(long)]
+        resume: bool,
+        /// Commit the generated tests on a new branch, push it and open a pull request (a merge
+        /// request on GitLab) into the branch they were generated from
+        #[arg(long)]
+        create_pr: bool,
+        /// Environment variable holding the access token for cloning private repositories over
+        /// HTTPS, pushing and opening the pull request (default: GH_TOKEN on GitHub, GITLAB_TOKEN
+        /// on GitLab)
+        #[arg(long)]
+        token_env: Option<String>,
+        /// Private key for SSH URLs, instead of the keys in ssh-agent
+        #[arg(long)]
+        ssh_key: Option<String>,
+        /// Files with a test file are skipped unless --force or --merge
+        #[command(flatten)]
+        overwrite: OverwriteArgs,
+        #[command(flatten)]
+        file_size: FileSizeArgs,
     },
     /// Install and configure uft for system-wide use
     Install {
@@ -95,6 +449,53 @@ enum Commands {
         #[arg(long)]
         force: bool,
     },
+    /// Remove what `install` set up: the installed configs and the PATH lines it added to shell
+    /// profiles
+    Uninstall {
+        /// Only print what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplatesAction {
+    /// List the templates by name
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Print a template's source
+    Show {
+        /// Template name, as `list` prints it, e.g. pytest/function_test
+        name: String,
+    },
+    /// Render a template with sample data, or with the test data in a JSON file
+    #[command(visible_alias = "preview")]
+    Render {
+        /// Template name, as `list` prints it, e.g. pytest/function_test
+        name: String,
+        /// JSON file of the data to render with: function_name, test_name, description,
+        /// inputs, expected_outputs, test_category, imports, setup_code and teardown_code
+        #[arg(long)]
+        data: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum LangAction {
+    /// Download a language's config, and any WebAssembly adapter it needs, checking their checksums
+    Add {
+        /// Language to install, optionally pinned to a version: kotlin or kotlin@1.2.0
+        pack: String,
+        /// Registry index URL or path [default: $UFT_REGISTRY, else the community registry]
+        #[arg(long)]
+        registry: Option<String>,
+        /// Directory to install into [default: ~/.config/uft/language_configs]
+        #[arg(long)]
+        dir: Option<String>,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -104,47 +505,481 @@ enum PluginType {
     Spring,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum CiProvider {
+    Github,
+}
+
+/// What the generating commands do with files they'd write that already exist. Each command
+/// has its own default, kept from before the flags existed
+#[derive(Args, Clone, Copy, Debug, Default)]
+struct OverwriteArgs {
+    /// Replace existing files with the generated ones
+    #[arg(long, conflicts_with_all = ["skip", "merge"])]
+    force: bool,
+    /// Leave existing files alone
+    #[arg(long, conflicts_with = "merge")]
+    skip: bool,
+    /// Add the generated tests an existing file lacks, keeping everything in it
+    #[arg(long)]
+    merge: bool,
+    /// Copy a file to <name>.bak before changing it
+    #[arg(long)]
+    backup: bool,
+}
+
+impl OverwriteArgs {
+    /// The policy asked for, else the command's `default`
+    fn policy(&self, default: OverwritePolicy) -> OverwritePolicy {
+        if self.force {
+            OverwritePolicy::Force
+        } else if self.skip {
+            OverwritePolicy::Skip
+        } else if self.merge {
+            OverwritePolicy::Merge
+        } else {
+            default
+        }
+    }
+
+    /// Write `content` to `path` under the policy, recording in `manifest` the files that changed
+    /// and the ids of the `patterns` the content came from
+    fn write(&self, manifest: &mut GenerationManifest, path: &Path, content: &str, default: OverwritePolicy, patterns: &[String]) -> Result<TestFileUpdate> {
+        self.write_with(manifest, path, content, patterns, |path, content, backup| write_generated_file(path, content, self.policy(default), backup))
+    }
+
+    /// [`write`](Self::write) for the tests of `source_file`, which only ever get the test
+    /// module into the source file when it's also the test file
+    fn write_tests(&self, manifest: &mut GenerationManifest, path: &Path, source_file: &Path, content: &str, default: OverwritePolicy, patterns: &[String]) -> Result<TestFileUpdate> {
+        self.write_with(manifest, path, content, patterns, |path, content, backup| write_tests(path, source_file, content, self.policy(default), backup))
+    }
+
+    fn write_with(&self, manifest: &mut GenerationManifest, path: &Path, content: &str, patterns: &[String], write: impl FnOnce(&Path, &str, bool) -> Result<TestFileUpdate>) -> Result<TestFileUpdate> {
+        let previous = fs::read_to_string(path).ok();
+        let previous_backup = fs::read_to_string(backup_path(path)).ok();
+        let update = write(path, content, self.backup)?;
+        if !matches!(update, TestFileUpdate::UpToDate | TestFileUpdate::Skipped) {
+            if self.backup && previous.is_some() {
+                manifest.record(&backup_path(path), previous_backup, &[])?;
+            }
+            manifest.record(path, previous, patterns)?;
+        }
+        Ok(update)
+    }
+
+    /// Write the `conftest.py` putting a Python source file's import root on `sys.path` when its
+    /// layout needs one, as in a `src/` layout. A conftest.py already there is left alone
+    fn write_conftest(&self, manifest: &mut GenerationManifest, source_file: &Path) -> Result<Option<PathBuf>> {
+        let Some((path, content)) = python_module(source_file).and_then(|module| module.conftest()) else {
+            return Ok(None);
+        };
+        if path.exists() {
+            return Ok(None);
+        }
+        self.write(manifest, &path, &content, OverwritePolicy::Skip, &[])?;
+        Ok(Some(path))
+    }
+}
+
+/// How large a source file `dir` and `git-repo` read whole
+#[derive(Args, Clone, Copy, Debug)]
+struct FileSizeArgs {
+    /// Largest source file to read whole, in bytes or with a K, M or G suffix
+    #[arg(long, value_parser = parse_file_size, default_value_t = DEFAULT_MAX_FILE_SIZE)]
+    max_file_size: u64,
+    /// What to do with larger files, usually generated or minified: skip, or sample (analyze
+    /// the whole lines within the limit)
+    #[arg(long, default_value = "skip")]
+    oversized: OversizedFiles,
+}
+
+impl FileSizeArgs {
+    /// A source file's content under the size limit, `None` when it's skipped
+    async fn read(&self, path: &Path, relative_path: &str) -> Option<Result<String>> {
+        match read_source(path, self.max_file_size, self.oversized).await {
+            Ok(SourceRead::Skipped { size }) => {
+                warn!("  ⏭️  Skipping {} ({} bytes, over --max-file-size)", relative_path, size);
+                None
+            }
+            Ok(SourceRead::Sampled { content, size }) => {
+                warn!("  ✂️  Sampling the first {} of {} bytes in {}", content.len(), size, relative_path);
+                Some(Ok(content))
+            }
+            Ok(SourceRead::Full(content)) => Some(Ok(content)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Tests a `dir` or `git-repo` write added: the whole suite unless merged into an existing file
+fn written_tests(update: TestFileUpdate, test_suite: &unified_test_framework::TestSuite) -> usize {
+    match update {
+        TestFileUpdate::Created(_) | TestFileUpdate::Overwritten(_) => test_suite.test_cases.len(),
+        update => update.tests_written(),
+    }
+}
+
+/// Count a written test file as processed, or as skipped when it was left as it was
+fn count_update(checkpoint: &mut RunCheckpoint, update: TestFileUpdate) {
+    match update {
+        TestFileUpdate::UpToDate | TestFileUpdate::Skipped => checkpoint.skipped += 1,
+        _ => checkpoint.processed += 1,
+    }
+}
+
+/// Point at the manifest of a run that wrote files, and at how to undo them
+fn note_manifest(manifest: &GenerationManifest, root: &Path) {
+    if !manifest.files.is_empty() {
+        info!("📝 {} files recorded in {}; `uft rollback` undoes them", manifest.files.len(), GenerationManifest::path(root).display());
+    }
+}
+
+/// One line on what writing a generated file did, for the commands that print a line per file
+fn print_file_update(path: &Path, update: TestFileUpdate) {
+    match update {
+        TestFileUpdate::Created(_) => println!("  ✅ {}", path.display()),
+        TestFileUpdate::Overwritten(_) => println!("  ♻️  Overwrote {}", path.display()),
+        TestFileUpdate::Merged(count) => println!("  ➕ {} new tests merged into {}", count, path.display()),
+        TestFileUpdate::UpToDate => println!("  ✔️  Up to date: {}", path.display()),
+        TestFileUpdate::Skipped => println!("  ⏭️  Already exists: {}", path.display()),
+    }
+}
+
+/// `println!` for results, which are logged instead when stdout carries JSON
+macro_rules! say {
+    ($format:expr, $($arg:tt)*) => {
+        if $format == OutputFormat::Json {
+            info!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Whether the command line asks for JSON output, checked before parsing so the banner
+/// stays off stdout
+fn wants_json(args: &[String]) -> bool {
+    args.windows(2).any(|pair| pair[0] == "--format" && pair[1] == "json") || args.iter().any(|arg| arg == "--format=json")
+}
+
+/// Whether the command line has `generate` print the test file, or read stdin, which implies it
+fn wants_stdout(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--stdout" || arg == "-")
+}
+
+/// Whether the command line asks for results and errors only
+fn wants_quiet(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--quiet" || arg == "-q")
+}
+
+/// Log progress and diagnostics to stderr, leaving stdout to results: plain messages by
+/// default, with levels, targets and the per-file and per-adapter spans once verbose
+fn init_logging(verbose: u8, quiet: bool) {
+    let level = match (quiet, verbose) {
+        (true, _) => Level::ERROR,
+        (false, 0) => Level::INFO,
+        (false, 1) => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(|| BarAwareStderr)
+        .without_time()
+        .with_target(verbose > 0)
+        .with_level(verbose > 0)
+        .init();
+}
+
+/// The progress bar on screen, if any, which log lines are written around
+static ACTIVE_BAR: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+/// Stderr that hides the progress bar while a log line is written, so the two don't garble
+struct BarAwareStderr;
+
+impl Write for BarAwareStderr {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match ACTIVE_BAR.lock().ok().as_deref().and_then(Option::as_ref) {
+            Some(bar) => bar.suspend(|| io::stderr().write(buf)),
+            None => io::stderr().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+/// Files processed for one language and the time spent on them
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct LanguageTiming {
+    files: usize,
+    elapsed: Duration,
+}
+
+/// Progress through a directory run: a bar with ETA and rate on a terminal, a log line per
+/// file otherwise, and the time taken per language
+struct GenerationProgress {
+    bar: Option<ProgressBar>,
+    total: usize,
+    done: usize,
+    started: Instant,
+    timings: BTreeMap<String, LanguageTiming>,
+}
+
+impl GenerationProgress {
+    /// Shows a bar only when stderr is a terminal and progress is being logged at all
+    fn new(total: usize) -> Self {
+        Self::with_bar(total, io::stderr().is_terminal() && LevelFilter::current() >= LevelFilter::INFO)
+    }
+
+    fn with_bar(total: usize, show_bar: bool) -> Self {
+        let bar = show_bar.then(|| {
+            let bar = ProgressBar::new(total as u64);
+            bar.set_style(
+                ProgressStyle::with_template("{spinner:.green} [{elapsed_precise}] {bar:30.cyan/blue} {pos}/{len} · {per_sec} · ETA {eta} {wide_msg}")
+                    .unwrap_or_else(|_| ProgressStyle::default_bar())
+                    .progress_chars("█▉▊▋▌▍▎▏ "),
+            );
+            bar.enable_steady_tick(Duration::from_millis(120));
+            if let Ok(mut active) = ACTIVE_BAR.lock() {
+                *active = Some(bar.clone());
+            }
+            bar
+        });
+        Self { bar, total, done: 0, started: Instant::now(), timings: BTreeMap::new() }
+    }
+
+    fn start_file(&self, relative_path: &str) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(relative_path.to_string());
+        }
+    }
+
+    fn finish_file(&mut self, relative_path: &str, language: &str, elapsed: Duration) {
+        let timing = self.timings.entry(language.to_string()).or_default();
+        timing.files += 1;
+        timing.elapsed += elapsed;
+        self.done += 1;
+        match &self.bar {
+            Some(bar) => bar.inc(1),
+            None => info!("[{}/{}] {} ({:.2?})", self.done, self.total, relative_path, elapsed),
+        }
+    }
+
+    /// Clear the bar, leaving the screen to the summary
+    fn finish(&mut self) {
+        if let Some(bar) = self.bar.take() {
+            bar.finish_and_clear();
+            if let Ok(mut active) = ACTIVE_BAR.lock() {
+                *active = None;
+            }
+        }
+    }
+
+    /// One line per language, slowest first, then the overall rate
+    fn timing_lines(&self, total_elapsed: Duration) -> Vec<String> {
+        let mut languages: Vec<_> = self.timings.iter().collect();
+        languages.sort_by(|a, b| b.1.elapsed.cmp(&a.1.elapsed));
+        let mut lines: Vec<String> = languages.into_iter()
+            .map(|(language, timing)| format!(
+                "{}: {} files in {:.2?} ({:.2?}/file)",
+                language, timing.files, timing.elapsed, timing.elapsed / timing.files.max(1) as u32
+            ))
+            .collect();
+        let rate = self.done as f64 / total_elapsed.as_secs_f64().max(f64::EPSILON);
+        lines.push(format!("Total: {} files in {:.2?} ({:.1} files/sec)", self.done, total_elapsed, rate));
+        lines
+    }
+
+    fn timings_json(&self) -> serde_json::Value {
+        self.timings.iter()
+            .map(|(language, timing)| (language.clone(), serde_json::json!({ "files": timing.files, "elapsed_ms": timing.elapsed.as_millis() as u64 })))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
+
+impl Drop for GenerationProgress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Display ASCII art banner for branding
-    AsciiArt::display_banner_colored();
+async fn main() -> ExitCode {
+    // Display ASCII art banner for branding, except when stdout carries the language server
+    // protocol, JSON, a test file or template, or only results are wanted
+    let args: Vec<String> = std::env::args().collect();
+    let subcommand = args.iter().skip(1).find(|arg| !arg.starts_with('-'));
+    if !matches!(subcommand.map(String::as_str), Some("server" | "templates")) && !wants_json(&args) && !wants_quiet(&args) && !wants_stdout(&args) {
+        AsciiArt::display_banner_colored();
+    }
     
     let cli = Cli::parse();
+    init_logging(cli.verbose, cli.quiet);
 
-    match cli.command {
-        Commands::Generate { path, output, config_dir } => {
+    match run(cli.command).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(UftError::exit_code_of(&e))
+        }
+    }
+}
+
+/// Run a subcommand. Failures scripts can branch on carry a [`UftError`], which picks the
+/// exit code
+async fn run(command: Commands) -> Result<()> {
+    match command {
+        Commands::Generate { path: input, lang, stdout, output, config_dir, enforce_coverage, with_integration, style, async_runtime, black_box, placement, execute, coverage, symbols, framework, min_confidence, test_type, format, overwrite } => {
+            let from_stdin = input == "-";
+            let stdout = stdout || from_stdin;
+            if test_type == TestType::E2e {
+                if format == OutputFormat::Json || stdout {
+                    return Err(UftError::Config("--format json, --stdout and stdin input aren't supported with --type e2e".to_string()).into());
+                }
+                return write_e2e_specs(Path::new(&input), &overwrite);
+            }
+            if stdout && format == OutputFormat::Json {
+                return Err(UftError::Config("--stdout prints the test file, so it can't be combined with --format json".to_string()).into());
+            }
+            // With the test file on stdout, results are logged as they are when it carries JSON
+            let results = if stdout { OutputFormat::Json } else { format };
             // Load languages dynamically
             let mut loader = LanguageLoader::new(config_dir.clone());
             let adapters = loader.load_all_languages()?;
             
+            // The name the source is analyzed under, whose extension picks the adapter
+            let path = match (&lang, from_stdin) {
+                (Some(lang), _) => {
+                    let extension = language_extension(&loader, lang)?;
+                    let stem = if from_stdin { Path::new("stdin") } else { Path::new(&input) };
+                    stem.with_extension(extension).to_string_lossy().to_string()
+                }
+                (None, true) => return Err(UftError::Config("Reading stdin needs --lang, e.g. --lang python".to_string()).into()),
+                (None, false) => input.clone(),
+            };
+            
+            let mut project_config = ProjectConfig::discover(Path::new(if from_stdin { "." } else { &input }))?;
+            if project_config.is_ignored(&path) {
+                info!("⏭️  {} matches an ignore pattern in the project config", path);
+                if format == OutputFormat::Json {
+                    print_json(&serde_json::json!({ "source_file": path, "test_file": null, "suite": null }))?;
+                }
+                return Ok(());
+            }
             let mut orchestrator = TestOrchestrator::new();
             for (lang, adapter) in adapters {
-                orchestrator.register_adapter(lang, adapter);
+                let config = AdapterConfig { style, async_runtime, black_box, placement, ..project_config.adapter_config(&lang) };
+                orchestrator.register_adapter_with_config(lang, adapter, config);
             }
-            println!("Generating tests for: {path}");
+            if let Some(framework) = framework {
+                project_config.frameworks.insert(orchestrator.detect_language(&path)?, framework);
+            }
+            if min_confidence.is_some() {
+                project_config.confidence_threshold = min_confidence;
+            }
+            orchestrator.set_project_config(project_config);
+            if let Some(report) = &coverage {
+                orchestrator.set_coverage_report(CoverageReport::load(Path::new(report))?);
+            }
+            info!("Generating tests for: {}", if from_stdin { "stdin" } else { &input });
             
-            let content = fs::read_to_string(&path)?;
-            let test_suite = orchestrator.generate_tests_for_file(&path, &content).await?;
+            let content = if from_stdin { io::read_to_string(io::stdin())? } else { fs::read_to_string(&input)? };
+            let (mut test_suite, patterns) = if with_integration {
+                (orchestrator.generate_combined_tests_for_file(&path, &content).await?, orchestrator.analyze_file(&path, &content).await?)
+            } else if !symbols.is_empty() {
+                let patterns = symbol_patterns(orchestrator.analyze_file(&path, &content).await?, &symbols);
+                if patterns.is_empty() {
+                    return Err(UftError::Generation(format!("No function or class named {} in {}", symbols.join(" or "), path)).into());
+                }
+                let subjects: Vec<String> = patterns.iter().map(|pattern| pattern.subject_name().to_string()).collect();
+                (orchestrator.generate_tests_for_subjects(&path, &content, &subjects).await?, patterns)
+            } else {
+                orchestrator.generate_tests_with_patterns(&path, &content).await?
+            };
+            
+            if coverage.is_some() && test_suite.test_cases.is_empty() {
+                info!("✅ All detected functions in {} are already covered", input);
+                if format == OutputFormat::Json {
+                    print_json(&serde_json::json!({ "source_file": path, "test_file": null, "suite": test_suite }))?;
+                }
+                return Ok(());
+            }
+            
+            info!("Generated {} test cases", test_suite.test_cases.len());
+            
+            if execute {
+                match Executor::new(&test_suite.language, Path::new(&path)) {
+                    Some(executor) => match executor.bake(&mut test_suite, &content).await {
+                        Ok(baked) => info!("Asserted observed outputs in {} test cases", baked),
+                        Err(e) => warn!("⚠️  Could not execute {}: {}", path, e),
+                    },
+                    None => warn!("⚠️  --execute is not supported for {}", test_suite.language),
+                }
+            }
             
-            println!("Generated {} test cases", test_suite.test_cases.len());
+            // Generate test file content based on language
+            let test_content = orchestrator.render_test_file(&mut test_suite, &patterns, &path, &content)?;
             
+            if stdout {
+                io::stdout().write_all(test_content.as_bytes())?;
+            } else {
             // Determine the proper test file path based on language conventions
             let source_path = Path::new(&path);
             let current_dir = std::env::current_dir()?;
-            let output_file = get_test_file_path(&current_dir, source_path, &test_suite.language, &test_suite.framework)?;
+            let output_file = orchestrator.test_file_path(&current_dir, source_path, &test_suite.language);
             
-            // Create output directory
-            if let Some(parent) = output_file.parent() {
-                fs::create_dir_all(parent)?;
+            let mut manifest = GenerationManifest::new(&current_dir, "generate");
+            let patterns = pattern_ids(&path, patterns.iter().map(|pattern| pattern.subject_name()));
+            let existing_tests = fs::read_to_string(&output_file).map(|tests| test_names(&tests)).unwrap_or_default();
+            let default_policy = if symbols.is_empty() { OverwritePolicy::Force } else { OverwritePolicy::Merge };
+            let update = overwrite.write_tests(&mut manifest, &output_file, source_path, &test_content, default_policy, &patterns)?;
+            let conftest = overwrite.write_conftest(&mut manifest, source_path)?;
+            note_manifest(&manifest, &current_dir);
+            match update {
+                TestFileUpdate::Merged(count) => say!(format, "{} new tests merged into: {}", count, output_file.display()),
+                TestFileUpdate::UpToDate => say!(format, "Tests up to date: {}", output_file.display()),
+                TestFileUpdate::Skipped => say!(format, "Tests already exist, left as they are: {} (--force or --merge to update)", output_file.display()),
+                _ => say!(format, "Tests written to: {}", output_file.display()),
+            }
+            if let Some(conftest) = conftest {
+                say!(format, "Import path set up in: {}", conftest.display());
+            }
+            if format == OutputFormat::Json {
+                let new_tests: Vec<_> = test_locations(&fs::read_to_string(&output_file).unwrap_or_default()).into_iter()
+                    .filter(|(name, _)| !existing_tests.contains(name))
+                    .map(|(name, line)| serde_json::json!({ "name": name, "line": line }))
+                    .collect();
+                print_json(&serde_json::json!({ "source_file": path, "test_file": output_file, "status": update.status(), "new_tests": new_tests, "suite": test_suite }))?;
+            }
             }
             
-            // Generate test file content based on language
-            let test_content = generate_test_file_content(&test_suite)?;
-            
-            fs::write(&output_file, test_content)?;
-            println!("Tests written to: {}", output_file.display());
+            if enforce_coverage {
+                let patterns = orchestrator.analyze_file(&path, &content).await?;
+                let estimated = test_suite.estimated_coverage(&patterns);
+                say!(results, "Estimated coverage: {:.1}% (target: {:.1}%)", estimated, test_suite.coverage_target);
+                
+                if estimated < test_suite.coverage_target {
+                    return Err(UftError::Generation(format!(
+                        "Detected patterns can't meet the coverage target for {}: {:.1}% < {:.1}%",
+                        path, estimated, test_suite.coverage_target
+                    )).into());
+                }
+            }
         }
-        Commands::IntegrationTest { path, output, config_dir } => {
+        Commands::IntegrationTest { path, output, config_dir, language, containers, overwrite } => {
             // Load languages dynamically
             let mut loader = LanguageLoader::new(config_dir.clone());
             let adapters = loader.load_all_languages()?;
@@ -156,56 +991,276 @@ async fn main() -> Result<()> {
             println!("Generating integration tests for: {path}");
             
             let content = fs::read_to_string(&path)?;
-            let language = orchestrator.detect_language(&path)?;
+            let recorded = recorded_requests(&content).is_some();
+            let language = match language {
+                Some(language) => language,
+                None if path.ends_with(".proto") || recorded => {
+                    return Err(anyhow::anyhow!("Pass --language (javascript, python or rust) to generate tests for {}", path));
+                }
+                None => orchestrator.detect_language(&path)?,
+            };
             
             // Check if the adapter supports integration tests
-            if language == "javascript" {
-                let js_adapter = unified_test_framework::JavaScriptAdapter::new();
-                let patterns = js_adapter.analyze_integration_patterns(&content, &path).await?;
-                
-                if patterns.is_empty() {
-                    println!("No integration patterns found in the file");
+            let config = AdapterConfig { containers, ..AdapterConfig::for_language(&language) };
+            let adapter: Box<dyn TestGenerator + Send + Sync> = match language.as_str() {
+                "javascript" => Box::new(unified_test_framework::JavaScriptAdapter::with_config(config)),
+                "python" => Box::new(unified_test_framework::PythonAdapter::with_config(config)),
+                "rust" => Box::new(unified_test_framework::RustAdapter::with_config(config)),
+                "go" => Box::new(unified_test_framework::GoAdapter::with_config(config)),
+                "java" => Box::new(unified_test_framework::JavaAdapter::with_config(config)),
+                _ => {
+                    println!("Integration test generation not yet supported for language: {}", language);
+                    println!("Currently supported: JavaScript, Python, Rust, Go, Java");
                     return Ok(());
                 }
-                
-                println!("Found {} integration patterns", patterns.len());
-                for pattern in &patterns {
-                    println!("- {:?} (confidence: {:.2})", pattern.pattern_type, pattern.confidence);
-                }
-                
-                let test_suite = js_adapter.generate_integration_tests(patterns).await?;
-                println!("Generated {} integration test cases", test_suite.test_cases.len());
-                
-                // Create output directory
-                let output_path = Path::new(&output);
-                fs::create_dir_all(output_path)?;
-                
-                // Generate integration test file
-                let source_path = Path::new(&path);
-                let file_stem = source_path.file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("test");
-                
-                let integration_test_file = output_path.join(format!("{}.integration.test.js", file_stem));
-                let test_content = generate_integration_test_content(&test_suite)?;
-                
-                fs::write(&integration_test_file, test_content)?;
-                
-                println!("Integration tests written to: {}", integration_test_file.display());
-                println!("\nSetup requirements:");
-                for req in &test_suite.setup_requirements {
-                    println!("  - {}", req);
+            };
+            let Some(integration) = adapter.as_integration() else {
+                return Ok(());
+            };
+            
+            let patterns = integration.analyze_integration_patterns(&content, &path).await?;
+            
+            if patterns.is_empty() {
+                println!("No integration patterns found in the file");
+                return Ok(());
+            }
+            
+            println!("Found {} integration patterns", patterns.len());
+            for pattern in &patterns {
+                println!("- {:?} (confidence: {:.2})", pattern.pattern_type, pattern.confidence);
+            }
+            
+            let services = integration.test_services(&patterns);
+            let pattern_ids = pattern_ids(&path, patterns.iter().map(|pattern| pattern.subject_name()));
+            let test_suite = integration.generate_integration_tests(patterns).await?;
+            println!("Generated {} integration test cases", test_suite.test_cases.len());
+            
+            // Create output directory
+            let output_path = Path::new(&output);
+            fs::create_dir_all(output_path)?;
+            
+            // Generate integration test file
+            let source_path = Path::new(&path);
+            let file_stem = source_path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("test");
+            // users.postman_collection.json -> users, shop.har -> shop
+            let file_stem = if recorded { file_stem.split('.').next().unwrap_or(file_stem) } else { file_stem };
+            
+            let integration_test_file = output_path.join(integration_test_file_name(&language, file_stem));
+            let test_content = generate_integration_test_content(&test_suite)?;
+            
+            let mut manifest = GenerationManifest::new(Path::new("."), "integration-test");
+            match overwrite.write(&mut manifest, &integration_test_file, &test_content, OverwritePolicy::Force, &pattern_ids)? {
+                TestFileUpdate::Created(_) | TestFileUpdate::Overwritten(_) => println!("Integration tests written to: {}", integration_test_file.display()),
+                update => print_file_update(&integration_test_file, update),
+            }
+            match containers {
+                Some(_) if services.is_empty() => println!("No databases or brokers detected; --containers has nothing to start"),
+                Some(ContainerSetup::Compose) => {
+                    let services: Vec<TestService> = services.iter().map(|(service, _)| *service).collect();
+                    let compose_path = output_path.join(COMPOSE_FILE);
+                    // There are no tests in it to merge, so --merge leaves it alone too
+                    match overwrite.write(&mut manifest, &compose_path, &compose_file(&services), OverwritePolicy::Force, &pattern_ids)? {
+                        TestFileUpdate::Created(_) | TestFileUpdate::Overwritten(_) => println!("Test services written to: {}", compose_path.display()),
+                        update => print_file_update(&compose_path, update),
+                    }
                 }
-                println!("\nCleanup requirements:");
-                for req in &test_suite.cleanup_requirements {
-                    println!("  - {}", req);
+                _ => {}
+            }
+            note_manifest(&manifest, Path::new("."));
+            println!("\nSetup requirements:");
+            for req in &test_suite.setup_requirements {
+                println!("  - {}", req);
+            }
+            println!("\nCleanup requirements:");
+            for req in &test_suite.cleanup_requirements {
+                println!("  - {}", req);
+            }
+        }
+        Commands::Fuzz { path, overwrite } => {
+            let source_path = Path::new(&path);
+            let language = harness_language(source_path)
+                .ok_or_else(|| UftError::UnsupportedLanguage(format!("Fuzz target generation not supported for: {}", path)))?;
+            let toolchain = FuzzGenerator::toolchain(language).unwrap_or_default();
+            
+            println!("🐛 Generating fuzz targets ({}) for: {}", toolchain, path);
+            
+            let content = fs::read_to_string(&path)?;
+            let harness_files = FuzzGenerator::generate(language, source_path, &content)?;
+            
+            if harness_files.is_empty() {
+                println!("No functions taking a single string or byte input found");
+                return Ok(());
+            }
+            
+            let mut manifest = GenerationManifest::new(Path::new("."), "fuzz");
+            for harness_file in &harness_files {
+                // A fuzz crate that's already there gets the new targets added to its manifest
+                let merged = fs::read_to_string(&harness_file.path).ok()
+                    .filter(|_| harness_file.path.ends_with("fuzz/Cargo.toml") && overwrite.policy(OverwritePolicy::Merge) == OverwritePolicy::Merge)
+                    .and_then(|existing| FuzzGenerator::merge_manifest(&existing, &harness_file.content));
+                match merged {
+                    Some((content, added)) => {
+                        overwrite.write_with(&mut manifest, &harness_file.path, &content, &[], |path, content, backup| write_generated_file(path, content, OverwritePolicy::Force, backup))?;
+                        println!("  ➕ {} new fuzz targets added to {}", added, harness_file.path.display());
+                    }
+                    None => {
+                        let update = overwrite.write(&mut manifest, &harness_file.path, &harness_file.content, OverwritePolicy::Skip, &[])?;
+                        print_file_update(&harness_file.path, update);
+                    }
                 }
-            } else {
-                println!("Integration test generation not yet supported for language: {}", language);
-                println!("Currently supported: JavaScript");
+            }
+            note_manifest(&manifest, Path::new("."));
+            
+            println!("\n💡 Run with:");
+            match language {
+                "rust" => println!("   cargo fuzz run <target>"),
+                "go" => println!("   go test -fuzz=Fuzz<Name>"),
+                "python" => println!("   python fuzz_<name>.py"),
+                _ => println!("   clang -g -fsanitize=fuzzer,address <name>_fuzzer.c {} -o fuzzer && ./fuzzer", path),
+            }
+        }
+        Commands::Bench { path, config_dir, overwrite } => {
+            let mut loader = LanguageLoader::new(config_dir.clone());
+            let adapters = loader.load_all_languages()?;
+            
+            let mut orchestrator = TestOrchestrator::new();
+            for (lang, adapter) in adapters {
+                orchestrator.register_adapter(lang, adapter);
+            }
+            
+            let source_path = Path::new(&path);
+            let language = orchestrator.detect_language(&path)?;
+            let toolchain = BenchGenerator::toolchain(&language, source_path)
+                .ok_or_else(|| UftError::UnsupportedLanguage(format!("Benchmark generation not supported for: {}", path)))?;
+            
+            println!("⏱️  Generating benchmarks ({}) for: {}", toolchain, path);
+            
+            let content = fs::read_to_string(&path)?;
+            let functions: Vec<_> = orchestrator.analyze_file(&path, &content).await?
+                .into_iter()
+                .filter_map(|pattern| match pattern.pattern_type {
+                    PatternType::Function(func) => Some(func),
+                    _ => None,
+                })
+                .collect();
+            let bench_files = BenchGenerator::generate(&language, source_path, &content, &functions)?;
+            
+            if bench_files.is_empty() {
+                println!("No benchmarkable functions found");
+                return Ok(());
+            }
+            
+            let mut manifest = GenerationManifest::new(Path::new("."), "bench");
+            let patterns = pattern_ids(&path, functions.iter().map(|func| func.name.as_str()));
+            for bench_file in &bench_files {
+                let update = overwrite.write(&mut manifest, &bench_file.path, &bench_file.content, OverwritePolicy::Skip, &patterns)?;
+                print_file_update(&bench_file.path, update);
+            }
+            note_manifest(&manifest, Path::new("."));
+            
+            println!("\n💡 Run with:");
+            match toolchain {
+                "criterion" => {
+                    let name = bench_files[0].path.file_stem().and_then(|s| s.to_str()).unwrap_or("bench");
+                    println!("   Add to Cargo.toml:");
+                    println!("     [dev-dependencies]\n     criterion = \"0.5\"\n\n     [[bench]]\n     name = \"{}\"\n     harness = false", name);
+                    println!("   cargo bench");
+                }
+                "pytest-benchmark" => println!("   pytest {} --benchmark-only", bench_files[0].path.display()),
+                "Deno.bench" => println!("   deno bench {}", bench_files[0].path.display()),
+                _ => println!("   npx vitest bench"),
+            }
+        }
+        Commands::Contract { path, consumer, provider, config_dir, overwrite } => {
+            let mut loader = LanguageLoader::new(config_dir.clone());
+            let adapters = loader.load_all_languages()?;
+            
+            let mut orchestrator = TestOrchestrator::new();
+            for (lang, adapter) in adapters {
+                orchestrator.register_adapter(lang, adapter);
+            }
+            
+            let source_path = Path::new(&path);
+            let language = orchestrator.detect_language(&path)?;
+            let toolchain = PactGenerator::toolchain(&language)
+                .ok_or_else(|| UftError::UnsupportedLanguage(format!("Contract test generation not supported for: {}", path)))?;
+            let adapter: Box<dyn TestGenerator + Send + Sync> = match language.as_str() {
+                "javascript" => Box::new(unified_test_framework::JavaScriptAdapter::new()),
+                "python" => Box::new(unified_test_framework::PythonAdapter::new()),
+                _ => Box::new(unified_test_framework::RustAdapter::new()),
+            };
+            let integration = adapter.as_integration()
+                .ok_or_else(|| UftError::UnsupportedLanguage(format!("No integration analysis for: {}", language)))?;
+            
+            println!("🤝 Generating contract tests ({}) for: {}", toolchain, path);
+            
+            let content = fs::read_to_string(&path)?;
+            let apis: Vec<_> = integration.analyze_integration_patterns(&content, &path).await?
+                .into_iter()
+                .filter_map(|pattern| match pattern.pattern_type {
+                    PatternType::ApiIntegration(api) => Some(api),
+                    _ => None,
+                })
+                .collect();
+            let consumer = consumer.unwrap_or_else(|| {
+                source_path.file_stem().and_then(|s| s.to_str()).unwrap_or("consumer").to_string()
+            });
+            let provider = provider
+                .or_else(|| PactGenerator::provider_name(&apis))
+                .unwrap_or_else(|| "provider".to_string());
+            let pact_files = PactGenerator::generate(&language, source_path, &consumer, &provider, &apis)?;
+            
+            if pact_files.is_empty() {
+                println!("No API calls found");
+                return Ok(());
+            }
+            
+            let mut manifest = GenerationManifest::new(Path::new("."), "contract");
+            let patterns = pattern_ids(&path, apis.iter().map(|api| api.endpoint.as_str()));
+            for pact_file in &pact_files {
+                let update = overwrite.write(&mut manifest, &pact_file.path, &pact_file.content, OverwritePolicy::Skip, &patterns)?;
+                print_file_update(&pact_file.path, update);
+            }
+            note_manifest(&manifest, Path::new("."));
+            
+            println!("\n💡 Running the tests writes the {} -> {} pact for publishing to a Pact Broker:", consumer, provider);
+            match toolchain {
+                "pact-js" => println!("   npm install --save-dev @pact-foundation/pact && npx jest {}", pact_files[0].path.display()),
+                "pact-python" => println!("   pip install pact-python && pytest {}", pact_files[0].path.display()),
+                _ => println!("   Add pact_consumer, reqwest, serde_json and tokio to [dev-dependencies], then cargo test --test {}", pact_files[0].path.file_stem().and_then(|s| s.to_str()).unwrap_or_default()),
+            }
+        }
+        Commands::ApiTests { spec, language, output, overwrite } => {
+            let toolchain = ApiTestGenerator::toolchain(&language)
+                .ok_or_else(|| UftError::UnsupportedLanguage(format!("API test generation not supported for language: {} (use python, javascript or rust)", language)))?;
+            let document = OpenApiSpec::load(Path::new(&spec))?;
+            
+            println!("🌐 Generating API tests ({}) for {} operations in: {}", toolchain, document.operations.len(), spec);
+            
+            let test_files = ApiTestGenerator::generate(&language, Path::new(&spec), Path::new(&output), &document)?;
+            if test_files.is_empty() {
+                println!("No operations found");
+                return Ok(());
+            }
+            
+            let mut manifest = GenerationManifest::new(Path::new("."), "api-tests");
+            let patterns = pattern_ids(&spec, document.operations.iter().map(|operation| operation.endpoint.path.as_str()));
+            for test_file in &test_files {
+                let update = overwrite.write(&mut manifest, &test_file.path, &test_file.content, OverwritePolicy::Skip, &patterns)?;
+                print_file_update(&test_file.path, update);
+            }
+            note_manifest(&manifest, Path::new("."));
+            
+            println!("\n💡 Point API_BASE_URL at a running server (and set API_TOKEN if it needs one):");
+            match language.as_str() {
+                "python" => println!("   pip install requests jsonschema && pytest {}", test_files[0].path.display()),
+                "javascript" => println!("   npm install --save-dev jest ajv && npx jest {}", test_files[0].path.display()),
+                _ => println!("   Move the file into tests/, add reqwest (json feature), serde_json, jsonschema and tokio to [dev-dependencies], then cargo test --test {}", test_files[0].path.file_stem().and_then(|s| s.to_str()).unwrap_or_default()),
             }
         }
-        Commands::Analyze { path, config_dir } => {
+        Commands::Analyze { path, config_dir, format } => {
             // Load languages dynamically
             let mut loader = LanguageLoader::new(config_dir.clone());
             let adapters = loader.load_all_languages()?;
@@ -215,10 +1270,16 @@ async fn main() -> Result<()> {
                 orchestrator.register_adapter(lang, adapter);
             }
             
-            println!("Analyzing patterns in: {path}");
+            info!("Analyzing patterns in: {path}");
             
             let content = fs::read_to_string(&path)?;
-            let patterns = orchestrator.analyze_file(&path, &content).await?;
+            let mut report = orchestrator.analysis_report(&path, &content).await?;
+            let test_file = orchestrator.test_file_path(&std::env::current_dir()?, Path::new(&path), &report.language);
+            report.check_tests(test_file.clone(), &read_existing_tests(&test_file, Path::new(&path)));
+            if format == OutputFormat::Json {
+                return print_json(&report);
+            }
+            let patterns = report.patterns;
             
             println!("Found {} patterns:", patterns.len());
             for pattern in patterns {
@@ -241,14 +1302,21 @@ async fn main() -> Result<()> {
             
             println!("Plugin built successfully in: {output}");
         }
-        Commands::Languages { config_dir } => {
+        Commands::Languages { config_dir, format } => {
             // Load languages dynamically
             let mut loader = LanguageLoader::new(config_dir.clone());
             loader.load_all_languages()?;
             
             let builtin_languages = loader.list_builtin_languages();
-            let dynamic_languages = loader.list_dynamic_languages();
-            let extensions = loader.get_supported_extensions();
+            let mut dynamic_languages = loader.list_dynamic_languages();
+            dynamic_languages.sort();
+            
+            if format == OutputFormat::Json {
+                let languages: Vec<_> = builtin_languages.iter().chain(&dynamic_languages)
+                    .map(|language| language_capabilities(&loader, language))
+                    .collect();
+                return print_json(&serde_json::json!({ "languages": languages }));
+            }
             
             // Collect language data for tabular display
             let mut language_data = Vec::new();
@@ -338,6 +1406,68 @@ async fn main() -> Result<()> {
                 total_languages, total_builtin, total_dynamic);
             println!("💡 Add new languages: Create JSON files in {}/", config_dir);
         }
+        Commands::ValidateConfig { path, format } => {
+            let results = validate_config_dir(Path::new(&path))?;
+            let invalid = results.iter().filter(|(_, issues)| !issues.is_empty()).count();
+            if format == OutputFormat::Json {
+                let files: Vec<_> = results.iter()
+                    .map(|(file, issues)| serde_json::json!({
+                        "file": file,
+                        "valid": issues.is_empty(),
+                        "issues": issues.iter().map(|issue| serde_json::json!({ "line": issue.line, "pointer": issue.pointer, "message": issue.message })).collect::<Vec<_>>(),
+                    }))
+                    .collect();
+                print_json(&serde_json::json!({ "files": files }))?;
+            } else {
+                for (file, issues) in &results {
+                    if issues.is_empty() {
+                        println!("✅ {}", file.display());
+                    }
+                    for issue in issues {
+                        println!("❌ {}:{}: {}", file.display(), issue.line, issue.message);
+                    }
+                }
+            }
+            if invalid > 0 {
+                return Err(UftError::Config(format!("{} of {} language configs are invalid", invalid, results.len())).into());
+            }
+            say!(format, "All {} language configs are valid", results.len());
+        }
+        Commands::Lang { action: LangAction::Add { pack, registry, dir } } => {
+            let (language, version) = parse_pack_spec(&pack);
+            let registry = registry.or_else(|| std::env::var("UFT_REGISTRY").ok()).unwrap_or_else(|| DEFAULT_REGISTRY.to_string());
+            let dir = dir.map(PathBuf::from).unwrap_or_else(user_language_dir);
+            let installed = LanguageRegistry::new(&registry)?.install(language, version, &dir).await?;
+            println!("✅ Installed {} {} into {}", language, installed.version, dir.display());
+            if installed.wasm_sha256.is_some() {
+                println!("   It runs a WebAssembly adapter, which needs uft built with the `wasm` feature");
+            }
+        }
+        Commands::Templates { action } => {
+            let engine = TemplateEngine::shared();
+            match action {
+                TemplatesAction::List { format: OutputFormat::Json } => print_json(&engine.get_available_templates())?,
+                TemplatesAction::List { format: OutputFormat::Text } => {
+                    for name in engine.get_available_templates() {
+                        println!("{}", name);
+                    }
+                }
+                TemplatesAction::Show { name } => print!("{}", engine.template_source(&name).map_err(|e| UftError::Config(e.to_string()))?),
+                TemplatesAction::Render { name, data } => {
+                    engine.template_source(&name).map_err(|e| UftError::Config(e.to_string()))?;
+                    let data = match data {
+                        Some(file) => {
+                            let content = fs::read_to_string(&file)
+                                .map_err(|e| UftError::Config(format!("Failed to read {}: {}", file, e)))?;
+                            serde_json::from_str(&content)
+                                .map_err(|e| UftError::Config(format!("Invalid template data in {}: {}", file, e)))?
+                        }
+                        None => engine.sample_data(&name),
+                    };
+                    print!("{}", engine.render_test(&name, &data)?);
+                }
+            }
+        }
         Commands::Install { skip_shell, force } => {
             println!("🚀 Installing Unified Test Framework...");
             
@@ -362,7 +1492,66 @@ async fn main() -> Result<()> {
             
             println!("\n✨ You can now run 'uft languages' from anywhere!");
         }
-        Commands::Dir { path, config_dir } => {
+        Commands::Uninstall { dry_run } => {
+            if dry_run {
+                println!("🔍 Dry run: nothing will be removed");
+            } else {
+                println!("🧹 Uninstalling Unified Test Framework...");
+            }
+            
+            uninstall_language_configs(dry_run)?;
+            remove_shell_integration(dry_run)?;
+            
+            if !dry_run {
+                println!("✅ Uninstalled. The uft binary itself is left where it was installed");
+            }
+        }
+        Commands::Watch { path, config_dir, debounce, include, exclude, overwrite } => {
+            let scope = PathScope::new(&include, &exclude)?;
+            watch_directory(Path::new(&path), &config_dir, Duration::from_millis(debounce), &scope, &overwrite).await?;
+        }
+        Commands::Init { path, config_dir, force } => {
+            let root = Path::new(&path);
+            let config_file = root.join(PROJECT_CONFIG_FILE);
+            if config_file.exists() && !force {
+                return Err(anyhow::anyhow!("{} already exists (use --force to overwrite)", config_file.display()));
+            }
+            let loader = LanguageLoader::new(config_dir);
+            let mut languages = detect_project_languages(root, &get_supported_extensions(&loader), &PathScope::default())?;
+            languages.sort();
+            fs::write(&config_file, scaffold_project_config(&languages))?;
+            println!("✅ Wrote {}", config_file.display());
+            if !languages.is_empty() {
+                println!("   Frameworks set for: {}", languages.join(", "));
+            }
+        }
+        Commands::Server { config_dir } => {
+            let project_config = ProjectConfig::discover(&std::env::current_dir()?)?;
+            let mut loader = LanguageLoader::new(config_dir.clone());
+            let mut orchestrator = TestOrchestrator::new();
+            for (lang, adapter) in loader.load_all_languages()? {
+                let config = project_config.adapter_config(&lang);
+                orchestrator.register_adapter_with_config(lang, adapter, config);
+            }
+            serve(orchestrator, config_dir).await;
+            // The runtime would otherwise wait on the blocked read of stdin before exiting
+            std::process::exit(0);
+        }
+        Commands::Daemon { jsonrpc: _, config_dir } => {
+            let root = std::env::current_dir()?;
+            let project_config = ProjectConfig::discover(&root)?;
+            let mut loader = LanguageLoader::new(config_dir);
+            let mut orchestrator = TestOrchestrator::new();
+            for (lang, adapter) in loader.load_all_languages()? {
+                let config = project_config.adapter_config(&lang);
+                orchestrator.register_adapter_with_config(lang, adapter, config);
+            }
+            orchestrator.set_project_config(project_config);
+            run_daemon(Daemon::new(orchestrator, root)).await?;
+            std::process::exit(0);
+        }
+        Commands::Dir { path, config_dir, style, async_runtime, black_box, placement, coverage, test_type, include, exclude, format, resume, overwrite, file_size } => {
+            let scope = PathScope::new(&include, &exclude)?;
             let target_dir = Path::new(&path);
             
             if !target_dir.exists() {
@@ -373,7 +1562,14 @@ async fn main() -> Result<()> {
                 return Err(anyhow::anyhow!("Path is not a directory: {}", path));
             }
             
-            println!("🔍 Scanning directory: {}", target_dir.display());
+            if test_type == TestType::E2e {
+                if format == OutputFormat::Json {
+                    return Err(UftError::Config("--format json isn't supported with --type e2e".to_string()).into());
+                }
+                return write_e2e_specs(target_dir, &overwrite);
+            }
+            
+            info!("🔍 Scanning directory: {}", target_dir.display());
             
             // Load language adapters
             let mut loader = LanguageLoader::new(config_dir.clone());
@@ -381,23 +1577,44 @@ async fn main() -> Result<()> {
             let supported_extensions = get_supported_extensions(&loader);
             
             // Detect project languages and let user choose frameworks
-            let project_languages = detect_project_languages(&target_dir, &supported_extensions)?;
-            println!("🔍 Detected languages: {:?}", project_languages);
+            let project_languages = detect_project_languages(target_dir, &supported_extensions, &scope)?;
+            info!("🔍 Detected languages: {:?}", project_languages);
             
-            let framework_choices = prompt_framework_choices(&project_languages)?;
+            let project_config = ProjectConfig::discover(target_dir)?;
+            let mut checkpoint = start_checkpoint(target_dir, resume)?;
+            let mut manifest = GenerationManifest::resume(target_dir, "dir", resume);
+            // There's no one to prompt when the output is for a program: configured frameworks
+            // or each language's default
+            let framework_choices = if !checkpoint.framework_choices.is_empty() {
+                checkpoint.framework_choices.clone()
+            } else if format == OutputFormat::Json {
+                project_languages.iter()
+                    .filter_map(|language| project_config.framework(language).map(|framework| (language.clone(), framework.to_string())))
+                    .collect()
+            } else {
+                prompt_framework_choices(&project_languages, &project_config)?
+            };
+            checkpoint.framework_choices = framework_choices.clone();
             
             let mut orchestrator = TestOrchestrator::new();
             for (lang, adapter) in adapters {
-                orchestrator.register_adapter(lang, adapter);
+                let config = AdapterConfig { style, async_runtime, black_box, placement, ..project_config.adapter_config(&lang) };
+                orchestrator.register_adapter_with_config(lang, adapter, config);
+            }
+            orchestrator.set_project_config(project_config);
+            if let Some(report) = &coverage {
+                orchestrator.set_coverage_report(CoverageReport::load(Path::new(report))?);
             }
             
             // Find all source files
-            let source_files = find_source_files_excluding_tests(&target_dir, &supported_extensions)?;
-            println!("📝 Found {} source files to test", source_files.len());
+            let mut source_files = find_source_files_excluding_tests(target_dir, &supported_extensions, &scope)?;
+            source_files.retain(|file| !orchestrator.project_config().is_ignored(&file.to_string_lossy()));
+            info!("📝 Found {} source files to test", source_files.len());
             
-            let mut total_tests = 0;
-            let mut processed_files = 0;
-            let mut skipped_files = 0;
+            let file_count = source_files.len();
+            skip_completed(&mut source_files, target_dir, &checkpoint);
+            let mut results = Vec::new();
+            let mut progress = GenerationProgress::new(source_files.len());
             
             // Process each file
             for file_path in source_files {
@@ -405,7 +1622,9 @@ async fn main() -> Result<()> {
                     .unwrap_or(&file_path)
                     .to_string_lossy();
                 
-                println!("🔍 Processing: {}", relative_path);
+                debug!("🔍 Processing: {}", relative_path);
+                progress.start_file(&relative_path);
+                let file_started = Instant::now();
                 
                 // Determine language and framework
                 let language = detect_file_language(&file_path, &supported_extensions, &loader)?;
@@ -414,89 +1633,324 @@ async fn main() -> Result<()> {
                 });
                 
                 // Check if test already exists
-                let test_file_path = get_test_file_path(&target_dir, &file_path, &language, &framework)?;
+                let test_file_path = orchestrator.test_file_path(target_dir, &file_path, &language);
                 
-                if test_file_path.exists() {
-                    println!("  ⏭️  Test already exists: {}", test_file_path.display());
-                    skipped_files += 1;
+                if overwrite.policy(OverwritePolicy::Skip) == OverwritePolicy::Skip && !read_existing_tests(&test_file_path, &file_path).is_empty() {
+                    debug!("  ⏭️  Test already exists: {}", test_file_path.display());
+                    results.push(serde_json::json!({ "source_file": file_path, "test_file": test_file_path, "status": "exists", "tests": 0 }));
+                    checkpoint.skipped += 1;
+                    progress.finish_file(&relative_path, &language, file_started.elapsed());
+                    checkpoint.complete(target_dir, &relative_path)?;
                     continue;
                 }
                 
-                match fs::read_to_string(&file_path) {
+                let Some(content) = file_size.read(&file_path, &relative_path).await else {
+                    results.push(serde_json::json!({ "source_file": file_path, "test_file": null, "status": "too_large", "tests": 0 }));
+                    checkpoint.skipped += 1;
+                    progress.finish_file(&relative_path, &language, file_started.elapsed());
+                    checkpoint.complete(target_dir, &relative_path)?;
+                    continue;
+                };
+                match content {
                     Ok(content) => {
-                        match orchestrator.generate_tests_for_file(
+                        match orchestrator.generate_tests_with_patterns(
                             &file_path.to_string_lossy(), 
                             &content
                         ).await {
-                            Ok(mut test_suite) => {
+                            Ok((mut test_suite, patterns)) => {
                                 if !test_suite.test_cases.is_empty() {
                                     // Update test suite with chosen framework
                                     test_suite.framework = framework.clone();
                                     
-                                    let test_content = generate_test_file_content_with_framework(&test_suite, &framework)?;
+                                    let test_content = orchestrator.project_config().apply_template(&language, &file_path.to_string_lossy(), &generate_test_file_content_with_framework(&test_suite, &framework, &patterns, &content)?)?;
+                                    let patterns = pattern_ids(&relative_path, patterns.iter().map(|pattern| pattern.subject_name()));
+                                    let update = overwrite.write_tests(&mut manifest, &test_file_path, &file_path, &test_content, OverwritePolicy::Skip, &patterns)?;
+                                    let tests = written_tests(update, &test_suite);
                                     
-                                    // Create test directory if needed
-                                    if let Some(parent) = test_file_path.parent() {
-                                        fs::create_dir_all(parent)?;
-                                    }
-                                    
-                                    fs::write(&test_file_path, test_content)?;
-                                    
-                                    println!("  ✅ Generated {} tests -> {}", 
-                                        test_suite.test_cases.len(), 
+                                    debug!("  ✅ {} {} tests -> {}", 
+                                        update.status(),
+                                        tests, 
                                         test_file_path.strip_prefix(&target_dir)
                                             .unwrap_or(&test_file_path)
                                             .display()
                                     );
+                                    if let Some(conftest) = overwrite.write_conftest(&mut manifest, &file_path)? {
+                                        debug!("  ✅ Import path set up in {}", conftest.strip_prefix(&target_dir).unwrap_or(&conftest).display());
+                                    }
                                     
-                                    total_tests += test_suite.test_cases.len();
-                                    processed_files += 1;
+                                    checkpoint.total_tests += tests;
+                                    results.push(serde_json::json!({ "source_file": file_path, "test_file": test_file_path, "status": update.status(), "tests": tests, "suite": test_suite }));
+                                    count_update(&mut checkpoint, update);
+                                } else if coverage.is_some() {
+                                    debug!("  ⏭️  Already covered");
+                                    results.push(serde_json::json!({ "source_file": file_path, "test_file": null, "status": "covered", "tests": 0 }));
+                                    checkpoint.skipped += 1;
                                 } else {
-                                    println!("  ⚠️  No testable patterns found");
+                                    warn!("  ⚠️  No testable patterns found");
+                                    results.push(serde_json::json!({ "source_file": file_path, "test_file": null, "status": "no_patterns", "tests": 0 }));
                                 }
                             }
-                            Err(e) => println!("  ❌ Error generating tests: {}", e),
+                            Err(e) => {
+                                error!("  ❌ Error generating tests: {}", e);
+                                checkpoint.failed += 1;
+                                results.push(serde_json::json!({ "source_file": file_path, "test_file": null, "status": "error", "tests": 0, "error": e.to_string() }));
+                            }
                         }
                     }
-                    Err(e) => println!("  ❌ Error reading file: {}", e),
+                    Err(e) => {
+                        error!("  ❌ Error reading file: {}", e);
+                        checkpoint.failed += 1;
+                        results.push(serde_json::json!({ "source_file": file_path, "test_file": null, "status": "error", "tests": 0, "error": e.to_string() }));
+                    }
                 }
+                progress.finish_file(&relative_path, &language, file_started.elapsed());
+                checkpoint.complete(target_dir, &relative_path)?;
+            }
+            progress.finish();
+            RunCheckpoint::clear(target_dir)?;
+            note_manifest(&manifest, target_dir);
+            
+            if format == OutputFormat::Json {
+                print_json(&serde_json::json!({
+                    "directory": target_dir,
+                    "files": results,
+                    "summary": { "processed": checkpoint.processed, "skipped": checkpoint.skipped, "failed": checkpoint.failed, "total_tests": checkpoint.total_tests, "elapsed_ms": progress.started.elapsed().as_millis() as u64, "timings": progress.timings_json() },
+                }))?;
+                return dir_outcome(checkpoint.failed, file_count);
             }
             
             println!("\n🎉 Test generation complete!");
             println!("📊 Summary:");
-            println!("   • Processed files: {}", processed_files);
-            println!("   • Skipped files (tests exist): {}", skipped_files);
-            println!("   • Total test cases: {}", total_tests);
+            println!("   • Processed files: {}", checkpoint.processed);
+            println!("   • Skipped files (tests exist): {}", checkpoint.skipped);
+            if checkpoint.failed > 0 {
+                println!("   • Failed files: {}", checkpoint.failed);
+            }
+            println!("   • Total test cases: {}", checkpoint.total_tests);
             println!("   • Directory: {}", target_dir.display());
+            println!("⏱️  Timing:");
+            for line in progress.timing_lines(progress.started.elapsed()) {
+                println!("   • {}", line);
+            }
             println!("\n💡 Next steps:");
             println!("   1. Review and implement test logic in generated files");
             println!("   2. Run tests with your project's test command");
+            dir_outcome(checkpoint.failed, file_count)?;
         }
-        Commands::GitRepo { url, config_dir, branch, in_repo } => {
-            println!("🔄 Cloning repository: {}", url);
+        Commands::Run { path, config_dir, list, format } => {
+            let project_root = Path::new(&path);
+            if !project_root.is_dir() {
+                return Err(anyhow::anyhow!("Path is not a directory: {}", path));
+            }
             
-            // Clone repository to working directory (not temp)
-            let repo_name = url.split('/').last().unwrap_or("repo").replace(".git", "");
-            let repo_dir = Path::new(&repo_name);
+            let mut loader = LanguageLoader::new(config_dir.clone());
+            loader.load_all_languages()?;
+            let project_config = ProjectConfig::discover(project_root)?;
+            let test_files = find_generated_test_files(project_root, &loader, &project_config)?;
+            if list {
+                let listed = list_test_files(project_root, &test_files)?;
+                if format == OutputFormat::Json {
+                    print_json(&serde_json::json!({ "root": project_root.canonicalize()?, "files": listed }))?;
+                } else {
+                    for file in &listed {
+                        println!("📄 {} ({}): {}", file.file.display(), file.runner, file.command.join(" "));
+                        for test in &file.tests {
+                            println!("   {}:{} {}", file.file.display(), test.line, test.name);
+                        }
+                    }
+                }
+                return Ok(());
+            }
+            if test_files.is_empty() {
+                say!(format, "No generated test files found under {} (generate some with `utf dir` first)", project_root.display());
+                if format == OutputFormat::Json {
+                    print_json(&serde_json::json!({ "runs": [], "passed": 0, "failed": 0 }))?;
+                }
+                return Ok(());
+            }
+            
+            let mut passed = 0;
+            let mut failed = 0;
+            let mut failed_runners = Vec::new();
+            let mut runs = Vec::new();
+            for (runner, files) in test_files {
+                info!("🧪 Running {} test file(s) with {}", files.len(), runner.name());
+                let run = runner.run(project_root, &files).await?;
+                if !run.success {
+                    say!(format, "{}", run.output.trim_end());
+                    failed_runners.push(runner.name());
+                }
+                say!(format, "  ✅ {} passed, ❌ {} failed", run.counts.passed, run.counts.failed);
+                passed += run.counts.passed;
+                failed += run.counts.failed;
+                runs.push(serde_json::json!({
+                    "runner": runner.name(),
+                    "command": run.command,
+                    "files": files,
+                    "passed": run.counts.passed,
+                    "failed": run.counts.failed,
+                    "success": run.success,
+                    "output": run.output,
+                }));
+            }
+            
+            say!(format, "\n📊 Summary: {} passed, {} failed", passed, failed);
+            if format == OutputFormat::Json {
+                print_json(&serde_json::json!({ "runs": runs, "passed": passed, "failed": failed }))?;
+            }
+            if !failed_runners.is_empty() {
+                return Err(anyhow::anyhow!("Generated tests failed under {}", failed_runners.join(", ")));
+            }
+        }
+        Commands::Report { path, config_dir, html, markdown, ci } => {
+            let target_dir = Path::new(&path);
+            let report = generation_report(target_dir, &config_dir).await?;
+            
+            fs::write(&html, report.to_html())?;
+            fs::write(&markdown, report.to_markdown())?;
+            println!("📊 {} patterns, {} tests generated, {} untested public functions", report.patterns(), report.tests_generated(), report.untested());
+            println!("📄 HTML report: {}", html);
+            println!("📄 Markdown report: {}", markdown);
+            if let Some(provider) = ci {
+                report_to_ci(provider, target_dir, &report)?;
+            }
+        }
+        Commands::Ci { path, config_dir, provider, artifacts } => {
+            let target_dir = Path::new(&path);
+            let report = generation_report(target_dir, &config_dir).await?;
+            
+            let artifacts = Path::new(&artifacts);
+            fs::create_dir_all(artifacts)?;
+            fs::write(artifacts.join("uft-report.html"), report.to_html())?;
+            fs::write(artifacts.join("uft-report.md"), report.to_markdown())?;
+            report_to_ci(provider, target_dir, &report)?;
+            println!("📊 {} patterns, {} tests generated, {} untested public functions", report.patterns(), report.tests_generated(), report.untested());
+            println!("📦 Reports written to {}/ for upload as artifacts", artifacts.display());
+        }
+        Commands::Rollback { path, force } => {
+            let root = Path::new(&path);
+            let manifest = GenerationManifest::load(root)?;
+            let rollback = manifest.rollback(force)?;
+            for deleted in &rollback.deleted {
+                println!("  🗑️  Deleted {}", deleted);
+            }
+            for restored in &rollback.restored {
+                println!("  ↩️  Restored {}", restored);
+            }
+            println!("⏪ Rolled back `{}`: {} files deleted, {} restored, {} removed", manifest.command, rollback.deleted.len(), rollback.restored.len(), MANIFEST_FILE);
+        }
+        Commands::Diff { base, path, config_dir, include, exclude, overwrite } => {
+            let scope = PathScope::new(&include, &exclude)?;
+            let changes = GitChanges::since(Path::new(&path), &base)?;
+            let root = changes.root.as_path();
+            
+            let mut loader = LanguageLoader::new(config_dir.clone());
+            let adapters = loader.load_all_languages()?;
+            let supported_extensions = get_supported_extensions(&loader);
+            let project_config = ProjectConfig::discover(root)?;
+            let mut orchestrator = TestOrchestrator::new();
+            for (lang, adapter) in adapters {
+                let config = project_config.adapter_config(&lang);
+                orchestrator.register_adapter_with_config(lang, adapter, config);
+            }
+            orchestrator.set_project_config(project_config);
+            
+            let changed: Vec<&ChangedFile> = changes.files.iter()
+                .filter(|file| file.path.extension().is_some_and(|ext| supported_extensions.contains(&ext.to_string_lossy().to_lowercase())))
+                .filter(|file| !is_ignored_path(&file.path) && !is_test_path(&file.path) && scope.allows(&file.path))
+                .filter(|file| !orchestrator.project_config().is_ignored(&root.join(&file.path).to_string_lossy()))
+                .collect();
+            info!("🔀 {} source files changed since {}", changed.len(), base);
+            
+            let mut manifest = GenerationManifest::new(root, "diff");
+            let mut functions = 0;
+            let mut tests_added = 0;
+            let mut failed = 0;
+            for file in &changed {
+                let relative = file.path.display();
+                match generate_for_change(&orchestrator, root, file, &overwrite, &mut manifest).await {
+                    Ok(Some(change)) => {
+                        let test_file = change.test_file.strip_prefix(root).unwrap_or(&change.test_file).display().to_string();
+                        functions += change.functions.len();
+                        match change.update {
+                            TestFileUpdate::Created(count) => {
+                                info!("  ✅ {} ({}): {} tests -> {}", relative, change.functions.join(", "), count, test_file);
+                                tests_added += count;
+                            }
+                            TestFileUpdate::Merged(count) => {
+                                info!("  ➕ {} ({}): {} new tests merged into {}", relative, change.functions.join(", "), count, test_file);
+                                tests_added += count;
+                            }
+                            TestFileUpdate::Overwritten(count) => {
+                                info!("  ♻️  {} ({}): {} tests replaced {}", relative, change.functions.join(", "), count, test_file);
+                                tests_added += count;
+                            }
+                            TestFileUpdate::UpToDate => info!("  ✔️  {} ({}): {} is up to date", relative, change.functions.join(", "), test_file),
+                            TestFileUpdate::Skipped => info!("  ⏭️  {} ({}): {} already exists", relative, change.functions.join(", "), test_file),
+                        }
+                    }
+                    Ok(None) => debug!("  ⏭️  {}: no testable functions changed", relative),
+                    Err(e) => {
+                        error!("  ❌ {}: {}", relative, e);
+                        failed += 1;
+                    }
+                }
+            }
             
-            if repo_dir.exists() {
-                fs::remove_dir_all(&repo_dir)?;
+            println!("📊 {} changed functions in {} files, {} tests added", functions, changed.len(), tests_added);
+            note_manifest(&manifest, root);
+            dir_outcome(failed, changed.len())?;
+        }
+        Commands::GitRepo { url, config_dir, branch, in_repo, include, exclude, resume, create_pr, token_env, ssh_key, overwrite, file_size } => {
+            let scope = PathScope::new(&include, &exclude)?;
+            // A local checkout is worked on in place, and pushed to its origin
+            let local = Path::new(&url).is_dir() && Repository::open(&url).is_ok();
+            let remote_url = if local { origin_url(Path::new(&url)).unwrap_or_else(|| url.clone()) } else { url.clone() };
+            let credentials = git_credentials(&remote_url, token_env.as_deref(), ssh_key.as_deref())?;
+            // Checked up front so a missing token doesn't surface only after generation
+            let pull_request = if create_pr { Some(pull_request_host(&remote_url, &credentials, token_env.as_deref())?) } else { None };
+            // The pull request carries only what uft wrote, which can't be told apart from edits
+            // already in the checkout
+            if local && create_pr && has_local_changes(&Repository::open(&url)?)? {
+                return Err(UftError::Config(format!("--create-pr needs a clean checkout, and {} has uncommitted changes: commit or stash them first", url)).into());
             }
             
-            let _repo = Repository::clone(&url, &repo_dir)?;
+            // Clone repository to working directory (not temp)
+            let repo_path = if local {
+                PathBuf::from(&url)
+            } else {
+                PathBuf::from(url.split('/').last().unwrap_or("repo").replace(".git", ""))
+            };
+            let repo_dir = repo_path.as_path();
             
-            // Checkout specified branch if not main
-            if branch != "main" {
-                let repo = Repository::open(&repo_dir)?;
-                let (object, reference) = repo.revparse_ext(&format!("origin/{}", branch))?;
-                repo.checkout_tree(&object, None)?;
-                match reference {
-                    Some(gref) => repo.set_head(gref.name().unwrap())?,
-                    None => repo.set_head_detached(object.id())?,
+            let resuming = !local && resume && repo_dir.exists();
+            if local {
+                info!("📂 Using local checkout: {}", repo_dir.display());
+            } else if resuming {
+                info!("⏩ Resuming in existing clone: {}", repo_dir.display());
+            } else {
+                info!("🔄 Cloning repository: {}", url);
+                if repo_dir.exists() {
+                    fs::remove_dir_all(&repo_dir)?;
                 }
+                
+                credentials.clone(&url, repo_dir)?;
+                info!("✅ Repository cloned to: {}", repo_dir.display());
             }
             
-            println!("✅ Repository cloned to: {}", repo_dir.display());
+            // Checkout the branch asked for, else stay on the one checked out: the remote's
+            // default in a fresh clone
+            let repo = Repository::open(repo_dir)?;
+            let branch = match branch {
+                Some(branch) if !resuming => {
+                    checkout_branch(&repo, &branch)?;
+                    branch
+                }
+                Some(branch) => branch,
+                None if local => repo.head()?.shorthand().unwrap_or("HEAD").to_string(),
+                None => default_branch(&repo)?,
+            };
+            info!("🌿 Branch: {}", branch);
             
             // Load language adapters
             let mut loader = LanguageLoader::new(config_dir.clone());
@@ -504,23 +1958,34 @@ async fn main() -> Result<()> {
             let supported_extensions = get_supported_extensions(&loader);
             
             // Detect project languages and let user choose frameworks
-            let project_languages = detect_project_languages(&repo_dir, &supported_extensions)?;
-            println!("🔍 Detected languages: {:?}", project_languages);
+            let project_languages = detect_project_languages(repo_dir, &supported_extensions, &scope)?;
+            info!("🔍 Detected languages: {:?}", project_languages);
             
-            let framework_choices = prompt_framework_choices(&project_languages)?;
+            let project_config = ProjectConfig::discover(repo_dir)?;
+            let mut checkpoint = start_checkpoint(repo_dir, resume)?;
+            let mut manifest = GenerationManifest::resume(repo_dir, "git-repo", resume);
+            let framework_choices = if checkpoint.framework_choices.is_empty() {
+                prompt_framework_choices(&project_languages, &project_config)?
+            } else {
+                checkpoint.framework_choices.clone()
+            };
+            checkpoint.framework_choices = framework_choices.clone();
             
             let mut orchestrator = TestOrchestrator::new();
             for (lang, adapter) in adapters {
-                orchestrator.register_adapter(lang, adapter);
+                let config = project_config.adapter_config(&lang);
+                orchestrator.register_adapter_with_config(lang, adapter, config);
             }
+            orchestrator.set_project_config(project_config);
             
             // Find all source files
-            let source_files = find_source_files_excluding_tests(&repo_dir, &supported_extensions)?;
-            println!("📝 Found {} source files to test", source_files.len());
+            let mut source_files = find_source_files_excluding_tests(repo_dir, &supported_extensions, &scope)?;
+            source_files.retain(|file| !orchestrator.project_config().is_ignored(&file.to_string_lossy()));
+            info!("📝 Found {} source files to test", source_files.len());
             
-            let mut total_tests = 0;
-            let mut processed_files = 0;
-            let mut skipped_files = 0;
+            let file_count = source_files.len();
+            skip_completed(&mut source_files, repo_dir, &checkpoint);
+            let mut progress = GenerationProgress::new(source_files.len());
             
             // Process each file
             for file_path in source_files {
@@ -528,7 +1993,9 @@ async fn main() -> Result<()> {
                     .unwrap_or(&file_path)
                     .to_string_lossy();
                 
-                println!("🔍 Processing: {}", relative_path);
+                debug!("🔍 Processing: {}", relative_path);
+                progress.start_file(&relative_path);
+                let file_started = Instant::now();
                 
                 // Determine language and framework
                 let language = detect_file_language(&file_path, &supported_extensions, &loader)?;
@@ -537,64 +2004,97 @@ async fn main() -> Result<()> {
                 });
                 
                 // Check if test already exists
-                let test_file_path = get_test_file_path(&repo_dir, &file_path, &language, &framework)?;
+                let test_file_path = orchestrator.test_file_path(repo_dir, &file_path, &language);
                 
-                if test_file_path.exists() {
-                    println!("  ⏭️  Test already exists: {}", test_file_path.display());
-                    skipped_files += 1;
+                if overwrite.policy(OverwritePolicy::Skip) == OverwritePolicy::Skip && !read_existing_tests(&test_file_path, &file_path).is_empty() {
+                    debug!("  ⏭️  Test already exists: {}", test_file_path.display());
+                    checkpoint.skipped += 1;
+                    progress.finish_file(&relative_path, &language, file_started.elapsed());
+                    checkpoint.complete(repo_dir, &relative_path)?;
                     continue;
                 }
                 
-                match fs::read_to_string(&file_path) {
+                let Some(content) = file_size.read(&file_path, &relative_path).await else {
+                    checkpoint.skipped += 1;
+                    progress.finish_file(&relative_path, &language, file_started.elapsed());
+                    checkpoint.complete(repo_dir, &relative_path)?;
+                    continue;
+                };
+                match content {
                     Ok(content) => {
-                        match orchestrator.generate_tests_for_file(
+                        match orchestrator.generate_tests_with_patterns(
                             &file_path.to_string_lossy(), 
                             &content
                         ).await {
-                            Ok(mut test_suite) => {
+                            Ok((mut test_suite, patterns)) => {
                                 if !test_suite.test_cases.is_empty() {
                                     // Update test suite with chosen framework
                                     test_suite.framework = framework.clone();
                                     
-                                    let test_content = generate_test_file_content_with_framework(&test_suite, &framework)?;
+                                    let test_content = orchestrator.project_config().apply_template(&language, &file_path.to_string_lossy(), &generate_test_file_content_with_framework(&test_suite, &framework, &patterns, &content)?)?;
+                                    let patterns = pattern_ids(&relative_path, patterns.iter().map(|pattern| pattern.subject_name()));
+                                    let update = overwrite.write_tests(&mut manifest, &test_file_path, &file_path, &test_content, OverwritePolicy::Skip, &patterns)?;
+                                    let tests = written_tests(update, &test_suite);
                                     
-                                    // Create test directory if needed
-                                    if let Some(parent) = test_file_path.parent() {
-                                        fs::create_dir_all(parent)?;
-                                    }
-                                    
-                                    fs::write(&test_file_path, test_content)?;
-                                    
-                                    println!("  ✅ Generated {} tests -> {}", 
-                                        test_suite.test_cases.len(), 
+                                    debug!("  ✅ {} {} tests -> {}", 
+                                        update.status(),
+                                        tests, 
                                         test_file_path.strip_prefix(&repo_dir)
                                             .unwrap_or(&test_file_path)
                                             .display()
                                     );
+                                    if let Some(conftest) = overwrite.write_conftest(&mut manifest, &file_path)? {
+                                        debug!("  ✅ Import path set up in {}", conftest.strip_prefix(&repo_dir).unwrap_or(&conftest).display());
+                                    }
                                     
-                                    total_tests += test_suite.test_cases.len();
-                                    processed_files += 1;
+                                    checkpoint.total_tests += tests;
+                                    count_update(&mut checkpoint, update);
                                 } else {
-                                    println!("  ⚠️  No testable patterns found");
+                                    warn!("  ⚠️  No testable patterns found");
                                 }
                             }
-                            Err(e) => println!("  ❌ Error generating tests: {}", e),
+                            Err(e) => {
+                                error!("  ❌ Error generating tests: {}", e);
+                                checkpoint.failed += 1;
+                            }
                         }
                     }
-                    Err(e) => println!("  ❌ Error reading file: {}", e),
+                    Err(e) => {
+                        error!("  ❌ Error reading file: {}", e);
+                        checkpoint.failed += 1;
+                    }
                 }
+                progress.finish_file(&relative_path, &language, file_started.elapsed());
+                checkpoint.complete(repo_dir, &relative_path)?;
             }
+            progress.finish();
+            RunCheckpoint::clear(repo_dir)?;
+            note_manifest(&manifest, repo_dir);
             
             println!("\n🎉 Test generation complete!");
             println!("📊 Summary:");
-            println!("   • Processed files: {}", processed_files);
-            println!("   • Skipped files (tests exist): {}", skipped_files);
-            println!("   • Total test cases: {}", total_tests);
+            println!("   • Processed files: {}", checkpoint.processed);
+            println!("   • Skipped files (tests exist): {}", checkpoint.skipped);
+            if checkpoint.failed > 0 {
+                println!("   • Failed files: {}", checkpoint.failed);
+            }
+            println!("   • Total test cases: {}", checkpoint.total_tests);
             println!("   • Repository: {}", repo_dir.display());
+            println!("⏱️  Timing:");
+            for line in progress.timing_lines(progress.started.elapsed()) {
+                println!("   • {}", line);
+            }
             println!("\n💡 Next steps:");
             println!("   1. cd {}", repo_dir.display());
             println!("   2. Review and implement test logic in generated files");
             println!("   3. Run tests with your project's test command");
+            if let Some(host) = pull_request {
+                match open_generated_tests_pr(&host, &credentials, repo_dir, &manifest, &branch, checkpoint.processed, checkpoint.total_tests).await? {
+                    Some(pr_url) => println!("\n🔀 Pull request opened: {}", pr_url),
+                    None => println!("\n🔀 No new tests to open a pull request with"),
+                }
+            }
+            dir_outcome(checkpoint.failed, file_count)?;
         }
     }
 
@@ -720,6 +2220,25 @@ async fn build_vscode_plugin(output_dir: &str) -> Result<()> {
           "type": "string",
           "default": "tests/",
           "description": "Output directory for generated tests"
+        },
+        "unifiedTesting.frameworks": {
+          "type": "object",
+          "additionalProperties": { "type": "string" },
+          "default": {},
+          "description": "Test framework per language, e.g. { \"python\": \"unittest\" }, over uft.toml's [frameworks]"
+        },
+        "unifiedTesting.minConfidence": {
+          "type": ["number", "null"],
+          "default": null,
+          "minimum": 0,
+          "maximum": 1,
+          "description": "Patterns detected with lower confidence get no tests, over uft.toml's confidence_threshold"
+        },
+        "unifiedTesting.overwrite": {
+          "type": "string",
+          "enum": ["", "merge", "force", "skip"],
+          "default": "",
+          "description": "What generating does to an existing test file; uft's default when empty"
         }
       }
     }
@@ -877,117 +2396,269 @@ tasks {
         main_plugin_source
     )?;
     
+    // Copy the daemon client the actions call
+    let daemon_client_source = include_str!("../../plugins/spring/src/main/java/com/unified/testing/UftDaemon.java");
+    fs::write(
+        format!("{}/src/main/java/com/unified/testing/UftDaemon.java", plugin_dir),
+        daemon_client_source
+    )?;
+    
     println!("Spring IDE plugin created at: {}", plugin_dir);
     println!("Run './gradlew buildPlugin' in the plugin directory to build");
     Ok(())
 }
 
-fn generate_test_file_content(test_suite: &unified_test_framework::TestSuite) -> Result<String> {
-    // If the test suite has generated test code, use it directly
-    if let Some(ref test_code) = test_suite.test_code {
-        return Ok(test_code.clone());
+/// Write browser specs for the routes found under `path` into its project
+fn write_e2e_specs(path: &Path, overwrite: &OverwriteArgs) -> Result<()> {
+    let project_root = E2eGenerator::project_root(path);
+    let framework = E2eFramework::detect(&project_root);
+    let routes = E2eGenerator::find_routes(path);
+    if routes.is_empty() {
+        println!("No routes found in {}", path.display());
+        return Ok(());
     }
     
-    // Fallback to the old method for backward compatibility
-    let mut content = String::new();
+    println!("🧭 Generating {} specs for {} routes in: {}", framework.name(), routes.len(), path.display());
+    for route in &routes {
+        println!("  - {}", route.path);
+    }
     
-    // Add imports only for non-Rust languages
-    if test_suite.language != "rust" {
-        for import in &test_suite.imports {
-            content.push_str(import);
-            content.push('\n');
-        }
-        content.push('\n');
+    let mut manifest = GenerationManifest::new(&project_root, "e2e");
+    let patterns = pattern_ids(&path.to_string_lossy(), routes.iter().map(|route| route.path.as_str()));
+    for spec in E2eGenerator::generate(&project_root, framework, &routes)? {
+        let update = overwrite.write(&mut manifest, &spec.path, &spec.content, OverwritePolicy::Skip, &patterns)?;
+        print_file_update(&spec.path, update);
     }
+    note_manifest(&manifest, &project_root);
     
-    // Add test cases based on language, now using test_body from individual test cases
-    match test_suite.language.as_str() {
-        "javascript" => {
-            content.push_str("describe('Generated Tests', () => {\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "  test('{}', () => {{\n    // {}\n{}\n  }});\n\n",
-                    test_case.name, 
-                    test_case.description,
-                    if test_case.test_body.trim().is_empty() {
-                        "    // TODO: Implement test logic".to_string()
-                    } else {
-                        test_case.test_body.clone()
-                    }
-                ));
+    println!("\n💡 Start the dev server (or set BASE_URL to a running deployment), then:");
+    match framework {
+        E2eFramework::Playwright => println!("   npm install --save-dev @playwright/test && npx playwright install && npx playwright test e2e/"),
+        E2eFramework::Cypress => println!("   npx cypress run --spec 'cypress/e2e/routes.cy.*'"),
+    }
+    Ok(())
+}
+
+/// The tests `diff` generated for the functions a change touched in one file
+struct ChangeTests {
+    functions: Vec<String>,
+    update: TestFileUpdate,
+    test_file: PathBuf,
+}
+
+/// Generate tests for the functions `change` touched, by default merging them into the file's
+/// test file; `None` when it touched none
+async fn generate_for_change(orchestrator: &TestOrchestrator, root: &Path, change: &ChangedFile, overwrite: &OverwriteArgs, manifest: &mut GenerationManifest) -> Result<Option<ChangeTests>> {
+    let file_path = root.join(&change.path);
+    let path = file_path.to_string_lossy();
+    let content = fs::read_to_string(&file_path)?;
+    let patterns = orchestrator.analyze_change(&path, &content, change).await?;
+    let mut functions: Vec<String> = patterns.iter()
+        .map(|pattern| pattern.subject_name().to_string())
+        .collect();
+    functions.dedup();
+    if functions.is_empty() {
+        return Ok(None);
+    }
+
+    let test_suite = orchestrator.generate_tests_for_patterns(&path, &content, patterns).await?;
+    if test_suite.test_cases.is_empty() {
+        return Ok(None);
+    }
+    let test_file = orchestrator.test_file_path(root, &file_path, &test_suite.language);
+    let generated = orchestrator.project_config().apply_template(&test_suite.language, &path, &generate_test_file_content(&test_suite)?)?;
+    let patterns = pattern_ids(&change.path.to_string_lossy(), functions.iter().map(String::as_str));
+    let update = overwrite.write_tests(manifest, &test_file, &file_path, &generated, OverwritePolicy::Merge, &patterns)?;
+    Ok(Some(ChangeTests { functions, update, test_file }))
+}
+
+/// What regenerating the tests of one changed file did
+enum WatchOutcome {
+    Created(usize),
+    Merged(usize),
+    Overwritten(usize),
+    UpToDate,
+    NoPatterns,
+}
+
+/// Regenerate the tests for `file`, writing a new test file or, by default, merging the tests
+/// it lacks into the existing one. When merging into a file seen before, in `saved`, only the
+/// definitions the save changed are analyzed
+async fn regenerate_tests(orchestrator: &TestOrchestrator, root: &Path, file: &Path, overwrite: &OverwriteArgs, manifest: &mut GenerationManifest, saved: &mut HashMap<PathBuf, String>) -> Result<(WatchOutcome, PathBuf)> {
+    let content = fs::read_to_string(file)?;
+    let previous = saved.insert(file.to_path_buf(), content.clone());
+    let path = file.to_string_lossy();
+    let (test_suite, patterns) = match previous {
+        Some(previous) if overwrite.policy(OverwritePolicy::Merge) == OverwritePolicy::Merge => {
+            let change = ChangedFile::between(file.to_path_buf(), &previous, &content)?;
+            let patterns = orchestrator.analyze_change(&path, &content, &change).await?;
+            if patterns.is_empty() {
+                let language = orchestrator.detect_language(&path)?;
+                return Ok((WatchOutcome::UpToDate, orchestrator.test_file_path(root, file, &language)));
             }
-            content.push_str("});\n");
+            (orchestrator.generate_tests_for_patterns(&path, &content, patterns.clone()).await?, patterns)
         }
-        "python" => {
-            content.push_str("class TestGenerated:\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    def {}(self):\n        \"\"\" {} \"\"\"\n{}\n\n",
-                    test_case.name, 
-                    test_case.description,
-                    if test_case.test_body.trim().is_empty() {
-                        "        # TODO: Implement test logic\n        pass".to_string()
-                    } else {
-                        test_case.test_body.clone()
-                    }
-                ));
+        _ => orchestrator.generate_tests_with_patterns(&path, &content).await?,
+    };
+    let test_file = orchestrator.test_file_path(root, file, &test_suite.language);
+    if test_suite.test_cases.is_empty() {
+        return Ok((WatchOutcome::NoPatterns, test_file));
+    }
+
+    let generated = orchestrator.project_config().apply_template(&test_suite.language, &file.to_string_lossy(), &generate_test_file_content(&test_suite)?)?;
+    let source = file.strip_prefix(root).unwrap_or(file).to_string_lossy();
+    let patterns = pattern_ids(&source, patterns.iter().map(|pattern| pattern.subject_name()));
+    let outcome = match overwrite.write_tests(manifest, &test_file, file, &generated, OverwritePolicy::Merge, &patterns)? {
+        TestFileUpdate::Created(count) => WatchOutcome::Created(count),
+        TestFileUpdate::Merged(count) => WatchOutcome::Merged(count),
+        TestFileUpdate::Overwritten(count) => WatchOutcome::Overwritten(count),
+        TestFileUpdate::UpToDate | TestFileUpdate::Skipped => WatchOutcome::UpToDate,
+    };
+    Ok((outcome, test_file))
+}
+
+/// Regenerate tests for the source files changed under `dir` until interrupted, batching the
+/// changes that arrive within `debounce` of each other
+async fn watch_directory(dir: &Path, config_dir: &str, debounce: Duration, scope: &PathScope, overwrite: &OverwriteArgs) -> Result<()> {
+    if !dir.is_dir() {
+        return Err(anyhow::anyhow!("Not a directory: {}", dir.display()));
+    }
+    let root = dir.canonicalize()?;
+
+    let mut loader = LanguageLoader::new(config_dir.to_string());
+    loader.load_all_languages()?;
+    let mut supported_extensions = get_supported_extensions(&loader);
+    let mut orchestrator = watch_orchestrator(config_dir, ProjectConfig::discover(&root)?)?;
+    let language_configs = loader.resolved_config_dir().and_then(|dir| dir.canonicalize().ok());
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let mut debouncer = notify_debouncer_mini::new_debouncer(debounce, move |result: notify_debouncer_mini::DebounceEventResult| {
+        let _ = sender.send(result);
+    })?;
+    debouncer.watcher().watch(&root, notify::RecursiveMode::Recursive)?;
+    if let Some(language_configs) = language_configs.as_ref().filter(|dir| !dir.starts_with(&root)) {
+        debouncer.watcher().watch(language_configs, notify::RecursiveMode::NonRecursive)?;
+    }
+    info!("👀 Watching {} for changes (Ctrl+C to stop)", root.display());
+    // One manifest for the whole session, so a rollback undoes everything it wrote
+    let mut manifest = GenerationManifest::new(&root, "watch");
+    // Each file as it was last regenerated from, to tell what the next save changed
+    let mut saved: HashMap<PathBuf, String> = HashMap::new();
+    // What uft last wrote to each file, so its own writes don't retrigger a run
+    let mut written: HashMap<PathBuf, String> = HashMap::new();
+
+    let (mut files_updated, mut tests_added) = (0, 0);
+    while let Some(result) = receiver.recv().await {
+        let events = match result {
+            Ok(events) => events,
+            Err(e) => {
+                error!("  ❌ Watch error: {}", e);
+                continue;
             }
-        }
-        "rust" => {
-            content.push_str("#[cfg(test)]\nmod tests {\n    use super::*;\n\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    #[test]\n    fn {}() {{\n        // {}\n{}\n    }}\n\n",
-                    test_case.name, 
-                    test_case.description,
-                    if test_case.test_body.trim().is_empty() {
-                        "        // TODO: Implement test logic".to_string()
-                    } else {
-                        test_case.test_body.clone()
-                    }
-                ));
+        };
+        // Edited templates apply from the next regeneration on, without restarting
+        if events.iter().any(|event| orchestrator.project_config().is_template_file(&event.path)) {
+            match reload_project_config(&root).and_then(|project_config| watch_orchestrator(config_dir, project_config)) {
+                Ok(reloaded) => {
+                    orchestrator = reloaded;
+                    info!("🔄 Reloaded the project config and templates");
+                }
+                Err(e) => error!("  ❌ Keeping the previous templates: {:#}", e),
             }
-            content.push_str("}\n");
         }
-        "go" => {
-            content.push_str("package main\n\nimport (\n\t\"testing\"\n)\n\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "func {}(t *testing.T) {{\n\t// {}\n{}\n}}\n\n",
-                    test_case.name, 
-                    test_case.description,
-                    if test_case.test_body.trim().is_empty() {
-                        "\t// TODO: Implement test logic".to_string()
-                    } else {
-                        test_case.test_body.clone()
+        // So do added, edited and removed language configs
+        if let Some(language_configs) = &language_configs {
+            if events.iter().any(|event| event.path.starts_with(language_configs)) {
+                let mut reloaded = LanguageLoader::new(config_dir.to_string());
+                match reloaded.load_all_languages() {
+                    Ok(adapters) => {
+                        info!("🔄 Reloaded the language configs: {}", orchestrator.reload_adapters(adapters));
+                        supported_extensions = get_supported_extensions(&reloaded);
                     }
-                ));
+                    Err(e) => error!("  ❌ Keeping the previous language configs: {:#}", e),
+                }
             }
         }
-        "java" => {
-            content.push_str("import org.junit.*;\nimport static org.junit.Assert.*;\n\n");
-            content.push_str(&format!("public class {}Test {{\n\n", 
-                test_suite.name.replace("Test", "")));
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    @Test\n    public void {}() {{\n        // {}\n{}\n    }}\n\n",
-                    test_case.name, 
-                    test_case.description,
-                    if test_case.test_body.trim().is_empty() {
-                        "        // TODO: Implement test logic".to_string()
-                    } else {
-                        test_case.test_body.clone()
+        // Generated test files mostly live on test paths, but inline Rust tests go into the
+        // source file, which is left alone until something other than uft changes it
+        let mut changed: Vec<PathBuf> = events.into_iter()
+            .map(|event| event.path)
+            .filter(|path| {
+                let relative = path.strip_prefix(&root).unwrap_or(path);
+                let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+                path.is_file() && !is_ignored_path(relative) && !is_test_path(relative) && scope.allows(relative) && supported_extensions.contains(&extension)
+                    && !orchestrator.project_config().is_ignored(&path.to_string_lossy())
+                    && !orchestrator.project_config().is_template_file(path)
+                    && !is_own_write(&written, path)
+            })
+            .collect();
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() {
+            continue;
+        }
+
+        for file in changed {
+            let relative = file.strip_prefix(&root).unwrap_or(&file).display().to_string();
+            match regenerate_tests(&orchestrator, &root, &file, overwrite, &mut manifest, &mut saved).await {
+                Ok((outcome, test_file)) => {
+                    if let Ok(content) = fs::read_to_string(&test_file) {
+                        // The next save of a file holding its own tests is told apart from this write
+                        if test_file == file {
+                            saved.insert(file.clone(), content.clone());
+                        }
+                        written.insert(test_file.clone(), content);
                     }
-                ));
+                    let test_file = test_file.strip_prefix(&root).unwrap_or(&test_file).display().to_string();
+                    match outcome {
+                        WatchOutcome::Created(count) => {
+                            info!("  ✅ {}: {} tests -> {}", relative, count, test_file);
+                            files_updated += 1;
+                            tests_added += count;
+                        }
+                        WatchOutcome::Merged(count) => {
+                            info!("  ➕ {}: {} new tests merged into {}", relative, count, test_file);
+                            files_updated += 1;
+                            tests_added += count;
+                        }
+                        WatchOutcome::Overwritten(count) => {
+                            info!("  ♻️  {}: {} tests replaced {}", relative, count, test_file);
+                            files_updated += 1;
+                            tests_added += count;
+                        }
+                        WatchOutcome::UpToDate => info!("  ✔️  {}: {} is up to date", relative, test_file),
+                        WatchOutcome::NoPatterns => warn!("  ⚠️  {}: no testable patterns found", relative),
+                    }
+                }
+                Err(e) => error!("  ❌ {}: {}", relative, e),
             }
-            content.push_str("}\n");
-        }
-        _ => {
-            return Err(anyhow::anyhow!("Unsupported language: {}", test_suite.language));
         }
+        println!("📊 {} test files updated, {} tests added since watching started", files_updated, tests_added);
     }
-    
-    Ok(content)
+    Ok(())
+}
+
+/// Whether `path` still holds exactly what uft last wrote to it
+fn is_own_write(written: &HashMap<PathBuf, String>, path: &Path) -> bool {
+    written.get(path).is_some_and(|content| fs::read_to_string(path).is_ok_and(|current| current == *content))
+}
+
+/// An orchestrator for `watch` with every language's adapter, configured by `project_config`
+fn watch_orchestrator(config_dir: &str, project_config: ProjectConfig) -> Result<TestOrchestrator> {
+    let mut orchestrator = TestOrchestrator::new();
+    for (lang, adapter) in LanguageLoader::new(config_dir.to_string()).load_all_languages()? {
+        let config = project_config.adapter_config(&lang);
+        orchestrator.register_adapter_with_config(lang, adapter, config);
+    }
+    orchestrator.set_project_config(project_config);
+    Ok(orchestrator)
+}
+
+/// The project config under `root` again, once its templates check out
+fn reload_project_config(root: &Path) -> Result<ProjectConfig> {
+    let project_config = ProjectConfig::discover(root)?;
+    project_config.check_templates()?;
+    Ok(project_config)
 }
 
 fn get_test_file_extension(language: &str) -> &str {
@@ -1009,6 +2680,20 @@ fn get_supported_extensions(loader: &LanguageLoader) -> Vec<String> {
         .collect()
 }
 
+/// The file extension for `lang`, a language name or an extension of one
+fn language_extension(loader: &LanguageLoader, lang: &str) -> Result<String> {
+    let extensions = loader.get_supported_extensions();
+    if extensions.contains_key(lang) {
+        return Ok(lang.to_string());
+    }
+    // The shortest, then alphabetically first, of its extensions: js over jsx, ts and tsx
+    extensions.into_iter()
+        .filter(|(_, language)| language == lang)
+        .map(|(extension, _)| extension)
+        .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+        .ok_or_else(|| UftError::UnsupportedLanguage(format!("Unknown language: {}", lang)).into())
+}
+
 /// Find all source files with supported extensions in a directory
 fn find_source_files(dir: &Path, supported_extensions: &[String]) -> Result<Vec<std::path::PathBuf>> {
     let mut source_files = Vec::new();
@@ -1120,6 +2805,106 @@ fn install_language_configs(force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Comment `install` writes above the PATH line it adds to a shell profile
+const INSTALL_MARKER: &str = "# Added by Unified Test Framework installer";
+
+/// Shell profiles `install` may have written to
+fn shell_config_files(home: &str) -> Vec<String> {
+    vec![
+        format!("{}/.bashrc", home),
+        format!("{}/.zshrc", home),
+        format!("{}/.config/fish/config.fish", home),
+    ]
+}
+
+/// Remove the language configs `install` copied, and the config directory if that leaves it empty
+fn uninstall_language_configs(dry_run: bool) -> Result<()> {
+    let home = std::env::var("HOME")?;
+    let config_dir = PathBuf::from(format!("{}/.config/uft", home));
+    let target_dir = config_dir.join("language_configs");
+    
+    if !target_dir.exists() {
+        println!("   ⏭️  No language configs installed");
+    } else if dry_run {
+        println!("   Would remove {}", target_dir.display());
+    } else {
+        fs::remove_dir_all(&target_dir)?;
+        println!("   ✅ Removed {}", target_dir.display());
+    }
+    
+    let left_empty = config_dir.read_dir()
+        .map(|mut entries| entries.all(|entry| entry.map(|e| e.path() == target_dir).unwrap_or(false)))
+        .unwrap_or(false);
+    if left_empty {
+        if dry_run {
+            println!("   Would remove {}", config_dir.display());
+        } else {
+            fs::remove_dir(&config_dir)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Remove the marked PATH lines `install` added to shell profiles
+fn remove_shell_integration(dry_run: bool) -> Result<()> {
+    let home = std::env::var("HOME")?;
+    let mut found = false;
+    
+    for config_file in shell_config_files(&home) {
+        let Ok(content) = fs::read_to_string(&config_file) else {
+            continue;
+        };
+        let Some(stripped) = strip_install_lines(&content) else {
+            continue;
+        };
+        found = true;
+        if dry_run {
+            println!("   Would remove the installer's PATH lines from {}", config_file);
+        } else {
+            fs::write(&config_file, stripped)?;
+            println!("   ✅ Shell configuration restored: {}", config_file);
+        }
+    }
+    
+    if !found {
+        println!("   ⏭️  No shell configuration added by the installer");
+    }
+    Ok(())
+}
+
+/// A shell profile without each installer marker, the PATH line after it and the blank line
+/// before it. `None` when it has no marker
+fn strip_install_lines(content: &str) -> Option<String> {
+    if !content.lines().any(|line| line.trim() == INSTALL_MARKER) {
+        return None;
+    }
+    
+    let lines: Vec<&str> = content.lines().collect();
+    let mut kept: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].trim() == INSTALL_MARKER {
+            if kept.last().is_some_and(|line| line.trim().is_empty()) {
+                kept.pop();
+            }
+            index += 1;
+            if lines.get(index).is_some_and(|line| line.contains("PATH")) {
+                index += 1;
+            }
+            continue;
+        }
+        kept.push(lines[index]);
+        index += 1;
+    }
+    
+    let mut stripped = kept.join("\n");
+    if !stripped.is_empty() && content.ends_with('\n') {
+        stripped.push('\n');
+    }
+    Some(stripped)
+}
+
 /// Configure shell integration (add uft to PATH if needed)
 fn configure_shell_integration(force: bool) -> Result<()> {
     let home = std::env::var("HOME")?;
@@ -1167,7 +2952,7 @@ fn configure_shell_integration(force: bool) -> Result<()> {
         .open(&config_file)?;
     
     writeln!(file, "")?;
-    writeln!(file, "# Added by Unified Test Framework installer")?;
+    writeln!(file, "{}", INSTALL_MARKER)?;
     writeln!(file, "{}", path_export)?;
     
     println!("   ✅ Shell configuration updated: {}", config_file);
@@ -1196,6 +2981,36 @@ fn get_available_frameworks_for_display(language: &str) -> String {
     frameworks.join(", ")
 }
 
+/// What uft can do for `language`, as `uft languages --format json` lists it
+fn language_capabilities(loader: &LanguageLoader, language: &str) -> serde_json::Value {
+    let mut extensions: Vec<String> = loader.get_supported_extensions().into_iter()
+        .filter(|(_, name)| name == language)
+        .map(|(extension, _)| extension)
+        .collect();
+    extensions.sort();
+    let config_file = loader.config_file(language);
+    // Where a test file goes, shown for a made-up source file
+    let example_source = PathBuf::from("src").join(format!("example.{}", extensions.first().map_or("txt", String::as_str)));
+    serde_json::json!({
+        "name": language,
+        "adapter": if config_file.is_some() { "dynamic" } else { "built-in" },
+        "extensions": extensions,
+        "frameworks": get_available_frameworks(language),
+        "default_framework": get_default_framework(language),
+        "test_file": {
+            "suffix": loader.get_test_file_extension(language),
+            "example": { "source": example_source, "test": ProjectConfig::default().test_file_path_with(Path::new(""), &example_source, language, loader.test_path(language)) },
+        },
+        "integration_tests": supports_integration_tests(language),
+        "config_source": config_file.map_or_else(|| "built-in".to_string(), |path| path.display().to_string()),
+    })
+}
+
+/// Whether `integration-test` has an adapter for `language`
+fn supports_integration_tests(language: &str) -> bool {
+    matches!(language, "javascript" | "python" | "rust" | "go" | "java")
+}
+
 /// Get language name with appropriate symbol for better identification
 fn get_language_with_symbol(language: &str) -> String {
     let symbol = match language.to_lowercase().as_str() {
@@ -1221,10 +3036,60 @@ fn visual_width(text: &str) -> usize {
     text.chars().count() + emoji_count
 }
 
+/// Starter uft.toml: every section with its options commented out, and the default framework
+/// of each detected language filled in
+fn scaffold_project_config(languages: &[String]) -> String {
+    let frameworks: String = languages.iter()
+        .map(|language| format!("{} = \"{}\"\n", language, get_default_framework(language)))
+        .collect();
+    format!(r#"# uft project configuration
+
+# Files never analyzed, as globs relative to this file
+ignore = []
+
+# Patterns detected with lower confidence (0.0-1.0) than this get no tests
+# confidence_threshold = 0.5
+
+[output]
+# Write every test file under one directory instead of next to its source
+# directory = "tests/generated"
+
+[frameworks]
+{frameworks}
+[naming]
+# Prefix for generated test names, for every language or per language
+# prefix = "test_"
+
+[naming.languages]
+# javascript = "should_"
+
+[coverage]
+# default = 80
+
+[coverage.languages]
+# python = 90
+
+[coverage.modules]
+# "src/payments" = 95
+
+[templates]
+# Skeleton new test files are written into; {{{{ tests }}}} marks where the tests go
+# python = "templates/test_template.py"
+
+# A comment block at the top of every generated test file: license, provenance and how to
+# regenerate it. `template` replaces the default lines, filling in {{{{ license }}}},
+# {{{{ version }}}}, {{{{ source }}}}, {{{{ commit }}}}, {{{{ timestamp }}}} and {{{{ command }}}}
+# [header]
+# license = "MIT"
+# timestamp = false
+# template = "Generated from {{{{ source }}}} by uft {{{{ version }}}}"
+"#)
+}
+
 /// Detect which programming languages are present in the repository
-fn detect_project_languages(repo_dir: &Path, supported_extensions: &[String]) -> Result<Vec<String>> {
+fn detect_project_languages(repo_dir: &Path, supported_extensions: &[String], scope: &PathScope) -> Result<Vec<String>> {
     let mut languages = std::collections::HashSet::new();
-    let source_files = find_source_files_excluding_tests(repo_dir, supported_extensions)?;
+    let source_files = find_source_files_excluding_tests(repo_dir, supported_extensions, scope)?;
     
     for file_path in source_files {
         if let Some(extension) = file_path.extension() {
@@ -1256,10 +3121,14 @@ fn extension_to_language(ext: &str) -> Option<String> {
 }
 
 /// Prompt user to choose testing frameworks for each detected language
-fn prompt_framework_choices(languages: &[String]) -> Result<HashMap<String, String>> {
+fn prompt_framework_choices(languages: &[String], project_config: &ProjectConfig) -> Result<HashMap<String, String>> {
     let mut choices = HashMap::new();
     
     for language in languages {
+        if let Some(framework) = project_config.framework(language) {
+            choices.insert(language.clone(), framework.to_string());
+            continue;
+        }
         let frameworks = get_available_frameworks(language);
         if frameworks.len() <= 1 {
             choices.insert(language.clone(), frameworks[0].clone());
@@ -1297,7 +3166,7 @@ fn get_available_frameworks(language: &str) -> Vec<String> {
         "java" => vec!["junit5".to_string(), "testng".to_string()],
         "javascript" | "typescript" => vec!["jest".to_string(), "mocha".to_string()],
         "python" => vec!["pytest".to_string(), "unittest".to_string()],
-        "rust" => vec!["cargo-test".to_string(), "nextest".to_string()],
+        "rust" => vec!["cargo-test".to_string(), "nextest".to_string(), "rstest".to_string()],
         "go" => vec!["testing".to_string(), "testify".to_string()],
         "php" => vec!["phpunit".to_string(), "pest".to_string()],
         "csharp" => vec!["nunit".to_string(), "xunit".to_string()],
@@ -1312,27 +3181,65 @@ fn get_default_framework(language: &str) -> String {
     get_available_frameworks(language)[0].clone()
 }
 
-/// Find source files excluding test directories
-fn find_source_files_excluding_tests(dir: &Path, supported_extensions: &[String]) -> Result<Vec<std::path::PathBuf>> {
+/// `--include`/`--exclude` globs narrowing a directory scan, matched against paths relative
+/// to the scanned directory
+#[derive(Default)]
+struct PathScope {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PathScope {
+    fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let build = |patterns: &[String]| -> Result<Option<GlobSet>> {
+            if patterns.is_empty() {
+                return Ok(None);
+            }
+            let mut builder = GlobSetBuilder::new();
+            for pattern in patterns {
+                builder.add(Glob::new(pattern).map_err(|e| UftError::Config(format!("Invalid glob {}: {}", pattern, e)))?);
+            }
+            Ok(Some(builder.build()?))
+        };
+        Ok(Self { include: build(include)?, exclude: build(exclude)? })
+    }
+
+    fn allows(&self, relative: &Path) -> bool {
+        self.include.as_ref().is_none_or(|include| include.is_match(relative))
+            && !self.exclude.as_ref().is_some_and(|exclude| exclude.is_match(relative))
+    }
+}
+
+/// Find source files excluding test directories, hidden files and whatever `.gitignore` and
+/// `.uftignore` files exclude
+fn find_source_files_excluding_tests(dir: &Path, supported_extensions: &[String], scope: &PathScope) -> Result<Vec<std::path::PathBuf>> {
     let mut source_files = Vec::new();
     
-    for entry in WalkDir::new(dir)
+    for entry in WalkBuilder::new(dir)
         .follow_links(false)
-        .into_iter()
+        .require_git(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .build()
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
+        let relative = path.strip_prefix(dir).unwrap_or(path);
         
-        // Skip directories, hidden files, and common non-source directories
-        if path.is_dir() || is_ignored_path(path) || is_test_path(path) {
+        // Skip directories and common non-source directories
+        if path.is_dir() || is_ignored_path(relative) || is_test_path(relative) || !scope.allows(relative) {
             continue;
         }
         
         // Check if file has supported extension
         if let Some(extension) = path.extension() {
             let ext_str = extension.to_string_lossy().to_lowercase();
-            if supported_extensions.contains(&ext_str) {
-                source_files.push(path.to_path_buf());
+            if !supported_extensions.contains(&ext_str) {
+                continue;
+            }
+            // Bundles, generated code and binaries only turn up patterns nobody tests
+            match sniff_source(path) {
+                Ok(Some(reason)) => debug!("⏭️  Skipping {} file: {}", reason, relative.display()),
+                _ => source_files.push(path.to_path_buf()),
             }
         }
     }
@@ -1358,192 +3265,302 @@ fn is_test_path(path: &Path) -> bool {
     false
 }
 
-/// Detect language of a specific file
-fn detect_file_language(file_path: &Path, supported_extensions: &[String], loader: &LanguageLoader) -> Result<String> {
-    if let Some(extension) = file_path.extension() {
-        let ext_str = extension.to_string_lossy().to_lowercase();
-        if let Some(language) = loader.get_supported_extensions().get(&ext_str) {
-            return Ok(language.clone());
-        }
+/// Check out `branch`, a local branch if there is one, else origin's. Uncommitted changes
+/// it would overwrite make this fail rather than be lost
+fn checkout_branch(repo: &Repository, branch: &str) -> Result<()> {
+    if let Ok(local) = repo.find_branch(branch, git2::BranchType::Local) {
+        let name = local.get().name().ok_or_else(|| anyhow::anyhow!("Branch name isn't UTF-8: {}", branch))?.to_string();
+        repo.checkout_tree(&local.get().peel(git2::ObjectType::Commit)?, None)?;
+        repo.set_head(&name)?;
+        return Ok(());
+    }
+    let (object, reference) = repo.revparse_ext(&format!("origin/{}", branch))?;
+    repo.checkout_tree(&object, None)?;
+    match reference {
+        Some(gref) => repo.set_head(gref.name().unwrap())?,
+        None => repo.set_head_detached(object.id())?,
+    }
+    Ok(())
+}
+
+/// URL of the origin remote of the repository at `path`
+fn origin_url(path: &Path) -> Option<String> {
+    Repository::open(path).ok()?.find_remote("origin").ok()?.url().map(str::to_string)
+}
+
+/// The remote's default branch, which a fresh clone checks out
+fn default_branch(repo: &Repository) -> Result<String> {
+    let remote_head = repo.find_reference("refs/remotes/origin/HEAD").ok()
+        .and_then(|reference| reference.symbolic_target().map(str::to_string))
+        .and_then(|target| target.strip_prefix("refs/remotes/origin/").map(str::to_string));
+    match remote_head {
+        Some(branch) => Ok(branch),
+        None => repo.head()?.shorthand().map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("Could not tell the repository's default branch")),
     }
-    Err(anyhow::anyhow!("Unsupported file type"))
 }
 
-/// Get the appropriate test file path for a source file
-fn get_test_file_path(repo_dir: &Path, source_file: &Path, language: &str, framework: &str) -> Result<std::path::PathBuf> {
-    let source_path = if source_file.is_absolute() {
-        source_file.to_path_buf()
-    } else {
-        repo_dir.join(source_file)
+/// Credentials for `url`: the token in `token_env`, which must then be set, else in the host's
+/// usual variable if that is, and `ssh_key`
+fn git_credentials(url: &str, token_env: Option<&str>, ssh_key: Option<&str>) -> Result<GitCredentials> {
+    let host = GitHost::from_url(url);
+    let token = match token_env {
+        Some(name) => Some(std::env::var(name).map_err(|_| UftError::Config(format!("--token-env names ${}, which isn't set", name)))?),
+        None => host.as_ref().and_then(|host| std::env::var(host.default_token_env()).ok()),
     };
+    let ssh_key = ssh_key.map(PathBuf::from);
+    if let Some(key) = ssh_key.as_ref().filter(|key| !key.is_file()) {
+        return Err(UftError::Config(format!("SSH key not found: {}", key.display())).into());
+    }
+    Ok(GitCredentials {
+        token,
+        token_user: host.map(|host| host.token_user().to_string()).unwrap_or_default(),
+        ssh_key,
+    })
+}
+
+/// Where to open the pull request for `url`, which needs a token to do it with
+fn pull_request_host(url: &str, credentials: &GitCredentials, token_env: Option<&str>) -> Result<GitHost> {
+    let host = GitHost::from_url(url)
+        .ok_or_else(|| UftError::Config(format!("Can't open a pull request for {}: only GitHub and GitLab are supported", url)))?;
+    if credentials.token.is_none() {
+        let token_env = token_env.unwrap_or(host.default_token_env());
+        return Err(UftError::Config(format!("--create-pr needs an access token in ${}", token_env)).into());
+    }
+    Ok(host)
+}
+
+/// Commit the files the run recorded in `manifest` on a new branch off `repo_dir`'s HEAD, push
+/// it and open a pull request into `base`, returning its URL; `None` when nothing was generated
+async fn open_generated_tests_pr(host: &GitHost, credentials: &GitCredentials, repo_dir: &Path, manifest: &GenerationManifest, base: &str, source_files: usize, test_cases: usize) -> Result<Option<String>> {
+    let token = credentials.token.as_deref().unwrap_or_default();
+    let repo = Repository::open(repo_dir)?;
+    let written: Vec<String> = manifest.files.iter().map(|entry| entry.path.clone()).collect();
+    let paths = changed_paths(&repo, &written)?;
+    if paths.is_empty() {
+        return Ok(None);
+    }
+
+    let branch = format!("uft/generated-tests-{}", SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs());
+    let message = generated_tests_message(&paths, source_files, test_cases);
+    commit_on_branch(&repo, &branch, &message, &paths)?;
+    push_branch(&repo, &branch, credentials)?;
+    info!("⬆️  Pushed {}", branch);
+
+    let (title, body) = message.split_once("\n\n").unwrap_or((&message, ""));
+    let request = PullRequest { branch: branch.clone(), base: base.to_string(), title: title.to_string(), body: body.to_string() };
+    host.open_pull_request(token, &request).await.map(Some)
+}
+
+/// The checkpoint to carry on from with `--resume`, else a fresh one replacing whatever an
+/// earlier interrupted run left
+fn start_checkpoint(root: &Path, resume: bool) -> Result<RunCheckpoint> {
+    if resume {
+        return RunCheckpoint::load(root);
+    }
+    if RunCheckpoint::path(root).exists() {
+        warn!("⚠️  Starting over; pass --resume to carry on the interrupted run instead");
+    }
+    Ok(RunCheckpoint::default())
+}
+
+/// Drop the files an earlier run already handled
+fn skip_completed(source_files: &mut Vec<PathBuf>, root: &Path, checkpoint: &RunCheckpoint) {
+    let file_count = source_files.len();
+    source_files.retain(|file| !checkpoint.is_completed(&file.strip_prefix(root).unwrap_or(file).to_string_lossy()));
+    if source_files.len() < file_count {
+        info!("⏩ Resuming: {} of {} files already done", file_count - source_files.len(), file_count);
+    }
+}
+
+/// How a directory run ends once `failed` of its `total` files failed: a generation error
+/// when all did, a partial failure when only some did
+fn dir_outcome(failed: usize, total: usize) -> Result<()> {
+    match failed {
+        0 => Ok(()),
+        _ if failed == total => Err(UftError::Generation(format!("All {} files failed", total)).into()),
+        _ => Err(UftError::PartialFailure { failed, total }.into()),
+    }
+}
+
+/// Report on every source file under `target_dir`
+async fn generation_report(target_dir: &Path, config_dir: &str) -> Result<GenerationReport> {
+    if !target_dir.is_dir() {
+        return Err(anyhow::anyhow!("Path is not a directory: {}", target_dir.display()));
+    }
     
-    let file_stem = source_path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut loader = LanguageLoader::new(config_dir.to_string());
+    let adapters = loader.load_all_languages()?;
+    let supported_extensions = get_supported_extensions(&loader);
+    let project_config = ProjectConfig::discover(target_dir)?;
+    let mut orchestrator = TestOrchestrator::new();
+    for (lang, adapter) in adapters {
+        let config = project_config.adapter_config(&lang);
+        orchestrator.register_adapter_with_config(lang, adapter, config);
+    }
+    orchestrator.set_project_config(project_config);
     
-    match language {
-        "java" => {
-            // Java: src/test/java/... mirrors src/main/java/...
-            let test_file_name = format!("{}Test.java", 
-                file_stem.chars().next().unwrap().to_uppercase().collect::<String>() + 
-                &file_stem[1..].to_string()
-            );
-            
-            if let Some(parent) = source_path.parent() {
-                Ok(parent.join("test").join(test_file_name))
-            } else {
-                Ok(repo_dir.join("test").join(test_file_name))
-            }
-        },
-        "javascript" | "typescript" => {
-            // JS/TS: __tests__ folder or .test.js alongside source
-            let ext = if language == "typescript" { "ts" } else { "js" };
-            let test_file_name = format!("{}.test.{}", file_stem, ext);
-            
-            if let Some(parent) = source_path.parent() {
-                Ok(parent.join("__tests__").join(test_file_name))
-            } else {
-                Ok(repo_dir.join("__tests__").join(test_file_name))
-            }
-        },
-        "python" => {
-            // Python: tests/ folder or test_ prefix
-            let test_file_name = format!("test_{}.py", file_stem);
-            if let Some(parent) = source_path.parent() {
-                Ok(parent.join("tests").join(test_file_name))
-            } else {
-                Ok(repo_dir.join("tests").join(test_file_name))
-            }
-        },
-        "rust" => {
-            // Rust: tests/ folder or inline tests
-            let test_file_name = format!("test_{}.rs", file_stem);
-            if let Some(parent) = source_path.parent() {
-                Ok(parent.join("tests").join(test_file_name))
-            } else {
-                Ok(repo_dir.join("tests").join(test_file_name))
+    let mut source_files = find_source_files_excluding_tests(target_dir, &supported_extensions, &PathScope::default())?;
+    source_files.retain(|file| !orchestrator.project_config().is_ignored(&file.to_string_lossy()));
+    info!("📝 Reporting on {} source files", source_files.len());
+    
+    let mut report = GenerationReport::default();
+    for file_path in source_files {
+        let relative_path = file_path.strip_prefix(target_dir).unwrap_or(&file_path).to_string_lossy().into_owned();
+        match report_file(&orchestrator, target_dir, &file_path, &relative_path, &supported_extensions, &loader).await {
+            Ok(file_report) => report.files.push(file_report),
+            Err(e) => warn!("  ⚠️  Skipping {}: {}", relative_path, e),
+        }
+    }
+    Ok(report)
+}
+
+/// Annotate the untested functions of `report`, whose files are relative to `target_dir`, and
+/// add it to the job summary
+fn report_to_ci(provider: CiProvider, target_dir: &Path, report: &GenerationReport) -> Result<()> {
+    match provider {
+        CiProvider::Github => {
+            for file in &report.files {
+                let path = target_dir.join(&file.source_file);
+                let source = fs::read_to_string(&path).unwrap_or_default();
+                let path = path.strip_prefix(".").unwrap_or(&path).display().to_string();
+                for annotation in untested_annotations(file, &path, &source) {
+                    println!("{}", annotation.to_workflow_command());
+                }
             }
-        },
-        "go" => {
-            // Go: _test.go suffix in same directory
-            let test_file_name = format!("{}_test.go", file_stem);
-            if let Some(parent) = source_path.parent() {
-                Ok(parent.join(test_file_name))
-            } else {
-                Ok(repo_dir.join(test_file_name))
+            match write_step_summary(&report.to_markdown())? {
+                Some(summary) => info!("📝 Job summary written to {}", summary.display()),
+                None => warn!("⚠️  GITHUB_STEP_SUMMARY isn't set; no job summary written"),
             }
-        },
-        _ => {
-            // Default: tests/ folder
-            let test_file_name = format!("test_{}.test", file_stem);
-            Ok(repo_dir.join("tests").join(test_file_name))
         }
     }
+    Ok(())
 }
 
-/// Generate test file content with specific framework
-fn generate_test_file_content_with_framework(test_suite: &unified_test_framework::TestSuite, framework: &str) -> Result<String> {
-    let mut content = String::new();
+/// Report on one source file: what it would get generated and what its existing test file
+/// (if any) leaves untested
+async fn report_file(orchestrator: &TestOrchestrator, root: &Path, file_path: &Path, relative_path: &str, supported_extensions: &[String], loader: &LanguageLoader) -> Result<FileReport> {
+    let language = detect_file_language(file_path, supported_extensions, loader)?;
+    let content = fs::read_to_string(file_path)?;
+    let path = file_path.to_string_lossy();
+    let patterns = orchestrator.analyze_file(&path, &content).await?;
+    let suite = orchestrator.generate_tests_for_file(&path, &content).await?;
+    let test_file = orchestrator.test_file_path(root, file_path, &language);
+    let existing_tests = read_existing_tests(&test_file, file_path);
+    Ok(FileReport::new(relative_path, &content, &patterns, &suite, &existing_tests))
+}
+
+/// The test files `dir` would write for the sources under `project_root` that exist, grouped by
+/// the runner that runs them and relative to `project_root`
+fn find_generated_test_files(project_root: &Path, loader: &LanguageLoader, project_config: &ProjectConfig) -> Result<BTreeMap<TestRunner, Vec<PathBuf>>> {
+    // Absolute, as the project config's output directory is
+    let project_root = &project_root.canonicalize()?;
+    let supported_extensions = get_supported_extensions(loader);
+    let mut test_files: BTreeMap<TestRunner, Vec<PathBuf>> = BTreeMap::new();
     
-    match (test_suite.language.as_str(), framework) {
-        ("java", "junit5") => {
-            content.push_str("import org.junit.jupiter.api.Test;\n");
-            content.push_str("import static org.junit.jupiter.api.Assertions.*;\n\n");
-            content.push_str(&format!("class {} {{\n\n", test_suite.name));
-            
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    @Test\n    void {}() {{\n        // {}\n        // TODO: Implement test logic\n    }}\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-            content.push_str("}\n");
-        },
-        ("java", "testng") => {
-            content.push_str("import org.testng.annotations.Test;\n");
-            content.push_str("import static org.testng.Assert.*;\n\n");
-            content.push_str(&format!("public class {} {{\n\n", test_suite.name));
-            
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    @Test\n    public void {}() {{\n        // {}\n        // TODO: Implement test logic\n    }}\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-            content.push_str("}\n");
-        },
-        ("javascript" | "typescript", "jest") => {
-            content.push_str("describe('Generated Tests', () => {\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "  test('{}', () => {{\n    // {}\n    // TODO: Implement test logic\n  }});\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-            content.push_str("});\n");
-        },
-        ("javascript" | "typescript", "mocha") => {
-            content.push_str("const { expect } = require('chai');\n\n");
-            content.push_str("describe('Generated Tests', () => {\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "  it('{}', () => {{\n    // {}\n    // TODO: Implement test logic\n  }});\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-            content.push_str("});\n");
-        },
-        ("python", "pytest") => {
-            content.push_str("import pytest\n\n");
-            content.push_str("class TestGenerated:\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    def {}(self):\n        \"\"\" {} \"\"\"\n        # TODO: Implement test logic\n        pass\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-        },
-        ("python", "unittest") => {
-            content.push_str("import unittest\n\n");
-            content.push_str("class TestGenerated(unittest.TestCase):\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    def {}(self):\n        \"\"\" {} \"\"\"\n        # TODO: Implement test logic\n        pass\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-            content.push_str("\nif __name__ == '__main__':\n    unittest.main()\n");
-        },
-        ("rust", _) => {
-            content.push_str("#[cfg(test)]\nmod tests {\n    use super::*;\n\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "    #[test]\n    fn {}() {{\n        // {}\n        // TODO: Implement test logic\n    }}\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-            content.push_str("}\n");
-        },
-        ("go", _) => {
-            content.push_str("package main\n\nimport (\n    \"testing\"\n)\n\n");
-            for test_case in &test_suite.test_cases {
-                content.push_str(&format!(
-                    "func {}(t *testing.T) {{\n    // {}\n    // TODO: Implement test logic\n}}\n\n",
-                    test_case.name, test_case.description
-                ));
-            }
-        },
-        _ => {
-            return generate_test_file_content(test_suite);
+    for source in find_source_files_excluding_tests(project_root, &supported_extensions, &PathScope::default())? {
+        let language = detect_file_language(&source, &supported_extensions, loader)?;
+        let test_file = project_config.test_file_path_with(project_root, &source, &language, loader.test_path(&language));
+        // A Rust source file counts once it holds its own tests
+        if !test_file.is_file() || (test_file == source && read_existing_tests(&test_file, &source).is_empty()) {
+            continue;
+        }
+        let relative = test_file.strip_prefix(project_root).unwrap_or(&test_file).to_path_buf();
+        let Some(runner) = TestRunner::for_language(&language) else {
+            info!("⏭️  No test runner for {}: {}", language, relative.display());
+            continue;
+        };
+        let files = test_files.entry(runner).or_default();
+        if !files.contains(&relative) {
+            files.push(relative);
         }
     }
     
-    Ok(content)
+    Ok(test_files)
+}
+
+/// The patterns `generate --symbol` picks: functions named one of `symbols`, and the methods
+/// of classes named one
+fn symbol_patterns(patterns: Vec<TestablePattern>, symbols: &[String]) -> Vec<TestablePattern> {
+    patterns.into_iter()
+        .filter(|pattern| symbols.iter().any(|symbol| {
+            symbol == pattern.subject_name() || pattern.context.class_name.as_ref() == Some(symbol)
+        }))
+        .collect()
 }
 
+/// A generated test file as `run --list` reports it
+#[derive(Debug, serde::Serialize)]
+struct ListedTestFile {
+    /// Relative to the project root, as the commands take it
+    file: PathBuf,
+    runner: &'static str,
+    /// Runs the whole file
+    command: Vec<String>,
+    tests: Vec<ListedTest>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ListedTest {
+    name: String,
+    /// 1-based line the test is defined on
+    line: usize,
+    /// Runs just this test
+    command: Vec<String>,
+}
+
+/// The tests in each of `test_files`, with the commands running them
+fn list_test_files(project_root: &Path, test_files: &BTreeMap<TestRunner, Vec<PathBuf>>) -> Result<Vec<ListedTestFile>> {
+    let project_root = project_root.canonicalize()?;
+    let mut listed = Vec::new();
+    for (runner, files) in test_files {
+        for file in files {
+            let content = fs::read_to_string(project_root.join(file))?;
+            let tests = test_locations(&content).into_iter()
+                .map(|(name, line)| ListedTest { command: runner.test_command(&project_root, file, &name), name, line })
+                .collect();
+            listed.push(ListedTestFile {
+                file: file.clone(),
+                runner: runner.name(),
+                command: runner.command(&project_root, std::slice::from_ref(file)),
+                tests,
+            });
+        }
+    }
+    Ok(listed)
+}
+
+/// Detect language of a specific file
+fn detect_file_language(file_path: &Path, supported_extensions: &[String], loader: &LanguageLoader) -> Result<String> {
+    if let Some(extension) = file_path.extension() {
+        let ext_str = extension.to_string_lossy().to_lowercase();
+        if let Some(language) = loader.get_supported_extensions().get(&ext_str) {
+            return Ok(language.clone());
+        }
+    }
+    Err(UftError::UnsupportedLanguage("Unsupported file type".to_string()).into())
+}
+
+/// Get the appropriate test file path for a source file
 /// Generate integration test file content
+/// File name for a language's integration tests, following its test discovery conventions
+fn integration_test_file_name(language: &str, file_stem: &str) -> String {
+    match language {
+        "python" => format!("test_{}_integration.py", file_stem),
+        "rust" => format!("{}_integration.rs", file_stem),
+        "go" => format!("{}_integration_test.go", file_stem),
+        "java" => format!("{}IntegrationTest.java", file_stem),
+        _ => format!("{}.integration.test.js", file_stem),
+    }
+}
+
 fn generate_integration_test_content(test_suite: &unified_test_framework::TestSuite) -> Result<String> {
     let mut content = String::new();
     
     match test_suite.language.as_str() {
+        "python" | "rust" | "go" | "java" if test_suite.test_code.is_some() => {
+            content.push_str(test_suite.test_code.as_deref().unwrap_or_default());
+        },
         "javascript" => {
             // Add imports
             for import in &test_suite.imports {
@@ -1553,6 +3570,10 @@ fn generate_integration_test_content(test_suite: &unified_test_framework::TestSu
             
             // Add setup/teardown hooks
             content.push_str("describe('Integration Tests', () => {\n");
+            for fixture in &test_suite.fixtures {
+                content.push_str(&fixture.code);
+                content.push('\n');
+            }
             content.push_str("  beforeAll(async () => {\n");
             content.push_str("    // Setup requirements:\n");
             for req in &test_suite.setup_requirements {
@@ -1574,9 +3595,13 @@ fn generate_integration_test_content(test_suite: &unified_test_framework::TestSu
                     test_case.name.replace('_', " ")
                 ));
                 content.push_str(&format!("    // {}\n", test_case.description));
-                content.push_str("    // TODO: Implement integration test logic\n");
-                content.push_str(&format!("    // Input: {}\n", test_case.input));
-                content.push_str(&format!("    // Expected: {}\n", test_case.expected_output));
+                if test_case.test_body.trim_start().starts_with("// TODO") {
+                    content.push_str("    // TODO: Implement integration test logic\n");
+                    content.push_str(&format!("    // Input: {}\n", test_case.input));
+                    content.push_str(&format!("    // Expected: {}\n", test_case.expected_output));
+                } else {
+                    content.push_str(&test_case.test_body);
+                }
                 content.push_str("  });\n\n");
             }
             
@@ -1595,7 +3620,7 @@ fn generate_integration_test_content(test_suite: &unified_test_framework::TestSu
 #[cfg(test)]
 mod tests {
     use super::*;
-    use unified_test_framework::{TestSuite, TestCase};
+    use unified_test_framework::{TestSuite, TestCase, TestCategory, TestType};
 
     fn create_test_suite(language: &str, framework: &str, test_cases: Vec<TestCase>) -> TestSuite {
         TestSuite {
@@ -1608,6 +3633,12 @@ mod tests {
                 "python" => vec!["import pytest".to_string()],
                 _ => vec![],
             },
+            test_type: TestType::Unit,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 80.0,
+            fixtures: vec![],
+            test_code: None,
         }
     }
 
@@ -1618,6 +3649,9 @@ mod tests {
             description: description.to_string(),
             input: serde_json::json!({}),
             expected_output: serde_json::json!({}),
+            test_body: String::new(),
+            assertions: vec![],
+            test_category: TestCategory::HappyPath,
         }
     }
 
@@ -1679,6 +3713,12 @@ mod tests {
             framework: "gtest".to_string(),
             test_cases: vec![test_case],
             imports: vec![],
+            test_type: TestType::Unit,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 70.0,
+            fixtures: vec![],
+            test_code: None,
         };
 
         let result = generate_test_file_content(&test_suite);
@@ -1767,6 +3807,7 @@ mod tests {
         assert!(build_gradle.exists());
         assert!(plugin_xml.exists());
         assert!(main_class.exists());
+        assert!(main_class.with_file_name("UftDaemon.java").exists());
         
         // Clean up
         std::fs::remove_dir_all(&temp_dir).ok();
@@ -1900,6 +3941,10 @@ mod tests {
         let package_json_content = std::fs::read_to_string(plugin_dir.join("package.json")).unwrap();
         assert!(package_json_content.contains("\"name\": \"unified-testing\""));
         assert!(package_json_content.contains("\"unifiedTesting.generateTests\""));
+        let package_json: serde_json::Value = serde_json::from_str(&package_json_content).unwrap();
+        let settings = &package_json["contributes"]["configuration"]["properties"];
+        assert_eq!(settings["unifiedTesting.overwrite"]["enum"], serde_json::json!(["", "merge", "force", "skip"]));
+        assert!(settings["unifiedTesting.frameworks"].is_object());
         
         // Check extension.ts content
         let extension_ts_content = std::fs::read_to_string(plugin_dir.join("src").join("extension.ts")).unwrap();
@@ -2004,7 +4049,7 @@ mod tests {
         
         if let Ok(cli) = cli {
             match cli.command {
-                Commands::Generate { path, output } => {
+                Commands::Generate { path, output, .. } => {
                     assert_eq!(path, "test.js");
                     assert_eq!(output, "tests/");
                 }
@@ -2023,7 +4068,7 @@ mod tests {
         
         if let Ok(cli) = cli {
             match cli.command {
-                Commands::Analyze { path } => {
+                Commands::Analyze { path, .. } => {
                     assert_eq!(path, "src/main.py");
                 }
                 _ => panic!("Expected Analyze command"),
@@ -2041,7 +4086,7 @@ mod tests {
         
         if let Ok(cli) = cli {
             match cli.command {
-                Commands::Generate { path, output } => {
+                Commands::Generate { path, output, .. } => {
                     assert_eq!(path, "test.rs");
                     assert_eq!(output, "my-tests/");
                 }
@@ -2050,6 +4095,693 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_generate_enforce_coverage_flag() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "app.py"]).unwrap();
+        match cli.command {
+            Commands::Generate { enforce_coverage, .. } => assert!(!enforce_coverage),
+            _ => panic!("Expected Generate command"),
+        }
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "app.py", "--enforce-coverage"]).unwrap();
+        match cli.command {
+            Commands::Generate { enforce_coverage, .. } => assert!(enforce_coverage),
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_generate_style_flag() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "app.js", "--style", "snapshot"]).unwrap();
+        match cli.command {
+            Commands::Generate { style, .. } => assert_eq!(style, TestStyle::Snapshot),
+            _ => panic!("Expected Generate command"),
+        }
+        
+        assert!(Cli::try_parse_from(vec!["unified-testing", "generate", "app.js", "--style", "fancy"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_async_runtime_flag() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "lib.rs", "--async-runtime", "async-std"]).unwrap();
+        match cli.command {
+            Commands::Generate { async_runtime, .. } => assert_eq!(async_runtime, AsyncRuntime::AsyncStd),
+            _ => panic!("Expected Generate command"),
+        }
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "src"]).unwrap();
+        match cli.command {
+            Commands::Dir { async_runtime, .. } => assert_eq!(async_runtime, AsyncRuntime::Tokio),
+            _ => panic!("Expected Dir command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_black_box_flag() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "pricing/discount.go", "--black-box"]).unwrap();
+        match cli.command {
+            Commands::Generate { black_box, .. } => assert!(black_box),
+            _ => panic!("Expected Generate command"),
+        }
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "."]).unwrap();
+        match cli.command {
+            Commands::Dir { black_box, .. } => assert!(!black_box),
+            _ => panic!("Expected Dir command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_placement_flag() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "src/parser.rs", "--placement", "integration"]).unwrap();
+        match cli.command {
+            Commands::Generate { placement, .. } => assert_eq!(placement, TestPlacement::Integration),
+            _ => panic!("Expected Generate command"),
+        }
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "src"]).unwrap();
+        match cli.command {
+            Commands::Dir { placement, .. } => assert_eq!(placement, TestPlacement::Inline),
+            _ => panic!("Expected Dir command"),
+        }
+        assert!(Cli::try_parse_from(vec!["unified-testing", "generate", "src/parser.rs", "--placement", "doctest"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_type_flag() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "web", "--type", "e2e"]).unwrap();
+        match cli.command {
+            Commands::Dir { test_type, .. } => assert_eq!(test_type, TestType::E2e),
+            _ => panic!("Expected Dir command"),
+        }
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "app.js"]).unwrap();
+        match cli.command {
+            Commands::Generate { test_type, .. } => assert_eq!(test_type, TestType::Unit),
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from(vec!["unified-testing", "generate", "app.js", "--type", "integration"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_integration_test_containers_flag() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "integration-test", "repo.py", "--containers", "compose"]).unwrap();
+        match cli.command {
+            Commands::IntegrationTest { containers, .. } => assert_eq!(containers, Some(ContainerSetup::Compose)),
+            _ => panic!("Expected IntegrationTest command"),
+        }
+        assert!(Cli::try_parse_from(vec!["unified-testing", "integration-test", "repo.py", "--containers", "vagrant"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_generate_execute_flag() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py"]).unwrap();
+        match cli.command {
+            Commands::Generate { execute, .. } => assert!(!execute),
+            _ => panic!("Expected Generate command"),
+        }
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py", "--execute"]).unwrap();
+        match cli.command {
+            Commands::Generate { execute, .. } => assert!(execute),
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_generate_symbols() {
+        use clap::Parser;
+        use unified_test_framework::{Context, FunctionPattern, SourceLocation};
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py", "--symbol", "add", "--symbol", "Shape"]).unwrap();
+        match cli.command {
+            Commands::Generate { symbols, .. } => assert_eq!(symbols, ["add", "Shape"]),
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py", "--symbol", "add", "--with-integration"]).is_err());
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py", "--framework", "unittest", "--min-confidence", "0.7"]).unwrap();
+        match cli.command {
+            Commands::Generate { framework, min_confidence, .. } => {
+                assert_eq!(framework.as_deref(), Some("unittest"));
+                assert_eq!(min_confidence, Some(0.7));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        let function = |name: &str, class: Option<&str>| TestablePattern {
+            id: name.to_string(),
+            pattern_type: PatternType::Function(FunctionPattern { name: name.to_string(), parameters: vec![], return_type: None }),
+            location: SourceLocation { file: "calc.py".to_string(), line: 1, column: 1 },
+            context: Context { function_name: Some(name.to_string()), class_name: class.map(String::from), module_name: None },
+            confidence: 0.9,
+        };
+        let patterns = vec![function("add", None), function("area", Some("Shape")), function("sub", None)];
+        let picked: Vec<String> = symbol_patterns(patterns, &["add".to_string(), "Shape".to_string()]).iter().map(|pattern| pattern.id.clone()).collect();
+        assert_eq!(picked, ["add", "area"]);
+    }
+
+    #[test]
+    fn test_cli_coverage_flag() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py", "--coverage", "lcov.info"]).unwrap();
+        match cli.command {
+            Commands::Generate { coverage, .. } => assert_eq!(coverage.as_deref(), Some("lcov.info")),
+            _ => panic!("Expected Generate command"),
+        }
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "src", "--coverage", "coverage.xml"]).unwrap();
+        match cli.command {
+            Commands::Dir { coverage, .. } => assert_eq!(coverage.as_deref(), Some("coverage.xml")),
+            _ => panic!("Expected Dir command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_verbosity_flags() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "src", "-vv"]).unwrap();
+        assert_eq!(cli.verbose, 2);
+        assert!(!cli.quiet);
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "--quiet", "analyze", "calc.py"]).unwrap();
+        assert!(cli.quiet);
+        assert!(Cli::try_parse_from(vec!["unified-testing", "analyze", "calc.py", "-q", "-v"]).is_err());
+        
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<_>>();
+        assert!(wants_quiet(&args("utf -q dir src")));
+        assert!(!wants_quiet(&args("utf dir src")));
+    }
+
+    #[test]
+    fn test_progress_timing_breakdown() {
+        let mut progress = GenerationProgress::with_bar(3, false);
+        progress.finish_file("src/a.py", "python", Duration::from_millis(10));
+        progress.finish_file("src/b.py", "python", Duration::from_millis(30));
+        progress.finish_file("src/lib.rs", "rust", Duration::from_millis(5));
+        assert_eq!(progress.timings["python"], LanguageTiming { files: 2, elapsed: Duration::from_millis(40) });
+        assert_eq!(
+            progress.timing_lines(Duration::from_secs(2)),
+            ["python: 2 files in 40.00ms (20.00ms/file)", "rust: 1 files in 5.00ms (5.00ms/file)", "Total: 3 files in 2.00s (1.5 files/sec)"]
+        );
+    }
+
+    #[test]
+    fn test_default_branch_follows_remote_head() {
+        let origin = tempfile::tempdir().unwrap();
+        let repo = Repository::init_opts(origin.path(), git2::RepositoryInitOptions::new().initial_head("develop")).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[]).unwrap();
+
+        let clone = tempfile::tempdir().unwrap();
+        let cloned = GitCredentials::default().clone(&origin.path().to_string_lossy(), &clone.path().join("repo")).unwrap();
+        assert_eq!(default_branch(&cloned).unwrap(), "develop");
+    }
+
+    #[test]
+    fn test_checkout_branch_prefers_local() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let initial = repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[]).unwrap();
+        repo.branch("feature", &repo.find_commit(initial).unwrap(), false).unwrap();
+
+        checkout_branch(&repo, "feature").unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+        assert!(checkout_branch(&repo, "missing").is_err());
+        assert_eq!(origin_url(dir.path()), None);
+    }
+
+    #[test]
+    fn test_dir_outcome_exit_codes() {
+        assert!(dir_outcome(0, 5).is_ok());
+        assert_eq!(UftError::exit_code_of(&dir_outcome(2, 5).unwrap_err()), 4);
+        assert_eq!(UftError::exit_code_of(&dir_outcome(5, 5).unwrap_err()), 1);
+        assert_eq!(PathScope::new(&["src/[".to_string()], &[]).err().map(|e| UftError::exit_code_of(&e)), Some(3));
+    }
+
+    #[test]
+    fn test_cli_ci_defaults() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "ci"]).unwrap();
+        match cli.command {
+            Commands::Ci { provider, artifacts, .. } => assert_eq!((provider, artifacts.as_str()), (CiProvider::Github, "uft-artifacts")),
+            _ => panic!("Expected Ci command"),
+        }
+        let cli = Cli::try_parse_from(vec!["unified-testing", "report", "--ci", "github"]).unwrap();
+        assert!(matches!(cli.command, Commands::Report { ci: Some(CiProvider::Github), .. }));
+    }
+
+    #[test]
+    fn test_cli_diff_defaults() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "diff"]).unwrap();
+        match cli.command {
+            Commands::Diff { base, path, .. } => assert_eq!((base.as_str(), path.as_str()), ("origin/main", ".")),
+            _ => panic!("Expected Diff command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_rollback_defaults() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "rollback"]).unwrap();
+        assert!(matches!(cli.command, Commands::Rollback { ref path, force: false } if path == "."));
+    }
+
+    #[test]
+    fn test_cli_overwrite_flags() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "src", "--merge", "--backup"]).unwrap();
+        match cli.command {
+            Commands::Dir { overwrite, .. } => {
+                assert_eq!(overwrite.policy(OverwritePolicy::Skip), OverwritePolicy::Merge);
+                assert!(overwrite.backup);
+            }
+            _ => panic!("Expected Dir command"),
+        }
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py"]).unwrap();
+        assert!(matches!(cli.command, Commands::Generate { overwrite, .. } if overwrite.policy(OverwritePolicy::Force) == OverwritePolicy::Force));
+        assert!(Cli::try_parse_from(vec!["unified-testing", "generate", "calc.py", "--force", "--skip"]).is_err());
+        assert!(Cli::try_parse_from(vec!["unified-testing", "bench", "calc.py", "--skip", "--merge"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_file_size_flags() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "src"]).unwrap();
+        assert!(matches!(cli.command, Commands::Dir { file_size: FileSizeArgs { max_file_size: DEFAULT_MAX_FILE_SIZE, oversized: OversizedFiles::Skip }, .. }));
+        let cli = Cli::try_parse_from(vec!["unified-testing", "git-repo", "repo", "--max-file-size", "256K", "--oversized", "sample"]).unwrap();
+        assert!(matches!(cli.command, Commands::GitRepo { file_size: FileSizeArgs { max_file_size: 262144, oversized: OversizedFiles::Sample }, .. }));
+        assert!(Cli::try_parse_from(vec!["unified-testing", "dir", "src", "--max-file-size", "big"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_templates_command() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "templates", "preview", "jest/async_test", "--data", "data.json"]).unwrap();
+        match cli.command {
+            Commands::Templates { action: TemplatesAction::Render { name, data } } => {
+                assert_eq!(name, "jest/async_test");
+                assert_eq!(data.as_deref(), Some("data.json"));
+            }
+            _ => panic!("Expected Templates render command"),
+        }
+        assert!(Cli::try_parse_from(vec!["unified-testing", "templates", "show"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_lang_add_command() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "lang", "add", "kotlin@1.2.0", "--registry", "index.json"]).unwrap();
+        match cli.command {
+            Commands::Lang { action: LangAction::Add { pack, registry, dir } } => {
+                assert_eq!(pack, "kotlin@1.2.0");
+                assert_eq!(registry.as_deref(), Some("index.json"));
+                assert_eq!(dir, None);
+            }
+            _ => panic!("Expected Lang add command"),
+        }
+    }
+
+    #[test]
+    fn test_language_capabilities() {
+        use clap::Parser;
+
+        let mut loader = LanguageLoader::new("./no_language_configs".to_string());
+        loader.load_all_languages().unwrap();
+        let python = language_capabilities(&loader, "python");
+        assert_eq!(python["adapter"], "built-in");
+        assert_eq!(python["extensions"], serde_json::json!(["py"]));
+        assert_eq!(python["default_framework"], "pytest");
+        assert_eq!(python["test_file"]["example"]["test"], "src/tests/test_example.py");
+        assert_eq!(python["integration_tests"], true);
+        assert_eq!(python["config_source"], "built-in");
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "languages", "--format", "json"]).unwrap();
+        assert!(matches!(cli.command, Commands::Languages { format: OutputFormat::Json, .. }));
+    }
+
+    #[test]
+    fn test_generate_from_stdin() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "generate", "-", "--lang", "python", "--stdout"]).unwrap();
+        match cli.command {
+            Commands::Generate { path, lang, stdout, .. } => {
+                assert_eq!(path, "-");
+                assert_eq!(lang.as_deref(), Some("python"));
+                assert!(stdout);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(wants_stdout(&["uft".to_string(), "generate".to_string(), "-".to_string()]));
+
+        let mut loader = LanguageLoader::new("./no_language_configs".to_string());
+        loader.load_all_languages().unwrap();
+        assert_eq!(language_extension(&loader, "python").unwrap(), "py");
+        assert_eq!(language_extension(&loader, "javascript").unwrap(), "js");
+        assert_eq!(language_extension(&loader, "tsx").unwrap(), "tsx");
+        assert_eq!(UftError::exit_code_of(&language_extension(&loader, "cobol").unwrap_err()), 2);
+    }
+
+    #[test]
+    fn test_cli_format_flag() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "analyze", "calc.py"]).unwrap();
+        match cli.command {
+            Commands::Analyze { format, .. } => assert_eq!(format, OutputFormat::Text),
+            _ => panic!("Expected Analyze command"),
+        }
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", "src", "--format", "json"]).unwrap();
+        match cli.command {
+            Commands::Dir { format, .. } => assert_eq!(format, OutputFormat::Json),
+            _ => panic!("Expected Dir command"),
+        }
+        
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<_>>();
+        assert!(wants_json(&args("utf generate calc.py --format json")));
+        assert!(wants_json(&args("utf analyze calc.py --format=json")));
+        assert!(!wants_json(&args("utf analyze calc.py --format text")));
+    }
+
+    #[test]
+    fn test_cli_api_tests_command() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "api-tests", "--spec", "openapi.yaml", "--language", "python"]).unwrap();
+        match cli.command {
+            Commands::ApiTests { spec, language, output, .. } => {
+                assert_eq!(spec, "openapi.yaml");
+                assert_eq!(language, "python");
+                assert_eq!(output, "api-tests/");
+            }
+            _ => panic!("Expected ApiTests command"),
+        }
+        assert!(Cli::try_parse_from(vec!["unified-testing", "api-tests", "--spec", "openapi.yaml"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_contract_command() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "contract", "src/client.js", "--provider", "users-api"]).unwrap();
+        match cli.command {
+            Commands::Contract { path, consumer, provider, .. } => {
+                assert_eq!(path, "src/client.js");
+                assert_eq!(consumer, None);
+                assert_eq!(provider.as_deref(), Some("users-api"));
+            }
+            _ => panic!("Expected Contract command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_bench_command() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "bench", "src/stats.rs"]).unwrap();
+        match cli.command {
+            Commands::Bench { path, config_dir, .. } => {
+                assert_eq!(path, "src/stats.rs");
+                assert_eq!(config_dir, "./language_configs");
+            }
+            _ => panic!("Expected Bench command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_watch_command() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "watch", "src", "--debounce", "200"]).unwrap();
+        match cli.command {
+            Commands::Watch { path, config_dir, debounce, .. } => {
+                assert_eq!(path, "src");
+                assert_eq!(config_dir, "./language_configs");
+                assert_eq!(debounce, 200);
+            }
+            _ => panic!("Expected Watch command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_init_command_scaffolds_project_config() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "init", "--force"]).unwrap();
+        match cli.command {
+            Commands::Init { path, force, .. } => {
+                assert_eq!(path, ".");
+                assert!(force);
+            }
+            _ => panic!("Expected Init command"),
+        }
+
+        let scaffold = scaffold_project_config(&["java".to_string(), "python".to_string()]);
+        let config = ProjectConfig::parse(&scaffold).unwrap();
+        assert_eq!(config.framework("java"), Some("junit5"));
+        assert_eq!(config.framework("python"), Some("pytest"));
+        assert!(config.ignore.is_empty());
+        assert!(scaffold.contains("{{ tests }}"));
+    }
+
+    #[test]
+    fn test_find_source_files_respects_ignore_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["src/app.py", "src/generated/models.py", "vendor/lib.py", "build_out/bundle.py", "tests/test_app.py", "node_modules/pkg/index.py", ".hidden/tool.py"] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "def f():\n    pass\n").unwrap();
+        }
+        fs::write(dir.path().join(".gitignore"), "build_out/\n").unwrap();
+        fs::write(dir.path().join(IGNORE_FILE), "src/generated/\n").unwrap();
+
+        let files = find_source_files_excluding_tests(dir.path(), &["py".to_string()], &PathScope::default()).unwrap();
+        let relative: Vec<_> = files.iter().map(|f| f.strip_prefix(dir.path()).unwrap().to_path_buf()).collect();
+        assert_eq!(relative, vec![PathBuf::from("src/app.py")]);
+    }
+
+    #[test]
+    fn test_find_source_files_skips_unscannable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("app.js", "function add(a, b) {\n  return a + b;\n}\n".to_string()),
+            ("vendor.min.js", "var a=1;\n".to_string()),
+            ("bundle.js", "function a(b){return b+1}var c=a(2);".repeat(100)),
+            ("schema.js", "// @generated by relay-compiler\nexport const query = {};\n".to_string()),
+            ("image.js", "GIF89a\0\0".to_string()),
+        ];
+        for (name, content) in files {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+
+        let found = find_source_files_excluding_tests(dir.path(), &["js".to_string()], &PathScope::default()).unwrap();
+        assert_eq!(found, vec![dir.path().join("app.js")]);
+    }
+
+    #[test]
+    fn test_include_and_exclude_globs_scope_scans() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "dir", ".", "--include", "services/api/**", "--include", "libs/**", "--exclude", "**/generated/**"]).unwrap();
+        let Commands::Dir { include, exclude, .. } = cli.command else {
+            panic!("Expected Dir command");
+        };
+        assert_eq!(include, vec!["services/api/**", "libs/**"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["services/api/app.py", "services/api/generated/client.py", "services/web/app.py", "libs/util.py"] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "def f():\n    pass\n").unwrap();
+        }
+        let scope = PathScope::new(&include, &exclude).unwrap();
+        let mut files: Vec<_> = find_source_files_excluding_tests(dir.path(), &["py".to_string()], &scope).unwrap()
+            .into_iter()
+            .map(|f| f.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec![PathBuf::from("libs/util.py"), PathBuf::from("services/api/app.py")]);
+        assert!(PathScope::new(&["src/[".to_string()], &[]).is_err());
+    }
+
+    #[test]
+    fn test_find_generated_test_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["calc.py", "tests/test_calc.py", "untested.py", "pkg/add.go", "pkg/add_test.go", "web/app.js", "src/lexer.rs"] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "").unwrap();
+        }
+        
+        fs::write(dir.path().join("src/parser.rs"), "pub fn parse() {}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_parse() {\n        parse();\n    }\n}\n").unwrap();
+
+        let loader = LanguageLoader::new("./no_language_configs".to_string());
+        let test_files = find_generated_test_files(dir.path(), &loader, &ProjectConfig::default()).unwrap();
+        assert_eq!(test_files.len(), 3);
+        assert_eq!(test_files[&TestRunner::Pytest], vec![PathBuf::from("tests/test_calc.py")]);
+        assert_eq!(test_files[&TestRunner::Go], vec![PathBuf::from("pkg/add_test.go")]);
+        assert_eq!(test_files[&TestRunner::Cargo], vec![PathBuf::from("src/parser.rs")]);
+
+        fs::write(dir.path().join("tests/test_calc.py"), "import pytest\n\n\ndef test_add():\n    assert add(1, 2) == 3\n").unwrap();
+        let listed = list_test_files(dir.path(), &test_files).unwrap();
+        let calc = listed.iter().find(|file| file.runner == "pytest").unwrap();
+        assert_eq!(calc.tests.len(), 1);
+        assert_eq!((calc.tests[0].name.as_str(), calc.tests[0].line), ("test_add", 4));
+        assert_eq!(calc.tests[0].command, ["python3", "-m", "pytest", "-q", "tests/test_calc.py", "-k", "test_add"]);
+        let parser = listed.iter().find(|file| file.runner == "cargo test").unwrap();
+        assert_eq!(parser.command, ["cargo", "test", "--lib", "parser::tests"]);
+        assert_eq!((parser.tests[0].line, &parser.tests[0].command[3]), (8, &"parser::tests::test_parse".to_string()));
+    }
+
+    #[test]
+    fn test_cli_report_command() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "report", "src", "--markdown", "comment.md"]).unwrap();
+        match cli.command {
+            Commands::Report { path, html, markdown, .. } => {
+                assert_eq!(path, "src");
+                assert_eq!(html, "uft-report.html");
+                assert_eq!(markdown, "comment.md");
+            }
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_strip_install_lines() {
+        let profile = format!("alias ll='ls -l'\n\n{}\nexport PATH=\"$PATH:/home/me/.cargo/bin\"\nexport EDITOR=vim\n", INSTALL_MARKER);
+        assert_eq!(strip_install_lines(&profile).unwrap(), "alias ll='ls -l'\nexport EDITOR=vim\n");
+        
+        let fish = format!("\n{}\nset -gx PATH $PATH /home/me/.cargo/bin\n", INSTALL_MARKER);
+        assert_eq!(strip_install_lines(&fish).unwrap(), "");
+        
+        assert!(strip_install_lines("export PATH=\"$PATH:/home/me/.cargo/bin\"\n").is_none());
+    }
+
+    #[test]
+    fn test_cli_server_command() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "server"]).unwrap();
+        match cli.command {
+            Commands::Server { config_dir } => assert_eq!(config_dir, "./language_configs"),
+            _ => panic!("Expected Server command"),
+        }
+
+        let cli = Cli::try_parse_from(vec!["unified-testing", "daemon", "--jsonrpc"]).unwrap();
+        assert!(matches!(cli.command, Commands::Daemon { jsonrpc: true, .. }));
+        assert!(Cli::try_parse_from(vec!["unified-testing", "daemon"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_tests_merges_new_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("math.py");
+        fs::write(&source, "def add(a, b):\n    return a + b\n").unwrap();
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(unified_test_framework::PythonAdapter::new()));
+
+        let mut manifest = GenerationManifest::new(dir.path(), "watch");
+        let mut saved = HashMap::new();
+        let (outcome, test_file) = regenerate_tests(&orchestrator, dir.path(), &source, &OverwriteArgs::default(), &mut manifest, &mut saved).await.unwrap();
+        assert!(matches!(outcome, WatchOutcome::Created(_)));
+        assert_eq!(test_file, dir.path().join("tests").join("test_math.py"));
+        let (outcome, _) = regenerate_tests(&orchestrator, dir.path(), &source, &OverwriteArgs::default(), &mut manifest, &mut saved).await.unwrap();
+        assert!(matches!(outcome, WatchOutcome::UpToDate));
+        let (outcome, _) = regenerate_tests(&orchestrator, dir.path(), &source, &OverwriteArgs::default(), &mut manifest, &mut HashMap::new()).await.unwrap();
+        assert!(matches!(outcome, WatchOutcome::UpToDate));
+
+        let edited = fs::read_to_string(&test_file).unwrap().replace("class TestGenerated:\n", "class TestGenerated:\n    # kept across regeneration\n");
+        fs::write(&test_file, &edited).unwrap();
+        fs::write(&source, "def add(a, b):\n    return a + b\n\ndef negate(n):\n    return -n\n").unwrap();
+        let (outcome, _) = regenerate_tests(&orchestrator, dir.path(), &source, &OverwriteArgs::default(), &mut manifest, &mut saved).await.unwrap();
+        assert!(matches!(outcome, WatchOutcome::Merged(count) if count > 0));
+        let merged = fs::read_to_string(&test_file).unwrap();
+        assert!(merged.contains("    # kept across regeneration\n") && merged.contains("negate"));
+
+        assert_eq!(manifest.files.len(), 1);
+        assert!(manifest.files[0].patterns.contains(&"math.py#negate".to_string()));
+        assert_eq!(manifest.rollback(false).unwrap().deleted, ["tests/test_math.py"]);
+        assert!(!test_file.exists());
+    }
+
+    #[tokio::test]
+    async fn test_regenerating_inline_rust_tests_adds_none() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n").unwrap();
+        let source = dir.path().join("src/util.rs");
+        fs::write(&source, "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n").unwrap();
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("rust".to_string(), Box::new(unified_test_framework::RustAdapter::new()));
+        let mut manifest = GenerationManifest::new(dir.path(), "generate");
+
+        let (outcome, test_file) = regenerate_tests(&orchestrator, dir.path(), &source, &OverwriteArgs::default(), &mut manifest, &mut HashMap::new()).await.unwrap();
+        assert!(matches!(outcome, WatchOutcome::Created(_) | WatchOutcome::Merged(_)));
+        assert_eq!(test_file, source);
+        let first = fs::read_to_string(&source).unwrap();
+        let count = test_names(&first).len();
+        assert!(count > 0);
+
+        // A full run again, as a second `generate` would do, then one from the written file as
+        // `watch` would see it
+        let (outcome, _) = regenerate_tests(&orchestrator, dir.path(), &source, &OverwriteArgs::default(), &mut manifest, &mut HashMap::new()).await.unwrap();
+        assert!(matches!(outcome, WatchOutcome::UpToDate));
+        let mut saved = HashMap::from([(source.clone(), "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n".to_string())]);
+        let (outcome, _) = regenerate_tests(&orchestrator, dir.path(), &source, &OverwriteArgs::default(), &mut manifest, &mut saved).await.unwrap();
+        assert!(matches!(outcome, WatchOutcome::UpToDate));
+        let second = fs::read_to_string(&source).unwrap();
+        assert_eq!(test_names(&second).len(), count);
+        assert!(!second.contains("fn test_test_"));
+
+        let written = HashMap::from([(source.clone(), second.clone())]);
+        assert!(is_own_write(&written, &source));
+        fs::write(&source, second.replace("a + b", "b + a")).unwrap();
+        assert!(!is_own_write(&written, &source));
+    }
+
+    #[test]
+    fn test_cli_fuzz_command() {
+        use clap::Parser;
+        
+        let cli = Cli::try_parse_from(vec!["unified-testing", "fuzz", "src/parser.rs"]).unwrap();
+        match cli.command {
+            Commands::Fuzz { path, .. } => assert_eq!(path, "src/parser.rs"),
+            _ => panic!("Expected Fuzz command"),
+        }
+    }
+
     #[test]
     fn test_cli_invalid_command() {
         use clap::Parser;
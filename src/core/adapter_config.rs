@@ -0,0 +1,290 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How generated assertions are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AssertStyle {
+    /// Framework matcher style, e.g. jest `expect(x).toBe(y)`
+    #[default]
+    Expect,
+    /// Plain assertion calls, e.g. node `assert.strictEqual(x, y)` or pytest `assert x == y`
+    Assert,
+}
+
+/// Overall shape of generated test bodies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TestStyle {
+    /// Assertions on the result's type and basic properties
+    #[default]
+    Standard,
+    /// Snapshot assertions for functions returning objects/structs
+    /// (jest `toMatchSnapshot`, `insta`, `syrupy`)
+    Snapshot,
+    /// One table-driven test per function instead of separate near-identical tests
+    /// (`pytest.mark.parametrize`, `rstest` cases, Go table tests, JUnit `@ParameterizedTest`)
+    Parameterized,
+}
+
+impl FromStr for TestStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "standard" => Ok(TestStyle::Standard),
+            "snapshot" => Ok(TestStyle::Snapshot),
+            "parameterized" => Ok(TestStyle::Parameterized),
+            other => Err(format!("Unknown test style: {} (expected standard, snapshot or parameterized)", other)),
+        }
+    }
+}
+
+impl fmt::Display for TestStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestStyle::Standard => write!(f, "standard"),
+            TestStyle::Snapshot => write!(f, "snapshot"),
+            TestStyle::Parameterized => write!(f, "parameterized"),
+        }
+    }
+}
+
+/// Runtime whose test attribute drives generated async tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AsyncRuntime {
+    /// `#[tokio::test]`
+    #[default]
+    Tokio,
+    /// `#[async_std::test]`
+    AsyncStd,
+}
+
+impl FromStr for AsyncRuntime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "tokio" => Ok(AsyncRuntime::Tokio),
+            "async-std" => Ok(AsyncRuntime::AsyncStd),
+            other => Err(format!("Unknown async runtime: {} (expected tokio or async-std)", other)),
+        }
+    }
+}
+
+impl fmt::Display for AsyncRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsyncRuntime::Tokio => write!(f, "tokio"),
+            AsyncRuntime::AsyncStd => write!(f, "async-std"),
+        }
+    }
+}
+
+/// Where generated unit tests go, for languages with more than one place for them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TestPlacement {
+    /// In the source file: a Rust `#[cfg(test)] mod tests` after its code, which can reach
+    /// private items through `use super::*`
+    #[default]
+    Inline,
+    /// A file in the crate's `tests/` directory, importing the module under test by its path
+    /// from the crate root; only public functions get tests
+    Integration,
+}
+
+impl FromStr for TestPlacement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "inline" => Ok(TestPlacement::Inline),
+            "integration" => Ok(TestPlacement::Integration),
+            other => Err(format!("Unknown test placement: {} (expected inline or integration)", other)),
+        }
+    }
+}
+
+impl fmt::Display for TestPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestPlacement::Inline => write!(f, "inline"),
+            TestPlacement::Integration => write!(f, "integration"),
+        }
+    }
+}
+
+/// How integration suites get the databases and brokers their tests connect to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerSetup {
+    /// Start each service from the suite with Testcontainers
+    Testcontainers,
+    /// Describe the services in a `docker-compose.test.yml` started before the tests
+    Compose,
+}
+
+impl FromStr for ContainerSetup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "testcontainers" => Ok(ContainerSetup::Testcontainers),
+            "compose" | "dockercompose" => Ok(ContainerSetup::Compose),
+            other => Err(format!("Unknown container setup: {} (expected testcontainers or compose)", other)),
+        }
+    }
+}
+
+impl fmt::Display for ContainerSetup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerSetup::Testcontainers => write!(f, "testcontainers"),
+            ContainerSetup::Compose => write!(f, "compose"),
+        }
+    }
+}
+
+/// Per-adapter knobs controlling the shape of generated tests
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdapterConfig {
+    /// Assertion style; adapters whose framework has a single idiom (pytest, cargo test) ignore it
+    pub assert_style: AssertStyle,
+    /// Maximum number of non-happy-path tests per function (`None` keeps every generated case)
+    pub edge_cases_per_function: Option<usize>,
+    /// Whether to emit error-handling tests
+    pub generate_error_tests: bool,
+    /// Prefix for generated test names; `None` keeps the adapter's convention
+    pub naming_prefix: Option<String>,
+    pub style: TestStyle,
+    /// Runtime for tests of async functions, where the language has more than one
+    pub async_runtime: AsyncRuntime,
+    /// Where unit tests go, where the language has more than one place (Rust)
+    pub placement: TestPlacement,
+    /// How integration suites start their databases and brokers; `None` leaves it to the
+    /// setup requirements
+    pub containers: Option<ContainerSetup>,
+    /// Test only the exported API, from a separate test package where the language has one
+    /// (Go's `foo_test`)
+    pub black_box: bool,
+}
+
+impl Default for AdapterConfig {
+    fn default() -> Self {
+        Self {
+            assert_style: AssertStyle::Expect,
+            edge_cases_per_function: None,
+            generate_error_tests: true,
+            naming_prefix: None,
+            style: TestStyle::Standard,
+            async_runtime: AsyncRuntime::Tokio,
+            placement: TestPlacement::Inline,
+            containers: None,
+            black_box: false,
+        }
+    }
+}
+
+impl AdapterConfig {
+    /// Default configuration for a built-in language
+    pub fn for_language(language: &str) -> Self {
+        let assert_style = match language {
+            "javascript" | "typescript" => AssertStyle::Expect,
+            _ => AssertStyle::Assert,
+        };
+
+        Self {
+            assert_style,
+            ..Self::default()
+        }
+    }
+
+    /// Build a test name from the configured prefix, falling back to the adapter's default
+    pub fn test_name(&self, default_prefix: &str, rest: &str) -> String {
+        let prefix = self.naming_prefix.as_deref().unwrap_or(default_prefix);
+        format!("{}{}", prefix, rest)
+    }
+
+    /// Trim a list of edge-case tests down to the configured limit
+    pub fn limit_edge_cases<T>(&self, mut edge_cases: Vec<T>) -> Vec<T> {
+        if let Some(limit) = self.edge_cases_per_function {
+            edge_cases.truncate(limit);
+        }
+        edge_cases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = AdapterConfig::default();
+        assert_eq!(config.assert_style, AssertStyle::Expect);
+        assert!(config.generate_error_tests);
+        assert!(config.edge_cases_per_function.is_none());
+        assert!(config.naming_prefix.is_none());
+    }
+
+    #[test]
+    fn test_for_language_assert_style() {
+        assert_eq!(AdapterConfig::for_language("javascript").assert_style, AssertStyle::Expect);
+        assert_eq!(AdapterConfig::for_language("python").assert_style, AssertStyle::Assert);
+        assert_eq!(AdapterConfig::for_language("rust").assert_style, AssertStyle::Assert);
+    }
+
+    #[test]
+    fn test_test_name_prefix() {
+        let mut config = AdapterConfig::default();
+        assert_eq!(config.test_name("test_", "add_works"), "test_add_works");
+
+        config.naming_prefix = Some("it_".to_string());
+        assert_eq!(config.test_name("test_", "add_works"), "it_add_works");
+    }
+
+    #[test]
+    fn test_test_style_from_str() {
+        assert_eq!("snapshot".parse::<TestStyle>(), Ok(TestStyle::Snapshot));
+        assert_eq!("Standard".parse::<TestStyle>(), Ok(TestStyle::Standard));
+        assert!("table".parse::<TestStyle>().is_err());
+        assert_eq!("parameterized".parse::<TestStyle>(), Ok(TestStyle::Parameterized));
+        assert_eq!(TestStyle::Snapshot.to_string(), "snapshot");
+    }
+
+    #[test]
+    fn test_async_runtime_from_str() {
+        assert_eq!("tokio".parse::<AsyncRuntime>(), Ok(AsyncRuntime::Tokio));
+        assert_eq!("async_std".parse::<AsyncRuntime>(), Ok(AsyncRuntime::AsyncStd));
+        assert!("smol".parse::<AsyncRuntime>().is_err());
+        assert_eq!(AsyncRuntime::AsyncStd.to_string(), "async-std");
+    }
+
+    #[test]
+    fn test_test_placement_from_str() {
+        assert_eq!("inline".parse::<TestPlacement>(), Ok(TestPlacement::Inline));
+        assert_eq!("Integration".parse::<TestPlacement>(), Ok(TestPlacement::Integration));
+        assert!("doctest".parse::<TestPlacement>().is_err());
+        assert_eq!(TestPlacement::Integration.to_string(), "integration");
+    }
+
+    #[test]
+    fn test_container_setup_from_str() {
+        assert_eq!("testcontainers".parse::<ContainerSetup>(), Ok(ContainerSetup::Testcontainers));
+        assert_eq!("docker-compose".parse::<ContainerSetup>(), Ok(ContainerSetup::Compose));
+        assert!("podman".parse::<ContainerSetup>().is_err());
+    }
+
+    #[test]
+    fn test_limit_edge_cases() {
+        let mut config = AdapterConfig::default();
+        assert_eq!(config.limit_edge_cases(vec![1, 2, 3]).len(), 3);
+
+        config.edge_cases_per_function = Some(1);
+        assert_eq!(config.limit_edge_cases(vec![1, 2, 3]), vec![1]);
+    }
+}
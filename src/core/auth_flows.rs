@@ -0,0 +1,410 @@
+use regex::Regex;
+
+/// What an authentication call does
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthFlow {
+    /// A JWT is signed with `secret`, the key expression as written
+    IssueToken { secret: String, algorithm: String },
+    /// A JWT is checked against `secret`
+    VerifyToken { secret: String, algorithm: String },
+    HashPassword,
+    VerifyPassword,
+    /// An OAuth authorization URL is built
+    OAuthAuthorize,
+}
+
+/// An authentication call and the function it sits in
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthCall {
+    pub flow: AuthFlow,
+    pub function: String,
+    /// Parameters of the function as written, e.g. `token: &str` or `password=None`
+    pub params: Vec<String>,
+    /// Return type, when the language declares one
+    pub returns: Option<String>,
+    /// The function catches the errors the call raises without raising its own
+    pub swallows_errors: bool,
+}
+
+/// A route served only behind authentication middleware, a dependency or a decorator
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedRoute {
+    /// Upper-case HTTP method
+    pub method: String,
+    /// Path with its parameters filled in, e.g. `/users/1`
+    pub path: String,
+}
+
+impl AuthCall {
+    /// Index of the parameter whose name contains one of `hints`, or of the only parameter
+    pub fn param_index(&self, hints: &[&str]) -> Option<usize> {
+        let names: Vec<String> = self.params.iter().map(|p| param_name(p).to_lowercase()).collect();
+        names.iter().position(|name| hints.iter().any(|hint| name.contains(hint)))
+            .or_else(|| (names.len() == 1).then_some(0))
+    }
+
+    /// Positions of the candidate password and the stored hash among a verifier's parameters;
+    /// unnamed pairs follow bcrypt's `(password, hash)` order
+    pub fn verify_param_indexes(&self) -> Option<(usize, usize)> {
+        let names: Vec<String> = self.params.iter().map(|p| param_name(p).to_lowercase()).collect();
+        let hash = names.iter().position(|n| ["hash", "digest", "stored", "encoded"].iter().any(|h| n.contains(h))).unwrap_or(1);
+        let password = (0..names.len()).filter(|i| *i != hash)
+            .find(|i| ["password", "pass", "pwd", "plain", "candidate"].iter().any(|p| names[*i].contains(p)))
+            .or_else(|| (0..names.len()).find(|i| *i != hash))?;
+        (hash < names.len()).then_some((password, hash))
+    }
+
+    /// Name of the parameter the secret is read from, when the key is passed in
+    pub fn secret_param(&self) -> Option<&str> {
+        let secret = match &self.flow {
+            AuthFlow::IssueToken { secret, .. } | AuthFlow::VerifyToken { secret, .. } => secret,
+            _ => return None,
+        };
+        let root = secret_root(secret);
+        self.params.iter().map(|p| param_name(p)).find(|name| *name == root)
+    }
+}
+
+impl ProtectedRoute {
+    /// Identifier-safe form of the method and path for test names
+    pub fn slug(&self) -> String {
+        let path = Regex::new(r"\W+").unwrap().replace_all(&self.path, "_").trim_matches('_').to_lowercase();
+        format!("{}_{}", self.method.to_lowercase(), if path.is_empty() { "root" } else { &path })
+    }
+}
+
+/// Bare name of a parameter: `mut token: &str`, `password=None` and `token = ''` give the name
+pub fn param_name(param: &str) -> &str {
+    param.split([':', '=']).next().unwrap_or(param).trim().trim_start_matches("mut ").trim_start_matches(['*', '&']).trim()
+}
+
+/// Environment variable a secret expression reads, e.g. `JWT_SECRET` from
+/// `process.env.JWT_SECRET`, `os.environ["JWT_SECRET"]` or `env::var("JWT_SECRET")`
+pub fn secret_env_var(secret: &str) -> Option<String> {
+    let env_regex = Regex::new(r#"process\.env\.(\w+)|os\.(?:environ(?:\.get)?|getenv)\s*[\[(]\s*["'](\w+)|env::var\(\s*"(\w+)""#).unwrap();
+    let cap = env_regex.captures(secret)?;
+    cap.get(1).or_else(|| cap.get(2)).or_else(|| cap.get(3)).map(|m| m.as_str().to_string())
+}
+
+/// First identifier of a key expression: `SECRET` from `SECRET.as_bytes()`
+fn secret_root(secret: &str) -> &str {
+    let secret = secret.trim_start_matches('&');
+    let secret = secret.strip_prefix("b\"").unwrap_or(secret).trim_start_matches(['"', '\'']);
+    secret.split(|c: char| !c.is_alphanumeric() && c != '_').next().unwrap_or(secret)
+}
+
+/// Top-level arguments of the call whose `(` is at `open`
+pub(crate) fn call_args(source: &str, open: usize) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut current = String::new();
+    for c in source[open..].chars() {
+        if let Some(q) = quote {
+            current.push(c);
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                if depth > 1 {
+                    current.push(c);
+                }
+            }
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                current.push(c);
+            }
+            ',' if depth == 1 => args.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        args.push(current.trim().to_string());
+    }
+    args
+}
+
+/// `HS256`, `RS256`, ... named in a call's text, or `HS256`, every library's default
+fn algorithm_in(text: &str) -> String {
+    Regex::new(r"\b((?:HS|RS|ES|PS)(?:256|384|512))\b").unwrap()
+        .captures(text)
+        .map(|cap| cap[1].to_string())
+        .unwrap_or_else(|| "HS256".to_string())
+}
+
+/// A function definition: its name, parameters, return type and where its body starts
+pub(crate) struct Definition {
+    pub(crate) name: String,
+    pub(crate) params: Vec<String>,
+    pub(crate) returns: Option<String>,
+    pub(crate) start: usize,
+}
+
+impl Definition {
+    /// Source from this definition up to the next one
+    pub(crate) fn body<'a>(&self, source: &'a str, defs: &[Definition]) -> &'a str {
+        let end = defs.iter().map(|d| d.start).filter(|start| *start > self.start).min().unwrap_or(source.len());
+        &source[self.start..end]
+    }
+}
+
+fn definitions(source: &str, regex: &Regex) -> Vec<Definition> {
+    regex.captures_iter(source).filter_map(|cap| {
+        let name = cap.name("name")?.as_str();
+        if matches!(name, "if" | "for" | "while" | "switch" | "catch" | "function" | "return") {
+            return None;
+        }
+        Some(Definition {
+            name: name.to_string(),
+            params: cap.name("params")
+                .map(|p| p.as_str().split(',').map(str::trim).filter(|p| !p.is_empty()).map(String::from).collect())
+                .unwrap_or_default(),
+            returns: cap.name("returns").map(|r| r.as_str().trim().to_string()),
+            start: cap.get(0).unwrap().start(),
+        })
+    }).collect()
+}
+
+/// `function f(...)`, `f = (...) =>` and method definitions in JavaScript
+pub(crate) fn js_definitions(source: &str) -> Vec<Definition> {
+    let def_regex = Regex::new(r"(?m)(?:function\s+(?P<name>\w+)\s*\((?P<params>[^)]*)\)|\b(?P<name2>\w+)\s*=\s*(?:async\s+)?(?:function\s*)?\((?P<params2>[^)]*)\)\s*=>|^\s*(?:async\s+)?(?P<name3>\w+)\s*\((?P<params3>[^)]*)\)\s*\{)").unwrap();
+    // The alternatives name their groups apart; fold them into `name`/`params`
+    def_regex.captures_iter(source).filter_map(|cap| {
+        let name = cap.name("name").or(cap.name("name2")).or(cap.name("name3"))?.as_str();
+        let params = cap.name("params").or(cap.name("params2")).or(cap.name("params3")).map(|p| p.as_str()).unwrap_or("");
+        (!matches!(name, "if" | "for" | "while" | "switch" | "catch" | "function" | "return")).then(|| Definition {
+            name: name.to_string(),
+            params: params.split(',').map(str::trim).filter(|p| !p.is_empty()).map(String::from).collect(),
+            returns: None,
+            start: cap.get(0).unwrap().start(),
+        })
+    }).collect()
+}
+
+/// `def` and `async def` definitions in Python, without their `self`/`cls` parameter
+pub(crate) fn python_definitions(source: &str) -> Vec<Definition> {
+    let def_regex = Regex::new(r"(?m)^[ \t]*(?:async\s+)?def\s+(?P<name>\w+)\s*\((?P<params>[^)]*)\)(?:\s*->\s*(?P<returns>[^:]+))?:").unwrap();
+    definitions(source, &def_regex).into_iter()
+        .map(|mut d| {
+            d.params.retain(|p| p != "self" && p != "cls");
+            d
+        })
+        .collect()
+}
+
+/// `fn` definitions in Rust, with their return types
+pub(crate) fn rust_definitions(source: &str) -> Vec<Definition> {
+    let def_regex = Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?fn\s+(?P<name>\w+)(?:<[^>]*>)?\s*\((?P<params>[^)]*)\)(?:\s*->\s*(?P<returns>[^{]+))?").unwrap();
+    definitions(source, &def_regex)
+}
+
+/// A call pattern and the flow it starts, read from the source at the call's `(`
+type FlowMatcher = (Regex, fn(&str, usize) -> Option<AuthFlow>);
+
+/// Calls matched by `flows`, each attributed to the last definition before it and reported
+/// once per function and kind of flow
+fn collect_calls(
+    source: &str,
+    defs: &[Definition],
+    flows: &[FlowMatcher],
+    swallows: impl Fn(&str) -> bool,
+) -> Vec<AuthCall> {
+    let mut calls: Vec<AuthCall> = Vec::new();
+    for (regex, flow_at) in flows {
+        for m in regex.find_iter(source) {
+            let Some(index) = defs.iter().rposition(|d| d.start < m.start()) else {
+                continue;
+            };
+            let Some(flow) = flow_at(source, m.end() - 1) else {
+                continue;
+            };
+            let def = &defs[index];
+            let flow = inline_local_secret(flow, &source[def.start..m.start()]);
+            let end = defs.get(index + 1).map(|d| d.start).unwrap_or(source.len());
+            if calls.iter().any(|c| c.function == def.name && std::mem::discriminant(&c.flow) == std::mem::discriminant(&flow)) {
+                continue;
+            }
+            calls.push(AuthCall {
+                flow,
+                function: def.name.clone(),
+                params: def.params.clone(),
+                returns: def.returns.clone(),
+                swallows_errors: swallows(&source[def.start..end]),
+            });
+        }
+    }
+    calls.sort_by_key(|c| defs.iter().position(|d| d.name == c.function));
+    calls
+}
+
+/// Replace a key read from a local binding with the bound expression, so
+/// `let secret = env::var("JWT_SECRET")?; ... from_secret(secret.as_bytes())` names the variable
+fn inline_local_secret(flow: AuthFlow, body: &str) -> AuthFlow {
+    let inline = |secret: String| -> String {
+        let root = secret_root(&secret);
+        if root.is_empty() || !secret.trim_start_matches('&').starts_with(root) {
+            return secret;
+        }
+        let binding = Regex::new(&format!(r"(?m)^[ \t]*(?:(?:let|const|var)\s+(?:mut\s+)?)?{}\s*(?::[^=\n]+)?=\s*([^;\n]+)", regex::escape(root))).unwrap();
+        match binding.captures_iter(body).last() {
+            Some(cap) => secret.replacen(root, cap[1].trim(), 1),
+            None => secret,
+        }
+    };
+    match flow {
+        AuthFlow::IssueToken { secret, algorithm } => AuthFlow::IssueToken { secret: inline(secret), algorithm },
+        AuthFlow::VerifyToken { secret, algorithm } => AuthFlow::VerifyToken { secret: inline(secret), algorithm },
+        flow => flow,
+    }
+}
+
+fn token_flow(source: &str, open: usize, issue: bool) -> Option<AuthFlow> {
+    let args = call_args(source, open);
+    let secret = args.get(1)?.clone();
+    let algorithm = algorithm_in(&args[1..].join(","));
+    Some(if issue { AuthFlow::IssueToken { secret, algorithm } } else { AuthFlow::VerifyToken { secret, algorithm } })
+}
+
+/// `jsonwebtoken`, `bcrypt`/`bcryptjs`/`argon2` and OAuth client calls in JavaScript
+pub fn js_auth_calls(source: &str) -> Vec<AuthCall> {
+    let defs = js_definitions(source);
+    let flows: Vec<FlowMatcher> = vec![
+        (Regex::new(r"\bjwt\.sign\s*\(").unwrap(), |s, open| token_flow(s, open, true)),
+        (Regex::new(r"\bjwt\.verify\s*\(").unwrap(), |s, open| token_flow(s, open, false)),
+        (Regex::new(r"\b(?:bcrypt|bcryptjs|argon2)\.hash(?:Sync)?\s*\(").unwrap(), |_, _| Some(AuthFlow::HashPassword)),
+        (Regex::new(r"\b(?:bcrypt|bcryptjs)\.compare(?:Sync)?\s*\(|\bargon2\.verify\s*\(").unwrap(), |_, _| Some(AuthFlow::VerifyPassword)),
+        (Regex::new(r"\.(?:authorizationUrl|authorizeURL|generateAuthUrl)\s*\(").unwrap(), |_, _| Some(AuthFlow::OAuthAuthorize)),
+    ];
+    collect_calls(source, &defs, &flows, |body| body.contains("catch") && !body.contains("throw"))
+}
+
+/// PyJWT/python-jose, bcrypt/passlib/argon2-cffi/werkzeug and OAuth client calls in Python
+pub fn python_auth_calls(source: &str) -> Vec<AuthCall> {
+    let defs = python_definitions(source);
+
+    let flows: Vec<FlowMatcher> = vec![
+        (Regex::new(r"\bjwt\.encode\s*\(").unwrap(), |s, open| token_flow(s, open, true)),
+        (Regex::new(r"\bjwt\.decode\s*\(").unwrap(), |s, open| token_flow(s, open, false)),
+        (Regex::new(r"\b(?:bcrypt\.hashpw|generate_password_hash|\w*(?:context|hasher|pwd)\w*\.hash|ph\.hash)\s*\(").unwrap(), |_, _| Some(AuthFlow::HashPassword)),
+        (Regex::new(r"\b(?:bcrypt\.checkpw|check_password_hash|\w*(?:context|hasher|pwd)\w*\.verify|ph\.verify)\s*\(").unwrap(), |_, _| Some(AuthFlow::VerifyPassword)),
+        (Regex::new(r"\.(?:authorization_url|create_authorization_url)\s*\(").unwrap(), |_, _| Some(AuthFlow::OAuthAuthorize)),
+    ];
+    collect_calls(source, &defs, &flows, |body| body.contains("except") && !body.contains("raise"))
+}
+
+/// `jsonwebtoken`, `bcrypt` and `argon2` calls in Rust
+pub fn rust_auth_calls(source: &str) -> Vec<AuthCall> {
+    let defs = rust_definitions(source);
+
+    let bare_bcrypt = Regex::new(r"use\s+bcrypt::\{[^}]*\}").unwrap().is_match(source);
+    let (hash, verify) = if bare_bcrypt {
+        (r"\bbcrypt::hash\s*\(|(?:^|[^\w.:])hash\s*\(|\.hash_password\s*\(", r"\bbcrypt::verify\s*\(|(?:^|[^\w.:])verify\s*\(|\.verify_password\s*\(")
+    } else {
+        (r"\bbcrypt::hash\s*\(|\.hash_password\s*\(", r"\bbcrypt::verify\s*\(|\.verify_password\s*\(")
+    };
+    let flows: Vec<FlowMatcher> = vec![
+        (Regex::new(r"\bEncodingKey::from_secret\s*\(").unwrap(), |s, open| rust_token_flow(s, open, true)),
+        (Regex::new(r"\bDecodingKey::from_secret\s*\(").unwrap(), |s, open| rust_token_flow(s, open, false)),
+        (Regex::new(hash).unwrap(), |_, _| Some(AuthFlow::HashPassword)),
+        (Regex::new(verify).unwrap(), |_, _| Some(AuthFlow::VerifyPassword)),
+    ];
+    collect_calls(source, &defs, &flows, |_| false)
+}
+
+/// The key passed to `from_secret`, and the algorithm the enclosing statement's `Header` or
+/// `Validation` names
+fn rust_token_flow(source: &str, open: usize, issue: bool) -> Option<AuthFlow> {
+    let secret = call_args(source, open).into_iter().next()?;
+    let statement_start = source[..open].rfind([';', '{']).map(|i| i + 1).unwrap_or(0);
+    let statement_end = source[open..].find(';').map(|i| open + i).unwrap_or(source.len());
+    let algorithm = Regex::new(r"Algorithm::(\w+)").unwrap()
+        .captures(&source[statement_start..statement_end])
+        .map(|cap| cap[1].to_string())
+        .unwrap_or_else(|| "HS256".to_string());
+    Some(if issue { AuthFlow::IssueToken { secret, algorithm } } else { AuthFlow::VerifyToken { secret, algorithm } })
+}
+
+/// Express routes registered with authentication middleware before the handler
+pub fn express_protected_routes(source: &str) -> Vec<ProtectedRoute> {
+    let route_regex = Regex::new(r#"\b(?:app|router)\.(get|post|put|patch|delete)\(\s*['"`]([^'"`]+)['"`]\s*,\s*(?:passport\.authenticate\(|(?:authenticate\w*|requireAuth\w*|ensureAuth\w*|isAuthenticated|verifyToken|authMiddleware|auth|protect|checkJwt|requireUser|jwtAuth)\b)"#).unwrap();
+    let param_regex = Regex::new(r":\w+").unwrap();
+    route_regex.captures_iter(source)
+        .map(|cap| ProtectedRoute {
+            method: cap[1].to_uppercase(),
+            path: param_regex.replace_all(&cap[2], "1").into_owned(),
+        })
+        .collect()
+}
+
+/// FastAPI routes depending on the current user (`Depends(get_current_user)`, `Security(...)`)
+/// and Flask views under `@login_required` or `@jwt_required()`
+pub fn python_protected_routes(source: &str) -> Vec<ProtectedRoute> {
+    let route_regex = Regex::new(r#"(?m)^[ \t]*@\w+\.(get|post|put|patch|delete|route)\(\s*["']([^"']+)["']([^\n]*)$"#).unwrap();
+    let def_regex = Regex::new(r"(?s)^(.*?)def\s+\w+\s*\(([^)]*)\)").unwrap();
+    let guard_regex = Regex::new(r"@login_required|@jwt_required|Depends\(\s*(?:get_current\w*|\w*auth\w*|\w*verify\w*|\w*token\w*|\w*user\w*)|Security\(").unwrap();
+    let methods_regex = Regex::new(r#"methods\s*=\s*\[\s*["'](\w+)"#).unwrap();
+    let param_regex = Regex::new(r"\{\w+\}|<(?:\w+:)?\w+>").unwrap();
+
+    route_regex.captures_iter(source).filter_map(|cap| {
+        let rest = &source[cap.get(0).unwrap().end()..];
+        let def = def_regex.captures(rest)?;
+        let guarded = format!("{}{}{}", &cap[3], &def[1], &def[2]);
+        if !guard_regex.is_match(&guarded) {
+            return None;
+        }
+        let method = match &cap[1] {
+            "route" => methods_regex.captures(&cap[3]).map(|m| m[1].to_uppercase()).unwrap_or_else(|| "GET".to_string()),
+            method => method.to_uppercase(),
+        };
+        Some(ProtectedRoute { method, path: param_regex.replace_all(&cap[2], "1").into_owned() })
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_js_auth_calls() {
+        let source = "const jwt = require('jsonwebtoken');\n\nfunction verifyToken(token) {\n  return jwt.verify(token, process.env.JWT_SECRET, { algorithms: ['HS512'] });\n}\n\nconst hashPassword = async (password) => bcrypt.hash(password, 10);\n\napp.get('/me', authenticate, (req, res) => res.json(req.user));\napp.delete('/users/:id', requireAuth, removeUser);\napp.get('/health', (req, res) => res.send('ok'));\n";
+        let calls = js_auth_calls(source);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].function, "verifyToken");
+        assert_eq!(calls[0].flow, AuthFlow::VerifyToken { secret: "process.env.JWT_SECRET".to_string(), algorithm: "HS512".to_string() });
+        assert_eq!(calls[0].param_index(&["token"]), Some(0));
+        assert_eq!(calls[1].flow, AuthFlow::HashPassword);
+        assert_eq!(secret_env_var("process.env.JWT_SECRET").as_deref(), Some("JWT_SECRET"));
+
+        let routes = express_protected_routes(source);
+        assert_eq!(routes, vec![
+            ProtectedRoute { method: "GET".to_string(), path: "/me".to_string() },
+            ProtectedRoute { method: "DELETE".to_string(), path: "/users/1".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_python_and_rust_auth_calls() {
+        let source = "def decode_token(token: str, secret: str):\n    try:\n        return jwt.decode(token, secret, algorithms=[\"HS256\"])\n    except jwt.PyJWTError:\n        return None\n\n@app.get(\"/items/{item_id}\")\ndef read_item(item_id: int, user = Depends(get_current_user)):\n    return {}\n";
+        let calls = python_auth_calls(source);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].swallows_errors);
+        assert_eq!(calls[0].secret_param(), Some("secret"));
+        assert_eq!(python_protected_routes(source), vec![ProtectedRoute { method: "GET".to_string(), path: "/items/1".to_string() }]);
+
+        let source = "pub fn issue(user: &str) -> Result<String, Error> {\n    encode(&Header::new(Algorithm::HS384), &claims(user), &EncodingKey::from_secret(SECRET.as_ref()))\n}\n";
+        let calls = rust_auth_calls(source);
+        assert_eq!(calls[0].flow, AuthFlow::IssueToken { secret: "SECRET.as_ref()".to_string(), algorithm: "HS384".to_string() });
+        assert_eq!(calls[0].returns.as_deref(), Some("Result<String, Error>"));
+    }
+}
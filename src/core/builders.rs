@@ -0,0 +1,404 @@
+use regex::Regex;
+
+use super::cli::braced;
+
+/// A chained builder method setting one field
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuilderSetter {
+    pub method: String,
+    pub param: String,
+    pub param_type: Option<String>,
+    /// The builder field it assigns
+    pub field: String,
+}
+
+/// What `build` does when a required field is unset
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissingField {
+    /// Returns `Err`, or throws/raises the named exception
+    Error(Option<String>),
+    /// Panics through `expect`/`unwrap`
+    Panic,
+}
+
+/// A field of the built type and how to read it from a built value
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetField {
+    pub name: String,
+    pub ty: Option<String>,
+    /// `.name`, or `.getName()` in Java
+    pub accessor: String,
+}
+
+/// A class or struct with chained setters and a `build` method
+#[derive(Debug, Clone, PartialEq)]
+pub struct Builder {
+    pub name: String,
+    /// Expression creating an empty builder, e.g. `ConfigBuilder::new()` or `Config.builder()`
+    pub create: String,
+    pub setters: Vec<BuilderSetter>,
+    /// Fields `build` rejects when unset
+    pub required: Vec<(String, MissingField)>,
+    /// Values, as written, that unset fields start with or `build` falls back to
+    pub defaults: Vec<(String, String)>,
+    /// The type `build` returns, without `Result`
+    pub target: Option<String>,
+    /// `build` returns a `Result`
+    pub fallible: bool,
+    pub target_fields: Vec<TargetField>,
+}
+
+impl Builder {
+    /// The setter assigning `field`
+    pub fn setter(&self, field: &str) -> Option<&BuilderSetter> {
+        self.setters.iter().find(|s| s.field == field)
+    }
+
+    /// How `build` reacts to `field` being unset, when it's required
+    pub fn missing(&self, field: &str) -> Option<&MissingField> {
+        self.required.iter().find(|(f, _)| f == field).map(|(_, missing)| missing)
+    }
+
+    /// The built type's field a builder field ends up in
+    pub fn target_field(&self, field: &str) -> Option<&TargetField> {
+        self.target_fields.iter().find(|f| f.name == field)
+    }
+}
+
+/// `impl` blocks with a `build` method and methods taking `self` and one argument that assign
+/// a field and return the builder. Builders are created with a no-argument `new()`, `Default`
+/// or a `builder()` function on another type
+pub fn rust_builders(source: &str) -> Vec<Builder> {
+    let block = Regex::new(r"(?m)^[ \t]*impl\s+(\w+)\s*\{").unwrap();
+    let build = Regex::new(r"\bfn\s+build\s*\(\s*(&\s*(?:mut\s+)?)?(?:mut\s+)?self\s*\)\s*->\s*([^{]+?)\s*\{").unwrap();
+    let setter = Regex::new(r"\bfn\s+(\w+)\s*\(\s*(&\s*mut\s+self|(?:mut\s+)?self)\s*,\s*(\w+)\s*:\s*([^)]+?)\s*\)\s*->\s*([&\w ]+?)\s*\{").unwrap();
+    let assignment = Regex::new(r"\bself\.(\w+)\s*=").unwrap();
+    let required = Regex::new(r"\bself\.(\w+)(?:\.(?:clone|take|as_ref|as_deref)\(\))?\s*\.\s*(ok_or(?:_else)?|expect|unwrap)\(").unwrap();
+    let default = Regex::new(r"\bself\.(\w+)(?:\.(?:clone|take)\(\))?\s*\.\s*(?:unwrap_or\(\s*([^()]+?(?:\([^()]*\))?)\s*\)|unwrap_or_default\(\))").unwrap();
+
+    block.captures_iter(source).filter_map(|cap| {
+        let name = cap[1].to_string();
+        let body = braced(source, cap.get(0).unwrap().end() - 1);
+        let build_cap = build.captures(body)?;
+        let build_by_ref = build_cap.get(1).is_some();
+        let returns = build_cap[2].trim();
+        let target = returns.strip_prefix("Result<")
+            .map(|inner| inner.split(',').next().unwrap_or(inner).trim_end_matches('>').trim())
+            .unwrap_or(returns);
+        let build_body = braced(body, build_cap.get(0).unwrap().end() - 1);
+
+        let setters: Vec<BuilderSetter> = setter.captures_iter(body).filter_map(|s| {
+            let by_ref = s[2].starts_with('&');
+            let returns_builder = matches!(s[5].trim_start_matches('&').trim_start_matches("mut ").trim(), "Self") || s[5].ends_with(name.as_str());
+            if !returns_builder || (by_ref && !build_by_ref) {
+                return None;
+            }
+            let method_body = braced(body, s.get(0).unwrap().end() - 1);
+            Some(BuilderSetter {
+                method: s[1].to_string(),
+                param: s[3].to_string(),
+                param_type: Some(s[4].trim().to_string()),
+                field: assignment.captures(method_body)?[1].to_string(),
+            })
+        }).collect();
+        if setters.is_empty() {
+            return None;
+        }
+
+        let create = if Regex::new(r"\bfn\s+new\s*\(\s*\)").unwrap().is_match(body) {
+            format!("{}::new()", name)
+        } else if Regex::new(&format!(r"derive\([^)]*\bDefault\b[^)]*\)\]\s*(?:pub(?:\([^)]*\))?\s+)?struct\s+{}\b|\bimpl\s+Default\s+for\s+{}\b", name, name)).unwrap().is_match(source) {
+            format!("{}::default()", name)
+        } else {
+            let factory = Regex::new(&format!(r"(?s)impl\s+(\w+)\s*\{{.*?\bfn\s+builder\s*\(\s*\)\s*->\s*{}\b", name)).unwrap();
+            format!("{}::builder()", &factory.captures(source)?[1])
+        };
+
+        let struct_fields = Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?\s+)?(\w+)\s*:\s*([^,\n]+?)\s*,?\s*$").unwrap();
+        let target_fields = Regex::new(&format!(r"\bstruct\s+{}\s*\{{", regex::escape(target))).unwrap().find(source)
+            .map(|m| struct_fields.captures_iter(braced(source, m.end() - 1)).map(|f| TargetField {
+                name: f[1].to_string(),
+                ty: Some(f[2].to_string()),
+                accessor: format!(".{}", &f[1]),
+            }).collect())
+            .unwrap_or_default();
+
+        Some(Builder {
+            name,
+            create,
+            setters,
+            required: required.captures_iter(build_body).map(|r| {
+                (r[1].to_string(), if r[2].starts_with("ok_or") { MissingField::Error(None) } else { MissingField::Panic })
+            }).collect(),
+            defaults: default.captures_iter(build_body).map(|d| {
+                (d[1].to_string(), d.get(2).map(|v| v.as_str().to_string()).unwrap_or_else(|| "Default::default()".to_string()))
+            }).collect(),
+            target: Some(target.to_string()),
+            fallible: returns.starts_with("Result<"),
+            target_fields,
+        })
+    }).collect()
+}
+
+/// Classes with a no-argument `build()` and one-argument methods returning the class, created
+/// with `new B()` when the class has no constructor arguments, or a static `builder()`
+pub fn java_builders(source: &str) -> Vec<Builder> {
+    let class = Regex::new(r"\bclass\s+(\w+)[^{;]*\{").unwrap();
+    let build = Regex::new(r"\b([\w.]+(?:<[^>]*>)?)\s+build\s*\(\s*\)[^{;]*\{").unwrap();
+    let required = Regex::new(r"if\s*\(\s*(?:this\.)?(\w+)\s*==\s*null\s*\)\s*\{?\s*throw\s+new\s+(\w+)|Objects\.requireNonNull\(\s*(?:this\.)?(\w+)").unwrap();
+    let field_default = Regex::new(r"(?m)^\s*(?:private|protected|public)?\s*[\w<>\[\], ]+?\s+(\w+)\s*=\s*([^;]+);").unwrap();
+    let getter = Regex::new(r"\bpublic\s+([\w<>\[\], ]+?)\s+((?:get|is)(\w+))\s*\(\s*\)").unwrap();
+    let classes: Vec<(String, usize, usize)> = class.captures_iter(source).map(|cap| {
+        let open = cap.get(0).unwrap().end() - 1;
+        (cap[1].to_string(), open, open + braced(source, open).len())
+    }).collect();
+
+    classes.iter().filter_map(|(name, open, close)| {
+        let body = braced(source, *open);
+        let nested_end = classes.iter().filter(|(_, o, _)| o > open && o < close).map(|(_, o, _)| *o).min().unwrap_or(*close);
+        let own = &source[*open..nested_end];
+        let build_cap = build.captures(own)?;
+        let build_body = braced(own, build_cap.get(0).unwrap().end() - 1);
+        let setter = Regex::new(&format!(r"\bpublic\s+{}\s+(\w+)\s*\(\s*(?:final\s+)?([\w<>\[\], .]+?)\s+(\w+)\s*\)\s*\{{", regex::escape(name))).unwrap();
+        let setters: Vec<BuilderSetter> = setter.captures_iter(own).filter_map(|s| {
+            let method_body = braced(own, s.get(0).unwrap().end() - 1);
+            Some(BuilderSetter {
+                method: s[1].to_string(),
+                param: s[3].to_string(),
+                param_type: Some(s[2].to_string()),
+                field: Regex::new(r"\bthis\.(\w+)\s*=").unwrap().captures(method_body)?[1].to_string(),
+            })
+        }).collect();
+        if setters.is_empty() {
+            return None;
+        }
+
+        let outer = classes.iter().filter(|(_, o, c)| o < open && c > close).max_by_key(|(_, o, _)| *o).map(|(n, _, _)| n.as_str());
+        let qualified = outer.map(|o| format!("{}.{}", o, name)).unwrap_or_else(|| name.clone());
+        let private_constructor = Regex::new(&format!(r"\bprivate\s+{}\s*\(", regex::escape(name))).unwrap().is_match(body);
+        let argumentless = !Regex::new(&format!(r"\b{}\s*\(\s*[^)\s]", regex::escape(name))).unwrap().is_match(body);
+        let create = match Regex::new(&format!(r"\bstatic\s+{}\s+builder\s*\(\s*\)", regex::escape(name))).unwrap().is_match(source) {
+            true => format!("{}.builder()", outer.unwrap_or(name)),
+            false if argumentless && !private_constructor => format!("new {}()", qualified),
+            false => return None,
+        };
+
+        let target = build_cap[1].to_string();
+        let target_fields = classes.iter().find(|(n, _, _)| *n == target)
+            .map(|(_, o, c)| getter.captures_iter(&source[*o..*c]).map(|g| {
+                let mut field = g[3].to_string();
+                field[..1].make_ascii_lowercase();
+                TargetField { name: field, ty: Some(g[1].to_string()), accessor: format!(".{}()", &g[2]) }
+            }).collect())
+            .unwrap_or_default();
+
+        Some(Builder {
+            name: qualified,
+            create,
+            setters,
+            required: required.captures_iter(build_body).map(|r| match r.get(1) {
+                Some(field) => (field.as_str().to_string(), MissingField::Error(Some(r[2].to_string()))),
+                None => (r[3].to_string(), MissingField::Error(Some("NullPointerException".to_string()))),
+            }).collect(),
+            defaults: field_default.captures_iter(&outside_methods(own))
+                .filter(|d| d[2].trim() != "null")
+                .map(|d| (d[1].to_string(), d[2].trim().to_string()))
+                .collect(),
+            target: Some(target),
+            fallible: false,
+            target_fields,
+        })
+    }).collect()
+}
+
+/// Classes with a `build()` method and one-argument methods that assign a field and
+/// `return this`, created with `new B()`
+pub fn js_builders(source: &str) -> Vec<Builder> {
+    let class = Regex::new(r"\bclass\s+(\w+)(?:\s+extends\s+[\w.]+)?\s*\{").unwrap();
+    let method = Regex::new(r"(?m)^[ \t]*(\w+)\s*\(\s*(\w+)(?:\s*:\s*([^)=]+?))?\s*\)(?:\s*:\s*[\w<>]+)?\s*\{").unwrap();
+    let assignment = Regex::new(r"\bthis\.(\w+)\s*=\s*([^;\n]+)").unwrap();
+    let required = Regex::new(r"if\s*\(\s*(?:!\s*this\.(\w+)|this\.(\w+)\s*===?\s*(?:undefined|null))\s*\)\s*\{?\s*throw\s+new\s+(\w+)").unwrap();
+    let fallback = Regex::new(r"\bthis\.(\w+)\s*(?:\?\?|\|\|)\s*('[^'\n]*'|\x22[^\x22\n]*\x22|-?\d+(?:\.\d+)?|true|false|\[\]|\{\})").unwrap();
+
+    class.captures_iter(source).filter_map(|cap| {
+        let name = cap[1].to_string();
+        let body = braced(source, cap.get(0).unwrap().end() - 1);
+        let build = Regex::new(r"(?m)^[ \t]*build\s*\(\s*\)(?:\s*:\s*[\w<>]+)?\s*\{").unwrap().find(body)?;
+        let build_body = braced(body, build.end() - 1);
+        let setters: Vec<BuilderSetter> = method.captures_iter(body).filter_map(|m| {
+            let method_body = braced(body, m.get(0).unwrap().end() - 1);
+            if matches!(&m[1], "constructor" | "build" | "if" | "for" | "while" | "switch" | "catch") || !method_body.contains("return this") {
+                return None;
+            }
+            Some(BuilderSetter {
+                method: m[1].to_string(),
+                param: m[2].to_string(),
+                param_type: m.get(3).map(|t| t.as_str().trim().to_string()),
+                field: assignment.captures(method_body)?[1].to_string(),
+            })
+        }).collect();
+        let constructor = Regex::new(r"\bconstructor\s*\(([^)]*)\)\s*\{").unwrap().captures(body);
+        if setters.is_empty() || constructor.as_ref().is_some_and(|c| !c[1].trim().is_empty()) {
+            return None;
+        }
+
+        let mut defaults: Vec<(String, String)> = constructor
+            .map(|c| assignment.captures_iter(braced(body, c.get(0).unwrap().end() - 1))
+                .filter(|a| !matches!(a[2].trim(), "null" | "undefined"))
+                .map(|a| (a[1].to_string(), a[2].trim().to_string()))
+                .collect())
+            .unwrap_or_default();
+        defaults.extend(fallback.captures_iter(build_body).map(|f| (f[1].to_string(), f[2].to_string())));
+
+        let target = Regex::new(r"\breturn\s+new\s+(\w+)\s*\(").unwrap().captures(build_body).map(|t| t[1].to_string());
+        let target_fields = target.as_ref()
+            .and_then(|t| Regex::new(&format!(r"\bclass\s+{}\b[^{{]*\{{", regex::escape(t))).unwrap().find(source))
+            .map(|m| assignment.captures_iter(braced(source, m.end() - 1))
+                .map(|a| TargetField { name: a[1].to_string(), ty: None, accessor: format!(".{}", &a[1]) })
+                .collect())
+            .unwrap_or_default();
+
+        Some(Builder {
+            create: format!("new {}()", name),
+            name,
+            setters,
+            required: required.captures_iter(build_body)
+                .map(|r| (r.get(1).or(r.get(2)).unwrap().as_str().to_string(), MissingField::Error(Some(r[3].to_string()))))
+                .collect(),
+            defaults,
+            target,
+            fallible: false,
+            target_fields,
+        })
+    }).collect()
+}
+
+/// Classes with a `build(self)` method and one-argument methods that assign a field and
+/// `return self`, created with `B()`
+pub fn python_builders(source: &str) -> Vec<Builder> {
+    let class = Regex::new(r"(?m)^class\s+(\w+)\s*(?:\([^)]*\))?\s*:").unwrap();
+    let method = Regex::new(r"(?m)^[ \t]+def\s+(\w+)\s*\(\s*self\s*,\s*(\w+)(?:\s*:\s*([^)=]+?))?\s*\)(?:\s*->\s*[^:]+)?:").unwrap();
+    let assignment = Regex::new(r"\bself\.(\w+)(?:\s*:\s*[^=\n]+)?\s*=\s*([^\n]+)").unwrap();
+    let required = Regex::new(r"if\s+(?:not\s+self\.(\w+)|self\.(\w+)\s+is\s+None)\s*:\s*\n?\s*raise\s+(\w+)").unwrap();
+    let fallback = Regex::new(r#"\bself\.(\w+)\s+or\s+('[^'\n]*'|"[^"\n]*"|-?\d+(?:\.\d+)?|True|False|\[\]|\{\})"#).unwrap();
+    let classes: Vec<(String, &str)> = class.captures_iter(source)
+        .map(|cap| (cap[1].to_string(), python_block(source, cap.get(0).unwrap().end())))
+        .collect();
+    let method_body = |class_body: &'_ str, name: &str| -> Option<String> {
+        let def = Regex::new(&format!(r"(?m)^[ \t]+def\s+{}\s*\([^)]*\)[^:]*:", name)).unwrap().find(class_body)?;
+        Some(python_block(class_body, def.end()).to_string())
+    };
+
+    classes.iter().filter_map(|(name, body)| {
+        let build_body = method_body(body, "build")?;
+        let setters: Vec<BuilderSetter> = method.captures_iter(body).filter_map(|m| {
+            let own = python_block(body, m.get(0).unwrap().end());
+            if !own.contains("return self") {
+                return None;
+            }
+            Some(BuilderSetter {
+                method: m[1].to_string(),
+                param: m[2].to_string(),
+                param_type: m.get(3).map(|t| t.as_str().trim().to_string()),
+                field: assignment.captures(own)?[1].to_string(),
+            })
+        }).collect();
+        let init = Regex::new(r"(?m)^[ \t]+def\s+__init__\s*\(([^)]*)\)").unwrap().captures(body);
+        if setters.is_empty() || init.as_ref().is_some_and(|i| i[1].split(',').any(|p| p.trim() != "self" && !p.contains('='))) {
+            return None;
+        }
+
+        let mut defaults: Vec<(String, String)> = method_body(body, "__init__")
+            .map(|init| assignment.captures_iter(&init)
+                .filter(|a| a[2].trim() != "None")
+                .map(|a| (a[1].to_string(), a[2].trim().to_string()))
+                .collect())
+            .unwrap_or_default();
+        defaults.extend(fallback.captures_iter(&build_body).map(|f| (f[1].to_string(), f[2].to_string())));
+
+        let target = Regex::new(r"\breturn\s+(\w+)\s*\(").unwrap().captures(&build_body).map(|t| t[1].to_string());
+        let dataclass_field = Regex::new(r"(?m)^[ \t]+(\w+)\s*:\s*([^=\n]+)").unwrap();
+        let target_fields = target.as_ref()
+            .and_then(|t| classes.iter().find(|(n, _)| n == t))
+            .map(|(_, target_body)| {
+                let fields: Vec<(String, Option<String>)> = match method_body(target_body, "__init__") {
+                    Some(init) => assignment.captures_iter(&init).map(|a| (a[1].to_string(), None)).collect(),
+                    None => dataclass_field.captures_iter(target_body).map(|f| (f[1].to_string(), Some(f[2].trim().to_string()))).collect(),
+                };
+                fields.into_iter().map(|(name, ty)| TargetField { accessor: format!(".{}", name), name, ty }).collect()
+            })
+            .unwrap_or_default();
+
+        Some(Builder {
+            create: format!("{}()", name),
+            name: name.clone(),
+            setters,
+            required: required.captures_iter(&build_body)
+                .map(|r| (r.get(1).or(r.get(2)).unwrap().as_str().to_string(), MissingField::Error(Some(r[3].to_string()))))
+                .collect(),
+            defaults,
+            target,
+            fallible: false,
+            target_fields,
+        })
+    }).collect()
+}
+
+/// The indented block after the `:` ending at `start`
+fn python_block(source: &str, start: usize) -> &str {
+    let rest = &source[start..];
+    let first = rest.find('\n').map(|i| i + 1).unwrap_or(rest.len());
+    let indent = rest[first..].lines().find(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .unwrap_or(0);
+    let mut end = first;
+    for line in rest[first..].split_inclusive('\n') {
+        if !line.trim().is_empty() && line.len() - line.trim_start().len() < indent {
+            break;
+        }
+        end += line.len();
+    }
+    &rest[..end]
+}
+
+/// Class body text outside method bodies
+fn outside_methods(body: &str) -> String {
+    let mut depth = 0;
+    body.chars().filter(|c| {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => return depth <= 1,
+        }
+        false
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builders_by_language() {
+        let builders = rust_builders("pub struct Client {\n    pub url: String,\n    pub retries: u32,\n}\n\n#[derive(Default)]\npub struct ClientBuilder {\n    url: Option<String>,\n    retries: Option<u32>,\n}\n\nimpl ClientBuilder {\n    pub fn url(mut self, url: impl Into<String>) -> Self {\n        self.url = Some(url.into());\n        self\n    }\n\n    pub fn with_retries(mut self, retries: u32) -> Self {\n        self.retries = Some(retries);\n        self\n    }\n\n    pub fn build(self) -> Result<Client, String> {\n        Ok(Client {\n            url: self.url.ok_or(\"url is required\")?,\n            retries: self.retries.unwrap_or(3),\n        })\n    }\n}\n");
+        assert_eq!(builders.len(), 1);
+        assert_eq!(builders[0].create, "ClientBuilder::default()");
+        assert_eq!(builders[0].setters.iter().map(|s| s.method.as_str()).collect::<Vec<_>>(), vec!["url", "with_retries"]);
+        assert_eq!(builders[0].missing("url"), Some(&MissingField::Error(None)));
+        assert_eq!(builders[0].defaults, vec![("retries".to_string(), "3".to_string())]);
+        assert_eq!(builders[0].target_field("retries").and_then(|f| f.ty.as_deref()), Some("u32"));
+
+        let builders = java_builders("public class Request {\n    private final String url;\n\n    public String getUrl() { return url; }\n\n    public static Builder builder() { return new Builder(); }\n\n    public static class Builder {\n        private String url;\n        private int timeout = 30;\n\n        public Builder url(String url) {\n            this.url = url;\n            return this;\n        }\n\n        public Request build() {\n            if (url == null) {\n                throw new IllegalStateException(\"url\");\n            }\n            return new Request(this);\n        }\n    }\n}\n");
+        assert_eq!((builders[0].name.as_str(), builders[0].create.as_str()), ("Request.Builder", "Request.builder()"));
+        assert_eq!(builders[0].missing("url"), Some(&MissingField::Error(Some("IllegalStateException".to_string()))));
+        assert_eq!(builders[0].defaults, vec![("timeout".to_string(), "30".to_string())]);
+        assert_eq!(builders[0].target_field("url").map(|f| f.accessor.as_str()), Some(".getUrl()"));
+
+        let builders = js_builders("class QueryBuilder {\n  constructor() {\n    this.limit = 10;\n  }\n\n  setTable(table) {\n    this.table = table;\n    return this;\n  }\n\n  build() {\n    if (!this.table) throw new Error('table is required');\n    return new Query(this.table, this.limit);\n  }\n}\n");
+        assert_eq!((builders[0].setters[0].field.as_str(), builders[0].defaults.clone()), ("table", vec![("limit".to_string(), "10".to_string())]));
+
+        let builders = python_builders("class EmailBuilder:\n    def __init__(self):\n        self.subject = \"\"\n        self.to = None\n\n    def with_to(self, to: str):\n        self.to = to\n        return self\n\n    def build(self):\n        if self.to is None:\n            raise ValueError(\"to is required\")\n        return Email(self.to, self.subject)\n");
+        assert_eq!(builders[0].missing("to"), Some(&MissingField::Error(Some("ValueError".to_string()))));
+        assert_eq!(builders[0].setters[0].param_type.as_deref(), Some("str"));
+    }
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::TestCase;
+
+/// Input/expected rows of a single table-driven test, stored as the test case's `input`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaseTable {
+    /// Parameter declarations in the target language, e.g. `a` or `a: i32`
+    pub parameters: Vec<String>,
+    /// Type of the expected column; `None` when the function's result isn't compared
+    pub expected_type: Option<String>,
+    pub rows: Vec<CaseRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaseRow {
+    pub label: String,
+    pub inputs: Vec<Value>,
+    /// `null` when no exact result can be inferred for the row
+    pub expected: Value,
+}
+
+impl CaseTable {
+    /// Build the rows for a function from one typical input row: the typical values,
+    /// their zero values and, when any input is numeric, their negations
+    pub fn from_sample(function_name: &str, parameters: Vec<String>, expected_type: Option<String>, sample: Vec<Value>, sample_expected: Value) -> Self {
+        let mut rows = vec![CaseRow {
+            label: "typical".to_string(),
+            expected: arithmetic_result(function_name, &sample).unwrap_or(sample_expected),
+            inputs: sample.clone(),
+        }];
+
+        let zeros: Vec<Value> = sample.iter().map(zero_value).collect();
+        rows.push(CaseRow {
+            label: "zero values".to_string(),
+            expected: arithmetic_result(function_name, &zeros).unwrap_or(Value::Null),
+            inputs: zeros,
+        });
+
+        if sample.iter().any(Value::is_number) {
+            let negated: Vec<Value> = sample.iter().map(negate).collect();
+            rows.push(CaseRow {
+                label: "negative values".to_string(),
+                expected: arithmetic_result(function_name, &negated).unwrap_or(Value::Null),
+                inputs: negated,
+            });
+        }
+
+        if expected_type.is_none() {
+            for row in &mut rows {
+                row.expected = Value::Null;
+            }
+        }
+
+        Self { parameters, expected_type, rows }
+    }
+
+    /// The table behind a parameterized test case, if it is one
+    pub fn from_test_case(test_case: &TestCase) -> Option<Self> {
+        serde_json::from_value(test_case.input.clone()).ok()
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+fn zero_value(value: &Value) -> Value {
+    match value {
+        Value::Number(n) if n.is_f64() => serde_json::json!(0.0),
+        Value::Number(_) => serde_json::json!(0),
+        Value::String(_) => serde_json::json!(""),
+        Value::Bool(_) => serde_json::json!(false),
+        Value::Array(_) => serde_json::json!([]),
+        Value::Object(_) => serde_json::json!({}),
+        Value::Null => Value::Null,
+    }
+}
+
+fn negate(value: &Value) -> Value {
+    match value {
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => serde_json::json!(-i),
+            None => serde_json::json!(-n.as_f64().unwrap_or_default()),
+        },
+        other => other.clone(),
+    }
+}
+
+/// Exact result for functions named after a basic arithmetic operation
+fn arithmetic_result(function_name: &str, inputs: &[Value]) -> Option<Value> {
+    if inputs.is_empty() || !inputs.iter().all(Value::is_number) {
+        return None;
+    }
+
+    let name = function_name.to_lowercase();
+    let integers: Option<Vec<i64>> = inputs.iter().map(Value::as_i64).collect();
+    let floats: Vec<f64> = inputs.iter().filter_map(Value::as_f64).collect();
+
+    if name.contains("add") || name.contains("sum") {
+        Some(match integers {
+            Some(values) => serde_json::json!(values.iter().sum::<i64>()),
+            None => serde_json::json!(floats.iter().sum::<f64>()),
+        })
+    } else if name.contains("multiply") || name.contains("product") {
+        Some(match integers {
+            Some(values) => serde_json::json!(values.iter().product::<i64>()),
+            None => serde_json::json!(floats.iter().product::<f64>()),
+        })
+    } else if (name.contains("subtract") || name.contains("minus")) && inputs.len() == 2 {
+        Some(match integers {
+            Some(values) => serde_json::json!(values[0] - values[1]),
+            None => serde_json::json!(floats[0] - floats[1]),
+        })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TestCategory;
+
+    #[test]
+    fn test_from_sample_rows() {
+        let table = CaseTable::from_sample(
+            "add",
+            vec!["a".to_string(), "b".to_string()],
+            Some("int".to_string()),
+            vec![serde_json::json!(5), serde_json::json!(3)],
+            Value::Null,
+        );
+
+        let labels: Vec<&str> = table.rows.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["typical", "zero values", "negative values"]);
+        assert_eq!(table.rows[0].expected, serde_json::json!(8));
+        assert_eq!(table.rows[1].expected, serde_json::json!(0));
+        assert_eq!(table.rows[2].inputs, vec![serde_json::json!(-5), serde_json::json!(-3)]);
+    }
+
+    #[test]
+    fn test_from_sample_unknown_result() {
+        let table = CaseTable::from_sample(
+            "greet",
+            vec!["name".to_string()],
+            Some("str".to_string()),
+            vec![serde_json::json!("Ada")],
+            serde_json::json!("Hello"),
+        );
+
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0].expected, serde_json::json!("Hello"));
+        assert_eq!(table.rows[1].inputs, vec![serde_json::json!("")]);
+        assert!(table.rows[1].expected.is_null());
+    }
+
+    #[test]
+    fn test_from_test_case_round_trip() {
+        let table = CaseTable::from_sample("multiply", vec!["x".to_string()], None, vec![serde_json::json!(2)], Value::Null);
+        let test_case = TestCase {
+            id: "1".to_string(),
+            name: "test_multiply_cases".to_string(),
+            description: String::new(),
+            input: table.to_value(),
+            expected_output: Value::Null,
+            test_body: String::new(),
+            assertions: vec![],
+            test_category: TestCategory::HappyPath,
+        };
+
+        assert_eq!(CaseTable::from_test_case(&test_case), Some(table));
+
+        let plain = TestCase { input: serde_json::json!({"a": 5}), ..test_case };
+        assert!(CaseTable::from_test_case(&plain).is_none());
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::UftError;
+
+/// Where a run keeps its checkpoint, relative to the directory it processes
+pub const CHECKPOINT_FILE: &str = ".uft/state.json";
+
+/// Progress of a `dir` or `git-repo` run, saved after every file so an interrupted run can
+/// carry on from where it stopped
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunCheckpoint {
+    /// Framework per language, reused instead of asking again
+    pub framework_choices: HashMap<String, String>,
+    /// Source files already handled, relative to the run's directory
+    pub completed: BTreeSet<String>,
+    pub processed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub total_tests: usize,
+}
+
+impl RunCheckpoint {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(CHECKPOINT_FILE)
+    }
+
+    /// The checkpoint an interrupted run left in `root`
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| UftError::Config(format!("No checkpoint to resume from at {}: {}", path.display(), e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| UftError::Config(format!("Invalid checkpoint {}: {}", path.display(), e)).into())
+    }
+
+    /// Written to a temporary file and renamed over the old one, so an interruption mid-write
+    /// keeps the previous checkpoint
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = Self::path(root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temporary = path.with_extension("json.tmp");
+        fs::write(&temporary, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temporary, &path)
+            .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+    }
+
+    pub fn is_completed(&self, relative_path: &str) -> bool {
+        self.completed.contains(relative_path)
+    }
+
+    /// Mark a file handled and save
+    pub fn complete(&mut self, root: &Path, relative_path: &str) -> Result<()> {
+        self.completed.insert(relative_path.to_string());
+        self.save(root)
+    }
+
+    /// Remove the checkpoint of a finished run, and `.uft/` with it when nothing else is there
+    pub fn clear(root: &Path) -> Result<()> {
+        let path = Self::path(root);
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        if let Some(parent) = path.parent() {
+            // Fails, harmlessly, when the directory has other files
+            let _ = fs::remove_dir(parent);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let root = tempfile::tempdir().unwrap();
+        assert_eq!(UftError::exit_code_of(&RunCheckpoint::load(root.path()).unwrap_err()), 3);
+
+        let mut checkpoint = RunCheckpoint::default();
+        checkpoint.framework_choices.insert("python".to_string(), "pytest".to_string());
+        checkpoint.processed = 1;
+        checkpoint.total_tests = 4;
+        checkpoint.complete(root.path(), "src/calc.py").unwrap();
+
+        let loaded = RunCheckpoint::load(root.path()).unwrap();
+        assert_eq!(loaded, checkpoint);
+        assert!(loaded.is_completed("src/calc.py"));
+        assert!(!loaded.is_completed("src/util.py"));
+
+        RunCheckpoint::clear(root.path()).unwrap();
+        assert!(!root.path().join(".uft").exists());
+    }
+}
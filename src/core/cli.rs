@@ -0,0 +1,320 @@
+use regex::Regex;
+
+use super::auth_flows::call_args;
+use super::{CliArgument, CliParser, CliPattern};
+
+impl CliParser {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CliParser::Clap => "clap",
+            CliParser::Argparse => "argparse",
+            CliParser::Commander => "commander",
+        }
+    }
+}
+
+impl CliArgument {
+    /// How usage errors name the argument: its flag, or its name for positionals
+    pub fn mention(&self) -> String {
+        self.flag.clone().unwrap_or_else(|| self.name.to_lowercase())
+    }
+
+    /// Sample value shaped by the declared type and the argument's name
+    pub fn sample_value(&self) -> &'static str {
+        let name = self.name.to_lowercase();
+        let numeric = self.value_type.as_deref().is_some_and(|ty| {
+            matches!(ty, "int" | "float" | "Number" | "f32" | "f64") || Regex::new(r"^[iu](?:8|16|32|64|128|size)$").unwrap().is_match(ty)
+        });
+        if name.contains("port") {
+            "8080"
+        } else if numeric {
+            "5"
+        } else if name.contains("url") {
+            "http://localhost:8080"
+        } else if name.contains("host") {
+            "localhost"
+        } else if ["file", "path", "input", "output", "config", "dir"].iter().any(|part| name.contains(part)) {
+            "utf-cli-test.txt"
+        } else {
+            "value"
+        }
+    }
+}
+
+impl CliPattern {
+    /// Identifier-safe name for tests: the program name, or the script's file stem
+    pub fn slug(&self) -> String {
+        let stem = std::path::Path::new(&self.script).file_stem().and_then(|s| s.to_str()).unwrap_or("cli");
+        let name = self.program.as_deref().unwrap_or(stem);
+        let slug = Regex::new(r"\W+").unwrap().replace_all(name, "_").trim_matches('_').to_lowercase();
+        if slug.is_empty() { "cli".to_string() } else { slug }
+    }
+
+    pub fn required_arguments(&self) -> Vec<&CliArgument> {
+        self.arguments.iter().filter(|arg| arg.required).collect()
+    }
+
+    /// Arguments supplying every required argument with a sample value, options before positionals
+    pub fn valid_args(&self) -> Vec<String> {
+        let required = self.required_arguments();
+        let options = required.iter().filter_map(|arg| {
+            let flag = arg.flag.clone()?;
+            Some(if arg.takes_value { vec![flag, arg.sample_value().to_string()] } else { vec![flag] })
+        });
+        let positionals = required.iter().filter(|arg| arg.flag.is_none()).map(|arg| vec![arg.sample_value().to_string()]);
+        options.chain(positionals).flatten().collect()
+    }
+}
+
+/// A clap parser: a `#[derive(Parser)]` struct with its fields' `#[arg]` attributes and a
+/// `#[derive(Subcommand)]` enum, or a builder's `Command::new` and `Arg::new` chains
+pub fn clap_cli(source: &str) -> Option<CliPattern> {
+    if !source.contains("clap") {
+        return None;
+    }
+    clap_derive_cli(source).or_else(|| clap_builder_cli(source))
+}
+
+fn clap_derive_cli(source: &str) -> Option<CliPattern> {
+    let attributes = r"((?:\s*#\[[^\]]*\]|\s*///[^\n]*)*)";
+    let parser = Regex::new(&format!(r"#\[derive\([^)]*\bParser\b[^)]*\)\]{}\s*(?:pub(?:\([^)]*\))?\s+)?struct\s+\w+\s*\{{", attributes)).unwrap();
+    let cap = parser.captures(source)?;
+    let program = Regex::new(r#"\bname\s*=\s*"([^"]+)""#).unwrap().captures(&cap[1]).map(|c| c[1].to_string());
+    let body = braced(source, cap.get(0).unwrap().end() - 1);
+
+    let field = Regex::new(&format!(r"{}\s*(?:pub(?:\([^)]*\))?\s+)?(\w+)\s*:\s*([^,]+?)\s*(?:,|$)", attributes)).unwrap();
+    let nested = Regex::new(r"#\[(?:command|clap)\(\s*(?:subcommand|flatten)").unwrap();
+    let arg_attribute = Regex::new(r"#\[(?:arg|clap)\(([^\]]*)\)\]").unwrap();
+    let long_name = Regex::new(r#"\blong\s*=\s*"([^"]+)""#).unwrap();
+    let long_flag = Regex::new(r"\blong\b").unwrap();
+    let short_name = Regex::new(r"\bshort\s*=\s*'(\w)'").unwrap();
+    let short_flag = Regex::new(r"\bshort\b").unwrap();
+    let required = Regex::new(r"\brequired\s*=\s*true").unwrap();
+    let mut arguments = Vec::new();
+    for f in field.captures_iter(body) {
+        if nested.is_match(&f[1]) {
+            continue;
+        }
+        let spec = arg_attribute.captures(&f[1]).map(|c| c[1].to_string()).unwrap_or_default();
+        let field_name = &f[2];
+        let ty = f[3].trim();
+        let long = long_name.captures(&spec).map(|c| c[1].to_string())
+            .or_else(|| long_flag.is_match(&spec).then(|| field_name.replace('_', "-")));
+        let short = short_name.captures(&spec).map(|c| c[1].to_string())
+            .or_else(|| short_flag.is_match(&spec).then(|| field_name[..1].to_string()));
+        let optional = ty.starts_with("Option<") || ty.starts_with("Vec<");
+        let switch = ty == "bool" || spec.contains("ArgAction::SetTrue") || spec.contains("ArgAction::Count");
+        let inner = ty.trim_start_matches("Option<").trim_start_matches("Vec<").trim_end_matches('>');
+        arguments.push(CliArgument {
+            name: long.clone().unwrap_or_else(|| field_name.to_string()),
+            flag: long.map(|l| format!("--{}", l)).or(short.map(|s| format!("-{}", s))),
+            required: required.is_match(&spec) || !(optional || switch || spec.contains("default_value")),
+            takes_value: !switch,
+            value_type: Some(inner.to_string()),
+        });
+    }
+
+    let subcommand = Regex::new(&format!(r"#\[derive\([^)]*\bSubcommand\b[^)]*\)\]{}\s*(?:pub(?:\([^)]*\))?\s+)?enum\s+\w+\s*\{{", attributes)).unwrap();
+    let subcommands = match subcommand.find(source) {
+        Some(m) => {
+            let top_level = top_level_text(braced(source, m.end() - 1));
+            Regex::new(r"(?m)^\s*(?:#\[[^\]]*\]\s*)*([A-Z]\w*)").unwrap()
+                .captures_iter(&top_level)
+                .map(|c| kebab_case(&c[1]))
+                .collect()
+        }
+        None => vec![],
+    };
+
+    Some(CliPattern { parser: CliParser::Clap, script: String::new(), program, arguments, subcommands })
+}
+
+fn clap_builder_cli(source: &str) -> Option<CliPattern> {
+    let command = Regex::new(r#"Command::new\(\s*"([^"]+)"\s*\)"#).unwrap();
+    let arg = Regex::new(r#"Arg::new\(\s*"([^"]+)"\s*\)"#).unwrap();
+    let first_subcommand = source.find(".subcommand(").unwrap_or(source.len());
+    let mut commands = command.captures_iter(source);
+    let program = commands.next()?[1].to_string();
+    let subcommands = commands
+        .filter(|c| source[..c.get(0).unwrap().start()].trim_end().ends_with(".subcommand("))
+        .map(|c| c[1].to_string())
+        .collect();
+
+    let starts: Vec<usize> = arg.find_iter(source).map(|m| m.start()).collect();
+    let arguments = arg.captures_iter(&source[..first_subcommand]).enumerate().map(|(i, cap)| {
+        let start = cap.get(0).unwrap().end();
+        let end = [starts.get(i + 1).copied(), source[start..].find(';').map(|e| start + e), Some(first_subcommand)]
+            .into_iter().flatten().filter(|end| *end >= start).min().unwrap_or(source.len());
+        let chain = &source[start..end];
+        let long = Regex::new(r#"\.long\(\s*"([^"]+)"\s*\)"#).unwrap().captures(chain).map(|c| c[1].to_string());
+        let short = Regex::new(r"\.short\(\s*'(\w)'\s*\)").unwrap().captures(chain).map(|c| c[1].to_string());
+        let switch = Regex::new(r"ArgAction::(?:SetTrue|SetFalse|Count|Help|Version)|\.num_args\(\s*0\s*\)").unwrap().is_match(chain);
+        CliArgument {
+            name: long.clone().unwrap_or_else(|| cap[1].to_string()),
+            flag: long.map(|l| format!("--{}", l)).or(short.map(|s| format!("-{}", s))),
+            required: chain.contains(".required(true)"),
+            takes_value: !switch,
+            value_type: Regex::new(r"value_parser!\(\s*(\w+)\s*\)").unwrap().captures(chain).map(|c| c[1].to_string()),
+        }
+    }).collect();
+
+    Some(CliPattern { parser: CliParser::Clap, script: String::new(), program: Some(program), arguments, subcommands })
+}
+
+/// An argparse parser: `add_argument` calls on the `ArgumentParser` and its `add_parser`
+/// subcommands
+pub fn argparse_cli(source: &str) -> Option<CliPattern> {
+    let parser = Regex::new(r"(\w+)\s*=\s*(?:argparse\.)?ArgumentParser\s*\(").unwrap().captures(source)?;
+    let keyword = |args: &[String], key: &str| -> Option<String> {
+        args.iter().find_map(|arg| {
+            let (name, value) = arg.split_once('=')?;
+            (name.trim() == key).then(|| value.trim().trim_matches(['"', '\'']).to_string())
+        })
+    };
+    let program = keyword(&call_args(source, parser.get(0).unwrap().end() - 1), "prog");
+
+    let add_argument = Regex::new(&format!(r"\b{}\.add_argument\s*\(", regex::escape(&parser[1]))).unwrap();
+    let arguments = add_argument.find_iter(source).filter_map(|m| {
+        let args = call_args(source, m.end() - 1);
+        let names: Vec<String> = args.iter()
+            .filter(|arg| arg.starts_with(['"', '\'']))
+            .map(|arg| arg.trim_matches(['"', '\'']).to_string())
+            .collect();
+        let flag = names.iter().find(|n| n.starts_with("--")).or_else(|| names.iter().find(|n| n.starts_with('-'))).cloned();
+        let name = match &flag {
+            Some(flag) => flag.trim_start_matches('-').to_string(),
+            None => names.first()?.clone(),
+        };
+        let action = keyword(&args, "action");
+        let nargs = keyword(&args, "nargs");
+        Some(CliArgument {
+            required: match flag {
+                Some(_) => keyword(&args, "required").as_deref() == Some("True"),
+                None => !matches!(nargs.as_deref(), Some("?" | "*")),
+            },
+            flag,
+            name,
+            takes_value: !matches!(action.as_deref(), Some("store_true" | "store_false" | "store_const" | "append_const" | "count" | "help" | "version")),
+            value_type: keyword(&args, "type"),
+        })
+    }).collect();
+
+    let subcommands = Regex::new(r#"\.add_parser\(\s*["']([\w-]+)["']"#).unwrap()
+        .captures_iter(source)
+        .map(|c| c[1].to_string())
+        .collect();
+    Some(CliPattern { parser: CliParser::Argparse, script: String::new(), program, arguments, subcommands })
+}
+
+/// A commander program: `.option`/`.requiredOption` and `.argument` calls before the first
+/// `.command(...)`, whose names become the subcommands
+pub fn commander_cli(source: &str) -> Option<CliPattern> {
+    if !Regex::new(r#"require\(\s*['"]commander['"]\s*\)|from\s+['"]commander['"]"#).unwrap().is_match(source) {
+        return None;
+    }
+    let top_level = &source[..source.find(".command(").unwrap_or(source.len())];
+    let program = Regex::new(r#"\.name\(\s*['"]([^'"]+)['"]"#).unwrap().captures(top_level).map(|c| c[1].to_string());
+
+    let mut arguments: Vec<CliArgument> = Regex::new(r#"\.(option|requiredOption)\(\s*['"]([^'"]+)['"]"#).unwrap()
+        .captures_iter(top_level)
+        .filter_map(|cap| {
+            let tokens: Vec<&str> = cap[2].split([',', ' ', '|']).filter(|t| !t.is_empty()).collect();
+            let flag = tokens.iter().find(|t| t.starts_with("--")).or_else(|| tokens.iter().find(|t| t.starts_with('-')))?.to_string();
+            Some(CliArgument {
+                name: flag.trim_start_matches('-').to_string(),
+                takes_value: tokens.iter().any(|t| t.starts_with(['<', '['])),
+                required: &cap[1] == "requiredOption",
+                flag: Some(flag),
+                value_type: None,
+            })
+        })
+        .collect();
+    for cap in Regex::new(r#"\.arguments?\(\s*['"]([^'"]+)['"]"#).unwrap().captures_iter(top_level) {
+        for token in cap[1].split_whitespace() {
+            arguments.push(CliArgument {
+                name: token.trim_matches(['<', '>', '[', ']']).trim_end_matches("...").to_string(),
+                flag: None,
+                required: token.starts_with('<'),
+                takes_value: true,
+                value_type: None,
+            });
+        }
+    }
+
+    let subcommands: Vec<String> = Regex::new(r#"\.command\(\s*['"]([\w-]+)"#).unwrap()
+        .captures_iter(source)
+        .map(|c| c[1].to_string())
+        .collect();
+    if arguments.is_empty() && subcommands.is_empty() {
+        return None;
+    }
+    Some(CliPattern { parser: CliParser::Commander, script: String::new(), program, arguments, subcommands })
+}
+
+/// Text between the brace at `open` and its match
+pub(crate) fn braced(source: &str, open: usize) -> &str {
+    let mut depth = 0;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &source[open + 1..open + i];
+                }
+            }
+            _ => {}
+        }
+    }
+    &source[open + 1..]
+}
+
+/// The text outside any nested braces or parentheses
+fn top_level_text(body: &str) -> String {
+    let mut depth = 0;
+    body.chars().filter(|c| {
+        match c {
+            '{' | '(' => depth += 1,
+            '}' | ')' => depth -= 1,
+            _ => return depth == 0,
+        }
+        false
+    }).collect()
+}
+
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            kebab.push('-');
+        }
+        kebab.push(c.to_ascii_lowercase());
+    }
+    kebab
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_parsers() {
+        let cli = clap_cli("use clap::{Parser, Subcommand};\n\n#[derive(Parser)]\n#[command(name = \"serve\")]\nstruct Cli {\n    /// Config file\n    config: PathBuf,\n    #[arg(short, long, default_value_t = 8080)]\n    port: u16,\n    #[arg(long)]\n    verbose: bool,\n    #[command(subcommand)]\n    command: Option<Commands>,\n}\n\n#[derive(Subcommand)]\nenum Commands {\n    /// Run migrations\n    Migrate { #[arg(long)] dry_run: bool },\n    CheckHealth,\n}\n").unwrap();
+        assert_eq!(cli.program.as_deref(), Some("serve"));
+        let flags: Vec<(Option<&str>, bool)> = cli.arguments.iter().map(|a| (a.flag.as_deref(), a.required)).collect();
+        assert_eq!(flags, vec![(None, true), (Some("--port"), false), (Some("--verbose"), false)]);
+        assert_eq!(cli.subcommands, vec!["migrate", "check-health"]);
+        assert_eq!(cli.valid_args(), vec!["utf-cli-test.txt"]);
+
+        let cli = argparse_cli("parser = argparse.ArgumentParser(prog=\"sync\", description=\"Sync (files)\")\nparser.add_argument(\"source\")\nparser.add_argument(\"-n\", \"--count\", type=int, required=True)\nparser.add_argument(\"--dry-run\", action=\"store_true\")\nsub = parser.add_subparsers()\nsub.add_parser(\"status\")\n").unwrap();
+        assert_eq!(cli.program.as_deref(), Some("sync"));
+        assert_eq!(cli.required_arguments().iter().map(|a| a.mention()).collect::<Vec<_>>(), vec!["source", "--count"]);
+        assert_eq!(cli.valid_args(), vec!["--count", "5", "value"]);
+        assert_eq!(cli.subcommands, vec!["status"]);
+
+        let cli = commander_cli("const { program } = require('commander');\nprogram.name('deploy').requiredOption('-e, --env <name>', 'target').option('-f, --force').argument('<service>');\nprogram.command('rollback <version>');\n").unwrap();
+        assert_eq!(cli.valid_args(), vec!["--env", "value", "value"]);
+        assert!(!cli.arguments[1].takes_value);
+        assert_eq!(cli.subcommands, vec!["rollback"]);
+    }
+}
@@ -0,0 +1,258 @@
+use regex::Regex;
+
+use super::auth_flows::{js_definitions, python_definitions, rust_definitions, Definition};
+use super::cli::braced;
+
+/// The synchronization a unit relies on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncPrimitive {
+    Mutex,
+    RwLock,
+    Atomic,
+    /// `threading.Lock`, `RLock`, `Semaphore` or `Condition`
+    ThreadLock,
+    /// `asyncio.Lock`, `Semaphore` or `Condition`
+    AsyncLock,
+    /// A `worker_threads` or web `Worker`
+    Worker,
+}
+
+/// A function or method that reaches shared state through a synchronization primitive
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConcurrentUnit {
+    pub function: String,
+    /// Type or class the method belongs to, which tests create without arguments
+    pub owner: Option<String>,
+    /// Expression creating an owner instance, e.g. `Counter::new()` or `Counter()`
+    pub create: Option<String>,
+    /// Parameter names with their annotated types
+    pub params: Vec<(String, Option<String>)>,
+    pub returns: Option<String>,
+    pub primitive: SyncPrimitive,
+    pub asynchronous: bool,
+    /// Expression reading a counter the unit adds one to, with `shared` standing for the
+    /// owner instance, e.g. `shared.value()` or `shared.count`
+    pub counter: Option<String>,
+}
+
+/// Methods taking `&self` on structs with `Mutex`, `RwLock` or atomic fields, and functions
+/// using a static of one of those types
+pub fn rust_concurrent_units(source: &str) -> Vec<ConcurrentUnit> {
+    let primitive_type = Regex::new(r"\b(Mutex|RwLock|Atomic(?:Bool|Usize|Isize|[UI](?:8|16|32|64)|Ptr))\b").unwrap();
+    let strukt = Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?\s+)?struct\s+(\w+)\s*\{").unwrap();
+    let field = Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?\s+)?(\w+)\s*:\s*([^\n]+?)\s*,?\s*$").unwrap();
+    let statik = Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?\s+)?static\s+(\w+)\s*:\s*([^=]+?)\s*=").unwrap();
+    let block = Regex::new(r"(?m)^[ \t]*impl\s+(\w+)\s*\{").unwrap();
+    let increment = Regex::new(r"fetch_add\(\s*1\s*,|\+=\s*1\s*;").unwrap();
+    let integer = Regex::new(r"^[ui](?:8|16|32|64|128|size)$").unwrap();
+    let primitive_of = |ty: &str| primitive_type.captures(ty).map(|c| match &c[1] {
+        "Mutex" => SyncPrimitive::Mutex,
+        "RwLock" => SyncPrimitive::RwLock,
+        _ => SyncPrimitive::Atomic,
+    });
+
+    let guarded: Vec<(String, String, SyncPrimitive)> = strukt.captures_iter(source).flat_map(|cap| {
+        let name = cap[1].to_string();
+        field.captures_iter(braced(source, cap.get(0).unwrap().end() - 1))
+            .filter_map(|f| Some((name.clone(), f[1].to_string(), primitive_of(&f[2])?)))
+            .collect::<Vec<_>>()
+    }).collect();
+    let statics: Vec<(String, SyncPrimitive)> = statik.captures_iter(source)
+        .filter_map(|s| Some((s[1].to_string(), primitive_of(&s[2])?)))
+        .collect();
+    let impls: Vec<(String, usize, usize)> = block.captures_iter(source).map(|cap| {
+        let open = cap.get(0).unwrap().end() - 1;
+        (cap[1].to_string(), open, open + braced(source, open).len())
+    }).collect();
+    let defs = rust_definitions(source);
+    let body_of = |def: &Definition| -> &str {
+        let text = def.body(source, &defs);
+        match (text.find('{'), text.find(';')) {
+            (Some(open), semicolon) if semicolon.is_none_or(|s| open < s) => braced(text, open),
+            _ => "",
+        }
+    };
+    let owner_of = |def: &Definition| impls.iter().find(|(_, open, close)| def.start > *open && def.start < *close).map(|(name, _, _)| name.as_str());
+    let create = |owner: &str| if impls.iter().any(|(name, open, close)| name == owner && Regex::new(r"\bfn\s+new\s*\(\s*\)").unwrap().is_match(&source[*open..*close])) {
+        Some(format!("{}::new()", owner))
+    } else if Regex::new(&format!(r"derive\([^)]*\bDefault\b[^)]*\)\]\s*(?:pub(?:\([^)]*\))?\s+)?struct\s+{}\b|\bimpl\s+Default\s+for\s+{}\b", owner, owner)).unwrap().is_match(source) {
+        Some(format!("{}::default()", owner))
+    } else {
+        None
+    };
+    let params_of = |def: &Definition| -> Vec<(String, Option<String>)> {
+        def.params.iter().filter(|p| !p.ends_with("self")).map(|p| {
+            let (name, ty) = p.split_once(':').unwrap_or((p, ""));
+            (name.trim().trim_start_matches("mut ").to_string(), Some(ty.trim().to_string()).filter(|t| !t.is_empty()))
+        }).collect()
+    };
+    let asynchronous = |def: &Definition| Regex::new(r"\basync\s+fn\b").unwrap().is_match(&source[def.start..def.start + source[def.start..].find('(').unwrap_or(0)]);
+
+    defs.iter().filter_map(|def| {
+        let body = body_of(def);
+        let (owner, counter_field, primitive, reader) = match owner_of(def) {
+            Some(owner) => {
+                if def.params.first().map(String::as_str) != Some("&self") || create(owner).is_none() {
+                    return None;
+                }
+                let uses = |name: &str| Regex::new(&format!(r"\bself\.{}\b", name)).unwrap().is_match(body);
+                let (_, field, primitive) = guarded.iter().find(|(o, f, _)| o == owner && uses(f))?;
+                // A `&self` getter returning an integer read from the same field
+                let reader = defs.iter().find(|d| {
+                    d.params.len() == 1 && d.params[0] == "&self" && owner_of(d) == Some(owner)
+                        && d.returns.as_deref().is_some_and(|r| integer.is_match(r))
+                        && Regex::new(&format!(r"\bself\.{}\b", field)).unwrap().is_match(body_of(d))
+                        && !increment.is_match(body_of(d))
+                });
+                (Some(owner.to_string()), field.clone(), *primitive, reader.map(|r| format!("shared.{}()", r.name)).zip(reader))
+            }
+            None => {
+                let (name, primitive) = statics.iter().find(|(s, _)| Regex::new(&format!(r"\b{}\b", s)).unwrap().is_match(body))?;
+                let reader = defs.iter().find(|d| {
+                    d.params.is_empty() && owner_of(d).is_none()
+                        && d.returns.as_deref().is_some_and(|r| integer.is_match(r))
+                        && Regex::new(&format!(r"\b{}\b", name)).unwrap().is_match(body_of(d))
+                        && !increment.is_match(body_of(d))
+                });
+                (None, name.clone(), *primitive, reader.map(|r| format!("{}()", r.name)).zip(reader))
+            }
+        };
+        if body.is_empty() || def.name == "new" || reader.as_ref().is_some_and(|(_, r)| r.start == def.start) {
+            return None;
+        }
+        let counts = increment.is_match(body) && body.contains(counter_field.as_str());
+        Some(ConcurrentUnit {
+            function: def.name.clone(),
+            create: owner.as_deref().and_then(create),
+            owner,
+            params: params_of(def),
+            returns: def.returns.clone(),
+            primitive,
+            asynchronous: asynchronous(def),
+            counter: reader.filter(|_| counts).map(|(counter, _)| counter),
+        })
+    }).collect()
+}
+
+/// Methods of argument-free classes that take a `threading` or `asyncio` lock held in an
+/// attribute, and functions taking a module-level lock
+pub fn python_concurrent_units(source: &str) -> Vec<ConcurrentUnit> {
+    let lock = Regex::new(r"(?m)^[ \t]*(self\.)?(\w+)\s*(?::\s*[^=\n]+)?=\s*(?:(threading|asyncio)\.)?(Lock|RLock|Semaphore|BoundedSemaphore|Condition)\(").unwrap();
+    let class = Regex::new(r"(?m)^class\s+(\w+)\s*(?:\([^)]*\))?\s*:").unwrap();
+    let increment = Regex::new(r"\bself\.(\w+)\s*\+=\s*1\b").unwrap();
+    let asyncio_imported = source.contains("from asyncio import");
+
+    let locks: Vec<(bool, String, SyncPrimitive)> = lock.captures_iter(source).map(|cap| {
+        let asynchronous = cap.get(3).map_or(asyncio_imported, |module| module.as_str() == "asyncio");
+        (cap.get(1).is_some(), cap[2].to_string(), if asynchronous { SyncPrimitive::AsyncLock } else { SyncPrimitive::ThreadLock })
+    }).collect();
+    if locks.is_empty() {
+        return Vec::new();
+    }
+    let classes: Vec<(String, usize, usize)> = class.captures_iter(source).map(|cap| {
+        let start = cap.get(0).unwrap().end();
+        (cap[1].to_string(), start, start + python_block(source, start).len())
+    }).collect();
+    let defs = python_definitions(source);
+
+    defs.iter().filter_map(|def| {
+        let header = &source[def.start..];
+        let body = python_block(source, def.start + header.find(":\n").map_or(header.len(), |i| i + 1));
+        let owner = classes.iter().find(|(_, start, end)| def.start > *start && def.start < *end);
+        let takes = |name: &str| Regex::new(&format!(r"(?m)^\s*(?:async\s+)?with\s+{}\b|\b{}\.acquire\(", regex::escape(name), regex::escape(name))).unwrap().is_match(body);
+        let (attribute, _, primitive) = locks.iter().find(|(attribute, name, _)| {
+            takes(&if *attribute { format!("self.{}", name) } else { name.clone() })
+        })?;
+        let (owner, counter) = match (owner, attribute) {
+            (Some((name, start, end)), true) => {
+                let init = Regex::new(r"(?m)^[ \t]+def\s+__init__\s*\(([^)]*)\)").unwrap().captures(&source[*start..*end]);
+                if init.is_some_and(|i| i[1].split(',').any(|p| p.trim() != "self" && !p.contains('='))) {
+                    return None;
+                }
+                (Some(name.clone()), increment.captures(body).map(|c| format!("shared.{}", &c[1])))
+            }
+            (None, false) => (None, None),
+            _ => return None,
+        };
+        if def.name.starts_with("__") {
+            return None;
+        }
+        Some(ConcurrentUnit {
+            function: def.name.clone(),
+            create: owner.as_ref().map(|owner| format!("{}()", owner)),
+            owner,
+            returns: def.returns.clone(),
+            params: def.params.iter().filter(|p| !p.starts_with('*') && !p.contains('=')).map(|p| {
+                let (name, ty) = p.split_once(':').unwrap_or((p, ""));
+                (name.trim().to_string(), Some(ty.trim().to_string()).filter(|t| !t.is_empty()))
+            }).collect(),
+            primitive: *primitive,
+            asynchronous: header.trim_start().starts_with("async"),
+            counter,
+        })
+    }).collect()
+}
+
+/// Top-level functions starting a `Worker`, in a file using `worker_threads` or web workers
+pub fn js_concurrent_units(source: &str) -> Vec<ConcurrentUnit> {
+    if !source.contains("new Worker(") {
+        return Vec::new();
+    }
+    let defs = js_definitions(source);
+    defs.iter().filter_map(|def| {
+        let text = &source[def.start..];
+        let callable = text.trim_start_matches("async ").starts_with("function") || text[..text.find('(').unwrap_or(0)].contains('=');
+        let body = def.body(source, &defs);
+        if !callable || !body.contains("new Worker(") {
+            return None;
+        }
+        Some(ConcurrentUnit {
+            function: def.name.clone(),
+            owner: None,
+            create: None,
+            returns: None,
+            params: def.params.iter().map(|p| {
+                let (name, ty) = p.split('=').next().unwrap_or(p).split_once(':').unwrap_or((p, ""));
+                (name.trim().to_string(), Some(ty.trim().to_string()).filter(|t| !t.is_empty()))
+            }).collect(),
+            primitive: SyncPrimitive::Worker,
+            asynchronous: true,
+            counter: None,
+        })
+    }).collect()
+}
+
+/// The indented block after the line ending at `start`
+fn python_block(source: &str, start: usize) -> &str {
+    let rest = &source[start..];
+    let first = rest.find('\n').map(|i| i + 1).unwrap_or(rest.len());
+    let indent = rest[first..].lines().find(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .unwrap_or(0);
+    let mut end = first;
+    for line in rest[first..].split_inclusive('\n') {
+        if !line.trim().is_empty() && line.len() - line.trim_start().len() < indent {
+            break;
+        }
+        end += line.len();
+    }
+    &rest[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrent_units_by_language() {
+        let units = rust_concurrent_units("use std::sync::atomic::{AtomicUsize, Ordering};\n\n#[derive(Default)]\npub struct Counter {\n    hits: AtomicUsize,\n}\n\nimpl Counter {\n    pub fn record(&self) {\n        self.hits.fetch_add(1, Ordering::SeqCst);\n    }\n\n    pub fn hits(&self) -> usize {\n        self.hits.load(Ordering::SeqCst)\n    }\n}\n");
+        assert_eq!(units.iter().map(|u| (u.function.as_str(), u.primitive, u.counter.as_deref())).collect::<Vec<_>>(), vec![("record", SyncPrimitive::Atomic, Some("shared.hits()"))]);
+
+        let units = python_concurrent_units("import asyncio\n\nclass Cache:\n    def __init__(self):\n        self._lock = asyncio.Lock()\n        self.writes = 0\n\n    async def put(self, key: str, value):\n        async with self._lock:\n            self.writes += 1\n");
+        assert_eq!((units[0].primitive, units[0].asynchronous, units[0].counter.as_deref()), (SyncPrimitive::AsyncLock, true, Some("shared.writes")));
+        assert_eq!(units[0].params, vec![("key".to_string(), Some("str".to_string())), ("value".to_string(), None)]);
+
+        let units = js_concurrent_units("const { Worker } = require('worker_threads');\n\nfunction runTask(input) {\n  return new Promise((resolve, reject) => {\n    const worker = new Worker('./task.js', { workerData: input });\n    worker.on('message', resolve);\n    worker.on('error', reject);\n  });\n}\n");
+        assert_eq!(units.iter().map(|u| u.function.as_str()).collect::<Vec<_>>(), vec!["runTask"]);
+    }
+}
@@ -0,0 +1,452 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{is_base_config, resolve_extends, LanguageLoader, UftError, TEST_PATH_PLACEHOLDERS};
+
+/// JSON Schema for language config files, as published in docs/
+pub const LANGUAGE_CONFIG_SCHEMA: &str = include_str!("../../docs/language-config.schema.json");
+
+/// Something wrong with a language config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    /// 1-based line of the offending value, or of the object missing a field
+    pub line: usize,
+    /// JSON pointer to the offending value, e.g. `/patterns/0/confidence`; empty for the whole file
+    pub pointer: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.pointer.as_str() {
+            "" => write!(f, "line {}: {}", self.line, self.message),
+            pointer => write!(f, "line {} at {}: {}", self.line, pointer, self.message),
+        }
+    }
+}
+
+/// Check one language config against the schema, and that its regexes compile and have the
+/// capture groups it names
+pub fn validate_language_config(content: &str) -> Vec<ConfigIssue> {
+    let config: Value = match serde_json::from_str(content) {
+        Ok(config) => config,
+        Err(e) => return vec![ConfigIssue { line: e.line(), pointer: String::new(), message: format!("Invalid JSON: {}", e) }],
+    };
+    check_language_config(&config, content)
+}
+
+/// [`validate_language_config`] for the config file at `path`, merged with the configs it
+/// `extends`. Issues point at the file's own lines, or at the nearest one for inherited values.
+/// A base config, which has no `name`, may leave out any field
+pub fn validate_language_config_file(path: &Path) -> Vec<ConfigIssue> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return vec![ConfigIssue { line: 1, pointer: String::new(), message: format!("Can't read {}: {}", path.display(), e) }],
+    };
+    let own: Value = match serde_json::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => return vec![ConfigIssue { line: e.line(), pointer: String::new(), message: format!("Invalid JSON: {}", e) }],
+    };
+    let config = match resolve_extends(path) {
+        Ok(config) => config,
+        Err(e) => return vec![ConfigIssue { line: line_of(&value_lines(&content), "/extends"), pointer: "/extends".to_string(), message: e.to_string() }],
+    };
+    let mut issues = check_language_config(&config, &content);
+    if is_base_config(&own) {
+        issues.retain(|issue| !(issue.pointer.is_empty() && issue.message.starts_with("Missing field")));
+    }
+    issues
+}
+
+fn check_language_config(config: &Value, content: &str) -> Vec<ConfigIssue> {
+    let schema: Value = serde_json::from_str(LANGUAGE_CONFIG_SCHEMA).expect("the language config schema is valid JSON");
+
+    let mut problems = Vec::new();
+    check_schema(&schema, config, "", &mut problems);
+
+    let patterns = config.get("patterns").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+    let adapter = ["script", "wasm", "command"].into_iter().filter(|key| config.get(key).is_some()).count();
+    if patterns.is_empty() && config.get("patterns").is_some() && adapter == 0 {
+        problems.push(("/patterns".to_string(), "`patterns` can't be empty unless `script`, `wasm` or `command` names an adapter".to_string()));
+    }
+    if adapter > 1 {
+        problems.push(("/command".to_string(), "Only one of `script`, `wasm` and `command` can name an adapter".to_string()));
+    }
+    if let Some(test_path) = config.get("test_path").and_then(Value::as_str) {
+        let placeholder = regex::Regex::new(r"\{[^}]*\}").expect("valid placeholder regex");
+        for found in placeholder.find_iter(test_path) {
+            if !TEST_PATH_PLACEHOLDERS.contains(&found.as_str()) {
+                problems.push(("/test_path".to_string(), format!("Unknown placeholder `{}` in `test_path`: expected one of {}", found.as_str(), TEST_PATH_PLACEHOLDERS.join(", "))));
+            } else if found.as_str() == "{parent}" && found.start() > 0 {
+                problems.push(("/test_path".to_string(), "`{parent}` can only start `test_path`".to_string()));
+            }
+        }
+    }
+    for (i, pattern) in patterns.iter().enumerate() {
+        let Some(source) = pattern.get("regex").and_then(Value::as_str) else { continue };
+        let regex = match regex::Regex::new(source) {
+            Ok(regex) => regex,
+            Err(e) => {
+                let message = e.to_string().lines().last().unwrap_or_default().to_string();
+                problems.push((format!("/patterns/{}/regex", i), format!("Invalid regex: {}", message)));
+                continue;
+            }
+        };
+        let endpoint = pattern.get("pattern_type").and_then(Value::as_str) == Some("api_endpoint");
+        let has_group = |group: &str| pattern.pointer(&format!("/capture_groups/{}", group)).is_some_and(Value::is_u64);
+        if endpoint && !has_group("path") && !has_group("name") {
+            problems.push((format!("/patterns/{}/capture_groups", i), "An `api_endpoint` rule needs a `path` or `name` capture group".to_string()));
+        }
+        for group in ["name", "parameters", "return_type", "method", "path"] {
+            let index = pattern.pointer(&format!("/capture_groups/{}", group)).and_then(Value::as_u64);
+            if let Some(index) = index.filter(|&index| index as usize >= regex.captures_len()) {
+                problems.push((
+                    format!("/patterns/{}/capture_groups/{}", i, group),
+                    format!("Capture group {} doesn't exist: the regex has {}", index, regex.captures_len() - 1),
+                ));
+            }
+        }
+    }
+
+    let lines = value_lines(content);
+    let mut issues: Vec<ConfigIssue> = problems.into_iter()
+        .map(|(pointer, message)| ConfigIssue { line: line_of(&lines, &pointer), pointer, message })
+        .collect();
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// Validate every JSON file in `dir`, or just `dir` when it's a file, by file name. Beyond each
+/// file's own problems, flags extensions another config or a built-in language already claims,
+/// since only one of them would be used
+pub fn validate_config_dir(dir: &Path) -> Result<Vec<(PathBuf, Vec<ConfigIssue>)>> {
+    let files = if dir.is_file() {
+        vec![dir.to_path_buf()]
+    } else {
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)
+            .map_err(|e| UftError::Config(format!("Can't read {}: {}", dir.display(), e)))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "json"))
+            .collect();
+        files.sort();
+        files
+    };
+
+    let mut claimed: HashMap<String, String> = LanguageLoader::new(String::new()).get_supported_extensions().into_iter()
+        .map(|(extension, language)| (extension, format!("the built-in {} adapter", language)))
+        .collect();
+    let mut results = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(&file)?;
+        let mut issues = validate_language_config_file(&file);
+        let own: Value = serde_json::from_str(&content).unwrap_or_default();
+        let config = if is_base_config(&own) { Value::Null } else { resolve_extends(&file).unwrap_or_default() };
+        let extensions = config.get("extensions").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+        let lines = value_lines(&content);
+        let name = file.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        for (i, extension) in extensions.iter().enumerate() {
+            let Some(extension) = extension.as_str() else { continue };
+            match claimed.get(extension) {
+                Some(owner) => issues.push(ConfigIssue {
+                    line: line_of(&lines, &format!("/extensions/{}", i)),
+                    pointer: format!("/extensions/{}", i),
+                    message: format!("Extension `{}` is already claimed by {}", extension, owner),
+                }),
+                None => {
+                    claimed.insert(extension.to_string(), name.clone());
+                }
+            }
+        }
+        issues.sort_by_key(|issue| issue.line);
+        results.push((file, issues));
+    }
+    Ok(results)
+}
+
+/// Check `value` against the subset of JSON Schema the language config schema uses, collecting
+/// JSON pointers with what's wrong at them
+fn check_schema(schema: &Value, value: &Value, pointer: &str, problems: &mut Vec<(String, String)>) {
+    if let Some(types) = schema.get("type") {
+        let allowed: Vec<&str> = match types {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+        let actual = json_type(value);
+        if !allowed.iter().any(|&allowed| allowed == actual || (allowed == "number" && actual == "integer")) {
+            problems.push((pointer.to_string(), format!("{} should be {}, not {}", field_name(pointer), allowed.join(" or "), actual)));
+            return;
+        }
+    }
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+            problems.push((pointer.to_string(), format!("Unknown {} {}: expected one of {}", field_name(pointer), value, options.join(", "))));
+        }
+    }
+    if let (Some(minimum), Some(number)) = (schema.get("minimum").and_then(Value::as_f64), value.as_f64()) {
+        if number < minimum {
+            problems.push((pointer.to_string(), format!("{} should be at least {}", field_name(pointer), minimum)));
+        }
+    }
+    if let (Some(maximum), Some(number)) = (schema.get("maximum").and_then(Value::as_f64), value.as_f64()) {
+        if number > maximum {
+            problems.push((pointer.to_string(), format!("{} should be at most {}", field_name(pointer), maximum)));
+        }
+    }
+    if schema.get("minLength").and_then(Value::as_u64).is_some_and(|min| value.as_str().is_some_and(|text| (text.chars().count() as u64) < min)) {
+        problems.push((pointer.to_string(), format!("{} can't be empty", field_name(pointer))));
+    }
+
+    match value {
+        Value::Object(object) => {
+            for field in schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    let within = if pointer.is_empty() { String::new() } else { format!(" in {}", field_name(pointer)) };
+                    problems.push((pointer.to_string(), format!("Missing field `{}`{}", field, within)));
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, field) in object {
+                let child = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                match (properties.and_then(|properties| properties.get(key)), schema.get("additionalProperties")) {
+                    (Some(field_schema), _) => check_schema(field_schema, field, &child, problems),
+                    (None, Some(Value::Bool(false))) => problems.push((child, format!("Unknown field `{}`", key))),
+                    (None, Some(additional)) => check_schema(additional, field, &child, problems),
+                    (None, None) => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if schema.get("minItems").and_then(Value::as_u64).is_some_and(|min| (items.len() as u64) < min) {
+                problems.push((pointer.to_string(), format!("{} can't be empty", field_name(pointer))));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check_schema(item_schema, item, &format!("{}/{}", pointer, i), problems);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_u64() || number.is_i64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// How a message names the value at `pointer`: `patterns[0].regex` for `/patterns/0/regex`
+fn field_name(pointer: &str) -> String {
+    let mut name = String::new();
+    for segment in pointer.split('/').skip(1) {
+        if segment.parse::<usize>().is_ok() {
+            name.push_str(&format!("[{}]", segment));
+        } else {
+            if !name.is_empty() {
+                name.push('.');
+            }
+            name.push_str(&segment.replace("~1", "/").replace("~0", "~"));
+        }
+    }
+    if name.is_empty() { "The config".to_string() } else { format!("`{}`", name) }
+}
+
+/// The line of the value at `pointer`, or of its nearest ancestor that's there
+fn line_of(lines: &HashMap<String, usize>, pointer: &str) -> usize {
+    let mut pointer = pointer;
+    loop {
+        if let Some(&line) = lines.get(pointer) {
+            return line;
+        }
+        match pointer.rfind('/') {
+            Some(parent) => pointer = &pointer[..parent],
+            None => return 1,
+        }
+    }
+}
+
+/// The line each value of a well-formed JSON document starts on, by JSON pointer
+fn value_lines(content: &str) -> HashMap<String, usize> {
+    let mut scanner = LineScanner { bytes: content.as_bytes(), pos: 0, line: 1, lines: HashMap::new() };
+    scanner.value(String::new());
+    scanner.lines
+}
+
+struct LineScanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+    lines: HashMap<String, usize>,
+}
+
+impl LineScanner<'_> {
+    fn value(&mut self, pointer: String) {
+        self.skip_whitespace();
+        self.lines.insert(pointer.clone(), self.line);
+        match self.bytes.get(self.pos) {
+            Some(b'{') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b'"') => {
+                            let key = self.string();
+                            self.skip_whitespace();
+                            self.pos += 1; // the colon
+                            self.value(format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1")));
+                        }
+                        Some(b',') => self.pos += 1,
+                        Some(_) => {
+                            self.pos += 1;
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut index = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(b',') => self.pos += 1,
+                        Some(_) => {
+                            self.value(format!("{}/{}", pointer, index));
+                            index += 1;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            Some(b'"') => {
+                self.string();
+            }
+            Some(_) => {
+                while self.bytes.get(self.pos).is_some_and(|byte| !b",]} \t\r\n".contains(byte)) {
+                    self.pos += 1;
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// The raw text of the string at `pos`, moving past it
+    fn string(&mut self) -> String {
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        let text = String::from_utf8_lossy(&self.bytes[start..self.pos.min(self.bytes.len())]).to_string();
+        self.pos += 1;
+        text
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            match byte {
+                b'\n' => self.line += 1,
+                b' ' | b'\t' | b'\r' => {}
+                _ => break,
+            }
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+  "name": "kotlin",
+  "extensions": ["kt", "py"],
+  "framework": "junt5",
+  "patterns": [
+    {
+      "name": "function",
+      "pattern_type": "function",
+      "regex": "fun\\s+(\\w+)",
+      "capture_groups": {
+        "name": 1,
+        "parameters": 2,
+        "parameter_separator": ",",
+        "parameter_format": "name_type"
+      },
+      "confidence": "high"
+    }
+  ],
+  "test_template": {
+    "test_function": "@Test fun {{TEST_NAME}}() {}",
+    "file_extension": "Test.kt",
+    "placeholders": {},
+    "footer": "}"
+  },
+  "imports": []
+}
+"#;
+
+    #[test]
+    fn test_validate_language_config() {
+        let issues: Vec<(usize, String)> = validate_language_config(CONFIG).into_iter()
+            .map(|issue| (issue.line, issue.message))
+            .collect();
+        assert_eq!(issues, vec![
+            (4, r#"Unknown `framework` "junt5": expected one of "jest", "mocha", "pytest", "unittest", "cargo-test", "nextest", "rstest", "testing", "testify", "junit5", "testng", "phpunit", "pest", "nunit", "xunit", "xctest", "quick", "kotest""#.to_string()),
+            (12, "Capture group 2 doesn't exist: the regex has 1".to_string()),
+            (16, "`patterns[0].confidence` should be number, not string".to_string()),
+            (23, "Unknown field `footer`".to_string()),
+        ]);
+        assert_eq!(validate_language_config("{\n  \"name\": \"kotlin\",\n}")[0].line, 3);
+        let missing = CONFIG.replace("      \"regex\": \"fun\\\\s+(\\\\w+)\",\n", "");
+        assert!(validate_language_config(&missing).contains(&ConfigIssue { line: 6, pointer: "/patterns/0".to_string(), message: "Missing field `regex` in `patterns[0]`".to_string() }));
+        let mut plugin: Value = serde_json::from_str(include_str!("../../language_configs/kotlin.json")).unwrap();
+        plugin["patterns"] = serde_json::json!([]);
+        let issues = validate_language_config(&plugin.to_string());
+        assert_eq!(issues[0].message, "`patterns` can't be empty unless `script`, `wasm` or `command` names an adapter");
+        plugin["wasm"] = serde_json::json!("kotlin.wasm");
+        assert!(validate_language_config(&plugin.to_string()).is_empty());
+        plugin["test_path"] = serde_json::json!("src/test/kotlin/{parent}/{Stem}Spec.kt");
+        assert_eq!(validate_language_config(&plugin.to_string())[0].message, "`{parent}` can only start `test_path`");
+        plugin["test_path"] = serde_json::json!("{parent}/{name}Test.kt");
+        assert_eq!(validate_language_config(&plugin.to_string())[0].message, "Unknown placeholder `{name}` in `test_path`: expected one of {parent}, {stem}, {Stem}, {ext}");
+        plugin.as_object_mut().unwrap().remove("test_path");
+        plugin["patterns"] = serde_json::json!([{
+            "name": "route", "pattern_type": "api_endpoint", "regex": "Route::(get|post)\\('([^']*)'", "confidence": 0.8,
+            "capture_groups": { "method": 1, "parameter_separator": ",", "parameter_format": "name_only" }
+        }]);
+        assert_eq!(validate_language_config(&plugin.to_string())[0].message, "An `api_endpoint` rule needs a `path` or `name` capture group");
+        plugin["patterns"][0]["capture_groups"]["path"] = serde_json::json!(3);
+        assert_eq!(validate_language_config(&plugin.to_string())[0].message, "Capture group 3 doesn't exist: the regex has 2");
+        plugin["patterns"] = serde_json::json!([]);
+        plugin["command"] = serde_json::json!(["./kotlin-adapter"]);
+        assert_eq!(validate_language_config(&plugin.to_string())[0].message, "Only one of `script`, `wasm` and `command` can name an adapter");
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("kotlin.json"), CONFIG).unwrap();
+        let results = validate_config_dir(dir.path()).unwrap();
+        assert!(results[0].1.contains(&ConfigIssue { line: 3, pointer: "/extensions/1".to_string(), message: "Extension `py` is already claimed by the built-in python adapter".to_string() }));
+        for (file, issues) in validate_config_dir(Path::new("language_configs")).unwrap() {
+            assert!(issues.is_empty(), "{}: {:?}", file.display(), issues);
+        }
+    }
+}
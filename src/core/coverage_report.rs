@@ -0,0 +1,221 @@
+use anyhow::{Context as _, Result};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::{PatternType, TestablePattern};
+
+/// Line and function hit counts for one source file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileCoverage {
+    /// Hits per 1-based line number, for executable lines only
+    pub lines: HashMap<usize, u64>,
+    /// Hits per function name, when the report records functions
+    pub functions: HashMap<String, u64>,
+}
+
+/// Existing coverage read from an LCOV tracefile or a Cobertura XML report
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoverageReport {
+    /// Keyed by the path as written in the report
+    pub files: HashMap<PathBuf, FileCoverage>,
+}
+
+impl CoverageReport {
+    /// Load a report, detecting Cobertura XML by its content and treating anything else as LCOV
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read coverage report {}", path.display()))?;
+        if content.trim_start().starts_with('<') {
+            Ok(Self::parse_cobertura(&content))
+        } else {
+            Ok(Self::parse_lcov(&content))
+        }
+    }
+
+    pub fn parse_lcov(content: &str) -> Self {
+        let mut report = Self::default();
+        let mut current: Option<(PathBuf, FileCoverage)> = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(path) = line.strip_prefix("SF:") {
+                current = Some((PathBuf::from(path), FileCoverage::default()));
+            } else if line == "end_of_record" {
+                if let Some((path, file)) = current.take() {
+                    report.files.insert(path, file);
+                }
+            } else if let Some((_, file)) = current.as_mut() {
+                if let Some(data) = line.strip_prefix("DA:") {
+                    let mut fields = data.split(',');
+                    if let (Some(Ok(number)), Some(Ok(hits))) = (fields.next().map(str::parse), fields.next().map(str::parse)) {
+                        file.lines.insert(number, hits);
+                    }
+                } else if let Some(data) = line.strip_prefix("FNDA:") {
+                    if let Some((hits, name)) = data.split_once(',') {
+                        file.functions.insert(name.to_string(), hits.parse().unwrap_or(0));
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    pub fn parse_cobertura(content: &str) -> Self {
+        let class_regex = Regex::new(r#"(?s)<class\b[^>]*\bfilename="([^"]+)"[^>]*>(.*?)</class>"#).unwrap();
+        let method_regex = Regex::new(r#"(?s)<method\b[^>]*\bname="([^"]+)"[^>]*>(.*?)</method>"#).unwrap();
+        let line_regex = Regex::new(r#"<line\b[^>]*\bnumber="(\d+)"[^>]*\bhits="(\d+)""#).unwrap();
+
+        let mut report = Self::default();
+        for class in class_regex.captures_iter(content) {
+            let file = report.files.entry(PathBuf::from(&class[1])).or_default();
+            for line in line_regex.captures_iter(&class[2]) {
+                let hits = line[2].parse().unwrap_or(0);
+                let entry = file.lines.entry(line[1].parse().unwrap_or(0)).or_insert(0);
+                *entry = (*entry).max(hits);
+            }
+            for method in method_regex.captures_iter(&class[2]) {
+                let hits = line_regex.captures_iter(&method[2]).map(|l| l[2].parse::<u64>().unwrap_or(0)).max().unwrap_or(0);
+                let entry = file.functions.entry(method[1].to_string()).or_insert(0);
+                *entry = (*entry).max(hits);
+            }
+        }
+
+        report
+    }
+
+    /// Coverage for a source file; report paths may be absolute or relative to any ancestor,
+    /// so the entry sharing the longest path suffix with `path` wins
+    pub fn file(&self, path: &Path) -> Option<&FileCoverage> {
+        let target = normalized(path);
+        self.files.iter()
+            .filter_map(|(reported, file)| {
+                let reported = normalized(reported);
+                let shared = target.iter().rev().zip(reported.iter().rev()).take_while(|(a, b)| a == b).count();
+                (shared == reported.len() || shared == target.len()).then_some((shared, file))
+            })
+            .max_by_key(|(shared, _)| *shared)
+            .map(|(_, file)| file)
+    }
+
+    /// Patterns still worth generating tests for: functions with an unexecuted line or a
+    /// zero function hit count. Files missing from the report keep every pattern.
+    pub fn uncovered_patterns(&self, file_path: &str, source: &str, patterns: Vec<TestablePattern>) -> Vec<TestablePattern> {
+        let Some(file) = self.file(Path::new(file_path)) else {
+            return patterns;
+        };
+
+        let mut starts: Vec<usize> = patterns.iter()
+            .filter_map(|p| match &p.pattern_type {
+                PatternType::Function(func) => definition_line(source, &func.name),
+                _ => None,
+            })
+            .collect();
+        starts.sort_unstable();
+        starts.dedup();
+
+        patterns.into_iter()
+            .filter(|pattern| {
+                let PatternType::Function(func) = &pattern.pattern_type else {
+                    return false;
+                };
+                if file.functions.get(&func.name) == Some(&0) {
+                    return true;
+                }
+                let Some(start) = definition_line(source, &func.name) else {
+                    return !file.functions.contains_key(&func.name);
+                };
+                let end = starts.iter().copied().find(|&line| line > start).unwrap_or(usize::MAX);
+                file.lines.iter().any(|(&line, &hits)| hits == 0 && line >= start && line < end)
+            })
+            .collect()
+    }
+}
+
+/// 1-based line of a function's definition
+pub fn definition_line(source: &str, name: &str) -> Option<usize> {
+    let name = regex::escape(name);
+    let definition = Regex::new(&format!(
+        r"\b(?:def|fn|function|func)\s+(?:\([^)]*\)\s*)?{name}\b|\b{name}\s*[=:]\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*=>|\w+\s*=>)|^\s*(?:(?:public|private|protected|static|final|async|override)\s+)*(?:[\w<>\[\],]+\s+)?{name}\s*\([^)]*\)\s*(?:throws\s+[\w., ]+)?\{{",
+        name = name
+    )).unwrap();
+
+    source.lines().position(|line| definition.is_match(line)).map(|index| index + 1)
+}
+
+fn normalized(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Context, FunctionPattern, SourceLocation};
+
+    fn function(name: &str) -> TestablePattern {
+        TestablePattern {
+            id: name.to_string(),
+            pattern_type: PatternType::Function(FunctionPattern { name: name.to_string(), parameters: vec![], return_type: None }),
+            location: SourceLocation { file: "src/calc.py".to_string(), line: 1, column: 0 },
+            context: Context { function_name: Some(name.to_string()), class_name: None, module_name: None },
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_parse_lcov() {
+        let lcov = "TN:\nSF:/work/app/src/calc.py\nFN:1,add\nFNDA:3,add\nFNDA:0,divide\nDA:1,3\nDA:2,3\nDA:5,0\nend_of_record\n";
+        let report = CoverageReport::parse_lcov(lcov);
+
+        let file = report.file(Path::new("src/calc.py")).unwrap();
+        assert_eq!(file.lines.get(&5), Some(&0));
+        assert_eq!(file.functions.get("divide"), Some(&0));
+        assert!(report.file(Path::new("src/other.py")).is_none());
+    }
+
+    #[test]
+    fn test_parse_cobertura() {
+        let xml = r#"<?xml version="1.0" ?>
+<coverage><packages><package name="app"><classes>
+<class name="calc.py" filename="app/calc.py">
+  <methods><method name="add" signature=""><lines><line number="2" hits="4"/></lines></method></methods>
+  <lines><line number="1" hits="1"/><line number="2" hits="4"/><line number="5" hits="0"/></lines>
+</class>
+</classes></package></packages></coverage>"#;
+        let report = CoverageReport::parse_cobertura(xml);
+
+        let file = report.file(Path::new("/repo/app/calc.py")).unwrap();
+        assert_eq!(file.lines.get(&2), Some(&4));
+        assert_eq!(file.lines.get(&5), Some(&0));
+        assert_eq!(file.functions.get("add"), Some(&4));
+    }
+
+    #[test]
+    fn test_uncovered_patterns() {
+        let source = "def add(a, b):\n    return a + b\n\ndef divide(a, b):\n    if b == 0:\n        raise ValueError()\n    return a / b\n";
+        let lcov = "SF:src/calc.py\nDA:1,2\nDA:2,2\nDA:4,1\nDA:5,1\nDA:6,0\nDA:7,1\nend_of_record\n";
+        let report = CoverageReport::parse_lcov(lcov);
+
+        let kept = report.uncovered_patterns("src/calc.py", source, vec![function("add"), function("divide")]);
+        let names: Vec<&str> = kept.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(names, vec!["divide"]);
+
+        // Files the report doesn't know about keep everything
+        assert_eq!(report.uncovered_patterns("src/new.py", source, vec![function("add")]).len(), 1);
+    }
+
+    #[test]
+    fn test_definition_line() {
+        assert_eq!(definition_line("x = 1\ndef area(w, h):\n    pass\n", "area"), Some(2));
+        assert_eq!(definition_line("const f = 1;\nconst add = (a, b) => a + b;\n", "add"), Some(2));
+        assert_eq!(definition_line("class A {\n    public int add(int a, int b) {\n", "add"), Some(2));
+        assert_eq!(definition_line("func (s *Server) Start() error {\n", "Start"), Some(1));
+    }
+}
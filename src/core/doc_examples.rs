@@ -0,0 +1,207 @@
+use regex::Regex;
+
+/// A usage example from a function's documentation
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocExample {
+    pub function: String,
+    /// Statements in documented order
+    pub steps: Vec<DocStep>,
+}
+
+/// One documented statement and the outcome documented for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocStep {
+    /// Source of the statement; Python continuation lines are joined with `\n`
+    pub code: String,
+    pub outcome: Option<DocOutcome>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocOutcome {
+    /// Documented result, as written (a Python repr or a JS literal)
+    Value(String),
+    /// Exception class named in a documented traceback
+    Raises(String),
+}
+
+/// `>>>` sessions from Python docstrings, one example per documented function
+pub fn python_doc_examples(source: &str) -> Vec<DocExample> {
+    let def_regex = Regex::new(r#"(?s)def\s+(\w+)\s*\([^)]*\)[^:]*:\s*[rRuU]?(?:"""(.*?)"""|'''(.*?)''')"#).unwrap();
+    let traceback_regex = Regex::new(r"^([A-Za-z_][\w.]*)(?::|$)").unwrap();
+
+    def_regex.captures_iter(source).filter_map(|cap| {
+        let docstring = cap.get(2).or_else(|| cap.get(3))?.as_str();
+        let mut steps: Vec<DocStep> = Vec::new();
+        let mut output: Vec<&str> = Vec::new();
+
+        let finish = |steps: &mut Vec<DocStep>, output: &mut Vec<&str>| {
+            if let (Some(step), Some(first)) = (steps.last_mut(), output.first()) {
+                step.outcome = if first.starts_with("Traceback") {
+                    output.last()
+                        .and_then(|last| traceback_regex.captures(last))
+                        .map(|c| DocOutcome::Raises(c[1].to_string()))
+                } else {
+                    Some(DocOutcome::Value(output.join("\n")))
+                };
+            }
+            output.clear();
+        };
+
+        for line in docstring.lines() {
+            let trimmed = line.trim();
+            if let Some(code) = trimmed.strip_prefix(">>>") {
+                finish(&mut steps, &mut output);
+                steps.push(DocStep { code: code.strip_prefix(' ').unwrap_or(code).to_string(), outcome: None });
+            } else if let Some(more) = trimmed.strip_prefix("...").filter(|_| output.is_empty() && !steps.is_empty()) {
+                let step = steps.last_mut().unwrap();
+                step.code.push('\n');
+                step.code.push_str(more.strip_prefix(' ').unwrap_or(more));
+            } else if trimmed.is_empty() {
+                finish(&mut steps, &mut output);
+            } else if !steps.is_empty() {
+                output.push(trimmed);
+            }
+        }
+        finish(&mut steps, &mut output);
+
+        (!steps.is_empty()).then(|| DocExample { function: cap[1].to_string(), steps })
+    }).collect()
+}
+
+/// `@example` blocks from JSDoc comments; a trailing `// => value` documents the statement's result
+pub fn jsdoc_examples(source: &str) -> Vec<DocExample> {
+    let comment_regex = Regex::new(
+        r"/\*\*((?:[^*]|\*+[^*/])*)\*+/\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?(?:function\s*\*?\s*(\w+)|(?:const|let|var)\s+(\w+)\s*=|(\w+)\s*\()"
+    ).unwrap();
+    let result_regex = Regex::new(r"^(.*?);?\s*//\s*(?:=>|→|returns?:?)\s*(.+?);?\s*$").unwrap();
+
+    comment_regex.captures_iter(source).filter_map(|cap| {
+        let function = cap.get(2).or_else(|| cap.get(3)).or_else(|| cap.get(4))?.as_str().to_string();
+        let mut steps = Vec::new();
+        let mut in_example = false;
+
+        for line in cap[1].lines() {
+            let line = line.trim().trim_start_matches('*').trim();
+            if line.starts_with('@') {
+                in_example = line.starts_with("@example");
+                continue;
+            }
+            if !in_example || line.is_empty() || line.starts_with("```") || line.starts_with("//") {
+                continue;
+            }
+
+            steps.push(match result_regex.captures(line) {
+                Some(result) => DocStep {
+                    code: result[1].trim().to_string(),
+                    outcome: Some(DocOutcome::Value(result[2].to_string())),
+                },
+                None => DocStep { code: line.to_string(), outcome: None },
+            });
+        }
+
+        (!steps.is_empty()).then_some(DocExample { function, steps })
+    }).collect()
+}
+
+/// Runnable code blocks from Rust `///` comments, one example per block. Hidden `# ` lines are
+/// kept; `use` lines for the crate itself, `crate_name`, are dropped since generated tests
+/// import with `super::*`. Without a crate name, every `use` of a crate other than `std`,
+/// `core` and `alloc` is taken to be one.
+pub fn rust_doc_examples(source: &str, crate_name: Option<&str>) -> Vec<DocExample> {
+    let fn_regex = Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+(\w+)").unwrap();
+    let crate_use_regex = Regex::new(r"^use\s+(\w+)::").unwrap();
+    let mut examples = Vec::new();
+    let mut doc_lines: Vec<&str> = Vec::new();
+
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        if let Some(doc) = trimmed.strip_prefix("///") {
+            doc_lines.push(doc.strip_prefix(' ').unwrap_or(doc));
+            continue;
+        }
+        if trimmed.starts_with("#[") || doc_lines.is_empty() {
+            continue;
+        }
+
+        if let Some(cap) = fn_regex.captures(line) {
+            let mut block: Option<Vec<String>> = None;
+            let mut runnable = false;
+            for doc in &doc_lines {
+                if let Some(info) = doc.trim().strip_prefix("```") {
+                    match block.take() {
+                        Some(lines) if runnable && !lines.is_empty() => {
+                            let steps = lines.into_iter().map(|code| DocStep { code, outcome: None }).collect();
+                            examples.push(DocExample { function: cap[1].to_string(), steps });
+                        }
+                        Some(_) => {}
+                        None => {
+                            runnable = info.split(',').map(str::trim).all(|attr| matches!(attr, "" | "rust" | "edition2018" | "edition2021"));
+                            block = Some(Vec::new());
+                        }
+                    }
+                } else if let Some(lines) = block.as_mut() {
+                    let code = match doc.strip_prefix('#') {
+                        Some(hidden) if hidden.is_empty() || hidden.starts_with(' ') => hidden.trim_start(),
+                        _ => doc,
+                    };
+                    let own_crate = crate_use_regex.captures(code).is_some_and(|c| match crate_name {
+                        Some(name) => &c[1] == name,
+                        None => !matches!(&c[1], "std" | "core" | "alloc"),
+                    });
+                    if !code.trim().is_empty() && !own_crate {
+                        lines.push(code.to_string());
+                    }
+                }
+            }
+        }
+        doc_lines.clear();
+    }
+
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python_doc_examples() {
+        let source = "def add(a, b):\n    \"\"\"Add numbers.\n\n    >>> add(2, 3)\n    5\n    >>> total = add(1, 1)\n    >>> for i in range(2):\n    ...     print(i)\n    0\n    1\n    >>> add(None, 1)\n    Traceback (most recent call last):\n        ...\n    TypeError: unsupported operand\n    \"\"\"\n    return a + b\n\ndef plain(x):\n    '''No examples.'''\n";
+        let examples = python_doc_examples(source);
+
+        assert_eq!(examples.len(), 1);
+        let steps = &examples[0].steps;
+        assert_eq!(steps[0], DocStep { code: "add(2, 3)".to_string(), outcome: Some(DocOutcome::Value("5".to_string())) });
+        assert_eq!(steps[1].outcome, None);
+        assert_eq!(steps[2].code, "for i in range(2):\n    print(i)");
+        assert_eq!(steps[2].outcome, Some(DocOutcome::Value("0\n1".to_string())));
+        assert_eq!(steps[3].outcome, Some(DocOutcome::Raises("TypeError".to_string())));
+    }
+
+    #[test]
+    fn test_jsdoc_examples() {
+        let source = "/** Config */\nconst limit = 3;\n\n/**\n * Join words.\n * @param {string[]} words\n * @example\n * join(['a', 'b']); // => 'a b'\n * const s = join([]);\n * @returns {string}\n */\nfunction join(words) {\n  return words.join(' ');\n}\n";
+        let examples = jsdoc_examples(source);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].function, "join");
+        assert_eq!(examples[0].steps, vec![
+            DocStep { code: "join(['a', 'b'])".to_string(), outcome: Some(DocOutcome::Value("'a b'".to_string())) },
+            DocStep { code: "const s = join([]);".to_string(), outcome: None },
+        ]);
+    }
+
+    #[test]
+    fn test_rust_doc_examples() {
+        let source = "/// Doubles.\n///\n/// ```\n/// # use my_crate::double;\n/// use serde_json::json;\n/// # let base = 2;\n/// assert_eq!(double(base), 4);\n/// ```\n///\n/// ```ignore\n/// double(huge);\n/// ```\n#[inline]\npub fn double(x: i32) -> i32 {\n    x * 2\n}\n";
+        let examples = rust_doc_examples(source, Some("my_crate"));
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].function, "double");
+        let code: Vec<&str> = examples[0].steps.iter().map(|s| s.code.as_str()).collect();
+        assert_eq!(code, vec!["use serde_json::json;", "let base = 2;", "assert_eq!(double(base), 4);"]);
+
+        let code: Vec<String> = rust_doc_examples(source, None)[0].steps.iter().map(|s| s.code.clone()).collect();
+        assert_eq!(code, vec!["let base = 2;", "assert_eq!(double(base), 4);"]);
+    }
+}
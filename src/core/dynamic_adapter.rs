@@ -6,7 +6,7 @@ use std::collections::HashMap;
 
 use crate::core::{
     TestablePattern, PatternType, TestCase, TestSuite, TestGenerator, 
-    SourceLocation, Context, FunctionPattern
+    SourceLocation, Context, FunctionPattern, LanguageScript, ScriptTest, ApiEndpoint, HttpMethod
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +17,22 @@ pub struct LanguageConfig {
     pub patterns: Vec<PatternConfig>,
     pub test_template: TestTemplate,
     pub imports: Vec<String>,
+    /// Where test files go, e.g. `{parent}/__tests__/{stem}.test.{ext}`, in place of the
+    /// `tests/` folder at the root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_path: Option<String>,
+    /// WebAssembly adapter module that analyzes files and generates tests in place of the
+    /// regex patterns, relative to the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm: Option<String>,
+    /// Executable and arguments of an adapter process speaking JSON-RPC over stdio, run in the
+    /// config file's directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+    /// Rhai script, relative to the config file, that finds more functions and writes test
+    /// bodies for the regex patterns' adapter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +51,10 @@ pub struct CaptureGroups {
     pub parameters: Option<usize>,    // Which capture group contains parameters
     pub parameter_separator: String,  // How parameters are separated (e.g., ",")
     pub parameter_format: String,     // Parameter format: "name_type", "type_name", "name_only"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<usize>,        // Which capture group contains an api_endpoint's HTTP method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<usize>,          // Which capture group contains an api_endpoint's route
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,11 +68,18 @@ pub struct TestTemplate {
 
 pub struct DynamicLanguageAdapter {
     config: LanguageConfig,
+    script: Option<LanguageScript>,
 }
 
 impl DynamicLanguageAdapter {
     pub fn new(config: LanguageConfig) -> Self {
-        Self { config }
+        Self { config, script: None }
+    }
+
+    /// Also run the config's Rhai script
+    pub fn with_script(mut self, script: LanguageScript) -> Self {
+        self.script = Some(script);
+        self
     }
 
     pub fn from_json(json_str: &str) -> Result<Self> {
@@ -78,9 +105,16 @@ impl DynamicLanguageAdapter {
                 
                 let line_num = content[..cap.get(0).unwrap().start()].matches('\n').count() + 1;
                 
+                let pattern_type = if pattern_config.pattern_type == "api_endpoint" {
+                    let method = self.extract_capture_group(&cap, &pattern_config.capture_groups.method, "GET");
+                    let path = self.extract_capture_group(&cap, &pattern_config.capture_groups.path.or(pattern_config.capture_groups.name), "/");
+                    PatternType::ApiCall(ApiEndpoint { method: http_method(&method), path, parameters })
+                } else {
+                    self.create_pattern_type(&pattern_config.pattern_type, &name, &parameters, &return_type)?
+                };
                 patterns.push(TestablePattern {
                     id: uuid::Uuid::new_v4().to_string(),
-                    pattern_type: self.create_pattern_type(&pattern_config.pattern_type, &name, &parameters, &return_type)?,
+                    pattern_type,
                     location: SourceLocation {
                         file: "".to_string(),
                         line: line_num,
@@ -99,6 +133,63 @@ impl DynamicLanguageAdapter {
         Ok(patterns)
     }
 
+    /// The functions the script's `analyze` finds
+    fn script_patterns(&self, script: &LanguageScript, content: &str, file_path: &str) -> Result<Vec<TestablePattern>> {
+        let class_name = self.extract_class_name(content);
+        Ok(script.analyze(content, file_path)?.into_iter().map(|function| TestablePattern {
+            id: uuid::Uuid::new_v4().to_string(),
+            location: SourceLocation { file: "".to_string(), line: function.line, column: 1 },
+            context: Context {
+                function_name: Some(function.name.clone()),
+                class_name: class_name.clone(),
+                module_name: None,
+            },
+            pattern_type: PatternType::Function(FunctionPattern {
+                name: function.name,
+                parameters: function.parameters,
+                return_type: Some(function.return_type.unwrap_or_else(|| "void".to_string())),
+            }),
+            confidence: 0.8,
+        }).collect())
+    }
+
+    /// A test case for an endpoint an `api_endpoint` rule found
+    fn endpoint_test_case(&self, endpoint: &ApiEndpoint) -> Result<TestCase> {
+        let method = endpoint.method.to_string();
+        let route: String = endpoint.path.split(|c: char| !c.is_alphanumeric())
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.to_title_case())
+            .collect();
+        let name = format!("test{}{}", method.to_title_case(), route);
+        let description = format!("Test for {} {}", method, endpoint.path);
+        let script_body = match &self.script {
+            Some(script) => script.test_body(&ScriptTest {
+                name: &name,
+                description: &description,
+                function: &endpoint.path,
+                parameters: &endpoint.parameters,
+                return_type: None,
+                method: Some(&method),
+                path: Some(&endpoint.path),
+            })?,
+            None => None,
+        };
+        Ok(TestCase {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            description,
+            input: serde_json::json!({
+                "method": method,
+                "path": endpoint.path,
+                "parameters": endpoint.parameters
+            }),
+            expected_output: serde_json::json!({ "status": 200 }),
+            test_body: script_body.unwrap_or_else(|| "        // TODO: Implement test logic".to_string()),
+            assertions: vec![],
+            test_category: crate::core::TestCategory::HappyPath,
+        })
+    }
+
     fn extract_capture_group(&self, cap: &regex::Captures, group_index: &Option<usize>, default: &str) -> String {
         if let Some(index) = group_index {
             cap.get(*index)
@@ -198,6 +289,7 @@ impl DynamicLanguageAdapter {
             let mut placeholders = HashMap::new();
             placeholders.insert("TEST_NAME".to_string(), test_case.name.clone());
             placeholders.insert("TEST_DESCRIPTION".to_string(), test_case.description.clone());
+            placeholders.insert("TEST_BODY".to_string(), test_case.test_body.clone());
             
             let test_function = self.replace_placeholders(
                 &self.config.test_template.test_function, 
@@ -235,8 +327,12 @@ impl DynamicLanguageAdapter {
 
 #[async_trait]
 impl TestGenerator for DynamicLanguageAdapter {
-    async fn analyze_code(&self, source: &str, _file_path: &str) -> Result<Vec<TestablePattern>> {
-        self.detect_patterns(source)
+    async fn analyze_code(&self, source: &str, file_path: &str) -> Result<Vec<TestablePattern>> {
+        let mut patterns = self.detect_patterns(source)?;
+        if let Some(script) = &self.script {
+            patterns.extend(self.script_patterns(script, source, file_path)?);
+        }
+        Ok(patterns)
     }
 
     async fn generate_tests(&self, patterns: Vec<TestablePattern>) -> Result<TestSuite> {
@@ -245,13 +341,27 @@ impl TestGenerator for DynamicLanguageAdapter {
         for pattern in &patterns {
             match &pattern.pattern_type {
                 PatternType::Function(func) => {
+                    let name = format!("test{}", func.name);
+                    let description = format!("Test for {} {}", 
+                        if self.config.name == "go" || self.config.name == "rust" { "function" } else { "method" }, 
+                        func.name
+                    );
+                    let script_body = match &self.script {
+                        Some(script) => script.test_body(&ScriptTest {
+                            name: &name,
+                            description: &description,
+                            function: &func.name,
+                            parameters: &func.parameters,
+                            return_type: func.return_type.as_deref(),
+                            method: None,
+                            path: None,
+                        })?,
+                        None => None,
+                    };
                     test_cases.push(TestCase {
                         id: uuid::Uuid::new_v4().to_string(),
-                        name: format!("test{}", func.name),
-                        description: format!("Test for {} {}", 
-                            if self.config.name == "go" || self.config.name == "rust" { "function" } else { "method" }, 
-                            func.name
-                        ),
+                        name,
+                        description,
                         input: serde_json::json!({
                             "function": func.name,
                             "parameters": func.parameters
@@ -259,11 +369,12 @@ impl TestGenerator for DynamicLanguageAdapter {
                         expected_output: serde_json::json!({
                             "type": func.return_type.as_ref().unwrap_or(&"void".to_string())
                         }),
-                        test_body: "        // TODO: Implement test logic".to_string(),
+                        test_body: script_body.unwrap_or_else(|| "        // TODO: Implement test logic".to_string()),
                         assertions: vec![],
                         test_category: crate::core::TestCategory::HappyPath,
                     });
                 }
+                PatternType::ApiCall(endpoint) => test_cases.push(self.endpoint_test_case(endpoint)?),
                 _ => {} // Skip other pattern types for now
             }
         }
@@ -277,13 +388,14 @@ impl TestGenerator for DynamicLanguageAdapter {
             name: format!("{}Test", class_name.replace("Test", "")),
             language: self.config.name.clone(),
             framework: self.config.framework.clone(),
+            test_code: Some(self.generate_test_content(&test_cases)),
             test_cases,
             imports: self.config.imports.clone(),
             test_type: crate::core::TestType::Unit,
             setup_requirements: vec![],
             cleanup_requirements: vec![],
             coverage_target: 70.0,
-            test_code: None,
+            fixtures: vec![],
         })
     }
 
@@ -303,8 +415,25 @@ impl TestGenerator for DynamicLanguageAdapter {
         70.0
     }
 
-    fn generate_test_code(&self, _test_suite: &TestSuite) -> Result<String> {
-        Ok("// Dynamic adapter tests - TODO: implement code generation".to_string())
+    fn test_path(&self) -> Option<&str> {
+        self.config.test_path.as_deref()
+    }
+
+    /// The suite's test cases with the config's `test_template`
+    fn generate_test_code(&self, test_suite: &TestSuite) -> Result<String> {
+        Ok(self.generate_test_content(&test_suite.test_cases))
+    }
+}
+
+/// The HTTP method an `api_endpoint` rule captured, e.g. `post` or `Post`; GET when it's none
+/// of the known ones
+fn http_method(captured: &str) -> HttpMethod {
+    match captured.trim().to_uppercase().as_str() {
+        "POST" => HttpMethod::Post,
+        "PUT" => HttpMethod::Put,
+        "PATCH" => HttpMethod::Patch,
+        "DELETE" => HttpMethod::Delete,
+        _ => HttpMethod::Get,
     }
 }
 
@@ -345,6 +474,10 @@ mod tests {
             name: "go".to_string(),
             extensions: vec!["go".to_string()],
             framework: "testing".to_string(),
+            wasm: None,
+            command: None,
+            script: None,
+            test_path: None,
             patterns: vec![
                 PatternConfig {
                     name: "function".to_string(),
@@ -356,6 +489,8 @@ mod tests {
                         return_type: Some(3),
                         parameter_separator: ",".to_string(),
                         parameter_format: "name_type".to_string(),
+                        method: None,
+                        path: None,
                     },
                     confidence: 0.9,
                 }
@@ -435,6 +570,44 @@ func Add(a int, b int) int {
         assert_eq!(test_suite.test_cases[0].name, "testAdd");
     }
 
+    #[tokio::test]
+    async fn test_api_endpoint_rules() {
+        let mut config = create_go_config();
+        config.patterns.push(PatternConfig {
+            name: "route".to_string(),
+            pattern_type: "api_endpoint".to_string(),
+            regex: r#"\.(Get|Post)\("([^"]*)""#.to_string(),
+            capture_groups: CaptureGroups {
+                name: None,
+                parameters: None,
+                return_type: None,
+                parameter_separator: ",".to_string(),
+                parameter_format: "name_only".to_string(),
+                method: Some(1),
+                path: Some(2),
+            },
+            confidence: 0.7,
+        });
+        let adapter = DynamicLanguageAdapter::new(config);
+        let source = "r.Get(\"/users/{id}\", show)\nr.Post(\"/users\", create)";
+        let patterns = adapter.analyze_code(source, "routes.go").await.unwrap();
+        assert_eq!(patterns.len(), 2);
+        match &patterns[1].pattern_type {
+            PatternType::ApiCall(endpoint) => {
+                assert!(matches!(endpoint.method, HttpMethod::Post));
+                assert_eq!(endpoint.path, "/users");
+            }
+            other => panic!("expected an endpoint, got {:?}", other),
+        }
+        assert_eq!(patterns[1].location.line, 2);
+        assert_eq!(patterns[1].confidence, 0.7);
+
+        let test_suite = adapter.generate_tests(patterns).await.unwrap();
+        let names: Vec<&str> = test_suite.test_cases.iter().map(|test_case| test_case.name.as_str()).collect();
+        assert_eq!(names, vec!["testGetUsersId", "testPostUsers"]);
+        assert_eq!(test_suite.test_cases[0].description, "Test for GET /users/{id}");
+    }
+
     #[test]
     fn test_parameter_parsing() {
         let adapter = DynamicLanguageAdapter::new(create_go_config());
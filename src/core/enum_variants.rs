@@ -0,0 +1,392 @@
+use regex::Regex;
+
+use super::auth_flows::{js_definitions, python_definitions, rust_definitions, Definition};
+use super::cli::braced;
+
+/// A Rust enum variant, a member of a TypeScript discriminated union or of a Python `Enum`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    /// The variant's name, or a union member's discriminant value
+    pub name: String,
+    /// Payload fields and their types; tuple variants name theirs `0`, `1`, ...
+    pub fields: Vec<(String, String)>,
+    /// The payload is a tuple, `V(T, U)`, rather than named fields
+    pub tuple: bool,
+}
+
+/// A Rust enum, TypeScript discriminated union or Python `Enum` class
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumType {
+    pub name: String,
+    /// The property telling union members apart, e.g. `kind`; `None` outside TypeScript
+    pub discriminant: Option<String>,
+    pub variants: Vec<Variant>,
+}
+
+/// How a dispatching function receives the enum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Receiver {
+    /// As its only argument
+    Argument,
+    /// As its only argument, by reference: `&E`
+    Reference,
+    /// As `self`, in a method of the enum
+    Method,
+}
+
+/// A function branching on the variants of an enum it receives
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantDispatch {
+    pub function: String,
+    pub returns: Option<String>,
+    pub enum_type: EnumType,
+    pub receiver: Receiver,
+    /// Variants with a `match` arm, `case` or comparison of their own
+    pub handled: Vec<String>,
+    /// Variants whose branch is `todo!()`, `unimplemented!()` or `raise NotImplementedError`
+    pub stubbed: Vec<String>,
+    /// A `_`, `default` or `else` branch catches the variants without one
+    pub fallback: bool,
+}
+
+impl VariantDispatch {
+    /// Why the function doesn't handle `variant`, or `None` when it has a branch of its own
+    pub fn gap(&self, variant: &str) -> Option<&'static str> {
+        if self.stubbed.iter().any(|v| v == variant) {
+            Some("its branch is a stub")
+        } else if self.handled.iter().any(|v| v == variant) {
+            None
+        } else if self.fallback {
+            Some("it falls through to the catch-all branch")
+        } else {
+            Some("no branch handles it")
+        }
+    }
+
+    fn new(def: &Definition, enum_type: &EnumType, receiver: Receiver) -> Self {
+        VariantDispatch {
+            function: def.name.clone(),
+            returns: def.returns.clone(),
+            enum_type: enum_type.clone(),
+            receiver,
+            handled: Vec::new(),
+            stubbed: Vec::new(),
+            fallback: false,
+        }
+    }
+
+    fn handle(&mut self, variant: &str, stub: bool) {
+        let list = if stub { &mut self.stubbed } else { &mut self.handled };
+        if !list.iter().any(|v| v == variant) {
+            list.push(variant.to_string());
+        }
+    }
+}
+
+/// Functions taking a non-generic enum, or methods on one, that `match` on it
+pub fn rust_variant_dispatches(source: &str) -> Vec<VariantDispatch> {
+    let enums = rust_enums(source);
+    let impl_block = Regex::new(r"\bimpl(?:<[^>]*>)?\s+(?:[\w:]+(?:<[^>]*>)?\s+for\s+)?(\w+)\s*\{").unwrap();
+    let impls: Vec<(String, usize, usize)> = impl_block.captures_iter(source).map(|cap| {
+        let open = cap.get(0).unwrap().end() - 1;
+        (cap[1].to_string(), open, open + braced(source, open).len())
+    }).collect();
+    let arm = Regex::new(r"(?m)^[ \t]*(.*?)=>").unwrap();
+    let binding = Regex::new(r"^(?:_|[a-z_]\w*)$").unwrap();
+    let stub = Regex::new(r"^\s*\{?\s*(?:todo|unimplemented)!").unwrap();
+    let defs = rust_definitions(source);
+
+    defs.iter().filter_map(|def| {
+        let (receiver, subject, enum_name) = match def.params.as_slice() {
+            [param] if matches!(param.as_str(), "self" | "&self" | "mut self") => {
+                let (name, _, _) = impls.iter().rev().find(|(_, open, close)| (*open..*close).contains(&def.start))?;
+                (Receiver::Method, "self", name.as_str())
+            }
+            [param] => {
+                let (name, ty) = param.split_once(':')?;
+                let (receiver, ty) = match ty.trim().strip_prefix('&') {
+                    Some(ty) => (Receiver::Reference, ty.trim()),
+                    None => (Receiver::Argument, ty.trim()),
+                };
+                (receiver, name.trim().trim_start_matches("mut "), ty)
+            }
+            _ => return None,
+        };
+        let enum_type = enums.iter().find(|e| e.name == enum_name)?;
+        let body = def.body(source, &defs);
+        let subject_match = Regex::new(&format!(r"\bmatch\s+[&*]?{}\s*\{{", regex::escape(subject))).unwrap();
+        let block = braced(body, subject_match.find(body)?.end() - 1);
+
+        let mut dispatch = VariantDispatch::new(def, enum_type, receiver);
+        for cap in arm.captures_iter(block) {
+            let pattern = cap[1].split(" if ").next().unwrap_or("").trim();
+            if binding.is_match(pattern) {
+                dispatch.fallback = true;
+                continue;
+            }
+            let branch = &block[cap.get(0).unwrap().end()..];
+            for variant in &enum_type.variants {
+                let named = Regex::new(&format!(r"(?:\b(?:{}|Self)::|^|\|\s*){}\b", regex::escape(&enum_type.name), regex::escape(&variant.name))).unwrap();
+                if named.is_match(pattern) {
+                    dispatch.handle(&variant.name, stub.is_match(branch));
+                }
+            }
+        }
+        Some(dispatch)
+    }).collect()
+}
+
+/// Functions taking a discriminated union, annotated by name, that `switch` on or compare its
+/// discriminant
+pub fn js_variant_dispatches(source: &str) -> Vec<VariantDispatch> {
+    let unions = ts_unions(source);
+    let case = Regex::new(r#"\bcase\s+(['"])(.*?)['"]\s*:"#).unwrap();
+    let default = Regex::new(r"\bdefault\s*:").unwrap();
+    let defs = js_definitions(source);
+
+    defs.iter().filter_map(|def| {
+        let [param] = def.params.as_slice() else { return None };
+        let (name, ty) = param.split_once(':')?;
+        let union = unions.iter().find(|u| u.name == ty.trim())?;
+        let subject = format!(r"\b{}\.{}\b", regex::escape(name.trim()), regex::escape(union.discriminant.as_deref()?));
+        let body = def.body(source, &defs);
+        let switch = Regex::new(&format!(r"\bswitch\s*\(\s*{}\s*\)\s*\{{", subject)).unwrap();
+        let comparison = Regex::new(&format!(r#"{0}\s*===?\s*['"]([^'"]*)['"]|['"]([^'"]*)['"]\s*===?\s*{0}"#, subject)).unwrap();
+
+        let mut dispatch = VariantDispatch::new(def, union, Receiver::Argument);
+        let mut found = false;
+        if let Some(m) = switch.find(body) {
+            let block = braced(body, m.end() - 1);
+            for cap in case.captures_iter(block) {
+                dispatch.handle(&cap[2], false);
+            }
+            dispatch.fallback = default.is_match(block);
+            found = true;
+        }
+        for cap in comparison.captures_iter(body) {
+            dispatch.handle(cap.get(1).or(cap.get(2)).unwrap().as_str(), false);
+            found = true;
+        }
+        found.then_some(dispatch)
+    }).collect()
+}
+
+/// Functions taking a member of an `Enum` class, or methods of one, that `match` on it or
+/// compare it against members
+pub fn python_variant_dispatches(source: &str) -> Vec<VariantDispatch> {
+    let enums = python_enums(source);
+    let case = Regex::new(r"(?m)^[ \t]*case\s+(.+?)\s*:[ \t]*$").unwrap();
+    let stub = Regex::new(r"^\s*raise\s+NotImplementedError\b").unwrap();
+    let otherwise = Regex::new(r"(?m)^[ \t]*else\s*:").unwrap();
+    let defs = python_definitions(source);
+
+    defs.iter().filter_map(|def| {
+        let body = def.body(source, &defs);
+        let ((enum_type, _, _), receiver, subject) = match def.params.as_slice() {
+            [] => (enums.iter().find(|(_, start, end)| (*start..*end).contains(&def.start))?, Receiver::Method, "self"),
+            [param] => {
+                let (name, ty) = param.split_once(':').map(|(n, t)| (n, Some(t.trim()))).unwrap_or((param, None));
+                let found = enums.iter().find(|(e, _, _)| match ty {
+                    Some(ty) => ty == e.name,
+                    None => body.contains(&format!("{}.", e.name)),
+                })?;
+                (found, Receiver::Argument, name.trim())
+            }
+            _ => return None,
+        };
+        let member = |text: &str| -> Vec<String> {
+            Regex::new(&format!(r"\b{}\.(\w+)\b", regex::escape(&enum_type.name))).unwrap()
+                .captures_iter(text)
+                .map(|cap| cap[1].to_string())
+                .filter(|name| enum_type.variants.iter().any(|v| v.name == *name))
+                .collect()
+        };
+        let subject = regex::escape(subject);
+        let compared = Regex::new(&format!(r"\b{0}\s*(?:==|is)\s*([\w.]+)|([\w.]+)\s*(?:==|is)\s*{0}\b|\b{0}\s+in\s*[(\[{{]([^)\]}}]*)", subject)).unwrap();
+
+        let mut dispatch = VariantDispatch::new(def, enum_type, receiver);
+        let mut found = false;
+        if Regex::new(&format!(r"(?m)^[ \t]*match\s+{}\s*:", subject)).unwrap().is_match(body) {
+            for cap in case.captures_iter(body) {
+                if cap[1].trim() == "_" {
+                    dispatch.fallback = true;
+                }
+                let is_stub = stub.is_match(&body[cap.get(0).unwrap().end()..]);
+                for name in member(&cap[1]) {
+                    dispatch.handle(&name, is_stub);
+                }
+            }
+            found = true;
+        }
+        for cap in compared.captures_iter(body) {
+            for name in member(cap.iter().skip(1).flatten().next().unwrap().as_str()) {
+                dispatch.handle(&name, false);
+                found = true;
+            }
+        }
+        if found && otherwise.is_match(body) {
+            dispatch.fallback = true;
+        }
+        found.then_some(dispatch)
+    }).collect()
+}
+
+/// Non-generic enums with their variants' payloads
+fn rust_enums(source: &str) -> Vec<EnumType> {
+    let item = Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?\s+)?enum\s+(\w+)\s*(<[^>{]*>)?\s*\{").unwrap();
+    let noise = Regex::new(r"//[^\n]*|#\[[^\]]*\]").unwrap();
+    let name = Regex::new(r"^\w+").unwrap();
+
+    item.captures_iter(source).filter(|cap| cap.get(2).is_none()).map(|cap| {
+        let body = noise.replace_all(braced(source, cap.get(0).unwrap().end() - 1), "");
+        let variants = split_top_level(&body, &[',']).into_iter().filter_map(|item| {
+            let variant = name.find(&item)?.as_str().to_string();
+            let rest = item[variant.len()..].trim();
+            let payload = rest.get(1..rest.len().saturating_sub(1)).unwrap_or("");
+            let (fields, tuple) = match rest.chars().next() {
+                Some('(') => (split_top_level(payload, &[',']).into_iter().enumerate().map(|(i, ty)| (i.to_string(), ty)).collect(), true),
+                Some('{') => (split_top_level(payload, &[',']).iter().filter_map(|field| {
+                    let (field, ty) = field.split_once(':')?;
+                    Some((field.trim().trim_start_matches("pub ").trim().to_string(), ty.trim().to_string()))
+                }).collect(), false),
+                _ => (Vec::new(), false),
+            };
+            Some(Variant { name: variant, fields, tuple })
+        }).collect();
+        EnumType { name: cap[1].to_string(), discriminant: None, variants }
+    }).collect()
+}
+
+/// `type X = { kind: 'a'; ... } | { kind: 'b'; ... }` unions, with members written inline or
+/// as named interfaces and object types, discriminated by a string-literal property they all
+/// share
+fn ts_unions(source: &str) -> Vec<EnumType> {
+    let alias = Regex::new(r"(?m)^[ \t]*(?:export\s+)?type\s+(\w+)\s*=").unwrap();
+    let property = Regex::new(r"^(?:readonly\s+)?(\w+)(\??)\s*:\s*(.+)$").unwrap();
+    let literal = Regex::new(r#"^(['"])(.*)['"]$"#).unwrap();
+
+    alias.captures_iter(source).filter_map(|cap| {
+        let start = cap.get(0).unwrap().end();
+        let members = split_top_level(type_expression(&source[start..]), &['|']);
+        if members.len() < 2 {
+            return None;
+        }
+        let objects = members.iter().map(|member| match member.strip_prefix('{') {
+            Some(inline) => Some(inline.strip_suffix('}').unwrap_or(inline).to_string()),
+            None => {
+                let named = Regex::new(&format!(r"(?:\binterface\s+{0}\b[^{{]*|\btype\s+{0}\s*=\s*)\{{", regex::escape(member))).unwrap();
+                Some(braced(source, named.find(source)?.end() - 1).to_string())
+            }
+        }).collect::<Option<Vec<String>>>()?;
+        let properties: Vec<Vec<(String, bool, String)>> = objects.iter().map(|object| {
+            split_top_level(object, &[';', ',', '\n']).iter()
+                .filter_map(|p| property.captures(p).map(|c| (c[1].to_string(), &c[2] == "?", c[3].trim().to_string())))
+                .collect()
+        }).collect();
+
+        let discriminant = properties[0].iter()
+            .map(|(name, _, _)| name)
+            .find(|name| properties.iter().all(|props| props.iter().any(|(n, _, ty)| n == *name && literal.is_match(ty))))?
+            .clone();
+        let variants = properties.iter().map(|props| {
+            let value = props.iter().find(|(n, _, _)| *n == discriminant).map(|(_, _, ty)| literal.captures(ty).unwrap()[2].to_string()).unwrap();
+            let fields = props.iter()
+                .filter(|(n, optional, _)| *n != discriminant && !optional)
+                .map(|(n, _, ty)| (n.clone(), ty.clone()))
+                .collect();
+            Variant { name: value, fields, tuple: false }
+        }).collect();
+        Some(EnumType { name: cap[1].to_string(), discriminant: Some(discriminant), variants })
+    }).collect()
+}
+
+/// The type expression at the start of `text`: up to a `;` or blank line outside braces
+fn type_expression(text: &str) -> &str {
+    let mut depth = 0;
+    let mut previous = ' ';
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' | '(' | '<' => depth += 1,
+            '}' | ')' | '>' => depth -= 1,
+            ';' if depth == 0 => return &text[..i],
+            '\n' if depth == 0 && previous == '\n' => return &text[..i],
+            _ => {}
+        }
+        if !(c == ' ' || c == '\t') {
+            previous = c;
+        }
+    }
+    text
+}
+
+/// `Enum`, `IntEnum`, `StrEnum` and `Flag` subclasses with the span of their bodies
+fn python_enums(source: &str) -> Vec<(EnumType, usize, usize)> {
+    let class = Regex::new(r"(?m)^class\s+(\w+)\s*\(\s*(?:enum\.)?(?:Enum|IntEnum|StrEnum|Flag|IntFlag)\s*\)\s*:").unwrap();
+    let member = Regex::new(r"^([ \t]+)([A-Za-z]\w*)\s*=[^=]").unwrap();
+
+    class.captures_iter(source).map(|cap| {
+        let start = cap.get(0).unwrap().end();
+        let mut end = source.len();
+        let mut offset = start;
+        let mut indent = None;
+        let mut variants = Vec::new();
+        for line in source[start..].split_inclusive('\n').skip(1) {
+            offset += source[offset..].find('\n').map(|i| i + 1).unwrap_or(0);
+            if !line.trim().is_empty() && !line.starts_with(char::is_whitespace) {
+                end = offset;
+                break;
+            }
+            if let Some(m) = member.captures(line) {
+                if *indent.get_or_insert_with(|| m[1].to_string()) == m[1] {
+                    variants.push(Variant { name: m[2].to_string(), fields: Vec::new(), tuple: false });
+                }
+            }
+        }
+        (EnumType { name: cap[1].to_string(), discriminant: None, variants }, start, end)
+    }).collect()
+}
+
+/// `text` split at `separators` outside brackets and generics
+fn split_top_level(text: &str, separators: &[char]) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut depth = 0;
+    let mut previous = ' ';
+    for c in text.chars() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '>' if previous != '-' && previous != '=' => depth -= 1,
+            _ if depth == 0 && separators.contains(&c) => {
+                parts.push(String::new());
+                previous = c;
+                continue;
+            }
+            _ => {}
+        }
+        parts.last_mut().unwrap().push(c);
+        previous = c;
+    }
+    parts.into_iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_dispatches_by_language() {
+        let dispatches = rust_variant_dispatches("pub enum Shape {\n    /// A circle\n    Circle(f64),\n    Rect { w: f64, h: f64 },\n    Empty,\n}\n\nimpl Shape {\n    pub fn area(&self) -> f64 {\n        match self {\n            Shape::Circle(r) => 3.14 * r * r,\n            Self::Rect { w, h } => w * h,\n            _ => 0.0,\n        }\n    }\n}\n\npub fn label(shape: &Shape) -> &'static str {\n    match shape {\n        Shape::Circle(_) | Shape::Rect { .. } => \"solid\",\n        Shape::Empty => todo!(),\n    }\n}\n");
+        assert_eq!(dispatches.len(), 2);
+        assert_eq!(dispatches[0].enum_type.variants[1].fields, vec![("w".to_string(), "f64".to_string()), ("h".to_string(), "f64".to_string())]);
+        assert_eq!((dispatches[0].receiver, dispatches[0].gap("Empty")), (Receiver::Method, Some("it falls through to the catch-all branch")));
+        assert_eq!((dispatches[1].receiver, dispatches[1].gap("Rect"), dispatches[1].gap("Empty")), (Receiver::Reference, None, Some("its branch is a stub")));
+
+        let dispatches = js_variant_dispatches("type Shape =\n  | { kind: 'circle'; radius: number }\n  | Square;\n\ninterface Square {\n  kind: 'square';\n  size: number;\n}\n\nfunction area(shape: Shape) {\n  switch (shape.kind) {\n    case 'circle':\n      return Math.PI * shape.radius ** 2;\n  }\n}\n");
+        assert_eq!(dispatches[0].enum_type.variants.iter().map(|v| v.name.as_str()).collect::<Vec<_>>(), vec!["circle", "square"]);
+        assert_eq!(dispatches[0].gap("square"), Some("no branch handles it"));
+
+        let dispatches = python_variant_dispatches("class Color(Enum):\n    RED = 1\n    GREEN = 2\n\n    def hex(self):\n        match self:\n            case Color.RED:\n                return '#f00'\n            case Color.GREEN:\n                raise NotImplementedError\n\n\ndef is_warm(color: Color):\n    if color == Color.RED:\n        return True\n    else:\n        return False\n");
+        assert_eq!((dispatches[0].receiver, dispatches[0].stubbed.clone()), (Receiver::Method, vec!["GREEN".to_string()]));
+        assert_eq!((dispatches[1].handled.clone(), dispatches[1].fallback), (vec!["RED".to_string()], true));
+    }
+}
@@ -0,0 +1,178 @@
+use regex::Regex;
+
+use super::auth_flows::{js_definitions, python_definitions, rust_definitions, Definition};
+
+/// What a function does when the variable it reads is unset
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissingEnv {
+    /// Falls back to this literal, as written in the source
+    Default(String),
+    /// Throws, raises or returns `Err`
+    Error,
+    /// Panics through `unwrap`/`expect`
+    Panic,
+    /// Carries on with `undefined`/`None`, to an effect the source doesn't show
+    Unknown,
+}
+
+/// A function reading an environment variable
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvVarUse {
+    pub function: String,
+    pub params: Vec<String>,
+    pub returns: Option<String>,
+    pub variable: String,
+    pub missing: MissingEnv,
+    /// The function returns the value read, or its default, unchanged
+    pub returned: bool,
+}
+
+/// Value to set a variable to in tests, shaped by its name: `8080` for `PORT`, a URL for
+/// `API_URL`, `true` for `DEBUG`
+pub fn sample_env_value(variable: &str) -> &'static str {
+    let name = variable.to_uppercase();
+    let has = |parts: &[&str]| parts.iter().any(|part| name.contains(part));
+    if has(&["PORT"]) {
+        "8080"
+    } else if has(&["URL", "URI", "ENDPOINT"]) {
+        "http://localhost:8080"
+    } else if has(&["HOST"]) {
+        "localhost"
+    } else if has(&["DEBUG", "ENABLE", "FLAG", "VERBOSE"]) {
+        "true"
+    } else if has(&["TIMEOUT", "COUNT", "MAX", "MIN", "LIMIT", "SIZE", "RETRIES", "WORKERS"]) {
+        "5"
+    } else if has(&["LEVEL"]) {
+        "info"
+    } else {
+        "test-value"
+    }
+}
+
+impl EnvVarUse {
+    /// Value to set the variable to, distinct from the function's own default
+    pub fn set_value(&self) -> &'static str {
+        let sample = sample_env_value(&self.variable);
+        match &self.missing {
+            MissingEnv::Default(default) if default.trim_matches(['"', '\'']) == sample => match sample {
+                "8080" => "9090",
+                "info" => "debug",
+                "true" => "false",
+                "5" => "10",
+                "localhost" => "127.0.0.1",
+                _ => "test-value-set",
+            },
+            _ => sample,
+        }
+    }
+}
+
+/// `process.env.X` and `process.env['X']` reads, with `||`/`??` fallbacks and
+/// `if (!process.env.X) throw` guards
+pub fn js_env_uses(source: &str) -> Vec<EnvVarUse> {
+    let read = Regex::new(r#"process\.env(?:\.(\w+)|\[\s*['"](\w+)['"]\s*\])"#).unwrap();
+    let fallback = Regex::new(r#"^\s*(?:\|\||\?\?)\s*('[^'\n]*'|"[^"\n]*"|-?\d+(?:\.\d+)?|true|false)"#).unwrap();
+    env_uses(source, &js_definitions(source), &read, |body, variable, after| {
+        let default = fallback.captures(after);
+        let guard = Regex::new(&format!(r#"!\s*process\.env(?:\.{0}\b|\[\s*['"]{0}['"]\s*\])[^{{;]*\)\s*\{{?\s*throw\b"#, variable)).unwrap();
+        let missing = match &default {
+            Some(cap) => MissingEnv::Default(cap[1].to_string()),
+            None if guard.is_match(body) => MissingEnv::Error,
+            None => MissingEnv::Unknown,
+        };
+        let rest = &after[default.map(|cap| cap.get(0).unwrap().end()).unwrap_or(0)..];
+        (missing, Regex::new(r"^\s*(?:;|\}|\n|$)").unwrap().is_match(rest))
+    }, &Regex::new(r"(?:\breturn|=>)\s*$").unwrap())
+}
+
+/// `os.environ["X"]`, `os.environ.get("X", default)` and `os.getenv("X", default)` reads
+pub fn python_env_uses(source: &str) -> Vec<EnvVarUse> {
+    let read = Regex::new(r#"os\.(?:environ\[\s*["'](\w+)["']\s*\]|(?:environ\.get|getenv)\(\s*["'](\w+)["']\s*(?:,\s*("[^"\n]*"|'[^'\n]*'|-?\d+(?:\.\d+)?|True|False|None)\s*)?\))"#).unwrap();
+    env_uses(source, &python_definitions(source), &read, |body, variable, after| {
+        let guard = Regex::new(&format!(r#"if\s+not\s+os\.(?:environ\.get|getenv)\(\s*["']{}["'][^)]*\)\s*:\s*raise\b"#, variable)).unwrap();
+        let subscript = Regex::new(&format!(r#"os\.environ\[\s*["']{}["']\s*\]"#, variable)).unwrap();
+        let default = Regex::new(&format!(r#"os\.(?:environ\.get|getenv)\(\s*["']{}["']\s*,\s*("[^"\n]*"|'[^'\n]*'|-?\d+(?:\.\d+)?|True|False)\s*\)"#, variable)).unwrap();
+        let missing = if let Some(cap) = default.captures(body) {
+            MissingEnv::Default(cap[1].to_string())
+        } else if guard.is_match(body) || (subscript.is_match(body) && !body.contains("except")) {
+            MissingEnv::Error
+        } else {
+            MissingEnv::Unknown
+        };
+        (missing, Regex::new(r"^\s*(?:\n|$)").unwrap().is_match(after))
+    }, &Regex::new(r"\breturn\s+$").unwrap())
+}
+
+/// `env::var("X")` reads, with `unwrap_or` fallbacks, `?` and `unwrap`/`expect`
+pub fn rust_env_uses(source: &str) -> Vec<EnvVarUse> {
+    let read = Regex::new(r#"\benv::var\(\s*"(\w+)"\s*\)"#).unwrap();
+    let fallback = Regex::new(r#"^\s*\.(?:unwrap_or\(\s*|unwrap_or_else\(\s*\|_\|\s*)(?:String::from\()?("(?:[^"\\\n]|\\.)*")\)?(?:\.to_string\(\)|\.to_owned\(\)|\.into\(\))?\s*\)|^\s*\.unwrap_or_default\(\)"#).unwrap();
+    env_uses(source, &rust_definitions(source), &read, |_, _, after| {
+        let default = fallback.captures(after);
+        let missing = match &default {
+            Some(cap) => MissingEnv::Default(cap.get(1).map(|m| m.as_str()).unwrap_or("\"\"").to_string()),
+            None if Regex::new(r"^\s*(?:\.map_err\([^;]*?\))?\?").unwrap().is_match(after) => MissingEnv::Error,
+            None if Regex::new(r"^\s*\.(?:unwrap|expect)\(").unwrap().is_match(after) => MissingEnv::Panic,
+            None => MissingEnv::Unknown,
+        };
+        let rest = &after[default.map(|cap| cap.get(0).unwrap().end()).unwrap_or(0)..];
+        (missing, Regex::new(r"^\s*;?\s*\}").unwrap().is_match(rest))
+    }, &Regex::new(r"(?:\breturn\s+|[{;]\s*(?:std::)?)$").unwrap())
+}
+
+/// One use per function and variable read inside it. `classify` sees the function body, the
+/// variable and the source after the read; `returned_before` matches the text before a read
+/// whose value the function returns
+fn env_uses(
+    source: &str,
+    defs: &[Definition],
+    read: &Regex,
+    classify: impl Fn(&str, &str, &str) -> (MissingEnv, bool),
+    returned_before: &Regex,
+) -> Vec<EnvVarUse> {
+    let mut uses: Vec<EnvVarUse> = Vec::new();
+    for def in defs {
+        let body = def.body(source, defs);
+        for cap in read.captures_iter(body) {
+            let Some(variable) = cap.iter().skip(1).flatten().next().map(|m| m.as_str()) else { continue };
+            if uses.iter().any(|u| u.function == def.name && u.variable == variable) {
+                continue;
+            }
+            let whole = cap.get(0).unwrap();
+            let (missing, returned_after) = classify(body, variable, &body[whole.end()..]);
+            uses.push(EnvVarUse {
+                function: def.name.clone(),
+                params: def.params.clone(),
+                returns: def.returns.clone(),
+                variable: variable.to_string(),
+                missing,
+                returned: returned_after && returned_before.is_match(&body[..whole.start()]),
+            });
+        }
+    }
+    uses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_uses_by_language() {
+        let uses = js_env_uses("function getPort() {\n  return process.env.PORT || 3000;\n}\n\nfunction connect() {\n  if (!process.env.DATABASE_URL) {\n    throw new Error('DATABASE_URL is required');\n  }\n  return open(process.env.DATABASE_URL);\n}\n");
+        assert_eq!(uses.len(), 2);
+        assert_eq!((uses[0].missing.clone(), uses[0].returned), (MissingEnv::Default("3000".to_string()), true));
+        assert_eq!((uses[1].variable.as_str(), uses[1].missing.clone(), uses[1].returned), ("DATABASE_URL", MissingEnv::Error, false));
+
+        let uses = python_env_uses("def log_level():\n    return os.getenv(\"LOG_LEVEL\", \"info\")\n\ndef secret():\n    return os.environ[\"API_KEY\"]\n");
+        assert_eq!((uses[0].missing.clone(), uses[0].returned), (MissingEnv::Default("\"info\"".to_string()), true));
+        assert_eq!((uses[1].missing.clone(), uses[1].returned), (MissingEnv::Error, true));
+
+        let uses = rust_env_uses("pub fn host() -> String {\n    env::var(\"HOST\").unwrap_or_else(|_| \"127.0.0.1\".to_string())\n}\n\npub fn key() -> Result<String, VarError> {\n    let key = std::env::var(\"API_KEY\")?;\n    Ok(key)\n}\n\npub fn port() -> u16 {\n    env::var(\"PORT\").expect(\"PORT\").parse().unwrap()\n}\n");
+        assert_eq!((uses[0].missing.clone(), uses[0].returned), (MissingEnv::Default("\"127.0.0.1\"".to_string()), true));
+        assert_eq!((uses[1].missing.clone(), uses[1].returned), (MissingEnv::Error, false));
+        assert_eq!((uses[2].missing.clone(), uses[2].returned), (MissingEnv::Panic, false));
+        assert_eq!(sample_env_value("PORT"), "8080");
+        assert_eq!(python_env_uses("def level():\n    return os.getenv(\"LOG_LEVEL\", \"info\")\n")[0].set_value(), "debug");
+    }
+}
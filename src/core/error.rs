@@ -0,0 +1,60 @@
+use std::fmt;
+
+/// Failures callers can tell apart, each with its own process exit code. They travel inside
+/// `anyhow::Error`; [`UftError::exit_code_of`] finds them anywhere in an error's chain
+#[derive(Debug, Clone, PartialEq)]
+pub enum UftError {
+    /// Analysis or generation failed
+    Generation(String),
+    /// No adapter handles the language or file type
+    UnsupportedLanguage(String),
+    /// A config file, template or command-line setting is invalid
+    Config(String),
+    /// Some files of a directory run failed while the rest went through
+    PartialFailure { failed: usize, total: usize },
+}
+
+impl UftError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Generation(_) => 1,
+            Self::UnsupportedLanguage(_) => 2,
+            Self::Config(_) => 3,
+            Self::PartialFailure { .. } => 4,
+        }
+    }
+
+    /// Exit code for any error: its `UftError`'s, else 1
+    pub fn exit_code_of(error: &anyhow::Error) -> u8 {
+        error.chain()
+            .find_map(|cause| cause.downcast_ref::<UftError>())
+            .map_or(1, UftError::exit_code)
+    }
+}
+
+impl fmt::Display for UftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Generation(message) | Self::UnsupportedLanguage(message) | Self::Config(message) => f.write_str(message),
+            Self::PartialFailure { failed, total } => write!(f, "{} of {} files failed", failed, total),
+        }
+    }
+}
+
+impl std::error::Error for UftError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_code_found_through_context() {
+        let error = Err::<(), _>(UftError::Config("Invalid ignore pattern: src/[".to_string()))
+            .context("Invalid uft.toml")
+            .unwrap_err();
+        assert_eq!(UftError::exit_code_of(&error), 3);
+        assert_eq!(UftError::exit_code_of(&anyhow::anyhow!("disk full")), 1);
+        assert_eq!(UftError::PartialFailure { failed: 2, total: 9 }.to_string(), "2 of 9 files failed");
+    }
+}
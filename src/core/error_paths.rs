@@ -0,0 +1,130 @@
+use regex::Regex;
+
+/// An error a function raises or returns explicitly
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorSite {
+    /// Exception class or error variant path, e.g. `ValueError` or `ParseError::Empty`
+    pub error: String,
+    /// Condition guarding the raise, when it sits directly under an `if`
+    pub guard: Option<String>,
+}
+
+/// Kind of argument that satisfies a guard condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerValue {
+    /// `null`/`None`/`undefined`
+    Missing,
+    /// Empty string or collection
+    Empty,
+    Negative,
+    Zero,
+    /// A value of the wrong type
+    WrongType,
+}
+
+impl ErrorSite {
+    /// The argument `param` needs for the guard to hold; `None` if the guard doesn't mention
+    /// it or tests it in a way no kind of argument is known to satisfy
+    pub fn trigger_for(&self, param: &str) -> Option<TriggerValue> {
+        let guard = self.guard.as_deref()?;
+        let p = regex::escape(param);
+        let mentions = |pattern: &str| Regex::new(&pattern.replace("{p}", &p)).unwrap().is_match(guard);
+
+        if !mentions(r"\b{p}\b") {
+            return None;
+        }
+
+        let trigger = if mentions(r"typeof\s+{p}\b|isinstance\(\s*{p}\b|isFinite\(\s*{p}\b|isArray\(\s*{p}\b") {
+            TriggerValue::WrongType
+        } else if mentions(r"\b{p}\s*<=?\s*0\b") {
+            TriggerValue::Negative
+        } else if mentions(r"\b{p}\s*===?\s*0\b") {
+            TriggerValue::Zero
+        } else if mentions(r"\b{p}\.length\s*===?\s*0|len\(\s*{p}\s*\)\s*==\s*0|\b{p}\.is_empty\(\)|\b{p}\.trim\(\)\.is_empty\(\)|\b{p}\s*===?\s*(?:''|\x22\x22)|not\s+{p}\.strip\(\)") {
+            TriggerValue::Empty
+        } else if mentions(r"\b{p}\s+is\s+None\b|\b{p}\s*===?\s*(?:null|undefined|None)\b|\b{p}\.is_none\(\)|(?:^|[^\w.])!\s*{p}(?:[^\w.(]|$)|\bnot\s+{p}(?:[^\w.(]|$)") {
+            TriggerValue::Missing
+        } else {
+            return None;
+        };
+        Some(trigger)
+    }
+
+    /// [`trigger_for`](Self::trigger_for) each of `params`; `None` when the raise is guarded
+    /// and no argument satisfies the guard, so a call can't be shown to reach it
+    pub fn triggers(&self, params: &[&str]) -> Option<Vec<Option<TriggerValue>>> {
+        let triggers: Vec<_> = params.iter().map(|param| self.trigger_for(param)).collect();
+        (self.guard.is_none() || triggers.iter().any(Option::is_some)).then_some(triggers)
+    }
+}
+
+/// Text between the first `{` at or after `from` and its matching `}`
+pub fn braced_block(source: &str, from: usize) -> Option<&str> {
+    let open = from + source.get(from..)?.find('{')?;
+    let mut depth = 0;
+    for (offset, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&source[open + 1..open + offset]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Keep the first site for each distinct error
+pub fn dedup_error_sites(sites: Vec<ErrorSite>) -> Vec<ErrorSite> {
+    let mut unique: Vec<ErrorSite> = Vec::new();
+    for site in sites {
+        if !unique.iter().any(|s| s.error == site.error) {
+            unique.push(site);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site(guard: &str) -> ErrorSite {
+        ErrorSite { error: "ValueError".to_string(), guard: Some(guard.to_string()) }
+    }
+
+    #[test]
+    fn test_trigger_for_guards() {
+        assert_eq!(site("amount < 0").trigger_for("amount"), Some(TriggerValue::Negative));
+        assert_eq!(site("divisor === 0").trigger_for("divisor"), Some(TriggerValue::Zero));
+        assert_eq!(site("typeof name !== 'string'").trigger_for("name"), Some(TriggerValue::WrongType));
+        assert_eq!(site("not isinstance(count, int)").trigger_for("count"), Some(TriggerValue::WrongType));
+        assert_eq!(site("items.length === 0").trigger_for("items"), Some(TriggerValue::Empty));
+        assert_eq!(site("input.trim().is_empty()").trigger_for("input"), Some(TriggerValue::Empty));
+        assert_eq!(site("user is None").trigger_for("user"), Some(TriggerValue::Missing));
+        assert_eq!(site("user is None").trigger_for("other"), None);
+        assert_eq!(site("!token").trigger_for("token"), Some(TriggerValue::Missing));
+        assert_eq!(site("not name").trigger_for("name"), Some(TriggerValue::Missing));
+        assert_eq!(site("limit > 100").trigger_for("limit"), None);
+        assert_eq!(site("user != null").trigger_for("user"), None);
+        assert_eq!(site("not user.active").trigger_for("user"), None);
+        assert_eq!(ErrorSite { error: "Error".to_string(), guard: None }.trigger_for("x"), None);
+    }
+
+    #[test]
+    fn test_triggers_skip_unsatisfiable_guards() {
+        assert_eq!(site("amount < 0").triggers(&["amount", "note"]), Some(vec![Some(TriggerValue::Negative), None]));
+        assert_eq!(site("amount > 100").triggers(&["amount"]), None);
+        assert_eq!(ErrorSite { error: "Error".to_string(), guard: None }.triggers(&["x"]), Some(vec![None]));
+    }
+
+    #[test]
+    fn test_braced_block_nesting() {
+        let source = "fn f() { if x { y(); } z(); } fn g() {}";
+        assert_eq!(braced_block(source, 0), Some(" if x { y(); } z(); "));
+        assert_eq!(braced_block("no braces", 0), None);
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use git2::build::RepoBuilder;
+use git2::{Cred, CredentialType, FetchOptions, RemoteCallbacks, Repository};
+use std::path::{Path, PathBuf};
+
+/// libgit2 asks again after every rejected credential, so give up after this many
+const MAX_CREDENTIAL_ATTEMPTS: usize = 3;
+
+/// How to authenticate with a git remote: an access token over HTTPS, and a key file or
+/// else the SSH agent over SSH
+#[derive(Debug, Clone, Default)]
+pub struct GitCredentials {
+    pub token: Option<String>,
+    /// User name sent with the token; `x-access-token` when empty
+    pub token_user: String,
+    /// Private key to use instead of the agent's
+    pub ssh_key: Option<PathBuf>,
+}
+
+impl GitCredentials {
+    /// The credential to answer a remote's challenge with
+    pub fn credential(&self, username_from_url: Option<&str>, allowed: CredentialType) -> std::result::Result<Cred, git2::Error> {
+        if allowed.contains(CredentialType::SSH_KEY) {
+            let user = username_from_url.unwrap_or("git");
+            return match &self.ssh_key {
+                Some(key) => Cred::ssh_key(user, None, key, None),
+                None => Cred::ssh_key_from_agent(user),
+            };
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if let Some(token) = &self.token {
+                let user = if self.token_user.is_empty() { "x-access-token" } else { &self.token_user };
+                return Cred::userpass_plaintext(user, token);
+            }
+        }
+        if allowed.contains(CredentialType::DEFAULT) {
+            return Cred::default();
+        }
+        Err(git2::Error::from_str("The remote needs credentials: pass --token-env or --ssh-key, or add a key to ssh-agent"))
+    }
+
+    pub fn callbacks(&self) -> RemoteCallbacks<'_> {
+        let mut attempts = 0;
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |_, username_from_url, allowed| {
+            attempts += 1;
+            if attempts > MAX_CREDENTIAL_ATTEMPTS {
+                return Err(git2::Error::from_str("Authentication failed: the remote rejected the credentials"));
+            }
+            self.credential(username_from_url, allowed)
+        });
+        callbacks
+    }
+
+    /// Clone `url` into `path`
+    pub fn clone(&self, url: &str, path: &Path) -> Result<Repository> {
+        let mut fetch_options = FetchOptions::new();
+        fetch_options.remote_callbacks(self.callbacks());
+        Ok(RepoBuilder::new().fetch_options(fetch_options).clone(url, path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_choice() {
+        let anonymous = GitCredentials::default();
+        assert!(anonymous.credential(None, CredentialType::USER_PASS_PLAINTEXT).is_err());
+
+        let token = GitCredentials { token: Some("secret".to_string()), token_user: "oauth2".to_string(), ssh_key: None };
+        let credential = token.credential(None, CredentialType::USER_PASS_PLAINTEXT).unwrap();
+        assert!(credential.has_username());
+
+        let origin = tempfile::tempdir().unwrap();
+        Repository::init(origin.path()).unwrap();
+        let clone = tempfile::tempdir().unwrap();
+        anonymous.clone(&origin.path().to_string_lossy(), &clone.path().join("shop")).unwrap();
+        assert!(clone.path().join("shop/.git").is_dir());
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::{anyhow, Result};
+use git2::{Delta, DiffHunk, DiffOptions, Patch, Repository};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use super::{definition_line, TestablePattern, UftError};
+
+/// A line that opens a function, method, class or other block analysis finds patterns in,
+/// in any of the supported languages
+static DEFINITION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(concat!(
+    r"^\s*(?:(?:pub(?:\([^)]*\))?|export|default|async|public|private|protected|static|final|abstract|override|unsafe)\s+)*",
+    r"(?:(?:def|fn|function|func|class|struct|impl|trait|interface|enum)\b",
+    r"|(?:const|let|var)\s+\w+\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*=>|\w+\s*=>)",
+    r"|(?:[\w<>\[\],]+\s+)?\w+\s*\([^)]*\)\s*(?:throws\s+[\w., ]+)?\{\s*$)",
+)).unwrap());
+
+/// Statements the method alternative of [`DEFINITION_REGEX`] would otherwise take for one
+const BLOCK_KEYWORDS: &[&str] = &["if", "for", "while", "switch", "catch", "else", "return", "do", "try"];
+
+/// A file changed since the base ref, with the lines it now has that the change touched
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedFile {
+    /// Relative to the repository root
+    pub path: PathBuf,
+    /// 1-based, inclusive; a deletion marks the line after it
+    pub lines: Vec<RangeInclusive<usize>>,
+}
+
+impl ChangedFile {
+    /// The lines `new` changed from `old`, two versions of the file at `path`
+    pub fn between(path: PathBuf, old: &str, new: &str) -> Result<Self> {
+        let mut options = DiffOptions::new();
+        options.context_lines(0);
+        let patch = Patch::from_buffers(old.as_bytes(), None, new.as_bytes(), None, Some(&mut options))?;
+        let lines = (0..patch.num_hunks())
+            .map(|index| patch.hunk(index).map(|(hunk, _)| hunk_lines(&hunk)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { path, lines })
+    }
+
+    /// The change with the lines from `line` on left out
+    pub fn before_line(&self, line: usize) -> Self {
+        let lines = self.lines.iter()
+            .filter(|changed| *changed.start() < line)
+            .map(|changed| *changed.start()..=(*changed.end()).min(line - 1))
+            .collect();
+        Self { path: self.path.clone(), lines }
+    }
+
+    /// The lines analysis of `source` has to cover for the change: each changed range widened
+    /// to the definitions around it, plus the opening lines of the ones those sit in, so a
+    /// method still reads as part of its class
+    pub fn analysis_lines(&self, source: &str) -> Vec<RangeInclusive<usize>> {
+        let lines: Vec<&str> = source.lines().collect();
+        if lines.is_empty() {
+            return Vec::new();
+        }
+        let mut regions: Vec<RangeInclusive<usize>> = Vec::new();
+        for changed in &self.lines {
+            let first = (*changed.start()).clamp(1, lines.len()) - 1;
+            let last = (*changed.end()).clamp(1, lines.len()) - 1;
+            let (mut start, mut end) = (first, last);
+            for index in [first, last] {
+                let spans = enclosing_spans(&lines, index);
+                if let Some((_, innermost)) = spans.first() {
+                    start = start.min(*innermost.start());
+                    end = end.max(*innermost.end());
+                }
+                // Outer definitions only contribute the lines that name them
+                regions.extend(spans.iter().skip(1).map(|(definition, span)| *span.start() + 1..=definition + 1));
+            }
+            regions.push(start + 1..=end + 1);
+        }
+
+        regions.sort_by_key(|region| *region.start());
+        let mut merged: Vec<RangeInclusive<usize>> = Vec::new();
+        for region in regions {
+            match merged.last_mut() {
+                Some(last) if *region.start() <= *last.end() + 1 => *last = *last.start()..=(*last.end()).max(*region.end()),
+                _ => merged.push(region),
+            }
+        }
+        merged
+    }
+
+    /// `source` with every line outside [`analysis_lines`](Self::analysis_lines) blanked to
+    /// spaces, so the patterns found in it keep their line numbers and byte offsets
+    pub fn changed_source(&self, source: &str) -> String {
+        let regions = self.analysis_lines(source);
+        source.split_inclusive('\n').enumerate()
+            .map(|(index, line)| {
+                if regions.iter().any(|region| region.contains(&(index + 1))) {
+                    return line.to_string();
+                }
+                let content = line.trim_end_matches(['\r', '\n']);
+                format!("{}{}", " ".repeat(content.len()), &line[content.len()..])
+            })
+            .collect()
+    }
+
+    /// The patterns of `source` whose code the change touched. A pattern is taken to run from
+    /// its definition to the line before the next one, which is as near as analysis gets to a
+    /// function's extent
+    pub fn touched_patterns<'a>(&self, patterns: &'a [TestablePattern], source: &str) -> Vec<&'a TestablePattern> {
+        // Not every adapter records where a pattern is, so look for its definition first
+        let lines: Vec<usize> = patterns.iter()
+            .map(|pattern| {
+                let name = pattern.subject_name();
+                definition_line(source, name.rsplit(['.', ':']).next().unwrap_or(name)).unwrap_or(pattern.location.line)
+            })
+            .collect();
+        let mut starts = lines.clone();
+        starts.sort_unstable();
+        starts.dedup();
+
+        patterns.iter().zip(lines)
+            .filter(|(_, start)| {
+                let start = *start;
+                let end = starts.iter().find(|&&line| line > start).map_or(usize::MAX, |next| next - 1);
+                self.lines.iter().any(|changed| *changed.start() <= end && *changed.end() >= start)
+            })
+            .map(|(pattern, _)| pattern)
+            .collect()
+    }
+}
+
+/// What changed in a repository's working tree since it forked from a base ref, the way
+/// `git diff <base>...` plus uncommitted and untracked files would show it
+#[derive(Debug, Clone)]
+pub struct GitChanges {
+    /// The repository's working directory
+    pub root: PathBuf,
+    /// Added and modified files; deleted ones have nothing left to test
+    pub files: Vec<ChangedFile>,
+}
+
+impl GitChanges {
+    /// Changes in the repository containing `path` since its merge base with `base`
+    pub fn since(path: &Path, base: &str) -> Result<Self> {
+        let repo = Repository::discover(path)
+            .map_err(|e| UftError::Config(format!("Not in a git repository: {}: {}", path.display(), e.message())))?;
+        let root = repo.workdir()
+            .ok_or_else(|| anyhow!("Repository at {} has no working directory", repo.path().display()))?
+            .to_path_buf();
+
+        let base_commit = repo.revparse_single(base)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|e| UftError::Config(format!("Unknown base ref {}: {}", base, e.message())))?;
+        let fork_point = match repo.head().ok().and_then(|head| head.peel_to_commit().ok()) {
+            Some(head) => repo.find_commit(repo.merge_base(base_commit.id(), head.id())?)?,
+            None => base_commit,
+        };
+
+        let mut options = DiffOptions::new();
+        options.context_lines(0)
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let diff = repo.diff_tree_to_workdir_with_index(Some(&fork_point.tree()?), Some(&mut options))?;
+
+        let mut files: BTreeMap<PathBuf, Vec<RangeInclusive<usize>>> = BTreeMap::new();
+        diff.foreach(
+            &mut |_, _| true,
+            None,
+            Some(&mut |delta, hunk| {
+                if delta.status() == Delta::Deleted {
+                    return true;
+                }
+                if let Some(path) = delta.new_file().path() {
+                    files.entry(path.to_path_buf()).or_default().push(hunk_lines(&hunk));
+                }
+                true
+            }),
+            None,
+        )?;
+
+        let files = files.into_iter()
+            .filter(|(path, _)| root.join(path).is_file())
+            .map(|(path, lines)| ChangedFile { path, lines })
+            .collect();
+        Ok(Self { root, files })
+    }
+}
+
+/// The 1-based lines a hunk covers in the new file; a deletion marks the line after it
+fn hunk_lines(hunk: &DiffHunk) -> RangeInclusive<usize> {
+    let start = (hunk.new_start() as usize).max(1);
+    start..=start + (hunk.new_lines() as usize).max(1) - 1
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_definition(line: &str) -> bool {
+    let keyword = line.trim_start().split(|c: char| !c.is_alphanumeric() && c != '_').next().unwrap_or_default();
+    DEFINITION_REGEX.is_match(line) && !BLOCK_KEYWORDS.contains(&keyword)
+}
+
+/// The 0-based index of the last line of the definition opened at `start`: the end of its
+/// indented body, and the bracket that closes it if one does
+fn definition_end(lines: &[&str], start: usize) -> usize {
+    let indent = indentation(lines[start]);
+    let mut end = start;
+    for (index, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || indentation(line) > indent {
+            if !trimmed.is_empty() {
+                end = index;
+            }
+            continue;
+        }
+        if trimmed.starts_with(['}', ')', ']']) {
+            end = index;
+            // `) -> Result<()> {` closes a signature and opens the body
+            if trimmed.ends_with(['{', ':']) {
+                continue;
+            }
+        }
+        break;
+    }
+    end
+}
+
+/// The definitions `index` sits in, innermost first, as 0-based indexes of the line opening
+/// each and of its lines from its decorators or annotations to its end
+fn enclosing_spans(lines: &[&str], index: usize) -> Vec<(usize, RangeInclusive<usize>)> {
+    let mut spans = Vec::new();
+    let mut indent = usize::MAX;
+    for start in (0..=index).rev() {
+        if !is_definition(lines[start]) || indentation(lines[start]) >= indent {
+            continue;
+        }
+        let end = definition_end(lines, start);
+        if end < index {
+            continue;
+        }
+        let mut first = start;
+        while first > 0 && lines[first - 1].trim_start().starts_with('@') {
+            first -= 1;
+        }
+        indent = indentation(lines[start]);
+        spans.push((start, first..=end));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Context, FunctionPattern, PatternType, SourceLocation};
+    use std::fs;
+
+    fn function(name: &str, line: usize) -> TestablePattern {
+        TestablePattern {
+            id: name.to_string(),
+            pattern_type: PatternType::Function(FunctionPattern { name: name.to_string(), parameters: vec![], return_type: None }),
+            location: SourceLocation { file: "calc.py".to_string(), line, column: 1 },
+            context: Context { function_name: Some(name.to_string()), class_name: None, module_name: None },
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_touched_patterns() {
+        let source = "def add(a, b):\n    return a + b\n\ndef sub(a, b):\n    return a - b\n\ndef mul(a, b):\n    return a * b\n";
+        let patterns = [function("add", 1), function("sub", 1), function("mul", 1)];
+        let changed = ChangedFile { path: PathBuf::from("calc.py"), lines: vec![5..=5, 9..=10] };
+        let touched: Vec<_> = changed.touched_patterns(&patterns, source).iter().map(|pattern| pattern.id.as_str()).collect();
+        assert_eq!(touched, ["sub", "mul"]);
+    }
+
+    #[test]
+    fn test_analysis_lines_widen_to_enclosing_definitions() {
+        let source = "import os\n\nclass Cart:\n    def __init__(self):\n        self.items = []\n\n    @property\n    def total(self):\n        count = len(self.items)\n        return count\n\ndef helper():\n    return 1\n";
+        let changed = ChangedFile { path: PathBuf::from("cart.py"), lines: vec![9..=9] };
+        assert_eq!(changed.analysis_lines(source), vec![3..=3, 7..=10]);
+
+        let source = "impl Cart {\n    pub fn total(\n        &self,\n    ) -> u32 {\n        if self.empty() {\n            return 0;\n        }\n        self.sum()\n    }\n}\n";
+        let changed = ChangedFile { path: PathBuf::from("cart.rs"), lines: vec![6..=6] };
+        assert_eq!(changed.analysis_lines(source), vec![1..=9]);
+
+        let changed = ChangedFile::between(PathBuf::from("cart.rs"), source, &source.replace("return 0", "return 1")).unwrap();
+        assert_eq!(changed.lines, vec![6..=6]);
+        assert_eq!(ChangedFile::between(PathBuf::from("cart.rs"), source, source).unwrap().lines, Vec::<RangeInclusive<usize>>::new());
+    }
+
+    #[test]
+    fn test_changes_since_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("calc.py"), "def add(a, b):\n    return a + b\n\ndef sub(a, b):\n    return a - b\n").unwrap();
+        fs::write(dir.path().join("old.py"), "x = 1\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"], git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[]).unwrap();
+
+        fs::write(dir.path().join("calc.py"), "def add(a, b):\n    return a + b\n\ndef sub(a, b):\n    return b - a\n").unwrap();
+        fs::write(dir.path().join("new.py"), "def mul(a, b):\n    return a * b\n").unwrap();
+        fs::remove_file(dir.path().join("old.py")).unwrap();
+
+        let changes = GitChanges::since(dir.path(), "HEAD").unwrap();
+        assert_eq!(changes.files, vec![
+            ChangedFile { path: PathBuf::from("calc.py"), lines: vec![5..=5] },
+            ChangedFile { path: PathBuf::from("new.py"), lines: vec![1..=2] },
+        ]);
+        assert_eq!(UftError::exit_code_of(&GitChanges::since(dir.path(), "no-such-ref").unwrap_err()), 3);
+    }
+}
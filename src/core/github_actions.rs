@@ -0,0 +1,104 @@
+use anyhow::Result;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::{definition_line, FileReport};
+
+/// Variable naming the file a step appends its job summary Markdown to
+pub const STEP_SUMMARY_ENV: &str = "GITHUB_STEP_SUMMARY";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnnotationLevel {
+    Notice,
+    Warning,
+}
+
+/// A message GitHub Actions shows on the workflow run and, with a line, on the PR diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    pub file: String,
+    pub line: Option<usize>,
+    pub title: String,
+    pub message: String,
+}
+
+impl Annotation {
+    /// The `::warning file=…,line=…,title=…::message` workflow command
+    pub fn to_workflow_command(&self) -> String {
+        let level = match self.level {
+            AnnotationLevel::Notice => "notice",
+            AnnotationLevel::Warning => "warning",
+        };
+        let mut properties = vec![format!("file={}", escape_property(&self.file))];
+        if let Some(line) = self.line {
+            properties.push(format!("line={}", line));
+        }
+        properties.push(format!("title={}", escape_property(&self.title)));
+        format!("::{} {}::{}", level, properties.join(","), escape_data(&self.message))
+    }
+}
+
+/// One annotation per untested public function of `file`, whose source is at `path`: a
+/// warning where its existing tests miss the function, a notice where it has no tests yet
+pub fn untested_annotations(file: &FileReport, path: &str, source: &str) -> Vec<Annotation> {
+    let level = if file.has_tests { AnnotationLevel::Warning } else { AnnotationLevel::Notice };
+    file.untested.iter()
+        .map(|name| Annotation {
+            level,
+            file: path.to_string(),
+            line: definition_line(source, name.rsplit(['.', ':']).next().unwrap_or(name)),
+            title: "Untested function".to_string(),
+            message: format!("{} has no tests; `uft generate {}` can write some", name, path),
+        })
+        .collect()
+}
+
+/// Append Markdown to the job summary; `None` outside GitHub Actions
+pub fn write_step_summary(markdown: &str) -> Result<Option<PathBuf>> {
+    let Some(path) = std::env::var_os(STEP_SUMMARY_ENV).map(PathBuf::from) else {
+        return Ok(None);
+    };
+    let mut summary = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(summary, "{}", markdown)?;
+    Ok(Some(path))
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConfidenceBands;
+
+    #[test]
+    fn test_untested_annotations() {
+        let mut file = FileReport {
+            source_file: "calc.py".to_string(),
+            language: "python".to_string(),
+            patterns: 2,
+            tests_generated: 6,
+            confidence: ConfidenceBands::default(),
+            untested: vec!["sub".to_string()],
+            has_tests: true,
+        };
+        let source = "def add(a, b):\n    return a + b\n\ndef sub(a, b):\n    return a - b\n";
+
+        let annotations = untested_annotations(&file, "src/calc.py", source);
+        assert_eq!(
+            annotations[0].to_workflow_command(),
+            "::warning file=src/calc.py,line=4,title=Untested function::sub has no tests; `uft generate src/calc.py` can write some"
+        );
+
+        file.has_tests = false;
+        assert_eq!(untested_annotations(&file, "src/calc.py", source)[0].level, AnnotationLevel::Notice);
+        assert_eq!(escape_property("a,b: 100%\n"), "a%2Cb%3A 100%25%0A");
+    }
+}
@@ -0,0 +1,176 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::{braced_block, GraphQlOperationType, GraphQlPattern};
+
+/// Named operations from `gql`/`graphql` tagged templates, or from the source itself when it
+/// is a GraphQL document, followed by the fields of `Query`/`Mutation`/`Subscription` resolver maps
+pub fn graphql_operations(source: &str) -> Vec<GraphQlPattern> {
+    let tagged_regex = Regex::new(r"\b(?:gql|graphql)\s*`([^`]*)`").unwrap();
+    let operation_regex = Regex::new(r"\b(query|mutation|subscription)\s+(\w+)\s*(?:\(([^)]*)\))?\s*\{").unwrap();
+    let variable_regex = Regex::new(r"\$(\w+)\s*:\s*([\w!\[\]]+)").unwrap();
+
+    let mut documents: Vec<String> = tagged_regex.captures_iter(source).map(|cap| cap[1].to_string()).collect();
+    if documents.is_empty() && is_graphql_document(source) {
+        documents.push(source.to_string());
+    }
+    let interpolation_regex = Regex::new(r"\$\{[^}]*\}").unwrap();
+    let documents: Vec<String> = documents.iter().map(|d| interpolation_regex.replace_all(d, "").into_owned()).collect();
+    let fragments = fragment_definitions(&documents);
+
+    let mut operations = Vec::new();
+    for document in &documents {
+        for cap in operation_regex.captures_iter(document) {
+            let start = cap.get(0).unwrap().start();
+            let Some(body) = braced_block(document, start) else { continue };
+            let end = start + document[start..].find('{').unwrap_or(0) + body.len() + 2;
+            let mut text = document[start..end].to_string();
+            with_spread_fragments(&mut text, &fragments);
+
+            operations.push(GraphQlPattern {
+                operation_type: operation_type(&cap[1]),
+                name: cap[2].to_string(),
+                document: Some(text),
+                variables: cap.get(3)
+                    .map(|vars| variable_regex.captures_iter(vars.as_str()).map(|v| format!("{}: {}", &v[1], &v[2])).collect())
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    operations.extend(resolver_fields(source));
+    operations
+}
+
+/// Whether the source is GraphQL rather than code, judged by its first definition
+pub fn is_graphql_document(source: &str) -> bool {
+    let definition_regex = Regex::new(
+        r"^(?:query|mutation|subscription|fragment|type|input|enum|interface|union|scalar|schema|extend|directive)\b[^=]*$|^\{"
+    ).unwrap();
+
+    source.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .is_some_and(|line| definition_regex.is_match(line))
+}
+
+/// Fields defined in `Query: { ... }`-style resolver maps
+fn resolver_fields(source: &str) -> Vec<GraphQlPattern> {
+    let type_regex = Regex::new(r"\b(Query|Mutation|Subscription)\s*:\s*\{").unwrap();
+    let key_regex = Regex::new(r"(?:^|,)\s*(?:async\s+)?(\w+)\s*[:(]").unwrap();
+
+    type_regex.captures_iter(source).flat_map(|cap| {
+        let body = braced_block(source, cap.get(0).unwrap().start()).unwrap_or_default();
+        key_regex.captures_iter(&top_level(body))
+            .map(|key| GraphQlPattern {
+                operation_type: operation_type(&cap[1].to_lowercase()),
+                name: key[1].to_string(),
+                document: None,
+                variables: vec![],
+            })
+            .collect::<Vec<_>>()
+    }).collect()
+}
+
+/// `fragment Name on Type { ... }` definitions across documents, by name
+fn fragment_definitions(documents: &[String]) -> HashMap<String, String> {
+    let fragment_regex = Regex::new(r"\bfragment\s+(\w+)\s+on\s+\w+\s*\{").unwrap();
+    let mut fragments = HashMap::new();
+    for document in documents {
+        for cap in fragment_regex.captures_iter(document) {
+            let start = cap.get(0).unwrap().start();
+            if let Some(body) = braced_block(document, start) {
+                let end = start + document[start..].find('{').unwrap_or(0) + body.len() + 2;
+                fragments.insert(cap[1].to_string(), document[start..end].to_string());
+            }
+        }
+    }
+    fragments
+}
+
+/// Append the definitions of fragments the document spreads, including nested spreads
+fn with_spread_fragments(document: &mut String, fragments: &HashMap<String, String>) {
+    let spread_regex = Regex::new(r"\.\.\.\s*(\w+)").unwrap();
+    let mut included: Vec<String> = Vec::new();
+    loop {
+        let missing: Vec<String> = spread_regex.captures_iter(document)
+            .map(|cap| cap[1].to_string())
+            .filter(|name| name != "on" && fragments.contains_key(name) && !included.contains(name))
+            .collect();
+        if missing.is_empty() {
+            break;
+        }
+        for name in missing {
+            if !included.contains(&name) {
+                document.push_str("\n\n");
+                document.push_str(&fragments[&name]);
+                included.push(name);
+            }
+        }
+    }
+}
+
+/// Text outside any nested braces, brackets or parentheses, keeping the outermost openers
+fn top_level(block: &str) -> String {
+    let mut depth = 0;
+    block.chars().filter(|c| {
+        let outside = depth == 0;
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            _ => {}
+        }
+        outside
+    }).collect()
+}
+
+fn operation_type(keyword: &str) -> GraphQlOperationType {
+    match keyword {
+        "mutation" => GraphQlOperationType::Mutation,
+        "subscription" => GraphQlOperationType::Subscription,
+        _ => GraphQlOperationType::Query,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations_from_tagged_templates() {
+        let source = "const USER_FIELDS = gql`\n  fragment UserFields on User { id name }\n`;\n\nexport const GET_USER = gql`\n  query GetUser($id: ID!, $full: Boolean) {\n    user(id: $id) { ...UserFields }\n  }\n  ${USER_FIELDS}\n`;\n";
+        let operations = graphql_operations(source);
+
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].name, "GetUser");
+        assert_eq!(operations[0].variables, vec!["id: ID!", "full: Boolean"]);
+        let document = operations[0].document.as_deref().unwrap();
+        assert!(document.starts_with("query GetUser($id: ID!, $full: Boolean) {"));
+        assert!(document.ends_with("fragment UserFields on User { id name }"));
+    }
+
+    #[test]
+    fn test_operations_from_graphql_document() {
+        let source = "mutation AddUser($name: String!) {\n  addUser(name: $name) { id }\n}\n\nquery Users {\n  users { id }\n}\n";
+        let operations = graphql_operations(source);
+
+        let names: Vec<(&str, GraphQlOperationType)> = operations.iter().map(|o| (o.name.as_str(), o.operation_type)).collect();
+        assert_eq!(names, vec![("AddUser", GraphQlOperationType::Mutation), ("Users", GraphQlOperationType::Query)]);
+        assert_eq!(operations[1].document.as_deref(), Some("query Users {\n  users { id }\n}"));
+        assert!(!is_graphql_document("type Props = { id: string };\nexport function f() {}\n"));
+    }
+
+    #[test]
+    fn test_resolver_fields() {
+        let source = "const resolvers = {\n  Query: {\n    users: () => db.all({ limit: 10 }),\n    async user(_, { id }) { return db.get(id); },\n  },\n  Mutation: {\n    addUser: async (_, args) => db.add(args),\n  },\n};\n";
+        let operations = graphql_operations(source);
+
+        let fields: Vec<(&str, GraphQlOperationType)> = operations.iter().map(|o| (o.name.as_str(), o.operation_type)).collect();
+        assert_eq!(fields, vec![
+            ("users", GraphQlOperationType::Query),
+            ("user", GraphQlOperationType::Query),
+            ("addUser", GraphQlOperationType::Mutation),
+        ]);
+        assert!(operations.iter().all(|o| o.document.is_none()));
+    }
+}
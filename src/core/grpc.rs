@@ -0,0 +1,87 @@
+use regex::Regex;
+
+use super::GrpcPattern;
+
+/// Whether the source is a protobuf definition rather than code
+pub fn is_proto_file(source: &str) -> bool {
+    Regex::new(r#"(?m)^\s*syntax\s*=\s*"proto[23]"\s*;"#).unwrap().is_match(source)
+        || Regex::new(r"(?m)^\s*service\s+\w+\s*\{[^}]*\brpc\s").unwrap().is_match(source)
+}
+
+/// RPCs declared by the services in a `.proto`, with the offset of each `rpc` keyword
+pub fn proto_services(source: &str, proto_file: &str) -> Vec<(usize, GrpcPattern)> {
+    let source = without_comments(source);
+    let package_regex = Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;").unwrap();
+    let service_regex = Regex::new(r"\bservice\s+(\w+)\s*\{").unwrap();
+    let rpc_regex = Regex::new(
+        r"\brpc\s+(\w+)\s*\(\s*(stream\s+)?\.?([\w.]+)\s*\)\s*returns\s*\(\s*(stream\s+)?\.?([\w.]+)\s*\)"
+    ).unwrap();
+
+    let package = package_regex.captures(&source).map(|cap| cap[1].to_string());
+    let mut rpcs = Vec::new();
+    for service in service_regex.captures_iter(&source) {
+        let start = service.get(0).unwrap().end();
+        let Some(body) = super::braced_block(&source, start - 1) else { continue };
+        for rpc in rpc_regex.captures_iter(body) {
+            rpcs.push((start + rpc.get(0).unwrap().start(), GrpcPattern {
+                package: package.clone(),
+                service: service[1].to_string(),
+                method: rpc[1].to_string(),
+                request_type: Some(rpc[3].to_string()),
+                response_type: Some(rpc[5].to_string()),
+                client_streaming: rpc.get(2).is_some(),
+                server_streaming: rpc.get(4).is_some(),
+                proto_file: Some(proto_file.to_string()),
+            }));
+        }
+    }
+    rpcs
+}
+
+/// RPC name for a generated client method: `say_hello` and `sayHello` become `SayHello`
+pub fn rpc_name(method: &str) -> String {
+    method.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Comments blanked out with spaces so offsets still point into the original source
+fn without_comments(source: &str) -> String {
+    Regex::new(r"(?s)//[^\n]*|/\*.*?\*/").unwrap()
+        .replace_all(source, |cap: &regex::Captures| cap[0].chars().map(|c| if c == '\n' { '\n' } else { ' ' }).collect::<String>())
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proto_services() {
+        let source = "syntax = \"proto3\";\npackage routeguide;\n\n// Interface exported by the server.\nservice RouteGuide {\n  rpc GetFeature(Point) returns (Feature) {}\n  // rpc Hidden(Point) returns (Point);\n  rpc ListFeatures(Rectangle) returns (stream Feature) {}\n  rpc RouteChat(stream RouteNote) returns (stream RouteNote);\n}\n\nmessage Point { int32 latitude = 1; }\n";
+        assert!(is_proto_file(source));
+
+        let rpcs = proto_services(source, "protos/route_guide.proto");
+        let names: Vec<&str> = rpcs.iter().map(|(_, rpc)| rpc.method.as_str()).collect();
+        assert_eq!(names, vec!["GetFeature", "ListFeatures", "RouteChat"]);
+        assert!(source[rpcs[0].0..].starts_with("rpc GetFeature"));
+
+        let (_, list) = &rpcs[1];
+        assert_eq!(list.package.as_deref(), Some("routeguide"));
+        assert_eq!(list.request_type.as_deref(), Some("Rectangle"));
+        assert!(list.server_streaming && !list.client_streaming);
+        assert!(rpcs[2].1.client_streaming && rpcs[2].1.server_streaming);
+        assert!(!is_proto_file("fn main() {}\n"));
+    }
+
+    #[test]
+    fn test_rpc_name() {
+        assert_eq!(rpc_name("say_hello"), "SayHello");
+        assert_eq!(rpc_name("sayHello"), "SayHello");
+        assert_eq!(rpc_name("SayHello"), "SayHello");
+    }
+}
@@ -1,14 +1,17 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::Path;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
 
 use crate::core::dynamic_adapter::{DynamicLanguageAdapter, LanguageConfig};
-use crate::core::{TestGenerator, TestCase};
+use crate::core::{validate_language_config_file, LanguageScript, ProcessLanguageAdapter, TestGenerator, TestCase, UftError, WasmLanguageAdapter};
 
 pub struct LanguageLoader {
     config_dir: String,
     loaded_configs: HashMap<String, LanguageConfig>,
+    /// The JSON file each dynamic language was loaded from
+    config_files: HashMap<String, PathBuf>,
 }
 
 impl LanguageLoader {
@@ -16,6 +19,7 @@ impl LanguageLoader {
         Self {
             config_dir,
             loaded_configs: HashMap::new(),
+            config_files: HashMap::new(),
         }
     }
 
@@ -40,7 +44,9 @@ impl LanguageLoader {
         adapters.insert("java".to_string(), Box::new(crate::adapters::JavaAdapter::new()));
     }
 
-    fn load_dynamic_adapters(&mut self, adapters: &mut HashMap<String, Box<dyn TestGenerator + Send + Sync>>) -> Result<()> {
+    /// The directory language configs are read from: the first of the config dir and the
+    /// default locations that exists
+    pub fn resolved_config_dir(&self) -> Option<PathBuf> {
         let config_paths = vec![
             self.config_dir.clone(),
             "./language_configs".to_string(),
@@ -48,7 +54,6 @@ impl LanguageLoader {
             "/usr/local/share/uft/language_configs".to_string(),
         ];
         
-        let mut config_dir_found = None;
         for path in config_paths {
             let expanded_path = if path.starts_with('~') {
                 path.replace("~", &std::env::var("HOME").unwrap_or_default())
@@ -57,12 +62,14 @@ impl LanguageLoader {
             };
             
             if Path::new(&expanded_path).exists() {
-                config_dir_found = Some(expanded_path);
-                break;
+                return Some(PathBuf::from(expanded_path));
             }
         }
-        
-        let config_dir = match config_dir_found {
+        None
+    }
+
+    fn load_dynamic_adapters(&mut self, adapters: &mut HashMap<String, Box<dyn TestGenerator + Send + Sync>>) -> Result<()> {
+        let config_dir = match self.resolved_config_dir() {
             Some(dir) => dir,
             None => return Ok(()), // No config directory found, skip dynamic loading
         };
@@ -74,13 +81,20 @@ impl LanguageLoader {
             let path = entry.path();
             
             if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
+                let base = fs::read_to_string(&path).ok()
+                    .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                    .is_some_and(|config| is_base_config(&config));
+                if base {
+                    tracing::debug!("Skipping base language config: {}", path.display());
+                    continue;
+                }
                 match self.load_language_config(&path) {
                     Ok((language_name, adapter)) => {
-                        println!("Loaded dynamic language adapter: {}", language_name);
+                        tracing::debug!("Loaded dynamic language adapter: {}", language_name);
                         adapters.insert(language_name, adapter);
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to load language config from {:?}: {}", path, e);
+                        tracing::warn!("Failed to load language config: {} (`uft validate-config` checks them all)", e);
                     }
                 }
             }
@@ -90,18 +104,35 @@ impl LanguageLoader {
     }
 
     fn load_language_config(&mut self, path: &Path) -> Result<(String, Box<dyn TestGenerator + Send + Sync>)> {
-        let content = fs::read_to_string(path)?;
-        let config: LanguageConfig = serde_json::from_str(&content)?;
+        let issues = validate_language_config_file(path);
+        if !issues.is_empty() {
+            let issues: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+            return Err(UftError::Config(format!("{} doesn't match the language config schema: {}", path.display(), issues.join("; "))).into());
+        }
+        let config: LanguageConfig = serde_json::from_value(resolve_extends(path)?)?;
         let language_name = config.name.clone();
         
         // Validate the configuration
         self.validate_config(&config)?;
         
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let adapter: Box<dyn TestGenerator + Send + Sync> = match (&config.wasm, &config.command) {
+            (Some(module), _) => Box::new(WasmLanguageAdapter::load(config.clone(), &directory.join(module))?),
+            (None, Some(command)) => Box::new(ProcessLanguageAdapter::new(config.clone(), command.clone(), directory)),
+            (None, None) => {
+                let adapter = DynamicLanguageAdapter::new(config.clone());
+                match &config.script {
+                    Some(script) => Box::new(adapter.with_script(LanguageScript::load(&config.name, &directory.join(script))?)),
+                    None => Box::new(adapter),
+                }
+            }
+        };
+        
         // Store for future reference
-        self.loaded_configs.insert(language_name.clone(), config.clone());
+        self.loaded_configs.insert(language_name.clone(), config);
+        self.config_files.insert(language_name.clone(), path.to_path_buf());
         
-        let adapter = DynamicLanguageAdapter::new(config);
-        Ok((language_name, Box::new(adapter)))
+        Ok((language_name, adapter))
     }
 
     fn validate_config(&self, config: &LanguageConfig) -> Result<()> {
@@ -113,8 +144,8 @@ impl LanguageLoader {
             return Err(anyhow::anyhow!("Language must have at least one file extension"));
         }
         
-        if config.patterns.is_empty() {
-            return Err(anyhow::anyhow!("Language must have at least one pattern configuration"));
+        if config.patterns.is_empty() && config.wasm.is_none() && config.command.is_none() && config.script.is_none() {
+            return Err(anyhow::anyhow!("Language must have at least one pattern configuration, a Rhai script, a WebAssembly adapter or an adapter command"));
         }
         
         // Validate regex patterns
@@ -202,6 +233,16 @@ impl LanguageLoader {
         ]
     }
     
+    /// The JSON config `language` was loaded from; `None` for built-in languages
+    pub fn config_file(&self, language: &str) -> Option<&Path> {
+        self.config_files.get(language).map(PathBuf::as_path)
+    }
+
+    /// The `test_path` convention `language`'s config sets, if any
+    pub fn test_path(&self, language: &str) -> Option<&str> {
+        self.loaded_configs.get(language)?.test_path.as_deref()
+    }
+
     pub fn list_dynamic_languages(&self) -> Vec<String> {
         self.loaded_configs.values()
             .map(|config| config.name.clone())
@@ -209,6 +250,85 @@ impl LanguageLoader {
     }
 }
 
+/// Whether a language config is a base for others to `extends`, rather than a language: one
+/// without a `name`
+pub fn is_base_config(config: &Value) -> bool {
+    config.is_object() && config.get("name").is_none()
+}
+
+/// The language config at `path`, merged over the base configs it `extends`: objects merge key
+/// by key, and any other value the config sets replaces the base's. A base is named by its file
+/// name without `.json`, in the same directory
+pub fn resolve_extends(path: &Path) -> Result<Value> {
+    resolve_from(path, &mut Vec::new())
+}
+
+fn resolve_from(path: &Path, extending: &mut Vec<PathBuf>) -> Result<Value> {
+    if extending.iter().any(|seen| seen == path) {
+        return Err(UftError::Config(format!("{} extends itself", path.display())).into());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| UftError::Config(format!("Can't read language config {}: {}", path.display(), e)))?;
+    let mut config: Value = serde_json::from_str(&content)
+        .map_err(|e| UftError::Config(format!("Invalid JSON in {}: {}", path.display(), e)))?;
+    let Some(base) = config.as_object_mut().and_then(|config| config.remove("extends")) else {
+        return Ok(config);
+    };
+    let Some(base) = base.as_str() else {
+        return Err(UftError::Config(format!("`extends` in {} should name a base config", path.display())).into());
+    };
+    extending.push(path.to_path_buf());
+    let mut resolved = resolve_from(&path.with_file_name(format!("{}.json", base)), extending)?;
+    merge_config(&mut resolved, config);
+    Ok(resolved)
+}
+
+fn merge_config(base: &mut Value, config: Value) {
+    match (base, config) {
+        (Value::Object(base), Value::Object(config)) => {
+            for (key, value) in config {
+                match base.get_mut(&key) {
+                    Some(inherited) => merge_config(inherited, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, config) => *base = config,
+    }
+}
+
+/// The languages a reload of the language configs added and removed
+#[derive(Debug, Default, PartialEq)]
+pub struct LanguageChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl LanguageChanges {
+    /// The changes from the languages in `before` to those in `after`
+    pub fn between<'a>(before: impl IntoIterator<Item = &'a String>, after: impl IntoIterator<Item = &'a String>) -> Self {
+        let before: BTreeSet<&String> = before.into_iter().collect();
+        let after: BTreeSet<&String> = after.into_iter().collect();
+        Self {
+            added: after.difference(&before).map(|language| language.to_string()).collect(),
+            removed: before.difference(&after).map(|language| language.to_string()).collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for LanguageChanges {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (self.added.is_empty(), self.removed.is_empty()) {
+            (true, true) => f.write_str("no languages added or removed"),
+            (false, true) => write!(f, "added {}", self.added.join(", ")),
+            (true, false) => write!(f, "removed {}", self.removed.join(", ")),
+            (false, false) => write!(f, "added {}; removed {}", self.added.join(", "), self.removed.join(", ")),
+        }
+    }
+}
+
 // Extension to DynamicLanguageAdapter to support test content generation
 impl DynamicLanguageAdapter {
     pub fn generate_test_content_from_cases(&self, test_cases: &[TestCase]) -> String {
@@ -229,7 +349,11 @@ mod tests {
         LanguageConfig {
             name: "kotlin".to_string(),
             extensions: vec!["kt".to_string()],
-            framework: "junit".to_string(),
+            framework: "junit5".to_string(),
+            wasm: None,
+            command: None,
+            script: None,
+            test_path: None,
             patterns: vec![
                 PatternConfig {
                     name: "function".to_string(),
@@ -241,6 +365,8 @@ mod tests {
                         return_type: Some(3),
                         parameter_separator: ",".to_string(),
                         parameter_format: "name_type".to_string(),
+                        method: None,
+                        path: None,
                     },
                     confidence: 0.9,
                 }
@@ -290,6 +416,84 @@ mod tests {
         // Should have built-ins plus the dynamic Kotlin adapter
         assert!(adapters.contains_key("kotlin"));
         assert_eq!(adapters.len(), 6); // 5 built-ins + 1 dynamic
+        assert_eq!(loader.config_file("kotlin"), Some(config_path.as_path()));
+        assert_eq!(loader.config_file("python"), None);
+    }
+
+    #[test]
+    fn test_load_extending_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut base = serde_json::to_value(create_test_config()).unwrap();
+        for field in ["name", "extensions"] {
+            base.as_object_mut().unwrap().remove(field);
+        }
+        fs::write(temp_dir.path().join("jvm-base.json"), base.to_string()).unwrap();
+        fs::write(temp_dir.path().join("scala.json"), r#"{
+  "extends": "jvm-base",
+  "name": "scala",
+  "extensions": ["scala"],
+  "test_template": { "file_extension": "Spec.scala" }
+}"#).unwrap();
+
+        let mut loader = LanguageLoader::new(temp_dir.path().to_string_lossy().to_string());
+        let adapters = loader.load_all_languages().unwrap();
+        assert!(adapters.contains_key("scala"));
+        assert_eq!(loader.list_dynamic_languages(), vec!["scala"]);
+        assert_eq!(loader.get_test_file_extension("scala"), "Spec.scala");
+        let scala = &loader.loaded_configs["scala"];
+        assert_eq!(scala.patterns.len(), 1);
+        assert_eq!(scala.test_template.test_function, create_test_config().test_template.test_function);
+        let results = crate::core::validate_config_dir(temp_dir.path()).unwrap();
+        assert!(results.iter().all(|(_, issues)| issues.is_empty()), "{:?}", results);
+
+        base["extends"] = serde_json::json!("scala");
+        fs::write(temp_dir.path().join("jvm-base.json"), base.to_string()).unwrap();
+        let error = resolve_extends(&temp_dir.path().join("scala.json")).unwrap_err();
+        assert_eq!(UftError::exit_code_of(&error), 3);
+        assert!(error.to_string().ends_with("scala.json extends itself"));
+    }
+
+    #[tokio::test]
+    async fn test_load_scripted_adapter() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.patterns.clear();
+        config.script = Some("kotlin.rhai".to_string());
+        config.test_template.test_function = "@Test\nfun {{TEST_NAME}}() {\n{{TEST_BODY}}\n}".to_string();
+        fs::write(temp_dir.path().join("kotlin.json"), serde_json::to_string(&config).unwrap()).unwrap();
+        fs::write(temp_dir.path().join("kotlin.rhai"), r#"
+fn analyze(source, file_path) {
+    find_all(source, `fun (\w+)\(\)`).map(|found| #{ name: found.captures[1], line: found.line })
+}
+fn test_body(test) { `    assertNotNull(${test.function}())` }
+"#).unwrap();
+
+        let mut loader = LanguageLoader::new(temp_dir.path().to_string_lossy().to_string());
+        let (_, adapter) = loader.load_language_config(&temp_dir.path().join("kotlin.json")).unwrap();
+        let patterns = adapter.analyze_code("fun answer() = 42", "Answer.kt").await.unwrap();
+        let suite = adapter.generate_tests(patterns).await.unwrap();
+        assert!(suite.test_code.unwrap().contains("fun testanswer() {\n    assertNotNull(answer())\n}"));
+
+        fs::write(temp_dir.path().join("kotlin.rhai"), "fn analyze(").unwrap();
+        let error = loader.load_language_config(&temp_dir.path().join("kotlin.json")).err().unwrap();
+        assert_eq!(UftError::exit_code_of(&error), 3);
+    }
+
+    #[test]
+    fn test_load_rejects_config_against_schema() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("kotlin.json");
+        let mut config = serde_json::to_value(create_test_config()).unwrap();
+        config["patterns"][0]["confidence"] = serde_json::json!("high");
+        fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+        let mut loader = LanguageLoader::new(temp_dir.path().to_string_lossy().to_string());
+        let error = loader.load_language_config(&config_path).err().unwrap();
+        assert_eq!(UftError::exit_code_of(&error), 3);
+        let message = error.to_string();
+        assert!(message.contains(&config_path.display().to_string()));
+        assert!(message.contains("at /patterns/0/confidence: `patterns[0].confidence` should be number, not string"), "{}", message);
+        assert!(!loader.load_all_languages().unwrap().contains_key("kotlin"));
     }
 
     #[test]
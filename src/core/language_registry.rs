@@ -0,0 +1,249 @@
+//! Community language packs: a language config, and optionally the WebAssembly adapter it
+//! names, published in a registry index and installed with `uft lang add`.
+//!
+//! The index is JSON listing each language's releases, with file URLs relative to the index:
+//!
+//! ```json
+//! { "languages": { "kotlin": [{
+//!     "version": "1.2.0",
+//!     "config": { "url": "kotlin/1.2.0/kotlin.json", "sha256": "..." },
+//!     "wasm": { "url": "kotlin/1.2.0/kotlin.wasm", "sha256": "..." }
+//! }] } }
+//! ```
+
+use anyhow::{anyhow, Result};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::{validate_language_config, UftError};
+
+/// Index `uft lang add` reads unless `--registry` or `UFT_REGISTRY` names another
+pub const DEFAULT_REGISTRY: &str = "https://raw.githubusercontent.com/samirparhi-dev/uft-languages/main/index.json";
+
+/// Where installed packs are recorded, beside their configs. Not `.json`, so the loader
+/// doesn't take it for a language
+pub const PACKS_LOCK_FILE: &str = "language-packs.lock";
+
+/// A registry's index of language packs
+#[derive(Debug, Deserialize)]
+pub struct RegistryIndex {
+    pub languages: BTreeMap<String, Vec<PackRelease>>,
+}
+
+/// One published version of a language pack
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackRelease {
+    pub version: String,
+    pub config: PackFile,
+    #[serde(default)]
+    pub wasm: Option<PackFile>,
+}
+
+/// A file in a pack, with the SHA-256 its download must have
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackFile {
+    pub url: String,
+    pub sha256: String,
+}
+
+/// What `uft lang add` installed, as recorded in [`PACKS_LOCK_FILE`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledPack {
+    pub version: String,
+    pub registry: String,
+    pub config_sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_sha256: Option<String>,
+}
+
+impl RegistryIndex {
+    /// `language`'s release at `version`, or its latest
+    pub fn release(&self, language: &str, version: Option<&str>) -> Result<&PackRelease> {
+        let releases = self.languages.get(language)
+            .filter(|releases| !releases.is_empty())
+            .ok_or_else(|| UftError::UnsupportedLanguage(format!("The registry has no {} language pack", language)))?;
+        match version {
+            Some(version) => releases.iter().find(|release| release.version == version.trim_start_matches('v'))
+                .ok_or_else(|| {
+                    let versions: Vec<&str> = releases.iter().map(|release| release.version.as_str()).collect();
+                    UftError::Config(format!("The registry has no {} {}: it has {}", language, version, versions.join(", "))).into()
+                }),
+            None => Ok(releases.iter().max_by_key(|release| version_key(&release.version)).expect("releases isn't empty")),
+        }
+    }
+}
+
+/// A version's dotted numbers, for ordering; anything else in a part counts as 0
+fn version_key(version: &str) -> Vec<u64> {
+    version.split(['.', '-', '+']).map(|part| part.parse().unwrap_or(0)).collect()
+}
+
+/// Split `kotlin@1.2.0` into the language and the pinned version
+pub fn parse_pack_spec(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once('@') {
+        Some((language, version)) => (language, Some(version)),
+        None => (spec, None),
+    }
+}
+
+/// Fail unless `bytes` hash to `expected`, a hex SHA-256
+pub fn verify_checksum(bytes: &[u8], expected: &str, what: &str) -> Result<String> {
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(anyhow!("Checksum mismatch for {}: expected {}, got {}", what, expected.trim(), actual));
+    }
+    Ok(actual)
+}
+
+/// The user's language config directory, which the loader falls back to
+pub fn user_language_dir() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".config/uft/language_configs")
+}
+
+/// A language pack registry, read over HTTP(S) or from disk
+pub struct LanguageRegistry {
+    index: Url,
+}
+
+impl LanguageRegistry {
+    /// The registry whose index is at `location`: a URL or a local path
+    pub fn new(location: &str) -> Result<Self> {
+        let index = match Url::parse(location) {
+            Ok(url) if url.scheme().len() > 1 => url,
+            _ => {
+                let path = fs::canonicalize(location)
+                    .map_err(|e| UftError::Config(format!("Can't read registry index {}: {}", location, e)))?;
+                Url::from_file_path(&path).map_err(|_| UftError::Config(format!("Invalid registry path {}", location)))?
+            }
+        };
+        Ok(Self { index })
+    }
+
+    async fn fetch(&self, url: &Url) -> Result<Vec<u8>> {
+        if url.scheme() == "file" {
+            let path = url.to_file_path().map_err(|_| anyhow!("Invalid file URL {}", url))?;
+            return fs::read(&path).map_err(|e| anyhow!("Can't read {}: {}", path.display(), e));
+        }
+        let response = reqwest::Client::new().get(url.clone())
+            .header("User-Agent", "uft")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Downloading {} failed ({})", url, response.status()));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    pub async fn index(&self) -> Result<RegistryIndex> {
+        let bytes = self.fetch(&self.index).await?;
+        serde_json::from_slice(&bytes).map_err(|e| anyhow!("Invalid registry index {}: {}", self.index, e))
+    }
+
+    /// Download `file`, checking its checksum
+    async fn download(&self, file: &PackFile) -> Result<(Vec<u8>, String)> {
+        let url = self.index.join(&file.url).map_err(|e| anyhow!("Invalid URL {} in the registry index: {}", file.url, e))?;
+        let bytes = self.fetch(&url).await?;
+        let checksum = verify_checksum(&bytes, &file.sha256, url.as_str())?;
+        Ok((bytes, checksum))
+    }
+
+    /// Install `language` at `version`, or its latest, into `dir`, recording it in the lock
+    /// file there. Nothing is written unless every file checks out
+    pub async fn install(&self, language: &str, version: Option<&str>, dir: &Path) -> Result<InstalledPack> {
+        let index = self.index().await?;
+        let release = index.release(language, version)?;
+        let (config, config_sha256) = self.download(&release.config).await?;
+        let content = String::from_utf8(config).map_err(|_| anyhow!("The {} config isn't UTF-8", language))?;
+        let issues = validate_language_config(&content);
+        if !issues.is_empty() {
+            let issues: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+            return Err(UftError::Config(format!("The {} {} config doesn't match the language config schema: {}", language, release.version, issues.join("; "))).into());
+        }
+        let parsed: Value = serde_json::from_str(&content)?;
+        let wasm = match (parsed.get("wasm").and_then(Value::as_str), &release.wasm) {
+            (Some(module), Some(file)) => Some((pack_path(module)?, self.download(file).await?)),
+            (Some(module), None) => return Err(anyhow!("The {} {} config needs {}, which the pack doesn't include", language, release.version, module)),
+            (None, _) => None,
+        };
+
+        fs::create_dir_all(dir)?;
+        if let Some((module, (bytes, _))) = &wasm {
+            fs::write(dir.join(module), bytes)?;
+        }
+        fs::write(dir.join(format!("{}.json", language)), &content)?;
+        let installed = InstalledPack {
+            version: release.version.clone(),
+            registry: self.index.to_string(),
+            config_sha256,
+            wasm_sha256: wasm.map(|(_, (_, checksum))| checksum),
+        };
+        let mut lock = read_packs_lock(dir)?;
+        lock.insert(language.to_string(), installed.clone());
+        fs::write(dir.join(PACKS_LOCK_FILE), serde_json::to_string_pretty(&lock)? + "\n")?;
+        Ok(installed)
+    }
+}
+
+/// The packs installed in `dir`, by language
+pub fn read_packs_lock(dir: &Path) -> Result<BTreeMap<String, InstalledPack>> {
+    match fs::read_to_string(dir.join(PACKS_LOCK_FILE)) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| UftError::Config(format!("Invalid {}: {}", dir.join(PACKS_LOCK_FILE).display(), e)).into()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A module path from a pack's config, which has to stay inside the config directory
+fn pack_path(module: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(module);
+    if path.components().any(|component| !matches!(component, Component::Normal(_))) {
+        return Err(anyhow!("The pack's `wasm` path {} leaves the config directory", module));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha256(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    #[tokio::test]
+    async fn test_install_language_pack() {
+        let registry = tempfile::tempdir().unwrap();
+        let config = include_str!("../../language_configs/kotlin.json");
+        fs::create_dir_all(registry.path().join("kotlin")).unwrap();
+        fs::write(registry.path().join("kotlin/kotlin.json"), config).unwrap();
+        let index = serde_json::json!({ "languages": { "kotlin": [
+            { "version": "1.10.0", "config": { "url": "kotlin/kotlin.json", "sha256": sha256(config.as_bytes()) } },
+            { "version": "1.9.0", "config": { "url": "kotlin/kotlin.json", "sha256": "0".repeat(64) } },
+        ] } });
+        fs::write(registry.path().join("index.json"), index.to_string()).unwrap();
+
+        let registry = LanguageRegistry::new(&registry.path().join("index.json").to_string_lossy()).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let installed = registry.install("kotlin", None, dir.path()).await.unwrap();
+        assert_eq!(installed.version, "1.10.0");
+        assert_eq!(fs::read_to_string(dir.path().join("kotlin.json")).unwrap(), config);
+        assert_eq!(read_packs_lock(dir.path()).unwrap()["kotlin"], installed);
+
+        let error = registry.install("kotlin", Some("1.9.0"), dir.path()).await.unwrap_err();
+        assert!(error.to_string().starts_with("Checksum mismatch for file://"), "{}", error);
+        assert_eq!(read_packs_lock(dir.path()).unwrap()["kotlin"].version, "1.10.0");
+        let error = registry.install("kotlin", Some("2.0"), dir.path()).await.unwrap_err();
+        assert_eq!(error.to_string(), "The registry has no kotlin 2.0: it has 1.10.0, 1.9.0");
+        let error = registry.install("cobol", None, dir.path()).await.unwrap_err();
+        assert_eq!(UftError::exit_code_of(&error), 2);
+
+        assert_eq!(parse_pack_spec("kotlin@1.9.0"), ("kotlin", Some("1.9.0")));
+        assert!(pack_path("../kotlin.wasm").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Rhai scripts that customize a regex-based language config without recompiling uft. A config
+//! names the script in `script`, and its `DynamicLanguageAdapter` calls whichever of these the
+//! script defines:
+//! - `analyze(source, file_path)`: functions to test besides the ones `patterns` find, as maps
+//!   of `name`, `parameters`, `return_type` and `line`
+//! - `test_body(test)`: the body of a test, given a map of its `name`, `description`,
+//!   `function`, `parameters` and `return_type`, and for endpoints `method` and `path`, for
+//!   the template's `{{TEST_BODY}}`
+//!
+//! Scripts can call `find_all(text, regex)`, which returns a map of `captures` (the whole match
+//! first, unmatched groups empty), `line` and `column` for each match.
+
+use anyhow::Result;
+use regex::Regex;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::UftError;
+
+/// Operations a script may run per call, so a runaway loop fails instead of hanging
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+/// A function a script's `analyze` found
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptFunction {
+    pub name: String,
+    #[serde(default)]
+    pub parameters: Vec<String>,
+    #[serde(default)]
+    pub return_type: Option<String>,
+    #[serde(default = "first_line")]
+    pub line: usize,
+}
+
+fn first_line() -> usize {
+    1
+}
+
+/// What a script's `test_body` is given
+#[derive(Debug, Serialize)]
+pub struct ScriptTest<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub function: &'a str,
+    pub parameters: &'a [String],
+    pub return_type: Option<&'a str>,
+    /// An endpoint's HTTP method and route, for tests of `api_endpoint` rules
+    pub method: Option<&'a str>,
+    pub path: Option<&'a str>,
+}
+
+/// A compiled Rhai script for one language
+pub struct LanguageScript {
+    language: String,
+    engine: Engine,
+    ast: AST,
+}
+
+impl LanguageScript {
+    /// Compile the script at `path` for `language`
+    pub fn load(language: &str, path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| UftError::Config(format!("Can't read Rhai script {}: {}", path.display(), e)))?;
+        Self::compile(language, &source)
+            .map_err(|e| UftError::Config(format!("Invalid Rhai script {}: {}", path.display(), e)).into())
+    }
+
+    /// Compile a script from its source
+    pub fn compile(language: &str, source: &str) -> Result<Self, rhai::ParseError> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        // Debug builds otherwise allow half the nesting release builds do
+        engine.set_max_expr_depths(64, 32);
+        engine.register_fn("find_all", find_all);
+        let ast = engine.compile(source)?;
+        Ok(Self { language: language.to_string(), engine, ast })
+    }
+
+    fn defines(&self, function: &str, arity: usize) -> bool {
+        self.ast.iter_functions().any(|f| f.name == function && f.params.len() == arity)
+    }
+
+    fn call(&self, function: &str, args: impl rhai::FuncArgs) -> Result<Dynamic> {
+        self.engine.call_fn::<Dynamic>(&mut Scope::new(), &self.ast, function, args)
+            .map_err(|e| UftError::Generation(format!("The {} script's {} failed: {}", self.language, function, e)).into())
+    }
+
+    /// The functions the script's `analyze` finds in `source`, if it defines one
+    pub fn analyze(&self, source: &str, file_path: &str) -> Result<Vec<ScriptFunction>> {
+        if !self.defines("analyze", 2) {
+            return Ok(vec![]);
+        }
+        let found = self.call("analyze", (source.to_string(), file_path.to_string()))?;
+        rhai::serde::from_dynamic(&found)
+            .map_err(|e| UftError::Generation(format!("The {} script's analyze returned {}: {}", self.language, found.type_name(), e)).into())
+    }
+
+    /// The body the script's `test_body` writes for `test`, if it defines one
+    pub fn test_body(&self, test: &ScriptTest) -> Result<Option<String>> {
+        if !self.defines("test_body", 1) {
+            return Ok(None);
+        }
+        let test = rhai::serde::to_dynamic(test)?;
+        let body = self.call("test_body", (test,))?;
+        body.into_string().map(Some)
+            .map_err(|found| UftError::Generation(format!("The {} script's test_body returned {}, not a string", self.language, found)).into())
+    }
+}
+
+/// Every match of `pattern` in `text`
+fn find_all(text: &str, pattern: &str) -> Result<Array, Box<EvalAltResult>> {
+    let regex = Regex::new(pattern).map_err(|e| format!("Invalid regex `{}`: {}", pattern, e))?;
+    Ok(regex.captures_iter(text).map(|cap| {
+        let whole = cap.get(0).expect("group 0 always matches");
+        let line_start = text[..whole.start()].rfind('\n').map_or(0, |i| i + 1);
+        let captures: Array = cap.iter()
+            .map(|group| Dynamic::from(group.map_or("", |m| m.as_str()).to_string()))
+            .collect();
+        let mut found = Map::new();
+        found.insert("captures".into(), captures.into());
+        found.insert("line".into(), ((text[..whole.start()].matches('\n').count() + 1) as i64).into());
+        found.insert("column".into(), ((whole.start() - line_start + 1) as i64).into());
+        Dynamic::from_map(found)
+    }).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"
+fn analyze(source, file_path) {
+    let functions = [];
+    for found in find_all(source, `fun\s+(\w+)\(([^)]*)\)`) {
+        let parameters = [];
+        for parameter in found.captures[2].split(",") {
+            parameter.trim();
+            if parameter != "" { parameters.push(parameter.split(":")[0]); }
+        }
+        functions.push(#{ name: found.captures[1], parameters: parameters, line: found.line });
+    }
+    functions
+}
+
+fn test_body(test) {
+    `        assertEquals(expected, ${test.function}(${test.parameters.len()} args))`
+}
+"#;
+
+    #[test]
+    fn test_language_script() {
+        let script = LanguageScript::compile("kotlin", SCRIPT).unwrap();
+        let functions = script.analyze("class Calc {\n    fun add(a: Int, b: Int) = a + b\n}", "Calc.kt").unwrap();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "add");
+        assert_eq!(functions[0].parameters, vec!["a", "b"]);
+        assert_eq!(functions[0].line, 2);
+
+        let parameters = vec!["a".to_string(), "b".to_string()];
+        let test = ScriptTest { name: "testadd", description: "Test for method add", function: "add", parameters: &parameters, return_type: None, method: None, path: None };
+        assert_eq!(script.test_body(&test).unwrap().unwrap(), "        assertEquals(expected, add(2 args))");
+
+        let empty = LanguageScript::compile("kotlin", "").unwrap();
+        assert!(empty.analyze("fun add() = 3", "Calc.kt").unwrap().is_empty());
+        assert!(empty.test_body(&test).unwrap().is_none());
+
+        let looping = LanguageScript::compile("kotlin", "fn analyze(source, file_path) { loop {} }").unwrap();
+        let error = looping.analyze("", "Calc.kt").unwrap_err();
+        assert_eq!(UftError::exit_code_of(&error), 1);
+        assert!(LanguageScript::compile("kotlin", "fn analyze(").is_err());
+    }
+}
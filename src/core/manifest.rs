@@ -0,0 +1,212 @@
+use anyhow::{Context as _, Result};
+use git2::{ObjectType, Oid};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::UftError;
+
+/// Where a run records what it wrote, relative to the directory it ran on
+pub const MANIFEST_FILE: &str = "uft-manifest.json";
+
+/// A file a run created or modified
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Relative to the manifest's directory
+    pub path: String,
+    /// Git blob id of what the run left in the file
+    pub hash: String,
+    /// The file's content before the run; `None` when the run created it
+    pub previous: Option<String>,
+    /// The patterns its tests were generated from, as `<source file>#<subject>`. Adapters give
+    /// patterns a fresh id on every analysis, so these are what identifies one across runs
+    pub patterns: Vec<String>,
+}
+
+/// Every file the last generating run wrote under a directory, so `uft rollback` can restore
+/// exactly what was there before. Saved after each write, so an interrupted run can be
+/// rolled back too
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerationManifest {
+    /// Subcommand of the run
+    pub command: String,
+    /// Unix seconds
+    pub started_at: u64,
+    pub files: Vec<ManifestEntry>,
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+/// What a rollback did, by path relative to the manifest's directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rollback {
+    pub deleted: Vec<String>,
+    pub restored: Vec<String>,
+}
+
+impl GenerationManifest {
+    /// An empty manifest for a run of `command` on `root`; nothing is saved until it writes a file
+    pub fn new(root: &Path, command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            started_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()),
+            files: Vec::new(),
+            root: root.to_path_buf(),
+        }
+    }
+
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(MANIFEST_FILE)
+    }
+
+    /// The manifest the last run left in `root`
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| UftError::Config(format!("No generation manifest at {}: {}", path.display(), e)))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .map_err(|e| UftError::Config(format!("Invalid generation manifest {}: {}", path.display(), e)))?;
+        Ok(Self { root: root.to_path_buf(), ..manifest })
+    }
+
+    /// The interrupted run's manifest when resuming it, else a new one
+    pub fn resume(root: &Path, command: &str, resuming: bool) -> Self {
+        match Self::load(root) {
+            Ok(manifest) if resuming => manifest,
+            _ => Self::new(root, command),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path(&self.root);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write generation manifest {}", path.display()))
+    }
+
+    /// Record that the run wrote `file`, whose content was `previous` before (`None` when it
+    /// didn't exist), and save. A file written twice keeps its first `previous`
+    pub fn record(&mut self, file: &Path, previous: Option<String>, patterns: &[String]) -> Result<()> {
+        let relative = file.strip_prefix(&self.root).unwrap_or(file).to_string_lossy().to_string();
+        let hash = blob_hash(&fs::read(file)?)?;
+        match self.files.iter_mut().find(|entry| entry.path == relative) {
+            Some(entry) => {
+                entry.hash = hash;
+                for pattern in patterns {
+                    if !entry.patterns.contains(pattern) {
+                        entry.patterns.push(pattern.clone());
+                    }
+                }
+            }
+            None => self.files.push(ManifestEntry { path: relative, hash, previous, patterns: patterns.to_vec() }),
+        }
+        self.save()
+    }
+
+    /// Files changed since the run wrote them, or deleted
+    pub fn modified_since(&self) -> Vec<&ManifestEntry> {
+        self.files.iter()
+            .filter(|entry| {
+                fs::read(self.root.join(&entry.path)).ok()
+                    .and_then(|content| blob_hash(&content).ok())
+                    .is_none_or(|hash| hash != entry.hash)
+            })
+            .collect()
+    }
+
+    /// Delete the files the run created and put back the ones it modified, then remove the
+    /// manifest. Refuses when any was changed since, unless `force`
+    pub fn rollback(&self, force: bool) -> Result<Rollback> {
+        let modified = self.modified_since();
+        if !modified.is_empty() && !force {
+            let paths: Vec<&str> = modified.iter().map(|entry| entry.path.as_str()).collect();
+            return Err(UftError::Config(format!(
+                "Changed since uft wrote them: {} (use --force to roll back anyway)",
+                paths.join(", ")
+            )).into());
+        }
+
+        let mut rollback = Rollback::default();
+        for entry in self.files.iter().rev() {
+            let path = self.root.join(&entry.path);
+            match &entry.previous {
+                Some(previous) => {
+                    fs::write(&path, previous)?;
+                    rollback.restored.push(entry.path.clone());
+                }
+                None => {
+                    if path.exists() {
+                        fs::remove_file(&path)?;
+                    }
+                    remove_empty_parents(&path, &self.root);
+                    rollback.deleted.push(entry.path.clone());
+                }
+            }
+        }
+        fs::remove_file(Self::path(&self.root))?;
+        Ok(rollback)
+    }
+}
+
+/// Manifest ids of the patterns with these subjects in `source`
+pub fn pattern_ids<'a>(source: &str, subjects: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for subject in subjects {
+        let id = format!("{}#{}", source, subject);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Git blob id of `content`, the hash `git hash-object` prints
+pub fn blob_hash(content: &[u8]) -> Result<String> {
+    Ok(Oid::hash_object(ObjectType::Blob, content)?.to_string())
+}
+
+/// Remove the directories above `path` that deleting it left empty, up to `root`
+fn remove_empty_parents(path: &Path, root: &Path) {
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        if current == root || !current.starts_with(root) || fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_rollback() {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(root.path().join("test_calc.py"), "def test_add():\n    pass\n").unwrap();
+
+        let mut manifest = GenerationManifest::new(root.path(), "dir");
+        let created = root.path().join("tests/unit/test_util.py");
+        fs::create_dir_all(created.parent().unwrap()).unwrap();
+        fs::write(&created, "def test_trim():\n    pass\n").unwrap();
+        manifest.record(&created, None, &["util.py#trim".to_string()]).unwrap();
+        let modified = root.path().join("test_calc.py");
+        fs::write(&modified, "def test_add():\n    pass\n\ndef test_sub():\n    pass\n").unwrap();
+        manifest.record(&modified, Some("def test_add():\n    pass\n".to_string()), &["calc.py#sub".to_string()]).unwrap();
+
+        let loaded = GenerationManifest::load(root.path()).unwrap();
+        assert_eq!(loaded, manifest);
+        assert_eq!(loaded.files[0].path, "tests/unit/test_util.py");
+        assert_eq!(blob_hash(b"hello\n").unwrap(), "ce013625030ba8dba906f756967f9e9ca394464a");
+
+        fs::write(&modified, "edited").unwrap();
+        assert_eq!(UftError::exit_code_of(&loaded.rollback(false).unwrap_err()), 3);
+        let rollback = loaded.rollback(true).unwrap();
+        assert_eq!(rollback.deleted, ["tests/unit/test_util.py"]);
+        assert_eq!(rollback.restored, ["test_calc.py"]);
+        assert_eq!(fs::read_to_string(&modified).unwrap(), "def test_add():\n    pass\n");
+        assert!(!root.path().join("tests").exists());
+        assert!(!GenerationManifest::path(root.path()).exists());
+    }
+}
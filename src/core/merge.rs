@@ -0,0 +1,224 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::core::{PatternType, TestablePattern};
+
+static INLINE_TESTS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^#\[cfg\(test\)\]\s*\n\s*mod tests\s*\{").unwrap());
+
+/// A generated test file cut into its imports and fixtures, one piece per test, and the lines
+/// closing its test class, module or `describe` block
+struct TestFileLayout<'a> {
+    header: &'a str,
+    tests: Vec<(String, &'a str)>,
+    footer: &'a str,
+}
+
+/// Test definitions at the indentation of the first one, so helpers nested in test bodies
+/// don't count. A Rust `fn` is a test under a test attribute, which keeps the code of a source
+/// file holding its own tests out
+fn test_definitions(text: &str) -> Vec<(usize, String)> {
+    let definition = Regex::new(r#"(?m)^([ \t]*)(?:(?:async\s+)?(fn|def|func)\s+(\w+)|(?:it|test)\(\s*['"`]([^'"`]+)['"`]|(?:public\s+)?void\s+(\w+)\s*\()"#).unwrap();
+    let found: Vec<(usize, usize, String)> = definition.captures_iter(text).filter_map(|cap| {
+        let start = cap.get(0).unwrap().start();
+        if cap.get(2).is_some_and(|keyword| keyword.as_str() == "fn") {
+            let attributes = text[..start].lines().rev().take_while(|line| line.trim_start().starts_with("#["));
+            if !attributes.into_iter().any(|line| line.contains("test")) {
+                return None;
+            }
+        }
+        let name = cap.get(3).or(cap.get(4)).or(cap.get(5))?.as_str().to_string();
+        Some((start, cap[1].len(), name))
+    }).collect();
+    let indent = found.first().map(|(_, indent, _)| *indent);
+    found.into_iter()
+        .filter(|(_, i, _)| Some(*i) == indent)
+        .map(|(start, _, name)| (start, name))
+        .collect()
+}
+
+fn layout(text: &str) -> TestFileLayout<'_> {
+    let lines: Vec<(usize, &str)> = text.split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+        .collect();
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let definitions = test_definitions(text);
+    let Some(&(first, _)) = definitions.first() else {
+        return TestFileLayout { header: text, tests: Vec::new(), footer: "" };
+    };
+    let indent = indent_of(&text[first..]);
+
+    // Attribute, annotation and decorator lines belong to the test below them
+    let starts: Vec<usize> = definitions.iter().map(|(start, _)| {
+        let index = lines.iter().position(|(offset, _)| offset == start).unwrap_or(0);
+        let attributes = lines[..index].iter().rev()
+            .take_while(|(_, line)| indent_of(line) == indent && (line.trim_start().starts_with('@') || line.trim_start().starts_with("#[")))
+            .count();
+        lines[index - attributes].0
+    }).collect();
+    let footer = lines.iter().rev()
+        .take_while(|(_, line)| line.trim().is_empty() || (indent_of(line) < indent && line.trim_start().starts_with(['}', ')'])))
+        .last()
+        .map(|(offset, _)| *offset)
+        .unwrap_or(text.len())
+        .max(first);
+
+    TestFileLayout {
+        header: &text[..starts[0]],
+        tests: definitions.iter().zip(&starts).enumerate().map(|(i, ((_, name), start))| {
+            (name.clone(), &text[*start..starts.get(i + 1).copied().unwrap_or(footer)])
+        }).collect(),
+        footer: &text[footer..],
+    }
+}
+
+/// Names of the tests a test file defines: functions, `def test_...` methods, `it`/`test`
+/// calls and Java test methods
+pub fn test_names(test_file: &str) -> Vec<String> {
+    test_definitions(test_file).into_iter().map(|(_, name)| name).collect()
+}
+
+/// [`test_names`] with the 1-based line each test is defined on
+pub fn test_locations(test_file: &str) -> Vec<(String, usize)> {
+    test_definitions(test_file).into_iter()
+        .map(|(start, name)| (name, test_file[..start].matches('\n').count() + 1))
+        .collect()
+}
+
+/// Functions among `patterns` that `tests` neither calls nor names a test after, each once
+pub fn untested_functions<'a>(patterns: &'a [TestablePattern], tests: &str) -> Vec<&'a TestablePattern> {
+    let names: Vec<String> = test_names(tests).iter().map(|name| name.to_lowercase()).collect();
+    let mut seen = Vec::new();
+    patterns.iter()
+        .filter(|pattern| matches!(pattern.pattern_type, PatternType::Function(_)))
+        .filter(|pattern| {
+            let name = pattern.subject_name();
+            let short = name.rsplit(['.', ':']).next().unwrap_or(name);
+            if seen.contains(&name) {
+                return false;
+            }
+            seen.push(name);
+            let called = Regex::new(&format!(r"\b{}\s*\(", regex::escape(short))).unwrap().is_match(tests);
+            !called && !names.iter().any(|test| test.contains(&short.to_lowercase()))
+        })
+        .collect()
+}
+
+fn is_import(line: &str) -> bool {
+    let line = line.trim();
+    ["use ", "import ", "from "].iter().any(|prefix| line.starts_with(prefix))
+        || (line.starts_with("const ") && line.contains("require("))
+}
+
+/// Where the `#[cfg(test)] mod tests` of a Rust file starts
+pub fn inline_tests_start(text: &str) -> Option<usize> {
+    INLINE_TESTS_REGEX.find(text).map(|m| m.start())
+}
+
+/// A Rust source file with the inline test module `tests` in place of its own, or after its
+/// code when it has none
+pub fn replace_inline_tests(source: &str, tests: &str) -> String {
+    match inline_tests_start(source) {
+        Some(start) => format!("{}{}", &source[..start], tests),
+        None => format!("{}\n\n{}", source.trim_end(), tests),
+    }
+}
+
+/// Fold a freshly generated test file into an existing one: tests it doesn't define yet go
+/// before its closing lines and imports it lacks after its last import, leaving everything
+/// else, including edits to tests it already has, untouched. `None` when it already defines
+/// every generated test
+pub fn merge_test_file(existing: &str, generated: &str) -> Option<String> {
+    // A generated inline test module goes into the Rust source file's own, or after its code
+    if let Some(start) = inline_tests_start(generated).filter(|start| *start > 0 && inline_tests_start(existing) != Some(0)) {
+        return merge_test_file(existing, &generated[start..]);
+    }
+    if inline_tests_start(generated) == Some(0) {
+        match inline_tests_start(existing) {
+            Some(0) => {}
+            Some(start) => return merge_test_file(&existing[start..], generated).map(|tests| format!("{}{}", &existing[..start], tests)),
+            None if test_names(generated).is_empty() => return None,
+            None => return Some(replace_inline_tests(existing, generated)),
+        }
+    }
+    let present = test_names(existing);
+    let generated = layout(generated);
+    let added: Vec<&str> = generated.tests.iter()
+        .filter(|(name, _)| !present.contains(name))
+        .map(|(_, text)| *text)
+        .collect();
+    if added.is_empty() {
+        return None;
+    }
+
+    let current = layout(existing);
+    let imports: Vec<&str> = generated.header.lines()
+        .filter(|line| is_import(line) && !current.header.lines().any(|l| l.trim() == line.trim()))
+        .collect();
+    let mut header = current.header.to_string();
+    if !imports.is_empty() {
+        let insert_at = current.header.split_inclusive('\n')
+            .scan(0, |offset, line| {
+                *offset += line.len();
+                Some((*offset, line))
+            })
+            .filter(|(_, line)| is_import(line))
+            .last()
+            .map_or(0, |(end, _)| end);
+        header.insert_str(insert_at, &imports.iter().map(|line| format!("{}\n", line)).collect::<String>());
+    }
+
+    let mut merged = header;
+    for (_, text) in &current.tests {
+        merged.push_str(text);
+    }
+    if !merged.ends_with("\n\n") {
+        merged.push_str(if merged.ends_with('\n') { "\n" } else { "\n\n" });
+    }
+    for text in added {
+        merged.push_str(text);
+    }
+    if !current.footer.is_empty() && !merged.ends_with("\n\n") {
+        merged.push('\n');
+    }
+    merged.push_str(current.footer.trim_start_matches('\n'));
+    Some(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_test_file_appends_new_tests() {
+        let existing = "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_add() {\n        // edited by hand\n        assert_eq!(add(1, 2), 3);\n    }\n}\n";
+        let generated = "#[cfg(test)]\nmod tests {\n    use super::*;\n    use rstest::rstest;\n\n    #[test]\n    fn test_add() {\n        assert_eq!(add(5, 5), 10);\n    }\n\n    #[tokio::test]\n    async fn test_fetch() {\n        let _ = fetch().await;\n    }\n\n}\n";
+        assert_eq!(merge_test_file(existing, generated).unwrap(), "#[cfg(test)]\nmod tests {\n    use super::*;\n    use rstest::rstest;\n\n    #[test]\n    fn test_add() {\n        // edited by hand\n        assert_eq!(add(1, 2), 3);\n    }\n\n    #[tokio::test]\n    async fn test_fetch() {\n        let _ = fetch().await;\n    }\n\n}\n");
+        assert_eq!(merge_test_file(existing, existing), None);
+
+        let existing = "import pytest\n\n\nclass TestGenerated:\n    def test_total(self):\n        \"\"\"Test total\"\"\"\n        assert total([]) == 0\n";
+        let generated = "import pytest\nimport threading\n\n\nclass TestGenerated:\n    def test_total(self):\n        \"\"\"Test total\"\"\"\n        assert total([1]) == 1\n\n    def test_counter_concurrently(self):\n        def work():\n            pass\n";
+        let merged = merge_test_file(existing, generated).unwrap();
+        assert!(merged.starts_with("import pytest\nimport threading\n\n\nclass TestGenerated:\n    def test_total(self):\n"));
+        assert!(merged.ends_with("        assert total([]) == 0\n\n    def test_counter_concurrently(self):\n        def work():\n            pass\n"));
+        assert_eq!(test_names(&merged), vec!["test_total", "test_counter_concurrently"]);
+        assert_eq!(test_locations(&merged), vec![("test_total".to_string(), 6), ("test_counter_concurrently".to_string(), 10)]);
+    }
+
+    #[test]
+    fn test_merge_inline_tests_into_a_rust_source_file() {
+        let generated = "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_parse() {\n        let _ = parse(\"\");\n    }\n}\n";
+        let source = "pub fn parse(input: &str) -> usize {\n    input.len()\n}\n";
+        assert!(test_names(source).is_empty());
+        assert_eq!(merge_test_file(source, generated).unwrap(), format!("{}\n{}", source, generated));
+
+        let with_tests = "pub fn parse(input: &str) -> usize {\n    input.len()\n}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_len() {\n        assert_eq!(parse(\"ab\"), 2);\n    }\n}\n";
+        let merged = merge_test_file(with_tests, generated).unwrap();
+        assert!(merged.starts_with("pub fn parse(input: &str) -> usize {\n    input.len()\n}\n\n#[cfg(test)]\nmod tests {\n"));
+        assert_eq!(test_names(&merged), vec!["test_len", "test_parse"]);
+        assert_eq!(merge_test_file(&merged, generated), None);
+    }
+}
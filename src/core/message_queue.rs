@@ -0,0 +1,140 @@
+use regex::Regex;
+
+use super::{MessageBroker, MessageQueuePattern, MessageRole};
+
+impl MessageBroker {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MessageBroker::Kafka => "Kafka",
+            MessageBroker::RabbitMq => "RabbitMQ",
+            MessageBroker::Sqs => "SQS",
+        }
+    }
+
+    /// Variable generated tests read the test broker's address from
+    pub fn env_var(&self) -> &'static str {
+        match self {
+            MessageBroker::Kafka => "KAFKA_BOOTSTRAP_SERVERS",
+            MessageBroker::RabbitMq => "RABBITMQ_URL",
+            MessageBroker::Sqs => "SQS_ENDPOINT_URL",
+        }
+    }
+}
+
+impl MessageQueuePattern {
+    /// Topic or queue generated tests use: the detected destination, or a throwaway name
+    pub fn test_destination(&self) -> String {
+        self.destination.clone().unwrap_or_else(|| match self.broker {
+            MessageBroker::Kafka => "utf-test-topic".to_string(),
+            _ => "utf-test-queue".to_string(),
+        })
+    }
+
+    /// Identifier-safe form of the destination for test names
+    pub fn slug(&self) -> String {
+        Regex::new(r"\W+").unwrap().replace_all(&self.test_destination(), "_").trim_matches('_').to_lowercase()
+    }
+}
+
+/// Producer and consumer calls for Kafka (kafka-python, confluent-kafka, kafkajs, rdkafka),
+/// RabbitMQ (pika, amqplib, lapin) and SQS (boto3, AWS SDK for JavaScript and Rust), with the
+/// offset of each call. A broker's calls only count when the source mentions its client.
+pub fn message_queue_calls(source: &str) -> Vec<(usize, MessageQueuePattern)> {
+    let lowercase = source.to_lowercase();
+    let mentions = |keywords: &[&str]| keywords.iter().any(|k| lowercase.contains(k));
+    let calls: &[(MessageBroker, MessageRole, &str)] = &[
+        (MessageBroker::Kafka, MessageRole::Producer, r#"(?i)\b\w*producer\s*\.\s*(?:send|produce|send_and_wait)\s*\(\s*(?:\{\s*topic\s*:\s*)?(?:['"]([\w.\-]+)['"])?"#),
+        (MessageBroker::Kafka, MessageRole::Producer, r#"\b(?:Future|Base)Record::(?:<[^>]*>::)?to\s*\(\s*"([\w.\-]+)""#),
+        (MessageBroker::Kafka, MessageRole::Consumer, r#"(?i)\b\w*consumer\s*\.\s*subscribe\s*\(\s*(?:&?\[\s*|\{\s*topics?\s*:\s*\[?\s*)?(?:['"]([\w.\-]+)['"])?"#),
+        (MessageBroker::Kafka, MessageRole::Consumer, r#"\b(?:AIO)?KafkaConsumer\s*\(\s*(?:['"]([\w.\-]+)['"])?"#),
+        (MessageBroker::RabbitMq, MessageRole::Producer, r#"\bbasic_publish\s*\(\s*(?:(?:exchange\s*=\s*)?['"][^'"]*['"]\s*,\s*(?:routing_key\s*=\s*)?['"]([^'"]+)['"])?"#),
+        (MessageBroker::RabbitMq, MessageRole::Producer, r#"\.\s*sendToQueue\s*\(\s*(?:['"]([^'"]+)['"])?"#),
+        (MessageBroker::RabbitMq, MessageRole::Producer, r#"\bchannel\s*\.\s*publish\s*\(\s*(?:['"][^'"]*['"]\s*,\s*['"]([^'"]+)['"])?"#),
+        (MessageBroker::RabbitMq, MessageRole::Consumer, r#"\bbasic_consume\s*\(\s*(?:(?:queue\s*=\s*)?['"]([^'"]+)['"])?"#),
+        (MessageBroker::RabbitMq, MessageRole::Consumer, r#"\bchannel\s*\.\s*consume\s*\(\s*(?:['"]([^'"]+)['"])?"#),
+        (MessageBroker::Sqs, MessageRole::Producer, r"\b(?:send_message(?:_batch)?|sendMessage(?:Batch)?|SendMessage(?:Batch)?Command)\s*\("),
+        (MessageBroker::Sqs, MessageRole::Consumer, r"\b(?:receive_message|receiveMessage|ReceiveMessageCommand)\s*\("),
+    ];
+
+    let mut found: Vec<(usize, MessageQueuePattern)> = Vec::new();
+    for (broker, role, pattern) in calls {
+        let client_mentioned = match broker {
+            MessageBroker::Kafka => mentions(&["kafka"]),
+            MessageBroker::RabbitMq => mentions(&["pika", "amqp", "lapin", "rabbit"]),
+            MessageBroker::Sqs => mentions(&["sqs"]),
+        };
+        if !client_mentioned {
+            continue;
+        }
+
+        for cap in Regex::new(pattern).unwrap().captures_iter(source) {
+            let start = cap.get(0).unwrap().start();
+            if found.iter().any(|(offset, _)| *offset == start) {
+                continue;
+            }
+            let destination = cap.get(1).map(|d| d.as_str().to_string()).or_else(|| destination_near(source, start, *broker));
+            found.push((start, MessageQueuePattern { broker: *broker, role: *role, destination }));
+        }
+    }
+    found.sort_by_key(|(offset, _)| *offset);
+
+    // `producer.send(FutureRecord::to("events"))` is one call: keep the match naming the topic
+    let named: Vec<(usize, MessageBroker, MessageRole)> = found.iter()
+        .filter(|(_, p)| p.destination.is_some())
+        .map(|(offset, p)| (*offset, p.broker, p.role))
+        .collect();
+    found.retain(|(offset, p)| {
+        p.destination.is_some() || !named.iter().any(|(o, b, r)| *b == p.broker && *r == p.role && o > offset && o - offset < 300)
+    });
+    found
+}
+
+/// Destination passed by keyword shortly after a call, e.g. `routing_key="orders"` or
+/// `.queue_url("https://.../orders")`; SQS queue URLs give their queue name
+fn destination_near(source: &str, start: usize, broker: MessageBroker) -> Option<String> {
+    let end = (start + 300..source.len()).find(|&i| source.is_char_boundary(i)).unwrap_or(source.len());
+    let window = &source[start..end];
+    let pattern = match broker {
+        MessageBroker::Kafka => r#"\btopic\s*[:=]\s*['"]([\w.\-]+)['"]"#,
+        MessageBroker::RabbitMq => r#"\b(?:routing_key|queue)\s*[:=]\s*['"]([^'"]+)['"]"#,
+        MessageBroker::Sqs => r#"(?i)\bqueue_?url\s*(?:[:=(]\s*)['"]([^'"]+)['"]"#,
+    };
+    let destination = Regex::new(pattern).unwrap().captures(window)?[1].to_string();
+    Some(match broker {
+        MessageBroker::Sqs => destination.rsplit('/').next().unwrap_or_default().to_string(),
+        _ => destination,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(source: &str) -> Vec<(MessageBroker, MessageRole, Option<String>)> {
+        message_queue_calls(source).into_iter().map(|(_, p)| (p.broker, p.role, p.destination)).collect()
+    }
+
+    #[test]
+    fn test_python_message_queue_calls() {
+        let source = "from kafka import KafkaProducer\nimport pika\nimport boto3\n\nproducer.send('orders', value=b'1')\nchannel.basic_publish(exchange='', routing_key='emails', body=b'hi')\nsqs = boto3.client('sqs')\nsqs.send_message(QueueUrl='https://sqs.us-east-1.amazonaws.com/1/jobs', MessageBody='x')\n";
+        assert_eq!(summary(source), vec![
+            (MessageBroker::Kafka, MessageRole::Producer, Some("orders".to_string())),
+            (MessageBroker::RabbitMq, MessageRole::Producer, Some("emails".to_string())),
+            (MessageBroker::Sqs, MessageRole::Producer, Some("jobs".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_javascript_and_rust_message_queue_calls() {
+        let js = "const { Kafka } = require('kafkajs');\nawait consumer.subscribe({ topics: ['payments'] });\nconst amqp = require('amqplib');\nchannel.consume(queueName, handle);\n";
+        assert_eq!(summary(js), vec![
+            (MessageBroker::Kafka, MessageRole::Consumer, Some("payments".to_string())),
+            (MessageBroker::RabbitMq, MessageRole::Consumer, None),
+        ]);
+
+        let rust = "use rdkafka::producer::FutureRecord;\nproducer.send(FutureRecord::to(\"events\").payload(&body), timeout).await;\n";
+        assert_eq!(summary(rust), vec![(MessageBroker::Kafka, MessageRole::Producer, Some("events".to_string()))]);
+
+        assert!(summary("fn send_message(to: &str) {}\nsend_message(\"bob\");\n").is_empty());
+    }
+}
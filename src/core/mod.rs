@@ -2,14 +2,94 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::Instrument;
 
 pub mod dynamic_adapter;
 pub mod language_loader;
 pub mod coverage_standards;
+pub mod adapter_config;
+pub mod project_config;
+pub mod case_table;
+pub mod error_paths;
+pub mod doc_examples;
+pub mod coverage_report;
+pub mod graphql;
+pub mod grpc;
+pub mod message_queue;
+pub mod containers;
+pub mod openapi;
+pub mod recorded_request;
+pub mod websocket;
+pub mod auth_flows;
+pub mod env_vars;
+pub mod cli;
+pub mod regex_literals;
+pub mod serialization;
+pub mod enum_variants;
+pub mod react_hooks;
+pub mod builders;
+pub mod concurrency;
+pub mod merge;
+pub mod test_files;
+pub mod report;
+pub mod error;
+pub mod checkpoint;
+pub mod git_diff;
+pub mod github_actions;
+pub mod pull_request;
+pub mod git_auth;
+pub mod manifest;
+pub mod config_validation;
+pub mod wasm_adapter;
+pub mod process_adapter;
+pub mod language_script;
+pub mod language_registry;
+pub mod source_reader;
+pub mod workspace;
 
 pub use dynamic_adapter::*;
 pub use language_loader::*;
 pub use coverage_standards::*;
+pub use adapter_config::*;
+pub use project_config::*;
+pub use case_table::*;
+pub use error_paths::*;
+pub use doc_examples::*;
+pub use coverage_report::*;
+pub use graphql::*;
+pub use grpc::*;
+pub use message_queue::*;
+pub use containers::*;
+pub use openapi::*;
+pub use recorded_request::*;
+pub use websocket::*;
+pub use auth_flows::*;
+pub use env_vars::*;
+pub use cli::*;
+pub use regex_literals::*;
+pub use serialization::*;
+pub use enum_variants::*;
+pub use react_hooks::*;
+pub use builders::*;
+pub use concurrency::*;
+pub use merge::*;
+pub use test_files::*;
+pub use report::*;
+pub use error::*;
+pub use checkpoint::*;
+pub use git_diff::*;
+pub use github_actions::*;
+pub use pull_request::*;
+pub use git_auth::*;
+pub use manifest::*;
+pub use config_validation::*;
+pub use wasm_adapter::*;
+pub use process_adapter::*;
+pub use language_script::*;
+pub use language_registry::*;
+pub use source_reader::*;
+pub use workspace::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceLocation {
@@ -45,6 +125,12 @@ pub enum PatternType {
     ApiIntegration(ApiIntegrationPattern),
     ComponentIntegration(ComponentPattern),
     WorkflowIntegration(WorkflowPattern),
+    GraphQlOperation(GraphQlPattern),
+    GrpcCall(GrpcPattern),
+    MessageQueue(MessageQueuePattern),
+    RecordedRequest(RecordedRequest),
+    WebSocket(WebSocketPattern),
+    CliCommand(CliPattern),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +160,7 @@ pub enum HttpMethod {
     Get,
     Post,
     Put,
+    Patch,
     Delete,
 }
 
@@ -83,6 +170,7 @@ impl ToString for HttpMethod {
             HttpMethod::Get => "GET".to_string(),
             HttpMethod::Post => "POST".to_string(),
             HttpMethod::Put => "PUT".to_string(),
+            HttpMethod::Patch => "PATCH".to_string(),
             HttpMethod::Delete => "DELETE".to_string(),
         }
     }
@@ -170,6 +258,146 @@ pub struct WorkflowPattern {
     pub is_async: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQlPattern {
+    pub operation_type: GraphQlOperationType,
+    /// Operation name, or the field name for a resolver definition
+    pub name: String,
+    /// Operation document with the fragments it spreads; `None` for resolver definitions
+    pub document: Option<String>,
+    /// Variable definitions as `name: Type`
+    pub variables: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GraphQlOperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl GraphQlOperationType {
+    /// Keyword starting an operation of this type
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            GraphQlOperationType::Query => "query",
+            GraphQlOperationType::Mutation => "mutation",
+            GraphQlOperationType::Subscription => "subscription",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcPattern {
+    /// Proto package, when known
+    pub package: Option<String>,
+    pub service: String,
+    /// RPC name as declared in the `.proto`, e.g. `SayHello`
+    pub method: String,
+    /// Message types as written in the `.proto`; `None` when only client calls were seen
+    pub request_type: Option<String>,
+    pub response_type: Option<String>,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+    /// `.proto` declaring the service, when known
+    pub proto_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageQueuePattern {
+    pub broker: MessageBroker,
+    pub role: MessageRole,
+    /// Topic, queue or routing key, when written as a literal
+    pub destination: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MessageBroker {
+    Kafka,
+    RabbitMq,
+    Sqs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MessageRole {
+    Producer,
+    Consumer,
+}
+
+/// An HTTP request replayed from a Postman collection or a HAR capture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// Name given to the request, prefixed with its folders (`METHOD /path` for captures)
+    pub name: String,
+    pub method: HttpMethod,
+    /// URL as recorded, with `{{variable}}` placeholders
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<RecordedBody>,
+    /// Variables the request refers to, with their recorded values
+    pub variables: Vec<(String, String)>,
+    pub expected_status: Option<u16>,
+    /// JSON Schema inferred from the recorded JSON response
+    pub response_schema: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedBody {
+    Json(serde_json::Value),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebSocketPattern {
+    pub client: WebSocketClient,
+    /// URL the client connects to, when written as a literal
+    pub url: Option<String>,
+    /// Events the source emits (socket.io), in source order
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WebSocketClient {
+    /// `new WebSocket(...)`, the browser API or the `ws` package
+    Native,
+    SocketIo,
+    Tungstenite,
+    TokioTungstenite,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliPattern {
+    pub parser: CliParser,
+    /// Source file declaring the parser: the script the tests run, or the binary it builds
+    pub script: String,
+    /// Program name given to the parser, when declared
+    pub program: Option<String>,
+    /// Top-level arguments, in declaration order
+    pub arguments: Vec<CliArgument>,
+    /// Subcommand names, in declaration order
+    pub subcommands: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliArgument {
+    /// Positional name, or the option's long name without dashes
+    pub name: String,
+    /// `--output` or `-o`; `None` for positionals
+    pub flag: Option<String>,
+    pub required: bool,
+    /// The option is followed by a value rather than being a switch
+    pub takes_value: bool,
+    /// Declared value type, e.g. `u16` or `int`
+    pub value_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CliParser {
+    Clap,
+    Argparse,
+    Commander,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestCase {
     pub id: String,
@@ -190,6 +418,8 @@ pub enum TestCategory {
     BoundaryCondition,
     Integration,
     Performance,
+    /// Token tampering, expiry, missing credentials and password hashing
+    Security,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,13 +433,152 @@ pub struct TestSuite {
     pub setup_requirements: Vec<String>,
     pub cleanup_requirements: Vec<String>,
     pub coverage_target: f32,
+    /// Shared setup the test bodies refer to by name
+    #[serde(default)]
+    pub fixtures: Vec<Fixture>,
     pub test_code: Option<String>,
 }
 
+/// The patterns detected in one file, as `analyze --format json` prints them
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    pub file: String,
+    pub language: String,
+    pub patterns: Vec<TestablePattern>,
+    /// Where the file's tests go, once [`check_tests`](Self::check_tests) looked there
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_file: Option<PathBuf>,
+    /// Functions the test file neither calls nor names a test after
+    #[serde(default)]
+    pub untested: Vec<String>,
+}
+
+impl AnalysisReport {
+    /// Record which of the functions found `tests`, the contents of `test_file`, misses
+    pub fn check_tests(&mut self, test_file: PathBuf, tests: &str) {
+        self.untested = untested_functions(&self.patterns, tests).into_iter()
+            .map(|pattern| pattern.subject_name().to_string())
+            .collect();
+        self.test_file = Some(test_file);
+    }
+}
+
+/// A named piece of shared test setup, e.g. a pytest fixture, a jest `beforeEach`
+/// or a Rust helper constructor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub name: String,
+    /// Rendered definition in the suite's language
+    pub code: String,
+}
+
+impl TestablePattern {
+    /// Identifier a generated test is expected to mention
+    pub fn subject_name(&self) -> &str {
+        match &self.pattern_type {
+            PatternType::FormValidation(field) => &field.name,
+            PatternType::ApiCall(endpoint) => &endpoint.path,
+            PatternType::Function(func) => &func.name,
+            PatternType::DatabaseOperation(db) => &db.method_name,
+            PatternType::ServiceIntegration(service) => &service.method_name,
+            PatternType::ApiIntegration(api) => &api.endpoint,
+            PatternType::ComponentIntegration(component) => &component.component_name,
+            PatternType::WorkflowIntegration(workflow) => &workflow.workflow_name,
+            PatternType::GraphQlOperation(operation) => &operation.name,
+            PatternType::GrpcCall(rpc) => &rpc.method,
+            PatternType::MessageQueue(queue) => queue.destination.as_deref().unwrap_or("message"),
+            PatternType::RecordedRequest(request) => &request.name,
+            PatternType::WebSocket(socket) => socket.url.as_deref().unwrap_or("websocket"),
+            PatternType::CliCommand(cli) => cli.program.as_deref().unwrap_or(&cli.script),
+        }
+    }
+}
+
+impl TestSuite {
+    /// Percentage of patterns referenced by at least one generated test case
+    pub fn estimated_coverage(&self, patterns: &[TestablePattern]) -> f32 {
+        if patterns.is_empty() {
+            return 100.0;
+        }
+
+        let covered = patterns.iter()
+            .filter(|pattern| {
+                let subject = pattern.subject_name().to_lowercase();
+                self.test_cases.iter().any(|tc| {
+                    tc.name.to_lowercase().contains(&subject)
+                        || tc.description.to_lowercase().contains(&subject)
+                        || tc.test_body.to_lowercase().contains(&subject)
+                })
+            })
+            .count();
+
+        covered as f32 / patterns.len() as f32 * 100.0
+    }
+
+    /// Fold another suite into this one, skipping duplicate imports and requirements
+    /// and suffixing clashing test names so every case survives.
+    /// Name, language, framework and coverage target stay as they are on `self`;
+    /// `test_code` is cleared since it no longer matches the combined cases.
+    pub fn merge(&mut self, other: TestSuite) {
+        for import in other.imports {
+            if !self.imports.contains(&import) {
+                self.imports.push(import);
+            }
+        }
+
+        for requirement in other.setup_requirements {
+            if !self.setup_requirements.contains(&requirement) {
+                self.setup_requirements.push(requirement);
+            }
+        }
+
+        for requirement in other.cleanup_requirements {
+            if !self.cleanup_requirements.contains(&requirement) {
+                self.cleanup_requirements.push(requirement);
+            }
+        }
+
+        for fixture in other.fixtures {
+            if !self.fixtures.iter().any(|f| f.name == fixture.name) {
+                self.fixtures.push(fixture);
+            }
+        }
+
+        for mut test_case in other.test_cases {
+            if self.test_cases.iter().any(|tc| tc.name == test_case.name) {
+                let base = test_case.name.clone();
+                let mut suffix = 2;
+                while self.test_cases.iter().any(|tc| tc.name == format!("{}_{}", base, suffix)) {
+                    suffix += 1;
+                }
+                test_case.name = format!("{}_{}", base, suffix);
+            }
+            self.test_cases.push(test_case);
+        }
+
+        self.test_code = None;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TestType {
     Unit,
     Integration,
+    /// Browser specs visiting a frontend's routes (Playwright or Cypress)
+    E2e,
+}
+
+impl std::str::FromStr for TestType {
+    type Err = String;
+
+    /// Generation modes of `generate` and `dir`; integration suites have their own command
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unit" => Ok(TestType::Unit),
+            "e2e" => Ok(TestType::E2e),
+            other => Err(format!("Unknown test type: {} (expected unit or e2e)", other)),
+        }
+    }
 }
 
 impl Default for TestType {
@@ -227,6 +596,20 @@ pub trait TestGenerator {
     fn get_supported_frameworks(&self) -> Vec<&str>;
     fn get_coverage_target(&self) -> f32;
     fn generate_test_code(&self, test_suite: &TestSuite) -> Result<String>;
+
+    /// Apply per-adapter generation settings; adapters without configurable output ignore them
+    fn configure(&mut self, _config: AdapterConfig) {}
+
+    /// A `test_path` convention overriding the built-in one: the language config's, or the one
+    /// the adapter's configuration calls for
+    fn test_path(&self) -> Option<&str> {
+        None
+    }
+
+    /// Integration test support, for adapters that implement `IntegrationTestGenerator`
+    fn as_integration(&self) -> Option<&(dyn IntegrationTestGenerator + Send + Sync)> {
+        None
+    }
 }
 
 #[async_trait]
@@ -236,55 +619,231 @@ pub trait IntegrationTestGenerator: TestGenerator {
     fn get_integration_frameworks(&self) -> Vec<&str>;
     fn get_setup_requirements(&self, patterns: &[TestablePattern]) -> Vec<String>;
     fn get_cleanup_requirements(&self, patterns: &[TestablePattern]) -> Vec<String>;
+    /// Databases and brokers the generated tests connect to, with the variable each is read from
+    fn test_services(&self, patterns: &[TestablePattern]) -> Vec<(TestService, &'static str)>;
 }
 
 pub struct TestOrchestrator {
     adapters: HashMap<String, Box<dyn TestGenerator + Send + Sync>>,
+    project_config: ProjectConfig,
+    coverage_report: Option<CoverageReport>,
 }
 
 impl TestOrchestrator {
     pub fn new() -> Self {
         Self {
             adapters: HashMap::new(),
+            project_config: ProjectConfig::default(),
+            coverage_report: None,
         }
     }
 
+    pub fn set_project_config(&mut self, config: ProjectConfig) {
+        self.project_config = config;
+    }
+
+    /// Only generate tests for functions the report shows as not fully covered
+    pub fn set_coverage_report(&mut self, report: CoverageReport) {
+        self.coverage_report = Some(report);
+    }
+
+    pub fn project_config(&self) -> &ProjectConfig {
+        &self.project_config
+    }
+
     pub fn register_adapter(&mut self, language: String, adapter: Box<dyn TestGenerator + Send + Sync>) {
         self.adapters.insert(language, adapter);
     }
 
+    pub fn register_adapter_with_config(&mut self, language: String, mut adapter: Box<dyn TestGenerator + Send + Sync>, config: AdapterConfig) {
+        adapter.configure(config);
+        self.adapters.insert(language, adapter);
+    }
+
+    /// Where the tests for `source_file` go, under the project config and the language's
+    /// test path convention
+    pub fn test_file_path(&self, repo_dir: &Path, source_file: &Path, language: &str) -> PathBuf {
+        let convention = self.adapters.get(language).and_then(|adapter| adapter.test_path());
+        self.project_config.test_file_path_with(repo_dir, source_file, language, convention)
+    }
+
+    /// Replace every adapter with `adapters`, configured by the project config, after the
+    /// language configs change
+    pub fn reload_adapters(&mut self, adapters: HashMap<String, Box<dyn TestGenerator + Send + Sync>>) -> LanguageChanges {
+        let changes = LanguageChanges::between(self.adapters.keys(), adapters.keys());
+        self.adapters.clear();
+        for (language, adapter) in adapters {
+            let config = self.project_config.adapter_config(&language);
+            self.register_adapter_with_config(language, adapter, config);
+        }
+        changes
+    }
+
+    /// Detect the testable patterns in a file, leaving out files the project config ignores
+    /// and patterns below its confidence threshold
     pub async fn analyze_file(&self, file_path: &str, content: &str) -> Result<Vec<TestablePattern>> {
         let language = self.detect_language(file_path)?;
         
         if let Some(adapter) = self.adapters.get(&language) {
-            adapter.analyze_code(content, file_path).await
+            let span = tracing::debug_span!("analyze", file = file_path, adapter = %language);
+            if self.project_config.is_ignored(file_path) {
+                span.in_scope(|| tracing::debug!("ignored by the project config"));
+                return Ok(Vec::new());
+            }
+            let patterns = adapter.analyze_code(content, file_path).instrument(span.clone()).await?;
+            let detected = patterns.len();
+            let patterns: Vec<TestablePattern> = patterns.into_iter().filter(|pattern| self.project_config.is_confident(pattern.confidence)).collect();
+            span.in_scope(|| tracing::debug!(detected, kept = patterns.len(), "patterns detected"));
+            Ok(patterns)
         } else {
-            Err(anyhow::anyhow!("No adapter found for language: {}", language))
+            Err(UftError::UnsupportedLanguage(format!("No adapter found for language: {}", language)).into())
         }
     }
 
+    /// [`analyze_file`](Self::analyze_file), along with the file and the language it was read as
+    pub async fn analysis_report(&self, file_path: &str, content: &str) -> Result<AnalysisReport> {
+        Ok(AnalysisReport {
+            file: file_path.to_string(),
+            language: self.detect_language(file_path)?,
+            patterns: self.analyze_file(file_path, content).await?,
+            test_file: None,
+            untested: Vec::new(),
+        })
+    }
+
     pub async fn generate_tests_for_file(&self, file_path: &str, content: &str) -> Result<TestSuite> {
-        let patterns = self.analyze_file(file_path, content).await?;
+        Ok(self.generate_tests_with_patterns(file_path, content).await?.0)
+    }
+
+    /// [`generate_tests_for_file`](Self::generate_tests_for_file), along with the patterns the
+    /// suite was generated from
+    pub async fn generate_tests_with_patterns(&self, file_path: &str, content: &str) -> Result<(TestSuite, Vec<TestablePattern>)> {
+        let mut patterns = self.analyze_file(file_path, content).await?;
         let language = self.detect_language(file_path)?;
         
         if let Some(adapter) = self.adapters.get(&language) {
-            adapter.generate_tests(patterns).await
+            // With every detected function covered there is nothing to generate, including source-derived tests
+            let mut source = content;
+            if let Some(report) = &self.coverage_report {
+                let detected = patterns.len();
+                patterns = report.uncovered_patterns(file_path, content, patterns);
+                if patterns.is_empty() && detected > 0 {
+                    source = "";
+                }
+            }
+
+            let span = tracing::debug_span!("generate", file = file_path, adapter = %language);
+            let mut suite = adapter.generate_comprehensive_tests(patterns.clone(), source).instrument(span.clone()).await?;
+            span.in_scope(|| tracing::debug!(tests = suite.test_cases.len(), framework = %suite.framework, "suite generated"));
+            suite.coverage_target = self.project_config.coverage_target(&language, file_path);
+            Ok((suite, patterns))
         } else {
-            Err(anyhow::anyhow!("No adapter found for language: {}", language))
+            Err(UftError::UnsupportedLanguage(format!("No adapter found for language: {}", language)).into())
+        }
+    }
+
+    /// [`analyze_file`](Self::analyze_file) for `watch` and `diff`: only the lines `change`
+    /// touched and the definitions around them are analyzed, so the cost of a save follows
+    /// the size of the edit rather than of the file. The patterns the change touched
+    pub async fn analyze_change(&self, file_path: &str, content: &str, change: &ChangedFile) -> Result<Vec<TestablePattern>> {
+        // Edits to a Rust file's inline test module, the tests merged into it among them,
+        // change no code under test
+        let change = match inline_tests_start(content) {
+            Some(start) => change.before_line(content[..start].lines().count() + 1),
+            None => change.clone(),
+        };
+        let patterns = self.analyze_file(file_path, &change.changed_source(content)).await?;
+        Ok(change.touched_patterns(&patterns, content).into_iter().cloned().collect())
+    }
+
+    /// Generate tests for just the functions named in `subjects`, dropping the tests the
+    /// rest of the file would get
+    pub async fn generate_tests_for_subjects(&self, file_path: &str, content: &str, subjects: &[String]) -> Result<TestSuite> {
+        let patterns: Vec<TestablePattern> = self.analyze_file(file_path, content).await?
+            .into_iter()
+            .filter(|pattern| subjects.iter().any(|subject| subject == pattern.subject_name()))
+            .collect();
+        self.generate_tests_for_patterns(file_path, content, patterns).await
+    }
+
+    /// Generate tests for `patterns` alone, already found in `content`, dropping the tests the
+    /// rest of the file would get
+    pub async fn generate_tests_for_patterns(&self, file_path: &str, content: &str, patterns: Vec<TestablePattern>) -> Result<TestSuite> {
+        let subjects: Vec<String> = patterns.iter().map(|pattern| pattern.subject_name().to_string()).collect();
+        let language = self.detect_language(file_path)?;
+        let adapter = self.adapters.get(&language)
+            .ok_or_else(|| UftError::UnsupportedLanguage(format!("No adapter found for language: {}", language)))?;
+
+        let mut suite = adapter.generate_comprehensive_tests(patterns, content).await?;
+        // Cases made from the patterns are theirs; the ones read off the whole file belong to the
+        // longest function name their own name is made of, so the tests of `add_all` aren't taken
+        // for tests of `add`
+        let file_wide = adapter.generate_comprehensive_tests(Vec::new(), content).await?.test_cases;
+        let mut names = subjects.clone();
+        names.extend(adapter.analyze_code(content, file_path).await?.iter().map(|pattern| pattern.subject_name().to_string()));
+        suite.test_cases.retain(|case| {
+            if !file_wide.iter().any(|other| other.name == case.name && other.test_body == case.test_body) {
+                return true;
+            }
+            let tokens = name_tokens(&case.name);
+            let matching: Vec<&String> = names.iter().filter(|name| contains_tokens(&tokens, &name_tokens(name))).collect();
+            let longest = matching.iter().map(|name| name_tokens(name).len()).max().unwrap_or(0);
+            matching.iter().any(|name| name_tokens(name).len() == longest && subjects.contains(name))
+        });
+        if suite.test_code.is_some() {
+            suite.test_code = Some(adapter.generate_test_code(&suite)?);
+        }
+        suite.coverage_target = self.project_config.coverage_target(&language, file_path);
+        Ok(suite)
+    }
+
+    /// The test file for `suite`, rendered for the project config's framework for its language
+    /// when it sets one, then put in the language's template skeleton
+    pub fn render_test_file(&self, suite: &mut TestSuite, patterns: &[TestablePattern], file_path: &str, content: &str) -> Result<String> {
+        let rendered = match self.project_config.framework(&suite.language) {
+            Some(framework) => {
+                suite.framework = framework.to_string();
+                generate_test_file_content_with_framework(suite, framework, patterns, content)?
+            }
+            None => generate_test_file_content(suite)?,
+        };
+        self.project_config.apply_template(&suite.language, file_path, &rendered)
+    }
+
+    /// Generate unit tests for a file and, when the adapter supports it, fold its
+    /// integration tests into the same suite
+    pub async fn generate_combined_tests_for_file(&self, file_path: &str, content: &str) -> Result<TestSuite> {
+        let mut suite = self.generate_tests_for_file(file_path, content).await?;
+        let language = self.detect_language(file_path)?;
+
+        let Some(adapter) = self.adapters.get(&language) else {
+            return Ok(suite);
+        };
+        let Some(integration) = adapter.as_integration() else {
+            return Ok(suite);
+        };
+
+        let patterns = integration.analyze_integration_patterns(content, file_path).await?;
+        if patterns.is_empty() {
+            return Ok(suite);
         }
+
+        suite.merge(integration.generate_integration_tests(patterns).await?);
+        suite.test_code = Some(adapter.generate_test_code(&suite)?);
+        Ok(suite)
     }
 
     pub fn detect_language(&self, file_path: &str) -> Result<String> {
         let extension = std::path::Path::new(file_path)
             .extension()
             .and_then(|s| s.to_str())
-            .ok_or_else(|| anyhow::anyhow!("Could not determine file extension"))?;
+            .ok_or_else(|| UftError::UnsupportedLanguage("Could not determine file extension".to_string()))?;
 
         // Check if we have an adapter registered for this extension
         // We need to check the registered adapters to see what languages we support
         for (language, _) in &self.adapters {
             match (language.as_str(), extension) {
-                ("javascript", "js" | "jsx" | "ts" | "tsx") => return Ok("javascript".to_string()),
+                ("javascript", "js" | "jsx" | "ts" | "tsx" | "graphql" | "gql") => return Ok("javascript".to_string()),
                 ("python", "py") => return Ok("python".to_string()),
                 ("rust", "rs") => return Ok("rust".to_string()),
                 ("go", "go") => return Ok("go".to_string()),
@@ -297,8 +856,34 @@ impl TestOrchestrator {
             }
         }
 
-        Err(anyhow::anyhow!("Unsupported file extension: {}", extension))
+        Err(UftError::UnsupportedLanguage(format!("Unsupported file extension: {}", extension)).into())
+    }
+}
+
+/// The lowercased words of an identifier, split at underscores and case changes, after any
+/// `Class.` or `module::` qualifier: `test_addAll_basic` is `test`, `add`, `all`, `basic`
+fn name_tokens(name: &str) -> Vec<String> {
+    let name = name.rsplit(['.', ':']).next().unwrap_or(name);
+    let mut tokens: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            previous = None;
+            continue;
+        }
+        let boundary = previous.is_none_or(|p| p.is_lowercase() && c.is_uppercase());
+        match tokens.last_mut() {
+            Some(token) if !boundary => token.extend(c.to_lowercase()),
+            _ => tokens.push(c.to_lowercase().collect()),
+        }
+        previous = Some(c);
     }
+    tokens
+}
+
+/// Whether `words` appear in `tokens` one after another
+fn contains_tokens(tokens: &[String], words: &[String]) -> bool {
+    !words.is_empty() && tokens.windows(words.len()).any(|window| window == words)
 }
 
 #[cfg(test)]
@@ -310,6 +895,15 @@ mod tests {
         patterns: Vec<TestablePattern>,
     }
 
+    /// An orchestrator with a mock adapter registered for each of `languages`
+    fn orchestrator_for(languages: &[&str]) -> TestOrchestrator {
+        let mut orchestrator = TestOrchestrator::new();
+        for language in languages {
+            orchestrator.register_adapter(language.to_string(), Box::new(MockAdapter::new(language)));
+        }
+        orchestrator
+    }
+
     impl MockAdapter {
         fn new(language: &str) -> Self {
             Self {
@@ -343,11 +937,32 @@ mod tests {
                     description: "Mock test".to_string(),
                     input: serde_json::json!({}),
                     expected_output: serde_json::json!({}),
+                    test_body: String::new(),
+                    assertions: vec![],
+                    test_category: TestCategory::HappyPath,
                 }).collect(),
                 imports: vec![],
+                test_type: TestType::Unit,
+                setup_requirements: vec![],
+                cleanup_requirements: vec![],
+                coverage_target: 0.0,
+                fixtures: vec![],
+                test_code: None,
             })
         }
 
+        async fn generate_comprehensive_tests(&self, patterns: Vec<TestablePattern>, _source: &str) -> Result<TestSuite> {
+            self.generate_tests(patterns).await
+        }
+
+        fn get_coverage_target(&self) -> f32 {
+            0.0
+        }
+
+        fn generate_test_code(&self, _test_suite: &TestSuite) -> Result<String> {
+            Ok(String::new())
+        }
+
         fn get_language(&self) -> &str {
             &self.language
         }
@@ -428,16 +1043,306 @@ mod tests {
         assert_eq!(orchestrator.adapters.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_orchestrator_reload_adapters() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("javascript".to_string(), Box::new(MockAdapter::new("javascript")));
+        orchestrator.register_adapter("kotlin".to_string(), Box::new(MockAdapter::new("kotlin")));
+        let mut adapters: HashMap<String, Box<dyn TestGenerator + Send + Sync>> = HashMap::new();
+        adapters.insert("javascript".to_string(), Box::new(MockAdapter::new("javascript")));
+        adapters.insert("swift".to_string(), Box::new(MockAdapter::new("swift")));
+
+        let changes = orchestrator.reload_adapters(adapters);
+        assert_eq!(changes, LanguageChanges { added: vec!["swift".to_string()], removed: vec!["kotlin".to_string()] });
+        assert_eq!(changes.to_string(), "added swift; removed kotlin");
+        assert_eq!(orchestrator.adapters.len(), 2);
+        assert_eq!(LanguageChanges::default().to_string(), "no languages added or removed");
+    }
+
+    #[test]
+    fn test_orchestrator_test_file_path() {
+        let mut config: LanguageConfig = serde_json::from_str(include_str!("../../language_configs/kotlin.json")).unwrap();
+        config.test_path = Some("src/test/kotlin/{Stem}Test.kt".to_string());
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("kotlin".to_string(), Box::new(DynamicLanguageAdapter::new(config)));
+        orchestrator.register_adapter("python".to_string(), Box::new(crate::adapters::PythonAdapter::new()));
+
+        let repo = Path::new("/repo");
+        assert_eq!(orchestrator.test_file_path(repo, Path::new("src/main/kotlin/calc.kt"), "kotlin"), PathBuf::from("/repo/src/test/kotlin/CalcTest.kt"));
+        assert_eq!(orchestrator.test_file_path(repo, Path::new("src/calc.py"), "python"), PathBuf::from("/repo/src/tests/test_calc.py"));
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_register_adapter_with_config() {
+        let mut orchestrator = TestOrchestrator::new();
+        let config = AdapterConfig {
+            naming_prefix: Some("it_".to_string()),
+            ..AdapterConfig::default()
+        };
+        orchestrator.register_adapter_with_config(
+            "javascript".to_string(),
+            Box::new(crate::adapters::JavaScriptAdapter::new()),
+            config,
+        );
+
+        let suite = orchestrator
+            .generate_tests_for_file("app.js", "function process(value) { return value; }")
+            .await
+            .unwrap();
+        assert!(!suite.test_cases.is_empty());
+        assert!(suite.test_cases.iter().all(|t| t.name.starts_with("it_")));
+    }
+
+    #[tokio::test]
+    async fn test_generate_tests_applies_project_coverage_target() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(crate::adapters::PythonAdapter::new()));
+        orchestrator.set_project_config(ProjectConfig::parse("[coverage.modules]\n\"src/billing\" = 95\n").unwrap());
+
+        let source = "def charge(amount):\n    return amount\n";
+        let billing = orchestrator.generate_tests_for_file("src/billing/charge.py", source).await.unwrap();
+        assert_eq!(billing.coverage_target, 95.0);
+
+        let other = orchestrator.generate_tests_for_file("src/users.py", source).await.unwrap();
+        assert_eq!(other.coverage_target, CoverageStandards::get_coverage_target("python"));
+    }
+
+    #[tokio::test]
+    async fn test_render_test_file_uses_configured_framework() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(crate::adapters::PythonAdapter::new()));
+        let source = "def add(a, b):\n    return a + b\n";
+
+        let (mut suite, patterns) = orchestrator.generate_tests_with_patterns("calc.py", source).await.unwrap();
+        let pytest = orchestrator.render_test_file(&mut suite, &patterns, "calc.py", source).unwrap();
+        assert!(!pytest.contains("unittest.TestCase"));
+
+        orchestrator.set_project_config(ProjectConfig::parse("[frameworks]\npython = \"unittest\"\n").unwrap());
+        let (mut suite, patterns) = orchestrator.generate_tests_with_patterns("calc.py", source).await.unwrap();
+        let unittest = orchestrator.render_test_file(&mut suite, &patterns, "calc.py", source).unwrap();
+        assert!(unittest.contains("unittest.TestCase"));
+        assert_eq!(suite.framework, "unittest");
+    }
+
+    #[tokio::test]
+    async fn test_generate_tests_skips_covered_functions() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(crate::adapters::PythonAdapter::new()));
+        orchestrator.set_coverage_report(CoverageReport::parse_lcov("SF:src/calc.py\nDA:1,1\nDA:2,1\nDA:4,1\nDA:5,0\nend_of_record\n"));
+
+        let source = "def add(a, b):\n    return a + b\n\ndef negate(x):\n    return -x\n";
+        let suite = orchestrator.generate_tests_for_file("src/calc.py", source).await.unwrap();
+        assert!(!suite.test_cases.is_empty());
+        assert!(suite.test_cases.iter().all(|t| !t.name.contains("add")));
+
+        let covered = "def add(a, b):\n    return a + b\n";
+        orchestrator.set_coverage_report(CoverageReport::parse_lcov("SF:src/calc.py\nDA:1,1\nDA:2,1\nend_of_record\n"));
+        assert!(orchestrator.generate_tests_for_file("src/calc.py", covered).await.unwrap().test_cases.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_project_config_filters_analysis() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(crate::adapters::PythonAdapter::new()));
+        let source = "def add(a, b):\n    return a + b\n";
+        assert!(!orchestrator.analyze_file("src/calc.py", source).await.unwrap().is_empty());
+
+        orchestrator.set_project_config(ProjectConfig::parse("ignore = [\"src/generated/**\"]\n").unwrap());
+        assert!(orchestrator.analyze_file("src/generated/calc.py", source).await.unwrap().is_empty());
+        assert!(!orchestrator.analyze_file("src/calc.py", source).await.unwrap().is_empty());
+
+        orchestrator.set_project_config(ProjectConfig::parse("confidence_threshold = 1.01\n").unwrap());
+        assert!(orchestrator.analyze_file("src/calc.py", source).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_generate_tests_for_a_name_prefixing_another() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(crate::adapters::PythonAdapter::new()));
+        orchestrator.register_adapter("javascript".to_string(), Box::new(crate::adapters::JavaScriptAdapter::new()));
+
+        let source = "def add(a, b):\n    return a + b\n\ndef add_all(items):\n    return sum(items)\n\ndef padding(text):\n    return text + ' '\n";
+        let suite = orchestrator.generate_tests_for_subjects("calc.py", source, &["add".to_string()]).await.unwrap();
+        assert!(!suite.test_cases.is_empty());
+        assert!(suite.test_cases.iter().all(|t| !t.name.contains("add_all") && !t.name.contains("padding")), "{:?}", suite.test_cases.iter().map(|t| &t.name).collect::<Vec<_>>());
+
+        let source = "function add(a, b) {\n  return a + b;\n}\n\nfunction addAll(items) {\n  return items.length;\n}\n";
+        let suite = orchestrator.generate_tests_for_subjects("calc.js", source, &["add".to_string()]).await.unwrap();
+        assert!(!suite.test_cases.is_empty());
+        assert!(suite.test_cases.iter().all(|t| !t.name.to_lowercase().contains("addall")));
+        let suite = orchestrator.generate_tests_for_subjects("calc.js", source, &["addAll".to_string()]).await.unwrap();
+        assert!(!suite.test_cases.is_empty());
+    }
+
+    #[test]
+    fn test_name_tokens() {
+        assert_eq!(name_tokens("test_addAll_basic"), ["test", "add", "all", "basic"]);
+        assert_eq!(name_tokens("Cart.total"), ["total"]);
+        assert_eq!(name_tokens("HTTPServer"), ["httpserver"]);
+        assert!(contains_tokens(&name_tokens("should_throw_Error_from_add"), &name_tokens("add")));
+        assert!(!contains_tokens(&name_tokens("test_padding_basic"), &name_tokens("add")));
+    }
+
+    #[tokio::test]
+    async fn test_generate_tests_for_subjects() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("javascript".to_string(), Box::new(crate::adapters::JavaScriptAdapter::new()));
+
+        let source = "function add(a, b) {\n  return a + b;\n}\n\nfunction negate(x) {\n  return -x;\n}\n";
+        let suite = orchestrator.generate_tests_for_subjects("src/calc.js", source, &["negate".to_string()]).await.unwrap();
+        assert!(!suite.test_cases.is_empty());
+        assert!(suite.test_cases.iter().all(|t| t.name.to_lowercase().contains("negate")));
+        assert!(!suite.test_code.unwrap().contains("add("));
+    }
+
+    #[tokio::test]
+    async fn test_analyze_change_only_reads_the_changed_definitions() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(crate::adapters::PythonAdapter::new()));
+
+        let old = "def add(a, b):\n    return a + b\n\ndef sub(a, b):\n    return a - b\n";
+        let new = "def add(a, b):\n    return a + b\n\ndef sub(a, b):\n    return b - a\n\ndef mul(a, b):\n    return a * b\n";
+        let change = ChangedFile::between(std::path::PathBuf::from("calc.py"), old, new).unwrap();
+        assert_eq!(change.changed_source(new), "              \n                \n\ndef sub(a, b):\n    return b - a\n\ndef mul(a, b):\n    return a * b\n");
+
+        let patterns = orchestrator.analyze_change("calc.py", new, &change).await.unwrap();
+        let names: Vec<&str> = patterns.iter().map(|pattern| pattern.subject_name()).collect();
+        assert_eq!(names, ["sub", "mul"]);
+        let suite = orchestrator.generate_tests_for_patterns("calc.py", new, patterns).await.unwrap();
+        assert!(suite.test_cases.iter().all(|t| !t.name.contains("add")));
+    }
+
+    #[tokio::test]
+    async fn test_analyze_change_keeps_the_impl_of_a_changed_rust_method() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("rust".to_string(), Box::new(crate::adapters::RustAdapter::new()));
+
+        let old = "pub fn helper(x: i32) -> i32 {\n    x + 1\n}\n\npub struct Cart {\n    rate: i32,\n}\n\nimpl Cart {\n    pub fn new(rate: i32) -> Self {\n        Cart { rate }\n    }\n\n    pub fn total(&self, qty: i32) -> i32 {\n        self.rate * qty\n    }\n}\n";
+        let new = old.replace("self.rate * qty", "self.rate * qty * 2");
+        let change = ChangedFile::between(std::path::PathBuf::from("src/cart.rs"), old, &new).unwrap();
+        assert_eq!(change.changed_source(&new).len(), new.len());
+
+        let patterns = orchestrator.analyze_change("src/cart.rs", &new, &change).await.unwrap();
+        let suite = orchestrator.generate_tests_for_patterns("src/cart.rs", &new, patterns).await.unwrap();
+        let code = suite.test_code.unwrap();
+        assert!(code.contains("instance.total(42)"), "{}", code);
+        assert!(!code.contains("= total(42)"));
+
+        // Tests merged into the file's own test module change none of its code
+        let with_tests = format!("{}\n#[cfg(test)]\nmod tests {{\n    use super::*;\n}}\n", new);
+        let merged = with_tests.replace("    use super::*;\n", "    use super::*;\n\n    #[test]\n    fn test_total() {\n        helper(1);\n    }\n");
+        let change = ChangedFile::between(std::path::PathBuf::from("src/cart.rs"), &with_tests, &merged).unwrap();
+        assert!(orchestrator.analyze_change("src/cart.rs", &merged, &change).await.unwrap().is_empty());
+    }
+
+    fn suite_with_cases(test_type: TestType, names: &[&str], imports: &[&str]) -> TestSuite {
+        TestSuite {
+            name: "Suite".to_string(),
+            language: "javascript".to_string(),
+            framework: "jest".to_string(),
+            test_cases: names.iter().map(|name| TestCase {
+                id: name.to_string(),
+                name: name.to_string(),
+                description: String::new(),
+                input: serde_json::json!({}),
+                expected_output: serde_json::json!(null),
+                test_body: String::new(),
+                assertions: vec![],
+                test_category: TestCategory::HappyPath,
+            }).collect(),
+            imports: imports.iter().map(|i| i.to_string()).collect(),
+            test_type,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 80.0,
+            fixtures: vec![],
+            test_code: Some("stale".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_suite_merge_dedupes_imports_and_names() {
+        let mut unit = suite_with_cases(TestType::Unit, &["should_fetch", "should_save"], &["import a", "import b"]);
+        let mut integration = suite_with_cases(TestType::Integration, &["should_fetch", "should_fetch_2"], &["import b", "import c"]);
+        integration.setup_requirements = vec!["Start test server".to_string()];
+        let cart = Fixture { name: "cart".to_string(), code: "  let cart;\n".to_string() };
+        unit.fixtures = vec![cart.clone()];
+        integration.fixtures = vec![cart.clone(), Fixture { name: "server".to_string(), code: "  let server;\n".to_string() }];
+
+        unit.merge(integration);
+
+        assert_eq!(unit.imports, vec!["import a", "import b", "import c"]);
+        let names: Vec<&str> = unit.test_cases.iter().map(|tc| tc.name.as_str()).collect();
+        assert_eq!(names, vec!["should_fetch", "should_save", "should_fetch_2", "should_fetch_2_2"]);
+        assert_eq!(unit.setup_requirements, vec!["Start test server"]);
+        assert_eq!(unit.fixtures.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(), vec!["cart", "server"]);
+        assert!(matches!(unit.test_type, TestType::Unit));
+        assert!(unit.test_code.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_generate_combined_tests_for_file() {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("javascript".to_string(), Box::new(crate::adapters::JavaScriptAdapter::new()));
+
+        let source = "function loadUsers() { return fetch('/api/users'); }";
+        let unit = orchestrator.generate_tests_for_file("users.js", source).await.unwrap();
+        let combined = orchestrator.generate_combined_tests_for_file("users.js", source).await.unwrap();
+
+        assert!(combined.test_cases.len() > unit.test_cases.len());
+        assert!(combined.test_code.unwrap().contains("require('supertest')"));
+    }
+
+    #[test]
+    fn test_estimated_coverage() {
+        let pattern = |name: &str| TestablePattern {
+            id: name.to_string(),
+            pattern_type: PatternType::Function(FunctionPattern {
+                name: name.to_string(),
+                parameters: vec![],
+                return_type: None,
+            }),
+            location: SourceLocation { file: "app.js".to_string(), line: 1, column: 1 },
+            context: Context { function_name: Some(name.to_string()), class_name: None, module_name: None },
+            confidence: 0.9,
+        };
+        let suite = TestSuite {
+            name: "Suite".to_string(),
+            language: "javascript".to_string(),
+            framework: "jest".to_string(),
+            test_cases: vec![TestCase {
+                id: "1".to_string(),
+                name: "should_execute_add".to_string(),
+                description: String::new(),
+                input: serde_json::json!({}),
+                expected_output: serde_json::json!(null),
+                test_body: String::new(),
+                assertions: vec![],
+                test_category: TestCategory::HappyPath,
+            }],
+            imports: vec![],
+            test_type: TestType::Unit,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 80.0,
+            fixtures: vec![],
+            test_code: None,
+        };
+
+        assert_eq!(suite.estimated_coverage(&[pattern("add"), pattern("remove")]), 50.0);
+        assert_eq!(suite.estimated_coverage(&[]), 100.0);
+    }
+
     #[tokio::test]
     async fn test_detect_language_js() {
-        let orchestrator = TestOrchestrator::new();
+        let orchestrator = orchestrator_for(&["javascript"]);
         assert_eq!(orchestrator.detect_language("test.js").unwrap(), "javascript");
         assert_eq!(orchestrator.detect_language("test.jsx").unwrap(), "javascript");
     }
 
     #[tokio::test]
     async fn test_detect_language_ts() {
-        let orchestrator = TestOrchestrator::new();
+        let orchestrator = orchestrator_for(&["javascript"]);
         // TypeScript files now use the JavaScript adapter
         assert_eq!(orchestrator.detect_language("test.ts").unwrap(), "javascript");
         assert_eq!(orchestrator.detect_language("test.tsx").unwrap(), "javascript");
@@ -445,32 +1350,33 @@ mod tests {
 
     #[tokio::test]
     async fn test_detect_language_python() {
-        let orchestrator = TestOrchestrator::new();
+        let orchestrator = orchestrator_for(&["python"]);
         assert_eq!(orchestrator.detect_language("test.py").unwrap(), "python");
     }
 
     #[tokio::test]
     async fn test_detect_language_rust() {
-        let orchestrator = TestOrchestrator::new();
+        let orchestrator = orchestrator_for(&["rust"]);
         assert_eq!(orchestrator.detect_language("test.rs").unwrap(), "rust");
     }
 
     #[tokio::test]
     async fn test_detect_language_go() {
-        let orchestrator = TestOrchestrator::new();
+        let orchestrator = orchestrator_for(&["go"]);
         assert_eq!(orchestrator.detect_language("test.go").unwrap(), "go");
     }
 
     #[tokio::test]
     async fn test_detect_language_java() {
-        let orchestrator = TestOrchestrator::new();
+        let orchestrator = orchestrator_for(&["java"]);
         assert_eq!(orchestrator.detect_language("Test.java").unwrap(), "java");
     }
 
     #[tokio::test]
     async fn test_detect_language_unsupported() {
         let orchestrator = TestOrchestrator::new();
-        assert!(orchestrator.detect_language("test.cpp").is_err());
+        let error = orchestrator.detect_language("test.cpp").unwrap_err();
+        assert_eq!(UftError::exit_code_of(&error), 2);
     }
 
     #[tokio::test]
@@ -511,12 +1417,40 @@ mod tests {
         assert_eq!(patterns[0].id, "test-pattern");
     }
 
+    #[tokio::test]
+    async fn test_analysis_report_serializes_patterns() {
+        let mut orchestrator = TestOrchestrator::new();
+        let pattern = TestablePattern {
+            id: "add".to_string(),
+            pattern_type: PatternType::Function(FunctionPattern {
+                name: "add".to_string(),
+                parameters: vec![],
+                return_type: None,
+            }),
+            location: SourceLocation { file: "app.js".to_string(), line: 3, column: 1 },
+            context: Context { function_name: Some("add".to_string()), class_name: None, module_name: None },
+            confidence: 0.9,
+        };
+        orchestrator.register_adapter("javascript".to_string(), Box::new(MockAdapter::with_patterns("javascript", vec![pattern])));
+
+        let mut report = orchestrator.analysis_report("app.js", "function add() {}").await.unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["file"], "app.js");
+        assert_eq!(json["language"], "javascript");
+        assert_eq!(json["patterns"][0]["location"]["line"], 3);
+
+        report.check_tests(PathBuf::from("__tests__/app.test.js"), "test('adds', () => expect(add(1, 2)).toBe(3));");
+        assert!(report.untested.is_empty());
+        report.check_tests(PathBuf::from("__tests__/app.test.js"), "");
+        assert_eq!(report.untested, vec!["add"]);
+    }
+
     #[tokio::test]
     async fn test_analyze_file_no_adapter() {
         let orchestrator = TestOrchestrator::new();
         let result = orchestrator.analyze_file("test.js", "code").await;
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("No adapter found"));
+        assert!(result.unwrap_err().to_string().contains("Unsupported file extension: js"));
     }
 
     #[tokio::test]
@@ -556,7 +1490,7 @@ mod tests {
         let orchestrator = TestOrchestrator::new();
         let result = orchestrator.generate_tests_for_file("test.js", "code").await;
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("No adapter found"));
+        assert!(result.unwrap_err().to_string().contains("Unsupported file extension: js"));
     }
 
     #[test]
@@ -567,6 +1501,9 @@ mod tests {
             description: "Test a function".to_string(),
             input: serde_json::json!({"param": "value"}),
             expected_output: serde_json::json!({"result": "success"}),
+            test_body: String::new(),
+            assertions: vec![],
+            test_category: TestCategory::HappyPath,
         };
         assert_eq!(test_case.id, "test-1");
         assert_eq!(test_case.name, "test_function");
@@ -581,6 +1518,12 @@ mod tests {
             framework: "jest".to_string(),
             test_cases: vec![],
             imports: vec!["import { expect } from 'jest';".to_string()],
+            test_type: TestType::Unit,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 80.0,
+            fixtures: vec![],
+            test_code: None,
         };
         assert_eq!(test_suite.name, "Test Suite");
         assert_eq!(test_suite.language, "javascript");
@@ -0,0 +1,495 @@
+use anyhow::{anyhow, Context as _, Result};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+
+use super::{ApiEndpoint, HttpMethod};
+
+/// Nesting of `$ref`s followed before a schema is left open, which also stops recursive schemas
+const MAX_REF_DEPTH: usize = 5;
+
+/// Keywords that only document a schema and are dropped from the ones embedded in tests
+const DOC_KEYWORDS: [&str; 3] = ["description", "xml", "externalDocs"];
+
+/// Keywords whose values are data rather than schemas
+const VALUE_KEYWORDS: [&str; 5] = ["example", "examples", "default", "enum", "const"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiParameter {
+    pub name: String,
+    pub location: ParameterLocation,
+    pub required: bool,
+    /// Schema with `$ref`s resolved
+    pub schema: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    /// JSON body schema with `$ref`s resolved, when documented
+    pub schema: Option<Value>,
+}
+
+/// One operation of an OpenAPI document; the endpoint lists its required parameters
+#[derive(Debug, Clone)]
+pub struct ApiOperation {
+    pub endpoint: ApiEndpoint,
+    pub operation_id: Option<String>,
+    pub parameters: Vec<ApiParameter>,
+    /// JSON request body schema with `$ref`s resolved
+    pub request_body: Option<Value>,
+    pub body_required: bool,
+    /// Documented responses with a numeric status, in status order
+    pub responses: Vec<ApiResponse>,
+}
+
+/// An OpenAPI 3 or Swagger 2 document, in YAML or JSON
+#[derive(Debug, Clone)]
+pub struct OpenApiSpec {
+    pub title: Option<String>,
+    /// First server URL (`servers`, or `schemes`/`host`/`basePath` in Swagger 2)
+    pub base_url: Option<String>,
+    pub operations: Vec<ApiOperation>,
+}
+
+impl OpenApiSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read OpenAPI document {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid OpenAPI document {}", path.display()))
+    }
+
+    /// Parse a document; JSON is read as the YAML subset it is
+    pub fn parse(content: &str) -> Result<Self> {
+        let document: Value = serde_yaml::from_str(content)?;
+        if document.get("openapi").is_none() && document.get("swagger").is_none() {
+            return Err(anyhow!("Missing `openapi` or `swagger` version field"));
+        }
+        let paths = document.get("paths")
+            .and_then(Value::as_object)
+            .ok_or_else(|| anyhow!("Missing `paths`"))?;
+
+        let mut operations = Vec::new();
+        for (path, item) in paths {
+            let shared = item.get("parameters").and_then(Value::as_array).cloned().unwrap_or_default();
+            for (key, method) in [("get", HttpMethod::Get), ("post", HttpMethod::Post), ("put", HttpMethod::Put), ("patch", HttpMethod::Patch), ("delete", HttpMethod::Delete)] {
+                if let Some(operation) = item.get(key) {
+                    operations.push(parse_operation(&document, path, method, operation, &shared));
+                }
+            }
+        }
+
+        Ok(Self {
+            title: document.pointer("/info/title").and_then(Value::as_str).map(str::to_string),
+            base_url: base_url(&document),
+            operations,
+        })
+    }
+}
+
+impl ApiOperation {
+    /// Identifier for the operation's tests: the snake-cased `operationId`, or the method and path
+    pub fn name(&self) -> String {
+        let raw = match &self.operation_id {
+            Some(id) => {
+                let mut snake = String::new();
+                for (i, c) in id.chars().enumerate() {
+                    if c.is_uppercase() && i > 0 && !snake.ends_with('_') {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                snake
+            }
+            None => format!("{}_{}", self.endpoint.method.to_string(), self.endpoint.path).to_lowercase(),
+        };
+        Regex::new(r"[\W_]+").unwrap().replace_all(&raw, "_").trim_matches('_').to_string()
+    }
+
+    /// Path with each path parameter replaced by a sample value
+    pub fn sample_path(&self) -> String {
+        self.filled_path(|param| match sample_value(&param.schema) {
+            Value::String(s) => s,
+            other => other.to_string(),
+        })
+    }
+
+    /// Path pointing at a resource that shouldn't exist
+    pub fn missing_resource_path(&self) -> String {
+        self.filled_path(|param| match param.schema.get("type").and_then(Value::as_str) {
+            Some("integer") | Some("number") => "999999".to_string(),
+            _ => "does-not-exist".to_string(),
+        })
+    }
+
+    fn filled_path(&self, value: impl Fn(&ApiParameter) -> String) -> String {
+        let mut path = self.endpoint.path.clone();
+        for param in self.parameters.iter().filter(|p| p.location == ParameterLocation::Path) {
+            path = path.replace(&format!("{{{}}}", param.name), &value(param));
+        }
+        path
+    }
+
+    /// Sample values for the required parameters at `location`
+    pub fn required_values(&self, location: ParameterLocation) -> Map<String, Value> {
+        self.parameters.iter()
+            .filter(|p| p.location == location && p.required)
+            .map(|p| (p.name.clone(), sample_value(&p.schema)))
+            .collect()
+    }
+
+    /// Documented 2xx responses
+    pub fn success_responses(&self) -> Vec<&ApiResponse> {
+        self.responses.iter().filter(|r| (200..300).contains(&r.status)).collect()
+    }
+
+    pub fn documents_status(&self, status: u16) -> bool {
+        self.responses.iter().any(|r| r.status == status)
+    }
+
+    /// Whether a request can leave out something the operation requires
+    pub fn has_required_input(&self) -> bool {
+        self.body_required || self.parameters.iter().any(|p| p.required && p.location != ParameterLocation::Path)
+    }
+}
+
+fn parse_operation(document: &Value, path: &str, method: HttpMethod, operation: &Value, shared: &[Value]) -> ApiOperation {
+    let mut parameters: Vec<ApiParameter> = Vec::new();
+    let mut request_body = None;
+    let mut body_required = false;
+
+    let declared = operation.get("parameters").and_then(Value::as_array).cloned().unwrap_or_default();
+    // Operation parameters override path-level ones with the same name and location
+    for raw in declared.iter().chain(shared) {
+        let param = resolve(document, raw, 0);
+        let Some(name) = param.get("name").and_then(Value::as_str) else { continue };
+        let location = match param.get("in").and_then(Value::as_str) {
+            Some("path") => ParameterLocation::Path,
+            Some("query") => ParameterLocation::Query,
+            Some("header") => ParameterLocation::Header,
+            Some("body") => {
+                if request_body.is_none() {
+                    request_body = param.get("schema").map(schema_for_test);
+                    body_required = param.get("required").and_then(Value::as_bool).unwrap_or(false);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if parameters.iter().any(|p| p.name == name && p.location == location) {
+            continue;
+        }
+
+        // Swagger 2 declares the type of non-body parameters on the parameter itself
+        let schema = match param.get("schema") {
+            Some(schema) => schema_for_test(schema),
+            None => Value::Object(param.as_object().into_iter().flatten()
+                .filter(|(key, _)| matches!(key.as_str(), "type" | "format" | "enum" | "items" | "minimum" | "maximum" | "default"))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()),
+        };
+        parameters.push(ApiParameter {
+            name: name.to_string(),
+            location,
+            required: location == ParameterLocation::Path || param.get("required").and_then(Value::as_bool).unwrap_or(false),
+            schema,
+        });
+    }
+
+    if let Some(body) = operation.get("requestBody").map(|b| resolve(document, b, 0)) {
+        request_body = json_content(&body).and_then(|c| c.get("schema")).map(schema_for_test);
+        body_required = body.get("required").and_then(Value::as_bool).unwrap_or(false);
+    }
+
+    let mut responses: Vec<ApiResponse> = operation.get("responses")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(status, response)| {
+            let status = status.parse().ok()?;
+            let response = resolve(document, response, 0);
+            let schema = json_content(&response).and_then(|c| c.get("schema"))
+                .or_else(|| response.get("schema"))
+                .map(schema_for_test);
+            Some(ApiResponse { status, schema })
+        })
+        .collect();
+    responses.sort_by_key(|r| r.status);
+
+    ApiOperation {
+        endpoint: ApiEndpoint {
+            method,
+            path: path.to_string(),
+            parameters: parameters.iter().filter(|p| p.required).map(|p| p.name.clone()).collect(),
+        },
+        operation_id: operation.get("operationId").and_then(Value::as_str).map(str::to_string),
+        parameters,
+        request_body,
+        body_required,
+        responses,
+    }
+}
+
+/// The `application/json` (or first JSON-like) media type of a body or response
+fn json_content(body: &Value) -> Option<&Value> {
+    let content = body.get("content")?.as_object()?;
+    content.get("application/json")
+        .or_else(|| content.iter().find(|(media, _)| media.contains("json")).map(|(_, c)| c))
+}
+
+/// Replace local `$ref`s with what they point at
+fn resolve(document: &Value, value: &Value, depth: usize) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                return match reference.strip_prefix('#').and_then(|pointer| document.pointer(pointer)) {
+                    Some(target) if depth < MAX_REF_DEPTH => resolve(document, &schema_for_test(target), depth + 1),
+                    _ => json!({}),
+                };
+            }
+            Value::Object(map.iter().map(|(k, v)| (k.clone(), resolve(document, v, depth))).collect())
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| resolve(document, v, depth)).collect()),
+        other => other.clone(),
+    }
+}
+
+/// JSON Schema accepted by validators: documentation keywords dropped and OpenAPI 3.0's
+/// `nullable` turned into a `null` type
+fn schema_for_test(schema: &Value) -> Value {
+    match schema {
+        Value::Object(map) => {
+            let mut cleaned: Map<String, Value> = map.iter()
+                .filter(|(key, _)| !DOC_KEYWORDS.contains(&key.as_str()) && key.as_str() != "nullable")
+                .map(|(key, value)| {
+                    // Property names are data, not keywords
+                    let value = if key == "properties" {
+                        Value::Object(value.as_object().into_iter().flatten().map(|(k, v)| (k.clone(), schema_for_test(v))).collect())
+                    } else if VALUE_KEYWORDS.contains(&key.as_str()) {
+                        value.clone()
+                    } else {
+                        schema_for_test(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect();
+            if map.get("nullable") == Some(&Value::Bool(true)) {
+                if let Some(Value::String(ty)) = cleaned.get("type").cloned() {
+                    cleaned.insert("type".to_string(), json!([ty, "null"]));
+                }
+            }
+            Value::Object(cleaned)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(schema_for_test).collect()),
+        other => other.clone(),
+    }
+}
+
+/// A value satisfying a resolved schema: its example, default or first enum value when given,
+/// otherwise built from its type with objects filled in for their required properties
+pub fn sample_value(schema: &Value) -> Value {
+    if let Some(value) = schema.get("example").or_else(|| schema.get("default")).or_else(|| schema.get("const")) {
+        return value.clone();
+    }
+    if let Some(first) = schema.get("enum").and_then(Value::as_array).and_then(|e| e.first()) {
+        return first.clone();
+    }
+    if let Some(first) = ["oneOf", "anyOf"].iter().find_map(|k| schema.get(*k).and_then(Value::as_array).and_then(|s| s.first())) {
+        return sample_value(first);
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in parts {
+            if let Value::Object(fields) = sample_value(part) {
+                merged.extend(fields);
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    let ty = match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).find(|t| *t != "null"),
+        Some(ty) => ty.as_str(),
+        None if schema.get("properties").is_some() => Some("object"),
+        None => None,
+    };
+    match ty {
+        Some("string") => match schema.get("format").and_then(Value::as_str) {
+            Some("date-time") => json!("2024-01-01T00:00:00Z"),
+            Some("date") => json!("2024-01-01"),
+            Some("email") => json!("test@example.com"),
+            Some("uuid") => json!("123e4567-e89b-12d3-a456-426614174000"),
+            Some("uri") | Some("url") => json!("https://example.com"),
+            _ => {
+                let min = schema.get("minLength").and_then(Value::as_u64).unwrap_or(0) as usize;
+                json!(format!("{:x<width$}", "test", width = min))
+            }
+        },
+        Some("integer") => json!(schema.get("minimum").and_then(Value::as_i64).unwrap_or(1)),
+        Some("number") => json!(schema.get("minimum").and_then(Value::as_f64).unwrap_or(1.5)),
+        Some("boolean") => json!(true),
+        Some("array") => json!([sample_value(schema.get("items").unwrap_or(&json!({})))]),
+        Some("object") => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let required: Vec<&str> = schema.get("required").and_then(Value::as_array)
+                .map(|names| names.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            Value::Object(properties.into_iter().flatten()
+                .filter(|(name, _)| required.is_empty() || required.contains(&name.as_str()))
+                .map(|(name, property)| (name.clone(), sample_value(property)))
+                .collect())
+        }
+        _ => json!("test"),
+    }
+}
+
+fn base_url(document: &Value) -> Option<String> {
+    if let Some(url) = document.pointer("/servers/0/url").and_then(Value::as_str) {
+        return Some(url.trim_end_matches('/').to_string());
+    }
+    let host = document.get("host").and_then(Value::as_str)?;
+    let scheme = document.pointer("/schemes/0").and_then(Value::as_str).unwrap_or("https");
+    let base_path = document.get("basePath").and_then(Value::as_str).unwrap_or("");
+    Some(format!("{}://{}{}", scheme, host, base_path.trim_end_matches('/')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Pet Store
+servers:
+  - url: https://petstore.example.com/v1/
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      parameters:
+        - name: limit
+          in: query
+          required: true
+          schema:
+            type: integer
+            minimum: 1
+      responses:
+        "200":
+          description: A page of pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: "#/components/schemas/Pet"
+    post:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/Pet"
+      responses:
+        "201":
+          description: Created
+        "422":
+          description: Invalid pet
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema:
+          type: integer
+    delete:
+      responses:
+        "204":
+          description: Deleted
+        "404":
+          description: Not found
+        default:
+          description: Error
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+          description: Name the pet answers to
+          example: Rex
+        tag:
+          type: string
+          nullable: true
+        owner:
+          $ref: "#/components/schemas/Pet"
+"##;
+
+    #[test]
+    fn test_parse_openapi_document() {
+        let spec = OpenApiSpec::parse(PETSTORE).unwrap();
+        assert_eq!(spec.title.as_deref(), Some("Pet Store"));
+        assert_eq!(spec.base_url.as_deref(), Some("https://petstore.example.com/v1"));
+        assert_eq!(spec.operations.len(), 3);
+
+        let list = &spec.operations[0];
+        assert_eq!(list.name(), "list_pets");
+        assert_eq!(list.endpoint.parameters, vec!["limit".to_string()]);
+        assert_eq!(list.required_values(ParameterLocation::Query).get("limit"), Some(&json!(1)));
+        let schema = list.responses[0].schema.as_ref().unwrap();
+        assert_eq!(schema["items"]["properties"]["tag"]["type"], json!(["string", "null"]));
+        assert!(schema["items"]["properties"]["name"].get("description").is_none());
+
+        let create = &spec.operations[1];
+        assert_eq!(create.name(), "post_pets");
+        assert!(create.body_required && create.has_required_input());
+        assert_eq!(sample_value(create.request_body.as_ref().unwrap()), json!({"name": "Rex"}));
+        assert_eq!(create.responses.iter().map(|r| r.status).collect::<Vec<_>>(), vec![201, 422]);
+
+        let delete = &spec.operations[2];
+        assert_eq!(delete.sample_path(), "/pets/1");
+        assert_eq!(delete.missing_resource_path(), "/pets/999999");
+        assert!(delete.documents_status(404) && !delete.has_required_input());
+    }
+
+    #[test]
+    fn test_parse_swagger_document() {
+        let swagger = r##"{
+  "swagger": "2.0",
+  "host": "api.example.com",
+  "basePath": "/v2",
+  "schemes": ["http"],
+  "paths": {
+    "/users/{id}": {
+      "put": {
+        "parameters": [
+          {"name": "id", "in": "path", "required": true, "type": "string", "format": "uuid"},
+          {"name": "user", "in": "body", "required": true, "schema": {"$ref": "#/definitions/User"}}
+        ],
+        "responses": {"200": {"description": "OK", "schema": {"$ref": "#/definitions/User"}}}
+      }
+    }
+  },
+  "definitions": {"User": {"type": "object", "properties": {"email": {"type": "string", "format": "email"}}}}
+}"##;
+        let spec = OpenApiSpec::parse(swagger).unwrap();
+        assert_eq!(spec.base_url.as_deref(), Some("http://api.example.com/v2"));
+
+        let update = &spec.operations[0];
+        assert_eq!(update.sample_path(), "/users/123e4567-e89b-12d3-a456-426614174000");
+        assert_eq!(sample_value(update.request_body.as_ref().unwrap()), json!({"email": "test@example.com"}));
+        assert!(update.responses[0].schema.is_some());
+
+        assert!(OpenApiSpec::parse("title: not a spec\n").is_err());
+    }
+}
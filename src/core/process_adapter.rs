@@ -0,0 +1,217 @@
+//! Language adapters run as an external process, so they can be written in any language. A
+//! language config names the executable and its arguments in `command`; uft starts it in the
+//! config's directory on the first file of its language and keeps it running.
+//!
+//! uft writes JSON-RPC 2.0 requests to its stdin and reads the responses from its stdout, one
+//! JSON object per line, answering each request before the next is sent:
+//! - `analyze`, with `{"source", "file_path"}`: the [`TestablePattern`]s found
+//! - `generateTests`, with `{"patterns", "source"}`: the `{"test_cases", "imports", "test_code"}`
+//!   generated, the last two optional
+//!
+//! An `error` response fails the file. The process's stderr goes to uft's, and it's killed when
+//! uft exits.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::Mutex;
+
+use super::{DynamicLanguageAdapter, GeneratedTests, LanguageConfig, TestGenerator, TestSuite, TestablePattern, UftError};
+
+#[derive(Serialize)]
+struct Request<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    #[serde(default)]
+    result: Value,
+    error: Option<ResponseError>,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    #[serde(default)]
+    code: i64,
+    message: String,
+}
+
+/// The running adapter process
+struct Connection {
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+/// A language whose patterns and tests come from an external process
+pub struct ProcessLanguageAdapter {
+    config: LanguageConfig,
+    command: Vec<String>,
+    /// Where the process runs: the config file's directory
+    directory: PathBuf,
+    connection: Mutex<Option<Connection>>,
+}
+
+impl ProcessLanguageAdapter {
+    /// An adapter running `command` in `directory` once it's first needed
+    pub fn new(config: LanguageConfig, command: Vec<String>, directory: &Path) -> Self {
+        Self { config, command, directory: directory.to_path_buf(), connection: Mutex::new(None) }
+    }
+
+    fn start(&self) -> Result<Connection> {
+        let (program, args) = self.command.split_first()
+            .ok_or_else(|| UftError::Config(format!("The {} adapter's `command` is empty", self.config.name)))?;
+        let mut child = tokio::process::Command::new(program)
+            .args(args)
+            .current_dir(&self.directory)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| UftError::Config(format!("Can't start the {} adapter `{}`: {}", self.config.name, self.command.join(" "), e)))?;
+        let stdin = child.stdin.take().ok_or_else(|| anyhow!("The {} adapter has no stdin", self.config.name))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow!("The {} adapter has no stdout", self.config.name))?;
+        Ok(Connection { _child: child, stdin, stdout: BufReader::new(stdout), next_id: 1 })
+    }
+
+    /// Send a `method` request and wait for its result, starting the process if it isn't
+    /// running. A process that exits is started again on the next request
+    async fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let mut connection = self.connection.lock().await;
+        if connection.is_none() {
+            *connection = Some(self.start()?);
+        }
+        let response = match exchange(connection.as_mut().expect("started above"), method, params).await {
+            Ok(response) => response,
+            Err(e) => {
+                *connection = None;
+                return Err(anyhow!("The {} adapter's {} failed: {}", self.config.name, method, e));
+            }
+        };
+        if let Some(error) = response.error {
+            return Err(UftError::Generation(format!("The {} adapter's {} failed ({}): {}", self.config.name, method, error.code, error.message)).into());
+        }
+        serde_json::from_value(response.result)
+            .map_err(|e| anyhow!("The {} adapter's {} returned unexpected JSON: {}", self.config.name, method, e))
+    }
+}
+
+/// Write one request and read lines until its response
+async fn exchange(connection: &mut Connection, method: &str, params: Value) -> Result<Response> {
+    let id = connection.next_id;
+    connection.next_id += 1;
+    let mut line = serde_json::to_string(&Request { jsonrpc: "2.0", id, method, params })?;
+    line.push('\n');
+    connection.stdin.write_all(line.as_bytes()).await?;
+    connection.stdin.flush().await?;
+
+    loop {
+        let mut line = String::new();
+        if connection.stdout.read_line(&mut line).await? == 0 {
+            return Err(anyhow!("the process exited"));
+        }
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(_) if line.trim().is_empty() => continue,
+            Err(e) => return Err(anyhow!("invalid JSON-RPC message: {}", e)),
+        };
+        // Notifications and stale responses carry another id, or none
+        if message.get("id").and_then(Value::as_u64) == Some(id) {
+            return Ok(serde_json::from_value(message)?);
+        }
+    }
+}
+
+#[async_trait]
+impl TestGenerator for ProcessLanguageAdapter {
+    async fn analyze_code(&self, source: &str, file_path: &str) -> Result<Vec<TestablePattern>> {
+        self.request("analyze", json!({ "source": source, "file_path": file_path })).await
+    }
+
+    async fn generate_tests(&self, patterns: Vec<TestablePattern>) -> Result<TestSuite> {
+        self.generate_comprehensive_tests(patterns, "").await
+    }
+
+    async fn generate_comprehensive_tests(&self, patterns: Vec<TestablePattern>, source: &str) -> Result<TestSuite> {
+        let generated: GeneratedTests = self.request("generateTests", json!({ "patterns": patterns, "source": source })).await?;
+        Ok(generated.into_suite(&self.config, &patterns))
+    }
+
+    fn get_language(&self) -> &str {
+        &self.config.name
+    }
+
+    fn get_supported_frameworks(&self) -> Vec<&str> {
+        vec![&self.config.framework]
+    }
+
+    fn get_coverage_target(&self) -> f32 {
+        70.0
+    }
+
+    fn test_path(&self) -> Option<&str> {
+        self.config.test_path.as_deref()
+    }
+
+    /// The suite's test cases with the config's `test_template`
+    fn generate_test_code(&self, test_suite: &TestSuite) -> Result<String> {
+        Ok(DynamicLanguageAdapter::new(self.config.clone()).generate_test_content(&test_suite.test_cases))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers `analyze` with no patterns after a log notification, `generateTests` with one
+    /// test case, and anything else with an error
+    const ADAPTER: &str = r#"while read -r line; do
+  id=$(printf '%s' "$line" | sed 's/^{"jsonrpc":"2.0","id":\([0-9]*\),.*/\1/')
+  case "$line" in
+    *'"method":"analyze"'*)
+      printf '{"jsonrpc":"2.0","method":"log","params":"analyzing"}\n'
+      printf '{"jsonrpc":"2.0","id":%s,"result":[]}\n' "$id" ;;
+    *'"method":"generateTests"'*)
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"test_cases":[{"id":"1","name":"test_add","description":"Adds","input":null,"expected_output":3,"test_body":"","assertions":[],"test_category":"HappyPath"}]}}\n' "$id" ;;
+    *)
+      printf '{"jsonrpc":"2.0","id":%s,"error":{"code":-32601,"message":"Method not found"}}\n' "$id" ;;
+  esac
+done
+"#;
+
+    #[tokio::test]
+    async fn test_process_adapter() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("adapter.sh"), ADAPTER).unwrap();
+        let config: LanguageConfig = serde_json::from_str(include_str!("../../language_configs/kotlin.json")).unwrap();
+        let mut plugin = serde_json::to_value(&config).unwrap();
+        plugin["patterns"] = json!([]);
+        plugin["command"] = json!(["sh", "adapter.sh"]);
+        std::fs::write(dir.path().join("kotlin.json"), plugin.to_string()).unwrap();
+        let adapters = crate::core::LanguageLoader::new(dir.path().to_string_lossy().to_string()).load_all_languages().unwrap();
+        assert!(adapters["kotlin"].analyze_code("fun add() = 3", "Calc.kt").await.unwrap().is_empty());
+
+        let adapter = ProcessLanguageAdapter::new(config.clone(), vec!["sh".to_string(), "adapter.sh".to_string()], dir.path());
+        assert!(adapter.analyze_code("fun add() = 3", "Calc.kt").await.unwrap().is_empty());
+        let suite = adapter.generate_comprehensive_tests(vec![], "fun add() = 3").await.unwrap();
+        assert_eq!(suite.test_cases[0].name, "test_add");
+        assert!(suite.test_code.unwrap().contains("test_add"));
+        let error = adapter.request::<Value>("shutdown", json!({})).await.unwrap_err();
+        assert_eq!(error.to_string(), "The kotlin adapter's shutdown failed (-32601): Method not found");
+
+        let missing = ProcessLanguageAdapter::new(config, vec!["./no-such-adapter".to_string()], dir.path());
+        let error = missing.analyze_code("", "Calc.kt").await.unwrap_err();
+        assert_eq!(UftError::exit_code_of(&error), 3);
+    }
+}
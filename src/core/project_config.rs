@@ -0,0 +1,544 @@
+use anyhow::{Context as _, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{default_test_path, expand_test_path, get_test_file_path, inline_tests_start, AdapterConfig, CoverageStandards, UftError, Workspace};
+
+/// File name of the per-project configuration
+pub const PROJECT_CONFIG_FILE: &str = "uft.toml";
+
+/// Alternative name for the per-project configuration, same format
+pub const PROJECT_CONFIG_ALIAS: &str = ".uftrc";
+
+/// Gitignore-syntax file listing paths directory scans skip, alongside `.gitignore`
+pub const IGNORE_FILE: &str = ".uftignore";
+
+/// Marker a template override puts where the generated tests go
+pub const TEMPLATE_TESTS_MARKER: &str = "{{ tests }}";
+
+/// Project-level settings read from `uft.toml` (or `.uftrc`)
+///
+/// ```toml
+/// ignore = ["src/generated/**", "**/*_pb2.py"]
+/// confidence_threshold = 0.6
+///
+/// [output]
+/// directory = "tests/generated"
+///
+/// [frameworks]
+/// java = "testng"
+///
+/// [naming]
+/// prefix = "test_"
+///
+/// [naming.languages]
+/// javascript = "it_"
+///
+/// [templates]
+/// python = "templates/pytest.py"
+///
+/// [header]
+/// license = "Apache-2.0"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectConfig {
+    /// Globs, relative to the config's directory, of files never analyzed
+    pub ignore: Vec<String>,
+    /// Patterns detected with lower confidence than this get no tests
+    pub confidence_threshold: Option<f32>,
+    pub coverage: CoverageConfig,
+    pub output: OutputConfig,
+    /// Test framework per language, used instead of asking
+    pub frameworks: HashMap<String, String>,
+    pub naming: NamingConfig,
+    /// Skeleton file per language that new test files are written into, with
+    /// `{{ tests }}` marking where the generated tests go
+    pub templates: HashMap<String, String>,
+    /// Comment block put at the top of every generated test file; none when unset
+    pub header: Option<HeaderConfig>,
+    /// Directory the config was loaded from; module paths are resolved against it
+    #[serde(skip)]
+    pub root: Option<PathBuf>,
+    /// Cargo, Node, Gradle, Maven or Go workspace the config was discovered in, whose member
+    /// packages hold their own tests
+    #[serde(skip)]
+    pub workspace: Workspace,
+}
+
+/// Coverage target overrides
+///
+/// ```toml
+/// [coverage]
+/// default = 75
+///
+/// [coverage.languages]
+/// python = 90
+///
+/// [coverage.modules]
+/// "src/payments" = 95
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoverageConfig {
+    pub default: Option<f32>,
+    pub languages: HashMap<String, f32>,
+    pub modules: HashMap<String, f32>,
+}
+
+/// Where generated test files are written
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputConfig {
+    /// Directory, relative to the config's directory, holding every test file; next to their
+    /// sources under each language's conventions when unset
+    pub directory: Option<String>,
+}
+
+/// The comment block at the top of generated test files. Without a `template` it's the license
+/// line, where and from what the file was generated, and how to regenerate it:
+///
+/// ```text
+/// # SPDX-License-Identifier: Apache-2.0
+/// # Generated by uft 0.1.0 from src/calc.py @ commit 1a2b3c4
+/// # Regenerate with: uft generate src/calc.py
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeaderConfig {
+    /// SPDX license identifier
+    pub license: Option<String>,
+    /// Add when the file was generated. Every regeneration then rewrites the file, so it's off
+    /// unless asked for
+    pub timestamp: bool,
+    /// Lines replacing the default ones, each commented for the file's language, with
+    /// `{{ license }}`, `{{ version }}`, `{{ source }}`, `{{ commit }}`, `{{ timestamp }}` and
+    /// `{{ command }}` filled in
+    pub template: Option<String>,
+}
+
+/// Test name prefixes replacing each adapter's own (`test_`, `should_`, ...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamingConfig {
+    pub prefix: Option<String>,
+    pub languages: HashMap<String, String>,
+}
+
+impl ProjectConfig {
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)
+            .map_err(|e| UftError::Config(format!("Failed to parse project config: {}", e)))?;
+        config.ignore_set()?;
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| UftError::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+        let mut config = Self::parse(&content)
+            .with_context(|| format!("Invalid {}", path.display()))?;
+        config.root = path.parent().map(Path::to_path_buf);
+        Ok(config)
+    }
+
+    /// Find the nearest `uft.toml` in `start` or its ancestors, falling back to defaults, along
+    /// with the workspace `start` is in
+    pub fn discover(start: &Path) -> Result<Self> {
+        let mut config = Self::discover_config(start)?;
+        config.workspace = Workspace::discover(start);
+        Ok(config)
+    }
+
+    fn discover_config(start: &Path) -> Result<Self> {
+        let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+        let start = if start.is_file() {
+            start.parent().map(Path::to_path_buf).unwrap_or(start)
+        } else {
+            start
+        };
+
+        for dir in start.ancestors() {
+            for name in [PROJECT_CONFIG_FILE, PROJECT_CONFIG_ALIAS] {
+                let candidate = dir.join(name);
+                if candidate.is_file() {
+                    return Self::load(&candidate);
+                }
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    /// Resolve the coverage target for a file: the longest matching module prefix wins,
+    /// then the language override, then the project default, then the language standard
+    pub fn coverage_target(&self, language: &str, file_path: &str) -> f32 {
+        let relative = self.relative_path(file_path);
+
+        let module_target = self.coverage.modules.iter()
+            .filter(|(prefix, _)| relative.starts_with(prefix.trim_start_matches("./")))
+            .max_by_key(|(prefix, _)| Path::new(prefix.as_str()).components().count())
+            .map(|(_, target)| *target);
+
+        module_target
+            .or_else(|| self.coverage.languages.get(&language.to_lowercase()).copied())
+            .or(self.coverage.default)
+            .unwrap_or_else(|| CoverageStandards::get_coverage_target(language))
+    }
+
+    fn ignore_set(&self) -> Result<GlobSet> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.ignore {
+            builder.add(Glob::new(pattern).map_err(|e| UftError::Config(format!("Invalid ignore pattern: {}: {}", pattern, e)))?);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Whether a file matches one of the `ignore` globs
+    pub fn is_ignored(&self, file_path: &str) -> bool {
+        if self.ignore.is_empty() {
+            return false;
+        }
+        let relative = self.relative_path(file_path);
+        self.ignore_set().is_ok_and(|set| set.is_match(&relative))
+    }
+
+    /// Whether a pattern detected with this confidence should get tests
+    pub fn is_confident(&self, confidence: f32) -> bool {
+        self.confidence_threshold.is_none_or(|threshold| confidence >= threshold)
+    }
+
+    /// Configured test framework for a language
+    pub fn framework(&self, language: &str) -> Option<&str> {
+        self.frameworks.get(&language.to_lowercase()).map(String::as_str)
+    }
+
+    /// Adapter configuration for a language with the configured test name prefix
+    pub fn adapter_config(&self, language: &str) -> AdapterConfig {
+        let naming_prefix = self.naming.languages.get(&language.to_lowercase())
+            .or(self.naming.prefix.as_ref())
+            .cloned();
+        AdapterConfig { naming_prefix, ..AdapterConfig::for_language(language) }
+    }
+
+    /// Where the tests for `source_file` go: under the output directory when one is set,
+    /// otherwise where the language's conventions put them relative to `repo_dir`. In a
+    /// workspace, both are relative to the member package holding the file instead
+    pub fn test_file_path(&self, repo_dir: &Path, source_file: &Path, language: &str) -> PathBuf {
+        self.test_file_path_with(repo_dir, source_file, language, None)
+    }
+
+    /// [`test_file_path`](Self::test_file_path), with a language config's `test_path`
+    /// convention in place of the built-in one when it has one
+    pub fn test_file_path_with(&self, repo_dir: &Path, source_file: &Path, language: &str, convention: Option<&str>) -> PathBuf {
+        let member_root = self.workspace.member_root(&repo_dir.join(source_file));
+        let pattern = convention.unwrap_or_else(|| default_test_path(language));
+        let root = match &member_root {
+            Some(member_root) if !pattern.starts_with("{parent}") => member_root,
+            _ => repo_dir,
+        };
+        let conventional = match convention {
+            Some(convention) => expand_test_path(convention, root, source_file),
+            None => get_test_file_path(root, source_file, language),
+        };
+        let output_root = member_root.as_deref().or(self.root.as_deref()).unwrap_or(repo_dir);
+        match (&self.output.directory, conventional.file_name()) {
+            (Some(directory), Some(name)) => output_root.join(directory).join(name),
+            _ => conventional,
+        }
+    }
+
+    /// Put generated test file content for `source_file` into the language's template override,
+    /// if it has one, under the configured header. An inline Rust test module, which goes into
+    /// the source file itself, gets neither
+    pub fn apply_template(&self, language: &str, source_file: &str, content: &str) -> Result<String> {
+        if inline_tests_start(content) == Some(0) {
+            return Ok(content.to_string());
+        }
+        let content = self.skeleton(language, content)?;
+        Ok(match self.file_header(language, source_file) {
+            Some(header) => format!("{}\n{}", header, content),
+            None => content,
+        })
+    }
+
+    /// Whether `path` is a project config file or one of the `[templates]` skeletons, which
+    /// `watch` and `server` reload when they change
+    pub fn is_template_file(&self, path: &Path) -> bool {
+        let name = path.file_name().and_then(|name| name.to_str());
+        if matches!(name, Some(PROJECT_CONFIG_FILE | PROJECT_CONFIG_ALIAS)) {
+            return true;
+        }
+        self.root.as_deref().is_some_and(|root| self.templates.values().any(|template| root.join(template) == path))
+    }
+
+    /// Read every `[templates]` skeleton, failing on one that's missing or has no marker
+    pub fn check_templates(&self) -> Result<()> {
+        for language in self.templates.keys() {
+            self.skeleton(language, "")?;
+        }
+        Ok(())
+    }
+
+    fn skeleton(&self, language: &str, content: &str) -> Result<String> {
+        let Some(template) = self.templates.get(&language.to_lowercase()) else {
+            return Ok(content.to_string());
+        };
+        let path = self.root.as_deref().map_or_else(|| PathBuf::from(template), |root| root.join(template));
+        let skeleton = fs::read_to_string(&path)
+            .map_err(|e| UftError::Config(format!("Failed to read template {}: {}", path.display(), e)))?;
+        if !skeleton.contains(TEMPLATE_TESTS_MARKER) {
+            return Err(UftError::Config(format!("Template {} has no {} marker", path.display(), TEMPLATE_TESTS_MARKER)).into());
+        }
+        Ok(skeleton.replace(TEMPLATE_TESTS_MARKER, content))
+    }
+
+    /// The configured header for the tests of `source_file`, commented for `language`
+    pub fn file_header(&self, language: &str, source_file: &str) -> Option<String> {
+        let header = self.header.as_ref()?;
+        let source = self.relative_path(source_file).to_string_lossy().replace('\\', "/");
+        let commit = head_commit(Path::new(source_file));
+        let timestamp = utc_timestamp(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()));
+        let command = format!("uft generate {}", source);
+        let version = env!("CARGO_PKG_VERSION");
+
+        let lines: Vec<String> = match &header.template {
+            Some(template) => template
+                .replace("{{ license }}", header.license.as_deref().unwrap_or_default())
+                .replace("{{ version }}", version)
+                .replace("{{ source }}", &source)
+                .replace("{{ commit }}", commit.as_deref().unwrap_or_default())
+                .replace("{{ timestamp }}", &timestamp)
+                .replace("{{ command }}", &command)
+                .lines()
+                .map(str::to_string)
+                .collect(),
+            None => {
+                let mut generated = format!("Generated by uft {} from {}", version, source);
+                if let Some(commit) = &commit {
+                    generated.push_str(&format!(" @ commit {}", commit));
+                }
+                if header.timestamp {
+                    generated.push_str(&format!(" on {}", timestamp));
+                }
+                header.license.iter().map(|license| format!("SPDX-License-Identifier: {}", license))
+                    .chain([generated, format!("Regenerate with: {}", command)])
+                    .collect()
+            }
+        };
+
+        let comment = match language.to_lowercase().as_str() {
+            "python" | "ruby" | "shell" | "bash" | "yaml" => "#",
+            _ => "//",
+        };
+        Some(lines.iter()
+            .map(|line| if line.is_empty() { format!("{}\n", comment) } else { format!("{} {}\n", comment, line) })
+            .collect())
+    }
+
+    fn relative_path(&self, file_path: &str) -> PathBuf {
+        let path = Path::new(file_path);
+        let path = path.strip_prefix(".").unwrap_or(path);
+
+        let Some(root) = &self.root else {
+            return path.to_path_buf();
+        };
+
+        if let Ok(relative) = path.strip_prefix(root) {
+            return relative.to_path_buf();
+        }
+
+        path.canonicalize()
+            .ok()
+            .and_then(|absolute| absolute.strip_prefix(root).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+/// Abbreviated id of the commit checked out in the repository holding `path`
+fn head_commit(path: &Path) -> Option<String> {
+    let dir = if path.is_dir() { path } else { path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new(".")) };
+    let repo = git2::Repository::discover(dir).ok()?;
+    let id = repo.head().ok()?.peel_to_commit().ok()?.id().to_string();
+    Some(id[..7].to_string())
+}
+
+/// `2024-01-31T09:05:00Z` for Unix seconds
+fn utc_timestamp(secs: u64) -> String {
+    let (days, time) = (secs / 86_400, secs % 86_400);
+    // Civil date from days since the epoch, after Howard Hinnant's `civil_from_days`
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, time / 3600, time % 3600 / 60, time % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+[coverage]
+default = 60
+
+[coverage.languages]
+python = 90
+
+[coverage.modules]
+"src/payments" = 95
+"src/payments/legacy" = 50
+"#;
+
+    #[test]
+    fn test_parse_coverage_overrides() {
+        let config = ProjectConfig::parse(SAMPLE).unwrap();
+        assert_eq!(config.coverage.default, Some(60.0));
+        assert_eq!(config.coverage.languages.get("python"), Some(&90.0));
+        assert_eq!(config.coverage.modules.len(), 2);
+    }
+
+    #[test]
+    fn test_coverage_target_resolution_order() {
+        let config = ProjectConfig::parse(SAMPLE).unwrap();
+        assert_eq!(config.coverage_target("python", "src/payments/legacy/old.py"), 50.0);
+        assert_eq!(config.coverage_target("python", "src/payments/charge.py"), 95.0);
+        assert_eq!(config.coverage_target("python", "src/users.py"), 90.0);
+        assert_eq!(config.coverage_target("rust", "src/lib.rs"), 60.0);
+    }
+
+    #[test]
+    fn test_coverage_target_without_config() {
+        let config = ProjectConfig::default();
+        assert_eq!(config.coverage_target("python", "app.py"), CoverageStandards::get_coverage_target("python"));
+    }
+
+    #[test]
+    fn test_discover_from_nested_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(PROJECT_CONFIG_FILE), SAMPLE).unwrap();
+        let nested = dir.path().join("src/payments");
+        fs::create_dir_all(&nested).unwrap();
+        let file = nested.join("charge.py");
+        fs::write(&file, "def charge(): pass").unwrap();
+
+        let config = ProjectConfig::discover(&file).unwrap();
+        assert!(config.root.is_some());
+        assert_eq!(config.coverage_target("python", &file.to_string_lossy()), 95.0);
+    }
+
+    #[test]
+    fn test_generation_settings() {
+        let config = ProjectConfig::parse(r#"
+ignore = ["src/generated/**"]
+confidence_threshold = 0.6
+
+[output]
+directory = "tests/generated"
+
+[frameworks]
+java = "testng"
+
+[naming]
+prefix = "check_"
+
+[naming.languages]
+javascript = "it_"
+"#).unwrap();
+        assert!(config.is_ignored("src/generated/models.py"));
+        assert!(!config.is_ignored("src/models.py"));
+        assert!(config.is_confident(0.6));
+        assert!(!config.is_confident(0.5));
+        assert_eq!(config.framework("Java"), Some("testng"));
+        assert_eq!(config.framework("python"), None);
+        assert_eq!(config.adapter_config("javascript").naming_prefix.as_deref(), Some("it_"));
+        assert_eq!(config.adapter_config("python").naming_prefix.as_deref(), Some("check_"));
+        assert_eq!(config.test_file_path(Path::new("/repo"), Path::new("src/calc.py"), "python"), PathBuf::from("/repo/tests/generated/test_calc.py"));
+
+        let error = ProjectConfig::parse("ignore = [\"src/[\"]\n").unwrap_err();
+        assert_eq!(UftError::exit_code_of(&error), 3);
+    }
+
+    #[test]
+    fn test_discover_uftrc_and_apply_template() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(PROJECT_CONFIG_ALIAS), "[templates]\npython = \"pytest.tmpl\"\n").unwrap();
+        fs::write(dir.path().join("pytest.tmpl"), "# Generated by uft\n{{ tests }}").unwrap();
+
+        let config = ProjectConfig::discover(dir.path()).unwrap();
+        assert_eq!(config.apply_template("python", "calc.py", "def test_add():\n    pass\n").unwrap(), "# Generated by uft\ndef test_add():\n    pass\n");
+        assert_eq!(config.apply_template("rust", "lib.rs", "fn x() {}").unwrap(), "fn x() {}");
+
+        let root = config.root.clone().unwrap();
+        assert!(config.is_template_file(&root.join("pytest.tmpl")));
+        assert!(config.is_template_file(&root.join(PROJECT_CONFIG_FILE)));
+        assert!(!config.is_template_file(&root.join("calc.py")));
+        assert!(config.check_templates().is_ok());
+        fs::write(dir.path().join("pytest.tmpl"), "# Generated by uft\n").unwrap();
+        assert_eq!(UftError::exit_code_of(&config.check_templates().unwrap_err()), 3);
+    }
+
+    #[test]
+    fn test_file_header() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(PROJECT_CONFIG_FILE), "[header]\nlicense = \"MIT\"\n").unwrap();
+        let source = dir.path().join("src/calc.py");
+        let config = ProjectConfig::discover(dir.path()).unwrap();
+
+        let content = config.apply_template("python", &source.to_string_lossy(), "def test_add():\n    pass\n").unwrap();
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(content, format!(
+            "# SPDX-License-Identifier: MIT\n# Generated by uft {} from src/calc.py\n# Regenerate with: uft generate src/calc.py\n\ndef test_add():\n    pass\n",
+            version
+        ));
+
+        let custom = ProjectConfig::parse("[header]\ntemplate = \"Copyright Acme\\n\\nFrom {{ source }} by uft {{ version }}\"\n").unwrap();
+        assert_eq!(
+            custom.file_header("go", "calc.go").unwrap(),
+            format!("// Copyright Acme\n//\n// From calc.go by uft {}\n", version)
+        );
+        assert!(ProjectConfig::default().file_header("go", "calc.go").is_none());
+        assert_eq!(utc_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(utc_timestamp(1_792_152_245), "2026-10-16T12:04:05Z");
+    }
+
+    #[test]
+    fn test_workspace_members_hold_their_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in [
+            ("package.json", r#"{ "workspaces": ["packages/*"] }"#),
+            ("packages/api/package.json", r#"{ "name": "api" }"#),
+            ("packages/api/src/users.js", "export const list = () => [];\n"),
+            ("scripts/seed.js", "export const seed = () => 1;\n"),
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let mut config = ProjectConfig::discover(dir.path()).unwrap();
+        let source = Path::new("packages/api/src/users.js");
+
+        assert_eq!(config.test_file_path(dir.path(), source, "javascript"), dir.path().join("packages/api/src/__tests__/users.test.js"));
+        assert_eq!(config.test_file_path_with(dir.path(), source, "javascript", Some("test/{stem}.spec.js")), dir.path().join("packages/api/test/users.spec.js"));
+        assert_eq!(config.test_file_path_with(dir.path(), Path::new("scripts/seed.js"), "javascript", Some("test/{stem}.spec.js")), dir.path().join("test/seed.spec.js"));
+        config.output.directory = Some("tests/generated".to_string());
+        assert_eq!(config.test_file_path(dir.path(), source, "javascript"), dir.path().join("packages/api/tests/generated/users.test.js"));
+    }
+
+    #[test]
+    fn test_discover_without_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ProjectConfig::discover(dir.path()).unwrap();
+        assert!(config.root.is_none());
+        assert!(config.coverage.modules.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::{anyhow, Result};
+use git2::{Oid, PushOptions, Repository, Signature, Status, StatusOptions};
+use serde_json::json;
+use std::path::Path;
+
+use super::{GitCredentials, CHECKPOINT_FILE, MANIFEST_FILE};
+
+/// Where a repository is hosted, for opening a pull request (merge request on GitLab)
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitHost {
+    Github { api: String, owner: String, repo: String },
+    Gitlab { api: String, project: String },
+}
+
+/// A pull request to open from a pushed branch
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+    pub branch: String,
+    pub base: String,
+    pub title: String,
+    pub body: String,
+}
+
+impl GitHost {
+    /// The host of an HTTPS or SSH clone URL: github.com or GitHub Enterprise, gitlab.com or a
+    /// self-hosted GitLab with "gitlab" in its name
+    pub fn from_url(url: &str) -> Option<Self> {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        // user@host:path for SCP-like SSH URLs, [user@]host/path otherwise
+        let rest = rest.rsplit_once('@').map_or(rest, |(_, rest)| rest);
+        let (host, path) = match rest.split_once('/') {
+            Some((host, path)) if !host.contains(':') || url.contains("://") => (host, path),
+            _ => rest.split_once(':')?,
+        };
+        let host = host.split(':').next().unwrap_or(host);
+        let path = path.trim_end_matches('/').trim_end_matches(".git");
+
+        if host == "github.com" || host.contains("github") {
+            let (owner, repo) = path.split_once('/')?;
+            let api = if host == "github.com" { "https://api.github.com".to_string() } else { format!("https://{}/api/v3", host) };
+            Some(Self::Github { api, owner: owner.to_string(), repo: repo.to_string() })
+        } else if host.contains("gitlab") {
+            Some(Self::Gitlab { api: format!("https://{}/api/v4", host), project: path.to_string() })
+        } else {
+            None
+        }
+    }
+
+    /// User name to authenticate pushes with alongside an access token
+    pub fn token_user(&self) -> &'static str {
+        match self {
+            Self::Github { .. } => "x-access-token",
+            Self::Gitlab { .. } => "oauth2",
+        }
+    }
+
+    /// Environment variable the token is read from unless another is named
+    pub fn default_token_env(&self) -> &'static str {
+        match self {
+            Self::Github { .. } => "GH_TOKEN",
+            Self::Gitlab { .. } => "GITLAB_TOKEN",
+        }
+    }
+
+    /// Open the pull request, returning its web URL
+    pub async fn open_pull_request(&self, token: &str, request: &PullRequest) -> Result<String> {
+        let client = reqwest::Client::new();
+        let (response, url_field) = match self {
+            Self::Github { api, owner, repo } => (
+                client.post(format!("{}/repos/{}/{}/pulls", api, owner, repo))
+                    .bearer_auth(token)
+                    .header("Accept", "application/vnd.github+json")
+                    .header("User-Agent", "uft")
+                    .json(&json!({ "title": request.title, "head": request.branch, "base": request.base, "body": request.body }))
+                    .send()
+                    .await?,
+                "html_url",
+            ),
+            Self::Gitlab { api, project } => (
+                client.post(format!("{}/projects/{}/merge_requests", api, project.replace('/', "%2F")))
+                    .header("PRIVATE-TOKEN", token)
+                    .json(&json!({ "title": request.title, "source_branch": request.branch, "target_branch": request.base, "description": request.body }))
+                    .send()
+                    .await?,
+                "web_url",
+            ),
+        };
+
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            let message = body.get("message").map(|message| message.to_string()).unwrap_or_default();
+            return Err(anyhow!("Opening the pull request failed ({}): {}", status, message));
+        }
+        body.get(url_field)
+            .and_then(|url| url.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("The pull request response had no {}", url_field))
+    }
+}
+
+/// The files among `written`, relative to the repository root, that are new or modified in
+/// the working tree, leaving out the generation manifest
+pub fn changed_paths(repo: &Repository, written: &[String]) -> Result<Vec<String>> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let statuses = repo.statuses(Some(&mut options))?;
+    Ok(statuses.iter()
+        .filter(|entry| entry.status().intersects(Status::WT_NEW | Status::WT_MODIFIED | Status::INDEX_NEW | Status::INDEX_MODIFIED))
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .filter(|path| path != MANIFEST_FILE && written.contains(path))
+        .collect())
+}
+
+/// Whether the working tree has changes of its own, untracked files included. What uft
+/// itself keeps there, its manifest and run checkpoint, doesn't count
+pub fn has_local_changes(repo: &Repository) -> Result<bool> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let statuses = repo.statuses(Some(&mut options))?;
+    Ok(statuses.iter()
+        .filter(|entry| !entry.status().is_ignored())
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .any(|path| path != MANIFEST_FILE && path != CHECKPOINT_FILE))
+}
+
+/// Commit message listing the test files added, after a conventional `test:` subject
+pub fn generated_tests_message(paths: &[String], source_files: usize, test_cases: usize) -> String {
+    let mut message = format!(
+        "test: add generated tests for {} source files\n\nGenerated by uft: {} test cases in {} test files.\n\n",
+        source_files, test_cases, paths.len()
+    );
+    for path in paths {
+        message.push_str(&format!("- {}\n", path));
+    }
+    message
+}
+
+/// Commit `paths`, relative to the repository root, onto a new branch `branch` off HEAD and
+/// check it out. Nothing else in the working tree is staged
+pub fn commit_on_branch(repo: &Repository, branch: &str, message: &str, paths: &[String]) -> Result<Oid> {
+    let head = repo.head()?.peel_to_commit()?;
+    repo.branch(branch, &head, false)?;
+    repo.set_head(&format!("refs/heads/{}", branch))?;
+
+    let mut index = repo.index()?;
+    for path in paths {
+        index.add_path(Path::new(path))?;
+    }
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = repo.signature().or_else(|_| Signature::now("uft", "uft@users.noreply.github.com"))?;
+    Ok(repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&head])?)
+}
+
+/// Push `branch` to origin
+pub fn push_branch(repo: &Repository, branch: &str, credentials: &GitCredentials) -> Result<()> {
+    let mut options = PushOptions::new();
+    options.remote_callbacks(credentials.callbacks());
+    let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+    repo.find_remote("origin")?.push(&[refspec.as_str()], Some(&mut options))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::IndexAddOption;
+    use std::fs;
+
+    #[test]
+    fn test_git_host_from_url() {
+        let github = GitHost::Github { api: "https://api.github.com".to_string(), owner: "acme".to_string(), repo: "shop".to_string() };
+        assert_eq!(GitHost::from_url("https://github.com/acme/shop.git"), Some(github.clone()));
+        assert_eq!(GitHost::from_url("git@github.com:acme/shop.git"), Some(github.clone()));
+        assert_eq!(GitHost::from_url("ssh://git@github.com/acme/shop"), Some(github));
+        assert_eq!(
+            GitHost::from_url("https://gitlab.example.com/platform/tools/shop.git"),
+            Some(GitHost::Gitlab { api: "https://gitlab.example.com/api/v4".to_string(), project: "platform/tools/shop".to_string() })
+        );
+        assert_eq!(GitHost::from_url("https://bitbucket.org/acme/shop.git"), None);
+    }
+
+    #[test]
+    fn test_commit_on_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("calc.py"), "def add(a, b):\n    return a + b\n").unwrap();
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"], IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[]).unwrap();
+
+        assert!(!has_local_changes(&repo).unwrap());
+        fs::write(dir.path().join(MANIFEST_FILE), "{}").unwrap();
+        assert!(!has_local_changes(&repo).unwrap());
+        fs::write(dir.path().join(".env.local"), "TOKEN=secret\n").unwrap();
+        assert!(has_local_changes(&repo).unwrap());
+
+        fs::create_dir(dir.path().join("tests")).unwrap();
+        fs::write(dir.path().join("tests/test_calc.py"), "def test_add():\n    pass\n").unwrap();
+        let written = vec!["tests/test_calc.py".to_string(), MANIFEST_FILE.to_string()];
+        let paths = changed_paths(&repo, &written).unwrap();
+        assert_eq!(paths, ["tests/test_calc.py"]);
+
+        let message = generated_tests_message(&paths, 1, 3);
+        assert!(message.starts_with("test: add generated tests for 1 source files\n\n"));
+        commit_on_branch(&repo, "uft/generated-tests", &message, &paths).unwrap();
+
+        assert_eq!(repo.head().unwrap().shorthand(), Some("uft/generated-tests"));
+        assert_eq!(repo.head().unwrap().peel_to_commit().unwrap().message(), Some(message.as_str()));
+        assert!(changed_paths(&repo, &written).unwrap().is_empty());
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_name(MANIFEST_FILE).is_none() && tree.get_name(".env.local").is_none());
+    }
+}
@@ -0,0 +1,246 @@
+use regex::Regex;
+use serde_json::Value;
+
+use super::auth_flows::call_args;
+use super::cli::braced;
+
+/// A `useState` value held by a hook
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookState {
+    pub name: String,
+    pub setter: String,
+    /// The initial value as written, e.g. `0` or a parameter's name
+    pub initial: String,
+}
+
+/// A function a hook defines that calls its state setters
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookAction {
+    pub name: String,
+    pub params: Vec<String>,
+    /// Each setter call as `(setter, argument)`, the argument as written, in call order
+    pub updates: Vec<(String, String)>,
+}
+
+/// A custom React hook: a `useXxx` function calling other hooks
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReactHook {
+    pub name: String,
+    /// Parameters with their defaults as written
+    pub params: Vec<(String, Option<String>)>,
+    pub states: Vec<HookState>,
+    pub actions: Vec<HookAction>,
+    /// Returned names and how to reach them from `result.current`: `.count` for an object,
+    /// `[0]` for an array, nothing for a hook returning one value
+    pub returned: Vec<(String, String)>,
+}
+
+impl ReactHook {
+    /// Path from `result.current` to a returned state or action
+    pub fn accessor(&self, name: &str) -> Option<&str> {
+        self.returned.iter().find(|(n, _)| n == name).map(|(_, path)| path.as_str())
+    }
+
+    /// State values after the first render, for states whose initial value is a literal or a
+    /// parameter with a known value in `args`
+    pub fn initial_values(&self, args: &[(String, Value)]) -> Vec<(String, Value)> {
+        self.states.iter()
+            .filter_map(|state| Some((state.name.clone(), evaluate(&state.initial, args)?)))
+            .collect()
+    }
+
+    /// State values after calling `action` with `action_args`, from the values in `current`.
+    /// States the action doesn't set keep their values; states set to something that can't be
+    /// worked out are left out
+    pub fn after(&self, action: &HookAction, current: &[(String, Value)], args: &[(String, Value)], action_args: &[(String, Value)]) -> Vec<(String, Value)> {
+        let updater = Regex::new(r"^(?:\(\s*(\w+)\s*\)|(\w+))\s*=>\s*(.+)$").unwrap();
+        let mut values: Vec<(String, Option<Value>)> = current.iter().map(|(n, v)| (n.clone(), Some(v.clone()))).collect();
+        for (setter, argument) in &action.updates {
+            let Some(state) = self.states.iter().find(|s| s.setter == *setter) else { continue };
+            let previous = values.iter().find(|(n, _)| *n == state.name).and_then(|(_, v)| v.clone());
+            let mut env: Vec<(String, Value)> = action_args.to_vec();
+            let next = match updater.captures(argument.trim()) {
+                Some(cap) => {
+                    let param = cap.get(1).or(cap.get(2)).unwrap().as_str().to_string();
+                    if let Some(previous) = previous {
+                        env.push((param, previous));
+                    }
+                    evaluate(&cap[3], &env_with(env, args, &values))
+                }
+                None => evaluate(argument, &env_with(env, args, &values)),
+            };
+            match values.iter_mut().find(|(n, _)| *n == state.name) {
+                Some(entry) => entry.1 = next,
+                None => values.push((state.name.clone(), next)),
+            }
+        }
+        values.into_iter().filter_map(|(n, v)| Some((n, v?))).collect()
+    }
+}
+
+/// Names an expression can refer to: the action's and updater's parameters first, then the
+/// hook's parameters and the current state
+fn env_with(mut env: Vec<(String, Value)>, args: &[(String, Value)], values: &[(String, Option<Value>)]) -> Vec<(String, Value)> {
+    env.extend(args.iter().cloned());
+    env.extend(values.iter().filter_map(|(n, v)| Some((n.clone(), v.clone()?))));
+    env
+}
+
+/// The value of a JavaScript literal: numbers, strings, booleans, `null` and empty arrays and
+/// objects
+pub fn js_literal_value(text: &str) -> Option<Value> {
+    let text = text.trim();
+    match text {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        "null" => Some(Value::Null),
+        "[]" => Some(Value::Array(Vec::new())),
+        "{}" => Some(Value::Object(Default::default())),
+        _ if text.len() >= 2 && (text.starts_with('\'') && text.ends_with('\'') || text.starts_with('"') && text.ends_with('"')) => {
+            Some(Value::String(text[1..text.len() - 1].to_string()))
+        }
+        _ => text.parse::<f64>().ok().map(number),
+    }
+}
+
+/// Value of a literal, a name bound in `env`, `!x`, or `x + n`/`x - n`
+fn evaluate(expr: &str, env: &[(String, Value)]) -> Option<Value> {
+    let expr = expr.trim();
+    if let Some(value) = js_literal_value(expr) {
+        return Some(value);
+    }
+    if let Some((_, value)) = env.iter().find(|(name, _)| name == expr) {
+        return Some(value.clone());
+    }
+    if let Some(operand) = expr.strip_prefix('!') {
+        return Some(Value::Bool(!evaluate(operand, env)?.as_bool()?));
+    }
+    let arithmetic = Regex::new(r"^([\w.]+)\s*([+-])\s*([\w.]+)$").unwrap();
+    let cap = arithmetic.captures(expr)?;
+    let (left, right) = (evaluate(&cap[1], env)?.as_f64()?, evaluate(&cap[3], env)?.as_f64()?);
+    Some(number(if &cap[2] == "+" { left + right } else { left - right }))
+}
+
+fn number(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        Value::from(n as i64)
+    } else {
+        Value::from(n)
+    }
+}
+
+/// `function useXxx(...) {` and `useXxx = (...) => {` definitions whose body calls a hook
+pub fn react_hooks(source: &str) -> Vec<ReactHook> {
+    let definition = Regex::new(r"(?:\bfunction\s+(use[A-Z]\w*)\s*\(([^)]*)\)[^{]*|\b(use[A-Z]\w*)\s*=\s*(?:\(([^)]*)\)|(\w+))[^{=]*=>\s*)\{").unwrap();
+    let calls_hook = Regex::new(r"\buse[A-Z]\w*\s*(?:<[^>]*>)?\(").unwrap();
+    let state = Regex::new(r"(?m)\bconst\s*\[\s*(\w+)\s*,\s*(\w+)\s*\]\s*=\s*(?:React\.)?useState(?:<[^>]*>)?\(").unwrap();
+    let action = Regex::new(r"(?:\bconst\s+(\w+)\s*=\s*(?:(?:React\.)?useCallback\(\s*)?(async\s*)?\(([^)]*)\)\s*=>|\b(async\s+)?function\s+(\w+)\s*\(([^)]*)\))\s*").unwrap();
+
+    definition.captures_iter(source).filter_map(|cap| {
+        let name = cap.get(1).or(cap.get(3))?.as_str().to_string();
+        let body = braced(source, cap.get(0).unwrap().end() - 1);
+        if !calls_hook.is_match(body) {
+            return None;
+        }
+        let params = cap.get(2).or(cap.get(4)).or(cap.get(5)).map(|p| p.as_str()).unwrap_or("")
+            .split(',').map(str::trim).filter(|p| !p.is_empty())
+            .map(|param| {
+                let (declared, default) = param.split_once('=').map(|(d, v)| (d, Some(v.trim().to_string()))).unwrap_or((param, None));
+                (declared.split(':').next().unwrap_or(declared).trim().to_string(), default)
+            })
+            .collect();
+
+        let states: Vec<HookState> = state.captures_iter(body).map(|s| {
+            let open = s.get(0).unwrap().end() - 1;
+            HookState {
+                name: s[1].to_string(),
+                setter: s[2].to_string(),
+                initial: call_args(body, open).into_iter().next().unwrap_or_else(|| "undefined".to_string()),
+            }
+        }).collect();
+
+        let actions = action.captures_iter(body).filter_map(|a| {
+            if a.get(2).or(a.get(4)).is_some() {
+                return None;
+            }
+            let rest = &body[a.get(0).unwrap().end()..];
+            let text = if rest.starts_with('{') { braced(rest, 0) } else { rest.split(['\n', ';']).next().unwrap_or("") };
+            let mut updates: Vec<(usize, String, String)> = states.iter().flat_map(|s| {
+                Regex::new(&format!(r"\b{}\(", regex::escape(&s.setter))).unwrap().find_iter(text)
+                    .map(|m| (m.start(), s.setter.clone(), call_args(text, m.end() - 1).into_iter().next().unwrap_or_default()))
+                    .collect::<Vec<_>>()
+            }).collect();
+            if updates.is_empty() {
+                return None;
+            }
+            updates.sort_by_key(|(position, _, _)| *position);
+            Some(HookAction {
+                name: a.get(1).or(a.get(5))?.as_str().to_string(),
+                params: a.get(3).or(a.get(6)).map(|p| p.as_str()).unwrap_or("").split(',')
+                    .map(|p| p.split([':', '=']).next().unwrap_or("").trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect(),
+                updates: updates.into_iter().map(|(_, setter, argument)| (setter, argument)).collect(),
+            })
+        }).collect();
+
+        Some(ReactHook { name, params, states, actions, returned: returned_names(body) })
+    }).collect()
+}
+
+/// Names in the body's own `return`, outside nested functions
+fn returned_names(body: &str) -> Vec<(String, String)> {
+    let mut depth = 0;
+    let mut returned = None;
+    for (i, c) in body.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            'r' if depth == 0 && body[i..].starts_with("return") && !body[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_') => {
+                returned = Some(body[i + "return".len()..].trim_start());
+            }
+            _ => {}
+        }
+    }
+    let Some(returned) = returned else { return Vec::new() };
+    let identifier = Regex::new(r"^\w+$").unwrap();
+    match returned.chars().next() {
+        Some('{') => call_args(returned, 0).into_iter().filter_map(|entry| {
+            let (key, value) = entry.split_once(':').map(|(k, v)| (k.trim(), v.trim())).unwrap_or((entry.trim(), entry.trim()));
+            identifier.is_match(value).then(|| (value.to_string(), format!(".{}", key)))
+        }).collect(),
+        Some('[') => call_args(returned, 0).into_iter().enumerate()
+            .filter(|(_, entry)| identifier.is_match(entry))
+            .map(|(i, entry)| (entry, format!("[{}]", i)))
+            .collect(),
+        _ => Regex::new(r"^(\w+)\s*;?\s*$").unwrap().captures(returned.lines().next().unwrap_or(""))
+            .map(|cap| vec![(cap[1].to_string(), String::new())])
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_react_hooks() {
+        let hooks = react_hooks("import { useState, useCallback } from 'react';\n\nexport function useCounter(initial = 0, step = 1) {\n  const [count, setCount] = useState(initial);\n  const increment = useCallback(() => setCount((c) => c + step), [step]);\n  const reset = () => {\n    setCount(initial);\n  };\n  return { count, increment, reset };\n}\n\nexport const useToggle = (start) => {\n  const [on, setOn] = useState(start);\n  const toggle = () => setOn(!on);\n  return [on, toggle];\n};\n");
+        assert_eq!(hooks.len(), 2);
+        let counter = &hooks[0];
+        assert_eq!(counter.params, vec![("initial".to_string(), Some("0".to_string())), ("step".to_string(), Some("1".to_string()))]);
+        assert_eq!(counter.actions.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), vec!["increment", "reset"]);
+        assert_eq!(counter.accessor("count"), Some(".count"));
+
+        let args = vec![("initial".to_string(), Value::from(5)), ("step".to_string(), Value::from(1))];
+        let initial = counter.initial_values(&args);
+        let incremented = counter.after(&counter.actions[0], &initial, &args, &[]);
+        assert_eq!(incremented, vec![("count".to_string(), Value::from(6))]);
+        assert_eq!(counter.after(&counter.actions[1], &incremented, &args, &[]), vec![("count".to_string(), Value::from(5))]);
+
+        let toggle = &hooks[1];
+        assert_eq!(toggle.accessor("toggle"), Some("[1]"));
+        let args = vec![("start".to_string(), Value::Bool(false))];
+        assert_eq!(toggle.after(&toggle.actions[0], &toggle.initial_values(&args), &args, &[]), vec![("on".to_string(), Value::Bool(true))]);
+    }
+}
@@ -0,0 +1,593 @@
+use regex::Regex;
+use serde_json::Value;
+
+use super::{Context, HttpMethod, PatternType, RecordedBody, RecordedRequest, SourceLocation, TestablePattern};
+
+/// Piece of a recorded string: literal text or a `{{variable}}` reference
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePart<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+/// Split a string on its `{{variable}}` references
+pub fn template_parts(template: &str) -> Vec<TemplatePart<'_>> {
+    let variable_regex = Regex::new(r"\{\{\s*([\w.\-]+)\s*\}\}").unwrap();
+    let mut parts = Vec::new();
+    let mut last = 0;
+    for cap in variable_regex.captures_iter(template) {
+        let whole = cap.get(0).unwrap();
+        if whole.start() > last {
+            parts.push(TemplatePart::Text(&template[last..whole.start()]));
+        }
+        parts.push(TemplatePart::Variable(cap.get(1).unwrap().as_str()));
+        last = whole.end();
+    }
+    if last < template.len() {
+        parts.push(TemplatePart::Text(&template[last..]));
+    }
+    parts
+}
+
+/// Environment variable overriding a recorded variable, e.g. `baseUrl` -> `BASE_URL`
+pub fn variable_env_var(name: &str) -> String {
+    let mut env = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 && !env.ends_with('_') {
+            env.push('_');
+        }
+        env.push(if c.is_alphanumeric() { c.to_ascii_uppercase() } else { '_' });
+    }
+    env
+}
+
+impl RecordedRequest {
+    /// Identifier-safe form of the request's name for test names
+    pub fn slug(&self) -> String {
+        Regex::new(r"[\W_]+").unwrap().replace_all(&self.name, "_").trim_matches('_').to_lowercase()
+    }
+}
+
+/// Variables the recorded requests among `patterns` refer to, each once with its recorded value
+pub fn recorded_variables(patterns: &[TestablePattern]) -> Vec<(String, String)> {
+    let mut variables: Vec<(String, String)> = Vec::new();
+    for pattern in patterns {
+        if let PatternType::RecordedRequest(request) = &pattern.pattern_type {
+            for variable in &request.variables {
+                if !variables.iter().any(|(name, _)| *name == variable.0) {
+                    variables.push(variable.clone());
+                }
+            }
+        }
+    }
+    variables
+}
+
+/// Setup requirement naming the environment variables that override the recorded ones
+pub fn recorded_variables_requirement(patterns: &[TestablePattern]) -> Option<String> {
+    let variables = recorded_variables(patterns);
+    (!variables.is_empty()).then(|| {
+        let overrides: Vec<String> = variables.iter().map(|(name, _)| variable_env_var(name)).collect();
+        format!("Point the replayed requests at a test deployment with {} (defaults: the recorded values)", overrides.join(", "))
+    })
+}
+
+/// Patterns for requests read from a collection or capture rather than detected in code
+pub fn recorded_request_patterns(requests: Vec<RecordedRequest>, file_path: &str) -> Vec<TestablePattern> {
+    requests.into_iter().enumerate()
+        .map(|(i, request)| TestablePattern {
+            id: uuid::Uuid::new_v4().to_string(),
+            pattern_type: PatternType::RecordedRequest(request),
+            location: SourceLocation { file: file_path.to_string(), line: i + 1, column: 0 },
+            context: Context { function_name: None, class_name: None, module_name: None },
+            confidence: 0.95,
+        })
+        .collect()
+}
+
+/// The requests in a Postman collection or HAR capture; `None` for any other source
+pub fn recorded_requests(source: &str) -> Option<Vec<RecordedRequest>> {
+    if is_postman_collection(source) {
+        Some(postman_requests(source))
+    } else if is_har_capture(source) {
+        Some(har_requests(source))
+    } else {
+        None
+    }
+}
+
+/// Whether the source is an exported Postman collection (v2.0 or v2.1)
+pub fn is_postman_collection(source: &str) -> bool {
+    if !source.trim_start().starts_with('{') {
+        return false;
+    }
+    serde_json::from_str::<Value>(source).is_ok_and(|collection| {
+        collection.pointer("/info/schema").and_then(Value::as_str).is_some_and(|s| s.contains("getpostman.com"))
+            || (collection.pointer("/info/_postman_id").is_some() && collection.get("item").is_some())
+    })
+}
+
+/// Every request in a Postman collection, folders included. Collection variables become the
+/// requests' variables, bearer and API key auth become headers, and the expected status comes
+/// from a `pm.response.to.have.status(...)` test or the first saved example response.
+pub fn postman_requests(source: &str) -> Vec<RecordedRequest> {
+    let Ok(collection) = serde_json::from_str::<Value>(source) else {
+        return vec![];
+    };
+    let variables: Vec<(String, String)> = collection.get("variable")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|v| Some((v.get("key")?.as_str()?.to_string(), value_text(v.get("value")?))))
+        .collect();
+
+    let mut requests = Vec::new();
+    collect_postman_items(&collection, &[], collection.get("auth"), &variables, &mut requests);
+    requests
+}
+
+fn collect_postman_items(parent: &Value, folders: &[&str], auth: Option<&Value>, variables: &[(String, String)], requests: &mut Vec<RecordedRequest>) {
+    for item in parent.get("item").and_then(Value::as_array).into_iter().flatten() {
+        let name = item.get("name").and_then(Value::as_str).unwrap_or("request");
+        let auth = item.get("auth").or(auth);
+        if item.get("item").is_some() {
+            let folders: Vec<&str> = folders.iter().copied().chain(std::iter::once(name)).collect();
+            collect_postman_items(item, &folders, auth, variables, requests);
+            continue;
+        }
+        let Some(request) = item.get("request") else { continue };
+        if let Some(recorded) = postman_request(item, request, &folders.join(" / "), name, auth, variables) {
+            requests.push(recorded);
+        }
+    }
+}
+
+fn postman_request(item: &Value, request: &Value, folder: &str, name: &str, auth: Option<&Value>, variables: &[(String, String)]) -> Option<RecordedRequest> {
+    // A request may be saved as just its URL
+    let (method, url) = match request {
+        Value::String(url) => ("GET", url.clone()),
+        _ => (
+            request.get("method").and_then(Value::as_str).unwrap_or("GET"),
+            match request.get("url")? {
+                Value::String(url) => url.clone(),
+                url => url.get("raw")?.as_str()?.to_string(),
+            },
+        ),
+    };
+    let method = match method.to_uppercase().as_str() {
+        "GET" => HttpMethod::Get,
+        "POST" => HttpMethod::Post,
+        "PUT" => HttpMethod::Put,
+        "PATCH" => HttpMethod::Patch,
+        "DELETE" => HttpMethod::Delete,
+        _ => return None,
+    };
+
+    let mut headers: Vec<(String, String)> = request.get("header")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|h| h.get("disabled") != Some(&Value::Bool(true)))
+        .filter_map(|h| Some((h.get("key")?.as_str()?.to_string(), value_text(h.get("value")?))))
+        .collect();
+    let auth = request.get("auth").or(auth);
+    if let Some(header) = auth.and_then(auth_header) {
+        if !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case(&header.0)) {
+            headers.push(header);
+        }
+    }
+
+    let body = request.get("body").and_then(|body| match body.get("mode").and_then(Value::as_str)? {
+        "raw" => {
+            let raw = body.get("raw")?.as_str()?;
+            if raw.trim().is_empty() {
+                return None;
+            }
+            Some(match serde_json::from_str(raw) {
+                Ok(json) => RecordedBody::Json(json),
+                Err(_) => RecordedBody::Text(raw.to_string()),
+            })
+        }
+        "urlencoded" => {
+            let fields: Vec<String> = body.get("urlencoded")?.as_array()?.iter()
+                .filter(|f| f.get("disabled") != Some(&Value::Bool(true)))
+                .filter_map(|f| Some(format!("{}={}", f.get("key")?.as_str()?, value_text(f.get("value")?))))
+                .collect();
+            if !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("content-type")) {
+                headers.push(("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()));
+            }
+            Some(RecordedBody::Text(fields.join("&")))
+        }
+        _ => None,
+    });
+
+    let mut recorded = RecordedRequest {
+        name: if folder.is_empty() { name.to_string() } else { format!("{} / {}", folder, name) },
+        method,
+        url: with_dynamic_values(&url),
+        headers: headers.into_iter().map(|(key, value)| (key, with_dynamic_values(&value))).collect(),
+        body: body.map(|body| match body {
+            RecordedBody::Json(json) => RecordedBody::Json(map_strings(json, &with_dynamic_values)),
+            RecordedBody::Text(text) => RecordedBody::Text(with_dynamic_values(&text)),
+        }),
+        variables: vec![],
+        expected_status: expected_status(item),
+        response_schema: item.pointer("/response/0/body").and_then(Value::as_str).and_then(response_schema),
+    };
+    recorded.variables = referenced_variables(&recorded, variables);
+    Some(recorded)
+}
+
+/// Whether the source is an HTTP Archive (HAR) capture
+pub fn is_har_capture(source: &str) -> bool {
+    if !source.trim_start().starts_with('{') {
+        return false;
+    }
+    serde_json::from_str::<Value>(source).is_ok_and(|har| har.pointer("/log/entries").is_some_and(Value::is_array))
+}
+
+/// The API calls in a HAR capture: XHR/fetch entries, or entries that aren't pages, scripts,
+/// styles, images or fonts when the capture doesn't record resource types. Repeated calls are
+/// kept once. Each origin becomes a `{{baseUrl}}` variable (`{{baseUrl2}}`, ... for others),
+/// credentials become `{{token}}`/`{{authorization}}` and `{{cookie}}`, and browser-managed
+/// headers are dropped. The recorded status and response shape become the expectations.
+pub fn har_requests(source: &str) -> Vec<RecordedRequest> {
+    let Ok(har) = serde_json::from_str::<Value>(source) else {
+        return vec![];
+    };
+    let mut origins: Vec<String> = Vec::new();
+    let mut requests: Vec<RecordedRequest> = Vec::new();
+    for entry in har.pointer("/log/entries").and_then(Value::as_array).into_iter().flatten() {
+        let Some(mut recorded) = har_request(entry, &mut origins) else { continue };
+        if requests.iter().any(|r| r.name == recorded.name) {
+            continue;
+        }
+        let defined: Vec<(String, String)> = origins.iter().enumerate()
+            .map(|(i, origin)| (origin_variable(i), origin.clone()))
+            .chain(recorded.variables.drain(..))
+            .collect();
+        recorded.variables = referenced_variables(&recorded, &defined);
+        requests.push(recorded);
+    }
+    requests
+}
+
+fn origin_variable(index: usize) -> String {
+    if index == 0 { "baseUrl".to_string() } else { format!("baseUrl{}", index + 1) }
+}
+
+/// The entry's request with its origin and credentials replaced by variables; the recorded
+/// credentials are left in `variables` for the caller to resolve
+fn har_request(entry: &Value, origins: &mut Vec<String>) -> Option<RecordedRequest> {
+    let request = entry.get("request")?;
+    let response_type = entry.pointer("/response/content/mimeType").and_then(Value::as_str).unwrap_or_default();
+    let is_api_call = match entry.get("_resourceType").and_then(Value::as_str) {
+        Some(resource_type) => matches!(resource_type, "xhr" | "fetch"),
+        None => !["text/html", "text/css", "javascript", "image/", "font/"].iter().any(|kind| response_type.contains(kind)),
+    };
+    if !is_api_call {
+        return None;
+    }
+
+    let url = request.get("url")?.as_str()?;
+    let scheme_end = url.find("://").filter(|_| url.starts_with("http"))? + 3;
+    let path_start = url[scheme_end..].find(['/', '?', '#']).map_or(url.len(), |i| scheme_end + i);
+    let (origin, rest) = url.split_at(path_start);
+    let rest = rest.split('#').next().unwrap_or_default();
+    let index = origins.iter().position(|known| known == origin).unwrap_or_else(|| {
+        origins.push(origin.to_string());
+        origins.len() - 1
+    });
+    let method = match request.get("method")?.as_str()?.to_uppercase().as_str() {
+        "GET" => HttpMethod::Get,
+        "POST" => HttpMethod::Post,
+        "PUT" => HttpMethod::Put,
+        "PATCH" => HttpMethod::Patch,
+        "DELETE" => HttpMethod::Delete,
+        _ => return None,
+    };
+
+    let mut credentials = Vec::new();
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for header in request.get("headers").and_then(Value::as_array).into_iter().flatten() {
+        let (Some(name), Some(value)) = (header.get("name").and_then(Value::as_str), header.get("value").and_then(Value::as_str)) else { continue };
+        let lower = name.to_lowercase();
+        let value = match lower.as_str() {
+            "authorization" => match value.strip_prefix("Bearer ") {
+                Some(token) => {
+                    credentials.push(("token".to_string(), token.to_string()));
+                    "Bearer {{token}}".to_string()
+                }
+                None => {
+                    credentials.push(("authorization".to_string(), value.to_string()));
+                    "{{authorization}}".to_string()
+                }
+            },
+            "cookie" => {
+                credentials.push(("cookie".to_string(), value.to_string()));
+                "{{cookie}}".to_string()
+            }
+            "content-type" | "accept" => value.to_string(),
+            _ if lower.starts_with("x-") => value.to_string(),
+            _ => continue,
+        };
+        if !headers.iter().any(|(known, _)| known.eq_ignore_ascii_case(name)) {
+            headers.push((name.to_string(), value));
+        }
+    }
+
+    let body = request.get("postData").and_then(|post| {
+        if let Some(text) = post.get("text").and_then(Value::as_str).filter(|text| !text.trim().is_empty()) {
+            return Some(match serde_json::from_str(text) {
+                Ok(json) => RecordedBody::Json(json),
+                Err(_) => RecordedBody::Text(text.to_string()),
+            });
+        }
+        let fields: Vec<String> = post.get("params")?.as_array()?.iter()
+            .filter_map(|p| Some(format!("{}={}", p.get("name")?.as_str()?, p.get("value")?.as_str()?)))
+            .collect();
+        (!fields.is_empty()).then(|| RecordedBody::Text(fields.join("&")))
+    });
+    if let Some(mime_type) = request.pointer("/postData/mimeType").and_then(Value::as_str).filter(|_| body.is_some()) {
+        if !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("content-type")) {
+            headers.push(("Content-Type".to_string(), mime_type.to_string()));
+        }
+    }
+
+    let content = entry.pointer("/response/content");
+    let response_text = content
+        .filter(|content| content.get("encoding").and_then(Value::as_str) != Some("base64"))
+        .and_then(|content| content.get("text")?.as_str());
+    let path = rest.split('?').next().filter(|path| !path.is_empty()).unwrap_or("/");
+    Some(RecordedRequest {
+        name: format!("{} {}", method.to_string(), if rest.is_empty() { path } else { rest }),
+        method,
+        url: format!("{{{{{}}}}}{}", origin_variable(index), rest),
+        headers,
+        body,
+        variables: credentials,
+        expected_status: entry.pointer("/response/status").and_then(Value::as_u64).filter(|&status| status > 0).map(|status| status as u16),
+        response_schema: response_text.and_then(response_schema),
+    })
+}
+
+/// JSON Schema for a recorded JSON response: its types and the object keys it had, nested up
+/// to [`MAX_SCHEMA_DEPTH`] levels. Arrays are described by their first item.
+pub fn response_schema(text: &str) -> Option<Value> {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+        return None;
+    }
+    serde_json::from_str::<Value>(text).ok().map(|json| inferred_schema(&json, 0))
+}
+
+/// Nesting past which recorded responses aren't constrained
+const MAX_SCHEMA_DEPTH: usize = 3;
+
+fn inferred_schema(value: &Value, depth: usize) -> Value {
+    if depth >= MAX_SCHEMA_DEPTH {
+        return serde_json::json!({});
+    }
+    match value {
+        Value::Object(map) => serde_json::json!({
+            "type": "object",
+            "properties": map.iter().map(|(key, value)| (key.clone(), inferred_schema(value, depth + 1))).collect::<serde_json::Map<_, _>>(),
+            "required": map.keys().collect::<Vec<_>>(),
+        }),
+        Value::Array(items) => match items.first() {
+            Some(item) => serde_json::json!({"type": "array", "items": inferred_schema(item, depth + 1)}),
+            None => serde_json::json!({"type": "array"}),
+        },
+        Value::String(_) => serde_json::json!({"type": "string"}),
+        Value::Number(n) if n.is_f64() => serde_json::json!({"type": "number"}),
+        Value::Number(_) => serde_json::json!({"type": "integer"}),
+        Value::Bool(_) => serde_json::json!({"type": "boolean"}),
+        // A recorded null says nothing about the field's type
+        Value::Null => serde_json::json!({}),
+    }
+}
+
+/// Header sent for bearer or API key (in header) auth
+fn auth_header(auth: &Value) -> Option<(String, String)> {
+    let setting = |kind: &str, key: &str| -> Option<String> {
+        auth.get(kind)?.as_array()?.iter()
+            .find(|entry| entry.get("key").and_then(Value::as_str) == Some(key))
+            .and_then(|entry| entry.get("value"))
+            .map(value_text)
+    };
+    match auth.get("type")?.as_str()? {
+        "bearer" => Some(("Authorization".to_string(), format!("Bearer {}", setting("bearer", "token")?))),
+        "apikey" if setting("apikey", "in").as_deref() != Some("query") => {
+            Some((setting("apikey", "key").unwrap_or_else(|| "X-API-Key".to_string()), setting("apikey", "value")?))
+        }
+        _ => None,
+    }
+}
+
+fn expected_status(item: &Value) -> Option<u16> {
+    let status_regex = Regex::new(r"pm\.response\.to\.have\.status\(\s*(\d{3})\s*\)|pm\.response\.code\s*\)\s*\.to\.(?:eql|equal|be)\(\s*(\d{3})\s*\)").unwrap();
+    let scripts: Vec<String> = item.get("event").and_then(Value::as_array).into_iter().flatten()
+        .filter(|event| event.get("listen").and_then(Value::as_str) == Some("test"))
+        .filter_map(|event| event.pointer("/script/exec"))
+        .map(|exec| match exec {
+            Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect::<Vec<_>>().join("\n"),
+            other => value_text(other),
+        })
+        .collect();
+
+    scripts.iter()
+        .find_map(|script| status_regex.captures(script))
+        .and_then(|cap| cap.get(1).or_else(|| cap.get(2))?.as_str().parse().ok())
+        .or_else(|| item.pointer("/response/0/code").and_then(Value::as_u64).map(|code| code as u16))
+}
+
+/// Variables a request refers to, with their collection values (empty when the collection
+/// doesn't define them)
+fn referenced_variables(request: &RecordedRequest, defined: &[(String, String)]) -> Vec<(String, String)> {
+    let mut texts: Vec<String> = vec![request.url.clone()];
+    texts.extend(request.headers.iter().map(|(_, value)| value.clone()));
+    match &request.body {
+        Some(RecordedBody::Json(json)) => texts.push(json.to_string()),
+        Some(RecordedBody::Text(text)) => texts.push(text.clone()),
+        None => {}
+    }
+
+    let mut variables: Vec<(String, String)> = Vec::new();
+    for text in &texts {
+        for part in template_parts(text) {
+            if let TemplatePart::Variable(name) = part {
+                if !variables.iter().any(|(known, _)| known == name) {
+                    let value = defined.iter().find(|(key, _)| key == name).map(|(_, value)| value.clone()).unwrap_or_default();
+                    variables.push((name.to_string(), value));
+                }
+            }
+        }
+    }
+    variables
+}
+
+/// Fixed stand-ins for Postman's dynamic variables such as `{{$guid}}`
+fn with_dynamic_values(text: &str) -> String {
+    Regex::new(r"\{\{\s*\$(\w+)\s*\}\}").unwrap().replace_all(text, |cap: &regex::Captures| {
+        match &cap[1] {
+            "guid" | "randomUUID" => "123e4567-e89b-12d3-a456-426614174000",
+            "timestamp" => "1700000000",
+            "isoTimestamp" => "2024-01-01T00:00:00.000Z",
+            "randomInt" => "42",
+            "randomEmail" | "randomExampleEmail" => "test@example.com",
+            _ => "test",
+        }.to_string()
+    }).into_owned()
+}
+
+fn map_strings(value: Value, f: &dyn Fn(&str) -> String) -> Value {
+    match value {
+        Value::String(s) => Value::String(f(&s)),
+        Value::Array(items) => Value::Array(items.into_iter().map(|v| map_strings(v, f)).collect()),
+        Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, map_strings(v, f))).collect()),
+        other => other,
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLLECTION: &str = r#"{
+  "info": {"name": "Users", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"},
+  "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{token}}", "type": "string"}]},
+  "variable": [{"key": "baseUrl", "value": "https://api.example.com"}, {"key": "token", "value": "secret"}],
+  "item": [
+    {
+      "name": "Accounts",
+      "item": [
+        {
+          "name": "Create user",
+          "event": [{"listen": "test", "script": {"exec": ["pm.test(\"created\", function () {", "    pm.response.to.have.status(201);", "});"]}}],
+          "request": {
+            "method": "POST",
+            "header": [{"key": "X-Trace", "value": "{{$guid}}"}, {"key": "X-Debug", "value": "1", "disabled": true}],
+            "body": {"mode": "raw", "raw": "{\"name\": \"Ada\", \"team\": \"{{teamId}}\"}"},
+            "url": {"raw": "{{baseUrl}}/users", "host": ["{{baseUrl}}"], "path": ["users"]}
+          }
+        }
+      ]
+    },
+    {
+      "name": "Health",
+      "request": {"method": "GET", "auth": {"type": "noauth"}, "url": "{{baseUrl}}/health"},
+      "response": [{"name": "ok", "code": 200}]
+    }
+  ]
+}"#;
+
+    #[test]
+    fn test_postman_requests() {
+        assert!(is_postman_collection(COLLECTION));
+        assert!(!is_postman_collection("{\"name\": \"package\"}"));
+
+        let requests = postman_requests(COLLECTION);
+        assert_eq!(requests.len(), 2);
+
+        let create = &requests[0];
+        assert_eq!(create.name, "Accounts / Create user");
+        assert_eq!(create.slug(), "accounts_create_user");
+        assert_eq!(create.url, "{{baseUrl}}/users");
+        assert_eq!(create.headers, vec![
+            ("X-Trace".to_string(), "123e4567-e89b-12d3-a456-426614174000".to_string()),
+            ("Authorization".to_string(), "Bearer {{token}}".to_string()),
+        ]);
+        assert!(matches!(&create.body, Some(RecordedBody::Json(json)) if json["team"] == "{{teamId}}"));
+        assert_eq!(create.variables, vec![
+            ("baseUrl".to_string(), "https://api.example.com".to_string()),
+            ("token".to_string(), "secret".to_string()),
+            ("teamId".to_string(), String::new()),
+        ]);
+        assert_eq!(create.expected_status, Some(201));
+
+        let health = &requests[1];
+        assert!(health.headers.is_empty());
+        assert_eq!(health.expected_status, Some(200));
+    }
+
+    #[test]
+    fn test_har_requests() {
+        let har = r#"{"log": {"version": "1.2", "entries": [
+          {"_resourceType": "document", "request": {"method": "GET", "url": "https://shop.example.com/", "headers": []}, "response": {"status": 200, "content": {"mimeType": "text/html"}}},
+          {"_resourceType": "fetch",
+           "request": {"method": "POST", "url": "https://shop.example.com/api/orders?draft=1", "headers": [
+             {"name": ":authority", "value": "shop.example.com"}, {"name": "Authorization", "value": "Bearer abc"},
+             {"name": "User-Agent", "value": "Mozilla/5.0"}, {"name": "X-Tenant", "value": "acme"}],
+             "postData": {"mimeType": "application/json", "text": "{\"sku\": \"A1\", \"quantity\": 2}"}},
+           "response": {"status": 201, "content": {"mimeType": "application/json", "text": "{\"id\": 7, \"total\": 9.5, \"items\": [{\"sku\": \"A1\"}], \"note\": null}"}}},
+          {"_resourceType": "xhr", "request": {"method": "POST", "url": "https://shop.example.com/api/orders?draft=1", "headers": []}, "response": {"status": 201, "content": {}}}
+        ]}}"#;
+        assert!(is_har_capture(har));
+        assert!(!is_har_capture(COLLECTION));
+
+        let requests = har_requests(har);
+        assert_eq!(requests.len(), 1);
+        let order = &requests[0];
+        assert_eq!(order.name, "POST /api/orders?draft=1");
+        assert_eq!(order.url, "{{baseUrl}}/api/orders?draft=1");
+        assert_eq!(order.headers, vec![
+            ("Authorization".to_string(), "Bearer {{token}}".to_string()),
+            ("X-Tenant".to_string(), "acme".to_string()),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ]);
+        assert_eq!(order.variables, vec![
+            ("baseUrl".to_string(), "https://shop.example.com".to_string()),
+            ("token".to_string(), "abc".to_string()),
+        ]);
+        assert_eq!(order.expected_status, Some(201));
+        assert_eq!(order.response_schema, Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "total": {"type": "number"},
+                "items": {"type": "array", "items": {"type": "object", "properties": {"sku": {}}, "required": ["sku"]}},
+                "note": {},
+            },
+            "required": ["id", "items", "note", "total"],
+        })));
+    }
+
+    #[test]
+    fn test_template_parts() {
+        assert_eq!(template_parts("{{baseUrl}}/users/{{ id }}?q=1"), vec![
+            TemplatePart::Variable("baseUrl"),
+            TemplatePart::Text("/users/"),
+            TemplatePart::Variable("id"),
+            TemplatePart::Text("?q=1"),
+        ]);
+        assert_eq!(variable_env_var("baseUrl"), "BASE_URL");
+        assert_eq!(variable_env_var("api-key"), "API_KEY");
+    }
+}
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+use super::auth_flows::{js_definitions, python_definitions, rust_definitions, Definition};
+
+/// Inputs repeating one character this many times before a `!` that fails the match: a short
+/// one for exponential backtracking and a long one for polynomial
+pub const BACKTRACKING_REPEATS: [usize; 2] = [25, 10_000];
+
+/// Time in milliseconds each backtracking input may take
+pub const BACKTRACKING_BUDGET_MS: u64 = 250;
+
+/// Where a call applies its pattern to the input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegexMode {
+    /// Anywhere: `test`, `re.search`, `is_match`
+    Search,
+    /// At the start: `re.match`
+    Start,
+    /// To the whole input: `re.fullmatch`
+    Full,
+}
+
+/// Groups of edge-case inputs tested against predicates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegexEdge {
+    /// A matching sample, padded with spaces and followed by a newline
+    Anchoring,
+    EmptyString,
+    /// Accented, emoji and CJK input
+    UnicodeInput,
+}
+
+impl RegexEdge {
+    pub fn slug(&self) -> &'static str {
+        match self {
+            RegexEdge::Anchoring => "anchoring",
+            RegexEdge::EmptyString => "empty_string",
+            RegexEdge::UnicodeInput => "unicode_input",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            RegexEdge::Anchoring => "a matching sample padded with spaces or followed by a newline",
+            RegexEdge::EmptyString => "the empty string",
+            RegexEdge::UnicodeInput => "non-ASCII input",
+        }
+    }
+}
+
+/// A function applying a regex literal to its single parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegexUse {
+    pub function: String,
+    pub param: String,
+    /// Declared type of the parameter, where the language has one
+    pub param_type: Option<String>,
+    /// The pattern as written, without delimiters or string escapes
+    pub pattern: String,
+    /// Flags as letters: `i`, `m`, `s`, `x`, `u`, and `a` for Python's `re.ASCII`
+    pub flags: String,
+    pub mode: RegexMode,
+    /// Whether `\w`, `\d` and `.` are Unicode-aware; `None` where that differs by class, as in
+    /// JavaScript without the `u` flag
+    pub unicode: Option<bool>,
+    /// The function only returns whether the parameter matches
+    pub predicate: bool,
+}
+
+/// An input and whether the pattern matches it, when the `regex` crate can run the pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegexCase {
+    pub input: String,
+    pub expected: Option<bool>,
+}
+
+impl RegexUse {
+    /// The pattern as the `regex` crate reads it, with its flags inline and its mode as anchors.
+    /// `$` stays a strict end of input, which is what an anchored validator means even where
+    /// Python lets it match before a trailing newline
+    fn rust_pattern(&self) -> String {
+        let flags: String = self.flags.chars().filter(|flag| "imsx".contains(*flag)).collect();
+        let inline = if flags.is_empty() { String::new() } else { format!("(?{})", flags) };
+        let pattern = self.pattern.replace(r"\Z", r"\z");
+        match self.mode {
+            RegexMode::Search => format!("{}(?:{})", inline, pattern),
+            RegexMode::Start => format!(r"{}\A(?:{})", inline, pattern),
+            RegexMode::Full => format!(r"{}\A(?:{})\z", inline, pattern),
+        }
+    }
+
+    /// Whether the pattern matches `input`, if the `regex` crate supports its syntax
+    pub fn matches(&self, input: &str) -> Option<bool> {
+        let run = |unicode: bool| {
+            regex::bytes::RegexBuilder::new(&self.rust_pattern())
+                .unicode(unicode)
+                .build()
+                .ok()
+                .map(|regex| regex.is_match(input.as_bytes()))
+        };
+        match self.unicode {
+            Some(unicode) => run(unicode),
+            None => run(true).filter(|matched| run(false) == Some(*matched)),
+        }
+    }
+
+    /// A short input the pattern matches, built from its syntax tree
+    pub fn sample(&self) -> Option<String> {
+        let hir = regex_syntax::Parser::new().parse(&self.rust_pattern()).ok()?;
+        let sample = sample_of(&hir);
+        (self.matches(&sample) == Some(true)).then_some(sample)
+    }
+
+    /// Inputs for `edge` with the expected outcome of each
+    pub fn cases(&self, edge: RegexEdge) -> Vec<RegexCase> {
+        let sample = self.sample().unwrap_or_else(|| "abc".to_string());
+        let inputs = match edge {
+            RegexEdge::Anchoring => vec![sample.clone(), format!(" {} ", sample), format!("{}\n", sample)],
+            RegexEdge::EmptyString => vec![String::new()],
+            RegexEdge::UnicodeInput => vec![format!("é{}", sample), format!("{}😀", sample), "日本語".to_string()],
+        };
+        inputs.into_iter().map(|input| RegexCase { expected: self.matches(&input), input }).collect()
+    }
+
+    /// The character repeated by the backtracking inputs: the first of the sample, which the
+    /// pattern's leading repetition accepts
+    pub fn backtracking_unit(&self) -> char {
+        self.sample().and_then(|sample| sample.chars().next()).unwrap_or('a')
+    }
+}
+
+/// The shortest expansion of each node, taking repetitions at least once so optional parts
+/// show up in the sample
+fn sample_of(hir: &Hir) -> String {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => String::new(),
+        HirKind::Literal(literal) => String::from_utf8_lossy(&literal.0).into_owned(),
+        HirKind::Class(Class::Unicode(class)) => {
+            let contains = |c: char| class.ranges().iter().any(|range| range.start() <= c && c <= range.end());
+            ['a', 'A', '0', '_', '-', '.', ' '].into_iter().find(|c| contains(*c))
+                .or_else(|| class.ranges().iter().map(|range| range.start()).find(|c| !c.is_control()))
+                .or_else(|| class.ranges().first().map(|range| range.start()))
+                .map(String::from)
+                .unwrap_or_default()
+        }
+        HirKind::Class(Class::Bytes(class)) => class.ranges().first()
+            .map(|range| char::from(range.start()).to_string())
+            .unwrap_or_default(),
+        HirKind::Repetition(repetition) => {
+            let count = repetition.min.max(1).min(repetition.max.unwrap_or(u32::MAX));
+            sample_of(&repetition.sub).repeat(count as usize)
+        }
+        HirKind::Capture(capture) => sample_of(&capture.sub),
+        HirKind::Concat(parts) => parts.iter().map(sample_of).collect(),
+        HirKind::Alternation(branches) => sample_of(&branches[0]),
+    }
+}
+
+const JS_LITERAL: &str = r"/((?:[^/\\\[\n]|\\.|\[(?:[^\]\\\n]|\\.)*\])+)/([dgimsuyv]*)";
+const PY_STRING: &str = r#"([rRuU]?)("(?:[^"\\\n]|\\.)*"|'(?:[^'\\\n]|\\.)*')"#;
+const RUST_STRING: &str = r##"r"([^"]*)"|r#"((?s:.)*?)"#|"((?:[^"\\]|\\.)*)""##;
+
+/// A call applying a pattern to an identifier
+struct Application {
+    start: usize,
+    end: usize,
+    pattern: String,
+    flags: String,
+    mode: RegexMode,
+    arg: String,
+    /// The identifier is the whole argument and the call a yes/no match
+    predicate_call: bool,
+}
+
+/// Regex literals and `new RegExp('...')` strings applied with `test`/`exec`, or passed to a
+/// string's `match`/`search`/`replace`/`split`
+pub fn js_regex_uses(source: &str) -> Vec<RegexUse> {
+    let named_regex = Regex::new(&format!(
+        r#"(?:const|let|var)\s+(\w+)\s*=\s*(?:{}|new\s+RegExp\(\s*(?:'((?:[^'\\\n]|\\.)*)'|"((?:[^"\\\n]|\\.)*)")\s*(?:,\s*['"]([dgimsuyv]*)['"]\s*)?\))"#,
+        JS_LITERAL
+    )).unwrap();
+    let named: HashMap<String, (String, String)> = named_regex.captures_iter(source).map(|cap| {
+        let pattern = match (cap.get(2), cap.get(4).or(cap.get(5))) {
+            (Some(literal), _) => literal.as_str().to_string(),
+            (None, string) => unescape(string.map(|m| m.as_str()).unwrap_or("")),
+        };
+        let flags = cap.get(3).or(cap.get(6)).map(|m| m.as_str()).unwrap_or("").to_string();
+        (cap[1].to_string(), (pattern, flags))
+    }).collect();
+
+    let mut applications = Vec::new();
+    let tested = Regex::new(&format!(r"(?:{}|\b(\w+))\.(test|exec)\(\s*(\w+)(\s*\))?", JS_LITERAL)).unwrap();
+    for cap in tested.captures_iter(source) {
+        let Some((pattern, flags)) = literal_or_named(cap.get(1).map(|m| (m.as_str(), cap.get(2).map_or("", |f| f.as_str()))), cap.get(3), &named) else { continue };
+        let whole = cap.get(0).unwrap();
+        applications.push(Application {
+            start: whole.start(),
+            end: whole.end(),
+            pattern,
+            flags,
+            mode: RegexMode::Search,
+            arg: cap[5].to_string(),
+            predicate_call: &cap[4] == "test" && cap.get(6).is_some(),
+        });
+    }
+    let passed = Regex::new(&format!(r"\b(\w+)(?:\.\w+\(\))*\.(?:match|matchAll|search|replace|replaceAll|split)\(\s*(?:{}|(\w+)\s*[,)])", JS_LITERAL)).unwrap();
+    for cap in passed.captures_iter(source) {
+        let Some((pattern, flags)) = literal_or_named(cap.get(2).map(|m| (m.as_str(), cap.get(3).map_or("", |f| f.as_str()))), cap.get(4), &named) else { continue };
+        let whole = cap.get(0).unwrap();
+        applications.push(Application {
+            start: whole.start(),
+            end: whole.end(),
+            pattern,
+            flags,
+            mode: RegexMode::Search,
+            arg: cap[1].to_string(),
+            predicate_call: false,
+        });
+    }
+
+    let head = Regex::new(r"^[^{]*?\)\s*(?:\{\s*(?:(?:const|let|var)\s+\w+\s*=\s*(?:/|new\s+RegExp\()[^\n]*\n\s*)?return\s+|=>\s*(?:\{\s*return\s+)?)$").unwrap();
+    let tail = Regex::new(r"^\s*;?\s*(?:\}|\n|$)").unwrap();
+    regex_uses(
+        &js_definitions(source),
+        applications,
+        |def| {
+            let text = &source[def.start..];
+            text.starts_with("function") || text[..text.find('(').unwrap_or(0)].contains('=')
+        },
+        |def, app| head.is_match(&source[def.start..app.start]) && tail.is_match(&source[app.end..]),
+        |flags| (flags.contains('u') || flags.contains('v')).then_some(true),
+    )
+}
+
+/// `re.match`/`fullmatch`/`search`/`findall`/`finditer`/`split` calls with a pattern string,
+/// and the same methods on patterns from `re.compile`
+pub fn python_regex_uses(source: &str) -> Vec<RegexUse> {
+    let named_regex = Regex::new(&format!(r"(?m)^[ \t]*(\w+)\s*=\s*re\.compile\(\s*{}\s*(?:,\s*(?:flags\s*=\s*)?([^)]*?))?\s*\)", PY_STRING)).unwrap();
+    let named: HashMap<String, (String, String)> = named_regex.captures_iter(source)
+        .map(|cap| (cap[1].to_string(), (python_string(&cap[2], &cap[3]), python_flags(cap.get(4).map_or("", |m| m.as_str())))))
+        .collect();
+    let mode = |method: &str| match method {
+        "match" => RegexMode::Start,
+        "fullmatch" => RegexMode::Full,
+        _ => RegexMode::Search,
+    };
+
+    let mut applications = Vec::new();
+    let module_call = Regex::new(&format!(
+        r"\bre\.(match|fullmatch|search|findall|finditer|split)\(\s*{}\s*,\s*(\w+)(?:\s*,\s*(?:flags\s*=\s*)?(re\.\w+(?:\s*\|\s*re\.\w+)*))?(\s*\))?",
+        PY_STRING
+    )).unwrap();
+    for cap in module_call.captures_iter(source) {
+        let whole = cap.get(0).unwrap();
+        applications.push(Application {
+            start: whole.start(),
+            end: whole.end(),
+            pattern: python_string(&cap[2], &cap[3]),
+            flags: python_flags(cap.get(5).map_or("", |m| m.as_str())),
+            mode: mode(&cap[1]),
+            arg: cap[4].to_string(),
+            predicate_call: matches!(&cap[1], "match" | "fullmatch" | "search") && cap.get(6).is_some(),
+        });
+    }
+    let method_call = Regex::new(r"\b(\w+)\.(match|fullmatch|search|findall|finditer|split)\(\s*(\w+)(\s*\))?").unwrap();
+    for cap in method_call.captures_iter(source) {
+        let Some((pattern, flags)) = named.get(&cap[1]).cloned() else { continue };
+        let whole = cap.get(0).unwrap();
+        applications.push(Application {
+            start: whole.start(),
+            end: whole.end(),
+            pattern,
+            flags,
+            mode: mode(&cap[2]),
+            arg: cap[3].to_string(),
+            predicate_call: matches!(&cap[2], "match" | "fullmatch" | "search") && cap.get(4).is_some(),
+        });
+    }
+
+    let head = Regex::new(r#"^[^\n]*:[ \t]*\n(?:\s*(?:"""(?s:.)*?"""|'''(?s:.)*?''')[ \t]*\n)?(?:\s*\w+\s*=\s*re\.compile\([^\n]*\n)?\s*return\s+(?:bool\(\s*)?$"#).unwrap();
+    let tail = Regex::new(r"^\s*\)?(?:\s+is\s+not\s+None)?[ \t]*(?:#[^\n]*)?(?:\n|$)").unwrap();
+    regex_uses(
+        &python_definitions(source),
+        applications,
+        |def| !source[def.start..].starts_with(char::is_whitespace),
+        |def, app| head.is_match(&source[def.start..app.start]) && tail.is_match(&source[app.end..]),
+        |flags| Some(!flags.contains('a')),
+    )
+}
+
+/// `Regex::new` patterns, inline or bound to a name (including `Lazy` and `lazy_static!`
+/// statics), used through `is_match`, `find`, `captures`, `replace` or `split`
+pub fn rust_regex_uses(source: &str) -> Vec<RegexUse> {
+    let named_regex = Regex::new(&format!(r"\b(\w+)\s*(?::[^=;]*)?=\s*[^;]*?Regex::new\(\s*(?:{})", RUST_STRING)).unwrap();
+    let named: HashMap<String, (String, String)> = named_regex.captures_iter(source)
+        .map(|cap| (cap[1].to_string(), (rust_string(&cap, 2), String::new())))
+        .collect();
+    let methods = "is_match|find|find_iter|captures|captures_iter|split|replace|replace_all";
+
+    let mut applications = Vec::new();
+    let inline = Regex::new(&format!(r"Regex::new\(\s*(?:{})\s*\)\s*\.(?:unwrap\(\)|expect\([^)]*\))\s*\.({})\(\s*&?(\w+)(\s*\))?", RUST_STRING, methods)).unwrap();
+    for cap in inline.captures_iter(source) {
+        let whole = cap.get(0).unwrap();
+        applications.push(Application {
+            start: whole.start(),
+            end: whole.end(),
+            pattern: rust_string(&cap, 1),
+            flags: String::new(),
+            mode: RegexMode::Search,
+            arg: cap[5].to_string(),
+            predicate_call: &cap[4] == "is_match" && cap.get(6).is_some(),
+        });
+    }
+    let method_call = Regex::new(&format!(r"\b(\w+)\.({})\(\s*&?(\w+)(\s*\))?", methods)).unwrap();
+    for cap in method_call.captures_iter(source) {
+        let Some((pattern, flags)) = named.get(&cap[1]).cloned() else { continue };
+        let whole = cap.get(0).unwrap();
+        applications.push(Application {
+            start: whole.start(),
+            end: whole.end(),
+            pattern,
+            flags,
+            mode: RegexMode::Search,
+            arg: cap[3].to_string(),
+            predicate_call: &cap[2] == "is_match" && cap.get(4).is_some(),
+        });
+    }
+
+    let head = Regex::new(r"^[^{]*\{\s*(?:let\s+\w+\s*=\s*Regex::new\([^;]*;\s*)?(?:return\s+)?$").unwrap();
+    let tail = Regex::new(r"^\s*;?\s*\}").unwrap();
+    regex_uses(
+        &rust_definitions(source),
+        applications,
+        |def| !def.params.iter().any(|p| p.ends_with("self")),
+        |def, app| def.returns.as_deref() == Some("bool") && head.is_match(&source[def.start..app.start]) && tail.is_match(&source[app.end..]),
+        |_| Some(true),
+    )
+}
+
+/// One use per function of a single parameter that a pattern is applied to. `callable`
+/// excludes methods; `predicate` checks the function does nothing but return the match
+fn regex_uses(
+    defs: &[Definition],
+    mut applications: Vec<Application>,
+    callable: impl Fn(&Definition) -> bool,
+    predicate: impl Fn(&Definition, &Application) -> bool,
+    unicode: impl Fn(&str) -> Option<bool>,
+) -> Vec<RegexUse> {
+    applications.sort_by_key(|app| app.start);
+    let mut uses: Vec<RegexUse> = Vec::new();
+    for app in &applications {
+        let Some(def) = defs.iter().filter(|d| d.start < app.start).max_by_key(|d| d.start) else { continue };
+        if def.params.len() != 1 || !callable(def) || uses.iter().any(|u| u.function == def.name) {
+            continue;
+        }
+        let (param, param_type) = split_param(&def.params[0]);
+        if param != app.arg {
+            continue;
+        }
+        uses.push(RegexUse {
+            function: def.name.clone(),
+            param,
+            param_type,
+            pattern: app.pattern.clone(),
+            flags: app.flags.clone(),
+            mode: app.mode,
+            unicode: unicode(&app.flags),
+            predicate: app.predicate_call && predicate(def, app),
+        });
+    }
+    uses
+}
+
+/// Name and declared type of a parameter, without a default value
+fn split_param(param: &str) -> (String, Option<String>) {
+    let param = param.split('=').next().unwrap_or(param).trim();
+    match param.split_once(':') {
+        Some((name, ty)) => (name.trim().trim_start_matches("mut ").to_string(), Some(ty.trim().to_string())),
+        None => (param.to_string(), None),
+    }
+}
+
+fn literal_or_named(
+    literal: Option<(&str, &str)>,
+    name: Option<regex::Match>,
+    named: &HashMap<String, (String, String)>,
+) -> Option<(String, String)> {
+    match literal {
+        Some((pattern, flags)) => Some((pattern.to_string(), flags.to_string())),
+        None => named.get(name?.as_str()).cloned(),
+    }
+}
+
+/// A Python string literal's value: raw strings as written, others unescaped
+fn python_string(prefix: &str, quoted: &str) -> String {
+    let inner = &quoted[1..quoted.len() - 1];
+    if prefix.eq_ignore_ascii_case("r") { inner.to_string() } else { unescape(inner) }
+}
+
+/// `re.IGNORECASE | re.M` as flag letters
+fn python_flags(flags: &str) -> String {
+    flags.split('|').filter_map(|flag| match flag.trim().trim_start_matches("re.") {
+        "I" | "IGNORECASE" => Some('i'),
+        "M" | "MULTILINE" => Some('m'),
+        "S" | "DOTALL" => Some('s'),
+        "X" | "VERBOSE" => Some('x'),
+        "A" | "ASCII" => Some('a'),
+        _ => None,
+    }).collect()
+}
+
+/// The value of whichever of the raw, hashed raw and plain string groups starting at `first`
+/// matched
+fn rust_string(cap: &regex::Captures, first: usize) -> String {
+    match (cap.get(first), cap.get(first + 1), cap.get(first + 2)) {
+        (Some(raw), _, _) | (None, Some(raw), _) => raw.as_str().to_string(),
+        (None, None, Some(plain)) => unescape(plain.as_str()),
+        _ => String::new(),
+    }
+}
+
+/// Resolve string escapes that change a pattern: `\\` to `\` and escaped quotes and newlines
+fn unescape(string: &str) -> String {
+    let mut result = String::new();
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(escaped @ ('\\' | '\'' | '"')) => result.push(escaped),
+            Some('n') => result.push('\n'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regex_uses_by_language() {
+        let uses = js_regex_uses("const ZIP = /^\\d{5}$/;\n\nfunction isZip(code) {\n  return ZIP.test(code);\n}\n\nconst slug = (title) => title.toLowerCase().replace(/[^a-z0-9]+/g, '-');\n");
+        assert_eq!(uses.len(), 2);
+        assert_eq!((uses[0].function.as_str(), uses[0].predicate, uses[0].unicode), ("isZip", true, None));
+        assert_eq!(uses[0].sample().as_deref(), Some("00000"));
+        assert_eq!(uses[0].cases(RegexEdge::Anchoring).iter().map(|c| c.expected).collect::<Vec<_>>(), vec![Some(true), Some(false), Some(false)]);
+        assert_eq!((uses[1].pattern.as_str(), uses[1].predicate), ("[^a-z0-9]+", false));
+
+        let uses = python_regex_uses("import re\n\nUSERNAME = re.compile(r\"^[a-z_]+$\", re.IGNORECASE)\n\ndef valid_username(name):\n    return bool(USERNAME.match(name))\n");
+        assert_eq!((uses[0].mode, uses[0].flags.as_str(), uses[0].predicate), (RegexMode::Start, "i", true));
+        assert_eq!(uses[0].cases(RegexEdge::EmptyString)[0].expected, Some(false));
+        assert_eq!(uses[0].cases(RegexEdge::UnicodeInput)[2].expected, Some(false));
+
+        let uses = rust_regex_uses("pub fn is_hex(s: &str) -> bool {\n    Regex::new(r\"^(?:[0-9a-f]{2})+$\").unwrap().is_match(s)\n}\n");
+        assert_eq!((uses[0].param_type.as_deref(), uses[0].predicate), (Some("&str"), true));
+        assert_eq!(uses[0].sample().as_deref(), Some("aa"));
+    }
+}
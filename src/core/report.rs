@@ -0,0 +1,251 @@
+use regex::Regex;
+use std::fmt::Write as _;
+
+use super::{untested_functions, TestSuite, TestablePattern};
+
+/// How many patterns fall in each confidence band: high from 0.8, medium from 0.5, low below
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConfidenceBands {
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+
+impl ConfidenceBands {
+    pub fn add(&mut self, confidence: f32) {
+        match confidence {
+            c if c >= 0.8 => self.high += 1,
+            c if c >= 0.5 => self.medium += 1,
+            _ => self.low += 1,
+        }
+    }
+
+    fn merge(&mut self, other: ConfidenceBands) {
+        self.high += other.high;
+        self.medium += other.medium;
+        self.low += other.low;
+    }
+}
+
+/// What generation found and produced for one source file
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub source_file: String,
+    pub language: String,
+    pub patterns: usize,
+    pub tests_generated: usize,
+    pub confidence: ConfidenceBands,
+    /// Public functions the file's existing tests neither call nor name a test after
+    pub untested: Vec<String>,
+    /// Whether the file has a test file at all
+    pub has_tests: bool,
+}
+
+impl FileReport {
+    /// Report for `source_file` from its detected patterns, the suite generated from them and
+    /// the tests it already has (empty when it has none)
+    pub fn new(source_file: &str, source: &str, patterns: &[TestablePattern], suite: &TestSuite, existing_tests: &str) -> Self {
+        let mut confidence = ConfidenceBands::default();
+        for pattern in patterns {
+            confidence.add(pattern.confidence);
+        }
+        let untested = untested_functions(patterns, existing_tests).into_iter()
+            .filter(|pattern| is_public(&suite.language, source, pattern.subject_name()))
+            .map(|pattern| pattern.subject_name().to_string())
+            .collect();
+
+        Self {
+            source_file: source_file.to_string(),
+            language: suite.language.clone(),
+            patterns: patterns.len(),
+            tests_generated: suite.test_cases.len(),
+            confidence,
+            untested,
+            has_tests: !existing_tests.trim().is_empty(),
+        }
+    }
+}
+
+/// Per-file summary of a generation run, rendered for a browser or a PR comment
+#[derive(Debug, Clone, Default)]
+pub struct GenerationReport {
+    pub files: Vec<FileReport>,
+}
+
+impl GenerationReport {
+    pub fn patterns(&self) -> usize {
+        self.files.iter().map(|file| file.patterns).sum()
+    }
+
+    pub fn tests_generated(&self) -> usize {
+        self.files.iter().map(|file| file.tests_generated).sum()
+    }
+
+    pub fn untested(&self) -> usize {
+        self.files.iter().map(|file| file.untested.len()).sum()
+    }
+
+    pub fn confidence(&self) -> ConfidenceBands {
+        let mut bands = ConfidenceBands::default();
+        for file in &self.files {
+            bands.merge(file.confidence);
+        }
+        bands
+    }
+
+    /// Markdown fragment, headed by a level-3 heading so it nests in a PR comment
+    pub fn to_markdown(&self) -> String {
+        let confidence = self.confidence();
+        let mut out = String::from("### Test generation report\n\n");
+        let _ = writeln!(
+            out,
+            "**{}** files · **{}** patterns · **{}** tests generated · **{}** untested public functions\n",
+            self.files.len(), self.patterns(), self.tests_generated(), self.untested()
+        );
+        let _ = writeln!(out, "Confidence: {} high · {} medium · {} low\n", confidence.high, confidence.medium, confidence.low);
+        out.push_str("| File | Language | Patterns | Tests | Confidence (high/medium/low) | Untested |\n");
+        out.push_str("|------|----------|---------:|------:|------------------------------|----------|\n");
+        for file in &self.files {
+            let untested = if file.untested.is_empty() {
+                "—".to_string()
+            } else {
+                file.untested.iter().map(|name| format!("`{}`", name)).collect::<Vec<_>>().join(", ")
+            };
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {}/{}/{} | {} |",
+                file.source_file, file.language, file.patterns, file.tests_generated,
+                file.confidence.high, file.confidence.medium, file.confidence.low, untested
+            );
+        }
+        out
+    }
+
+    /// Standalone HTML page
+    pub fn to_html(&self) -> String {
+        let confidence = self.confidence();
+        let mut rows = String::new();
+        for file in &self.files {
+            let untested = file.untested.iter().map(|name| format!("<code>{}</code>", escape_html(name))).collect::<Vec<_>>().join(", ");
+            let _ = writeln!(
+                rows,
+                "      <tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{} / {} / {}</td><td>{}</td></tr>",
+                escape_html(&file.source_file), escape_html(&file.language), file.patterns, file.tests_generated,
+                file.confidence.high, file.confidence.medium, file.confidence.low,
+                if untested.is_empty() { "—".to_string() } else { untested }
+            );
+        }
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Test generation report</title>
+  <style>
+    body {{ font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }}
+    table {{ border-collapse: collapse; width: 100%; }}
+    th, td {{ border: 1px solid #d0d7de; padding: 0.4rem 0.6rem; text-align: left; }}
+    th {{ background: #f6f8fa; }}
+    .summary span {{ margin-right: 1.5rem; }}
+  </style>
+</head>
+<body>
+  <h1>Test generation report</h1>
+  <p class="summary"><span><strong>{}</strong> files</span><span><strong>{}</strong> patterns</span><span><strong>{}</strong> tests generated</span><span><strong>{}</strong> untested public functions</span></p>
+  <p>Confidence: {} high · {} medium · {} low</p>
+  <table>
+    <thead>
+      <tr><th>File</th><th>Language</th><th>Patterns</th><th>Tests</th><th>Confidence (high / medium / low)</th><th>Untested</th></tr>
+    </thead>
+    <tbody>
+{}    </tbody>
+  </table>
+</body>
+</html>
+"#,
+            self.files.len(), self.patterns(), self.tests_generated(), self.untested(),
+            confidence.high, confidence.medium, confidence.low, rows
+        )
+    }
+}
+
+/// Whether a function belongs to its file's public surface: not `_`-prefixed, declared `pub`
+/// in Rust, capitalized in Go and not `private` in Java or Kotlin
+pub fn is_public(language: &str, source: &str, name: &str) -> bool {
+    let short = name.rsplit(['.', ':']).next().unwrap_or(name);
+    if short.starts_with('_') {
+        return false;
+    }
+    // A keyword-introduced definition, else the first mention followed by a parameter list,
+    // which covers Java and Kotlin methods declared by their return type
+    let definition = Regex::new(&format!(r"\b(?:fn|def|func|function|fun|class)\s+(?:\([^)]*\)\s*)?{}\b", regex::escape(short))).unwrap();
+    let signature = Regex::new(&format!(r"\b{}\s*\(", regex::escape(short))).unwrap();
+    let line = source.lines().find(|line| definition.is_match(line))
+        .or_else(|| source.lines().find(|line| signature.is_match(line)))
+        .unwrap_or_default()
+        .trim_start();
+    match language {
+        "rust" => line.starts_with("pub"),
+        "go" => short.starts_with(|c: char| c.is_uppercase()),
+        "java" | "kotlin" => !line.contains("private"),
+        _ => true,
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Context, FunctionPattern, PatternType, SourceLocation, TestType};
+
+    fn function(name: &str, confidence: f32) -> TestablePattern {
+        TestablePattern {
+            id: name.to_string(),
+            pattern_type: PatternType::Function(FunctionPattern { name: name.to_string(), parameters: vec![], return_type: None }),
+            location: SourceLocation { file: "lib.rs".to_string(), line: 1, column: 1 },
+            context: Context { function_name: Some(name.to_string()), class_name: None, module_name: None },
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_file_report_lists_untested_public_functions() {
+        let source = "pub fn add(a: i32, b: i32) -> i32 { a + b }\npub fn sub(a: i32, b: i32) -> i32 { a - b }\nfn helper() {}\n";
+        let suite = TestSuite {
+            name: "lib".to_string(),
+            language: "rust".to_string(),
+            framework: "cargo-test".to_string(),
+            test_cases: vec![],
+            imports: vec![],
+            test_type: TestType::Unit,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 80.0,
+            fixtures: vec![],
+            test_code: None,
+        };
+        let patterns = [function("add", 0.9), function("sub", 0.6), function("helper", 0.3)];
+
+        let report = FileReport::new("src/lib.rs", source, &patterns, &suite, "#[test]\nfn test_add() {}\n");
+        assert_eq!(report.untested, vec!["sub"]);
+        assert_eq!(report.confidence, ConfidenceBands { high: 1, medium: 1, low: 1 });
+
+        let report = GenerationReport { files: vec![report] };
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| `src/lib.rs` | rust | 3 | 0 | 1/1/1 | `sub` |"));
+        assert!(report.to_html().contains("<tr><td><code>src/lib.rs</code></td>"));
+    }
+
+    #[test]
+    fn test_is_public() {
+        assert!(!is_public("python", "def _helper():\n", "_helper"));
+        assert!(is_public("go", "func Add(a, b int) int {\n", "Add"));
+        assert!(!is_public("go", "func (c *Calc) add(a int) int {\n", "add"));
+        assert!(!is_public("java", "    private int twice(int x) {\n", "twice"));
+        assert!(is_public("javascript", "export function add(a, b) {\n", "add"));
+    }
+}
@@ -0,0 +1,301 @@
+use regex::Regex;
+
+use super::auth_flows::{call_args, js_definitions, Definition};
+use super::cli::braced;
+
+/// The library a type serializes with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SerializationLibrary {
+    Serde,
+    Pydantic,
+    Jackson,
+}
+
+/// A field of a serializable type
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerialField {
+    pub name: String,
+    /// Key in the JSON form, after renames and aliases
+    pub key: String,
+    pub ty: String,
+    /// May be left out of the JSON: an `Option` or a field with a default
+    pub has_default: bool,
+    /// Left out of the JSON entirely, e.g. `#[serde(skip)]` or `@JsonIgnore`
+    pub skipped: bool,
+}
+
+/// A type deriving serde's traits, extending pydantic's `BaseModel` or annotated for Jackson
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerializableType {
+    pub name: String,
+    pub library: SerializationLibrary,
+    pub fields: Vec<SerialField>,
+    pub serializes: bool,
+    pub deserializes: bool,
+    /// Values can be compared directly: `PartialEq` and `Debug` in Rust, always for pydantic
+    pub comparable: bool,
+    /// Tests can build a value: a struct with named fields and no generics, or a Java class
+    /// Jackson can instantiate
+    pub constructible: bool,
+}
+
+impl SerializableType {
+    /// Fields the JSON must contain for deserialization to succeed
+    pub fn required_fields(&self) -> Vec<&SerialField> {
+        self.fields.iter().filter(|f| !f.has_default && !f.skipped).collect()
+    }
+}
+
+/// JSON that fails to parse: an object cut off after its first key
+pub fn malformed_json(ty: &SerializableType) -> String {
+    let key = ty.fields.iter().find(|f| !f.skipped).map(|f| f.key.as_str()).unwrap_or("id");
+    format!("{{\"{}\": ", key)
+}
+
+/// Structs deriving `Serialize` and/or `Deserialize`
+pub fn serde_types(source: &str) -> Vec<SerializableType> {
+    let item = Regex::new(r"(?m)^[ \t]*((?:#\[[^\n]*\][ \t]*\n[ \t]*)+)(?:pub(?:\([^)]*\))?\s+)?struct\s+(\w+)(<[^>{]*>)?\s*(\{|\(|;)").unwrap();
+    let field = Regex::new(r"(?m)^[ \t]*((?:#\[[^\n]*\][ \t]*\n[ \t]*)*)(?:pub(?:\([^)]*\))?\s+)?(\w+)\s*:\s*([^\n]+?)\s*,?\s*$").unwrap();
+    let rename = Regex::new(r#"\brename\s*=\s*"([^"]+)""#).unwrap();
+    let rename_all = Regex::new(r#"\brename_all\s*=\s*"([^"]+)""#).unwrap();
+    let skip = Regex::new(r"#\[serde\([^\]]*\bskip(?:_deserializing)?\b").unwrap();
+    let default = Regex::new(r"#\[serde\([^\]]*\bdefault\b").unwrap();
+
+    item.captures_iter(source).filter_map(|cap| {
+        let attributes = &cap[1];
+        let derives = |name: &str| Regex::new(&format!(r"derive\([^)]*\b{}\b", name)).unwrap().is_match(attributes);
+        let (serializes, deserializes) = (derives("Serialize"), derives("Deserialize"));
+        if !serializes && !deserializes {
+            return None;
+        }
+        let named = &cap[4] == "{";
+        let body = if named { braced(source, cap.get(4).unwrap().start()) } else { "" };
+        let case = rename_all.captures(attributes).map(|c| c[1].to_string());
+        let fields = field.captures_iter(body).map(|f| {
+            let (field_attributes, name, ty) = (&f[1], f[2].to_string(), f[3].trim().to_string());
+            let key = rename.captures(field_attributes).map(|c| c[1].to_string())
+                .unwrap_or_else(|| rename_case(&name, case.as_deref()));
+            SerialField {
+                has_default: ty.starts_with("Option<") || default.is_match(field_attributes) || default.is_match(attributes),
+                skipped: skip.is_match(field_attributes),
+                name,
+                key,
+                ty,
+            }
+        }).collect();
+        Some(SerializableType {
+            name: cap[2].to_string(),
+            library: SerializationLibrary::Serde,
+            fields,
+            serializes,
+            deserializes,
+            comparable: derives("PartialEq") && derives("Debug"),
+            constructible: named && cap.get(3).is_none(),
+        })
+    }).collect()
+}
+
+/// Classes extending pydantic's `BaseModel`, with their annotated fields
+pub fn pydantic_models(source: &str) -> Vec<SerializableType> {
+    let class = Regex::new(r"(?m)^class\s+(\w+)\s*\(\s*(?:pydantic\.)?BaseModel\s*\)\s*:").unwrap();
+    let field = Regex::new(r"^[ \t]+(\w+)\s*:\s*([^=\n]+?)\s*(?:=\s*(.+?))?\s*$").unwrap();
+    let alias = Regex::new(r#"\balias\s*=\s*["']([^"']+)["']"#).unwrap();
+    let required = Regex::new(r"^Field\(\s*(?:\.\.\.|\)|\w+\s*=)").unwrap();
+
+    class.captures_iter(source).map(|cap| {
+        let body = &source[cap.get(0).unwrap().end()..];
+        let mut fields = Vec::new();
+        for line in body.lines().skip(1) {
+            if !line.trim().is_empty() && !line.starts_with(char::is_whitespace) {
+                break;
+            }
+            let Some(f) = field.captures(line) else { continue };
+            let (name, ty) = (f[1].to_string(), f[2].trim().to_string());
+            if name == "model_config" || ty.starts_with("ClassVar") {
+                continue;
+            }
+            let default = f.get(3).map(|d| d.as_str());
+            let has_default = match default {
+                Some(d) if d.starts_with("Field(") => !required.is_match(d) || d.contains("default"),
+                Some(_) => true,
+                None => false,
+            };
+            fields.push(SerialField {
+                key: default.and_then(|d| alias.captures(d)).map(|c| c[1].to_string()).unwrap_or_else(|| name.clone()),
+                name,
+                ty,
+                has_default,
+                skipped: false,
+            });
+        }
+        SerializableType {
+            name: cap[1].to_string(),
+            library: SerializationLibrary::Pydantic,
+            fields,
+            serializes: true,
+            deserializes: true,
+            comparable: true,
+            constructible: true,
+        }
+    }).collect()
+}
+
+/// Classes and records in a file importing Jackson whose declaration, components or fields carry a
+/// `@Json...` annotation
+pub fn jackson_types(source: &str) -> Vec<SerializableType> {
+    if !source.contains("com.fasterxml.jackson") {
+        return Vec::new();
+    }
+    let declaration = Regex::new(r"((?:@[\w.]+(?:\([^)]*\))?\s*)*)(?:public\s+|protected\s+|private\s+)?(?:static\s+)?(?:final\s+)?(class|record)\s+(\w+)([^{;]*)\{").unwrap();
+    let annotation = Regex::new(r"@[\w.]+(?:\([^)]*\))?").unwrap();
+    let field = Regex::new(r"((?:@[\w.]+(?:\([^)]*\))?\s*)*)(?:private|protected|public)\s+(?:final\s+)?([\w.]+(?:<[^;=()]*>)?(?:\[\])?)\s+(\w+)\s*(?:=[^;]*)?;").unwrap();
+    let property = Regex::new(r#"@JsonProperty\(\s*(?:value\s*=\s*)?"([^"]+)""#).unwrap();
+
+    declaration.captures_iter(source).filter_map(|cap| {
+        let name = cap[3].to_string();
+        let open = cap.get(0).unwrap().end() - 1;
+        let body = braced(source, open);
+        let members = outside_braces(body);
+        if ![&cap[1], &cap[4], &members].iter().any(|part| part.contains("@Json")) {
+            return None;
+        }
+        let snake_case = cap[1].contains("SnakeCaseStrategy");
+        let key = |annotations: &str, field: &str| property.captures(annotations).map(|c| c[1].to_string())
+            .unwrap_or_else(|| if snake_case { rename_case(field, Some("snake_case")) } else { field.to_string() });
+
+        let fields: Vec<SerialField> = if &cap[2] == "record" {
+            let open = cap.get(4).unwrap().start() + cap[4].find('(').unwrap_or(0);
+            call_args(source, open).into_iter().filter_map(|param| {
+                let declared = annotation.replace_all(&param, "");
+                let mut words = declared.split_whitespace().rev();
+                let (field_name, ty) = (words.next()?, words.next()?);
+                Some(SerialField { key: key(&param, field_name), name: field_name.to_string(), ty: ty.to_string(), has_default: false, skipped: false })
+            }).collect()
+        } else {
+            field.captures_iter(&members)
+                .filter(|f| !f[0].contains(" static "))
+                .map(|f| SerialField {
+                    key: key(&f[1], &f[3]),
+                    name: f[3].to_string(),
+                    ty: f[2].to_string(),
+                    has_default: true,
+                    skipped: f[1].contains("@JsonIgnore") && !f[1].contains("@JsonIgnoreProperties"),
+                })
+                .collect()
+        };
+        let constructors = Regex::new(&format!(r"(?m)^\s*(?:public\s+|protected\s+|private\s+)?{}\s*\(([^)]*)\)", name)).unwrap();
+        let no_arg = constructors.captures_iter(&members).all(|c| c[1].trim().is_empty()) || members.contains("@JsonCreator");
+        Some(SerializableType {
+            name,
+            library: SerializationLibrary::Jackson,
+            constructible: &cap[2] == "record" || no_arg,
+            fields,
+            serializes: true,
+            deserializes: true,
+            comparable: false,
+        })
+    }).collect()
+}
+
+/// Which way a function converts JSON
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsonDirection {
+    /// `JSON.stringify`
+    Serialize,
+    /// `JSON.parse`
+    Deserialize,
+}
+
+/// A JavaScript function of one parameter calling `JSON.stringify` or `JSON.parse`
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonCodec {
+    pub function: String,
+    pub direction: JsonDirection,
+    /// The function returns `JSON.stringify(param)`/`JSON.parse(param)` and nothing else
+    pub direct: bool,
+    /// The body catches errors, so malformed input may not throw
+    pub guarded: bool,
+}
+
+/// Functions and arrow functions converting their parameter with `JSON.stringify`/`JSON.parse`
+pub fn js_json_codecs(source: &str) -> Vec<JsonCodec> {
+    let defs = js_definitions(source);
+    let call = Regex::new(r"JSON\.(stringify|parse)\(").unwrap();
+    let direct = Regex::new(r"^[^{]*?\)\s*(?:\{\s*return\s+|=>\s*(?:\{\s*return\s+)?)JSON\.(?:stringify|parse)\(\s*(\w+)\s*\)\s*;?\s*(?:\}|\n|$)").unwrap();
+    defs.iter().filter_map(|def: &Definition| {
+        let text = &source[def.start..];
+        let callable = text.starts_with("function") || text[..text.find('(').unwrap_or(0)].contains('=');
+        if def.params.len() != 1 || !callable {
+            return None;
+        }
+        let body = def.body(source, &defs);
+        let cap = call.captures(body)?;
+        Some(JsonCodec {
+            function: def.name.clone(),
+            direction: if &cap[1] == "stringify" { JsonDirection::Serialize } else { JsonDirection::Deserialize },
+            direct: direct.captures(body).is_some_and(|d| d[1] == *def.params[0].split('=').next().unwrap_or("").trim()),
+            guarded: Regex::new(r"\btry\s*\{").unwrap().is_match(body),
+        })
+    }).collect()
+}
+
+/// A field name in serde's `rename_all` cases
+fn rename_case(name: &str, case: Option<&str>) -> String {
+    let words: Vec<String> = name.split('_').filter(|w| !w.is_empty()).map(str::to_lowercase).collect();
+    let capitalized = |w: &String| w[..1].to_uppercase() + &w[1..];
+    match case {
+        Some("camelCase") => words.iter().enumerate().map(|(i, w)| if i == 0 { w.clone() } else { capitalized(w) }).collect(),
+        Some("PascalCase") => words.iter().map(capitalized).collect(),
+        Some("kebab-case") => words.join("-"),
+        Some("SCREAMING_SNAKE_CASE") => name.to_uppercase(),
+        Some("snake_case") => {
+            let mut snake = String::new();
+            for (i, c) in name.char_indices() {
+                if c.is_uppercase() && i > 0 {
+                    snake.push('_');
+                }
+                snake.extend(c.to_lowercase());
+            }
+            snake
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// A class body without the bodies of its methods and nested types
+fn outside_braces(body: &str) -> String {
+    let mut depth = 0;
+    body.chars().filter(|c| {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => return depth == 0,
+        }
+        false
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializable_types_by_language() {
+        let types = serde_types("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n#[serde(rename_all = \"camelCase\")]\npub struct User {\n    pub user_id: u64,\n    #[serde(rename = \"mail\")]\n    pub email: Option<String>,\n    #[serde(skip)]\n    cache: Vec<u8>,\n}\n\n#[derive(Serialize)]\nstruct Wrapper<T>(T);\n");
+        assert_eq!(types.len(), 2);
+        assert_eq!(types[0].fields.iter().map(|f| f.key.as_str()).collect::<Vec<_>>(), vec!["userId", "mail", "cache"]);
+        assert_eq!(types[0].required_fields().len(), 1);
+        assert!(types[0].comparable && types[0].constructible && !types[1].constructible);
+
+        let models = pydantic_models("from pydantic import BaseModel, Field\n\nclass Item(BaseModel):\n    name: str\n    price: float = Field(..., alias=\"unitPrice\")\n    tags: list[str] = []\n\n    def total(self):\n        return self.price\n");
+        assert_eq!(models[0].fields.iter().map(|f| (f.key.as_str(), f.has_default)).collect::<Vec<_>>(), vec![("name", false), ("unitPrice", false), ("tags", true)]);
+
+        let types = jackson_types("import com.fasterxml.jackson.annotation.JsonProperty;\n\npublic class Order {\n    @JsonProperty(\"order_id\")\n    private long id;\n    private String status;\n    private static final int LIMIT = 5;\n\n    public Order(long id) {\n        this.id = id;\n    }\n}\n");
+        assert_eq!(types[0].fields.iter().map(|f| f.key.as_str()).collect::<Vec<_>>(), vec!["order_id", "status"]);
+        assert!(!types[0].constructible);
+        assert_eq!(malformed_json(&types[0]), "{\"order_id\": ");
+
+        let codecs = js_json_codecs("function save(user) {\n  return JSON.stringify(user);\n}\n\nconst load = (text) => {\n  try {\n    return JSON.parse(text);\n  } catch (e) {\n    return null;\n  }\n};\n");
+        assert_eq!(codecs.iter().map(|c| (c.direction, c.direct, c.guarded)).collect::<Vec<_>>(), vec![(JsonDirection::Serialize, true, false), (JsonDirection::Deserialize, false, true)]);
+    }
+}
@@ -0,0 +1,356 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::adapters::{GoAdapter, JavaAdapter, PythonAdapter, RustAdapter};
+use crate::core::{inline_tests_start, UftError, merge_test_file, replace_inline_tests, test_names, AdapterConfig, TestPlacement, TestSuite, TestablePattern};
+use crate::templates::{TemplateEngine, TestSuiteContext};
+
+static JAVA_PACKAGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;").unwrap());
+
+/// What writing generated tests to a test file did to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestFileUpdate {
+    /// The file didn't exist; written with this many tests
+    Created(usize),
+    /// This many tests were merged into the existing file
+    Merged(usize),
+    /// The file already defined every generated test
+    UpToDate,
+    /// The existing file was replaced by one with this many tests
+    Overwritten(usize),
+    /// The file existed and was left alone
+    Skipped,
+}
+
+impl TestFileUpdate {
+    /// How many tests the write put in the file
+    pub fn tests_written(&self) -> usize {
+        match self {
+            Self::Created(count) | Self::Merged(count) | Self::Overwritten(count) => *count,
+            Self::UpToDate | Self::Skipped => 0,
+        }
+    }
+
+    /// How `--format json` output and the daemon report the write
+    pub fn status(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "generated",
+            Self::Merged(_) => "merged",
+            Self::Overwritten(_) => "overwritten",
+            Self::UpToDate | Self::Skipped => "exists",
+        }
+    }
+}
+
+/// What writing generated tests does to a test file that already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverwritePolicy {
+    /// Replace it
+    Force,
+    /// Leave it alone
+    Skip,
+    /// Add the generated tests it doesn't define yet
+    Merge,
+}
+
+/// Render a test suite as a test file: the adapter's own code when it produced one, otherwise
+/// its test cases with the `{framework}/{language}_test_suite` template
+pub fn generate_test_file_content(test_suite: &TestSuite) -> Result<String> {
+    // If the test suite has generated test code, use it directly
+    if let Some(ref test_code) = test_suite.test_code {
+        return Ok(test_code.clone());
+    }
+    
+    TemplateEngine::shared().render_test_suite(&test_suite.language, &test_suite.framework, &TestSuiteContext::from_suite(test_suite))
+}
+
+/// Render a test suite as a test file for `framework`: the adapters' own generators for JUnit 5
+/// and TestNG, unittest, rstest and Go, which work from the `patterns` in `source`, otherwise
+/// the `{framework}/{language}_test_suite` template
+pub fn generate_test_file_content_with_framework(test_suite: &TestSuite, framework: &str, patterns: &[TestablePattern], source: &str) -> Result<String> {
+    match (test_suite.language.as_str(), framework) {
+        ("java", "junit5" | "testng") => {
+            JavaAdapter::new().generate_test_file(test_suite, source, framework, TemplateEngine::shared())
+        },
+        ("python", "unittest") => {
+            let module = PythonAdapter::new().generate_unittest_module(patterns, source, TemplateEngine::shared())?;
+            if !module.is_empty() {
+                return Ok(module);
+            }
+            TemplateEngine::shared().render_test_suite("python", framework, &TestSuiteContext::from_suite(test_suite))
+        },
+        ("rust", "rstest") => {
+            // The suite's code was generated for the crate's `tests/` directory rather than inline
+            let placement = match &test_suite.test_code {
+                Some(code) if !code.starts_with("#[cfg(test)]") => TestPlacement::Integration,
+                _ => TestPlacement::Inline,
+            };
+            RustAdapter::with_config(AdapterConfig { placement, ..AdapterConfig::for_language("rust") })
+                .generate_rstest_module(patterns, source)
+        },
+        ("go", _) if !patterns.is_empty() => {
+            let framework = if framework == "testify" { "testify" } else { "testing" };
+            // The suite's code was generated in an external `_test` package for black-box tests
+            let black_box = test_suite.test_code.as_deref()
+                .and_then(|code| code.lines().find(|line| line.starts_with("package ")))
+                .is_some_and(|package| package.ends_with("_test"));
+            GoAdapter::with_config(AdapterConfig { black_box, ..AdapterConfig::for_language("go") })
+                .generate_test_file(patterns, source, framework, TemplateEngine::shared())
+        },
+        ("javascript" | "typescript" | "python" | "rust" | "go", _) => {
+            TemplateEngine::shared().render_test_suite(&test_suite.language, framework, &TestSuiteContext::from_suite(test_suite))
+        },
+        _ => generate_test_file_content(test_suite),
+    }
+}
+
+/// Placeholders a `test_path` convention can use: the source file's directory, which can only
+/// start the path, its name without the extension, that name capitalized, and its extension
+pub const TEST_PATH_PLACEHOLDERS: [&str; 4] = ["{parent}", "{stem}", "{Stem}", "{ext}"];
+
+/// The built-in test path convention for `language`
+pub fn default_test_path(language: &str) -> &'static str {
+    match language {
+        // Java outside a Maven or Gradle build: a test/ folder beside the source
+        "java" => "{parent}/test/{Stem}Test.java",
+        // JS/TS: __tests__ folder
+        "javascript" => "{parent}/__tests__/{stem}.test.js",
+        "typescript" => "{parent}/__tests__/{stem}.test.ts",
+        // Python: tests/ folder with a test_ prefix
+        "python" => "{parent}/tests/test_{stem}.py",
+        // Rust: an inline `mod tests` in the source file itself
+        "rust" => "{parent}/{stem}.rs",
+        // Go: _test.go suffix in the same directory
+        "go" => "{parent}/{stem}_test.go",
+        // Default: tests/ folder at the root
+        _ => "tests/test_{stem}.test",
+    }
+}
+
+/// Where the tests for `source_file` go under the language's conventions
+pub fn get_test_file_path(repo_dir: &Path, source_file: &Path, language: &str) -> PathBuf {
+    if language == "java" {
+        if let Some(path) = java_test_file_path(repo_dir, source_file) {
+            return path;
+        }
+    }
+    expand_test_path(default_test_path(language), repo_dir, source_file)
+}
+
+/// Build files marking the root of a Maven or Gradle module
+const JAVA_BUILD_FILES: [&str; 3] = ["pom.xml", "build.gradle", "build.gradle.kts"];
+
+/// The Maven/Gradle location for the tests of a Java class: `src/test/java` mirroring the
+/// `src/main/java` it's under, else the module's `src/test/java` plus its package's directories.
+/// `None` outside a build, where tests stay beside the source
+fn java_test_file_path(repo_dir: &Path, source_file: &Path) -> Option<PathBuf> {
+    let source_path = repo_dir.join(source_file);
+    let test_name = format!("{}Test.java", source_path.file_stem()?.to_string_lossy());
+
+    let components: Vec<Component> = source_path.components().collect();
+    if let Some(main) = components.windows(3).rposition(|window| window.iter().map(|c| c.as_os_str()).eq(["src", "main", "java"])) {
+        let module: PathBuf = components[..main].iter().collect();
+        let package: PathBuf = components[main + 3..components.len() - 1].iter().collect();
+        return Some(module.join("src/test/java").join(package).join(test_name));
+    }
+
+    let module = source_path.parent()?.ancestors()
+        .take_while(|dir| dir.starts_with(repo_dir))
+        .find(|dir| JAVA_BUILD_FILES.iter().any(|build_file| dir.join(build_file).is_file()))?;
+    let source = fs::read_to_string(&source_path).ok()?;
+    let package = JAVA_PACKAGE_REGEX.captures(&source).map(|cap| cap[1].replace('.', "/")).unwrap_or_default();
+    Some(module.join("src/test/java").join(package).join(test_name))
+}
+
+/// Where the `pattern` convention, e.g. `{parent}/__tests__/{stem}.test.{ext}`, puts the tests
+/// for `source_file`: beside it when the pattern starts with `{parent}`, else under `repo_dir`
+pub fn expand_test_path(pattern: &str, repo_dir: &Path, source_file: &Path) -> PathBuf {
+    let source_path = if source_file.is_absolute() {
+        source_file.to_path_buf()
+    } else {
+        repo_dir.join(source_file)
+    };
+    let stem = source_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let mut chars = stem.chars();
+    let capitalized: String = chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default();
+    let extension = source_path.extension().unwrap_or_default().to_string_lossy();
+    let expand = |path: &str| path.replace("{stem}", &stem).replace("{Stem}", &capitalized).replace("{ext}", &extension);
+
+    match pattern.strip_prefix("{parent}") {
+        Some(rest) => source_path.parent().unwrap_or(repo_dir).join(expand(rest.trim_start_matches('/'))),
+        None => repo_dir.join(expand(pattern)),
+    }
+}
+
+/// Write a generated test file for `source_file` to `path`, merging it into the tests already
+/// there
+pub fn write_test_file(path: &Path, source_file: &Path, generated: &str) -> Result<TestFileUpdate> {
+    write_tests(path, source_file, generated, OverwritePolicy::Merge, false)
+}
+
+/// [`write_generated_file`] for the tests of `source_file`. When `path` is the source file
+/// itself, as with inline Rust tests, only the test module in `generated` goes into it, and a
+/// write that would replace the source is refused
+pub fn write_tests(path: &Path, source_file: &Path, generated: &str, policy: OverwritePolicy, backup: bool) -> Result<TestFileUpdate> {
+    if !is_same_file(path, source_file) {
+        return write_generated_file(path, generated, policy, backup);
+    }
+    let start = inline_tests_start(generated).ok_or_else(|| {
+        UftError::Generation(format!("Refusing to overwrite {} with tests that aren't a `mod tests`: it's the source file", path.display()))
+    })?;
+    // The source file being there doesn't mean its tests are
+    let policy = match policy {
+        OverwritePolicy::Skip if inline_tests_start(&fs::read_to_string(path)?).is_none() => OverwritePolicy::Merge,
+        policy => policy,
+    };
+    write_generated_file(path, &generated[start..], policy, backup)
+}
+
+/// Whether `test_file` is `source_file`, which is the case for inline Rust tests
+fn is_same_file(test_file: &Path, source_file: &Path) -> bool {
+    match (test_file.canonicalize(), source_file.canonicalize()) {
+        (Ok(test_file), Ok(source_file)) => test_file == source_file,
+        _ => false,
+    }
+}
+
+/// Write a generated file to `path`, treating one already there as `policy` says. With
+/// `backup`, a file about to change is first copied to [`backup_path`]
+pub fn write_generated_file(path: &Path, generated: &str, policy: OverwritePolicy, backup: bool) -> Result<TestFileUpdate> {
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, generated)?;
+        return Ok(TestFileUpdate::Created(test_names(generated).len()));
+    }
+    let existing = fs::read_to_string(path)?;
+    let (content, update) = match policy {
+        OverwritePolicy::Skip => return Ok(TestFileUpdate::Skipped),
+        OverwritePolicy::Force if existing == generated => return Ok(TestFileUpdate::UpToDate),
+        // Only the test module of a Rust source file holding its own tests is replaced
+        OverwritePolicy::Force if inline_tests_start(generated).is_some() && inline_tests_start(&existing) != Some(0) => {
+            let tests = &generated[inline_tests_start(generated).unwrap_or(0)..];
+            (replace_inline_tests(&existing, tests), TestFileUpdate::Overwritten(test_names(tests).len()))
+        }
+        OverwritePolicy::Force => (generated.to_string(), TestFileUpdate::Overwritten(test_names(generated).len())),
+        OverwritePolicy::Merge => match merge_test_file(&existing, generated) {
+            Some(merged) => {
+                let added = test_names(&merged).len() - test_names(&existing).len();
+                (merged, TestFileUpdate::Merged(added))
+            }
+            None => return Ok(TestFileUpdate::UpToDate),
+        },
+    };
+    if backup {
+        fs::copy(path, backup_path(path))?;
+    }
+    fs::write(path, content)?;
+    Ok(update)
+}
+
+/// The tests `test_file` already has for `source_file`, empty when it doesn't exist. When the
+/// two are the same Rust file, only its `#[cfg(test)] mod tests` counts
+pub fn read_existing_tests(test_file: &Path, source_file: &Path) -> String {
+    let content = fs::read_to_string(test_file).unwrap_or_default();
+    if !is_same_file(test_file, source_file) {
+        return content;
+    }
+    inline_tests_start(&content).map_or_else(String::new, |start| content[start..].to_string())
+}
+
+/// Where `--backup` keeps the previous version of a file: `test_calc.py` -> `test_calc.py.bak`
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_file_paths() {
+        let repo = Path::new("/repo");
+        assert_eq!(get_test_file_path(repo, Path::new("src/calc.py"), "python"), PathBuf::from("/repo/src/tests/test_calc.py"));
+        assert_eq!(get_test_file_path(repo, Path::new("src/calc.java"), "java"), PathBuf::from("/repo/src/test/CalcTest.java"));
+        assert_eq!(get_test_file_path(repo, Path::new("/repo/pkg/calc.go"), "go"), PathBuf::from("/repo/pkg/calc_test.go"));
+        assert_eq!(get_test_file_path(repo, Path::new("src/Calc.kt"), "kotlin"), PathBuf::from("/repo/tests/test_Calc.test"));
+        assert_eq!(expand_test_path("{parent}/__tests__/{stem}.spec.{ext}", repo, Path::new("lib/calc.mjs")), PathBuf::from("/repo/lib/__tests__/calc.spec.mjs"));
+        assert_eq!(expand_test_path("src/test/kotlin/{Stem}Test.kt", repo, Path::new("src/main/kotlin/calc.kt")), PathBuf::from("/repo/src/test/kotlin/CalcTest.kt"));
+    }
+
+    #[test]
+    fn test_java_test_file_paths_mirror_the_build_layout() {
+        let repo = Path::new("/repo");
+        assert_eq!(
+            get_test_file_path(repo, Path::new("services/billing/src/main/java/com/acme/billing/Invoice.java"), "java"),
+            PathBuf::from("/repo/services/billing/src/test/java/com/acme/billing/InvoiceTest.java")
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("app/java/com/acme/Cart.java");
+        fs::create_dir_all(source.parent().unwrap()).unwrap();
+        fs::write(&source, "package com.acme;\n\npublic class Cart {}\n").unwrap();
+        assert_eq!(get_test_file_path(dir.path(), &source, "java"), dir.path().join("app/java/com/acme/test/CartTest.java"));
+        fs::write(dir.path().join("app/build.gradle.kts"), "plugins { java }\n").unwrap();
+        assert_eq!(get_test_file_path(dir.path(), &source, "java"), dir.path().join("app/src/test/java/com/acme/CartTest.java"));
+    }
+
+    #[test]
+    fn test_write_generated_file_policies() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tests/test_calc.py");
+        let first = "def test_add():\n    pass\n";
+        let second = "def test_add():\n    pass\n\ndef test_sub():\n    pass\n";
+        assert_eq!(write_generated_file(&path, first, OverwritePolicy::Skip, true).unwrap(), TestFileUpdate::Created(1));
+        assert!(!backup_path(&path).exists());
+
+        fs::write(&path, "def test_add():\n    assert True\n").unwrap();
+        assert_eq!(write_generated_file(&path, second, OverwritePolicy::Skip, true).unwrap(), TestFileUpdate::Skipped);
+        assert_eq!(write_generated_file(&path, second, OverwritePolicy::Merge, false).unwrap(), TestFileUpdate::Merged(1));
+        assert!(fs::read_to_string(&path).unwrap().contains("assert True"));
+        assert!(!backup_path(&path).exists());
+
+        let before = fs::read_to_string(&path).unwrap();
+        assert_eq!(write_generated_file(&path, second, OverwritePolicy::Force, true).unwrap(), TestFileUpdate::Overwritten(2));
+        assert_eq!(fs::read_to_string(&path).unwrap(), second);
+        assert_eq!(backup_path(&path), dir.path().join("tests/test_calc.py.bak"));
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), before);
+        assert_eq!(write_generated_file(&path, second, OverwritePolicy::Force, true).unwrap(), TestFileUpdate::UpToDate);
+    }
+
+    #[test]
+    fn test_inline_rust_tests_in_the_source_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("src/parser.rs");
+        assert_eq!(get_test_file_path(dir.path(), Path::new("src/parser.rs"), "rust"), source);
+        fs::create_dir_all(source.parent().unwrap()).unwrap();
+        let code = "pub fn parse(input: &str) -> usize {\n    input.len()\n}\n";
+        fs::write(&source, code).unwrap();
+        assert_eq!(read_existing_tests(&source, &source), "");
+
+        let tests = "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_parse() {\n        let _ = parse(\"\");\n    }\n}\n";
+        assert_eq!(write_generated_file(&source, tests, OverwritePolicy::Force, false).unwrap(), TestFileUpdate::Overwritten(1));
+        assert_eq!(fs::read_to_string(&source).unwrap(), format!("{}\n{}", code, tests));
+        assert_eq!(read_existing_tests(&source, &source), tests);
+        assert_eq!(write_generated_file(&source, tests, OverwritePolicy::Merge, false).unwrap(), TestFileUpdate::UpToDate);
+
+        // A header in front of the module never replaces the source
+        let with_header = format!("// SPDX-License-Identifier: MIT\n\n{}", tests.replace("test_parse", "test_parse_empty"));
+        assert_eq!(write_tests(&source, &source, &with_header, OverwritePolicy::Force, false).unwrap(), TestFileUpdate::Overwritten(1));
+        let written = fs::read_to_string(&source).unwrap();
+        assert!(written.starts_with(code) && written.contains("fn test_parse_empty") && !written.contains("SPDX"));
+        assert!(write_tests(&source, &source, "fn main() {}\n", OverwritePolicy::Force, false).is_err());
+        assert_eq!(write_tests(&source, &source, tests, OverwritePolicy::Skip, false).unwrap(), TestFileUpdate::Skipped);
+        fs::write(&source, code).unwrap();
+        assert_eq!(write_tests(&source, &source, tests, OverwritePolicy::Skip, false).unwrap(), TestFileUpdate::Merged(1));
+        assert!(fs::read_to_string(&source).unwrap().starts_with(code));
+    }
+}
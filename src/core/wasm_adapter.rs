@@ -0,0 +1,223 @@
+//! Language adapters compiled to WebAssembly, for languages that need a real parser. A language
+//! config names the module in `wasm`, and uft runs it with wasmtime when built with the `wasm`
+//! feature.
+//!
+//! The module imports nothing and exports its `memory` and:
+//! - `uft_alloc(len: i32) -> i32`: where to write `len` bytes of input
+//! - `uft_analyze(ptr: i32, len: i32) -> i64`: given `{"source", "file_path"}`, the
+//!   [`TestablePattern`]s found
+//! - `uft_generate(ptr: i32, len: i32) -> i64`: given `{"patterns", "source"}`, the
+//!   `{"test_cases", "imports", "test_code"}` generated, the last two optional
+//!
+//! Input and output are UTF-8 JSON. A function returns its output's address in the high 32 bits
+//! and its length in the low ones, and fails by returning `{"error": "..."}`. Every call gets a
+//! fresh instance, so a module needn't free anything.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+use super::{DynamicLanguageAdapter, LanguageConfig, TestCase, TestGenerator, TestSuite, TestType, TestablePattern, UftError};
+use crate::templates::filters;
+
+/// Functions a module must export
+pub const WASM_EXPORTS: [&str; 4] = ["memory", "uft_alloc", "uft_analyze", "uft_generate"];
+
+/// What an adapter plugin generates for some patterns: `uft_generate`'s output here, and the
+/// result of a process adapter's `generateTests`
+#[derive(Debug, Deserialize)]
+pub(crate) struct GeneratedTests {
+    test_cases: Vec<TestCase>,
+    #[serde(default)]
+    imports: Vec<String>,
+    /// The whole test file; rendered from the config's `test_template` when the plugin leaves it out
+    #[serde(default)]
+    test_code: Option<String>,
+}
+
+impl GeneratedTests {
+    /// The test suite for `config`'s language
+    pub(crate) fn into_suite(self, config: &LanguageConfig, patterns: &[TestablePattern]) -> TestSuite {
+        let subject = patterns.iter()
+            .find_map(|pattern| pattern.context.class_name.clone())
+            .unwrap_or_else(|| filters::pascal_case(&config.name).unwrap_or_default());
+        let mut suite = TestSuite {
+            name: format!("{}Test", subject.replace("Test", "")),
+            language: config.name.clone(),
+            framework: config.framework.clone(),
+            test_cases: self.test_cases,
+            imports: if self.imports.is_empty() { config.imports.clone() } else { self.imports },
+            test_type: TestType::Unit,
+            setup_requirements: vec![],
+            cleanup_requirements: vec![],
+            coverage_target: 70.0,
+            fixtures: vec![],
+            test_code: self.test_code,
+        };
+        if suite.test_code.is_none() {
+            suite.test_code = Some(DynamicLanguageAdapter::new(config.clone()).generate_test_content(&suite.test_cases));
+        }
+        suite
+    }
+}
+
+/// A language whose patterns and tests come from a WebAssembly module
+pub struct WasmLanguageAdapter {
+    config: LanguageConfig,
+    #[cfg(feature = "wasm")]
+    module: wasmtime::Module,
+}
+
+impl WasmLanguageAdapter {
+    /// Compile the module at `path` for `config`'s language
+    #[cfg(feature = "wasm")]
+    pub fn load(config: LanguageConfig, path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| UftError::Config(format!("Can't read WebAssembly adapter {}: {}", path.display(), e)))?;
+        let module = wasmtime::Module::new(&wasmtime::Engine::default(), &bytes)
+            .map_err(|e| UftError::Config(format!("Invalid WebAssembly adapter {}: {}", path.display(), e)))?;
+        if let Some(missing) = WASM_EXPORTS.iter().find(|export| module.get_export(export).is_none()) {
+            return Err(UftError::Config(format!("WebAssembly adapter {} doesn't export `{}`", path.display(), missing)).into());
+        }
+        Ok(Self { config, module })
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    pub fn load(config: LanguageConfig, path: &Path) -> Result<Self> {
+        Err(UftError::Config(format!(
+            "{} needs the WebAssembly adapter {}, but this uft was built without the `wasm` feature",
+            config.name, path.display()
+        )).into())
+    }
+
+    /// Call `function` with `input` in a fresh instance of the module, parsing its output
+    #[cfg(feature = "wasm")]
+    fn call<T: DeserializeOwned>(&self, function: &str, input: &Value) -> Result<T> {
+        let mut store = wasmtime::Store::new(self.module.engine(), ());
+        let instance = wasmtime::Instance::new(&mut store, &self.module, &[])?;
+        let memory = instance.get_memory(&mut store, "memory")
+            .ok_or_else(|| anyhow::anyhow!("The {} adapter's `memory` isn't a memory", self.config.name))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "uft_alloc")?;
+        let exported = instance.get_typed_func::<(i32, i32), i64>(&mut store, function)?;
+
+        let input = serde_json::to_vec(input)?;
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, ptr as u32 as usize, &input)?;
+        let packed = exported.call(&mut store, (ptr, len))? as u64;
+        let (start, len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        let output = memory.data(&store).get(start..start + len)
+            .ok_or_else(|| anyhow::anyhow!("The {} adapter's {} returned output outside its memory", self.config.name, function))?;
+        parse_output(&self.config.name, function, output)
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    fn call<T: DeserializeOwned>(&self, _function: &str, _input: &Value) -> Result<T> {
+        Err(UftError::Config("uft was built without the `wasm` feature".to_string()).into())
+    }
+}
+
+/// A module's JSON output, or the error it reported
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+fn parse_output<T: DeserializeOwned>(language: &str, function: &str, output: &[u8]) -> Result<T> {
+    let output: Value = serde_json::from_slice(output)
+        .map_err(|e| anyhow::anyhow!("The {} adapter's {} returned invalid JSON: {}", language, function, e))?;
+    if let Some(error) = output.get("error").and_then(Value::as_str) {
+        return Err(UftError::Generation(format!("The {} adapter's {} failed: {}", language, function, error)).into());
+    }
+    serde_json::from_value(output)
+        .map_err(|e| anyhow::anyhow!("The {} adapter's {} returned unexpected JSON: {}", language, function, e))
+}
+
+#[async_trait]
+impl TestGenerator for WasmLanguageAdapter {
+    async fn analyze_code(&self, source: &str, file_path: &str) -> Result<Vec<TestablePattern>> {
+        self.call("uft_analyze", &json!({ "source": source, "file_path": file_path }))
+    }
+
+    async fn generate_tests(&self, patterns: Vec<TestablePattern>) -> Result<TestSuite> {
+        self.generate_comprehensive_tests(patterns, "").await
+    }
+
+    async fn generate_comprehensive_tests(&self, patterns: Vec<TestablePattern>, source: &str) -> Result<TestSuite> {
+        let generated: GeneratedTests = self.call("uft_generate", &json!({ "patterns": patterns, "source": source }))?;
+        Ok(generated.into_suite(&self.config, &patterns))
+    }
+
+    fn get_language(&self) -> &str {
+        &self.config.name
+    }
+
+    fn get_supported_frameworks(&self) -> Vec<&str> {
+        vec![&self.config.framework]
+    }
+
+    fn get_coverage_target(&self) -> f32 {
+        70.0
+    }
+
+    fn test_path(&self) -> Option<&str> {
+        self.config.test_path.as_deref()
+    }
+
+    /// The suite's test cases with the config's `test_template`
+    fn generate_test_code(&self, test_suite: &TestSuite) -> Result<String> {
+        Ok(DynamicLanguageAdapter::new(self.config.clone()).generate_test_content(&test_suite.test_cases))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output() {
+        let patterns: Vec<TestablePattern> = parse_output("kotlin", "uft_analyze", b"[]").unwrap();
+        assert!(patterns.is_empty());
+        let error = parse_output::<Vec<TestablePattern>>("kotlin", "uft_analyze", br#"{"error": "unbalanced braces"}"#).unwrap_err();
+        assert_eq!(error.to_string(), "The kotlin adapter's uft_analyze failed: unbalanced braces");
+        assert_eq!(UftError::exit_code_of(&error), 1);
+        assert!(parse_output::<Vec<TestablePattern>>("kotlin", "uft_analyze", b"{").is_err());
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn test_wasm_adapter_needs_feature() {
+        let config: LanguageConfig = serde_json::from_str(include_str!("../../language_configs/kotlin.json")).unwrap();
+        let error = WasmLanguageAdapter::load(config, Path::new("kotlin.wasm")).err().unwrap();
+        assert_eq!(UftError::exit_code_of(&error), 3);
+        assert!(error.to_string().ends_with("built without the `wasm` feature"));
+    }
+
+    /// A module whose `uft_generate` returns one test case and `uft_analyze` no patterns
+    #[cfg(feature = "wasm")]
+    const PLUGIN: &str = r#"(module
+  (memory (export "memory") 1)
+  (data (i32.const 0) "[]")
+  (data (i32.const 16) "{\"test_cases\": [{\"id\": \"1\", \"name\": \"test_add\", \"description\": \"Adds\", \"input\": null, \"expected_output\": 3, \"test_body\": \"\", \"assertions\": [], \"test_category\": \"HappyPath\"}]}")
+  (func (export "uft_alloc") (param i32) (result i32) (i32.const 1024))
+  (func (export "uft_analyze") (param i32 i32) (result i64) (i64.const 2))
+  (func (export "uft_generate") (param i32 i32) (result i64) (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 174))))"#;
+
+    #[cfg(feature = "wasm")]
+    #[tokio::test]
+    async fn test_wasm_adapter() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kotlin.wat");
+        std::fs::write(&path, PLUGIN).unwrap();
+        let config: LanguageConfig = serde_json::from_str(include_str!("../../language_configs/kotlin.json")).unwrap();
+        let adapter = WasmLanguageAdapter::load(config.clone(), &path).unwrap();
+
+        assert!(adapter.analyze_code("fun add() = 3", "Calc.kt").await.unwrap().is_empty());
+        let suite = adapter.generate_comprehensive_tests(vec![], "fun add() = 3").await.unwrap();
+        assert_eq!(suite.test_cases[0].name, "test_add");
+        assert!(suite.test_code.unwrap().contains("test_add"));
+
+        std::fs::write(&path, "(module (memory (export \"memory\") 1))").unwrap();
+        let error = WasmLanguageAdapter::load(config, &path).err().unwrap();
+        assert!(error.to_string().ends_with("doesn't export `uft_alloc`"));
+    }
+}
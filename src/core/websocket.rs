@@ -0,0 +1,105 @@
+use regex::Regex;
+
+use super::{WebSocketClient, WebSocketPattern};
+
+impl WebSocketClient {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebSocketClient::Native => "WebSocket",
+            WebSocketClient::SocketIo => "socket.io",
+            WebSocketClient::Tungstenite => "tungstenite",
+            WebSocketClient::TokioTungstenite => "tokio-tungstenite",
+        }
+    }
+}
+
+impl WebSocketPattern {
+    /// Identifier-safe form of the URL's last path segment (or host) for test names
+    pub fn slug(&self) -> String {
+        let Some(url) = &self.url else {
+            return "client".to_string();
+        };
+        let without_scheme = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+        let without_query = without_scheme.split(['?', '#']).next().unwrap_or_default();
+        let (host, path) = without_query.split_once('/').unwrap_or((without_query, ""));
+        let name = path.rsplit('/').find(|segment| !segment.is_empty())
+            .unwrap_or_else(|| host.split(':').next().unwrap_or_default());
+        let slug = Regex::new(r"\W+").unwrap().replace_all(name, "_").trim_matches('_').to_lowercase();
+        if slug.is_empty() { "client".to_string() } else { slug }
+    }
+
+    /// Events the generated test sends: the detected ones, or the default `message` event
+    pub fn test_events(&self) -> Vec<String> {
+        if self.events.is_empty() { vec!["message".to_string()] } else { self.events.clone() }
+    }
+}
+
+/// WebSocket clients opened by the source, with the offset of each: `new WebSocket(...)`,
+/// socket.io's `io(...)`, and tungstenite's `connect` or tokio-tungstenite's `connect_async`.
+/// A library's calls only count when the source mentions it.
+pub fn websocket_clients(source: &str) -> Vec<(usize, WebSocketPattern)> {
+    let url = r#"(?:(?:Url::parse\(\s*)?['"`]([^'"`$]+)['"`])?"#;
+    let mut calls: Vec<(WebSocketClient, String)> = vec![(WebSocketClient::Native, format!(r"\bnew\s+(?:WebSocket|ReconnectingWebSocket)\s*\(\s*{}", url))];
+    if source.contains("socket.io") {
+        calls.push((WebSocketClient::SocketIo, format!(r"\bio(?:\.connect)?\s*\(\s*{}", url)));
+    }
+    if source.contains("tokio_tungstenite") {
+        calls.push((WebSocketClient::TokioTungstenite, format!(r"\bconnect_async\s*\(\s*&?{}", url)));
+    } else if source.contains("tungstenite") {
+        let imports_connect = Regex::new(r"use\s+tungstenite::(?:\{[^}]*\bconnect\b|connect\b)").unwrap().is_match(source);
+        let call = if imports_connect { r"\b(?:tungstenite::)?connect" } else { r"\btungstenite::connect" };
+        calls.push((WebSocketClient::Tungstenite, format!(r"{}\s*\(\s*&?{}", call, url)));
+    }
+
+    let events: Vec<String> = Regex::new(r#"\.emit\s*\(\s*['"]([\w:.\-]+)['"]"#).unwrap()
+        .captures_iter(source)
+        .map(|cap| cap[1].to_string())
+        .filter(|event| !matches!(event.as_str(), "connect" | "disconnect" | "error"))
+        .fold(Vec::new(), |mut events, event| {
+            if !events.contains(&event) {
+                events.push(event);
+            }
+            events
+        });
+
+    let mut found: Vec<(usize, WebSocketPattern)> = Vec::new();
+    for (client, pattern) in calls {
+        for cap in Regex::new(&pattern).unwrap().captures_iter(source) {
+            found.push((cap.get(0).unwrap().start(), WebSocketPattern {
+                client,
+                url: cap.get(1).map(|url| url.as_str().to_string()),
+                events: if client == WebSocketClient::SocketIo { events.clone() } else { vec![] },
+            }));
+        }
+    }
+    found.sort_by_key(|(offset, _)| *offset);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(source: &str) -> Vec<(WebSocketClient, Option<String>, Vec<String>)> {
+        websocket_clients(source).into_iter().map(|(_, p)| (p.client, p.url, p.events)).collect()
+    }
+
+    #[test]
+    fn test_websocket_clients() {
+        let js = "const socket = new WebSocket('wss://example.com/live?room=1');\nconst { io } = require('socket.io-client');\nconst chat = io(`${host}`);\nchat.emit('join', room);\nchat.emit('chat:message', text);\n";
+        assert_eq!(summary(js), vec![
+            (WebSocketClient::Native, Some("wss://example.com/live?room=1".to_string()), vec![]),
+            (WebSocketClient::SocketIo, None, vec!["join".to_string(), "chat:message".to_string()]),
+        ]);
+
+        let rust = "use tokio_tungstenite::connect_async;\nlet (ws, _) = connect_async(\"ws://localhost:9001/feed\").await?;\n";
+        assert_eq!(summary(rust), vec![(WebSocketClient::TokioTungstenite, Some("ws://localhost:9001/feed".to_string()), vec![])]);
+        let blocking = "use tungstenite::{connect, Message};\nlet (mut socket, _) = connect(Url::parse(\"ws://localhost:3012/socket\").unwrap())?;\n";
+        assert_eq!(summary(blocking)[0].0, WebSocketClient::Tungstenite);
+        assert!(summary("fn connect(db: &str) {}\nconnect(\"postgres://\");\n").is_empty());
+
+        let pattern = WebSocketPattern { client: WebSocketClient::Native, url: Some("wss://example.com/live?room=1".to_string()), events: vec![] };
+        assert_eq!(pattern.slug(), "live");
+        assert_eq!(WebSocketPattern { url: Some("ws://localhost:8080".to_string()), ..pattern }.slug(), "localhost");
+    }
+}
@@ -0,0 +1,498 @@
+use globset::{GlobBuilder, GlobSetBuilder};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+static GRADLE_INCLUDE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?m)^\s*include\b\s*\(?([^)\n]*)"#).unwrap());
+static QUOTED_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"["']([^"']+)["']"#).unwrap());
+static MAVEN_MODULE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<module>\s*([^<]+?)\s*</module>").unwrap());
+static MAVEN_PARENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<parent>.*?</parent>").unwrap());
+static MAVEN_ARTIFACT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<artifactId>\s*([^<]+?)\s*</artifactId>").unwrap());
+static GO_MODULE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^module\s+(\S+)").unwrap());
+static GO_USE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?ms)^use\s*(?:\(\s*(.*?)\)|(\S+))").unwrap());
+
+/// Directories member globs never descend into
+const SKIPPED_DIRS: [&str; 5] = ["node_modules", "target", "build", "dist", "vendor"];
+
+/// Build tool that declared a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    /// `[workspace] members` in Cargo.toml
+    Cargo,
+    /// `workspaces` in package.json (npm, yarn) or `packages` in pnpm-workspace.yaml
+    Node,
+    /// `include` in settings.gradle(.kts)
+    Gradle,
+    /// `<modules>` in pom.xml
+    Maven,
+    /// `use` in go.work, or a lone go.mod
+    Go,
+}
+
+/// A package of a workspace, which its tests belong to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// Crate, npm package, Gradle project, Maven artifact or Go module name
+    pub name: String,
+    /// Directory of the member's manifest, relative to the workspace root
+    pub root: PathBuf,
+}
+
+/// A multi-package build: the directory declaring it and the packages in it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub kind: Option<WorkspaceKind>,
+    pub members: Vec<WorkspaceMember>,
+}
+
+impl Workspace {
+    /// The workspace declared in `start` or the nearest of its ancestors, up to the enclosing
+    /// Git checkout; empty when there's none
+    pub fn discover(start: &Path) -> Self {
+        let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+        for dir in start.ancestors() {
+            if let Some(workspace) = Self::declared_in(dir) {
+                return workspace;
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        Self::default()
+    }
+
+    /// The workspace whose manifest is in `dir`, if any
+    pub fn declared_in(dir: &Path) -> Option<Self> {
+        let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+        let (kind, members) = if let Some(members) = read("Cargo.toml").and_then(|manifest| cargo_members(dir, &manifest)) {
+            (WorkspaceKind::Cargo, members)
+        } else if let Some(members) = read("pnpm-workspace.yaml").and_then(|manifest| pnpm_members(dir, &manifest)) {
+            (WorkspaceKind::Node, members)
+        } else if let Some(members) = read("package.json").and_then(|manifest| npm_members(dir, &manifest)) {
+            (WorkspaceKind::Node, members)
+        } else if let Some(members) = read("settings.gradle.kts").or_else(|| read("settings.gradle")).map(|settings| gradle_members(dir, &settings)) {
+            (WorkspaceKind::Gradle, members)
+        } else if let Some(members) = read("pom.xml").and_then(|pom| maven_members(dir, &pom)) {
+            (WorkspaceKind::Maven, members)
+        } else if let Some(members) = read("go.work").map(|work| go_members(dir, &work)) {
+            (WorkspaceKind::Go, members)
+        } else if let Some(name) = read("go.mod").and_then(|module| go_module_name(&module)) {
+            (WorkspaceKind::Go, vec![WorkspaceMember { name, root: PathBuf::new() }])
+        } else {
+            return None;
+        };
+        Some(Self { root: dir.to_path_buf(), kind: Some(kind), members })
+    }
+
+    /// The member `path` is in: the one with the deepest root above it
+    pub fn member_for(&self, path: &Path) -> Option<&WorkspaceMember> {
+        let relative = self.relative(path)?;
+        self.members.iter()
+            .filter(|member| relative.starts_with(&member.root))
+            .max_by_key(|member| member.root.components().count())
+    }
+
+    /// The root of the member `path` is in, spelled like `path` (relative or absolute)
+    pub fn member_root(&self, path: &Path) -> Option<PathBuf> {
+        let member = self.member_for(path)?;
+        let depth = self.relative(path)?.strip_prefix(&member.root).ok()?.components().count();
+        let mut root = path.to_path_buf();
+        for _ in 0..depth {
+            root.pop();
+        }
+        Some(root)
+    }
+
+    fn relative(&self, path: &Path) -> Option<PathBuf> {
+        if self.members.is_empty() {
+            return None;
+        }
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        path.strip_prefix(&self.root).ok().map(Path::to_path_buf)
+    }
+}
+
+/// `[workspace] members`, minus `exclude`, with a root `[package]` as a member too
+fn cargo_members(dir: &Path, manifest: &str) -> Option<Vec<WorkspaceMember>> {
+    let manifest: toml::Value = toml::from_str(manifest).ok()?;
+    let workspace = manifest.get("workspace")?;
+    let strings = |key: &str| -> Vec<String> {
+        workspace.get(key).and_then(toml::Value::as_array).into_iter().flatten()
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect()
+    };
+    let mut patterns = strings("members");
+    patterns.extend(strings("exclude").into_iter().map(|pattern| format!("!{}", pattern)));
+
+    let mut members: Vec<WorkspaceMember> = expand_members(dir, &patterns).into_iter()
+        .filter_map(|root| Some(WorkspaceMember { name: cargo_package_name(&dir.join(&root))?, root }))
+        .collect();
+    if let Some(name) = manifest.get("package").and_then(|package| package.get("name")).and_then(toml::Value::as_str) {
+        members.push(WorkspaceMember { name: name.to_string(), root: PathBuf::new() });
+    }
+    Some(members)
+}
+
+/// `[package] name` from the `Cargo.toml` in `dir`, as written
+pub(crate) fn cargo_package_name(dir: &Path) -> Option<String> {
+    let manifest: toml::Value = toml::from_str(&fs::read_to_string(dir.join("Cargo.toml")).ok()?).ok()?;
+    Some(manifest.get("package")?.get("name")?.as_str()?.to_string())
+}
+
+/// `packages` in pnpm-workspace.yaml
+fn pnpm_members(dir: &Path, manifest: &str) -> Option<Vec<WorkspaceMember>> {
+    let manifest: serde_yaml::Value = serde_yaml::from_str(manifest).ok()?;
+    let patterns: Vec<String> = manifest.get("packages")?.as_sequence()?.iter()
+        .filter_map(|pattern| pattern.as_str().map(str::to_string))
+        .collect();
+    Some(node_members(dir, &patterns))
+}
+
+/// `workspaces` in package.json, as an array or yarn's `{ "packages": [...] }`
+fn npm_members(dir: &Path, manifest: &str) -> Option<Vec<WorkspaceMember>> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest).ok()?;
+    let workspaces = manifest.get("workspaces")?;
+    let patterns: Vec<String> = workspaces.as_array().or_else(|| workspaces.get("packages")?.as_array())?.iter()
+        .filter_map(|pattern| pattern.as_str().map(str::to_string))
+        .collect();
+    Some(node_members(dir, &patterns))
+}
+
+fn node_members(dir: &Path, patterns: &[String]) -> Vec<WorkspaceMember> {
+    expand_members(dir, patterns).into_iter()
+        .filter_map(|root| {
+            let manifest: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join(&root).join("package.json")).ok()?).ok()?;
+            Some(WorkspaceMember { name: manifest.get("name")?.as_str()?.to_string(), root })
+        })
+        .collect()
+}
+
+/// The projects `include`d in a Gradle settings file: `:lib:core` lives in lib/core
+fn gradle_members(dir: &Path, settings: &str) -> Vec<WorkspaceMember> {
+    GRADLE_INCLUDE_REGEX.captures_iter(settings)
+        .flat_map(|include| QUOTED_REGEX.captures_iter(include.get(1).map_or("", |m| m.as_str())).map(|path| path[1].to_string()).collect::<Vec<_>>())
+        .filter_map(|path| {
+            let segments: Vec<&str> = path.split(':').filter(|segment| !segment.is_empty()).collect();
+            let name = segments.last()?.to_string();
+            let root: PathBuf = segments.iter().collect();
+            dir.join(&root).is_dir().then_some(WorkspaceMember { name, root })
+        })
+        .collect()
+}
+
+/// The `<modules>` of an aggregator pom, named by their artifact ids
+fn maven_members(dir: &Path, pom: &str) -> Option<Vec<WorkspaceMember>> {
+    if !pom.contains("<modules>") {
+        return None;
+    }
+    Some(MAVEN_MODULE_REGEX.captures_iter(pom)
+        .filter_map(|module| {
+            let root = normalize(Path::new(&module[1]));
+            let pom = fs::read_to_string(dir.join(&root).join("pom.xml")).ok()?;
+            let own = MAVEN_PARENT_REGEX.replace(&pom, "");
+            Some(WorkspaceMember { name: MAVEN_ARTIFACT_REGEX.captures(&own)?[1].to_string(), root })
+        })
+        .collect())
+}
+
+/// The modules a go.work `use`s, named by their go.mod
+fn go_members(dir: &Path, work: &str) -> Vec<WorkspaceMember> {
+    GO_USE_REGEX.captures_iter(work)
+        .flat_map(|directive| directive.get(1).or(directive.get(2)).map_or("", |m| m.as_str()).split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        .filter(|path| !path.starts_with("//"))
+        .filter_map(|path| {
+            let root = normalize(Path::new(&path));
+            let name = go_module_name(&fs::read_to_string(dir.join(&root).join("go.mod")).ok()?)?;
+            Some(WorkspaceMember { name, root })
+        })
+        .collect()
+}
+
+fn go_module_name(go_mod: &str) -> Option<String> {
+    GO_MODULE_REGEX.captures(go_mod).map(|module| module[1].to_string())
+}
+
+/// Import path of the Go package holding `file`: the nearest go.mod's module path, followed by
+/// the directories between it and the file
+pub fn go_import_path(file: &Path) -> Option<String> {
+    let dir = file.parent()?;
+    dir.ancestors().find_map(|module_dir| {
+        let module = go_module_name(&fs::read_to_string(module_dir.join("go.mod")).ok()?)?;
+        let package_dir = dir.strip_prefix(module_dir).ok()?;
+        Some(std::iter::once(module)
+            .chain(package_dir.components().map(|component| component.as_os_str().to_string_lossy().to_string()))
+            .collect::<Vec<_>>()
+            .join("/"))
+    })
+}
+
+/// Files marking the root of a Python project, where pytest runs
+const PYTHON_PROJECT_FILES: [&str; 3] = ["pyproject.toml", "setup.py", "setup.cfg"];
+
+/// How the tests import a Python source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonModule {
+    /// Dotted module path, e.g. `shop.billing.invoice`
+    pub name: String,
+    /// Directory the module path starts from, which has to be on `sys.path`
+    pub root: PathBuf,
+    /// Directory of the nearest pyproject.toml, setup.py or setup.cfg
+    pub project: Option<PathBuf>,
+}
+
+impl PythonModule {
+    /// A `conftest.py` for the project root putting the module's root on `sys.path`, when the
+    /// two differ, as in a `src/` layout
+    pub fn conftest(&self) -> Option<(PathBuf, String)> {
+        let project = self.project.as_ref()?;
+        let relative = self.root.strip_prefix(project).ok().filter(|relative| !relative.as_os_str().is_empty())?;
+        let segments: Vec<String> = relative.components()
+            .map(|component| format!("{:?}", component.as_os_str().to_string_lossy()))
+            .collect();
+        let content = format!(
+            "import os\nimport sys\n\n# Make the modules under {0}/ importable from the tests\nsys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), {1}))\n",
+            relative.display(),
+            segments.join(", ")
+        );
+        Some((project.join("conftest.py"), content))
+    }
+}
+
+/// The import path of a Python source file. It is relative to the project's source directory
+/// when the file is in one (`package-dir`, `packages.find` `where` or Poetry's `from` in
+/// pyproject.toml, else a `src/` beside it), and otherwise to the top of the `__init__.py`
+/// chain above the file. `None` for a file that isn't a `.py` module or a segment that isn't an
+/// identifier
+pub fn python_module(file: &Path) -> Option<PythonModule> {
+    if file.extension()? != "py" {
+        return None;
+    }
+    let dir = file.parent()?;
+    let project = dir.ancestors()
+        .find(|dir| PYTHON_PROJECT_FILES.iter().any(|name| dir.join(name).is_file()))
+        .map(Path::to_path_buf);
+    let source_root = project.as_ref().and_then(|project| {
+        python_source_dirs(project).into_iter()
+            .map(|source_dir| project.join(source_dir))
+            .find(|source_dir| dir.starts_with(source_dir))
+    });
+    let root = source_root.unwrap_or_else(|| {
+        dir.ancestors().find(|dir| !dir.join("__init__.py").is_file()).unwrap_or(dir).to_path_buf()
+    });
+
+    let stem = file.file_stem()?.to_string_lossy();
+    let segments: Vec<String> = dir.strip_prefix(&root).ok()?.components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .chain((stem != "__init__").then(|| stem.to_string()))
+        .collect();
+    let identifier = |segment: &String| !segment.is_empty()
+        && !segment.starts_with(|c: char| c.is_ascii_digit())
+        && segment.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if segments.is_empty() || !segments.iter().all(identifier) {
+        return None;
+    }
+    Some(PythonModule { name: segments.join("."), root, project })
+}
+
+/// Directories of a Python project its packages are found in, relative to it
+fn python_source_dirs(project: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(pyproject) = fs::read_to_string(project.join("pyproject.toml")).ok().and_then(|manifest| manifest.parse::<toml::Value>().ok()) {
+        let tool = |path: &[&str]| path.iter().try_fold(pyproject.get("tool")?, |value, key| value.get(key));
+        let strings = |value: Option<&toml::Value>| -> Vec<String> {
+            match value {
+                Some(toml::Value::String(dir)) => vec![dir.clone()],
+                Some(toml::Value::Array(dirs)) => dirs.iter().filter_map(|dir| dir.as_str().map(str::to_string)).collect(),
+                _ => Vec::new(),
+            }
+        };
+        dirs.extend(strings(tool(&["setuptools", "package-dir", ""])));
+        dirs.extend(strings(tool(&["setuptools", "packages", "find", "where"])));
+        if let Some(packages) = tool(&["poetry", "packages"]).and_then(toml::Value::as_array) {
+            dirs.extend(packages.iter().filter_map(|package| package.get("from")?.as_str().map(str::to_string)));
+        }
+    }
+    dirs.push("src".to_string());
+    dirs.into_iter()
+        .map(|dir| normalize(Path::new(&dir)))
+        .filter(|dir| !dir.as_os_str().is_empty() && project.join(dir).is_dir())
+        .collect()
+}
+
+/// The directories under `dir` matching the member globs, minus those matching a `!` one
+fn expand_members(dir: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let mut include = GlobSetBuilder::new();
+    let mut exclude = GlobSetBuilder::new();
+    for pattern in patterns {
+        let (builder, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (&mut exclude, pattern),
+            None => (&mut include, pattern.as_str()),
+        };
+        // `*` stops at a slash, as in Cargo and npm member globs
+        if let Ok(glob) = GlobBuilder::new(&normalize(Path::new(pattern)).to_string_lossy()).literal_separator(true).build() {
+            builder.add(glob);
+        }
+    }
+    let (Ok(include), Ok(exclude)) = (include.build(), exclude.build()) else {
+        return Vec::new();
+    };
+
+    let mut members: Vec<PathBuf> = WalkDir::new(dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.file_type().is_dir() && !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.path().strip_prefix(dir).ok().map(Path::to_path_buf))
+        .filter(|root| include.is_match(root) && !exclude.is_match(root))
+        .collect();
+    members.sort();
+    members
+}
+
+/// A member path without `./` or a trailing slash
+fn normalize(path: &Path) -> PathBuf {
+    path.components().filter(|component| !matches!(component, Component::CurDir)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, files: &[(&str, &str)]) {
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    fn members(workspace: &Workspace) -> Vec<(String, String)> {
+        let mut members: Vec<_> = workspace.members.iter().map(|member| (member.name.clone(), member.root.to_string_lossy().to_string())).collect();
+        members.sort();
+        members
+    }
+
+    #[test]
+    fn test_discover_workspaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo = dir.path().join("cargo");
+        write(&cargo, &[
+            ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/scratch\"]\n"),
+            ("crates/core/Cargo.toml", "[package]\nname = \"acme-core\"\n"),
+            ("crates/cli/Cargo.toml", "[package]\nname = \"acme-cli\"\n"),
+            ("crates/scratch/Cargo.toml", "[package]\nname = \"scratch\"\n"),
+            ("crates/core/src/lib.rs", ""),
+        ]);
+        let workspace = Workspace::discover(&cargo.join("crates/core/src"));
+        assert_eq!(workspace.kind, Some(WorkspaceKind::Cargo));
+        assert_eq!(members(&workspace), vec![("acme-cli".to_string(), "crates/cli".to_string()), ("acme-core".to_string(), "crates/core".to_string())]);
+
+        let yarn = dir.path().join("yarn");
+        write(&yarn, &[
+            ("package.json", r#"{ "private": true, "workspaces": { "packages": ["packages/*"] } }"#),
+            ("packages/ui/package.json", r#"{ "name": "@acme/ui" }"#),
+            ("packages/ui/node_modules/dep/package.json", r#"{ "name": "dep" }"#),
+        ]);
+        assert_eq!(members(&Workspace::discover(&yarn)), vec![("@acme/ui".to_string(), "packages/ui".to_string())]);
+
+        let pnpm = dir.path().join("pnpm");
+        write(&pnpm, &[
+            ("pnpm-workspace.yaml", "packages:\n  - 'apps/**'\n  - '!apps/legacy'\n"),
+            ("apps/web/package.json", r#"{ "name": "web" }"#),
+            ("apps/legacy/package.json", r#"{ "name": "legacy" }"#),
+        ]);
+        assert_eq!(members(&Workspace::discover(&pnpm)), vec![("web".to_string(), "apps/web".to_string())]);
+
+        let gradle = dir.path().join("gradle");
+        write(&gradle, &[
+            ("settings.gradle.kts", "rootProject.name = \"acme\"\ninclude(\":app\", \":lib:core\")\n"),
+            ("app/build.gradle.kts", ""),
+            ("lib/core/build.gradle.kts", ""),
+        ]);
+        assert_eq!(members(&Workspace::discover(&gradle)), vec![("app".to_string(), "app".to_string()), ("core".to_string(), "lib/core".to_string())]);
+
+        let maven = dir.path().join("maven");
+        write(&maven, &[
+            ("pom.xml", "<project><artifactId>parent</artifactId><modules><module>service</module></modules></project>"),
+            ("service/pom.xml", "<project><parent><artifactId>parent</artifactId></parent><artifactId>acme-service</artifactId></project>"),
+        ]);
+        assert_eq!(members(&Workspace::discover(&maven)), vec![("acme-service".to_string(), "service".to_string())]);
+
+        let go = dir.path().join("go");
+        write(&go, &[
+            ("go.work", "go 1.22\n\nuse (\n\t./api\n\t./tools\n)\n"),
+            ("api/go.mod", "module example.com/acme/api\n\ngo 1.22\n"),
+            ("tools/go.mod", "module example.com/acme/tools\n"),
+        ]);
+        assert_eq!(members(&Workspace::discover(&go)), vec![
+            ("example.com/acme/api".to_string(), "api".to_string()),
+            ("example.com/acme/tools".to_string(), "tools".to_string()),
+        ]);
+        assert_eq!(go_import_path(&go.join("api/internal/billing/invoice.go")), Some("example.com/acme/api/internal/billing".to_string()));
+        assert_eq!(go_import_path(&go.join("api/main.go")), Some("example.com/acme/api".to_string()));
+        assert_eq!(go_import_path(&go.join("loose.go")), None);
+
+        fs::create_dir_all(dir.path().join("plain/.git")).unwrap();
+        assert_eq!(Workspace::discover(&dir.path().join("plain")), Workspace::default());
+    }
+
+    #[test]
+    fn test_member_root_keeps_the_path_spelling() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), &[
+            ("package.json", r#"{ "workspaces": ["packages/*"] }"#),
+            ("packages/api/package.json", r#"{ "name": "api" }"#),
+            ("packages/api/src/routes/users.js", ""),
+            ("scripts/build.js", ""),
+        ]);
+        let workspace = Workspace::discover(dir.path());
+        let source = dir.path().join("packages/api/src/routes/users.js");
+
+        assert_eq!(workspace.member_for(&source).map(|member| member.name.as_str()), Some("api"));
+        assert_eq!(workspace.member_root(&source), Some(dir.path().join("packages/api")));
+        assert_eq!(workspace.member_root(&dir.path().join("scripts/build.js")), None);
+    }
+
+    #[test]
+    fn test_python_module_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let module = |path: &str| python_module(&dir.path().join(path)).map(|module| (module.name, module.root));
+
+        write(dir.path(), &[
+            ("flat/shop/__init__.py", ""),
+            ("flat/shop/billing/__init__.py", ""),
+            ("flat/shop/billing/invoice.py", ""),
+            ("flat/scripts/deploy.py", ""),
+            ("flat/scripts/my-tool.py", ""),
+        ]);
+        assert_eq!(module("flat/shop/billing/invoice.py"), Some(("shop.billing.invoice".to_string(), dir.path().join("flat"))));
+        assert_eq!(module("flat/shop/billing/__init__.py"), Some(("shop.billing".to_string(), dir.path().join("flat"))));
+        assert_eq!(module("flat/scripts/deploy.py"), Some(("deploy".to_string(), dir.path().join("flat/scripts"))));
+        assert_eq!(module("flat/scripts/my-tool.py"), None);
+        assert_eq!(module("flat/scripts/deploy.js"), None);
+
+        write(dir.path(), &[
+            ("srclayout/pyproject.toml", "[project]\nname = \"shop\"\n"),
+            ("srclayout/src/shop/cart.py", ""),
+            ("setuptools/pyproject.toml", "[tool.setuptools.packages.find]\nwhere = [\"lib\"]\n"),
+            ("setuptools/lib/shop/__init__.py", ""),
+            ("setuptools/lib/shop/cart.py", ""),
+            ("poetry/pyproject.toml", "[tool.poetry]\npackages = [{ include = \"shop\", from = \"python\" }]\n"),
+            ("poetry/python/shop/cart.py", ""),
+        ]);
+        assert_eq!(module("srclayout/src/shop/cart.py"), Some(("shop.cart".to_string(), dir.path().join("srclayout/src"))));
+        assert_eq!(module("setuptools/lib/shop/cart.py"), Some(("shop.cart".to_string(), dir.path().join("setuptools/lib"))));
+        assert_eq!(module("poetry/python/shop/cart.py"), Some(("shop.cart".to_string(), dir.path().join("poetry/python"))));
+
+        let (path, content) = python_module(&dir.path().join("srclayout/src/shop/cart.py")).unwrap().conftest().unwrap();
+        assert_eq!(path, dir.path().join("srclayout/conftest.py"));
+        assert!(content.ends_with("sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), \"src\"))\n"));
+        assert_eq!(python_module(&dir.path().join("flat/shop/billing/invoice.py")).unwrap().conftest(), None);
+    }
+}
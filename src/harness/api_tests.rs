@@ -0,0 +1,401 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::path::Path;
+
+use super::HarnessFile;
+use crate::adapters::python::python_literal;
+use crate::core::{sample_value, ApiOperation, OpenApiSpec, ParameterLocation};
+
+/// Base URL used when the document names no server, or only a path on one
+const LOCAL_SERVER: &str = "http://localhost:8000";
+
+/// What a generated test sends for one operation
+struct ApiRequest {
+    method: String,
+    path: String,
+    query: serde_json::Map<String, Value>,
+    headers: serde_json::Map<String, Value>,
+    body: Option<Value>,
+}
+
+impl ApiRequest {
+    /// A request with every required parameter and the body filled in
+    fn complete(operation: &ApiOperation) -> Self {
+        Self {
+            method: operation.endpoint.method.to_string(),
+            path: operation.sample_path(),
+            query: operation.required_values(ParameterLocation::Query),
+            headers: operation.required_values(ParameterLocation::Header),
+            body: operation.request_body.as_ref().map(sample_value),
+        }
+    }
+
+    /// The same request without any of the required query parameters, headers or body
+    fn without_required_input(operation: &ApiOperation) -> Self {
+        Self {
+            query: Default::default(),
+            headers: Default::default(),
+            body: None,
+            ..Self::complete(operation)
+        }
+    }
+
+    fn for_missing_resource(operation: &ApiOperation) -> Self {
+        Self { path: operation.missing_resource_path(), ..Self::complete(operation) }
+    }
+}
+
+/// What a generated test asserts about the response
+enum Expectation {
+    /// One of the documented success statuses, with the body checked against the schema
+    /// documented for the first of them that has one
+    Success { statuses: Vec<u16>, schema: Option<(u16, Value)> },
+    ClientError,
+    Status(u16),
+}
+
+struct ApiTest {
+    name: String,
+    description: String,
+    request: ApiRequest,
+    expectation: Expectation,
+}
+
+/// Generates request/response tests (pytest, jest or `cargo test`) for every operation of an
+/// OpenAPI document: documented status codes, required parameters and response schemas
+pub struct ApiTestGenerator;
+
+impl ApiTestGenerator {
+    pub fn toolchain(language: &str) -> Option<&'static str> {
+        match language {
+            "python" => Some("pytest + requests + jsonschema"),
+            "javascript" => Some("jest + fetch + ajv"),
+            "rust" => Some("reqwest + jsonschema"),
+            _ => None,
+        }
+    }
+
+    /// Build the test file for `spec`, named after the document and placed in `output_dir`
+    pub fn generate(language: &str, spec_path: &Path, output_dir: &Path, spec: &OpenApiSpec) -> Result<Vec<HarnessFile>> {
+        Self::toolchain(language)
+            .ok_or_else(|| anyhow!("API test generation not supported for language: {}", language))?;
+
+        let tests = Self::tests(spec);
+        if tests.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let stem = spec_path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("openapi")
+            .replace(['-', '.'], "_");
+        let title = spec.title.clone().unwrap_or_else(|| stem.clone());
+        let base_url = match spec.base_url.as_deref() {
+            Some(url) if url.contains("://") => url.to_string(),
+            Some(path) => format!("{}{}", LOCAL_SERVER, path),
+            None => LOCAL_SERVER.to_string(),
+        };
+
+        let file = match language {
+            "python" => HarnessFile {
+                path: output_dir.join(format!("test_{}.py", stem)),
+                content: Self::api_tests_python(&title, &base_url, &tests),
+            },
+            "javascript" => HarnessFile {
+                path: output_dir.join(format!("{}.test.js", stem)),
+                content: Self::api_tests_js(&title, &base_url, &tests),
+            },
+            _ => HarnessFile {
+                path: output_dir.join(format!("{}_test.rs", stem)),
+                content: Self::api_tests_rust(&title, &base_url, &tests),
+            },
+        };
+
+        Ok(vec![file])
+    }
+
+    /// A happy-path test per operation, plus a missing-input test when something is required
+    /// and a not-found test when a 404 is documented for a path with parameters
+    fn tests(spec: &OpenApiSpec) -> Vec<ApiTest> {
+        let mut tests = Vec::new();
+        for operation in &spec.operations {
+            let name = operation.name();
+            let route = format!("{} {}", operation.endpoint.method.to_string(), operation.endpoint.path);
+            let success = operation.success_responses();
+
+            tests.push(ApiTest {
+                name: name.clone(),
+                description: format!("{} responds as documented", route),
+                request: ApiRequest::complete(operation),
+                expectation: Expectation::Success {
+                    statuses: success.iter().map(|r| r.status).collect(),
+                    schema: success.iter().find_map(|r| r.schema.clone().map(|s| (r.status, s))),
+                },
+            });
+            if operation.has_required_input() {
+                tests.push(ApiTest {
+                    name: format!("{}_rejects_missing_required_input", name),
+                    description: format!("{} rejects a request without its required input", route),
+                    request: ApiRequest::without_required_input(operation),
+                    expectation: Expectation::ClientError,
+                });
+            }
+            if operation.documents_status(404) && operation.sample_path() != operation.endpoint.path {
+                tests.push(ApiTest {
+                    name: format!("{}_not_found", name),
+                    description: format!("{} responds 404 for a missing resource", route),
+                    request: ApiRequest::for_missing_resource(operation),
+                    expectation: Expectation::Status(404),
+                });
+            }
+        }
+        tests
+    }
+
+    fn api_tests_python(title: &str, base_url: &str, tests: &[ApiTest]) -> String {
+        let mut code = format!(
+            "\"\"\"Tests for the {} API, generated from its OpenAPI document\"\"\"\nimport os\n\nimport jsonschema\nimport requests\n\nBASE_URL = os.environ.get(\"API_BASE_URL\", \"{}\")\nHEADERS = {{\"Authorization\": f\"Bearer {{os.environ['API_TOKEN']}}\"}} if \"API_TOKEN\" in os.environ else {{}}\n",
+            title, base_url
+        );
+        for test in tests {
+            let request = &test.request;
+            let mut args = vec![format!("f\"{{BASE_URL}}{}\"", request.path.replace('{', "{{").replace('}', "}}"))];
+            if !request.query.is_empty() {
+                args.push(format!("params={}", python_literal(&Value::Object(request.query.clone()))));
+            }
+            args.push(match request.headers.is_empty() {
+                true => "headers=HEADERS".to_string(),
+                false => format!("headers={{**HEADERS, {}}}", python_literal(&Value::Object(request.headers.clone())).trim_matches(['{', '}'])),
+            });
+            if let Some(body) = &request.body {
+                args.push(format!("json={}", python_literal(body)));
+            }
+            args.push("timeout=10".to_string());
+
+            code.push_str(&format!(
+                "\n\ndef test_{}():\n    \"\"\"{}\"\"\"\n    response = requests.{}({})\n",
+                test.name,
+                test.description,
+                request.method.to_lowercase(),
+                args.join(", ")
+            ));
+            match &test.expectation {
+                Expectation::Success { statuses, schema } => {
+                    code.push_str(&match statuses.as_slice() {
+                        [] => "    assert response.status_code < 400\n".to_string(),
+                        [status] => format!("    assert response.status_code == {}\n", status),
+                        _ => format!("    assert response.status_code in ({})\n", join(statuses)),
+                    });
+                    if let Some((status, schema)) = schema {
+                        let validate = format!("jsonschema.validate(response.json(), {})", python_literal(schema));
+                        code.push_str(&match statuses.len() {
+                            1 => format!("    {}\n", validate),
+                            _ => format!("    if response.status_code == {}:\n        {}\n", status, validate),
+                        });
+                    }
+                }
+                Expectation::ClientError => code.push_str("    assert 400 <= response.status_code < 500\n"),
+                Expectation::Status(status) => code.push_str(&format!("    assert response.status_code == {}\n", status)),
+            }
+        }
+        code
+    }
+
+    fn api_tests_js(title: &str, base_url: &str, tests: &[ApiTest]) -> String {
+        let mut code = format!(
+            "const Ajv = require('ajv');\n\nconst ajv = new Ajv({{ strict: false, validateFormats: false }});\nconst BASE_URL = process.env.API_BASE_URL || '{}';\nconst HEADERS = process.env.API_TOKEN ? {{ Authorization: `Bearer ${{process.env.API_TOKEN}}` }} : {{}};\n\nfunction request(method, path, {{ query = {{}}, headers = {{}}, body }} = {{}}) {{\n  const url = new URL(BASE_URL + path);\n  Object.entries(query).forEach(([name, value]) => url.searchParams.set(name, value));\n  const json = body === undefined ? {{}} : {{ 'Content-Type': 'application/json' }};\n  return fetch(url, {{ method, headers: {{ ...HEADERS, ...json, ...headers }}, body: body === undefined ? undefined : JSON.stringify(body) }});\n}}\n\ndescribe('{} API', () => {{\n",
+            base_url, title.replace('\'', "\\'")
+        );
+        for (i, test) in tests.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            let request = &test.request;
+            let mut options = Vec::new();
+            if !request.query.is_empty() {
+                options.push(format!("query: {}", Value::Object(request.query.clone())));
+            }
+            if !request.headers.is_empty() {
+                options.push(format!("headers: {}", Value::Object(request.headers.clone())));
+            }
+            if let Some(body) = &request.body {
+                options.push(format!("body: {}", body));
+            }
+            let options = match options.is_empty() {
+                true => String::new(),
+                false => format!(", {{ {} }}", options.join(", ")),
+            };
+
+            code.push_str(&format!(
+                "  test('{}', async () => {{\n    const response = await request('{}', '{}'{});\n",
+                test.description, request.method, request.path, options
+            ));
+            match &test.expectation {
+                Expectation::Success { statuses, schema } => {
+                    code.push_str(&match statuses.as_slice() {
+                        [] => "    expect(response.status).toBeLessThan(400);\n".to_string(),
+                        [status] => format!("    expect(response.status).toBe({});\n", status),
+                        _ => format!("    expect([{}]).toContain(response.status);\n", join(statuses)),
+                    });
+                    if let Some((status, schema)) = schema {
+                        let validate = format!("ajv.validate({}, await response.json());\n    expect(ajv.errors).toBeNull();", schema);
+                        code.push_str(&match statuses.len() {
+                            1 => format!("    {}\n", validate),
+                            _ => format!("    if (response.status === {}) {{\n      {}\n    }}\n", status, validate.replace("\n    ", "\n      ")),
+                        });
+                    }
+                }
+                Expectation::ClientError => code.push_str("    expect(response.status).toBeGreaterThanOrEqual(400);\n    expect(response.status).toBeLessThan(500);\n"),
+                Expectation::Status(status) => code.push_str(&format!("    expect(response.status).toBe({});\n", status)),
+            }
+            code.push_str("  });\n");
+        }
+        code.push_str("});\n");
+        code
+    }
+
+    fn api_tests_rust(title: &str, base_url: &str, tests: &[ApiTest]) -> String {
+        let mut code = format!(
+            "//! Tests for the {} API, generated from its OpenAPI document\nuse serde_json::{{json, Value}};\n\nfn request(method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {{\n    let base_url = std::env::var(\"API_BASE_URL\").unwrap_or_else(|_| \"{}\".to_string());\n    let request = reqwest::Client::new().request(method, format!(\"{{}}{{}}\", base_url, path));\n    match std::env::var(\"API_TOKEN\") {{\n        Ok(token) => request.bearer_auth(token),\n        Err(_) => request,\n    }}\n}}\n\nfn assert_matches_schema(body: &Value, schema: &str) {{\n    let schema: Value = serde_json::from_str(schema).unwrap();\n    assert!(jsonschema::is_valid(&schema, body), \"response does not match the documented schema: {{}}\", body);\n}}\n",
+            title, base_url
+        );
+        for test in tests {
+            let request = &test.request;
+            let mut builder = vec![format!("request(reqwest::Method::{}, \"{}\")", request.method, request.path.escape_default())];
+            if !request.query.is_empty() {
+                builder.push(format!(".query(&json!({}))", Value::Object(request.query.clone())));
+            }
+            for (name, value) in &request.headers {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                builder.push(format!(".header(\"{}\", \"{}\")", name, value.escape_default()));
+            }
+            if let Some(body) = &request.body {
+                builder.push(format!(".json(&json!({}))", body));
+            }
+            builder.extend([".send()".to_string(), ".await".to_string(), ".expect(\"request failed\")".to_string()]);
+
+            code.push_str(&format!(
+                "\n/// {}\n#[tokio::test]\nasync fn {}() {{\n    let response = {};\n",
+                test.description,
+                test.name,
+                builder.join("\n        ")
+            ));
+            match &test.expectation {
+                Expectation::Success { statuses, schema } => {
+                    code.push_str(&match statuses.as_slice() {
+                        [] => "    assert!(response.status().as_u16() < 400, \"unexpected status {}\", response.status());\n".to_string(),
+                        [status] => format!("    assert_eq!(response.status().as_u16(), {});\n", status),
+                        _ => format!("    let status = response.status().as_u16();\n    assert!([{}].contains(&status), \"unexpected status {{}}\", status);\n", join(statuses)),
+                    });
+                    if let Some((status, schema)) = schema {
+                        let validate = format!(
+                            "let body: Value = response.json().await.expect(\"response is not JSON\");\n    assert_matches_schema(&body, {});",
+                            raw_string(&schema.to_string())
+                        );
+                        code.push_str(&match statuses.len() {
+                            1 => format!("    {}\n", validate),
+                            _ => format!("    if status == {} {{\n        {}\n    }}\n", status, validate.replace("\n    ", "\n        ")),
+                        });
+                    }
+                }
+                Expectation::ClientError => code.push_str("    assert!(response.status().is_client_error(), \"expected a 4xx, got {}\", response.status());\n"),
+                Expectation::Status(status) => code.push_str(&format!("    assert_eq!(response.status().as_u16(), {});\n", status)),
+            }
+            code.push_str("}\n");
+        }
+        code
+    }
+}
+
+fn join(statuses: &[u16]) -> String {
+    statuses.iter().map(u16::to_string).collect::<Vec<_>>().join(", ")
+}
+
+/// Rust raw string literal with enough `#`s for its content
+fn raw_string(content: &str) -> String {
+    let mut hashes = String::from("#");
+    while content.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{0}\"{1}\"{0}", hashes, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"
+openapi: 3.0.0
+info:
+  title: Users
+servers:
+  - url: /api
+paths:
+  /users:
+    post:
+      operationId: createUser
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [email]
+              properties:
+                email: {type: string, format: email}
+      responses:
+        "201":
+          description: Created
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [id]
+                properties:
+                  id: {type: integer}
+        "422":
+          description: Invalid
+  /users/{id}:
+    get:
+      parameters:
+        - {name: id, in: path, required: true, schema: {type: integer}}
+        - {name: X-Tenant, in: header, required: true, schema: {type: string}}
+      responses:
+        "200":
+          description: OK
+        "404":
+          description: Missing
+"##;
+
+    #[test]
+    fn test_generate_api_tests_python() {
+        let spec = OpenApiSpec::parse(SPEC).unwrap();
+        let files = ApiTestGenerator::generate("python", Path::new("docs/users-api.yaml"), Path::new("api-tests"), &spec).unwrap();
+        let content = &files[0].content;
+
+        assert_eq!(files[0].path, Path::new("api-tests/test_users_api.py"));
+        assert!(content.contains("BASE_URL = os.environ.get(\"API_BASE_URL\", \"http://localhost:8000/api\")\n"));
+        assert!(content.contains("def test_create_user():\n    \"\"\"POST /users responds as documented\"\"\"\n    response = requests.post(f\"{BASE_URL}/users\", headers=HEADERS, json={\"email\": \"test@example.com\"}, timeout=10)\n    assert response.status_code == 201\n    jsonschema.validate(response.json(), {\"properties\": {\"id\": {\"type\": \"integer\"}}, \"required\": [\"id\"], \"type\": \"object\"})\n"));
+        assert!(content.contains("def test_create_user_rejects_missing_required_input():\n    \"\"\"POST /users rejects a request without its required input\"\"\"\n    response = requests.post(f\"{BASE_URL}/users\", headers=HEADERS, timeout=10)\n    assert 400 <= response.status_code < 500\n"));
+        assert!(content.contains("    response = requests.get(f\"{BASE_URL}/users/999999\", headers={**HEADERS, \"X-Tenant\": \"test\"}, timeout=10)\n    assert response.status_code == 404\n"));
+    }
+
+    #[test]
+    fn test_generate_api_tests_js_and_rust() {
+        let spec = OpenApiSpec::parse(SPEC).unwrap();
+
+        let files = ApiTestGenerator::generate("javascript", Path::new("openapi.yaml"), Path::new("out"), &spec).unwrap();
+        assert_eq!(files[0].path, Path::new("out/openapi.test.js"));
+        assert!(files[0].content.contains("  test('GET /users/{id} responds as documented', async () => {\n    const response = await request('GET', '/users/1', { headers: {\"X-Tenant\":\"test\"} });\n    expect(response.status).toBe(200);\n  });\n"));
+
+        let files = ApiTestGenerator::generate("rust", Path::new("openapi.yaml"), Path::new("tests"), &spec).unwrap();
+        assert_eq!(files[0].path, Path::new("tests/openapi_test.rs"));
+        assert!(files[0].content.contains("async fn create_user() {\n    let response = request(reqwest::Method::POST, \"/users\")\n        .json(&json!({\"email\":\"test@example.com\"}))\n        .send()\n"));
+        assert!(files[0].content.contains("    assert_matches_schema(&body, r#\"{\"properties\":{\"id\":{\"type\":\"integer\"}},\"required\":[\"id\"],\"type\":\"object\"}\"#);\n"));
+
+        assert!(ApiTestGenerator::generate("go", Path::new("openapi.yaml"), Path::new("out"), &spec).is_err());
+    }
+}
@@ -0,0 +1,291 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::path::Path;
+
+use super::{find_cargo_root, rust_crate_name, rust_module_path, HarnessFile};
+use crate::adapters::python::python_literal;
+use crate::adapters::rust::{rust_case_value, rust_literal};
+use crate::adapters::{JavaScriptAdapter, PythonAdapter};
+use crate::core::{braced_block, FunctionPattern};
+
+/// A function to benchmark with its arguments written as source literals
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchTarget {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+/// Generates criterion benches, pytest-benchmark tests and `vitest bench`/`Deno.bench` files
+pub struct BenchGenerator;
+
+impl BenchGenerator {
+    /// Benchmarking toolchain used for a language; JavaScript uses `Deno.bench` when the
+    /// source lives in a Deno project
+    pub fn toolchain(language: &str, source_path: &Path) -> Option<&'static str> {
+        match language {
+            "rust" => Some("criterion"),
+            "python" => Some("pytest-benchmark"),
+            "javascript" if Self::is_deno_project(source_path) => Some("Deno.bench"),
+            "javascript" => Some("vitest bench"),
+            _ => None,
+        }
+    }
+
+    /// Detected functions that can be called from a separate benchmark file with sample arguments.
+    /// For Rust only hot functions (loops, iterator chains or recursion) are kept.
+    pub fn find_targets(language: &str, source: &str, functions: &[FunctionPattern]) -> Vec<BenchTarget> {
+        functions.iter()
+            .filter_map(|func| match language {
+                "rust" => Self::rust_target(source, func),
+                "python" => Self::python_target(source, func),
+                "javascript" => Self::javascript_target(source, func),
+                _ => None,
+            })
+            .fold(Vec::new(), |mut targets, target| {
+                if !targets.iter().any(|t: &BenchTarget| t.name == target.name) {
+                    targets.push(target);
+                }
+                targets
+            })
+    }
+
+    /// Build the benchmark file for the detected functions of `source_path`
+    pub fn generate(language: &str, source_path: &Path, source: &str, functions: &[FunctionPattern]) -> Result<Vec<HarnessFile>> {
+        let toolchain = Self::toolchain(language, source_path)
+            .ok_or_else(|| anyhow!("Benchmark generation not supported for language: {}", language))?;
+
+        let targets = Self::find_targets(language, source, functions);
+        if targets.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let source_dir = source_path.parent().unwrap_or_else(|| Path::new(""));
+        let stem = source_path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("module");
+        let extension = source_path.extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("js");
+
+        let file = match (language, toolchain) {
+            ("rust", _) => Self::criterion_bench(source_path, stem, &targets)?,
+            ("python", _) => HarnessFile {
+                path: source_dir.join(format!("test_{}_benchmark.py", stem)),
+                content: Self::pytest_benchmark(stem, &targets),
+            },
+            (_, "Deno.bench") => HarnessFile {
+                path: source_dir.join(format!("{}_bench.{}", stem, extension)),
+                content: Self::deno_bench(stem, extension, &targets),
+            },
+            _ => HarnessFile {
+                path: source_dir.join(format!("{}.bench.{}", stem, extension)),
+                content: Self::vitest_bench(stem, extension, &targets),
+            },
+        };
+
+        Ok(vec![file])
+    }
+
+    fn rust_target(source: &str, func: &FunctionPattern) -> Option<BenchTarget> {
+        let name = regex::escape(&func.name);
+        let start = Regex::new(&format!(r"(?m)^pub(?:\([^)]*\))?\s+fn\s+{}\b", name)).unwrap().find(source)?.start();
+        let body = braced_block(source, start)?;
+        let hot = Regex::new(&format!(r"\bfor\b|\bwhile\b|\bloop\b|\.iter\(|\.into_iter\(|\.chars\(|\.map\(|\.fold\(|\b{}\s*\(", name)).unwrap();
+        if !hot.is_match(body) {
+            return None;
+        }
+
+        let args = func.parameters.iter()
+            .filter(|p| !p.is_empty())
+            .enumerate()
+            .map(|(i, param)| {
+                let (_, ty) = param.split_once(':')?;
+                let ty = ty.trim();
+                rust_case_value(ty, i).map(|value| rust_literal(&value, ty))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(BenchTarget { name: func.name.clone(), args })
+    }
+
+    fn python_target(source: &str, func: &FunctionPattern) -> Option<BenchTarget> {
+        // Top-level functions only; methods would need an instance to call
+        let top_level = Regex::new(&format!(r"(?m)^(?:async\s+)?def\s+{}\s*\(", regex::escape(&func.name))).unwrap();
+        if func.name.starts_with('_') || func.name.starts_with("test") || !top_level.is_match(source) {
+            return None;
+        }
+
+        let adapter = PythonAdapter::new();
+        let args = func.parameters.iter()
+            .map(|p| p.split([':', '=']).next().unwrap_or(p).trim())
+            .filter(|p| !p.is_empty() && !p.starts_with('*'))
+            .enumerate()
+            .map(|(i, param)| python_literal(&adapter.get_sample_value_for_python_param(param, i)))
+            .collect();
+
+        Some(BenchTarget { name: func.name.clone(), args })
+    }
+
+    fn javascript_target(source: &str, func: &FunctionPattern) -> Option<BenchTarget> {
+        let name = regex::escape(&func.name);
+        let exported = Regex::new(&format!(
+            r"export\s+(?:default\s+)?(?:async\s+)?(?:function\s*\*?|const|let|var)\s+{name}\b|export\s*\{{[^}}]*\b{name}\b|module\.exports\s*=\s*\{{[^}}]*\b{name}\b|exports\.{name}\s*=",
+            name = name
+        )).unwrap();
+        if !exported.is_match(source) {
+            return None;
+        }
+
+        let adapter = JavaScriptAdapter::new();
+        let args = func.parameters.iter()
+            .map(|p| p.split([':', '=']).next().unwrap_or(p).trim())
+            .filter(|p| !p.is_empty() && !p.starts_with("..."))
+            .enumerate()
+            .map(|(i, param)| adapter.get_sample_value_for_param(param, i).to_string())
+            .collect();
+
+        Some(BenchTarget { name: func.name.clone(), args })
+    }
+
+    fn is_deno_project(source_path: &Path) -> bool {
+        let start = source_path.canonicalize().unwrap_or_else(|_| source_path.to_path_buf());
+        start.ancestors().any(|dir| dir.join("deno.json").is_file() || dir.join("deno.jsonc").is_file())
+    }
+
+    fn criterion_bench(source_path: &Path, stem: &str, targets: &[BenchTarget]) -> Result<HarnessFile> {
+        let crate_root = find_cargo_root(source_path)
+            .ok_or_else(|| anyhow!("No Cargo.toml found above {}", source_path.display()))?;
+        let crate_name = rust_crate_name(&crate_root)
+            .ok_or_else(|| anyhow!("Could not read package name from {}", crate_root.join("Cargo.toml").display()))?;
+        let module_path = rust_module_path(&crate_name, &crate_root, source_path);
+
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        let imports = match names.as_slice() {
+            [name] => name.to_string(),
+            _ => format!("{{{}}}", names.join(", ")),
+        };
+        let mut code = format!(
+            "use std::hint::black_box;\n\nuse criterion::{{criterion_group, criterion_main, Criterion}};\nuse {}::{};\n",
+            module_path, imports
+        );
+        for target in targets {
+            let args: Vec<String> = target.args.iter().map(|arg| format!("black_box({})", arg)).collect();
+            code.push_str(&format!(
+                "\nfn bench_{0}(c: &mut Criterion) {{\n    c.bench_function(\"{0}\", |b| b.iter(|| {0}({1})));\n}}\n",
+                target.name, args.join(", ")
+            ));
+        }
+        let groups: Vec<String> = names.iter().map(|name| format!("bench_{}", name)).collect();
+        code.push_str(&format!("\ncriterion_group!(benches, {});\ncriterion_main!(benches);\n", groups.join(", ")));
+
+        Ok(HarnessFile {
+            path: crate_root.join("benches").join(format!("{}_bench.rs", stem)),
+            content: code,
+        })
+    }
+
+    fn pytest_benchmark(module: &str, targets: &[BenchTarget]) -> String {
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        let mut code = format!("from {} import {}\n", module, names.join(", "));
+        for target in targets {
+            let args: String = target.args.iter().map(|arg| format!(", {}", arg)).collect();
+            code.push_str(&format!(
+                "\n\ndef test_{0}_benchmark(benchmark):\n    benchmark({0}{1})\n",
+                target.name, args
+            ));
+        }
+        code
+    }
+
+    fn vitest_bench(module: &str, extension: &str, targets: &[BenchTarget]) -> String {
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        let mut code = format!(
+            "import {{ bench, describe }} from 'vitest';\nimport {{ {} }} from './{}.{}';\n\ndescribe('{}', () => {{\n",
+            names.join(", "), module, extension, module
+        );
+        for (i, target) in targets.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            code.push_str(&format!("  bench('{0}', () => {{\n    {0}({1});\n  }});\n", target.name, target.args.join(", ")));
+        }
+        code.push_str("});\n");
+        code
+    }
+
+    fn deno_bench(module: &str, extension: &str, targets: &[BenchTarget]) -> String {
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        let mut code = format!("import {{ {} }} from \"./{}.{}\";\n", names.join(", "), module, extension);
+        for target in targets {
+            code.push_str(&format!("\nDeno.bench(\"{0}\", () => {{\n  {0}({1});\n}});\n", target.name, target.args.join(", ")));
+        }
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn function(name: &str, parameters: &[&str]) -> FunctionPattern {
+        FunctionPattern {
+            name: name.to_string(),
+            parameters: parameters.iter().map(|p| p.to_string()).collect(),
+            return_type: None,
+        }
+    }
+
+    #[test]
+    fn test_find_rust_hot_functions() {
+        let source = "pub fn total(n: u32) -> u32 {\n    (0..n).map(|i| i * 2).sum()\n}\n\npub fn double(x: i32) -> i32 {\n    x * 2\n}\n\npub fn fib(n: u64) -> u64 {\n    if n < 2 { n } else { fib(n - 1) + fib(n - 2) }\n}\n\npub fn walk(p: &Path) {\n    for _ in p.iter() {}\n}\n";
+        let functions = [function("total", &["n: u32"]), function("double", &["x: i32"]), function("fib", &["n: u64"]), function("walk", &["p: &Path"])];
+
+        let targets = BenchGenerator::find_targets("rust", source, &functions);
+        assert_eq!(targets, vec![
+            BenchTarget { name: "total".to_string(), args: vec!["5".to_string()] },
+            BenchTarget { name: "fib".to_string(), args: vec!["5".to_string()] },
+        ]);
+    }
+
+    #[test]
+    fn test_generate_pytest_benchmark() {
+        let source = "def area(width, height):\n    return width * height\n\nclass Shape:\n    def scale(self, factor):\n        pass\n";
+        let functions = [function("area", &["width", "height"]), function("scale", &["self", "factor"])];
+
+        let files = BenchGenerator::generate("python", Path::new("app/shapes.py"), source, &functions).unwrap();
+        assert_eq!(files[0].path, Path::new("app/test_shapes_benchmark.py"));
+        assert_eq!(files[0].content, "from shapes import area\n\n\ndef test_area_benchmark(benchmark):\n    benchmark(area, 1, 10)\n");
+    }
+
+    #[test]
+    fn test_generate_vitest_bench() {
+        let source = "function add(a, b) { return a + b; }\nfunction internal() {}\nmodule.exports = { add };\n";
+        let functions = [function("add", &["a", "b"]), function("internal", &[])];
+
+        let files = BenchGenerator::generate("javascript", Path::new("src/math.js"), source, &functions).unwrap();
+        assert_eq!(files[0].path, Path::new("src/math.bench.js"));
+        assert!(files[0].content.contains("import { add } from './math.js';"));
+        assert!(files[0].content.contains("  bench('add', () => {\n    add(5, 5);\n  });\n"));
+    }
+
+    #[test]
+    fn test_generate_criterion_bench() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"demo-lib\"\nversion = \"0.1.0\"\n").unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("deno.json"), "{}").unwrap();
+        let source_path = dir.path().join("src/stats.rs");
+        let source = "pub fn mean(values: &str) -> f64 {\n    values.chars().count() as f64\n}\n";
+        fs::write(&source_path, source).unwrap();
+
+        let files = BenchGenerator::generate("rust", &source_path, source, &[function("mean", &["values: &str"])]).unwrap();
+        assert!(files[0].path.ends_with("benches/stats_bench.rs"));
+        assert!(files[0].content.contains("use demo_lib::stats::mean;"));
+        assert!(files[0].content.contains("b.iter(|| mean(black_box(\"test_string_0\")))"));
+        assert!(files[0].content.contains("criterion_group!(benches, bench_mean);"));
+
+        assert_eq!(BenchGenerator::toolchain("javascript", &dir.path().join("src/app.js")), Some("Deno.bench"));
+        assert!(BenchGenerator::generate("go", Path::new("main.go"), "", &[]).is_err());
+    }
+}
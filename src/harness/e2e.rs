@@ -0,0 +1,352 @@
+use anyhow::Result;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::HarnessFile;
+
+/// A page route declared in a router config or implied by a pages/app directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendRoute {
+    /// Path as declared, e.g. `/users/:id` or `/users/[id]`
+    pub path: String,
+    /// Component rendered for the route, when the declaration names one
+    pub component: Option<String>,
+}
+
+impl FrontendRoute {
+    /// Path to visit, dynamic segments filled with sample values
+    pub fn sample_path(&self) -> String {
+        let segments: Vec<&str> = self.path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                if segment.starts_with("[...") || segment.starts_with("[[...") || segment.ends_with('*') {
+                    "example"
+                } else if segment.starts_with(':') || segment.starts_with('[') {
+                    "1"
+                } else {
+                    segment
+                }
+            })
+            .collect();
+        format!("/{}", segments.join("/"))
+    }
+
+    /// Catch-all "not found" routes, which have no page of their own to check
+    fn is_fallback(&self) -> bool {
+        matches!(self.path.as_str(), "*" | "/*" | "**" | "/**") || self.path.contains(":pathMatch(")
+    }
+}
+
+/// Browser test runner for the generated specs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum E2eFramework {
+    Playwright,
+    Cypress,
+}
+
+impl E2eFramework {
+    /// Cypress when the project already uses it and not Playwright, otherwise Playwright
+    pub fn detect(project_root: &Path) -> Self {
+        let manifest = std::fs::read_to_string(project_root.join("package.json")).unwrap_or_default();
+        let has_config = |name: &str| ["js", "ts", "mjs", "cjs"].iter().any(|ext| project_root.join(format!("{}.config.{}", name, ext)).is_file());
+        let cypress = manifest.contains("\"cypress\"") || has_config("cypress");
+        let playwright = manifest.contains("\"@playwright/test\"") || has_config("playwright");
+        if cypress && !playwright { E2eFramework::Cypress } else { E2eFramework::Playwright }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            E2eFramework::Playwright => "Playwright",
+            E2eFramework::Cypress => "Cypress",
+        }
+    }
+}
+
+/// Generates browser specs visiting every route of a frontend project and checking the page
+/// renders: a successful response, visible text and no uncaught errors
+pub struct E2eGenerator;
+
+impl E2eGenerator {
+    /// Routes declared in `path` (a file or a project directory): React Router `<Route>`
+    /// elements, route objects (React Router, Vue Router, Angular) and, for Next.js and Nuxt
+    /// projects, pages under `pages/` or `app/`
+    pub fn find_routes(path: &Path) -> Vec<FrontendRoute> {
+        let file_routing = file_based_routing(&Self::project_root(path));
+        let mut routes: Vec<FrontendRoute> = Vec::new();
+        let files = WalkDir::new(path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_skipped_dir(entry.file_name().to_str().unwrap_or_default()))
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file());
+        for entry in files {
+            let file = entry.path();
+            if !matches!(file.extension().and_then(|e| e.to_str()), Some("js" | "jsx" | "ts" | "tsx" | "mjs" | "vue")) {
+                continue;
+            }
+            let Ok(source) = std::fs::read_to_string(file) else { continue };
+            let mut found = declared_routes(&source);
+            if file_routing {
+                found.extend(page_route(file, &source));
+            }
+            for route in found {
+                if !route.is_fallback() && !routes.iter().any(|known| known.path == route.path) {
+                    routes.push(route);
+                }
+            }
+        }
+        routes
+    }
+
+    /// Nearest directory at or above `path` with a `package.json`
+    pub fn project_root(path: &Path) -> PathBuf {
+        let start = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        start.ancestors()
+            .find(|dir| dir.join("package.json").is_file())
+            .map(Path::to_path_buf)
+            .unwrap_or(start)
+    }
+
+    /// The spec for `routes`, placed where the framework looks for tests under the project root
+    pub fn generate(project_root: &Path, framework: E2eFramework, routes: &[FrontendRoute]) -> Result<Vec<HarnessFile>> {
+        if routes.is_empty() {
+            return Ok(vec![]);
+        }
+        let typescript = project_root.join("tsconfig.json").is_file();
+        let extension = if typescript { "ts" } else { "js" };
+        let base_url = format!("http://localhost:{}", dev_server_port(project_root));
+
+        let file = match framework {
+            E2eFramework::Playwright => HarnessFile {
+                path: project_root.join("e2e").join(format!("routes.spec.{}", extension)),
+                content: Self::playwright_spec(&base_url, routes, typescript),
+            },
+            E2eFramework::Cypress => HarnessFile {
+                path: project_root.join("cypress").join("e2e").join(format!("routes.cy.{}", extension)),
+                content: Self::cypress_spec(&base_url, routes),
+            },
+        };
+        Ok(vec![file])
+    }
+
+    fn playwright_spec(base_url: &str, routes: &[FrontendRoute], typescript: bool) -> String {
+        let mut code = format!(
+            "import {{ test, expect }} from '@playwright/test';\n\n// Start the app first; BASE_URL defaults to the dev server's usual address\nconst BASE_URL = process.env.BASE_URL || '{}';\n\ntest.describe('routes', () => {{\n",
+            base_url
+        );
+        for (i, route) in routes.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            code.push_str(&format!(
+                "  test('{}', async ({{ page }}) => {{\n    const errors{} = [];\n    page.on('pageerror', (error) => errors.push(error.message));\n    const response = await page.goto(`${{BASE_URL}}{}`);\n    expect(response?.status()).toBeLessThan(400);\n    await expect(page.locator('body')).not.toHaveText(/^\\s*$/);\n    expect(errors).toEqual([]);\n  }});\n",
+                test_title(route),
+                if typescript { ": string[]" } else { "" },
+                route.sample_path()
+            ));
+        }
+        code.push_str("});\n");
+        code
+    }
+
+    fn cypress_spec(base_url: &str, routes: &[FrontendRoute]) -> String {
+        // cy.visit fails on error responses and Cypress fails tests on uncaught exceptions
+        let mut code = format!(
+            "// Start the app first; CYPRESS_BASE_URL overrides the dev server's usual address\nconst BASE_URL = Cypress.env('BASE_URL') || '{}';\n\ndescribe('routes', () => {{\n",
+            base_url
+        );
+        for (i, route) in routes.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            code.push_str(&format!(
+                "  it('{}', () => {{\n    cy.visit(`${{BASE_URL}}{}`);\n    cy.get('body').invoke('text').should('match', /\\S/);\n  }});\n",
+                test_title(route),
+                route.sample_path()
+            ));
+        }
+        code.push_str("});\n");
+        code
+    }
+}
+
+fn test_title(route: &FrontendRoute) -> String {
+    let title = match &route.component {
+        Some(component) => format!("{} renders {}", route.path, component),
+        None => format!("{} renders", route.path),
+    };
+    title.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+fn is_skipped_dir(name: &str) -> bool {
+    name.starts_with('.') || matches!(name, "node_modules" | "dist" | "build" | "out" | "coverage" | "e2e" | "cypress" | "__tests__")
+}
+
+/// Whether the project routes by file (Next.js or Nuxt)
+fn file_based_routing(project_root: &Path) -> bool {
+    let manifest = std::fs::read_to_string(project_root.join("package.json")).unwrap_or_default();
+    manifest.contains("\"next\"") || manifest.contains("\"nuxt\"")
+}
+
+/// Port the framework's dev server listens on by default
+fn dev_server_port(project_root: &Path) -> u16 {
+    let manifest = std::fs::read_to_string(project_root.join("package.json")).unwrap_or_default();
+    if manifest.contains("\"@angular/core\"") {
+        4200
+    } else if manifest.contains("\"vite\"") {
+        5173
+    } else if manifest.contains("\"@vue/cli-service\"") {
+        8080
+    } else {
+        3000
+    }
+}
+
+/// `<Route path=...>` elements, and `path:` entries of route objects in files using a router
+fn declared_routes(source: &str) -> Vec<FrontendRoute> {
+    let mut routes = Vec::new();
+    let path_attr = Regex::new(r#"\bpath=\{?\s*["'`]([^"'`]*)["'`]"#).unwrap();
+    let element = Regex::new(r"\b(?:element|Component)=\{\s*<?(\w+)|\bcomponent=\{(\w+)\}").unwrap();
+    for start in source.match_indices("<Route ").map(|(i, _)| i) {
+        let tag = jsx_tag(&source[start..]);
+        if let Some(cap) = path_attr.captures(tag) {
+            routes.push(FrontendRoute {
+                path: normalized_path(&cap[1]),
+                component: element.captures(tag).and_then(|c| c.get(1).or_else(|| c.get(2))).map(|m| m.as_str().to_string()),
+            });
+        }
+    }
+
+    let uses_router = ["createBrowserRouter", "createHashRouter", "createMemoryRouter", "useRoutes", "createRouter", "VueRouter", "RouterModule", "Routes = ["]
+        .iter()
+        .any(|marker| source.contains(marker));
+    if uses_router {
+        let path_key = Regex::new(r#"\bpath\s*:\s*["'`]([^"'`]*)["'`]"#).unwrap();
+        let component_key = Regex::new(r"\b(?:element\s*:\s*<(\w+)|[cC]omponent\s*:\s*(\w+))").unwrap();
+        let matches: Vec<_> = path_key.captures_iter(source).collect();
+        for (i, cap) in matches.iter().enumerate() {
+            let whole = cap.get(0).unwrap();
+            // The rest of this route object: up to the next route's path
+            let end = matches.get(i + 1).map_or(source.len(), |next| next.get(0).unwrap().start());
+            let rest = &source[whole.end()..end];
+            let rest = &rest[..rest.find('}').unwrap_or(rest.len())];
+            routes.push(FrontendRoute {
+                path: normalized_path(&cap[1]),
+                component: component_key.captures(rest)
+                    .and_then(|c| c.get(1).or_else(|| c.get(2)))
+                    .map(|m| m.as_str().to_string())
+                    .filter(|name| name != "lazy"),
+            });
+        }
+    }
+    routes
+}
+
+/// Source of the JSX tag starting `source`, up to its closing `>` outside braces
+fn jsx_tag(source: &str) -> &str {
+    let mut depth = 0;
+    for (i, c) in source.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            '>' if depth == 0 => return &source[..=i],
+            _ => {}
+        }
+    }
+    source
+}
+
+fn normalized_path(path: &str) -> String {
+    if path.starts_with('/') || path.starts_with('*') { path.to_string() } else { format!("/{}", path) }
+}
+
+/// Route of a Next.js or Nuxt page file: `pages/users/[id].tsx` or `app/users/[id]/page.tsx`
+fn page_route(file: &Path, source: &str) -> Option<FrontendRoute> {
+    let components: Vec<&str> = file.iter().filter_map(|c| c.to_str()).collect();
+    let stem = file.file_stem()?.to_str()?;
+    let mut segments: Vec<&str> = if let Some(pages) = components.iter().rposition(|c| *c == "pages") {
+        let dirs = &components[pages + 1..components.len() - 1];
+        if dirs.first() == Some(&"api") || stem.starts_with('_') || matches!(stem, "404" | "500") {
+            return None;
+        }
+        dirs.iter().copied().chain((stem != "index").then_some(stem)).collect()
+    } else if let Some(app) = components.iter().rposition(|c| *c == "app") {
+        if stem != "page" {
+            return None;
+        }
+        let dirs = &components[app + 1..components.len() - 1];
+        if dirs.iter().any(|dir| dir.starts_with('_') || dir.starts_with('@')) {
+            return None;
+        }
+        dirs.to_vec()
+    } else {
+        return None;
+    };
+    // Route groups like `(marketing)` don't appear in the URL
+    segments.retain(|segment| !(segment.starts_with('(') && segment.ends_with(')')));
+
+    let component = Regex::new(r"export\s+default\s+(?:async\s+)?(?:function\s+|class\s+)?([A-Z]\w*)").unwrap()
+        .captures(source)
+        .map(|cap| cap[1].to_string());
+    Some(FrontendRoute { path: format!("/{}", segments.join("/")), component })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_routes() {
+        let source = r#"
+import { BrowserRouter, Routes, Route } from 'react-router-dom';
+export default function App() {
+  return (
+    <Routes>
+      <Route path="/" element={<Home />} />
+      <Route path="users/:id" element={<UserPage user={{ admin: false }} />} />
+      <Route path="*" element={<NotFound />} />
+    </Routes>
+  );
+}
+const router = createRouter({ routes: [
+  { path: '/about', component: About },
+  { path: '/settings', component: () => import('./Settings.vue') },
+] });
+"#;
+        let routes = declared_routes(source);
+        assert_eq!(routes[0], FrontendRoute { path: "/".to_string(), component: Some("Home".to_string()) });
+        assert_eq!(routes[1], FrontendRoute { path: "/users/:id".to_string(), component: Some("UserPage".to_string()) });
+        assert!(routes[2].is_fallback());
+        assert_eq!(routes[3], FrontendRoute { path: "/about".to_string(), component: Some("About".to_string()) });
+        assert_eq!(routes[4], FrontendRoute { path: "/settings".to_string(), component: None });
+
+        assert_eq!(routes[1].sample_path(), "/users/1");
+        let page = page_route(Path::new("src/app/(shop)/products/[...slug]/page.tsx"), "export default function ProductPage() {}").unwrap();
+        assert_eq!((page.path.as_str(), page.sample_path().as_str()), ("/products/[...slug]", "/products/example"));
+        assert_eq!(page_route(Path::new("pages/index.jsx"), "").unwrap().path, "/");
+        assert!(page_route(Path::new("pages/api/users.js"), "").is_none());
+    }
+
+    #[test]
+    fn test_generate_specs() {
+        let dir = tempfile::tempdir().unwrap();
+        let routes = vec![
+            FrontendRoute { path: "/".to_string(), component: Some("Home".to_string()) },
+            FrontendRoute { path: "/users/:id".to_string(), component: None },
+        ];
+        std::fs::write(dir.path().join("package.json"), r#"{"devDependencies": {"vite": "^5.0.0"}}"#).unwrap();
+        assert_eq!(E2eFramework::detect(dir.path()), E2eFramework::Playwright);
+        let files = E2eGenerator::generate(dir.path(), E2eFramework::Playwright, &routes).unwrap();
+        assert_eq!(files[0].path, dir.path().join("e2e/routes.spec.js"));
+        assert!(files[0].content.contains("const BASE_URL = process.env.BASE_URL || 'http://localhost:5173';\n"));
+        assert!(files[0].content.contains("  test('/ renders Home', async ({ page }) => {\n    const errors = [];\n"));
+        assert!(files[0].content.contains("    const response = await page.goto(`${BASE_URL}/users/1`);\n    expect(response?.status()).toBeLessThan(400);\n    await expect(page.locator('body')).not.toHaveText(/^\\s*$/);\n"));
+
+        std::fs::write(dir.path().join("package.json"), r#"{"devDependencies": {"cypress": "^13.0.0"}}"#).unwrap();
+        assert_eq!(E2eFramework::detect(dir.path()), E2eFramework::Cypress);
+        let files = E2eGenerator::generate(dir.path(), E2eFramework::Cypress, &routes).unwrap();
+        assert_eq!(files[0].path, dir.path().join("cypress/e2e/routes.cy.js"));
+        assert!(files[0].content.contains("  it('/users/:id renders', () => {\n    cy.visit(`${BASE_URL}/users/1`);\n    cy.get('body').invoke('text').should('match', /\\S/);\n"));
+    }
+}
@@ -0,0 +1,319 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use crate::adapters::python::python_literal;
+use crate::core::{TestCategory, TestSuite};
+
+/// Prefix marking result lines in the evaluation script's output
+const RESULT_MARKER: &str = "__utf_result__";
+
+/// A call to a function under test with arguments written as source literals
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleCall {
+    pub function: String,
+    pub args: String,
+}
+
+/// Runs detected functions with their generated sample inputs and bakes the observed
+/// outputs into the tests' assertions
+pub struct Executor {
+    language: String,
+    source_path: PathBuf,
+    /// Wall-clock limit for the evaluation process, including compilation
+    pub timeout: Duration,
+}
+
+impl Executor {
+    /// Executor for a built-in language that can be evaluated; `None` for anything else
+    pub fn new(language: &str, source_path: &Path) -> Option<Self> {
+        let timeout = match language {
+            "javascript" | "python" => Duration::from_secs(10),
+            "rust" => Duration::from_secs(60),
+            _ => return None,
+        };
+
+        Some(Self {
+            language: language.to_string(),
+            source_path: source_path.canonicalize().unwrap_or_else(|_| source_path.to_path_buf()),
+            timeout,
+        })
+    }
+
+    /// The `result = f(args)` call a happy-path test makes, with the index of its line
+    pub fn sample_call(&self, test_body: &str) -> Option<(usize, SampleCall)> {
+        let pattern = match self.language.as_str() {
+            "javascript" => r"^\s*const result = (\w+)\((.*)\);\s*$",
+            "python" => r"^\s*result = (\w+)\((.*)\)\s*$",
+            _ => r"^\s*let result = (\w+)\((.*)\);\s*$",
+        };
+        let regex = Regex::new(pattern).unwrap();
+
+        test_body.lines().enumerate().find_map(|(index, line)| {
+            regex.captures(line).map(|cap| (index, SampleCall { function: cap[1].to_string(), args: cap[2].to_string() }))
+        })
+    }
+
+    /// Script evaluating every call against the source file and printing one marked line per
+    /// successful call, in order
+    pub fn eval_script(&self, source: &str, calls: &[SampleCall]) -> String {
+        let mut script = String::new();
+
+        match self.language.as_str() {
+            "javascript" => {
+                let export_regex = Regex::new(r"(?m)^export\s+(?:default\s+)?").unwrap();
+                script.push_str(&export_regex.replace_all(source, ""));
+                script.push('\n');
+                for (i, call) in calls.iter().enumerate() {
+                    script.push_str(&format!(
+                        "try {{ const __out = JSON.stringify({}({})); if (__out !== undefined) console.log('{}{} ' + __out); }} catch (_) {{}}\n",
+                        call.function, call.args, RESULT_MARKER, i
+                    ));
+                }
+            }
+            "python" => {
+                let directory = self.source_path.parent().unwrap_or_else(|| Path::new("."));
+                let module = self.source_path.file_stem().and_then(|s| s.to_str()).unwrap_or("module");
+                script.push_str("import json, sys\n");
+                script.push_str(&format!("sys.path.insert(0, {:?})\n", directory.display().to_string()));
+                script.push_str(&format!("import {} as __target\n", module));
+                for (i, call) in calls.iter().enumerate() {
+                    script.push_str(&format!(
+                        "try:\n    print('{}{} ' + json.dumps(__target.{}({})))\nexcept Exception:\n    pass\n",
+                        RESULT_MARKER, i, call.function, call.args
+                    ));
+                }
+            }
+            _ => {
+                script.push_str("#[allow(dead_code, unused)]\n");
+                script.push_str(&format!("#[path = {:?}]\nmod target;\n\n", self.source_path.display().to_string()));
+                script.push_str("fn main() {\n    std::panic::set_hook(Box::new(|_| {}));\n");
+                for (i, call) in calls.iter().enumerate() {
+                    script.push_str(&format!(
+                        "    if let Ok(out) = std::panic::catch_unwind(|| format!(\"{{:?}}\", target::{}({}))) {{\n        println!(\"{}{} {{}}\", out);\n    }}\n",
+                        call.function, call.args, RESULT_MARKER, i
+                    ));
+                }
+                script.push_str("}\n");
+            }
+        }
+
+        script
+    }
+
+    /// Run the calls in a scratch directory with a cleared environment and the configured timeout.
+    /// Calls that fail, panic or return something without a literal form yield `None`.
+    pub async fn run(&self, source: &str, calls: &[SampleCall]) -> Result<Vec<Option<Value>>> {
+        if calls.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let scratch = std::env::temp_dir().join(format!("utf-execute-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&scratch)?;
+        let output = self.run_in(&scratch, source, calls).await;
+        let _ = std::fs::remove_dir_all(&scratch);
+        let stdout = output?;
+
+        let mut results = vec![None; calls.len()];
+        for line in stdout.lines() {
+            let Some((index, payload)) = line.strip_prefix(RESULT_MARKER).and_then(|rest| rest.split_once(' ')) else {
+                continue;
+            };
+            if let (Ok(index), Ok(value)) = (index.parse::<usize>(), serde_json::from_str::<Value>(payload)) {
+                if let Some(slot) = results.get_mut(index) {
+                    *slot = Some(value);
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    async fn run_in(&self, scratch: &Path, source: &str, calls: &[SampleCall]) -> Result<String> {
+        let script = self.eval_script(source, calls);
+
+        let mut command = match self.language.as_str() {
+            "javascript" => self.node_command(scratch, source, &script),
+            "python" => {
+                let mut command = sandboxed("python3", scratch);
+                command.arg("-c").arg(&script);
+                command
+            }
+            _ => {
+                let main = scratch.join("main.rs");
+                let binary = scratch.join("eval");
+                std::fs::write(&main, &script)?;
+
+                let mut compile = sandboxed("rustc", scratch);
+                compile.args(["--edition", "2021", "-A", "warnings", "-o"]).arg(&binary).arg(&main);
+                let compiled = tokio::time::timeout(self.timeout, compile.output()).await
+                    .map_err(|_| anyhow!("Compiling the evaluation harness timed out after {:?}", self.timeout))??;
+                if !compiled.status.success() {
+                    return Err(anyhow!(
+                        "Could not compile {} standalone: {}",
+                        self.source_path.display(),
+                        String::from_utf8_lossy(&compiled.stderr).lines().find(|l| l.starts_with("error")).unwrap_or("unknown error")
+                    ));
+                }
+                sandboxed(&binary.display().to_string(), scratch)
+            }
+        };
+
+        let output = tokio::time::timeout(self.timeout, command.output()).await
+            .map_err(|_| anyhow!("Executing {} timed out after {:?}", self.source_path.display(), self.timeout))??;
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        // The calls catch their own errors, so a failed run never got as far as making them
+        if !output.status.success() && !stdout.contains(RESULT_MARKER) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!(
+                "No calls ran, loading {} failed: {}",
+                self.source_path.display(),
+                stderr.lines().find(|l| l.split(':').next().is_some_and(|head| head.ends_with("Error"))).unwrap_or("unknown error")
+            ));
+        }
+        Ok(stdout)
+    }
+
+    /// `node` evaluating `script` from the source's directory, so the relative modules it
+    /// imports resolve, and as an ES module when the source uses `import`
+    fn node_command(&self, scratch: &Path, source: &str, script: &str) -> tokio::process::Command {
+        let directory = self.source_path.parent().filter(|dir| dir.is_dir()).unwrap_or(scratch);
+        let mut command = sandboxed("node", directory);
+        if Regex::new(r"(?m)^\s*import\s+[\w{*'\x22]").unwrap().is_match(source) {
+            command.arg("--input-type=module");
+        }
+        command.arg("--eval").arg(script);
+        command
+    }
+
+    /// Execute each happy-path test's sample call and add an exact assertion on the observed
+    /// output; returns how many tests were updated
+    pub async fn bake(&self, suite: &mut TestSuite, source: &str) -> Result<usize> {
+        let targets: Vec<(usize, usize, SampleCall)> = suite.test_cases.iter().enumerate()
+            .filter(|(_, test)| matches!(test.test_category, TestCategory::HappyPath))
+            .filter_map(|(i, test)| self.sample_call(&test.test_body).map(|(line, call)| (i, line, call)))
+            .collect();
+        let calls: Vec<SampleCall> = targets.iter().map(|(_, _, call)| call.clone()).collect();
+        let outputs = self.run(source, &calls).await?;
+
+        let mut baked = 0;
+        for ((test_index, line_index, _), output) in targets.into_iter().zip(outputs) {
+            let Some(value) = output else { continue };
+            let test = &mut suite.test_cases[test_index];
+            let body = self.insert_assertion(&test.test_body, line_index, &value);
+            // Adapters render the file up front, so patch the rendered body as well
+            if let Some(code) = suite.test_code.as_mut() {
+                *code = code.replace(&test.test_body, &body);
+            }
+            test.test_body = body;
+            test.expected_output = value;
+            baked += 1;
+        }
+        Ok(baked)
+    }
+
+    /// Insert an equality assertion on `result` after the given line, matching its indentation
+    fn insert_assertion(&self, test_body: &str, line_index: usize, value: &Value) -> String {
+        let mut lines: Vec<String> = test_body.lines().map(str::to_string).collect();
+        let indent: String = lines[line_index].chars().take_while(|c| c.is_whitespace()).collect();
+
+        let assertion = match self.language.as_str() {
+            "javascript" if test_body.contains("expect(") => format!("expect(result).toEqual({});", value),
+            "javascript" => format!("assert.deepStrictEqual(result, {});", value),
+            "python" => format!("assert result == {}", python_literal(value)),
+            _ => format!("assert_eq!(result, {});", rust_expected_literal(value)),
+        };
+        lines.insert(line_index + 1, format!("{}{}", indent, assertion));
+
+        let mut body = lines.join("\n");
+        if test_body.ends_with('\n') {
+            body.push('\n');
+        }
+        body
+    }
+}
+
+/// Command running in `directory` with only the variables needed to locate toolchains
+fn sandboxed(program: &str, directory: &Path) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new(program);
+    command.current_dir(directory)
+        .env_clear()
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    for key in ["PATH", "HOME", "RUSTUP_HOME", "CARGO_HOME", "RUSTUP_TOOLCHAIN"] {
+        if let Ok(value) = std::env::var(key) {
+            command.env(key, value);
+        }
+    }
+    command
+}
+
+/// Rust expression equal to a value printed with `{:?}`: strings compare against `&str`
+/// and sequences against arrays
+fn rust_expected_literal(value: &Value) -> String {
+    match value {
+        Value::Array(items) => format!("[{}]", items.iter().map(rust_expected_literal).collect::<Vec<_>>().join(", ")),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_call() {
+        let executor = Executor::new("python", Path::new("calc.py")).unwrap();
+        let body = "        \"\"\"doc\"\"\"\n        result = add(5, 3)\n        assert result is not None\n";
+        assert_eq!(
+            executor.sample_call(body),
+            Some((1, SampleCall { function: "add".to_string(), args: "5, 3".to_string() }))
+        );
+        assert!(executor.sample_call("        fixture.add(5, 3)\n").is_none());
+        assert!(Executor::new("go", Path::new("calc.go")).is_none());
+    }
+
+    #[test]
+    fn test_insert_assertion() {
+        let js = Executor::new("javascript", Path::new("calc.js")).unwrap();
+        let body = "    const result = greet(\"Ada\");\n    expect(result).toBeDefined();\n";
+        assert_eq!(
+            js.insert_assertion(body, 0, &serde_json::json!("Hello Ada")),
+            "    const result = greet(\"Ada\");\n    expect(result).toEqual(\"Hello Ada\");\n    expect(result).toBeDefined();\n"
+        );
+
+        let python = Executor::new("python", Path::new("calc.py")).unwrap();
+        assert_eq!(
+            python.insert_assertion("        result = flags(1)\n", 0, &serde_json::json!([true, null])),
+            "        result = flags(1)\n        assert result == [True, None]\n"
+        );
+    }
+
+    #[test]
+    fn test_node_runs_from_the_source_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let source_path = dir.path().join("calc.js");
+        std::fs::write(&source_path, "").unwrap();
+        let executor = Executor::new("javascript", &source_path).unwrap();
+        let scratch = std::env::temp_dir();
+
+        let command = executor.node_command(&scratch, "const db = require('./db');\n", "script");
+        assert_eq!(command.as_std().get_current_dir(), Some(executor.source_path.parent().unwrap()));
+        assert_eq!(command.as_std().get_args().collect::<Vec<_>>(), ["--eval", "script"]);
+
+        let command = executor.node_command(&scratch, "import { find } from './db.js';\n", "script");
+        assert_eq!(command.as_std().get_args().collect::<Vec<_>>(), ["--input-type=module", "--eval", "script"]);
+    }
+
+    #[test]
+    fn test_rust_eval_script() {
+        let executor = Executor::new("rust", Path::new("/work/calc.rs")).unwrap();
+        let script = executor.eval_script("", &[SampleCall { function: "add".to_string(), args: "5, 3".to_string() }]);
+        assert!(script.contains("#[path = \"/work/calc.rs\"]\nmod target;"));
+        assert!(script.contains("format!(\"{:?}\", target::add(5, 3))"));
+        assert!(script.contains("println!(\"__utf_result__0 {}\", out);"));
+    }
+}
@@ -0,0 +1,467 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::path::Path;
+
+use super::{find_cargo_root, rust_crate_name, rust_module_path, HarnessFile};
+use crate::core::workspace::cargo_package_name;
+use crate::templates::filters;
+
+/// Kind of input a fuzzable function accepts
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuzzInput {
+    /// UTF-8 / NUL-terminated text
+    Text,
+    /// Raw bytes (with an explicit length for C/C++)
+    Bytes,
+}
+
+/// A function whose only input is a string or byte buffer
+#[derive(Debug, Clone)]
+pub struct FuzzableFunction {
+    pub name: String,
+    /// Declared parameter type(s), as written in the source
+    pub param_type: String,
+    pub input: FuzzInput,
+    /// Full signature, used to declare C/C++ functions in the harness
+    pub signature: String,
+}
+
+/// Generates cargo-fuzz targets, libFuzzer harnesses, Go fuzz tests and Atheris harnesses
+pub struct FuzzGenerator;
+
+impl FuzzGenerator {
+    /// Fuzzing toolchain used for a language
+    pub fn toolchain(language: &str) -> Option<&'static str> {
+        match language {
+            "rust" => Some("cargo-fuzz"),
+            "c" | "cpp" => Some("libFuzzer"),
+            "go" => Some("go test -fuzz"),
+            "python" => Some("atheris"),
+            _ => None,
+        }
+    }
+
+    /// Functions taking a single string or byte input
+    pub fn find_fuzzable_functions(language: &str, source: &str) -> Vec<FuzzableFunction> {
+        match language {
+            "rust" => Self::find_rust_functions(source),
+            "go" => Self::find_go_functions(source),
+            "python" => Self::find_python_functions(source),
+            "c" | "cpp" => Self::find_c_functions(source),
+            _ => vec![],
+        }
+    }
+
+    /// Build harness files for every fuzzable function in `source_path`
+    pub fn generate(language: &str, source_path: &Path, source: &str) -> Result<Vec<HarnessFile>> {
+        if Self::toolchain(language).is_none() {
+            return Err(anyhow!("Fuzz target generation not supported for language: {}", language));
+        }
+
+        let functions = Self::find_fuzzable_functions(language, source);
+        if functions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let source_dir = source_path.parent().unwrap_or_else(|| Path::new(""));
+        let stem = source_path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("module");
+
+        let files = match language {
+            "rust" => Self::rust_targets(source_path, &functions)?,
+            "go" => vec![HarnessFile {
+                path: source_dir.join(format!("{}_fuzz_test.go", stem)),
+                content: Self::go_fuzz_tests(source, &functions),
+            }],
+            "python" => functions.iter()
+                .map(|func| HarnessFile {
+                    path: source_dir.join(format!("fuzz_{}.py", func.name)),
+                    content: Self::atheris_harness(stem, func),
+                })
+                .collect(),
+            _ => {
+                let extension = if language == "cpp" { "cc" } else { "c" };
+                functions.iter()
+                    .map(|func| HarnessFile {
+                        path: source_dir.join(format!("{}_fuzzer.{}", func.name, extension)),
+                        content: Self::libfuzzer_harness(func),
+                    })
+                    .collect()
+            }
+        };
+
+        Ok(files)
+    }
+
+    /// An existing `fuzz/Cargo.toml` with the `[[bin]]` targets of a generated one it doesn't
+    /// declare yet appended, and how many were added; `None` when there's nothing to add or
+    /// the existing manifest doesn't parse
+    pub fn merge_manifest(existing: &str, generated: &str) -> Option<(String, usize)> {
+        let bin_name = |manifest: &toml::Value| manifest.get("name").and_then(toml::Value::as_str).map(str::to_string);
+        let declared: Vec<String> = toml::from_str::<toml::Value>(existing).ok()?
+            .get("bin").and_then(toml::Value::as_array).into_iter().flatten()
+            .filter_map(bin_name)
+            .collect();
+
+        let mut merged = existing.trim_end().to_string();
+        merged.push('\n');
+        let mut added = 0;
+        for target in generated.split("\n[[bin]]\n").skip(1) {
+            let name = toml::from_str::<toml::Value>(target).ok().as_ref().and_then(bin_name);
+            if name.is_some_and(|name| !declared.contains(&name)) {
+                merged.push_str(&format!("\n[[bin]]\n{}", target));
+                added += 1;
+            }
+        }
+        (added > 0).then_some((merged, added))
+    }
+
+    fn find_rust_functions(source: &str) -> Vec<FuzzableFunction> {
+        let fn_regex = Regex::new(r"(?m)^\s*pub(?:\([^)]*\))?\s+fn\s+(\w+)\s*(?:<[^>]*>)?\s*\(([^)]*)\)").unwrap();
+        let lifetime_regex = Regex::new(r"'\w+\s*").unwrap();
+
+        fn_regex.captures_iter(source)
+            .filter_map(|cap| {
+                let params = Self::split_params(&cap[2]);
+                let [param] = params.as_slice() else { return None };
+                let (_, ty) = param.split_once(':')?;
+                let ty: String = lifetime_regex.replace_all(ty, "").split_whitespace().collect();
+
+                let input = match ty.as_str() {
+                    "&str" | "String" | "&String" => FuzzInput::Text,
+                    "&[u8]" | "Vec<u8>" | "&Vec<u8>" => FuzzInput::Bytes,
+                    _ => return None,
+                };
+
+                Some(FuzzableFunction {
+                    name: cap[1].to_string(),
+                    param_type: ty,
+                    input,
+                    signature: cap[0].trim().to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn find_go_functions(source: &str) -> Vec<FuzzableFunction> {
+        let func_regex = Regex::new(r"(?m)^func\s+(\w+)\s*\(([^)]*)\)").unwrap();
+
+        func_regex.captures_iter(source)
+            .filter_map(|cap| {
+                let name = &cap[1];
+                if name == "main" || name == "init" || ["Test", "Fuzz", "Benchmark"].iter().any(|p| name.starts_with(p)) {
+                    return None;
+                }
+
+                let params = Self::split_params(&cap[2]);
+                let [param] = params.as_slice() else { return None };
+                let parts: Vec<&str> = param.split_whitespace().collect();
+                let [_, ty] = parts.as_slice() else { return None };
+
+                let input = match *ty {
+                    "string" => FuzzInput::Text,
+                    "[]byte" => FuzzInput::Bytes,
+                    _ => return None,
+                };
+
+                Some(FuzzableFunction {
+                    name: name.to_string(),
+                    param_type: ty.to_string(),
+                    input,
+                    signature: cap[0].to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn find_python_functions(source: &str) -> Vec<FuzzableFunction> {
+        // Top-level functions only; methods would need an instance to call
+        let def_regex = Regex::new(r"(?m)^def\s+(\w+)\s*\(([^)]*)\)").unwrap();
+
+        def_regex.captures_iter(source)
+            .filter_map(|cap| {
+                let name = &cap[1];
+                if name.starts_with('_') || name.starts_with("test") {
+                    return None;
+                }
+
+                let params = Self::split_params(&cap[2]);
+                let [param] = params.as_slice() else { return None };
+                let (param_name, annotation) = match param.split_once(':') {
+                    Some((param_name, annotation)) => (param_name.trim(), annotation.split('=').next().unwrap_or("").trim()),
+                    None => (param.split('=').next().unwrap_or("").trim(), ""),
+                };
+
+                let input = match annotation {
+                    "str" => FuzzInput::Text,
+                    "bytes" | "bytearray" => FuzzInput::Bytes,
+                    "" if ["data", "raw", "payload", "buf", "buffer"].contains(&param_name) => FuzzInput::Bytes,
+                    "" if ["text", "s", "string", "input", "content", "source", "line"].contains(&param_name) => FuzzInput::Text,
+                    _ => return None,
+                };
+
+                Some(FuzzableFunction {
+                    name: name.to_string(),
+                    param_type: annotation.to_string(),
+                    input,
+                    signature: cap[0].to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn find_c_functions(source: &str) -> Vec<FuzzableFunction> {
+        // Definitions starting at column 0; static functions aren't linkable from a harness
+        let def_regex = Regex::new(r"(?m)^([A-Za-z_][\w \t\*]*?[\s\*])(\w+)\s*\(([^)]*)\)\s*\{").unwrap();
+        let text_regex = Regex::new(r"^(?:const\s+)?char\s*\*\s*\w*$").unwrap();
+        let bytes_regex = Regex::new(r"^(?:const\s+)?(?:uint8_t|unsigned\s+char|char|void)\s*\*\s*\w*$").unwrap();
+        let len_regex = Regex::new(r"^(?:const\s+)?(?:size_t|int|unsigned(?:\s+int)?|long|unsigned\s+long)\s+\w+$").unwrap();
+
+        def_regex.captures_iter(source)
+            .filter_map(|cap| {
+                let return_type = cap[1].trim();
+                if return_type.contains("static") || ["if", "while", "for", "switch", "return", "else"].contains(&return_type) {
+                    return None;
+                }
+
+                let params = Self::split_params(&cap[3]);
+                let input = match params.as_slice() {
+                    [text] if text_regex.is_match(text) => FuzzInput::Text,
+                    [buf, len] if bytes_regex.is_match(buf) && len_regex.is_match(len) => FuzzInput::Bytes,
+                    _ => return None,
+                };
+
+                Some(FuzzableFunction {
+                    name: cap[2].to_string(),
+                    param_type: params.join(", "),
+                    input,
+                    signature: format!("{} {}({})", return_type, &cap[2], cap[3].trim()),
+                })
+            })
+            .collect()
+    }
+
+    fn split_params(params: &str) -> Vec<String> {
+        params.split(',')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty() && p != "void")
+            .collect()
+    }
+
+    fn rust_targets(source_path: &Path, functions: &[FuzzableFunction]) -> Result<Vec<HarnessFile>> {
+        let crate_root = find_cargo_root(source_path)
+            .ok_or_else(|| anyhow!("No Cargo.toml found above {}", source_path.display()))?;
+        let crate_name = rust_crate_name(&crate_root)
+            .ok_or_else(|| anyhow!("Could not read package name from {}", crate_root.join("Cargo.toml").display()))?;
+        // The dependency is keyed by package name; `use` paths take the library's name
+        let package_name = cargo_package_name(&crate_root).unwrap_or_else(|| crate_name.clone());
+        let module_path = rust_module_path(&crate_name, &crate_root, source_path);
+        let fuzz_dir = crate_root.join("fuzz");
+
+        let mut files: Vec<HarnessFile> = functions.iter()
+            .map(|func| {
+                let call = match func.param_type.as_str() {
+                    "&str" => "let _ = {f}(input);",
+                    "String" => "let _ = {f}(input.to_string());",
+                    "&String" => "let _ = {f}(&input.to_string());",
+                    "&[u8]" => "let _ = {f}(data);",
+                    "Vec<u8>" => "let _ = {f}(data.to_vec());",
+                    _ => "let _ = {f}(&data.to_vec());",
+                }.replace("{f}", &func.name);
+
+                let body = match func.input {
+                    FuzzInput::Text => format!("    if let Ok(input) = std::str::from_utf8(data) {{\n        {}\n    }}\n", call),
+                    FuzzInput::Bytes => format!("    {}\n", call),
+                };
+
+                HarnessFile {
+                    path: fuzz_dir.join("fuzz_targets").join(format!("{}.rs", func.name)),
+                    content: format!(
+                        "#![no_main]\n\nuse libfuzzer_sys::fuzz_target;\nuse {}::{};\n\nfuzz_target!(|data: &[u8]| {{\n{}}});\n",
+                        module_path, func.name, body
+                    ),
+                }
+            })
+            .collect();
+
+        let mut manifest = format!(
+            "[package]\nname = \"{}-fuzz\"\nversion = \"0.0.0\"\npublish = false\nedition = \"2021\"\n\n[package.metadata]\ncargo-fuzz = true\n\n[dependencies]\nlibfuzzer-sys = \"0.4\"\n\n[dependencies.{}]\npath = \"..\"\n",
+            package_name, package_name
+        );
+        for func in functions {
+            manifest.push_str(&format!(
+                "\n[[bin]]\nname = \"{0}\"\npath = \"fuzz_targets/{0}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+                func.name
+            ));
+        }
+        files.push(HarnessFile {
+            path: fuzz_dir.join("Cargo.toml"),
+            content: manifest,
+        });
+
+        Ok(files)
+    }
+
+    fn go_fuzz_tests(source: &str, functions: &[FuzzableFunction]) -> String {
+        let package = Regex::new(r"(?m)^package\s+(\w+)").unwrap()
+            .captures(source)
+            .map(|cap| cap[1].to_string())
+            .unwrap_or_else(|| "main".to_string());
+
+        let mut code = format!("package {}\n\nimport \"testing\"\n", package);
+        for func in functions {
+            let (seed, ty) = match func.input {
+                FuzzInput::Text => ("\"example\"", "string"),
+                FuzzInput::Bytes => ("[]byte(\"example\")", "[]byte"),
+            };
+            code.push_str(&format!(
+                "\nfunc Fuzz{0}(f *testing.F) {{\n\tf.Add({1})\n\tf.Fuzz(func(t *testing.T, input {2}) {{\n\t\t{3}(input)\n\t}})\n}}\n",
+                filters::capitalize(&func.name), seed, ty, func.name
+            ));
+        }
+        code
+    }
+
+    fn atheris_harness(module: &str, func: &FuzzableFunction) -> String {
+        let call = match func.input {
+            FuzzInput::Text => format!("    fdp = atheris.FuzzedDataProvider(data)\n    {}(fdp.ConsumeUnicodeNoSurrogates(len(data)))\n", func.name),
+            FuzzInput::Bytes => format!("    {}(data)\n", func.name),
+        };
+
+        format!(
+            "import sys\n\nimport atheris\n\nwith atheris.instrument_imports():\n    from {} import {}\n\n\ndef TestOneInput(data):\n{}\n\ndef main():\n    atheris.Setup(sys.argv, TestOneInput)\n    atheris.Fuzz()\n\n\nif __name__ == \"__main__\":\n    main()\n",
+            module, func.name, call
+        )
+    }
+
+    fn libfuzzer_harness(func: &FuzzableFunction) -> String {
+        let body = match func.input {
+            FuzzInput::Text => format!(
+                "    char *input = (char *)malloc(size + 1);\n    if (input == NULL) {{\n        return 0;\n    }}\n    memcpy(input, data, size);\n    input[size] = '\\0';\n    {}(input);\n    free(input);\n",
+                func.name
+            ),
+            FuzzInput::Bytes => {
+                let buffer_type = func.param_type.split(',').next().unwrap_or("").trim();
+                let cast = Regex::new(r"\s*\w+$").unwrap().replace(buffer_type, "");
+                format!("    {}(({})data, size);\n", func.name, cast.trim())
+            }
+        };
+
+        format!(
+            "#include <stddef.h>\n#include <stdint.h>\n#include <stdlib.h>\n#include <string.h>\n\n{};\n\nint LLVMFuzzerTestOneInput(const uint8_t *data, size_t size) {{\n{}    return 0;\n}}\n",
+            func.signature, body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find_rust_functions() {
+        let source = r#"
+pub fn parse(input: &str) -> Result<Ast, Error> { todo!() }
+pub fn decode<'a>(data: &'a [u8]) -> Vec<u8> { todo!() }
+pub fn add(a: i32, b: i32) -> i32 { a + b }
+fn private(input: &str) {}
+"#;
+        let functions = FuzzGenerator::find_fuzzable_functions("rust", source);
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["parse", "decode"]);
+        assert_eq!(functions[0].input, FuzzInput::Text);
+        assert_eq!(functions[1].input, FuzzInput::Bytes);
+        assert_eq!(functions[1].param_type, "&[u8]");
+    }
+
+    #[test]
+    fn test_find_go_and_python_functions() {
+        let go = "package parser\n\nfunc Parse(data []byte) error { return nil }\nfunc Add(a, b int) int { return a + b }\nfunc TestParse(t *testing.T) {}\n";
+        let go_functions = FuzzGenerator::find_fuzzable_functions("go", go);
+        assert_eq!(go_functions.len(), 1);
+        assert_eq!(go_functions[0].input, FuzzInput::Bytes);
+
+        let python = "def parse(text: str):\n    pass\n\ndef area(w, h):\n    pass\n\ndef load(data):\n    pass\n";
+        let py_functions = FuzzGenerator::find_fuzzable_functions("python", python);
+        let names: Vec<&str> = py_functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["parse", "load"]);
+    }
+
+    #[test]
+    fn test_find_c_functions() {
+        let source = "int parse_header(const uint8_t *buf, size_t len) {\n    return 0;\n}\n\nstatic int helper(const char *s) {\n    return 0;\n}\n\nvoid greet(const char *name) {\n}\n";
+        let functions = FuzzGenerator::find_fuzzable_functions("c", source);
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["parse_header", "greet"]);
+
+        let harness = FuzzGenerator::libfuzzer_harness(&functions[0]);
+        assert!(harness.contains("int parse_header(const uint8_t *buf, size_t len);"));
+        assert!(harness.contains("parse_header((const uint8_t *)data, size);"));
+    }
+
+    #[test]
+    fn test_generate_go_fuzz_test() {
+        let source = "package parser\n\nfunc parse(input string) error { return nil }\n";
+        let files = FuzzGenerator::generate("go", Path::new("pkg/parser/parse.go"), source).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, Path::new("pkg/parser/parse_fuzz_test.go"));
+        assert!(files[0].content.starts_with("package parser"));
+        assert!(files[0].content.contains("func FuzzParse(f *testing.F)"));
+    }
+
+    #[test]
+    fn test_generate_atheris_harness() {
+        let source = "def parse(text: str):\n    pass\n";
+        let files = FuzzGenerator::generate("python", Path::new("app/parser.py"), source).unwrap();
+        assert_eq!(files[0].path, Path::new("app/fuzz_parse.py"));
+        assert!(files[0].content.contains("from parser import parse"));
+        assert!(files[0].content.contains("ConsumeUnicodeNoSurrogates"));
+    }
+
+    #[test]
+    fn test_generate_cargo_fuzz_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"demo-lib\"\nversion = \"0.1.0\"\n").unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        let source_path = dir.path().join("src/parser.rs");
+        let source = "pub fn parse(input: &str) -> bool { true }\n";
+        fs::write(&source_path, source).unwrap();
+
+        let files = FuzzGenerator::generate("rust", &source_path, source).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[0].path.ends_with("fuzz/fuzz_targets/parse.rs"));
+        assert!(files[0].content.contains("use demo_lib::parser::parse;"));
+        assert!(files[0].content.contains("std::str::from_utf8(data)"));
+        assert!(files[1].path.ends_with("fuzz/Cargo.toml"));
+        assert!(files[1].content.contains("path = \"fuzz_targets/parse.rs\""));
+        assert!(files[1].content.contains("name = \"demo-lib-fuzz\""));
+        assert!(files[1].content.contains("\n[dependencies.demo-lib]\npath = \"..\"\n"));
+
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"demo-lib\"\nversion = \"0.1.0\"\n\n[lib]\nname = \"demo\"\n").unwrap();
+        let files = FuzzGenerator::generate("rust", &source_path, source).unwrap();
+        assert!(files[0].content.contains("use demo::parser::parse;"));
+        assert!(files[1].content.contains("\n[dependencies.demo-lib]\npath = \"..\"\n"));
+    }
+
+    #[test]
+    fn test_merge_manifest_adds_new_targets() {
+        let existing = "[package]\nname = \"demo-fuzz\"\n\n[dependencies]\nlibfuzzer-sys = \"0.4\"\n\n[[bin]]\nname = \"parse\"\npath = \"fuzz_targets/parse.rs\"\n";
+        let generated = "[package]\nname = \"demo-fuzz\"\n\n[[bin]]\nname = \"parse\"\npath = \"fuzz_targets/parse.rs\"\ntest = false\n\n[[bin]]\nname = \"decode\"\npath = \"fuzz_targets/decode.rs\"\ntest = false\n";
+
+        let (merged, added) = FuzzGenerator::merge_manifest(existing, generated).unwrap();
+        assert_eq!(added, 1);
+        assert!(merged.starts_with(existing));
+        assert!(merged.ends_with("\n[[bin]]\nname = \"decode\"\npath = \"fuzz_targets/decode.rs\"\ntest = false\n"));
+        assert_eq!(merged.matches("name = \"parse\"").count(), 1);
+
+        assert_eq!(FuzzGenerator::merge_manifest(&merged, generated), None);
+        assert_eq!(FuzzGenerator::merge_manifest("not = [toml", generated), None);
+    }
+
+    #[test]
+    fn test_generate_unsupported_language() {
+        assert!(FuzzGenerator::generate("javascript", Path::new("a.js"), "").is_err());
+    }
+}
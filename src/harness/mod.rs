@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+pub mod api_tests;
+pub mod bench;
+pub mod e2e;
+pub mod execute;
+pub mod fuzz;
+pub mod pact;
+pub mod runner;
+
+pub use api_tests::*;
+pub use bench::*;
+pub use e2e::*;
+pub use execute::*;
+pub use fuzz::*;
+pub use pact::*;
+pub use runner::*;
+
+/// A generated harness file and where it belongs relative to the current directory
+#[derive(Debug, Clone)]
+pub struct HarnessFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Map a source file to the language name used by the harness generators
+pub fn harness_language(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "rs" => Some("rust"),
+        "go" => Some("go"),
+        "py" => Some("python"),
+        "c" => Some("c"),
+        "cc" | "cpp" | "cxx" => Some("cpp"),
+        _ => None,
+    }
+}
+
+/// Nearest directory at or above `start` containing a `Cargo.toml`
+pub(crate) fn find_cargo_root(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+    start.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Library name from a crate root's manifest, `[lib] name` or else the package name, normalized
+/// to its `use` form
+pub(crate) fn rust_crate_name(crate_root: &Path) -> Option<String> {
+    let manifest = std::fs::read_to_string(crate_root.join("Cargo.toml")).ok()?;
+    let value: toml::Value = toml::from_str(&manifest).ok()?;
+    value.get("lib").and_then(|lib| lib.get("name"))
+        .or_else(|| value.get("package")?.get("name"))?
+        .as_str()
+        .map(|name| name.replace('-', "_"))
+}
+
+/// `use` path of the module defined by a Rust source file, e.g. `my_crate::parser::lexer`
+pub(crate) fn rust_module_path(crate_name: &str, crate_root: &Path, source_path: &Path) -> String {
+    let source_path = source_path.canonicalize().unwrap_or_else(|_| source_path.to_path_buf());
+    let mut segments = vec![crate_name.to_string()];
+
+    if let Ok(relative) = source_path.strip_prefix(crate_root.join("src")) {
+        let relative = relative.with_extension("");
+        for component in relative.components() {
+            let name = component.as_os_str().to_string_lossy();
+            if !matches!(name.as_ref(), "lib" | "main" | "mod") {
+                segments.push(name.into_owned());
+            }
+        }
+    }
+
+    segments.join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_harness_language() {
+        assert_eq!(harness_language(Path::new("src/lib.rs")), Some("rust"));
+        assert_eq!(harness_language(Path::new("parser.cc")), Some("cpp"));
+        assert_eq!(harness_language(Path::new("app.py")), Some("python"));
+        assert_eq!(harness_language(Path::new("index.js")), None);
+    }
+
+    #[test]
+    fn test_rust_module_path() {
+        let root = Path::new("/work/demo");
+        assert_eq!(rust_module_path("demo", root, Path::new("/work/demo/src/lib.rs")), "demo");
+        assert_eq!(rust_module_path("demo", root, Path::new("/work/demo/src/parser/mod.rs")), "demo::parser");
+        assert_eq!(rust_module_path("demo", root, Path::new("/work/demo/src/parser/lexer.rs")), "demo::parser::lexer");
+    }
+}
@@ -0,0 +1,279 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::path::Path;
+
+use super::{find_cargo_root, HarnessFile};
+use crate::core::{ApiIntegrationPattern, HttpMethod};
+
+/// One request/response expectation of the consumer's contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct PactInteraction {
+    pub description: String,
+    pub method: String,
+    /// Request path with placeholders filled in and any host removed
+    pub path: String,
+    pub has_body: bool,
+    pub authenticated: bool,
+}
+
+impl PactInteraction {
+    /// Identifier for the interaction's test built from its path
+    pub fn test_name(&self) -> String {
+        let name = Regex::new(r"\W+").unwrap().replace_all(&self.path, "_").trim_matches('_').to_lowercase();
+        if name.is_empty() { "root".to_string() } else { name }
+    }
+}
+
+/// Generates Pact consumer tests (pact-js, pact-python, pact_consumer) describing the requests
+/// the detected API calls make and the responses they expect
+pub struct PactGenerator;
+
+impl PactGenerator {
+    pub fn toolchain(language: &str) -> Option<&'static str> {
+        match language {
+            "javascript" => Some("pact-js"),
+            "python" => Some("pact-python"),
+            "rust" => Some("pact_consumer"),
+            _ => None,
+        }
+    }
+
+    /// Provider name for a set of calls: the host of the first absolute endpoint
+    pub fn provider_name(apis: &[ApiIntegrationPattern]) -> Option<String> {
+        apis.iter().find_map(|api| {
+            let (_, rest) = api.endpoint.split_once("://")?;
+            let host = rest.split(['/', ':', '?']).next()?;
+            (!host.is_empty() && !host.contains(['{', '$'])).then(|| host.to_string())
+        })
+    }
+
+    /// One interaction per distinct method and path
+    pub fn interactions(apis: &[ApiIntegrationPattern]) -> Vec<PactInteraction> {
+        let mut interactions: Vec<PactInteraction> = Vec::new();
+        for api in apis {
+            let method = api.method.to_string();
+            let path = contract_path(&api.endpoint);
+            if interactions.iter().any(|i| i.method == method && i.path == path) {
+                continue;
+            }
+            interactions.push(PactInteraction {
+                description: format!("a {} request to {}", method, path),
+                has_body: matches!(api.method, HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch),
+                authenticated: api.authentication_required,
+                method,
+                path,
+            });
+        }
+        interactions
+    }
+
+    /// Build the consumer test for the API calls detected in `source_path`
+    pub fn generate(language: &str, source_path: &Path, consumer: &str, provider: &str, apis: &[ApiIntegrationPattern]) -> Result<Vec<HarnessFile>> {
+        Self::toolchain(language)
+            .ok_or_else(|| anyhow!("Contract test generation not supported for language: {}", language))?;
+
+        let interactions = Self::interactions(apis);
+        if interactions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let source_dir = source_path.parent().unwrap_or_else(|| Path::new(""));
+        let stem = source_path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("module");
+
+        let file = match language {
+            "javascript" => {
+                let extension = source_path.extension().and_then(|s| s.to_str()).unwrap_or("js");
+                HarnessFile {
+                    path: source_dir.join(format!("{}.pact.test.{}", stem, extension)),
+                    content: Self::pact_js(consumer, provider, &interactions),
+                }
+            }
+            "python" => HarnessFile {
+                path: source_dir.join(format!("test_{}_pact.py", stem)),
+                content: Self::pact_python(consumer, provider, &interactions),
+            },
+            _ => {
+                let crate_root = find_cargo_root(source_path)
+                    .ok_or_else(|| anyhow!("No Cargo.toml found above {}", source_path.display()))?;
+                HarnessFile {
+                    path: crate_root.join("tests").join(format!("{}_pact.rs", stem)),
+                    content: Self::pact_rust(consumer, provider, &interactions),
+                }
+            }
+        };
+
+        Ok(vec![file])
+    }
+
+    fn pact_js(consumer: &str, provider: &str, interactions: &[PactInteraction]) -> String {
+        let mut code = format!(
+            "const path = require('path');\nconst {{ PactV3, MatchersV3 }} = require('@pact-foundation/pact');\n\nconst provider = new PactV3({{\n  consumer: '{}',\n  provider: '{}',\n  dir: path.resolve(process.cwd(), 'pacts'),\n}});\n\ndescribe('{} contract', () => {{\n",
+            consumer, provider, provider
+        );
+        for (i, interaction) in interactions.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            let mut request = format!("method: '{}', path: '{}'", interaction.method, interaction.path);
+            let mut fetch_options = format!("method: '{}'", interaction.method);
+            let mut headers = Vec::new();
+            if interaction.authenticated {
+                request.push_str(", headers: { Authorization: MatchersV3.like('Bearer token') }");
+                headers.push("Authorization: 'Bearer token'");
+            }
+            if interaction.has_body {
+                request.push_str(", body: MatchersV3.like({})");
+                headers.push("'Content-Type': 'application/json'");
+                fetch_options.push_str(", body: JSON.stringify({})");
+            }
+            if !headers.is_empty() {
+                fetch_options.push_str(&format!(", headers: {{ {} }}", headers.join(", ")));
+            }
+            code.push_str(&format!(
+                "  it('{desc}', () => {{\n    provider\n      .uponReceiving('{desc}')\n      .withRequest({{ {request} }})\n      .willRespondWith({{ status: 200, headers: {{ 'Content-Type': 'application/json' }}, body: MatchersV3.like({{}}) }});\n\n    return provider.executeTest(async (mockServer) => {{\n      // Point the code's API client at mockServer.url to record the requests it really makes\n      const response = await fetch(`${{mockServer.url}}{path}`, {{ {options} }});\n      expect(response.status).toBe(200);\n    }});\n  }});\n",
+                desc = interaction.description,
+                request = request,
+                path = interaction.path,
+                options = fetch_options,
+            ));
+        }
+        code.push_str("});\n");
+        code
+    }
+
+    fn pact_python(consumer: &str, provider: &str, interactions: &[PactInteraction]) -> String {
+        let mut code = format!(
+            "import pytest\nimport requests\nfrom pact import Consumer, Like, Provider\n\n\n@pytest.fixture(scope=\"module\")\ndef pact():\n    pact = Consumer(\"{}\").has_pact_with(Provider(\"{}\"), pact_dir=\"pacts\")\n    pact.start_service()\n    yield pact\n    pact.stop_service()\n",
+            consumer, provider
+        );
+        for interaction in interactions {
+            let name = interaction.test_name();
+            let mut request_args = String::new();
+            let mut call_args = String::new();
+            if interaction.authenticated {
+                request_args.push_str(", headers={\"Authorization\": Like(\"Bearer token\")}");
+                call_args.push_str(", headers={\"Authorization\": \"Bearer token\"}");
+            }
+            if interaction.has_body {
+                request_args.push_str(", body=Like({})");
+                call_args.push_str(", json={}");
+            }
+            code.push_str(&format!(
+                "\n\ndef test_{method}_{name}(pact):\n    (pact\n     .upon_receiving(\"{desc}\")\n     .with_request(\"{upper}\", \"{path}\"{request_args})\n     .will_respond_with(200, headers={{\"Content-Type\": \"application/json\"}}, body=Like({{}})))\n\n    with pact:\n        # Point the code's API client at pact.uri to record the requests it really makes\n        response = requests.{method}(f\"{{pact.uri}}{path}\"{call_args}, timeout=10)\n    assert response.status_code == 200\n",
+                method = interaction.method.to_lowercase(),
+                upper = interaction.method,
+                name = name,
+                desc = interaction.description,
+                path = interaction.path,
+                request_args = request_args,
+                call_args = call_args,
+            ));
+        }
+        code
+    }
+
+    fn pact_rust(consumer: &str, provider: &str, interactions: &[PactInteraction]) -> String {
+        let mut code = "use pact_consumer::prelude::*;\n".to_string();
+        for interaction in interactions {
+            let name = interaction.test_name();
+            let mut request = format!("i.request.method(\"{}\").path(\"{}\")", interaction.method, interaction.path);
+            let mut call = vec![format!(".request(reqwest::Method::{}, pact.path(\"{}\"))", interaction.method, interaction.path)];
+            if interaction.authenticated {
+                request.push_str(".header(\"Authorization\", term!(\"^Bearer .+$\", \"Bearer token\"))");
+                call.push(".bearer_auth(\"token\")".to_string());
+            }
+            if interaction.has_body {
+                request.push_str(".json_body(json_pattern!(like!({})))");
+                call.push(".json(&serde_json::json!({}))".to_string());
+            }
+            code.push_str(&format!(
+                "\n#[tokio::test]\nasync fn {method}_{name}() {{\n    let pact = PactBuilder::new(\"{consumer}\", \"{provider}\")\n        .interaction(\"{desc}\", \"\", |mut i| {{\n            {request};\n            i.response.ok().content_type(\"application/json\").json_body(json_pattern!(like!({{}})));\n            i.clone()\n        }})\n        .start_mock_server(None, None);\n\n    // Point the code's API client at pact.url() to record the requests it really makes\n    let response = reqwest::Client::new()\n        {call}\n        .send()\n        .await\n        .expect(\"request to the Pact mock server failed\");\n    assert_eq!(response.status(), 200);\n}}\n",
+                method = interaction.method.to_lowercase(),
+                name = name,
+                consumer = consumer,
+                provider = provider,
+                desc = interaction.description,
+                request = request,
+                call = call.join("\n        "),
+            ));
+        }
+        code
+    }
+}
+
+/// Request path of an endpoint as written in source: host and query dropped, and `{id}`,
+/// `${id}` or `:id` placeholders filled in
+fn contract_path(endpoint: &str) -> String {
+    let endpoint = endpoint.replace("${", "{");
+    let without_host = match endpoint.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+        // A leading placeholder is the base URL
+        None => endpoint.strip_prefix('{').and_then(|rest| rest.split_once('}')).map_or(endpoint.as_str(), |(_, path)| path),
+    };
+    let filled = Regex::new(r"\{[^}]*\}|:\w+").unwrap().replace_all(without_host, "1");
+    match filled.split(['?', '#']).next().unwrap_or_default() {
+        "" => "/".to_string(),
+        p if p.starts_with('/') => p.to_string(),
+        p => format!("/{}", p),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api(method: HttpMethod, endpoint: &str, authentication_required: bool) -> ApiIntegrationPattern {
+        ApiIntegrationPattern {
+            endpoint: endpoint.to_string(),
+            method,
+            request_body: None,
+            response_type: None,
+            authentication_required,
+        }
+    }
+
+    #[test]
+    fn test_interactions_and_provider_name() {
+        let apis = [
+            api(HttpMethod::Get, "https://api.example.com/users/{id}?full=1", false),
+            api(HttpMethod::Get, "${base}/users/${userId}", false),
+            api(HttpMethod::Post, "/orders/:orderId/items", true),
+        ];
+        let interactions = PactGenerator::interactions(&apis);
+
+        assert_eq!(interactions.len(), 2);
+        assert_eq!(interactions[0].description, "a GET request to /users/1");
+        assert_eq!(interactions[1].path, "/orders/1/items");
+        assert!(interactions[1].has_body && interactions[1].authenticated);
+        assert_eq!(PactGenerator::provider_name(&apis).as_deref(), Some("api.example.com"));
+    }
+
+    #[test]
+    fn test_generate_pact_python() {
+        let files = PactGenerator::generate("python", Path::new("app/client.py"), "web", "users-api", &[api(HttpMethod::Get, "{base_url}/users/{id}", true)]).unwrap();
+        assert_eq!(files[0].path, Path::new("app/test_client_pact.py"));
+        assert!(files[0].content.contains("    pact = Consumer(\"web\").has_pact_with(Provider(\"users-api\"), pact_dir=\"pacts\")\n"));
+        assert!(files[0].content.contains("def test_get_users_1(pact):\n"));
+        assert!(files[0].content.contains("     .with_request(\"GET\", \"/users/1\", headers={\"Authorization\": Like(\"Bearer token\")})\n"));
+        assert!(files[0].content.contains("        response = requests.get(f\"{pact.uri}/users/1\", headers={\"Authorization\": \"Bearer token\"}, timeout=10)\n"));
+    }
+
+    #[test]
+    fn test_generate_pact_js_and_rust() {
+        let apis = [api(HttpMethod::Post, "/orders", false)];
+        let files = PactGenerator::generate("javascript", Path::new("src/orders.js"), "web", "orders-api", &apis).unwrap();
+        assert_eq!(files[0].path, Path::new("src/orders.pact.test.js"));
+        assert!(files[0].content.contains("      .withRequest({ method: 'POST', path: '/orders', body: MatchersV3.like({}) })\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"shop\"\n").unwrap();
+        let files = PactGenerator::generate("rust", &dir.path().join("src/orders.rs"), "web", "orders-api", &apis).unwrap();
+        assert!(files[0].path.ends_with("tests/orders_pact.rs"));
+        assert!(files[0].content.contains("async fn post_orders() {\n    let pact = PactBuilder::new(\"web\", \"orders-api\")\n"));
+        assert!(files[0].content.contains("        .request(reqwest::Method::POST, pact.path(\"/orders\"))\n        .json(&serde_json::json!({}))\n"));
+
+        assert!(PactGenerator::generate("go", Path::new("main.go"), "web", "api", &apis).is_err());
+    }
+}
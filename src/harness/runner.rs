@@ -0,0 +1,313 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use super::{find_cargo_root, rust_module_path};
+
+/// The test command a language's generated tests run under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TestRunner {
+    Cargo,
+    Pytest,
+    Npm,
+    Go,
+    Gradle,
+}
+
+/// Tests a runner reported as passed and failed
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// The outcome of running one runner over its test files
+#[derive(Debug, Clone)]
+pub struct TestRun {
+    pub runner: TestRunner,
+    pub command: Vec<String>,
+    pub counts: TestCounts,
+    /// Whether the command exited successfully
+    pub success: bool,
+    /// Combined stdout and stderr
+    pub output: String,
+}
+
+impl TestRunner {
+    /// Runner for a language's test files; `None` for languages without one
+    pub fn for_language(language: &str) -> Option<Self> {
+        match language {
+            "rust" => Some(Self::Cargo),
+            "python" => Some(Self::Pytest),
+            "javascript" | "typescript" => Some(Self::Npm),
+            "go" => Some(Self::Go),
+            "java" | "kotlin" => Some(Self::Gradle),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo test",
+            Self::Pytest => "pytest",
+            Self::Npm => "npm test",
+            Self::Go => "go test",
+            Self::Gradle => "gradle test",
+        }
+    }
+
+    /// Command line running just `test_files`, given relative to `project_root`
+    pub fn command(&self, project_root: &Path, test_files: &[PathBuf]) -> Vec<String> {
+        let stems = || test_files.iter().filter_map(|file| file.file_stem()).map(|stem| stem.to_string_lossy().into_owned());
+        match self {
+            // Integration tests by their target's name, inline test modules by the path of the
+            // module, under the target holding them
+            Self::Cargo => {
+                let (integration, inline): (Vec<&PathBuf>, Vec<&PathBuf>) = test_files.iter().partition(|file| is_cargo_test_target(file));
+                let mut command: Vec<String> = ["cargo", "test"].into_iter().map(String::from)
+                    .chain(integration.iter().filter_map(|file| file.file_stem()).flat_map(|stem| ["--test".to_string(), stem.to_string_lossy().into_owned()]))
+                    .collect();
+                let mut filters = Vec::new();
+                for file in inline {
+                    let (target, module) = cargo_inline_tests(project_root, file);
+                    if !command.windows(target.len()).any(|window| window == target.as_slice()) {
+                        command.extend(target);
+                    }
+                    filters.push(module);
+                }
+                // Filters would leave out the integration tests too
+                if integration.is_empty() {
+                    match filters.len() {
+                        0 => {}
+                        1 => command.append(&mut filters),
+                        _ => command.extend(std::iter::once("--".to_string()).chain(filters)),
+                    }
+                }
+                command
+            }
+            Self::Pytest => ["python3", "-m", "pytest", "-q"].into_iter().map(String::from)
+                .chain(test_files.iter().map(|file| file.display().to_string()))
+                .collect(),
+            // Arguments after `--` reach the test script, which jest and vitest take as path filters
+            Self::Npm => ["npm", "test", "--"].into_iter().map(String::from)
+                .chain(test_files.iter().map(|file| file.display().to_string()))
+                .collect(),
+            // go test runs packages, so each test file's directory
+            Self::Go => {
+                let mut packages: Vec<String> = test_files.iter()
+                    .map(|file| match file.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                        Some(directory) => format!("./{}", directory.display()),
+                        None => ".".to_string(),
+                    })
+                    .collect();
+                packages.sort();
+                packages.dedup();
+                ["go", "test", "-v"].into_iter().map(String::from).chain(packages).collect()
+            }
+            Self::Gradle => {
+                let gradle = if project_root.join("gradlew").is_file() { "./gradlew" } else { "gradle" };
+                [gradle, "test"].into_iter().map(String::from)
+                    .chain(stems().flat_map(|stem| ["--tests".to_string(), stem]))
+                    .collect()
+            }
+        }
+    }
+
+    /// Command line running just the test `name` in `test_file`, given relative to `project_root`.
+    /// Runners that only filter by substring may run tests whose names contain it too
+    pub fn test_command(&self, project_root: &Path, test_file: &Path, name: &str) -> Vec<String> {
+        let mut command = self.command(project_root, &[test_file.to_path_buf()]);
+        match self {
+            Self::Cargo if !is_cargo_test_target(test_file) => {
+                if let Some(module) = command.last_mut() {
+                    *module = format!("{}::{}", module, name);
+                }
+            }
+            Self::Cargo => command.extend(["--".to_string(), name.to_string()]),
+            Self::Pytest => command.extend(["-k".to_string(), name.to_string()]),
+            Self::Npm => command.extend(["-t".to_string(), name.to_string()]),
+            Self::Go => {
+                let packages = command.split_off(3);
+                command.extend(["-run".to_string(), format!("^{}$", name)]);
+                command.extend(packages);
+            }
+            Self::Gradle => {
+                let class = command.pop().unwrap_or_default();
+                command.push(format!("{}.{}", class, name));
+            }
+        }
+        command
+    }
+
+    /// Pass and fail counts from the runner's output
+    pub fn parse_counts(&self, output: &str) -> TestCounts {
+        let sum = |pattern: &str| -> usize {
+            Regex::new(pattern).unwrap()
+                .captures_iter(output)
+                .filter_map(|captures| captures[1].parse::<usize>().ok())
+                .sum()
+        };
+        match self {
+            Self::Cargo => TestCounts {
+                passed: sum(r"test result: \w+\. (\d+) passed"),
+                failed: sum(r"test result: \w+\. \d+ passed; (\d+) failed"),
+            },
+            // The closing summary line, e.g. `2 failed, 5 passed in 0.12s`
+            Self::Pytest => {
+                let summary = output.lines().rev().find(|line| line.contains(" passed") || line.contains(" failed") || line.contains(" error")).unwrap_or_default();
+                let count = |pattern: &str| Regex::new(pattern).unwrap()
+                    .captures(summary)
+                    .and_then(|captures| captures[1].parse::<usize>().ok())
+                    .unwrap_or(0);
+                TestCounts {
+                    passed: count(r"(\d+) passed"),
+                    failed: count(r"(\d+) failed") + count(r"(\d+) errors?"),
+                }
+            }
+            // jest and vitest: `Tests:       1 failed, 3 passed, 4 total`
+            Self::Npm => {
+                let summary = output.lines().find(|line| line.trim_start().starts_with("Tests:") || line.trim_start().starts_with("Tests ")).unwrap_or_default();
+                let count = |pattern: &str| Regex::new(pattern).unwrap()
+                    .captures(summary)
+                    .and_then(|captures| captures[1].parse::<usize>().ok())
+                    .unwrap_or(0);
+                TestCounts { passed: count(r"(\d+) passed"), failed: count(r"(\d+) failed") }
+            }
+            Self::Go => TestCounts {
+                passed: output.lines().filter(|line| line.trim_start().starts_with("--- PASS:")).count(),
+                failed: output.lines().filter(|line| line.trim_start().starts_with("--- FAIL:")).count(),
+            },
+            // Gradle only prints counts when something fails: `5 tests completed, 2 failed`
+            Self::Gradle => {
+                let captures = Regex::new(r"(\d+) tests? completed, (\d+) failed").unwrap().captures(output);
+                match captures {
+                    Some(captures) => {
+                        let completed: usize = captures[1].parse().unwrap_or(0);
+                        let failed: usize = captures[2].parse().unwrap_or(0);
+                        TestCounts { passed: completed.saturating_sub(failed), failed }
+                    }
+                    None => TestCounts::default(),
+                }
+            }
+        }
+    }
+
+    /// Run `test_files` from `project_root` and collect the counts
+    pub async fn run(&self, project_root: &Path, test_files: &[PathBuf]) -> Result<TestRun> {
+        let command = self.command(project_root, test_files);
+        let output = tokio::process::Command::new(&command[0])
+            .args(&command[1..])
+            .current_dir(project_root)
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|e| anyhow!("Could not run {}: {}", command[0], e))?;
+
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(TestRun {
+            runner: *self,
+            counts: self.parse_counts(&text),
+            success: output.status.success(),
+            output: text,
+            command,
+        })
+    }
+}
+
+/// Whether a Rust test file is an integration test target, in a crate's `tests/`, rather than a
+/// source file holding its own `mod tests`
+fn is_cargo_test_target(test_file: &Path) -> bool {
+    test_file.parent().and_then(Path::file_name).is_some_and(|dir| dir == "tests")
+}
+
+/// The cargo target holding the inline tests of a Rust source file, `--lib` or its binary,
+/// and the path of its test module in there, e.g. `parser::tests`
+fn cargo_inline_tests(project_root: &Path, source_file: &Path) -> (Vec<String>, String) {
+    let path = project_root.join(source_file);
+    let crate_root = find_cargo_root(path.parent().unwrap_or(project_root)).unwrap_or_else(|| project_root.to_path_buf());
+    let module = rust_module_path("crate", &crate_root, &path);
+    let module = module.strip_prefix("crate").unwrap_or(&module).trim_start_matches("::");
+    let (target, module) = match module.strip_prefix("bin::") {
+        Some(binary) => {
+            let (name, module) = binary.split_once("::").unwrap_or((binary, ""));
+            (vec!["--bin".to_string(), name.to_string()], module)
+        }
+        None if path.file_name().is_some_and(|name| name == "main.rs") => (vec!["--bins".to_string()], module),
+        None => (vec!["--lib".to_string()], module),
+    };
+    let tests = if module.is_empty() { "tests".to_string() } else { format!("{}::tests", module) };
+    (target, tests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runner_commands() {
+        let root = Path::new("/work/demo");
+        assert_eq!(TestRunner::for_language("typescript"), Some(TestRunner::Npm));
+        assert_eq!(TestRunner::for_language("ruby"), None);
+
+        assert_eq!(
+            TestRunner::Cargo.command(root, &[PathBuf::from("tests/test_parser.rs")]),
+            ["cargo", "test", "--test", "test_parser"]
+        );
+        assert_eq!(
+            TestRunner::Cargo.command(root, &[PathBuf::from("src/parser/lexer.rs")]),
+            ["cargo", "test", "--lib", "parser::lexer::tests"]
+        );
+        assert_eq!(
+            TestRunner::Cargo.command(root, &[PathBuf::from("src/lib.rs"), PathBuf::from("src/parser.rs"), PathBuf::from("src/bin/cli.rs")]),
+            ["cargo", "test", "--lib", "--bin", "cli", "--", "tests", "parser::tests", "tests"]
+        );
+        assert_eq!(
+            TestRunner::Pytest.command(root, &[PathBuf::from("tests/test_calc.py")]),
+            ["python3", "-m", "pytest", "-q", "tests/test_calc.py"]
+        );
+        assert_eq!(
+            TestRunner::Go.command(root, &[PathBuf::from("calc/add_test.go"), PathBuf::from("calc/sub_test.go"), PathBuf::from("main_test.go")]),
+            ["go", "test", "-v", ".", "./calc"]
+        );
+        assert_eq!(
+            TestRunner::Gradle.command(root, &[PathBuf::from("src/test/CalcTest.java")]),
+            ["gradle", "test", "--tests", "CalcTest"]
+        );
+
+        assert_eq!(
+            TestRunner::Pytest.test_command(root, Path::new("tests/test_calc.py"), "test_add"),
+            ["python3", "-m", "pytest", "-q", "tests/test_calc.py", "-k", "test_add"]
+        );
+        assert_eq!(
+            TestRunner::Cargo.test_command(root, Path::new("src/parser.rs"), "test_parse"),
+            ["cargo", "test", "--lib", "parser::tests::test_parse"]
+        );
+        assert_eq!(
+            TestRunner::Go.test_command(root, Path::new("calc/add_test.go"), "TestAdd"),
+            ["go", "test", "-v", "-run", "^TestAdd$", "./calc"]
+        );
+        assert_eq!(
+            TestRunner::Gradle.test_command(root, Path::new("src/test/CalcTest.java"), "testAdd"),
+            ["gradle", "test", "--tests", "CalcTest.testAdd"]
+        );
+    }
+
+    #[test]
+    fn test_parse_counts() {
+        let cargo = "test result: ok. 3 passed; 0 failed; 0 ignored\ntest result: FAILED. 1 passed; 2 failed; 0 ignored\n";
+        assert_eq!(TestRunner::Cargo.parse_counts(cargo), TestCounts { passed: 4, failed: 2 });
+
+        let pytest = "..F\nFAILED tests/test_calc.py::test_add\n1 failed, 2 passed, 1 error in 0.03s\n";
+        assert_eq!(TestRunner::Pytest.parse_counts(pytest), TestCounts { passed: 2, failed: 2 });
+
+        let jest = "Test Suites: 1 failed, 1 total\nTests:       1 failed, 3 passed, 4 total\n";
+        assert_eq!(TestRunner::Npm.parse_counts(jest), TestCounts { passed: 3, failed: 1 });
+
+        let go = "=== RUN   TestAdd\n--- PASS: TestAdd (0.00s)\n=== RUN   TestSub\n--- FAIL: TestSub (0.00s)\n";
+        assert_eq!(TestRunner::Go.parse_counts(go), TestCounts { passed: 1, failed: 1 });
+
+        assert_eq!(TestRunner::Gradle.parse_counts("5 tests completed, 2 failed"), TestCounts { passed: 3, failed: 2 });
+    }
+}
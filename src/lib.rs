@@ -2,8 +2,12 @@ pub mod core;
 pub mod adapters;
 pub mod ascii_art;
 pub mod templates;
+pub mod harness;
+pub mod server;
 
 pub use core::*;
 pub use adapters::*;
 pub use ascii_art::*;
-pub use templates::*;
\ No newline at end of file
+pub use templates::*;
+pub use harness::*;
+pub use server::*;
\ No newline at end of file
@@ -0,0 +1,254 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::core::{read_existing_tests, test_locations, test_names, write_test_file, TestOrchestrator, TestablePattern};
+
+/// Notification the daemon sends while it works on a request, naming the request's id
+pub const PROGRESS_NOTIFICATION: &str = "$/progress";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// A JSON-RPC error response's code and message
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(INTERNAL_ERROR, format!("{:#}", error))
+    }
+}
+
+impl From<std::io::Error> for RpcError {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(INTERNAL_ERROR, error.to_string())
+    }
+}
+
+/// Parameters of `analyze` and `generate`
+#[derive(Debug, Deserialize)]
+struct FileParams {
+    path: PathBuf,
+    /// Unsaved editor contents, read from `path` when absent
+    #[serde(default)]
+    source: Option<String>,
+    /// Only generate tests for these functions, or the methods of these classes
+    #[serde(default)]
+    symbols: Vec<String>,
+}
+
+impl FileParams {
+    fn parse(params: Value) -> Result<Self, RpcError> {
+        serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, format!("Expected {{\"path\", \"source\"?, \"symbols\"?}}: {}", e)))
+    }
+
+    fn source(&self) -> Result<String, RpcError> {
+        match &self.source {
+            Some(source) => Ok(source.clone()),
+            None => Ok(fs::read_to_string(&self.path)?),
+        }
+    }
+}
+
+/// Long-running analyzer for IDE plugins that can't host a language client: JSON-RPC 2.0 over
+/// stdin and stdout, one message per line
+pub struct Daemon {
+    orchestrator: TestOrchestrator,
+    /// Project root test file paths are resolved against
+    root: PathBuf,
+}
+
+impl Daemon {
+    pub fn new(orchestrator: TestOrchestrator, root: PathBuf) -> Self {
+        Self { orchestrator, root }
+    }
+
+    /// Answer one line of input, passing each progress notification for it to `notify`.
+    /// `None` for notifications, which get no response
+    pub async fn handle(&self, line: &str, notify: &mut impl FnMut(Value)) -> Option<Value> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(e) => return Some(error_response(Value::Null, &RpcError::new(PARSE_ERROR, e.to_string()))),
+        };
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return Some(error_response(id.unwrap_or(Value::Null), &RpcError::new(INVALID_REQUEST, "Expected a request with a method")));
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let mut progress = |fraction: f64, text: String| {
+            if let Some(id) = &id {
+                notify(json!({ "jsonrpc": "2.0", "method": PROGRESS_NOTIFICATION, "params": { "id": id, "fraction": fraction, "message": text } }));
+            }
+        };
+        let result = match method {
+            "analyze" => self.analyze(params, &mut progress).await,
+            "generate" => self.generate(params, &mut progress).await,
+            "shutdown" => Ok(Value::Null),
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+        };
+
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, &error),
+        })
+    }
+
+    /// The file's patterns and the functions its test file misses, as `analyze --format json`
+    /// prints them
+    async fn analyze(&self, params: Value, progress: &mut impl FnMut(f64, String)) -> Result<Value, RpcError> {
+        let params = FileParams::parse(params)?;
+        let file = params.path.to_string_lossy().to_string();
+        progress(0.0, format!("Analyzing patterns in {}", file));
+        let mut report = self.orchestrator.analysis_report(&file, &params.source()?).await?;
+        let test_file = self.orchestrator.test_file_path(&self.root, &params.path, &report.language);
+        progress(0.5, format!("Checking {}", test_file.display()));
+        report.check_tests(test_file.clone(), &read_existing_tests(&test_file, &params.path));
+        progress(1.0, format!("{} patterns found", report.patterns.len()));
+        Ok(serde_json::to_value(report).map_err(anyhow::Error::from)?)
+    }
+
+    /// Generate the file's tests and merge them into its test file, as `generate --symbol`
+    /// does when symbols are given
+    async fn generate(&self, params: Value, progress: &mut impl FnMut(f64, String)) -> Result<Value, RpcError> {
+        let params = FileParams::parse(params)?;
+        let file = params.path.to_string_lossy().to_string();
+        let source = params.source()?;
+        progress(0.0, format!("Analyzing patterns in {}", file));
+        let (mut suite, patterns) = if params.symbols.is_empty() {
+            self.orchestrator.generate_tests_with_patterns(&file, &source).await?
+        } else {
+            let patterns: Vec<TestablePattern> = self.orchestrator.analyze_file(&file, &source).await?.into_iter()
+                .filter(|pattern| params.symbols.iter().any(|symbol| {
+                    symbol == pattern.subject_name() || pattern.context.class_name.as_ref() == Some(symbol)
+                }))
+                .collect();
+            let subjects: Vec<String> = patterns.iter().map(|pattern| pattern.subject_name().to_string()).collect();
+            (self.orchestrator.generate_tests_for_subjects(&file, &source, &subjects).await?, patterns)
+        };
+        let test_file = self.orchestrator.test_file_path(&self.root, &params.path, &suite.language);
+        if suite.test_cases.is_empty() {
+            progress(1.0, "No testable patterns found".to_string());
+            return Ok(json!({ "source_file": file, "test_file": test_file, "status": "no_patterns", "added": 0, "new_tests": [] }));
+        }
+
+        progress(0.5, format!("Writing {} tests to {}", suite.test_cases.len(), test_file.display()));
+        let generated = self.orchestrator.render_test_file(&mut suite, &patterns, &file, &source)?;
+        let existing_tests = fs::read_to_string(&test_file).map(|tests| test_names(&tests)).unwrap_or_default();
+        let update = write_test_file(&test_file, &params.path, &generated)?;
+        let new_tests: Vec<Value> = test_locations(&fs::read_to_string(&test_file)?).into_iter()
+            .filter(|(name, _)| !existing_tests.contains(name))
+            .map(|(name, line)| json!({ "name": name, "line": line }))
+            .collect();
+        progress(1.0, format!("Added {} tests", update.tests_written()));
+        Ok(json!({ "source_file": file, "test_file": test_file, "status": update.status(), "added": update.tests_written(), "new_tests": new_tests }))
+    }
+}
+
+fn error_response(id: Value, error: &RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": error.code, "message": error.message } })
+}
+
+fn send(message: &Value) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", message);
+    let _ = stdout.flush();
+}
+
+/// Answer requests on stdin until it closes or a `shutdown` request comes in
+pub async fn run_daemon(daemon: Daemon) -> Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = daemon.handle(&line, &mut |notification| send(&notification)).await {
+            send(&response);
+        }
+        let shutdown = serde_json::from_str::<Value>(&line).is_ok_and(|message| message["method"] == "shutdown");
+        if shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PythonAdapter;
+
+    fn daemon(root: &std::path::Path) -> Daemon {
+        let mut orchestrator = TestOrchestrator::new();
+        orchestrator.register_adapter("python".to_string(), Box::new(PythonAdapter::new()));
+        Daemon::new(orchestrator, root.to_path_buf())
+    }
+
+    #[tokio::test]
+    async fn test_daemon_analyzes_and_generates_with_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("calc.py");
+        fs::write(&source, "def add(a, b):\n    return a + b\n").unwrap();
+        let daemon = daemon(dir.path());
+        let mut notifications = Vec::new();
+
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "analyze", "params": { "path": source } });
+        let response = daemon.handle(&request.to_string(), &mut |n| notifications.push(n)).await.unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["language"], "python");
+        assert_eq!(response["result"]["untested"], json!(["add"]));
+        assert!(notifications.iter().all(|n| n["method"] == PROGRESS_NOTIFICATION && n["params"]["id"] == 1));
+        assert_eq!(notifications.last().unwrap()["params"]["fraction"], 1.0);
+
+        let request = json!({ "jsonrpc": "2.0", "id": 2, "method": "generate", "params": { "path": source } });
+        let response = daemon.handle(&request.to_string(), &mut |_| {}).await.unwrap();
+        let result = &response["result"];
+        assert_eq!(result["status"], "generated");
+        assert!(result["added"].as_u64().unwrap() > 0);
+        assert!(std::path::Path::new(result["test_file"].as_str().unwrap()).exists());
+
+        let response = daemon.handle(&request.to_string(), &mut |_| {}).await.unwrap();
+        assert_eq!(response["result"]["status"], "exists");
+        assert_eq!(response["result"]["new_tests"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_daemon_errors_and_notifications() {
+        let dir = tempfile::tempdir().unwrap();
+        let daemon = daemon(dir.path());
+        let mut notifications = Vec::new();
+
+        let response = daemon.handle("{not json", &mut |n| notifications.push(n)).await.unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+        assert_eq!(response["id"], Value::Null);
+
+        let response = daemon.handle(r#"{"jsonrpc": "2.0", "id": 3, "method": "refactor"}"#, &mut |n| notifications.push(n)).await.unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = daemon.handle(r#"{"jsonrpc": "2.0", "id": 4, "method": "analyze", "params": {}}"#, &mut |n| notifications.push(n)).await.unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let missing = dir.path().join("missing.py");
+        let request = json!({ "jsonrpc": "2.0", "method": "analyze", "params": { "path": missing } });
+        assert_eq!(daemon.handle(&request.to_string(), &mut |n| notifications.push(n)).await, None);
+        assert!(notifications.is_empty());
+    }
+}
@@ -0,0 +1,717 @@
+use anyhow::Result;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tower_lsp::jsonrpc;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+use crate::core::{inline_tests_start, merge_test_file, read_existing_tests, test_names, untested_functions, write_tests, LanguageLoader, OverwritePolicy, PatternType, ProjectConfig, TestFileUpdate, TestOrchestrator, TestablePattern, IGNORE_FILE};
+
+pub mod daemon;
+pub mod settings;
+
+pub use daemon::*;
+pub use settings::*;
+
+/// Command writing the tests for the file whose URI it's given
+pub const GENERATE_TESTS_COMMAND: &str = "uft.generateTests";
+
+/// Command listing the untested functions under the files or directories whose URIs it's
+/// given, the whole workspace without any
+pub const UNTESTED_FUNCTIONS_COMMAND: &str = "uft.untestedFunctions";
+
+/// Name diagnostics and messages from the server carry as their source
+const DIAGNOSTIC_SOURCE: &str = "uft";
+
+/// How long language config changes settle before they're reloaded
+const CONFIG_DEBOUNCE: Duration = Duration::from_millis(300);
+
+type ConfigWatcher = notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>;
+
+/// Language server reporting untested functions and writing tests for them
+pub struct Backend {
+    client: Client,
+    orchestrator: Arc<RwLock<TestOrchestrator>>,
+    settings: RwLock<ServerSettings>,
+    /// The project config as read from disk, before the settings override parts of it
+    project_config: RwLock<ProjectConfig>,
+    root: RwLock<PathBuf>,
+    documents: RwLock<HashMap<Url, String>>,
+    pulls_configuration: AtomicBool,
+    /// Where the language configs are loaded from, reloaded when they change
+    config_dir: Option<String>,
+    config_watcher: Mutex<Option<ConfigWatcher>>,
+}
+
+/// Code action running [`GENERATE_TESTS_COMMAND`] for the file at `uri`, for editors that only
+/// reach workspace commands through code actions
+pub fn generate_file_action(uri: &Url) -> CodeActionOrCommand {
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title: "Generate tests for this file".to_string(),
+        kind: Some(CodeActionKind::SOURCE),
+        command: Some(Command {
+            title: "Generate tests for this file".to_string(),
+            command: GENERATE_TESTS_COMMAND.to_string(),
+            arguments: Some(vec![json!(uri)]),
+        }),
+        ..CodeAction::default()
+    })
+}
+
+/// Where `name` is defined in `source`: the first line defining it, else the first mentioning
+/// it, else the line the adapter reported
+fn definition_range(source: &str, name: &str, reported_line: usize) -> Range {
+    let short = name.rsplit(['.', ':']).next().unwrap_or(name);
+    let word = Regex::new(&format!(r"\b{}\b", regex::escape(short))).unwrap();
+    let definition = Regex::new(r"^\s*(?:pub|fn|def|async|function|func|export|public|private|protected|static|const|let|var|class)\b").unwrap();
+    let mentions: Vec<(usize, &str, usize)> = source.lines().enumerate()
+        .filter_map(|(index, line)| word.find(line).map(|m| (index, line, m.start())))
+        .collect();
+    let found = mentions.iter().find(|(_, line, _)| definition.is_match(line)).or(mentions.first());
+    match found {
+        Some((index, line, start)) => {
+            let column = line[..*start].encode_utf16().count() as u32;
+            Range::new(Position::new(*index as u32, column), Position::new(*index as u32, column + short.encode_utf16().count() as u32))
+        }
+        None => {
+            let line = reported_line.saturating_sub(1) as u32;
+            Range::new(Position::new(line, 0), Position::new(line, 0))
+        }
+    }
+}
+
+/// One diagnostic per function in `source` that `tests` neither calls nor names a test after
+pub fn untested_symbols(source: &str, patterns: &[TestablePattern], tests: &str) -> Vec<Diagnostic> {
+    untested_functions(patterns, tests).into_iter()
+        .map(|pattern| {
+            let name = pattern.subject_name();
+            Diagnostic {
+                range: definition_range(source, name, pattern.location.line),
+                severity: Some(DiagnosticSeverity::INFORMATION),
+                code: Some(NumberOrString::String("untested".to_string())),
+                source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                message: format!("`{}` has no tests", name),
+                data: Some(json!({ "symbol": name })),
+                ..Diagnostic::default()
+            }
+        })
+        .collect()
+}
+
+/// Where each function in `source` that `tests` doesn't cover is defined, as `{uri, range,
+/// name}`: an LSP location with the function's name, for quickfix lists
+pub fn untested_locations(uri: &Url, source: &str, patterns: &[TestablePattern], tests: &str) -> Vec<Value> {
+    untested_symbols(source, patterns, tests).into_iter()
+        .map(|diagnostic| json!({ "uri": uri, "range": diagnostic.range, "name": diagnostic.data.map(|data| data["symbol"].clone()) }))
+        .collect()
+}
+
+/// Whether `path` is a test file by its name or directory, so it isn't reported as untested
+fn is_test_file(path: &Path) -> bool {
+    let in_test_directory = path.parent().is_some_and(|parent| parent.components()
+        .any(|component| matches!(component.as_os_str().to_str(), Some("test" | "tests" | "__tests__" | "spec" | "specs"))));
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+    in_test_directory
+        || stem.starts_with("test_")
+        || ["_test", ".test", ".spec", "Test", "Tests"].iter().any(|suffix| stem.ends_with(suffix))
+}
+
+/// The function or class the cursor on `line` is in, with the functions to generate tests
+/// for: the closest definition at or above the line, else the first below it. A class stands
+/// for all of its methods
+pub fn symbol_at(source: &str, patterns: &[TestablePattern], line: u32) -> Option<(String, Vec<String>)> {
+    let functions: Vec<&TestablePattern> = patterns.iter()
+        .filter(|pattern| matches!(pattern.pattern_type, PatternType::Function(_)))
+        .collect();
+    let mut candidates: Vec<(u32, String, Vec<String>)> = Vec::new();
+    for function in &functions {
+        if let Some(class) = &function.context.class_name {
+            if !candidates.iter().any(|(_, name, _)| name == class) {
+                let methods = functions.iter()
+                    .filter(|f| f.context.class_name.as_ref() == Some(class))
+                    .map(|f| f.subject_name().to_string())
+                    .collect();
+                candidates.push((definition_range(source, class, 0).start.line, class.clone(), methods));
+            }
+        }
+    }
+    for function in &functions {
+        let name = function.subject_name().to_string();
+        if !candidates.iter().any(|(_, n, subjects)| *n == name && subjects.len() == 1) {
+            candidates.push((definition_range(source, &name, function.location.line).start.line, name.clone(), vec![name]));
+        }
+    }
+
+    let above = candidates.iter().filter(|(start, _, _)| *start <= line).max_by_key(|(start, _, _)| *start);
+    let (_, name, subjects) = above.or_else(|| candidates.iter().min_by_key(|(start, _, _)| *start))?;
+    Some((name.clone(), subjects.clone()))
+}
+
+/// Edit writing `generated` tests to `test_file`: creating it, or merging the tests it lacks
+/// into `existing`. `None` when it already has them all
+pub fn test_file_edit(test_file: &Url, existing: Option<&str>, generated: &str) -> Option<WorkspaceEdit> {
+    let document = OptionalVersionedTextDocumentIdentifier { uri: test_file.clone(), version: None };
+    let operations = match existing {
+        None => vec![
+            DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                uri: test_file.clone(),
+                options: Some(CreateFileOptions { overwrite: Some(false), ignore_if_exists: Some(true) }),
+                annotation_id: None,
+            })),
+            DocumentChangeOperation::Edit(TextDocumentEdit {
+                text_document: document,
+                edits: vec![OneOf::Left(TextEdit::new(Range::default(), generated.to_string()))],
+            }),
+        ],
+        Some(existing) => {
+            let merged = merge_test_file(existing, generated)?;
+            let last_line = existing.rsplit('\n').next().unwrap_or("");
+            let end = Position::new(existing.matches('\n').count() as u32, last_line.encode_utf16().count() as u32);
+            vec![DocumentChangeOperation::Edit(TextDocumentEdit {
+                text_document: document,
+                edits: vec![OneOf::Left(TextEdit::new(Range::new(Position::new(0, 0), end), merged))],
+            })]
+        }
+    };
+    Some(WorkspaceEdit {
+        document_changes: Some(DocumentChanges::Operations(operations)),
+        ..WorkspaceEdit::default()
+    })
+}
+
+impl Backend {
+    pub fn new(client: Client, orchestrator: TestOrchestrator) -> Self {
+        Self {
+            client,
+            orchestrator: Arc::new(RwLock::new(orchestrator)),
+            settings: RwLock::new(ServerSettings::default()),
+            project_config: RwLock::new(ProjectConfig::default()),
+            root: RwLock::new(std::env::current_dir().unwrap_or_default()),
+            documents: RwLock::new(HashMap::new()),
+            pulls_configuration: AtomicBool::new(false),
+            config_dir: None,
+            config_watcher: Mutex::new(None),
+        }
+    }
+
+    /// Reload the adapters when the language configs in `config_dir` change
+    pub fn with_config_dir(mut self, config_dir: String) -> Self {
+        self.config_dir = Some(config_dir);
+        self
+    }
+
+    /// Text of an open document, or of the file on disk
+    async fn text(&self, uri: &Url, path: &Path) -> Result<String> {
+        match self.documents.read().await.get(uri) {
+            Some(text) => Ok(text.clone()),
+            None => Ok(fs::read_to_string(path)?),
+        }
+    }
+
+    /// Source path and test file of a document in a language the settings enable
+    async fn target(&self, uri: &Url) -> Option<(PathBuf, PathBuf)> {
+        let path = uri.to_file_path().ok()?;
+        let orchestrator = self.orchestrator.read().await;
+        let language = orchestrator.detect_language(&path.to_string_lossy()).ok()?;
+        let settings = self.settings.read().await;
+        if !settings.supports(&settings_language(&path, &language)) {
+            return None;
+        }
+        let test_file = settings.test_file(&orchestrator, &self.root.read().await, &path, &language);
+        Some((path, test_file))
+    }
+
+    async fn publish_diagnostics(&self, uri: Url) {
+        let mut diagnostics = Vec::new();
+        if let Some((path, test_file)) = self.target(&uri).await {
+            if let Ok(text) = self.text(&uri, &path).await {
+                let patterns = self.orchestrator.read().await.analyze_file(&path.to_string_lossy(), &text).await.unwrap_or_default();
+                let tests = read_existing_tests(&test_file, &path);
+                diagnostics = untested_symbols(&text, &patterns, &tests);
+            }
+        }
+        self.client.publish_diagnostics(uri, diagnostics, None).await;
+    }
+
+    /// Generate tests for a document and write them to its test file, treating the tests
+    /// already there as the `overwrite` setting says. Tests that go into the document itself,
+    /// as inline Rust tests do, are merged into it with an edit the editor applies to its buffer
+    async fn generate_tests(&self, uri: &Url) -> Result<(PathBuf, TestFileUpdate)> {
+        let (path, test_file) = self.target(uri).await
+            .ok_or_else(|| anyhow::anyhow!("{} is not in a language the server is set up for", uri))?;
+        let text = self.text(uri, &path).await?;
+        let orchestrator = self.orchestrator.read().await;
+        let (mut test_suite, patterns) = orchestrator.generate_tests_with_patterns(&path.to_string_lossy(), &text).await?;
+        if test_suite.test_cases.is_empty() {
+            return Ok((test_file, TestFileUpdate::UpToDate));
+        }
+        let generated = orchestrator.render_test_file(&mut test_suite, &patterns, &path.to_string_lossy(), &text)?;
+        if test_file == path {
+            let skip = self.settings.read().await.overwrite_policy() == OverwritePolicy::Skip && inline_tests_start(&text).is_some();
+            let Some(merged) = merge_test_file(&text, &generated).filter(|_| !skip) else {
+                return Ok((test_file, if skip { TestFileUpdate::Skipped } else { TestFileUpdate::UpToDate }));
+            };
+            let added = test_names(&merged).len() - test_names(&text).len();
+            let edit = test_file_edit(uri, Some(&text), &generated).ok_or_else(|| anyhow::anyhow!("No tests to add to {}", uri))?;
+            if !self.client.apply_edit(edit).await?.applied {
+                return Err(anyhow::anyhow!("The editor didn't apply the tests to {}", uri));
+            }
+            return Ok((test_file, TestFileUpdate::Merged(added)));
+        }
+        let update = write_tests(&test_file, &path, &generated, self.settings.read().await.overwrite_policy(), false)?;
+        Ok((test_file, update))
+    }
+
+    /// Untested functions in the source files under `path`, skipping test files and what
+    /// `.gitignore`, `.uftignore` and the project config's `ignore` leave out
+    async fn untested_under(&self, path: &Path) -> Vec<Value> {
+        let files: Vec<PathBuf> = ignore::WalkBuilder::new(path)
+            .follow_links(false)
+            .require_git(false)
+            .add_custom_ignore_filename(IGNORE_FILE)
+            .build()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.into_path())
+            .filter(|file| file.is_file() && !is_test_file(file.strip_prefix(path).unwrap_or(file)))
+            .collect();
+        let mut locations = Vec::new();
+        for file in files {
+            let Ok(uri) = Url::from_file_path(&file) else { continue };
+            let Some((file, test_file)) = self.target(&uri).await else { continue };
+            let orchestrator = self.orchestrator.read().await;
+            if orchestrator.project_config().is_ignored(&file.to_string_lossy()) {
+                continue;
+            }
+            let Ok(text) = self.text(&uri, &file).await else { continue };
+            let patterns = orchestrator.analyze_file(&file.to_string_lossy(), &text).await.unwrap_or_default();
+            let tests = read_existing_tests(&test_file, &file);
+            locations.extend(untested_locations(&uri, &text, &patterns, &tests));
+        }
+        locations
+    }
+
+    /// Read the project config and its templates again after one was saved, keeping the ones
+    /// in use when they don't check out
+    async fn reload_project_config(&self) {
+        let reloaded = ProjectConfig::discover(&self.root.read().await)
+            .and_then(|config| config.check_templates().map(|_| config));
+        match reloaded {
+            Ok(config) => {
+                *self.project_config.write().await = config;
+                self.apply_settings().await;
+                self.client.log_message(MessageType::INFO, "Reloaded the project config and templates").await;
+                self.publish_all().await;
+            }
+            Err(e) => self.client.log_message(MessageType::WARNING, format!("Keeping the previous templates: {:#}", e)).await,
+        }
+    }
+
+    /// Start watching the language config directory, registering its adapters again whenever a
+    /// config is added, edited or removed
+    async fn watch_language_configs(&self) {
+        let Some(config_dir) = self.config_dir.clone() else { return };
+        let Some(watched) = LanguageLoader::new(config_dir.clone()).resolved_config_dir() else { return };
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let watcher = notify_debouncer_mini::new_debouncer(CONFIG_DEBOUNCE, move |result: notify_debouncer_mini::DebounceEventResult| {
+            let _ = sender.send(result);
+        }).and_then(|mut watcher| {
+            watcher.watcher().watch(&watched, notify::RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+        let watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                self.client.log_message(MessageType::WARNING, format!("Can't watch {} for language config changes: {}", watched.display(), e)).await;
+                return;
+            }
+        };
+        *self.config_watcher.lock().expect("config watcher lock poisoned") = Some(watcher);
+
+        let (orchestrator, client) = (self.orchestrator.clone(), self.client.clone());
+        tokio::spawn(async move {
+            while let Some(result) = receiver.recv().await {
+                if result.is_err() {
+                    continue;
+                }
+                let mut loader = LanguageLoader::new(config_dir.clone());
+                match loader.load_all_languages() {
+                    Ok(adapters) => {
+                        let changes = orchestrator.write().await.reload_adapters(adapters);
+                        client.log_message(MessageType::INFO, format!("Reloaded the language configs: {}", changes)).await;
+                    }
+                    Err(e) => client.log_message(MessageType::WARNING, format!("Keeping the previous language configs: {:#}", e)).await,
+                }
+            }
+        });
+    }
+
+    /// Give the orchestrator the project config with the settings' overrides
+    async fn apply_settings(&self) {
+        let config = self.settings.read().await.apply(&*self.project_config.read().await);
+        self.orchestrator.write().await.set_project_config(config);
+    }
+
+    /// Ask the client for the `unified_testing` section when it answers `workspace/configuration`
+    async fn pull_settings(&self) {
+        if !self.pulls_configuration.load(Ordering::Relaxed) {
+            return;
+        }
+        let item = ConfigurationItem { scope_uri: None, section: Some(SETTINGS_SECTION.to_string()) };
+        if let Ok(values) = self.client.configuration(vec![item]).await {
+            if let Some(settings) = values.first().and_then(ServerSettings::from_value) {
+                *self.settings.write().await = settings;
+                self.apply_settings().await;
+            }
+        }
+    }
+
+    async fn publish_all(&self) {
+        let uris: Vec<Url> = self.documents.read().await.keys().cloned().collect();
+        for uri in uris {
+            self.publish_diagnostics(uri).await;
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+        if let Some(root) = params.workspace_folders.as_ref()
+            .and_then(|folders| folders.first())
+            .and_then(|folder| folder.uri.to_file_path().ok())
+        {
+            *self.root.write().await = root;
+        }
+        if let Ok(config) = ProjectConfig::discover(&self.root.read().await) {
+            *self.project_config.write().await = config;
+        }
+        if let Some(settings) = params.initialization_options.as_ref().and_then(ServerSettings::from_value) {
+            *self.settings.write().await = settings;
+        }
+        self.apply_settings().await;
+        let pulls = params.capabilities.workspace.as_ref().and_then(|workspace| workspace.configuration).unwrap_or(false);
+        self.pulls_configuration.store(pulls, Ordering::Relaxed);
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                    ..TextDocumentSyncOptions::default()
+                })),
+                code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                    code_action_kinds: Some(vec![CodeActionKind::REFACTOR, CodeActionKind::SOURCE]),
+                    ..CodeActionOptions::default()
+                })),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![GENERATE_TESTS_COMMAND.to_string(), UNTESTED_FUNCTIONS_COMMAND.to_string()],
+                    ..ExecuteCommandOptions::default()
+                }),
+                ..ServerCapabilities::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "uft".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        self.pull_settings().await;
+        self.watch_language_configs().await;
+    }
+
+    async fn shutdown(&self) -> jsonrpc::Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.write().await.insert(uri.clone(), params.text_document.text);
+        self.publish_diagnostics(uri).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let uri = params.text_document.uri;
+        if let Some(change) = params.content_changes.into_iter().last() {
+            self.documents.write().await.insert(uri.clone(), change.text);
+        }
+        self.publish_diagnostics(uri).await;
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let uri = params.text_document.uri;
+        if let Some(text) = params.text {
+            self.documents.write().await.insert(uri.clone(), text);
+        }
+        if let Ok(path) = uri.to_file_path() {
+            if self.orchestrator.read().await.project_config().is_template_file(&path) {
+                self.reload_project_config().await;
+                return;
+            }
+        }
+        if self.settings.read().await.auto_generate && self.target(&uri).await.is_some() {
+            match self.generate_tests(&uri).await {
+                Ok((test_file, TestFileUpdate::Created(count) | TestFileUpdate::Merged(count))) => {
+                    self.client.log_message(MessageType::INFO, format!("Added {} tests to {}", count, test_file.display())).await;
+                }
+                Ok(_) => {}
+                Err(e) => self.client.log_message(MessageType::WARNING, format!("Generating tests for {} failed: {}", uri, e)).await,
+            }
+        }
+        self.publish_diagnostics(uri).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.write().await.remove(&uri);
+        self.client.publish_diagnostics(uri, Vec::new(), None).await;
+    }
+
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        match ServerSettings::from_value(&params.settings) {
+            Some(settings) => {
+                *self.settings.write().await = settings;
+                self.apply_settings().await;
+            }
+            None => self.pull_settings().await,
+        }
+        self.publish_all().await;
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> jsonrpc::Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let Some((path, test_file)) = self.target(&uri).await else {
+            return Ok(None);
+        };
+        let file_action = generate_file_action(&uri);
+        let Ok(text) = self.text(&uri, &path).await else {
+            return Ok(Some(vec![file_action]));
+        };
+        let orchestrator = self.orchestrator.read().await;
+        let patterns = orchestrator.analyze_file(&path.to_string_lossy(), &text).await.unwrap_or_default();
+        let Some((name, subjects)) = symbol_at(&text, &patterns, params.range.start.line) else {
+            return Ok(Some(vec![file_action]));
+        };
+        let subject_patterns: Vec<TestablePattern> = patterns.iter()
+            .filter(|pattern| subjects.iter().any(|subject| subject == pattern.subject_name()))
+            .cloned()
+            .collect();
+        let generated = match orchestrator.generate_tests_for_subjects(&path.to_string_lossy(), &text, &subjects).await {
+            Ok(mut suite) if !suite.test_cases.is_empty() => orchestrator.render_test_file(&mut suite, &subject_patterns, &path.to_string_lossy(), &text),
+            _ => return Ok(Some(vec![file_action])),
+        };
+        let (Ok(generated), Ok(test_uri)) = (generated, Url::from_file_path(&test_file)) else {
+            return Ok(Some(vec![file_action]));
+        };
+        let existing = self.text(&test_uri, &test_file).await.ok();
+        // A symbol's tests are always merged in: replacing the file with them would drop the rest
+        if existing.is_some() && self.settings.read().await.overwrite_policy() == OverwritePolicy::Skip {
+            return Ok(Some(vec![file_action]));
+        }
+        let Some(edit) = test_file_edit(&test_uri, existing.as_deref(), &generated) else {
+            return Ok(Some(vec![file_action]));
+        };
+
+        let diagnostics: Vec<Diagnostic> = params.context.diagnostics.into_iter()
+            .filter(|diagnostic| diagnostic.source.as_deref() == Some(DIAGNOSTIC_SOURCE))
+            .filter(|diagnostic| diagnostic.data.as_ref().and_then(|data| data["symbol"].as_str()).is_some_and(|symbol| subjects.iter().any(|s| s == symbol)))
+            .collect();
+        Ok(Some(vec![CodeActionOrCommand::CodeAction(CodeAction {
+            title: format!("Generate tests for `{}`", name),
+            kind: Some(CodeActionKind::REFACTOR),
+            diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
+            edit: Some(edit),
+            ..CodeAction::default()
+        }), file_action]))
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> jsonrpc::Result<Option<Value>> {
+        if params.command == UNTESTED_FUNCTIONS_COMMAND {
+            let mut paths = Vec::new();
+            for argument in &params.arguments {
+                let path = argument.as_str()
+                    .and_then(|uri| Url::parse(uri).ok())
+                    .and_then(|uri| uri.to_file_path().ok())
+                    .ok_or_else(|| jsonrpc::Error::invalid_params(format!("{} takes the URIs of files or directories", UNTESTED_FUNCTIONS_COMMAND)))?;
+                paths.push(path);
+            }
+            if paths.is_empty() {
+                paths.push(self.root.read().await.clone());
+            }
+            let mut locations = Vec::new();
+            for path in paths {
+                locations.extend(self.untested_under(&path).await);
+            }
+            return Ok(Some(Value::Array(locations)));
+        }
+        if params.command != GENERATE_TESTS_COMMAND {
+            return Err(jsonrpc::Error::invalid_params(format!("Unknown command: {}", params.command)));
+        }
+        let uri = params.arguments.first()
+            .and_then(|argument| argument.as_str().or_else(|| argument.get("uri")?.as_str()))
+            .and_then(|uri| Url::parse(uri).ok())
+            .ok_or_else(|| jsonrpc::Error::invalid_params(format!("{} takes the URI of the file to test", GENERATE_TESTS_COMMAND)))?;
+
+        let (test_file, update) = self.generate_tests(&uri).await.map_err(|e| jsonrpc::Error {
+            code: jsonrpc::ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })?;
+        let added = update.tests_written();
+        let message = if added > 0 {
+            format!("Added {} tests to {}", added, test_file.display())
+        } else {
+            format!("{} is up to date", test_file.display())
+        };
+        self.client.show_message(MessageType::INFO, message).await;
+        self.publish_diagnostics(uri).await;
+        Ok(Some(json!({ "testFile": test_file, "added": added })))
+    }
+}
+
+/// Serve the language protocol over stdin and stdout until the client shuts the server down,
+/// reloading the language configs in `config_dir` as they change
+pub async fn serve(orchestrator: TestOrchestrator, config_dir: String) {
+    let (service, socket) = LspService::new(|client| Backend::new(client, orchestrator).with_config_dir(config_dir));
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket).serve(service).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Context, FunctionPattern, SourceLocation};
+
+    fn function(name: &str) -> TestablePattern {
+        TestablePattern {
+            id: name.to_string(),
+            pattern_type: PatternType::Function(FunctionPattern { name: name.to_string(), parameters: vec![], return_type: None }),
+            location: SourceLocation { file: "calc.py".to_string(), line: 1, column: 0 },
+            context: Context { function_name: Some(name.to_string()), class_name: None, module_name: None },
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_untested_symbols_skips_tested_functions() {
+        let source = "def add(a, b):\n    return a + b\n\n\ndef subtract(a, b):\n    return add(a, -b)\n";
+        let tests = "class TestGenerated:\n    def test_add(self):\n        assert add(1, 2) == 3\n";
+        let diagnostics = untested_symbols(source, &[function("add"), function("subtract"), function("subtract")], tests);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "`subtract` has no tests");
+        assert_eq!(diagnostics[0].range, Range::new(Position::new(4, 4), Position::new(4, 12)));
+        assert_eq!(diagnostics[0].source.as_deref(), Some("uft"));
+        assert_eq!(untested_symbols(source, &[function("subtract")], "def test_subtract_negative(self):\n").len(), 0);
+    }
+
+    #[test]
+    fn test_symbol_at_cursor_and_test_file_edit() {
+        let mut area = function("area");
+        area.context.class_name = Some("Shape".to_string());
+        let mut scale = function("scale");
+        scale.context.class_name = Some("Shape".to_string());
+        let patterns = vec![function("add"), area, scale];
+        let source = "def add(a, b):\n    return a + b\n\n\nclass Shape:\n    def area(self):\n        return 0\n\n    def scale(self, factor):\n        return factor\n";
+
+        assert_eq!(symbol_at(source, &patterns, 1), Some(("add".to_string(), vec!["add".to_string()])));
+        assert_eq!(symbol_at(source, &patterns, 4), Some(("Shape".to_string(), vec!["area".to_string(), "scale".to_string()])));
+        assert_eq!(symbol_at(source, &patterns, 9), Some(("scale".to_string(), vec!["scale".to_string()])));
+        assert_eq!(symbol_at(source, &[], 0), None);
+
+        let uri = Url::parse("file:///repo/tests/test_calc.py").unwrap();
+        let generated = "import pytest\n\n\nclass TestGenerated:\n    def test_add(self):\n        assert add(1, 2) == 3\n";
+        let Some(DocumentChanges::Operations(created)) = test_file_edit(&uri, None, generated).unwrap().document_changes else {
+            panic!("Expected document operations");
+        };
+        assert!(matches!(&created[0], DocumentChangeOperation::Op(ResourceOp::Create(_))));
+
+        let existing = "import pytest\n\n\nclass TestGenerated:\n    def test_total(self):\n        assert total([]) == 0\n";
+        let Some(DocumentChanges::Operations(merged)) = test_file_edit(&uri, Some(existing), generated).unwrap().document_changes else {
+            panic!("Expected document operations");
+        };
+        let DocumentChangeOperation::Edit(edit) = &merged[0] else {
+            panic!("Expected a text edit");
+        };
+        let OneOf::Left(text_edit) = &edit.edits[0] else {
+            panic!("Expected a plain text edit");
+        };
+        assert_eq!(text_edit.range, Range::new(Position::new(0, 0), Position::new(6, 0)));
+        assert!(text_edit.new_text.ends_with("    def test_add(self):\n        assert add(1, 2) == 3\n"));
+        assert!(test_file_edit(&uri, Some(generated), generated).is_none());
+
+        // Inline Rust tests edit the source document, keeping its code
+        let source_uri = Url::parse("file:///repo/src/parser.rs").unwrap();
+        let code = "pub fn parse(input: &str) -> usize {\n    input.len()\n}\n";
+        let tests = "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_parse() {\n        let _ = parse(\"\");\n    }\n}\n";
+        let Some(DocumentChanges::Operations(inline)) = test_file_edit(&source_uri, Some(code), tests).unwrap().document_changes else {
+            panic!("Expected document operations");
+        };
+        let DocumentChangeOperation::Edit(edit) = &inline[0] else {
+            panic!("Expected a text edit");
+        };
+        let OneOf::Left(text_edit) = &edit.edits[0] else {
+            panic!("Expected a plain text edit");
+        };
+        assert_eq!(text_edit.new_text, format!("{}\n{}", code, tests));
+
+        let CodeActionOrCommand::CodeAction(action) = generate_file_action(&uri) else {
+            panic!("Expected a code action");
+        };
+        let command = action.command.unwrap();
+        assert_eq!(command.command, GENERATE_TESTS_COMMAND);
+        assert_eq!(command.arguments.unwrap(), vec![json!("file:///repo/tests/test_calc.py")]);
+    }
+
+    #[test]
+    fn test_untested_locations_for_quickfix() {
+        let uri = Url::parse("file:///repo/src/calc.py").unwrap();
+        let source = "def add(a, b):\n    return a + b\n\n\ndef subtract(a, b):\n    return add(a, -b)\n";
+        let locations = untested_locations(&uri, source, &[function("add"), function("subtract")], "def test_add(self):\n");
+
+        assert_eq!(locations, vec![json!({
+            "uri": "file:///repo/src/calc.py",
+            "range": {"start": {"line": 4, "character": 4}, "end": {"line": 4, "character": 12}},
+            "name": "subtract",
+        })]);
+
+        assert!(is_test_file(Path::new("tests/test_calc.py")));
+        assert!(is_test_file(Path::new("src/calc.test.js")));
+        assert!(is_test_file(Path::new("src/CalcTest.java")));
+        assert!(is_test_file(Path::new("pkg/calc_test.go")));
+        assert!(!is_test_file(Path::new("src/calc.py")));
+        assert!(!is_test_file(Path::new("src/contest.py")));
+    }
+
+    #[test]
+    fn test_server_settings_from_workspace_configuration() {
+        let plugin = json!({"unified_testing": {"auto_generate": true, "output_directory": "tests/", "supported_languages": ["javascript", "typescript", "python", "rust"], "frameworks": {"Python": "unittest"}, "min_confidence": 0.7, "overwrite": "skip"}});
+        let settings = ServerSettings::from_value(&plugin).unwrap();
+        assert!(settings.auto_generate);
+        assert_eq!(settings.overwrite_policy(), OverwritePolicy::Skip);
+        let project = ProjectConfig::parse("confidence_threshold = 0.5\n[frameworks]\npython = \"pytest\"\njava = \"testng\"\n").unwrap();
+        let config = settings.apply(&project);
+        assert_eq!(config.framework("python"), Some("unittest"));
+        assert_eq!(config.framework("java"), Some("testng"));
+        assert!(!config.is_confident(0.6));
+        assert!(settings.supports("typescript"));
+        assert!(!settings.supports("java"));
+        assert_eq!(ServerSettings::from_value(&plugin[SETTINGS_SECTION]), Some(settings.clone()));
+        assert_eq!(settings.test_file(&TestOrchestrator::new(), Path::new("/repo"), Path::new("src/calc.py"), "python"), PathBuf::from("/repo/tests/test_calc.py"));
+
+        assert_eq!(ServerSettings::from_value(&Value::Null), None);
+        let defaults = ServerSettings::from_value(&json!({})).unwrap();
+        assert!(defaults.supports("go"));
+        assert_eq!(defaults.overwrite_policy(), OverwritePolicy::Merge);
+        assert!(defaults.apply(&project).is_confident(0.5));
+        assert_eq!(defaults.test_file(&TestOrchestrator::new(), Path::new("/repo"), Path::new("src/calc.py"), "python"), PathBuf::from("/repo/src/tests/test_calc.py"));
+    }
+}
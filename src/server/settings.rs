@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::core::{OverwritePolicy, ProjectConfig, TestOrchestrator};
+
+/// The section editor plugins put the server's settings under
+pub const SETTINGS_SECTION: &str = "unified_testing";
+
+/// Workspace settings, as the Zed extension sends them under `unified_testing`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ServerSettings {
+    /// Write tests for a file whenever it's saved
+    pub auto_generate: bool,
+    /// Directory, relative to the workspace root, to write test files to; next to their
+    /// sources under each language's conventions when unset
+    pub output_directory: Option<String>,
+    /// Languages to report untested symbols for; every language with an adapter when empty
+    pub supported_languages: Vec<String>,
+    /// Test framework per language, over the project config's `[frameworks]`
+    pub frameworks: HashMap<String, String>,
+    /// Patterns detected with lower confidence than this get no tests, over the project
+    /// config's `confidence_threshold`
+    pub min_confidence: Option<f32>,
+    /// What generating does to a test file that already exists; merging in the new tests
+    /// when unset
+    pub overwrite: Option<OverwritePolicy>,
+}
+
+impl ServerSettings {
+    /// Read settings from either the whole workspace configuration or just its
+    /// `unified_testing` section. `None` when neither holds any
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let section = value.get(SETTINGS_SECTION).unwrap_or(value);
+        if !section.is_object() {
+            return None;
+        }
+        serde_json::from_value(section.clone()).ok()
+    }
+
+    /// `config` with the frameworks and confidence threshold these settings set in place of
+    /// its own
+    pub fn apply(&self, config: &ProjectConfig) -> ProjectConfig {
+        let mut config = config.clone();
+        for (language, framework) in &self.frameworks {
+            config.frameworks.insert(language.to_lowercase(), framework.clone());
+        }
+        if self.min_confidence.is_some() {
+            config.confidence_threshold = self.min_confidence;
+        }
+        config
+    }
+
+    pub fn overwrite_policy(&self) -> OverwritePolicy {
+        self.overwrite.unwrap_or(OverwritePolicy::Merge)
+    }
+
+    pub fn supports(&self, language: &str) -> bool {
+        self.supported_languages.is_empty() || self.supported_languages.iter().any(|l| l == language)
+    }
+
+    /// Where the tests for `source` go: the conventional test file name, in the output directory
+    /// when one is set, else where the project config puts it
+    pub fn test_file(&self, orchestrator: &TestOrchestrator, root: &Path, source: &Path, language: &str) -> PathBuf {
+        let conventional = orchestrator.test_file_path(root, source, language);
+        match (&self.output_directory, conventional.file_name()) {
+            (Some(directory), Some(name)) => root.join(directory).join(name),
+            _ => conventional,
+        }
+    }
+}
+
+/// Language name of a source file as the settings spell it, which tells TypeScript apart
+/// from JavaScript
+pub fn settings_language(path: &Path, detected: &str) -> String {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("ts" | "tsx") => "typescript".to_string(),
+        _ => detected.to_string(),
+    }
+}
@@ -2,6 +2,9 @@ use askama::Template;
 use serde_json::Value;
 use anyhow::Result;
 
+use super::filters;
+use super::{ApiTestContext, GoTestContext, JavaTestClass, JavaTestMethod, RstestCase, RstestContext, SuiteTest, TestSuiteContext};
+
 // Template structs for each test type with Askama derive macro
 #[derive(Template)]
 #[template(path = "jest/function_test.html")]
@@ -89,6 +92,49 @@ pub struct PytestClassTemplate {
     pub methods: Vec<String>,
 }
 
+#[derive(Template)]
+#[template(path = "unittest/function_test.html")]
+pub struct UnittestFunctionTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub inputs: Vec<Value>,
+    pub expected_outputs: Vec<Value>,
+    pub test_category: String,
+    pub imports: Vec<String>,
+    pub setup_code: String,
+    pub teardown_code: String,
+}
+
+#[derive(Template)]
+#[template(path = "unittest/async_test.html")]
+pub struct UnittestAsyncTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub inputs: Vec<Value>,
+    pub expected_outputs: Vec<Value>,
+    pub test_category: String,
+    pub imports: Vec<String>,
+    pub setup_code: String,
+    pub teardown_code: String,
+}
+
+#[derive(Template)]
+#[template(path = "unittest/class_test.html")]
+pub struct UnittestClassTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub inputs: Vec<Value>,
+    pub expected_outputs: Vec<Value>,
+    pub test_category: String,
+    pub imports: Vec<String>,
+    pub setup_code: String,
+    pub teardown_code: String,
+    pub methods: Vec<String>,
+}
+
 #[derive(Template)]
 #[template(path = "cargo/function_test.html")]
 pub struct CargoFunctionTemplate {
@@ -132,6 +178,120 @@ pub struct CargoStructTemplate {
     pub methods: Vec<String>,
 }
 
+#[derive(Template)]
+#[template(path = "jest/api_test.html")]
+pub struct JestApiTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub method: String,
+    pub path: String,
+    pub payload: Value,
+    pub sends_body: bool,
+    pub status: u64,
+    pub query: Vec<(String, String)>,
+    pub query_string: String,
+    pub body_json: String,
+}
+
+#[derive(Template)]
+#[template(path = "pytest/api_test.html")]
+pub struct PytestApiTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub method: String,
+    pub path: String,
+    pub payload: Value,
+    pub sends_body: bool,
+    pub status: u64,
+    pub query: Vec<(String, String)>,
+    pub query_string: String,
+    pub body_json: String,
+}
+
+#[derive(Template)]
+#[template(path = "unittest/api_test.html")]
+pub struct UnittestApiTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub method: String,
+    pub path: String,
+    pub payload: Value,
+    pub sends_body: bool,
+    pub status: u64,
+    pub query: Vec<(String, String)>,
+    pub query_string: String,
+    pub body_json: String,
+}
+
+#[derive(Template)]
+#[template(path = "cargo/api_test.html")]
+pub struct CargoApiTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub method: String,
+    pub path: String,
+    pub payload: Value,
+    pub sends_body: bool,
+    pub status: u64,
+    pub query: Vec<(String, String)>,
+    pub query_string: String,
+    pub body_json: String,
+}
+
+#[derive(Template)]
+#[template(path = "go-testing/api_test.html")]
+pub struct GoApiTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub method: String,
+    pub path: String,
+    pub payload: Value,
+    pub sends_body: bool,
+    pub status: u64,
+    pub query: Vec<(String, String)>,
+    pub query_string: String,
+    pub body_json: String,
+}
+
+#[derive(Template)]
+#[template(path = "junit/api_test.html")]
+pub struct JunitApiTemplate {
+    pub function_name: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub method: String,
+    pub path: String,
+    pub payload: Value,
+    pub sends_body: bool,
+    pub status: u64,
+    pub query: Vec<(String, String)>,
+    pub query_string: String,
+    pub body_json: String,
+}
+
+#[derive(Template)]
+#[template(path = "cargo/rstest_test.html")]
+pub struct CargoRstestTemplate {
+    pub test_name: String,
+    pub description: String,
+    pub params: Vec<String>,
+    pub expected_type: String,
+    pub cases: Vec<RstestCase>,
+    pub attributes: String,
+    pub body: String,
+}
+
 #[derive(Template)]
 #[template(path = "go-testing/function_test.html")]
 pub struct GoFunctionTemplate {
@@ -190,6 +350,140 @@ pub struct GoBenchmarkTemplate {
     pub teardown_code: String,
 }
 
+#[derive(Template)]
+#[template(path = "go/function_test.html")]
+pub struct GoCasesFunctionTemplate {
+    pub function_name: String,
+    pub callee: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub package_name: String,
+    pub params: Vec<(String, String)>,
+    pub want_type: String,
+    pub assign: String,
+    pub discard: String,
+    pub returns_error: bool,
+    pub args: String,
+    pub args_format: String,
+    pub cases: Vec<GoCase>,
+}
+
+#[derive(Template)]
+#[template(path = "go/table_test.html")]
+pub struct GoTableTemplate {
+    pub function_name: String,
+    pub callee: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub package_name: String,
+    pub params: Vec<(String, String)>,
+    pub want_type: String,
+    pub assign: String,
+    pub discard: String,
+    pub returns_error: bool,
+    pub args: String,
+    pub args_format: String,
+    pub cases: Vec<GoCase>,
+}
+
+#[derive(Template)]
+#[template(path = "go/testify_test.html")]
+pub struct GoTestifyTemplate {
+    pub function_name: String,
+    pub callee: String,
+    pub test_name: String,
+    pub description: String,
+    pub test_category: String,
+    pub package_name: String,
+    pub params: Vec<(String, String)>,
+    pub want_type: String,
+    pub assign: String,
+    pub discard: String,
+    pub returns_error: bool,
+    pub args: String,
+    pub args_format: String,
+    pub cases: Vec<GoCase>,
+}
+
+#[derive(Template)]
+#[template(path = "junit5/test_class.html")]
+pub struct Junit5TestClassTemplate {
+    pub package_name: String,
+    pub class_name: String,
+    pub subject: String,
+    pub constructible: bool,
+    pub tests: Vec<JavaTestMethod>,
+}
+
+#[derive(Template)]
+#[template(path = "testng/test_class.html")]
+pub struct TestngTestClassTemplate {
+    pub package_name: String,
+    pub class_name: String,
+    pub subject: String,
+    pub constructible: bool,
+    pub tests: Vec<JavaTestMethod>,
+}
+
+#[derive(Template)]
+#[template(path = "jest/javascript_test_suite.html")]
+pub struct JestSuiteTemplate {
+    pub imports: Vec<String>,
+    pub tests: Vec<SuiteTest>,
+}
+
+#[derive(Template)]
+#[template(path = "mocha/javascript_test_suite.html")]
+pub struct MochaSuiteTemplate {
+    pub tests: Vec<SuiteTest>,
+}
+
+#[derive(Template)]
+#[template(path = "pytest/python_test_suite.html")]
+pub struct PytestSuiteTemplate {
+    pub imports: Vec<String>,
+    pub tests: Vec<SuiteTest>,
+}
+
+#[derive(Template)]
+#[template(path = "unittest/python_test_suite.html")]
+pub struct UnittestSuiteTemplate {
+    pub tests: Vec<SuiteTest>,
+}
+
+#[derive(Template)]
+#[template(path = "cargo/rust_test_suite.html")]
+pub struct CargoSuiteTemplate {
+    pub tests: Vec<SuiteTest>,
+}
+
+#[derive(Template)]
+#[template(path = "go-testing/go_test_suite.html")]
+pub struct GoSuiteTemplate {
+    pub tests: Vec<SuiteTest>,
+}
+
+#[derive(Template)]
+#[template(path = "junit5/java_test_suite.html")]
+pub struct Junit5SuiteTemplate {
+    pub class_name: String,
+    pub tests: Vec<SuiteTest>,
+}
+
+/// One `t.Run` subtest of the `go/*` templates
+pub struct GoCase {
+    /// Quoted Go string
+    pub name: String,
+    /// `(parameter, Go expression)` for each table field
+    pub fields: Vec<(String, String)>,
+    /// Arguments of a direct call
+    pub call_args: String,
+    /// Go expression of the expected first result; empty when it isn't checked
+    pub want: String,
+}
+
 #[derive(Template)]
 #[template(path = "junit/method_test.html")]
 pub struct JunitMethodTemplate {
@@ -349,6 +643,52 @@ impl AskamaTemplateEngine {
         Ok(template.render()?)
     }
     
+    pub fn render_unittest_function_test(&self, data: &crate::TestTemplateData) -> Result<String> {
+        let template = UnittestFunctionTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            inputs: data.inputs.clone(),
+            expected_outputs: data.expected_outputs.clone(),
+            test_category: data.test_category.clone(),
+            imports: data.imports.clone(),
+            setup_code: data.setup_code.clone().unwrap_or_default(),
+            teardown_code: data.teardown_code.clone().unwrap_or_default(),
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_unittest_async_test(&self, data: &crate::TestTemplateData) -> Result<String> {
+        let template = UnittestAsyncTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            inputs: data.inputs.clone(),
+            expected_outputs: data.expected_outputs.clone(),
+            test_category: data.test_category.clone(),
+            imports: data.imports.clone(),
+            setup_code: data.setup_code.clone().unwrap_or_default(),
+            teardown_code: data.teardown_code.clone().unwrap_or_default(),
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_unittest_class_test(&self, data: &crate::TestTemplateData, methods: Vec<String>) -> Result<String> {
+        let template = UnittestClassTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            inputs: data.inputs.clone(),
+            expected_outputs: data.expected_outputs.clone(),
+            test_category: data.test_category.clone(),
+            imports: data.imports.clone(),
+            setup_code: data.setup_code.clone().unwrap_or_default(),
+            teardown_code: data.teardown_code.clone().unwrap_or_default(),
+            methods,
+        };
+        Ok(template.render()?)
+    }
+    
     pub fn render_cargo_function_test(&self, data: &crate::TestTemplateData) -> Result<String> {
         let template = CargoFunctionTemplate {
             function_name: data.function_name.clone(),
@@ -395,6 +735,133 @@ impl AskamaTemplateEngine {
         Ok(template.render()?)
     }
     
+    pub fn render_jest_api_test(&self, data: &crate::TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        let fields = ApiFields::new(data, context);
+        let template = JestApiTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            method: fields.method,
+            path: fields.path,
+            payload: fields.payload,
+            sends_body: fields.sends_body,
+            status: fields.status,
+            query: fields.query,
+            query_string: fields.query_string,
+            body_json: fields.body_json,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_pytest_api_test(&self, data: &crate::TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        let fields = ApiFields::new(data, context);
+        let template = PytestApiTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            method: fields.method,
+            path: fields.path,
+            payload: fields.payload,
+            sends_body: fields.sends_body,
+            status: fields.status,
+            query: fields.query,
+            query_string: fields.query_string,
+            body_json: fields.body_json,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_unittest_api_test(&self, data: &crate::TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        let fields = ApiFields::new(data, context);
+        let template = UnittestApiTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            method: fields.method,
+            path: fields.path,
+            payload: fields.payload,
+            sends_body: fields.sends_body,
+            status: fields.status,
+            query: fields.query,
+            query_string: fields.query_string,
+            body_json: fields.body_json,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_cargo_api_test(&self, data: &crate::TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        let fields = ApiFields::new(data, context);
+        let template = CargoApiTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            method: fields.method,
+            path: fields.path,
+            payload: fields.payload,
+            sends_body: fields.sends_body,
+            status: fields.status,
+            query: fields.query,
+            query_string: fields.query_string,
+            body_json: fields.body_json,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_go_api_test(&self, data: &crate::TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        let fields = ApiFields::new(data, context);
+        let template = GoApiTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            method: fields.method,
+            path: fields.path,
+            payload: fields.payload,
+            sends_body: fields.sends_body,
+            status: fields.status,
+            query: fields.query,
+            query_string: fields.query_string,
+            body_json: fields.body_json,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_junit_api_test(&self, data: &crate::TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        let fields = ApiFields::new(data, context);
+        let template = JunitApiTemplate {
+            function_name: data.function_name.clone(),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            method: fields.method,
+            path: fields.path,
+            payload: fields.payload,
+            sends_body: fields.sends_body,
+            status: fields.status,
+            query: fields.query,
+            query_string: fields.query_string,
+            body_json: fields.body_json,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_cargo_rstest_test(&self, context: &RstestContext) -> Result<String> {
+        let template = CargoRstestTemplate {
+            test_name: context.test_name.clone(),
+            description: context.description.clone(),
+            params: context.params.clone(),
+            expected_type: context.expected_type.clone(),
+            cases: context.cases.clone(),
+            attributes: context.attributes.clone(),
+            body: context.body.clone(),
+        };
+        Ok(template.render()?)
+    }
+    
     pub fn render_go_function_test(&self, data: &crate::TestTemplateData) -> Result<String> {
         let template = GoFunctionTemplate {
             function_name: data.function_name.clone(),
@@ -457,6 +924,91 @@ impl AskamaTemplateEngine {
         Ok(template.render()?)
     }
     
+    pub fn render_go_cases_function_test(&self, data: &crate::TestTemplateData, context: &GoTestContext) -> Result<String> {
+        let fields = GoFields::new(context);
+        let template = GoCasesFunctionTemplate {
+            function_name: data.function_name.clone(),
+            callee: context.callee(&data.function_name),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            package_name: context.package.clone(),
+            params: fields.params,
+            want_type: fields.want_type,
+            assign: fields.assign,
+            discard: fields.discard,
+            returns_error: fields.returns_error,
+            args: fields.args,
+            args_format: fields.args_format,
+            cases: fields.cases,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_go_table_test(&self, data: &crate::TestTemplateData, context: &GoTestContext) -> Result<String> {
+        let fields = GoFields::new(context);
+        let template = GoTableTemplate {
+            function_name: data.function_name.clone(),
+            callee: context.callee(&data.function_name),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            package_name: context.package.clone(),
+            params: fields.params,
+            want_type: fields.want_type,
+            assign: fields.assign,
+            discard: fields.discard,
+            returns_error: fields.returns_error,
+            args: fields.args,
+            args_format: fields.args_format,
+            cases: fields.cases,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_go_testify_test(&self, data: &crate::TestTemplateData, context: &GoTestContext) -> Result<String> {
+        let fields = GoFields::new(context);
+        let template = GoTestifyTemplate {
+            function_name: data.function_name.clone(),
+            callee: context.callee(&data.function_name),
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            test_category: data.test_category.clone(),
+            package_name: context.package.clone(),
+            params: fields.params,
+            want_type: fields.want_type,
+            assign: fields.assign,
+            discard: fields.discard,
+            returns_error: fields.returns_error,
+            args: fields.args,
+            args_format: fields.args_format,
+            cases: fields.cases,
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_junit5_test_class(&self, class: &JavaTestClass) -> Result<String> {
+        let template = Junit5TestClassTemplate {
+            package_name: class.package.clone(),
+            class_name: class.class_name.clone(),
+            subject: class.subject.clone(),
+            constructible: class.constructible,
+            tests: class.tests.clone(),
+        };
+        Ok(template.render()?)
+    }
+    
+    pub fn render_testng_test_class(&self, class: &JavaTestClass) -> Result<String> {
+        let template = TestngTestClassTemplate {
+            package_name: class.package.clone(),
+            class_name: class.class_name.clone(),
+            subject: class.subject.clone(),
+            constructible: class.constructible,
+            tests: class.tests.clone(),
+        };
+        Ok(template.render()?)
+    }
+    
     pub fn render_junit_method_test(&self, data: &crate::TestTemplateData) -> Result<String> {
         let template = JunitMethodTemplate {
             function_name: data.function_name.clone(),
@@ -520,6 +1072,74 @@ impl AskamaTemplateEngine {
         Ok(template.render()?)
     }
     
+    /// Render a `go/*` template for a function with this package, signature and cases
+    pub fn render_go_test(&self, template_name: &str, data: &crate::TestTemplateData, context: &GoTestContext) -> Result<String> {
+        match template_name {
+            "go/function_test" => self.render_go_cases_function_test(data, context),
+            "go/table_test" => self.render_go_table_test(data, context),
+            "go/testify_test" => self.render_go_testify_test(data, context),
+            _ => Err(anyhow::anyhow!("Unknown Go template: {}", template_name)),
+        }
+    }
+
+    /// Render an `*/api_test` template for requests to this endpoint
+    pub fn render_api_test(&self, template_name: &str, data: &crate::TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        match template_name {
+            "jest/api_test" => self.render_jest_api_test(data, context),
+            "pytest/api_test" => self.render_pytest_api_test(data, context),
+            "unittest/api_test" => self.render_unittest_api_test(data, context),
+            "cargo/api_test" => self.render_cargo_api_test(data, context),
+            "go-testing/api_test" => self.render_go_api_test(data, context),
+            "junit/api_test" => self.render_junit_api_test(data, context),
+            _ => Err(anyhow::anyhow!("Unknown API test template: {}", template_name)),
+        }
+    }
+
+    /// Render a `junit5/*` or `testng/*` template for a whole test class
+    pub fn render_java_test_class(&self, template_name: &str, class: &JavaTestClass) -> Result<String> {
+        match template_name {
+            "junit5/test_class" => self.render_junit5_test_class(class),
+            "testng/test_class" => self.render_testng_test_class(class),
+            _ => Err(anyhow::anyhow!("Unknown Java test class template: {}", template_name)),
+        }
+    }
+
+    /// Render a `*/<language>_test_suite` template for a whole test file
+    pub fn render_test_suite(&self, template_name: &str, suite: &TestSuiteContext) -> Result<String> {
+        let tests = suite.tests.clone();
+        let rendered = match template_name {
+            "jest/javascript_test_suite" => JestSuiteTemplate { imports: suite.imports.clone(), tests }.render()?,
+            // The adapter's bodies assert with Jest's matchers, which chai doesn't have
+            "mocha/javascript_test_suite" => MochaSuiteTemplate {
+                tests: tests.into_iter().map(|test| SuiteTest { body: String::new(), ..test }).collect(),
+            }.render()?,
+            "pytest/python_test_suite" => PytestSuiteTemplate { imports: suite.imports.clone(), tests }.render()?,
+            "unittest/python_test_suite" => UnittestSuiteTemplate { tests }.render()?,
+            "cargo/rust_test_suite" => CargoSuiteTemplate { tests }.render()?,
+            "go-testing/go_test_suite" => GoSuiteTemplate { tests }.render()?,
+            "junit5/java_test_suite" => Junit5SuiteTemplate { class_name: suite.class_name.clone(), tests }.render()?,
+            _ => return Err(anyhow::anyhow!("Unknown test suite template: {}", template_name)),
+        };
+        Ok(format!("{}\n", rendered))
+    }
+
+    /// Render a class or struct template with the methods it should cover; other templates
+    /// don't take methods and render as [`Self::render_test`] does
+    pub fn render_class_test(&self, template_name: &str, data: &crate::TestTemplateData, methods: Vec<String>) -> Result<String> {
+        match template_name {
+            "jest/class_test" => self.render_jest_class_test(data, methods),
+            "pytest/class_test" => self.render_pytest_class_test(data, methods),
+            "unittest/class_test" => self.render_unittest_class_test(data, methods),
+            "cargo/struct_test" => self.render_cargo_struct_test(data, methods),
+            "go-testing/struct_test" => self.render_go_struct_test(data, methods),
+            "go-testing/interface_test" => self.render_go_interface_test(data, methods),
+            "junit/class_test" => self.render_junit_class_test(data, methods),
+            "junit/integration_test" => self.render_junit_integration_test(data, methods),
+            "junit/mock_test" => self.render_junit_mock_test(data, methods),
+            _ => self.render_test(template_name, data),
+        }
+    }
+
     pub fn render_test(&self, template_name: &str, data: &crate::TestTemplateData) -> Result<String> {
         match template_name {
             "jest/function_test" => self.render_jest_function_test(data),
@@ -528,13 +1148,23 @@ impl AskamaTemplateEngine {
             "pytest/function_test" => self.render_pytest_function_test(data),
             "pytest/async_test" => self.render_pytest_async_test(data),
             "pytest/class_test" => self.render_pytest_class_test(data, vec![]), // Default empty methods
+            "unittest/function_test" => self.render_unittest_function_test(data),
+            "unittest/async_test" => self.render_unittest_async_test(data),
+            "unittest/class_test" => self.render_unittest_class_test(data, vec![]), // Default empty methods
             "cargo/function_test" => self.render_cargo_function_test(data),
             "cargo/async_test" => self.render_cargo_async_test(data),
             "cargo/struct_test" => self.render_cargo_struct_test(data, vec![]), // Default empty methods
+            "cargo/rstest_test" => self.render_cargo_rstest_test(&RstestContext::from_template_data(data)),
             "go-testing/function_test" => self.render_go_function_test(data),
             "go-testing/struct_test" => self.render_go_struct_test(data, vec![]), // Default empty methods
             "go-testing/interface_test" => self.render_go_interface_test(data, vec![]), // Default empty methods
             "go-testing/benchmark_test" => self.render_go_benchmark_test(data),
+            "go/function_test" | "go/table_test" | "go/testify_test" => self.render_go_test(template_name, data, &GoTestContext::from_template_data(data)),
+            "jest/api_test" | "pytest/api_test" | "unittest/api_test" | "cargo/api_test" | "go-testing/api_test" | "junit/api_test" => {
+                self.render_api_test(template_name, data, &ApiTestContext::from_template_data(data))
+            }
+            name if name.ends_with("_test_suite") => self.render_test_suite(name, &TestSuiteContext::from_template_data(data)),
+            "junit5/test_class" | "testng/test_class" => self.render_java_test_class(template_name, &JavaTestClass::from_template_data(data)),
             "junit/method_test" => self.render_junit_method_test(data),
             "junit/class_test" => self.render_junit_class_test(data, vec![]), // Default empty methods
             "junit/integration_test" => self.render_junit_integration_test(data, vec![]), // Default empty methods
@@ -542,4 +1172,142 @@ impl AskamaTemplateEngine {
             _ => Err(anyhow::anyhow!("Unknown template: {}", template_name)),
         }
     }
-}
\ No newline at end of file
+}
+/// What the `*/api_test` templates render from an [`ApiTestContext`]
+struct ApiFields {
+    method: String,
+    path: String,
+    /// Each parameter with its input, as the JSON body or the query
+    payload: Value,
+    /// Whether the method takes a body; the payload is the query otherwise
+    sends_body: bool,
+    /// Expected response status, from the data's first expected output
+    status: u64,
+    query: Vec<(String, String)>,
+    /// `?name=value&...`, percent-encoded; empty without a query
+    query_string: String,
+    body_json: String,
+}
+
+impl ApiFields {
+    fn new(data: &crate::TestTemplateData, context: &ApiTestContext) -> Self {
+        let method = context.method.to_uppercase();
+        let sends_body = matches!(method.as_str(), "POST" | "PUT" | "PATCH");
+        let payload: serde_json::Map<String, Value> = context.params.iter().cloned().zip(data.inputs.iter().cloned()).collect();
+        let query: Vec<(String, String)> = match sends_body {
+            true => vec![],
+            false => payload.iter().map(|(name, value)| (name.clone(), value.as_str().map_or_else(|| value.to_string(), str::to_string))).collect(),
+        };
+        let query_string = match query.is_empty() {
+            true => String::new(),
+            false => {
+                let mut url = reqwest::Url::parse("http://localhost/").expect("valid URL");
+                url.query_pairs_mut().extend_pairs(&query);
+                format!("?{}", url.query().unwrap_or_default())
+            }
+        };
+        Self {
+            status: data.expected_outputs.first().and_then(|expected| expected.get("status")).and_then(Value::as_u64).unwrap_or(200),
+            path: if context.path.starts_with('/') { context.path.clone() } else { format!("/{}", context.path) },
+            body_json: Value::Object(payload.clone()).to_string(),
+            payload: Value::Object(payload),
+            method,
+            sends_body,
+            query,
+            query_string,
+        }
+    }
+}
+
+/// What the `go/*` templates render from a [`GoTestContext`]
+struct GoFields {
+    /// Table fields, with variadic parameters as slices
+    params: Vec<(String, String)>,
+    /// Type of the first result when it's compared, else empty
+    want_type: String,
+    /// `got, err := ` and the like, naming the compared result and a trailing error
+    assign: String,
+    /// `_, _ = ` and the like, discarding every result
+    discard: String,
+    returns_error: bool,
+    /// The table call's arguments, `tt.a, tt.b`
+    args: String,
+    args_format: String,
+    cases: Vec<GoCase>,
+}
+
+impl GoFields {
+    fn new(context: &GoTestContext) -> Self {
+        let returns_error = context.results.last().is_some_and(|result| result == "error");
+        let values = context.results.len() - returns_error as usize;
+        let want_type = match context.results.first() {
+            Some(result) if values == 1 && is_go_basic(result) => result.clone(),
+            _ => String::new(),
+        };
+
+        let names: Vec<&str> = (0..context.results.len())
+            .map(|i| if returns_error && i == values { "err" } else if i == 0 && !want_type.is_empty() { "got" } else { "_" })
+            .collect();
+        let assign = match names.as_slice() {
+            [] => String::new(),
+            names if names.iter().all(|name| *name == "_") => format!("{} = ", names.join(", ")),
+            names => format!("{} := ", names.join(", ")),
+        };
+        let discard = match context.results.len() {
+            0 => String::new(),
+            count => format!("{} = ", vec!["_"; count].join(", ")),
+        };
+
+        // Parameters named like the table's own fields get an `Arg` suffix
+        let field_names: Vec<String> = context.params.iter()
+            .map(|(name, _)| if matches!(name.as_str(), "name" | "want" | "checkWant") { format!("{}Arg", name) } else { name.clone() })
+            .collect();
+        let params = field_names.iter().zip(&context.params)
+            .map(|(field, (_, ty))| (field.clone(), ty.strip_prefix("...").map_or_else(|| ty.clone(), |element| format!("[]{}", element))))
+            .collect();
+        let args = field_names.iter().zip(&context.params)
+            .map(|(field, (_, ty))| if ty.starts_with("...") { format!("tt.{}...", field) } else { format!("tt.{}", field) })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let args_format = vec!["%v"; context.params.len()].join(", ");
+
+        let cases = context.cases.iter().map(|row| {
+            let values: Vec<&Value> = context.params.iter().enumerate().map(|(i, _)| row.inputs.get(i).unwrap_or(&Value::Null)).collect();
+            let fields = field_names.iter().zip(&context.params).zip(&values)
+                .map(|((field, (_, ty)), value)| (field.clone(), go_expression(value, ty)))
+                .collect();
+            let call_args = context.params.iter().zip(&values)
+                .filter_map(|((_, ty), value)| match (ty.strip_prefix("..."), value) {
+                    (Some(element), Value::Array(items)) => Some(items.iter().map(|item| go_expression(item, element)).collect::<Vec<_>>().join(", ")),
+                    (Some(_), _) => None,
+                    (None, value) => Some(go_expression(value, ty)),
+                })
+                .filter(|arg| !arg.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let want = if want_type.is_empty() || row.expected.is_null() { String::new() } else { go_expression(&row.expected, &want_type) };
+            GoCase { name: filters::go_literal(&row.label).unwrap_or_default(), fields, call_args, want }
+        }).collect();
+
+        Self { params, want_type, assign, discard, returns_error, args, args_format, cases }
+    }
+}
+
+/// Go's comparable built-in types, whose values have literals
+pub(crate) fn is_go_basic(ty: &str) -> bool {
+    matches!(ty, "int" | "int8" | "int16" | "int32" | "int64" | "uint" | "uint8" | "uint16" | "uint32" | "uint64"
+        | "float32" | "float64" | "string" | "bool" | "byte" | "rune")
+}
+
+/// `value` as a Go expression of type `ty`: a literal for basic types, a slice for a
+/// variadic parameter and the zero value otherwise
+fn go_expression(value: &Value, ty: &str) -> String {
+    match ty.strip_prefix("...") {
+        Some(element) => match value {
+            Value::Array(items) => format!("[]{}{{{}}}", element, items.iter().map(|item| go_expression(item, element)).collect::<Vec<_>>().join(", ")),
+            _ => "nil".to_string(),
+        },
+        None if is_go_basic(ty) && !value.is_null() => filters::go_literal(value).unwrap_or_default(),
+        None => format!("*new({})", ty),
+    }
+}
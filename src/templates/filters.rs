@@ -0,0 +1,156 @@
+//! Filters the Askama templates use to turn names and `serde_json::Value`s into code:
+//! `{{ function_name|pascal_case }}`, `{{ input|py_literal }}`
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Display;
+
+use crate::adapters::python::python_literal;
+
+/// `fetchUserData`, `FetchUserData` and `fetch-user data` all give `fetch_user_data`
+pub fn snake_case<T: Display>(name: T) -> askama::Result<String> {
+    Ok(words(&name.to_string()).join("_"))
+}
+
+/// `fetch_user_data` gives `fetchUserData`
+pub fn camel_case<T: Display>(name: T) -> askama::Result<String> {
+    let pascal = pascal_case(name)?;
+    let mut chars = pascal.chars();
+    Ok(chars.next().map(|first| first.to_lowercase().chain(chars).collect()).unwrap_or_default())
+}
+
+/// `fetch_user_data` gives `FetchUserData`
+pub fn pascal_case<T: Display>(name: T) -> askama::Result<String> {
+    Ok(words(&name.to_string()).iter().map(|word| capitalize(word)).collect())
+}
+
+/// A Python literal: `None`, `True`, lists and dicts
+pub fn py_literal<T: Serialize>(value: T) -> askama::Result<String> {
+    Ok(python_literal(&to_value(value)?))
+}
+
+/// A JavaScript literal, which JSON already is
+pub fn js_literal<T: Serialize>(value: T) -> askama::Result<String> {
+    Ok(to_value(value)?.to_string())
+}
+
+/// A Go literal: JSON for strings, numbers and booleans, `nil` for null and untyped
+/// `interface{}` slices and maps
+pub fn go_literal<T: Serialize>(value: T) -> askama::Result<String> {
+    Ok(go_expression(&to_value(value)?))
+}
+
+fn go_expression(value: &Value) -> String {
+    match value {
+        Value::Null => "nil".to_string(),
+        Value::Array(items) => format!("[]interface{{}}{{{}}}", items.iter().map(go_expression).collect::<Vec<_>>().join(", ")),
+        Value::Object(fields) => format!(
+            "map[string]interface{{}}{{{}}}",
+            fields.iter().map(|(key, value)| format!("{}: {}", Value::String(key.clone()), go_expression(value))).collect::<Vec<_>>().join(", ")
+        ),
+        other => other.to_string(),
+    }
+}
+
+/// A Java expression: string literals, `L`-suffixed longs past `int`, `null`, and
+/// `List.of`/`Map.of` for arrays and objects
+pub fn java_literal<T: Serialize>(value: T) -> askama::Result<String> {
+    Ok(java_expression(&to_value(value)?))
+}
+
+fn java_expression(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) if i32::try_from(integer).is_err() => format!("{}L", integer),
+            _ => number.to_string(),
+        },
+        Value::Array(items) => format!("List.of({})", items.iter().map(java_expression).collect::<Vec<_>>().join(", ")),
+        Value::Object(fields) => format!(
+            "Map.of({})",
+            fields.iter().map(|(key, value)| format!("{}, {}", Value::String(key.clone()), java_expression(value))).collect::<Vec<_>>().join(", ")
+        ),
+        other => other.to_string(),
+    }
+}
+
+/// A Rust expression: `&str` literals, `vec![...]` for arrays, `None` for null and a
+/// `serde_json::json!` for objects, which have no literal
+pub fn rust_literal<T: Serialize>(value: T) -> askama::Result<String> {
+    Ok(rust_expression(&to_value(value)?))
+}
+
+fn rust_expression(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::String(text) => format!("{:?}", text),
+        Value::Array(items) => format!("vec![{}]", items.iter().map(rust_expression).collect::<Vec<_>>().join(", ")),
+        Value::Object(_) => format!("serde_json::json!({})", value),
+        other => other.to_string(),
+    }
+}
+
+fn to_value<T: Serialize>(value: T) -> askama::Result<Value> {
+    serde_json::to_value(value).map_err(|e| askama::Error::Custom(Box::new(e)))
+}
+
+/// Lowercase words of an identifier, split at separators and case changes
+fn words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        // A capital starts a word after a lowercase letter or digit, and ends an acronym
+        // before a lowercase letter: `parseHTTPResponse` is parse, http, response
+        let previous = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1);
+        let boundary = c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()
+            || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase())));
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// `fetchUser` gives `FetchUser`; the rest of the word is left as it is
+pub fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_casing_filters() {
+        assert_eq!(snake_case("parseHTTPResponse").unwrap(), "parse_http_response");
+        assert_eq!(snake_case("UserService").unwrap(), "user_service");
+        assert_eq!(camel_case("fetch_user_data").unwrap(), "fetchUserData");
+        assert_eq!(pascal_case("validate-email v2").unwrap(), "ValidateEmailV2");
+    }
+
+    #[test]
+    fn test_literal_filters() {
+        let value = json!([true, null, "it's", 1.5]);
+        assert_eq!(py_literal(&value).unwrap(), r#"[True, None, "it's", 1.5]"#);
+        assert_eq!(js_literal(&value).unwrap(), r#"[true,null,"it's",1.5]"#);
+        assert_eq!(rust_literal(json!(["a\"b", "c"])).unwrap(), r#"vec!["a\"b", "c"]"#);
+        assert_eq!(rust_literal(json!(null)).unwrap(), "None");
+        assert_eq!(java_literal(json!([3000000000i64, "a", {"k": null}])).unwrap(), r#"List.of(3000000000L, "a", Map.of("k", null))"#);
+        assert_eq!(go_literal(json!({"tags": ["a", null]})).unwrap(), r#"map[string]interface{}{"tags": []interface{}{"a", nil}}"#);
+        assert_eq!(rust_literal(json!({"id": 1})).unwrap(), r#"serde_json::json!({"id":1})"#);
+    }
+}
@@ -1,12 +1,16 @@
 use serde_json::Value;
 use serde::{Serialize, Deserialize};
 use anyhow::Result;
+use once_cell::sync::Lazy;
+
+use crate::core::{CaseRow, TestSuite, UftError};
 
 pub mod javascript;
 pub mod python;
 pub mod rust;
 pub mod advanced_patterns;
 pub mod askama_engine;
+pub mod filters;
 
 #[cfg(test)]
 mod tests;
@@ -24,6 +28,303 @@ pub struct TestTemplateData {
     pub teardown_code: Option<String>,
 }
 
+/// What the `go/*` templates need beyond a [`TestTemplateData`]: the package the test file
+/// belongs to and the function's typed signature, with a `t.Run` subtest for each case
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoTestContext {
+    pub package: String,
+    /// Package the function is imported as when `package` is an external `_test` package
+    pub callee_package: Option<String>,
+    /// `(name, type)`, with `...T` for a variadic parameter
+    pub params: Vec<(String, String)>,
+    pub results: Vec<String>,
+    pub cases: Vec<CaseRow>,
+}
+
+impl GoTestContext {
+    /// For previewing a `go/*` template with `data`: package main, a parameter for each of
+    /// its inputs and one case calling the function with them
+    pub fn from_template_data(data: &TestTemplateData) -> Self {
+        let expected = data.expected_outputs.first().cloned().unwrap_or(Value::Null);
+        Self {
+            package: "main".to_string(),
+            callee_package: None,
+            params: data.inputs.iter().enumerate().map(|(i, input)| (format!("arg{}", i), go_type(input).to_string())).collect(),
+            results: if expected.is_null() { vec![] } else { vec![go_type(&expected).to_string()] },
+            cases: vec![CaseRow { label: "typical".to_string(), inputs: data.inputs.clone(), expected }],
+        }
+    }
+
+    /// How the test calls `function`, qualified by its package from an external test package
+    pub fn callee(&self, function: &str) -> String {
+        match &self.callee_package {
+            Some(package) => format!("{}.{}", package, function),
+            None => function.to_string(),
+        }
+    }
+}
+
+fn go_type(value: &Value) -> &'static str {
+    match value {
+        Value::Number(number) if number.is_f64() => "float64",
+        Value::Number(_) => "int",
+        Value::String(_) => "string",
+        Value::Bool(_) => "bool",
+        _ => "interface{}",
+    }
+}
+
+/// A Java test class for the `junit5/*` and `testng/*` templates: a test method for each
+/// generated case, against a `subject` created before each
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaTestClass {
+    /// Package of the class under test, which the test class shares; empty for the default package
+    pub package: String,
+    pub class_name: String,
+    /// The class under test
+    pub subject: String,
+    /// Whether `subject` can be created with `new` and no arguments
+    pub constructible: bool,
+    pub tests: Vec<JavaTestMethod>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaTestMethod {
+    pub name: String,
+    pub description: String,
+    /// The method under test; empty when the case isn't about one
+    pub method: String,
+    pub params: Vec<String>,
+    /// The method's return type, `void` when it returns nothing and empty when it isn't known
+    pub returns: String,
+    /// Java expression of the expected result; empty when it isn't known
+    pub expected: String,
+}
+
+impl JavaTestClass {
+    /// For previewing a template with `data`: a test of one method of the `function_name` class
+    pub fn from_template_data(data: &TestTemplateData) -> Self {
+        let subject = filters::pascal_case(&data.function_name).unwrap_or_default();
+        let expected = data.expected_outputs.first().filter(|expected| !expected.is_null());
+        Self {
+            package: String::new(),
+            class_name: format!("{}Test", subject),
+            constructible: true,
+            tests: vec![JavaTestMethod {
+                name: filters::camel_case(&data.test_name).unwrap_or_default(),
+                description: data.description.clone(),
+                method: "process".to_string(),
+                params: (0..data.inputs.len()).map(|i| format!("arg{}", i)).collect(),
+                returns: if expected.is_some() { String::new() } else { "void".to_string() },
+                expected: expected.and_then(|expected| filters::java_literal(expected).ok()).unwrap_or_default(),
+            }],
+            subject,
+        }
+    }
+}
+
+/// The endpoint an `*/api_test` template sends its requests to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiTestContext {
+    pub method: String,
+    /// Path after the base URL, e.g. `/api/users`
+    pub path: String,
+    /// Request parameters, sent with the data's inputs as their values
+    pub params: Vec<String>,
+}
+
+impl ApiTestContext {
+    /// For previewing a template with `data`: the endpoint its `function_name` was made from,
+    /// e.g. `POST /api/users` for `post__api_users`
+    pub fn from_template_data(data: &TestTemplateData) -> Self {
+        let (method, path) = data.function_name.split_once('_').unwrap_or(("get", ""));
+        Self {
+            method: method.to_uppercase(),
+            path: if path.is_empty() { "/".to_string() } else { path.replace('_', "/") },
+            params: (0..data.inputs.len()).map(|i| format!("param{}", i)).collect(),
+        }
+    }
+}
+
+/// An `rstest` test for the `cargo/rstest_test` template, with a `#[case]` for each row of
+/// inputs and the expected result
+#[derive(Debug, Clone, PartialEq)]
+pub struct RstestContext {
+    pub test_name: String,
+    pub description: String,
+    /// `name: Type` for each parameter
+    pub params: Vec<String>,
+    /// Type of the `expected` parameter; empty when the result isn't compared
+    pub expected_type: String,
+    pub cases: Vec<RstestCase>,
+    /// Attribute lines of a runtime, which make the test async; empty for a sync test
+    pub attributes: String,
+    /// The test's statements, indented for a `mod tests` block
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RstestCase {
+    /// Identifier after `case::`
+    pub label: String,
+    /// Rust expressions of the arguments
+    pub values: Vec<String>,
+    /// `Some(..)` of the expected result, or `None` when it isn't known
+    pub expected: String,
+}
+
+impl RstestContext {
+    /// For previewing the template with `data`: a parameter for each of its inputs and one
+    /// case calling the function with them
+    pub fn from_template_data(data: &TestTemplateData) -> Self {
+        let expected = data.expected_outputs.first().filter(|expected| !expected.is_null());
+        let args: Vec<String> = (0..data.inputs.len()).map(|i| format!("arg{}", i)).collect();
+        let call = format!("{}({})", data.function_name, args.join(", "));
+        Self {
+            test_name: data.test_name.clone(),
+            description: data.description.clone(),
+            params: args.iter().zip(&data.inputs).map(|(arg, input)| format!("{}: {}", arg, rust_type(input))).collect(),
+            expected_type: expected.map(|expected| rust_type(expected).to_string()).unwrap_or_default(),
+            cases: vec![RstestCase {
+                label: "typical".to_string(),
+                values: data.inputs.iter().map(|input| filters::rust_literal(input).unwrap_or_default()).collect(),
+                expected: expected.and_then(|expected| filters::rust_literal(expected).ok()).map(|literal| format!("Some({})", literal)).unwrap_or_default(),
+            }],
+            attributes: String::new(),
+            body: match expected {
+                Some(_) => format!("        let result = {};
+        if let Some(expected) = expected {{
+            assert_eq!(result, expected);
+        }}
+", call),
+                None => format!("        {};
+", call),
+            },
+        }
+    }
+}
+
+/// A whole test file for a `*/<language>_test_suite` template
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestSuiteContext {
+    /// Name of the Java test class
+    pub class_name: String,
+    /// The suite's import lines, which templates for another framework than the adapter's leave out
+    pub imports: Vec<String>,
+    pub tests: Vec<SuiteTest>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuiteTest {
+    pub name: String,
+    pub description: String,
+    /// The test's statements, indented for where the template puts them; empty for a TODO
+    pub body: String,
+}
+
+impl TestSuiteContext {
+    pub fn from_suite(test_suite: &TestSuite) -> Self {
+        let subject = filters::pascal_case(test_suite.name.replace("Test", "")).unwrap_or_default();
+        Self {
+            class_name: format!("{}Test", subject),
+            imports: test_suite.imports.clone(),
+            tests: test_suite.test_cases.iter().map(|test_case| SuiteTest {
+                name: test_case.name.clone(),
+                description: test_case.description.clone(),
+                body: if test_case.test_body.trim().is_empty() { String::new() } else { test_case.test_body.trim_end().to_string() },
+            }).collect(),
+        }
+    }
+
+    /// For previewing a template with `data`: a suite of its one test, without a body
+    pub fn from_template_data(data: &TestTemplateData) -> Self {
+        Self {
+            class_name: format!("{}Test", filters::pascal_case(&data.function_name).unwrap_or_default()),
+            imports: data.imports.clone(),
+            tests: vec![SuiteTest { name: data.test_name.clone(), description: data.description.clone(), body: String::new() }],
+        }
+    }
+}
+
+/// The `{framework}/{language}_test_suite` template for a whole test file, or the one for the
+/// language's default framework when `framework` has none
+fn suite_template(language: &str, framework: &str) -> Option<String> {
+    let language = if language == "typescript" { "javascript" } else { language };
+    let default = match language {
+        "javascript" => "jest",
+        "python" => "pytest",
+        "rust" => "cargo",
+        "go" => "go-testing",
+        "java" => "junit5",
+        _ => return None,
+    };
+    [framework, default].into_iter()
+        .map(|framework| match framework {
+            "cargo-test" | "nextest" => "cargo",
+            "testing" => "go-testing",
+            "junit" => "junit5",
+            other => other,
+        })
+        .map(|directory| format!("{}/{}_test_suite", directory, language))
+        .find(|name| TEMPLATES.iter().any(|(template, _)| template == name))
+}
+
+fn rust_type(value: &Value) -> &'static str {
+    match value {
+        Value::Number(number) if number.is_f64() => "f64",
+        Value::Number(_) => "i64",
+        Value::String(_) => "&str",
+        Value::Bool(_) => "bool",
+        _ => "serde_json::Value",
+    }
+}
+
+/// Every template by name, with the source compiled into it
+const TEMPLATES: &[(&str, &str)] = &[
+    ("jest/function_test", include_str!("../../templates/jest/function_test.html")),
+    ("jest/async_test", include_str!("../../templates/jest/async_test.html")),
+    ("jest/class_test", include_str!("../../templates/jest/class_test.html")),
+    ("jest/api_test", include_str!("../../templates/jest/api_test.html")),
+    ("pytest/function_test", include_str!("../../templates/pytest/function_test.html")),
+    ("pytest/async_test", include_str!("../../templates/pytest/async_test.html")),
+    ("pytest/class_test", include_str!("../../templates/pytest/class_test.html")),
+    ("pytest/api_test", include_str!("../../templates/pytest/api_test.html")),
+    ("unittest/function_test", include_str!("../../templates/unittest/function_test.html")),
+    ("unittest/async_test", include_str!("../../templates/unittest/async_test.html")),
+    ("unittest/class_test", include_str!("../../templates/unittest/class_test.html")),
+    ("unittest/api_test", include_str!("../../templates/unittest/api_test.html")),
+    ("cargo/function_test", include_str!("../../templates/cargo/function_test.html")),
+    ("cargo/async_test", include_str!("../../templates/cargo/async_test.html")),
+    ("cargo/struct_test", include_str!("../../templates/cargo/struct_test.html")),
+    ("cargo/rstest_test", include_str!("../../templates/cargo/rstest_test.html")),
+    ("cargo/api_test", include_str!("../../templates/cargo/api_test.html")),
+    ("go-testing/function_test", include_str!("../../templates/go-testing/function_test.html")),
+    ("go-testing/struct_test", include_str!("../../templates/go-testing/struct_test.html")),
+    ("go-testing/interface_test", include_str!("../../templates/go-testing/interface_test.html")),
+    ("go-testing/benchmark_test", include_str!("../../templates/go-testing/benchmark_test.html")),
+    ("go-testing/api_test", include_str!("../../templates/go-testing/api_test.html")),
+    ("go/function_test", include_str!("../../templates/go/function_test.html")),
+    ("go/table_test", include_str!("../../templates/go/table_test.html")),
+    ("go/testify_test", include_str!("../../templates/go/testify_test.html")),
+    ("junit5/test_class", include_str!("../../templates/junit5/test_class.html")),
+    ("testng/test_class", include_str!("../../templates/testng/test_class.html")),
+    ("junit/method_test", include_str!("../../templates/junit/method_test.html")),
+    ("junit/class_test", include_str!("../../templates/junit/class_test.html")),
+    ("junit/integration_test", include_str!("../../templates/junit/integration_test.html")),
+    ("junit/mock_test", include_str!("../../templates/junit/mock_test.html")),
+    ("junit/api_test", include_str!("../../templates/junit/api_test.html")),
+    ("jest/javascript_test_suite", include_str!("../../templates/jest/javascript_test_suite.html")),
+    ("mocha/javascript_test_suite", include_str!("../../templates/mocha/javascript_test_suite.html")),
+    ("pytest/python_test_suite", include_str!("../../templates/pytest/python_test_suite.html")),
+    ("unittest/python_test_suite", include_str!("../../templates/unittest/python_test_suite.html")),
+    ("cargo/rust_test_suite", include_str!("../../templates/cargo/rust_test_suite.html")),
+    ("go-testing/go_test_suite", include_str!("../../templates/go-testing/go_test_suite.html")),
+    ("junit5/java_test_suite", include_str!("../../templates/junit5/java_test_suite.html")),
+];
+
+/// The engine every generator renders with, built on first use
+static SHARED_ENGINE: Lazy<TemplateEngine> = Lazy::new(|| TemplateEngine::new().expect("the compiled-in templates load"));
+
 pub struct TemplateEngine {
     askama_engine: askama_engine::AskamaTemplateEngine,
 }
@@ -33,37 +334,85 @@ impl TemplateEngine {
         let askama_engine = askama_engine::AskamaTemplateEngine::new();
         Ok(Self { askama_engine })
     }
+
+    /// The process-wide engine, so generating a file doesn't set up one of its own
+    pub fn shared() -> &'static TemplateEngine {
+        &SHARED_ENGINE
+    }
     
     pub fn render_test(&self, template_name: &str, data: &TestTemplateData) -> Result<String> {
         self.askama_engine.render_test(template_name, data)
     }
     
-    pub fn render_test_suite(&self, _language: &str, _framework: &str, _tests: Vec<TestTemplateData>) -> Result<String> {
-        // Test suite rendering would be implemented based on requirements
-        // For now, return a simple concatenation message
-        Ok("Test suite rendering not yet implemented for Askama".to_string())
+    /// Render a `go/*` template for a function with this package, signature and cases
+    pub fn render_go_test(&self, template_name: &str, data: &TestTemplateData, context: &GoTestContext) -> Result<String> {
+        self.askama_engine.render_go_test(template_name, data, context)
+    }
+    
+    /// Render an `*/api_test` template for requests to this endpoint
+    pub fn render_api_test(&self, template_name: &str, data: &TestTemplateData, context: &ApiTestContext) -> Result<String> {
+        self.askama_engine.render_api_test(template_name, data, context)
+    }
+    
+    /// Render the `cargo/rstest_test` template for a table-driven test
+    pub fn render_rstest_test(&self, context: &RstestContext) -> Result<String> {
+        self.askama_engine.render_cargo_rstest_test(context)
+    }
+    
+    /// Render a `junit5/*` or `testng/*` template for a whole test class
+    pub fn render_java_test_class(&self, template_name: &str, class: &JavaTestClass) -> Result<String> {
+        self.askama_engine.render_java_test_class(template_name, class)
+    }
+    
+    /// Render a class or struct template with the methods it should cover
+    pub fn render_class_test(&self, template_name: &str, data: &TestTemplateData, methods: Vec<String>) -> Result<String> {
+        self.askama_engine.render_class_test(template_name, data, methods)
+    }
+    
+    /// Render a whole test file with the `{framework}/{language}_test_suite` template, or the
+    /// language's default framework's when `framework` has none
+    pub fn render_test_suite(&self, language: &str, framework: &str, suite: &TestSuiteContext) -> Result<String> {
+        let template_name = suite_template(language, framework)
+            .ok_or_else(|| UftError::UnsupportedLanguage(format!("Unsupported language: {}", language)))?;
+        self.askama_engine.render_test_suite(&template_name, suite)
     }
     
     pub fn get_available_templates(&self) -> Vec<String> {
-        vec![
-            "jest/function_test".to_string(),
-            "jest/async_test".to_string(), 
-            "jest/class_test".to_string(),
-            "pytest/function_test".to_string(),
-            "pytest/async_test".to_string(),
-            "pytest/class_test".to_string(),
-            "cargo/function_test".to_string(),
-            "cargo/async_test".to_string(),
-            "cargo/struct_test".to_string(),
-            "go-testing/function_test".to_string(),
-            "go-testing/struct_test".to_string(),
-            "go-testing/interface_test".to_string(),
-            "go-testing/benchmark_test".to_string(),
-            "junit/method_test".to_string(),
-            "junit/class_test".to_string(),
-            "junit/integration_test".to_string(),
-            "junit/mock_test".to_string(),
-        ]
+        TEMPLATES.iter().map(|(name, _)| name.to_string()).collect()
+    }
+
+    /// The Askama source a template was compiled from
+    pub fn template_source(&self, template_name: &str) -> Result<&'static str> {
+        TEMPLATES.iter()
+            .find(|(name, _)| *name == template_name)
+            .map(|(_, source)| *source)
+            .ok_or_else(|| anyhow::anyhow!("Unknown template: {}", template_name))
+    }
+
+    /// Made-up data of the kind a template is rendered with, for previewing it
+    pub fn sample_data(&self, template_name: &str) -> TestTemplateData {
+        let pattern = match template_name.rsplit('/').next().unwrap_or(template_name) {
+            "async_test" => TestPattern::AsyncFunction {
+                name: "fetch_user".to_string(),
+                params: vec!["user_id".to_string()],
+                return_type: Some("object".to_string()),
+            },
+            "class_test" | "struct_test" | "interface_test" | "integration_test" | "mock_test" | "test_class" => TestPattern::Class {
+                name: "UserService".to_string(),
+                methods: vec!["create_user".to_string(), "find_user".to_string()],
+            },
+            "api_test" => TestPattern::ApiEndpoint {
+                path: "/api/users".to_string(),
+                method: "POST".to_string(),
+                params: vec!["name".to_string(), "email".to_string()],
+            },
+            _ => TestPattern::Function {
+                name: "validate_email".to_string(),
+                params: vec!["email".to_string()],
+                return_type: Some("bool".to_string()),
+            },
+        };
+        pattern.generate_template_data("unit")
     }
 }
 